@@ -1,17 +1,208 @@
 mod presentation;
 
-use std::collections::HashMap;
+use std::{process, sync::Arc, time::Duration};
 
 use clap::Parser;
-use expense_tracking::domain::repositories::BrandRepository;
-use in_memory_storage::application::repositories::BrandRepositoryInMemoryImpl;
-use presentation::{FrostyPineCli, clap_args::CliArgs};
+use expense_tracking::domain::{
+    repositories::BrandRepository,
+    search::{IndexedBrandRepository, SearchService},
+};
+use in_memory_storage::{
+    adapters::repositories::{
+        BrandRepositoryInMemoryImpl, CategoryRepositoryInMemoryImpl, ProductRepositoryInMemoryImpl, StoreRepositoryInMemoryImpl,
+    },
+    infrastructures::data_sources::{InMemoryCache, InMemorySnapshot, SnapshotError},
+};
+use presentation::{
+    FrostyPineCli,
+    clap_args::{CliArgs, Service},
+    passphrase_prompt::{prompt_new_passphrase, prompt_passphrase},
+};
+use sqlite_storage::{
+    adapters::repositories::{
+        BrandRepositorySqliteImpl, CategoryRepositorySqliteImpl, ProductRepositorySqliteImpl, StoreRepositorySqliteImpl,
+    },
+    infrastructures::data_sources::{SqliteDatabase, SqliteDatabaseError},
+};
+use storage_encryption::infrastructures::encryption::Cipher;
+use storage_migrations::infrastructures::migrations::MigrationPlan;
+use tokio::sync::RwLock;
+
+const SNAPSHOT_PERIOD: Duration = Duration::from_secs(60);
 
 #[tokio::main()]
 async fn main() {
     let cli_args: CliArgs = CliArgs::parse();
-    let brand_repository: Box<dyn BrandRepository> =
-        Box::new(BrandRepositoryInMemoryImpl::new(HashMap::new()));
 
-    FrostyPineCli::new(cli_args, brand_repository).run().await
+    if let Service::Rekey { decrypt } = cli_args.service {
+        rekey(&cli_args, decrypt);
+        return;
+    }
+
+    if let Service::Migrate { dry_run } = cli_args.service {
+        migrate(&cli_args, dry_run);
+        return;
+    }
+
+    let cipher: Option<Cipher> = (cli_args.encrypted || is_encrypted(&cli_args)).then(prompt_passphrase);
+    let snapshot: Option<InMemorySnapshot> = cli_args.snapshot.as_ref().map(|path| match &cipher {
+        Some(cipher) => InMemorySnapshot::new(path).with_cipher(cipher.clone()),
+        None => InMemorySnapshot::new(path),
+    });
+    let cache: Arc<RwLock<InMemoryCache>> = Arc::new(RwLock::new(match &snapshot {
+        Some(snapshot) => snapshot.load().unwrap_or_else(|e| snapshot_failure("Unable to load snapshot", e)),
+        None => InMemoryCache::new(),
+    }));
+    let database: Option<SqliteDatabase> = cli_args.database.as_ref().map(|path| {
+        match &cipher {
+            Some(cipher) => SqliteDatabase::open_encrypted(path, cipher.clone()),
+            None => SqliteDatabase::open(path),
+        }
+        .unwrap_or_else(|e| database_failure("Unable to open database", e))
+    });
+    let storage: Arc<dyn BrandRepository> = match &database {
+        Some(database) => Arc::new(BrandRepositorySqliteImpl::new(database.clone())),
+        None => Arc::new(BrandRepositoryInMemoryImpl::new(Arc::clone(&cache))),
+    };
+    let periodic_save = snapshot
+        .as_ref()
+        .map(|snapshot| snapshot.spawn_periodic_save(Arc::clone(&cache), SNAPSHOT_PERIOD));
+
+    let search_service: SearchService = match &database {
+        Some(database) => {
+            SearchService::build_from(
+                &*storage,
+                &CategoryRepositorySqliteImpl::new(database.clone()),
+                &StoreRepositorySqliteImpl::new(database.clone()),
+                &ProductRepositorySqliteImpl::new(database.clone()),
+            )
+            .await
+        }
+        None => {
+            SearchService::build_from(
+                &*storage,
+                &CategoryRepositoryInMemoryImpl::new(Arc::clone(&cache)),
+                &StoreRepositoryInMemoryImpl::new(Arc::clone(&cache)),
+                &ProductRepositoryInMemoryImpl::new(Arc::clone(&cache)),
+            )
+            .await
+        }
+    };
+
+    let brand_repository: Box<dyn BrandRepository> = Box::new(IndexedBrandRepository::new(storage, search_service.index()));
+
+    FrostyPineCli::new(cli_args, brand_repository, search_service).run().await;
+
+    if let Some(periodic_save) = periodic_save {
+        periodic_save.abort();
+    }
+
+    if let Some(snapshot) = snapshot {
+        snapshot.save(&*cache.read().await).expect("Unable to save snapshot");
+    }
+}
+
+fn is_encrypted(cli_args: &CliArgs) -> bool {
+    match (&cli_args.database, &cli_args.snapshot) {
+        (Some(path), _) => SqliteDatabase::is_encrypted(path).unwrap_or_else(|e| database_failure("Unable to open database", e)),
+        (None, Some(path)) => InMemorySnapshot::new(path)
+            .is_encrypted()
+            .unwrap_or_else(|e| snapshot_failure("Unable to load snapshot", e)),
+        (None, None) => false,
+    }
+}
+
+fn rekey(cli_args: &CliArgs, decrypt: bool) {
+    match (&cli_args.database, &cli_args.snapshot) {
+        (Some(path), _) => {
+            let encrypted: bool = SqliteDatabase::is_encrypted(path).unwrap_or_else(|e| database_failure("Unable to open database", e));
+            let current: Option<Cipher> = encrypted.then(prompt_passphrase);
+            let new: Option<Cipher> = (!decrypt).then(prompt_new_passphrase);
+
+            SqliteDatabase::rekey(path, current, new).unwrap_or_else(|e| database_failure("Unable to rekey database", e));
+        }
+        (None, Some(path)) => {
+            let snapshot: InMemorySnapshot = InMemorySnapshot::new(path);
+            let encrypted: bool = snapshot
+                .is_encrypted()
+                .unwrap_or_else(|e| snapshot_failure("Unable to load snapshot", e));
+            let current: Option<Cipher> = encrypted.then(prompt_passphrase);
+            let new: Option<Cipher> = (!decrypt).then(prompt_new_passphrase);
+
+            match current {
+                Some(current) => snapshot.with_cipher(current).rekey(new),
+                None => snapshot.rekey(new),
+            }
+            .unwrap_or_else(|e| snapshot_failure("Unable to rekey snapshot", e));
+        }
+        (None, None) => exit_with("rekey needs either --database or --snapshot"),
+    }
+}
+
+fn migrate(cli_args: &CliArgs, dry_run: bool) {
+    let cipher: Option<Cipher> = (cli_args.encrypted || is_encrypted(cli_args)).then(prompt_passphrase);
+
+    let plan: MigrationPlan = match (&cli_args.database, &cli_args.snapshot) {
+        (Some(path), _) => {
+            let plan: MigrationPlan =
+                SqliteDatabase::migration_plan(path, cipher.clone()).unwrap_or_else(|e| database_failure("Unable to read database", e));
+
+            if !dry_run && !plan.is_empty() {
+                match cipher {
+                    Some(cipher) => SqliteDatabase::open_encrypted(path, cipher),
+                    None => SqliteDatabase::open(path),
+                }
+                .unwrap_or_else(|e| database_failure("Unable to migrate database", e));
+            }
+
+            plan
+        }
+        (None, Some(path)) => {
+            let snapshot: InMemorySnapshot = match cipher {
+                Some(cipher) => InMemorySnapshot::new(path).with_cipher(cipher),
+                None => InMemorySnapshot::new(path),
+            };
+
+            match dry_run {
+                true => snapshot.migration_plan(),
+                false => snapshot.migrate(),
+            }
+            .unwrap_or_else(|e| snapshot_failure("Unable to migrate snapshot", e))
+        }
+        (None, None) => exit_with("migrate needs either --database or --snapshot"),
+    };
+
+    if plan.is_empty() {
+        println!("Already at version {}, nothing to migrate", plan.to_version);
+        return;
+    }
+
+    println!(
+        "{} version {} to version {}:",
+        if dry_run { "Would migrate" } else { "Migrated" },
+        plan.from_version,
+        plan.to_version
+    );
+    plan.steps.iter().for_each(|step| println!("  - {}", step));
+}
+
+fn database_failure<T>(context: &str, error: SqliteDatabaseError) -> T {
+    match error {
+        SqliteDatabaseError::WrongPassphrase => exit_with(&format!("{}: wrong passphrase", context)),
+        SqliteDatabaseError::PassphraseRequired => exit_with(&format!("{}: the database is encrypted, pass --encrypted", context)),
+        error => panic!("{}: {:?}", context, error),
+    }
+}
+
+fn snapshot_failure<T>(context: &str, error: SnapshotError) -> T {
+    match error {
+        SnapshotError::WrongPassphrase => exit_with(&format!("{}: wrong passphrase", context)),
+        SnapshotError::PassphraseRequired => exit_with(&format!("{}: the snapshot is encrypted, pass --encrypted", context)),
+        error => panic!("{}: {:?}", context, error),
+    }
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(1)
 }
@@ -1,4 +1,5 @@
 pub mod clap_args;
 mod frosty_pine_cli;
+pub mod passphrase_prompt;
 
 pub use frosty_pine_cli::FrostyPineCli;
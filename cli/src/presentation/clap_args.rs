@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use chrono::{DateTime, NaiveDateTime, Utc};
 use clap::{Args, Parser, Subcommand};
 
@@ -5,6 +7,18 @@ use clap::{Args, Parser, Subcommand};
 #[command(name = "frosty-pine")]
 #[command(version, about, long_about = None)]
 pub struct CliArgs {
+    /// SQLite file to keep data in, data only lives in memory when omitted
+    #[arg(long, global = true)]
+    pub database: Option<PathBuf>,
+
+    /// Snapshot file the in-memory data is loaded from at startup and saved back to, ignored with --database
+    #[arg(long, global = true, conflicts_with = "database")]
+    pub snapshot: Option<PathBuf>,
+
+    /// Keeps the --database or --snapshot file encrypted, prompting for its passphrase unless FROSTY_PINE_PASSPHRASE is set
+    #[arg(long, global = true)]
+    pub encrypted: bool,
+
     /// Service to operate on
     #[command(subcommand)]
     pub service: Service,
@@ -21,7 +35,27 @@ pub enum Service {
     /// Operates on Stores
     Stores(StoresArgs),
     /// Operates on Transactions
-    Transactions,
+    Transactions(TransactionsArgs),
+    /// Searches products, brands, stores and categories by name
+    Search {
+        query: String,
+
+        /// Maximum number of results to show
+        #[arg(short, long, default_value_t = 10)]
+        limit: usize,
+    },
+    /// Changes the passphrase of the --database or --snapshot file, encrypting it if it was not
+    Rekey {
+        /// Stores the file in plaintext again instead
+        #[arg(long)]
+        decrypt: bool,
+    },
+    /// Upgrades the --database or --snapshot file to the format of this version
+    Migrate {
+        /// Only reports what would change
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 #[derive(Debug, Args, Clone)]
@@ -61,6 +95,19 @@ pub enum BrandCommands {
         #[arg(short, long, required_unless_present = "id", conflicts_with = "id")]
         name: Option<String>,
     },
+
+    /// Lists deleted entries that can still be restored
+    Trash {
+        /// Permanently removes entries deleted more than this many days ago
+        #[arg(long)]
+        purge_older_than_days: Option<i64>,
+    },
+
+    /// Brings a deleted entry back from the trash
+    Restore {
+        #[arg(short, long)]
+        name: String,
+    },
 }
 
 #[derive(Debug, Args)]
@@ -100,6 +147,19 @@ enum CategoryCommands {
         #[arg(short, long, required_unless_present = "id", conflicts_with = "id")]
         name: Option<String>,
     },
+
+    /// Lists deleted entries that can still be restored
+    Trash {
+        /// Permanently removes entries deleted more than this many days ago
+        #[arg(long)]
+        purge_older_than_days: Option<i64>,
+    },
+
+    /// Brings a deleted entry back from the trash
+    Restore {
+        #[arg(short, long)]
+        id: String,
+    },
 }
 
 #[derive(Debug, Args)]
@@ -115,32 +175,16 @@ enum ProductCommands {
         #[arg(short, long)]
         name: String,
 
-        #[arg(
-            long,
-            required_unless_present = "brand_name",
-            conflicts_with = "brand_name"
-        )]
+        #[arg(long, required_unless_present = "brand_name", conflicts_with = "brand_name")]
         brand_id: Option<String>,
 
-        #[arg(
-            long,
-            required_unless_present = "brand_id",
-            conflicts_with = "brand_id"
-        )]
+        #[arg(long, required_unless_present = "brand_id", conflicts_with = "brand_id")]
         brand_name: Option<String>,
 
-        #[arg(
-            long,
-            required_unless_present = "category_name",
-            conflicts_with = "category_name"
-        )]
+        #[arg(long, required_unless_present = "category_name", conflicts_with = "category_name")]
         category_id: Option<String>,
 
-        #[arg(
-            long,
-            required_unless_present = "category_id",
-            conflicts_with = "category_id"
-        )]
+        #[arg(long, required_unless_present = "category_id", conflicts_with = "category_id")]
         category_name: Option<String>,
     },
 
@@ -191,6 +235,19 @@ enum ProductCommands {
         #[arg(short, long, required_unless_present = "id", conflicts_with = "id")]
         name: Option<String>,
     },
+
+    /// Lists deleted entries that can still be restored
+    Trash {
+        /// Permanently removes entries deleted more than this many days ago
+        #[arg(long)]
+        purge_older_than_days: Option<i64>,
+    },
+
+    /// Brings a deleted entry back from the trash
+    Restore {
+        #[arg(short, long)]
+        id: String,
+    },
 }
 
 #[derive(Debug, Args)]
@@ -230,6 +287,19 @@ enum StoreCommands {
         #[arg(short, long, required_unless_present = "id", conflicts_with = "id")]
         name: Option<String>,
     },
+
+    /// Lists deleted entries that can still be restored
+    Trash {
+        /// Permanently removes entries deleted more than this many days ago
+        #[arg(long)]
+        purge_older_than_days: Option<i64>,
+    },
+
+    /// Brings a deleted entry back from the trash
+    Restore {
+        #[arg(short, long)]
+        id: String,
+    },
 }
 
 #[derive(Debug, Args)]
@@ -250,24 +320,16 @@ enum TransactionCommands {
             conflicts_with = "date_time",
             value_parser = parse_date
         )]
-        date: DateTime<Utc>,
+        date: Option<DateTime<Utc>>,
 
         /// Accepts YYYY-MM-DD hh:mm:ss
-        #[arg(short, long, required_unless_present = "date", conflicts_with = "date", value_parser = parse_date_time)]
-        date_time: DateTime<Utc>,
+        #[arg(short = 't', long, required_unless_present = "date", conflicts_with = "date", value_parser = parse_date_time)]
+        date_time: Option<DateTime<Utc>>,
 
-        #[arg(
-            long,
-            required_unless_present = "store_name",
-            conflicts_with = "store_name"
-        )]
+        #[arg(long, required_unless_present = "store_name", conflicts_with = "store_name")]
         store_id: Option<String>,
 
-        #[arg(
-            long,
-            required_unless_present = "store_id",
-            conflicts_with = "store_id"
-        )]
+        #[arg(long, required_unless_present = "store_id", conflicts_with = "store_id")]
         store_name: Option<String>,
     },
 
@@ -306,6 +368,19 @@ enum TransactionCommands {
         #[arg(short, long, required_unless_present = "id", conflicts_with = "id")]
         name: Option<String>,
     },
+
+    /// Lists deleted entries that can still be restored
+    Trash {
+        /// Permanently removes entries deleted more than this many days ago
+        #[arg(long)]
+        purge_older_than_days: Option<i64>,
+    },
+
+    /// Brings a deleted entry back from the trash
+    Restore {
+        #[arg(short, long)]
+        id: String,
+    },
 }
 
 #[derive(Debug, Args)]
@@ -324,60 +399,28 @@ struct Item {
     #[arg(short, long, required_unless_present = "date", conflicts_with = "date", value_parser = parse_date_time)]
     date_time: DateTime<Utc>,
 
-    #[arg(
-        long,
-        required_unless_present = "store_name",
-        conflicts_with = "store_name"
-    )]
+    #[arg(long, required_unless_present = "store_name", conflicts_with = "store_name")]
     store_id: Option<String>,
 
-    #[arg(
-        long,
-        required_unless_present = "store_id",
-        conflicts_with = "store_id"
-    )]
+    #[arg(long, required_unless_present = "store_id", conflicts_with = "store_id")]
     store_name: Option<String>,
 
-    #[arg(
-        long,
-        required_unless_present = "product_name",
-        conflicts_with = "product_name"
-    )]
+    #[arg(long, required_unless_present = "product_name", conflicts_with = "product_name")]
     product_id: Option<String>,
 
-    #[arg(
-        long,
-        required_unless_present = "product_id",
-        conflicts_with = "product_id"
-    )]
+    #[arg(long, required_unless_present = "product_id", conflicts_with = "product_id")]
     product_name: String,
 
-    #[arg(
-        long,
-        required_unless_present = "product_brand_name",
-        conflicts_with = "product_brand_name"
-    )]
+    #[arg(long, required_unless_present = "product_brand_name", conflicts_with = "product_brand_name")]
     product_brand_id: Option<String>,
 
-    #[arg(
-        long,
-        required_unless_present = "product_brand_id",
-        conflicts_with = "product_brand_id"
-    )]
+    #[arg(long, required_unless_present = "product_brand_id", conflicts_with = "product_brand_id")]
     product_brand_name: Option<String>,
 
-    #[arg(
-        long,
-        required_unless_present = "product_category_name",
-        conflicts_with = "product_category_name"
-    )]
+    #[arg(long, required_unless_present = "product_category_name", conflicts_with = "product_category_name")]
     product_category_id: Option<String>,
 
-    #[arg(
-        long,
-        required_unless_present = "product_category_id",
-        conflicts_with = "product_category_id"
-    )]
+    #[arg(long, required_unless_present = "product_category_id", conflicts_with = "product_category_id")]
     product_category_name: Option<String>,
     // #[arg(
     //     long,
@@ -403,9 +446,6 @@ fn parse_date_time(arg: &str) -> Result<DateTime<Utc>, chrono::ParseError> {
     parse_arg_to_chrono_date_time(arg, "%Y-%m-%d %H:%M:%S")
 }
 
-fn parse_arg_to_chrono_date_time(
-    arg: &str,
-    pattern: &str,
-) -> Result<DateTime<Utc>, chrono::ParseError> {
+fn parse_arg_to_chrono_date_time(arg: &str, pattern: &str) -> Result<DateTime<Utc>, chrono::ParseError> {
     NaiveDateTime::parse_from_str(arg, pattern).map(|i| i.and_utc())
 }
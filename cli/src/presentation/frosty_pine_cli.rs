@@ -1,5 +1,9 @@
+use chrono::{Duration, Utc};
 use expense_tracking::domain::entities::Brand;
 use expense_tracking::domain::repositories::BrandRepository;
+use expense_tracking::domain::repositories::DeleteBehaviour;
+use expense_tracking::domain::search::SearchService;
+use futures::StreamExt;
 
 use crate::presentation::clap_args::BrandCommands;
 use crate::presentation::clap_args::CliArgs;
@@ -9,31 +13,56 @@ use crate::presentation::clap_args::Service;
 pub struct FrostyPineCli {
     cli_args: CliArgs,
     brand_repository: Box<dyn BrandRepository>,
+    search_service: SearchService,
 }
 
 impl FrostyPineCli {
-    pub fn new(args: CliArgs, brand_repository: Box<dyn BrandRepository>) -> Self {
+    pub fn new(args: CliArgs, brand_repository: Box<dyn BrandRepository>, search_service: SearchService) -> Self {
         Self {
             cli_args: args,
             brand_repository,
+            search_service,
         }
     }
 
     pub async fn run(&mut self) {
-        match &self.cli_args.service {
-            Service::Brands(args) => match &args.command {
+        if let Service::Search { query, limit } = &self.cli_args.service {
+            for hit in self.search_service.search(query, *limit) {
+                println!("{:?}\t{}\t{}", hit.kind, hit.title, hit.key);
+            }
+        }
+
+        if let Service::Brands(args) = &self.cli_args.service {
+            match &args.command {
                 BrandCommands::Add { name } => {
                     let new_brand = Brand::new(name.clone());
-                    self.brand_repository.create(&new_brand).await;
+                    println!("{:?}", self.brand_repository.create(&new_brand).await);
+                }
+                BrandCommands::Get { id, name } if name.clone().and(id.clone()).is_none() => {
+                    let mut brands = self.brand_repository.stream_all();
+
+                    while let Some(brand) = brands.next().await {
+                        println!("{:?}", brand);
+                    }
+                }
+                BrandCommands::Delete { name: Some(name), .. } => {
+                    let brand = Brand::new(name.clone());
+                    println!("{:?}", self.brand_repository.delete(&brand, DeleteBehaviour::Restrict).await);
                 }
-                BrandCommands::Get { id, name } => {
-                    if name.clone().and(id.clone()).is_none() {
-                        println!("{:?}", self.brand_repository.retrieve_all().await);
+                BrandCommands::Trash { purge_older_than_days } => {
+                    if let Some(days) = purge_older_than_days {
+                        let cutoff = Utc::now() - Duration::days(*days);
+                        println!("{:?}", self.brand_repository.purge_older_than(cutoff).await);
                     }
+
+                    println!("{:?}", self.brand_repository.list_trashed().await);
+                }
+                BrandCommands::Restore { name } => {
+                    let brand = Brand::new(name.clone());
+                    println!("{:?}", self.brand_repository.restore(&brand).await);
                 }
                 _ => {}
-            },
-            _ => {}
+            }
         }
     }
 }
@@ -0,0 +1,27 @@
+use storage_encryption::infrastructures::encryption::{Cipher, new_passphrase_from_environment, passphrase_from_environment};
+
+/// Asks for the passphrase of an existing file, unless `FROSTY_PINE_PASSPHRASE` already provides it.
+pub fn prompt_passphrase() -> Cipher {
+    passphrase_from_environment()
+        .unwrap_or_else(|| Cipher::new(rpassword::prompt_password("Passphrase: ").expect("Unable to read passphrase")))
+}
+
+/// Asks twice for a new passphrase until both entries match, unless `FROSTY_PINE_NEW_PASSPHRASE` already provides it.
+pub fn prompt_new_passphrase() -> Cipher {
+    if let Some(cipher) = new_passphrase_from_environment() {
+        return cipher;
+    }
+
+    loop {
+        let passphrase: String = rpassword::prompt_password("New passphrase: ").expect("Unable to read passphrase");
+        let confirmation: String = rpassword::prompt_password("Repeat new passphrase: ").expect("Unable to read passphrase");
+
+        if passphrase != confirmation {
+            eprintln!("Passphrases do not match, try again");
+        } else if passphrase.is_empty() {
+            eprintln!("The passphrase cannot be empty, use --decrypt to store the file in plaintext");
+        } else {
+            return Cipher::new(passphrase);
+        }
+    }
+}
@@ -258,6 +258,7 @@ fn wire__crate__adapters__translations__rust_factory__rust_factory_retrieve_all_
 const _: fn() = || {
     let Brand = None::<crate::adapters::translations::mirrors::Brand>.unwrap();
     let _: String = Brand.name;
+    let _: u64 = Brand.version;
 };
 
 // Section: related_funcs
@@ -331,7 +332,11 @@ impl SseDecode for crate::adapters::translations::mirrors::Brand {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
         let mut var_name = <String>::sse_decode(deserializer);
-        return crate::adapters::translations::mirrors::Brand { name: var_name };
+        let mut var_version = <u64>::sse_decode(deserializer);
+        return crate::adapters::translations::mirrors::Brand {
+            name: var_name,
+            version: var_version,
+        };
     }
 }
 
@@ -393,6 +398,13 @@ impl SseDecode for () {
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {}
 }
 
+impl SseDecode for u64 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        deserializer.cursor.read_u64::<NativeEndian>().unwrap()
+    }
+}
+
 impl SseDecode for usize {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
@@ -491,7 +503,7 @@ impl flutter_rust_bridge::IntoIntoDart<FrbWrapper<Arc<FlutterPresenter>>> for Ar
 // Codec=Dco (DartCObject based), see doc to use other codecs
 impl flutter_rust_bridge::IntoDart for FrbWrapper<crate::adapters::translations::mirrors::Brand> {
     fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
-        [self.0.name.into_into_dart().into_dart()].into_dart()
+        [self.0.name.into_into_dart().into_dart(), self.0.version.into_into_dart().into_dart()].into_dart()
     }
 }
 impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for FrbWrapper<crate::adapters::translations::mirrors::Brand> {}
@@ -615,6 +627,7 @@ impl SseEncode for crate::adapters::translations::mirrors::Brand {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
         <String>::sse_encode(self.name, serializer);
+        <u64>::sse_encode(self.version, serializer);
     }
 }
 
@@ -667,6 +680,13 @@ impl SseEncode for () {
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {}
 }
 
+impl SseEncode for u64 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        serializer.cursor.write_u64::<NativeEndian>(self).unwrap();
+    }
+}
+
 impl SseEncode for usize {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -1,10 +1,12 @@
-use std::{
-    collections::HashMap,
-    sync::{Arc, Mutex},
-};
+use std::sync::Arc;
 
 use expense_tracking::domain::{entities::Brand, repositories::BrandRepository, use_cases::RetrieveAllBrandsUseCase};
-use in_memory_storage::adapters::repositories::BrandRepositoryInMemoryImpl;
+use tokio::sync::RwLock;
+
+use in_memory_storage::{
+    adapters::{mappers::BrandMapper, repositories::BrandRepositoryInMemoryImpl},
+    infrastructures::data_sources::InMemoryCache,
+};
 
 use crate::adapters::presenters::flutter_presenter::FlutterPresenter;
 
@@ -14,14 +16,13 @@ pub struct RustFactory {}
 
 impl RustFactory {
     pub fn brand_repository_in_memory_impl(initial_data: Vec<Brand>) -> RustOpaque<Arc<dyn BrandRepository>> {
-        let data: Arc<Mutex<HashMap<String, Brand>>> = Arc::new(Mutex::new(
-            initial_data
-                .into_iter()
-                .map(|b| (b.name.clone(), b.clone()))
-                .collect::<HashMap<String, Brand>>(),
-        ));
-
-        RustOpaque::new(Arc::new(BrandRepositoryInMemoryImpl::new(data)))
+        let mut cache: InMemoryCache = InMemoryCache::new();
+
+        initial_data.iter().for_each(|b| {
+            cache.upsert_brand(BrandMapper::to_model(b));
+        });
+
+        RustOpaque::new(Arc::new(BrandRepositoryInMemoryImpl::new(Arc::new(RwLock::new(cache)))))
     }
 
     pub fn retrieve_all_brands_use_case(brand_repository: RustOpaque<Arc<dyn BrandRepository>>) -> RustOpaque<RetrieveAllBrandsUseCase> {
@@ -0,0 +1,13 @@
+mod brand_repository_event_sourced_impl;
+mod category_repository_event_sourced_impl;
+#[cfg(test)]
+mod conformance_tests;
+mod product_repository_event_sourced_impl;
+mod store_repository_event_sourced_impl;
+mod transaction_repository_event_sourced_impl;
+
+pub use brand_repository_event_sourced_impl::BrandRepositoryEventSourcedImpl;
+pub use category_repository_event_sourced_impl::CategoryRepositoryEventSourcedImpl;
+pub use product_repository_event_sourced_impl::ProductRepositoryEventSourcedImpl;
+pub use store_repository_event_sourced_impl::StoreRepositoryEventSourcedImpl;
+pub use transaction_repository_event_sourced_impl::TransactionRepositoryEventSourcedImpl;
@@ -0,0 +1,292 @@
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+
+use expense_tracking::domain::{
+    entities::{Brand, NamePolicy},
+    repositories::{
+        BrandRepository, BrandRepositoryCreateError, BrandRepositoryDeleteError, BrandRepositoryRestoreError,
+        BrandRepositoryRetrieveAllError, BrandRepositoryUpdateError, DeleteBehaviour, Trashed,
+    },
+};
+
+use crate::infrastructures::data_sources::{BrandDocument, EventStore, active, drain_older_than, is_active, trashed};
+
+#[derive(Debug, Clone)]
+pub struct BrandRepositoryEventSourcedImpl {
+    store: EventStore,
+    name_policy: NamePolicy,
+}
+
+impl BrandRepositoryEventSourcedImpl {
+    pub fn new(store: EventStore) -> Self {
+        Self {
+            store,
+            name_policy: NamePolicy::default(),
+        }
+    }
+
+    pub fn with_name_policy(self, name_policy: NamePolicy) -> Self {
+        Self { name_policy, ..self }
+    }
+
+    /// The key under which the ledger stores the brand named `name`, or `name` itself for an unknown brand.
+    fn stored_name(&self, brands: &BTreeMap<String, BrandDocument>, name: &String) -> String {
+        self.name_policy.find(name, brands.keys()).unwrap_or(name).clone()
+    }
+}
+
+#[async_trait]
+impl BrandRepository for BrandRepositoryEventSourcedImpl {
+    async fn create(&self, brand: &Brand) -> Result<Brand, BrandRepositoryCreateError> {
+        self.store
+            .write(|ledger| {
+                if self.name_policy.find(&brand.name, ledger.brands.keys()).is_some() {
+                    return Err(BrandRepositoryCreateError::BrandAlreadyExists);
+                }
+
+                ledger.brands.insert(brand.name.clone(), BrandDocument::from(brand));
+                Ok(brand.clone())
+            })
+            .map_err(|e| BrandRepositoryCreateError::UnableToSaveBrand(format!("{:?}", e)))?
+    }
+
+    async fn retrieve_all(&self) -> Result<Vec<Brand>, BrandRepositoryRetrieveAllError> {
+        self.store
+            .read(|ledger| active(&ledger.brands).map(Brand::from).collect())
+            .map_err(|e| BrandRepositoryRetrieveAllError::UnableToRetrieveBrands(format!("{:?}", e)))
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Brand, BrandRepositoryRetrieveAllError>> {
+        self.store.stream_active(
+            |ledger| &ledger.brands,
+            |_, brand| Some(Brand::from(brand)),
+            |e| BrandRepositoryRetrieveAllError::UnableToRetrieveBrands(format!("{:?}", e)),
+        )
+    }
+
+    async fn update(&self, brand: &Brand) -> Result<Brand, BrandRepositoryUpdateError> {
+        self.store
+            .write(|ledger| {
+                let name: String = self.stored_name(&ledger.brands, &brand.name);
+                let existing: &mut BrandDocument = match ledger.brands.get_mut(&name) {
+                    Some(existing) if existing.deleted_at.is_none() => existing,
+                    _ => return Err(BrandRepositoryUpdateError::BrandNotFound),
+                };
+
+                if existing.version != brand.version {
+                    return Err(BrandRepositoryUpdateError::VersionConflict {
+                        current_version: existing.version,
+                    });
+                }
+
+                existing.version += 1;
+                Ok(Brand::from(&*existing))
+            })
+            .map_err(|e| BrandRepositoryUpdateError::UnableToUpdateBrand(format!("{:?}", e)))?
+    }
+
+    async fn delete(&self, brand: &Brand, behaviour: DeleteBehaviour<Brand>) -> Result<Brand, BrandRepositoryDeleteError> {
+        self.store
+            .write(|ledger| {
+                let name: String = self.stored_name(&ledger.brands, &brand.name);
+                if !is_active(&ledger.brands, &name) {
+                    return Err(BrandRepositoryDeleteError::BrandNotFound);
+                }
+
+                let now: DateTime<Utc> = Utc::now();
+
+                match &behaviour {
+                    DeleteBehaviour::Restrict => {
+                        if active(&ledger.products).any(|product| product.brand == name) {
+                            return Err(BrandRepositoryDeleteError::BrandStillReferenced);
+                        }
+                    }
+                    DeleteBehaviour::Cascade => ledger.cascade_products(|product| product.brand == name, now),
+                    DeleteBehaviour::Reassign(replacement) => {
+                        let replacement: String = self.stored_name(&ledger.brands, &replacement.name);
+                        if replacement == name || !is_active(&ledger.brands, &replacement) {
+                            return Err(BrandRepositoryDeleteError::ReplacementBrandNotFound);
+                        }
+
+                        for product in ledger.products.values_mut().filter(|product| product.brand == name) {
+                            product.brand = replacement.clone();
+                        }
+                    }
+                }
+
+                let removed: &mut BrandDocument = ledger.brands.get_mut(&name).ok_or(BrandRepositoryDeleteError::BrandNotFound)?;
+                removed.deleted_at = Some(now);
+                Ok(Brand::from(&*removed))
+            })
+            .map_err(|e| BrandRepositoryDeleteError::UnableToDeleteBrand(format!("{:?}", e)))?
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Brand>>, BrandRepositoryRetrieveAllError> {
+        self.store
+            .read(|ledger| {
+                trashed(&ledger.brands)
+                    .map(|(brand, deleted_at)| Trashed::new(Brand::from(brand), deleted_at))
+                    .collect()
+            })
+            .map_err(|e| BrandRepositoryRetrieveAllError::UnableToRetrieveBrands(format!("{:?}", e)))
+    }
+
+    async fn restore(&self, brand: &Brand) -> Result<Brand, BrandRepositoryRestoreError> {
+        self.store
+            .write(|ledger| {
+                let name: String = self.stored_name(&ledger.brands, &brand.name);
+                let trashed: &mut BrandDocument = match ledger.brands.get_mut(&name) {
+                    Some(trashed) if trashed.deleted_at.is_some() => trashed,
+                    _ => return Err(BrandRepositoryRestoreError::BrandNotInTrash),
+                };
+                let deleted_at: Option<DateTime<Utc>> = trashed.deleted_at.take();
+                let restored: Brand = Brand::from(&*trashed);
+
+                if let Some(deleted_at) = deleted_at {
+                    ledger.restore_products(|product| product.brand == name, deleted_at);
+                }
+
+                Ok(restored)
+            })
+            .map_err(|e| BrandRepositoryRestoreError::UnableToRestoreBrand(format!("{:?}", e)))?
+    }
+
+    async fn purge_older_than(&self, cutoff: DateTime<Utc>) -> Result<Vec<Brand>, BrandRepositoryDeleteError> {
+        self.store
+            .write(|ledger| {
+                let purged: Vec<BrandDocument> = drain_older_than(&mut ledger.brands, cutoff);
+
+                ledger.remove_products(|product| purged.iter().any(|brand| brand.name == product.brand));
+                Ok(purged.iter().map(Brand::from).collect())
+            })
+            .map_err(|e| BrandRepositoryDeleteError::UnableToDeleteBrand(format!("{:?}", e)))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use chrono::{Duration, Utc};
+    use futures::StreamExt;
+
+    use super::BrandRepositoryEventSourcedImpl;
+    use crate::infrastructures::data_sources::{Event, EventStore};
+    use expense_tracking::domain::{
+        entities::Brand,
+        repositories::{
+            BrandRepository, BrandRepositoryCreateError, BrandRepositoryDeleteError, BrandRepositoryRestoreError,
+            BrandRepositoryRetrieveAllError, BrandRepositoryUpdateError, DeleteBehaviour,
+        },
+    };
+
+    async fn given_repository_with(directory: &tempfile::TempDir, brands: Vec<Brand>) -> BrandRepositoryEventSourcedImpl {
+        let repository: BrandRepositoryEventSourcedImpl = BrandRepositoryEventSourcedImpl::new(EventStore::open(directory.path()).unwrap());
+
+        for brand in brands {
+            repository.create(&brand).await.unwrap();
+        }
+
+        repository
+    }
+
+    #[tokio::test]
+    async fn create_should_persist_brand_across_reopen() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let brand: Brand = Brand::new("Mora Radunz".to_owned());
+        let repository: BrandRepositoryEventSourcedImpl = given_repository_with(&directory, vec![]).await;
+
+        let result: Result<Brand, BrandRepositoryCreateError> = repository.create(&brand).await;
+        let expected: Result<Brand, BrandRepositoryCreateError> = Ok(brand.clone());
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+
+        drop(repository);
+        let reopened: BrandRepositoryEventSourcedImpl = given_repository_with(&directory, vec![]).await;
+
+        let result: Result<Vec<Brand>, BrandRepositoryRetrieveAllError> = reopened.retrieve_all().await;
+        let expected: Result<Vec<Brand>, BrandRepositoryRetrieveAllError> = Ok(vec![brand]);
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn create_existing_brand_should_fail() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let brand: Brand = Brand::new("Mora Radunz".to_owned());
+        let repository: BrandRepositoryEventSourcedImpl = given_repository_with(&directory, vec![brand.clone()]).await;
+
+        let result: Result<Brand, BrandRepositoryCreateError> = repository.create(&brand).await;
+        let expected: Result<Brand, BrandRepositoryCreateError> = Err(BrandRepositoryCreateError::BrandAlreadyExists);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn stream_all_should_yield_all_brands_in_name_order() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let brands: Vec<Brand> = (0..25).rev().map(|i| Brand::new(format!("Brand {:02}", i))).collect();
+        let repository: BrandRepositoryEventSourcedImpl = given_repository_with(&directory, brands.clone()).await;
+
+        let result: Result<Vec<Brand>, BrandRepositoryRetrieveAllError> =
+            repository.stream_all().collect::<Vec<_>>().await.into_iter().collect();
+        let expected: Result<Vec<Brand>, BrandRepositoryRetrieveAllError> = Ok(brands.into_iter().rev().collect());
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn update_should_bump_version_and_reject_stale_versions() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let brand: Brand = Brand::new("Mora Radunz".to_owned());
+        let repository: BrandRepositoryEventSourcedImpl = given_repository_with(&directory, vec![brand.clone()]).await;
+
+        let result: Result<Brand, BrandRepositoryUpdateError> = repository.update(&brand).await;
+        let expected: Result<Brand, BrandRepositoryUpdateError> = Ok(Brand {
+            version: 1,
+            ..brand.clone()
+        });
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+
+        let result: Result<Brand, BrandRepositoryUpdateError> = repository.update(&brand).await;
+        let expected: Result<Brand, BrandRepositoryUpdateError> = Err(BrandRepositoryUpdateError::VersionConflict { current_version: 1 });
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn delete_restore_and_purge_should_move_brand_through_trash() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let brand: Brand = Brand::new("Mora Radunz".to_owned());
+        let repository: BrandRepositoryEventSourcedImpl = given_repository_with(&directory, vec![brand.clone()]).await;
+
+        assert_eq!(repository.delete(&brand, DeleteBehaviour::Restrict).await, Ok(brand.clone()));
+        assert_eq!(
+            repository.delete(&brand, DeleteBehaviour::Restrict).await,
+            Err(BrandRepositoryDeleteError::BrandNotFound)
+        );
+
+        assert_eq!(repository.restore(&brand).await, Ok(brand.clone()));
+        assert_eq!(repository.restore(&brand).await, Err(BrandRepositoryRestoreError::BrandNotInTrash));
+
+        repository.delete(&brand, DeleteBehaviour::Restrict).await.unwrap();
+        assert_eq!(repository.purge_older_than(Utc::now() - Duration::days(1)).await, Ok(vec![]));
+        assert_eq!(repository.purge_older_than(Utc::now() + Duration::days(1)).await, Ok(vec![brand]));
+        assert_eq!(repository.list_trashed().await, Ok(vec![]));
+
+        let result: Vec<&str> = repository
+            .store
+            .history()
+            .unwrap()
+            .iter()
+            .map(|record| match record.event {
+                Event::BrandCreated(_) => "created",
+                Event::BrandDeleted(_) => "deleted",
+                Event::BrandRestored(_) => "restored",
+                Event::BrandPurged(_) => "purged",
+                _ => "other",
+            })
+            .collect();
+        let expected: Vec<&str> = vec!["created", "deleted", "restored", "deleted", "purged"];
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+}
@@ -0,0 +1,267 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use uuid_b64::UuidB64;
+
+use expense_tracking::domain::{
+    entities::{Category, NamePolicy},
+    repositories::{CategoryRepository, CategoryRepositoryError, DeleteBehaviour, Trashed},
+};
+
+use crate::infrastructures::data_sources::{
+    CategoryDocument, EventLedger, EventStore, EventStoreError, active, drain_older_than, is_active, trashed,
+};
+
+#[derive(Debug, Clone)]
+pub struct CategoryRepositoryEventSourcedImpl {
+    store: EventStore,
+    name_policy: NamePolicy,
+}
+
+impl CategoryRepositoryEventSourcedImpl {
+    pub fn new(store: EventStore) -> Self {
+        Self {
+            store,
+            name_policy: NamePolicy::default(),
+        }
+    }
+
+    pub fn with_name_policy(self, name_policy: NamePolicy) -> Self {
+        Self { name_policy, ..self }
+    }
+
+    /// Whether another category, even a trashed one, bears the name of `category` under the name policy.
+    fn is_name_taken(&self, ledger: &EventLedger, category: &Category) -> bool {
+        let id: String = category.id.to_string();
+
+        ledger
+            .categories
+            .iter()
+            .any(|(key, existing)| key != &id && self.name_policy.matches(&existing.name, &category.name))
+    }
+}
+
+fn storage_error(error: EventStoreError) -> CategoryRepositoryError {
+    CategoryRepositoryError::UnableToAccessStorage(format!("{:?}", error))
+}
+
+#[async_trait]
+impl CategoryRepository for CategoryRepositoryEventSourcedImpl {
+    async fn create_or_update(&mut self, category: &Category) -> Result<Option<Category>, CategoryRepositoryError> {
+        self.store
+            .write(|ledger| {
+                if self.is_name_taken(ledger, category) {
+                    return Err(CategoryRepositoryError::CategoryAlreadyExists);
+                }
+
+                match ledger.categories.get_mut(&category.id.to_string()) {
+                    Some(existing) if existing.deleted_at.is_some() => Err(CategoryRepositoryError::CategoryAlreadyExists),
+                    Some(existing) if existing.version != category.version => Err(CategoryRepositoryError::VersionConflict {
+                        current_version: existing.version,
+                    }),
+                    Some(existing) => {
+                        let previous: Category = Category::from(&*existing);
+
+                        existing.name = category.name.clone();
+                        existing.version += 1;
+                        Ok(Some(previous))
+                    }
+                    None => {
+                        ledger.categories.insert(category.id.to_string(), CategoryDocument::from(category));
+                        Ok(None)
+                    }
+                }
+            })
+            .map_err(storage_error)?
+    }
+
+    async fn retrieve_all(&self) -> Result<Vec<Category>, CategoryRepositoryError> {
+        self.store
+            .read(|ledger| active(&ledger.categories).map(Category::from).collect())
+            .map_err(storage_error)
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Category, CategoryRepositoryError>> {
+        self.store.stream_active(
+            |ledger| &ledger.categories,
+            |_, category| Some(Category::from(category)),
+            storage_error,
+        )
+    }
+
+    async fn delete(&mut self, id: &UuidB64, behaviour: DeleteBehaviour<Category>) -> Result<Category, CategoryRepositoryError> {
+        self.store
+            .write(|ledger| {
+                if !is_active(&ledger.categories, &id.to_string()) {
+                    return Err(CategoryRepositoryError::CategoryNotFound);
+                }
+
+                let now: DateTime<Utc> = Utc::now();
+
+                match &behaviour {
+                    DeleteBehaviour::Restrict => {
+                        if active(&ledger.products).any(|product| product.category == *id) {
+                            return Err(CategoryRepositoryError::CategoryStillReferenced);
+                        }
+                    }
+                    DeleteBehaviour::Cascade => ledger.cascade_products(|product| product.category == *id, now),
+                    DeleteBehaviour::Reassign(replacement) => {
+                        if replacement.id == *id || !is_active(&ledger.categories, &replacement.id.to_string()) {
+                            return Err(CategoryRepositoryError::ReplacementCategoryNotFound);
+                        }
+
+                        for product in ledger.products.values_mut().filter(|product| product.category == *id) {
+                            product.category = replacement.id;
+                        }
+                    }
+                }
+
+                let removed: &mut CategoryDocument = ledger
+                    .categories
+                    .get_mut(&id.to_string())
+                    .ok_or(CategoryRepositoryError::CategoryNotFound)?;
+                removed.deleted_at = Some(now);
+                Ok(Category::from(&*removed))
+            })
+            .map_err(storage_error)?
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Category>>, CategoryRepositoryError> {
+        self.store
+            .read(|ledger| {
+                trashed(&ledger.categories)
+                    .map(|(category, deleted_at)| Trashed::new(Category::from(category), deleted_at))
+                    .collect()
+            })
+            .map_err(storage_error)
+    }
+
+    async fn restore(&mut self, id: &UuidB64) -> Result<Category, CategoryRepositoryError> {
+        self.store
+            .write(|ledger| {
+                let trashed: &mut CategoryDocument = match ledger.categories.get_mut(&id.to_string()) {
+                    Some(trashed) if trashed.deleted_at.is_some() => trashed,
+                    _ => return Err(CategoryRepositoryError::CategoryNotInTrash),
+                };
+                let deleted_at: Option<DateTime<Utc>> = trashed.deleted_at.take();
+                let restored: Category = Category::from(&*trashed);
+
+                if let Some(deleted_at) = deleted_at {
+                    ledger.restore_products(|product| product.category == *id, deleted_at);
+                }
+
+                Ok(restored)
+            })
+            .map_err(storage_error)?
+    }
+
+    async fn purge_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<Vec<Category>, CategoryRepositoryError> {
+        self.store
+            .write(|ledger| {
+                let purged: Vec<CategoryDocument> = drain_older_than(&mut ledger.categories, cutoff);
+
+                ledger.remove_products(|product| purged.iter().any(|category| category.id == product.category));
+                Ok(purged.iter().map(Category::from).collect())
+            })
+            .map_err(storage_error)?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::CategoryRepositoryEventSourcedImpl;
+    use crate::infrastructures::data_sources::EventStore;
+    use expense_tracking::domain::{
+        entities::Category,
+        repositories::{CategoryRepository, CategoryRepositoryError, DeleteBehaviour},
+    };
+
+    fn given_empty_repository(directory: &tempfile::TempDir) -> CategoryRepositoryEventSourcedImpl {
+        CategoryRepositoryEventSourcedImpl::new(EventStore::open(directory.path()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn create_or_update_should_insert_then_update_with_new_version() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let category: Category = Category::new(None, "Dairy".to_owned());
+        let mut repository: CategoryRepositoryEventSourcedImpl = given_empty_repository(&directory);
+
+        assert_eq!(repository.create_or_update(&category).await, Ok(None));
+
+        let renamed: Category = Category {
+            name: "Dairy Products".to_owned(),
+            ..category.clone()
+        };
+        let result: Result<Option<Category>, CategoryRepositoryError> = repository.create_or_update(&renamed).await;
+        let expected: Result<Option<Category>, CategoryRepositoryError> = Ok(Some(category.clone()));
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+
+        assert_eq!(
+            repository.retrieve_all().await,
+            Ok(vec![Category {
+                version: 1,
+                ..renamed.clone()
+            }])
+        );
+        assert_eq!(
+            repository.create_or_update(&renamed).await,
+            Err(CategoryRepositoryError::VersionConflict { current_version: 1 })
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_with_reassign_should_require_active_replacement() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let category: Category = Category::new(None, "Dairy".to_owned());
+        let replacement: Category = Category::new(None, "Food".to_owned());
+        let mut repository: CategoryRepositoryEventSourcedImpl = given_empty_repository(&directory);
+        repository.create_or_update(&category).await.unwrap();
+
+        let result: Result<Category, CategoryRepositoryError> = repository
+            .delete(&category.id, DeleteBehaviour::Reassign(replacement.clone()))
+            .await;
+        let expected: Result<Category, CategoryRepositoryError> = Err(CategoryRepositoryError::ReplacementCategoryNotFound);
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+
+        repository.create_or_update(&replacement).await.unwrap();
+
+        let result: Result<Category, CategoryRepositoryError> =
+            repository.delete(&category.id, DeleteBehaviour::Reassign(replacement)).await;
+        let expected: Result<Category, CategoryRepositoryError> = Ok(category);
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn delete_restore_and_purge_should_move_category_through_trash() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let category: Category = Category::new(None, "Dairy".to_owned());
+        let mut repository: CategoryRepositoryEventSourcedImpl = given_empty_repository(&directory);
+        repository.create_or_update(&category).await.unwrap();
+
+        assert_eq!(
+            repository.delete(&category.id, DeleteBehaviour::Restrict).await,
+            Ok(category.clone())
+        );
+        assert_eq!(
+            repository.create_or_update(&category).await,
+            Err(CategoryRepositoryError::CategoryAlreadyExists)
+        );
+        assert_eq!(repository.restore(&category.id).await, Ok(category.clone()));
+        assert_eq!(
+            repository.restore(&category.id).await,
+            Err(CategoryRepositoryError::CategoryNotInTrash)
+        );
+
+        assert_eq!(
+            repository.delete(&category.id, DeleteBehaviour::Restrict).await,
+            Ok(category.clone())
+        );
+        assert_eq!(
+            repository.purge_older_than(Utc::now() + Duration::days(1)).await,
+            Ok(vec![category])
+        );
+        assert_eq!(repository.list_trashed().await, Ok(vec![]));
+    }
+}
@@ -0,0 +1,55 @@
+use repository_conformance::{repository_conformance_suite, suites::RepositoryBackend};
+
+use crate::{
+    adapters::repositories::{
+        BrandRepositoryEventSourcedImpl, CategoryRepositoryEventSourcedImpl, ProductRepositoryEventSourcedImpl,
+        StoreRepositoryEventSourcedImpl, TransactionRepositoryEventSourcedImpl,
+    },
+    infrastructures::data_sources::EventStore,
+};
+
+struct EventSourcedBackend {
+    store: EventStore,
+    _root: tempfile::TempDir,
+}
+
+impl EventSourcedBackend {
+    fn new() -> Self {
+        let root: tempfile::TempDir = tempfile::tempdir().unwrap();
+
+        Self {
+            store: EventStore::open(root.path()).unwrap(),
+            _root: root,
+        }
+    }
+}
+
+impl RepositoryBackend for EventSourcedBackend {
+    type Brands = BrandRepositoryEventSourcedImpl;
+    type Categories = CategoryRepositoryEventSourcedImpl;
+    type Stores = StoreRepositoryEventSourcedImpl;
+    type Products = ProductRepositoryEventSourcedImpl;
+    type Transactions = TransactionRepositoryEventSourcedImpl;
+
+    fn brands(&self) -> Self::Brands {
+        BrandRepositoryEventSourcedImpl::new(self.store.clone())
+    }
+
+    fn categories(&self) -> Self::Categories {
+        CategoryRepositoryEventSourcedImpl::new(self.store.clone())
+    }
+
+    fn stores(&self) -> Self::Stores {
+        StoreRepositoryEventSourcedImpl::new(self.store.clone())
+    }
+
+    fn products(&self) -> Self::Products {
+        ProductRepositoryEventSourcedImpl::new(self.store.clone())
+    }
+
+    fn transactions(&self) -> Self::Transactions {
+        TransactionRepositoryEventSourcedImpl::new(self.store.clone())
+    }
+}
+
+repository_conformance_suite!(EventSourcedBackend::new());
@@ -0,0 +1,280 @@
+use std::collections::BTreeSet;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use uuid_b64::UuidB64;
+
+use expense_tracking::domain::{
+    entities::{NamePolicy, Product},
+    repositories::{DeleteBehaviour, ProductRepository, ProductRepositoryError, Trashed},
+};
+
+use crate::infrastructures::data_sources::{
+    Document, EventLedger, EventStore, EventStoreError, ProductDocument, active, drain_older_than, is_active, trashed,
+};
+
+#[derive(Debug, Clone)]
+pub struct ProductRepositoryEventSourcedImpl {
+    store: EventStore,
+    name_policy: NamePolicy,
+}
+
+impl ProductRepositoryEventSourcedImpl {
+    pub fn new(store: EventStore) -> Self {
+        Self {
+            store,
+            name_policy: NamePolicy::default(),
+        }
+    }
+
+    pub fn with_name_policy(self, name_policy: NamePolicy) -> Self {
+        Self { name_policy, ..self }
+    }
+
+    /// Whether another product of the same brand, even a trashed one, bears the name of `product` under the name policy.
+    fn is_name_taken(&self, ledger: &EventLedger, product: &ProductDocument) -> bool {
+        let id: String = product.key();
+
+        ledger
+            .products
+            .iter()
+            .any(|(key, existing)| key != &id && existing.brand == product.brand && self.name_policy.matches(&existing.name, &product.name))
+    }
+}
+
+fn storage_error(error: EventStoreError) -> ProductRepositoryError {
+    ProductRepositoryError::UnableToAccessStorage(format!("{:?}", error))
+}
+
+fn check_references(ledger: &EventLedger, product: &ProductDocument) -> Result<(), ProductRepositoryError> {
+    if !is_active(&ledger.brands, &product.brand) {
+        return Err(ProductRepositoryError::BrandNotFound);
+    }
+
+    if !is_active(&ledger.categories, &product.category.to_string()) {
+        return Err(ProductRepositoryError::CategoryNotFound);
+    }
+
+    Ok(())
+}
+
+fn find_product(ledger: &EventLedger, id: &UuidB64) -> Option<(ProductDocument, Product)> {
+    let document: &ProductDocument = ledger.products.get(&id.to_string())?;
+
+    Some((document.clone(), ledger.product(document)?))
+}
+
+#[async_trait]
+impl ProductRepository for ProductRepositoryEventSourcedImpl {
+    async fn create_or_update(&mut self, product: &Product) -> Result<Option<Product>, ProductRepositoryError> {
+        self.store
+            .write(|ledger| {
+                let document: ProductDocument = ProductDocument::from(product);
+
+                check_references(ledger, &document)?;
+
+                if self.is_name_taken(ledger, &document) {
+                    return Err(ProductRepositoryError::ProductAlreadyExists);
+                }
+
+                match find_product(ledger, &product.id) {
+                    Some((existing, _)) if existing.deleted_at.is_some() => Err(ProductRepositoryError::ProductAlreadyExists),
+                    Some((existing, _)) if existing.version != product.version => Err(ProductRepositoryError::VersionConflict {
+                        current_version: existing.version,
+                    }),
+                    Some((existing, previous)) => {
+                        ledger.products.insert(
+                            document.key(),
+                            ProductDocument {
+                                version: existing.version + 1,
+                                ..document
+                            },
+                        );
+                        Ok(Some(previous))
+                    }
+                    None => {
+                        ledger.products.insert(document.key(), document);
+                        Ok(None)
+                    }
+                }
+            })
+            .map_err(storage_error)?
+    }
+
+    async fn retrieve_all(&self) -> Result<Vec<Product>, ProductRepositoryError> {
+        self.store
+            .read(|ledger| active(&ledger.products).filter_map(|product| ledger.product(product)).collect())
+            .map_err(storage_error)
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Product, ProductRepositoryError>> {
+        self.store
+            .stream_active(|ledger| &ledger.products, EventLedger::product, storage_error)
+    }
+
+    async fn delete(&mut self, id: &UuidB64, behaviour: DeleteBehaviour<Product>) -> Result<Product, ProductRepositoryError> {
+        self.store
+            .write(|ledger| {
+                let removed: Product = match find_product(ledger, id) {
+                    Some((existing, removed)) if existing.deleted_at.is_none() => removed,
+                    _ => return Err(ProductRepositoryError::ProductNotFound),
+                };
+
+                let now: DateTime<Utc> = Utc::now();
+
+                match &behaviour {
+                    DeleteBehaviour::Restrict => {
+                        if active(&ledger.transactions).any(|transaction| transaction.items.iter().any(|item| item.product == *id)) {
+                            return Err(ProductRepositoryError::ProductStillReferenced);
+                        }
+                    }
+                    DeleteBehaviour::Cascade => ledger.cascade_transactions(&BTreeSet::from([id.to_string()]), now),
+                    DeleteBehaviour::Reassign(replacement) => {
+                        if replacement.id == *id || !is_active(&ledger.products, &replacement.id.to_string()) {
+                            return Err(ProductRepositoryError::ReplacementProductNotFound);
+                        }
+
+                        for item in ledger
+                            .transactions
+                            .values_mut()
+                            .flat_map(|transaction| transaction.items.iter_mut())
+                            .filter(|item| item.product == *id)
+                        {
+                            item.product = replacement.id;
+                        }
+                    }
+                }
+
+                if let Some(product) = ledger.products.get_mut(&id.to_string()) {
+                    product.deleted_at = Some(now);
+                }
+
+                Ok(removed)
+            })
+            .map_err(storage_error)?
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Product>>, ProductRepositoryError> {
+        self.store
+            .read(|ledger| {
+                trashed(&ledger.products)
+                    .filter_map(|(product, deleted_at)| Some(Trashed::new(ledger.product(product)?, deleted_at)))
+                    .collect()
+            })
+            .map_err(storage_error)
+    }
+
+    async fn restore(&mut self, id: &UuidB64) -> Result<Product, ProductRepositoryError> {
+        self.store
+            .write(|ledger| match find_product(ledger, id) {
+                Some((trashed, product)) if let Some(deleted_at) = trashed.deleted_at => {
+                    check_references(ledger, &trashed)?;
+
+                    ledger.products.insert(
+                        trashed.key(),
+                        ProductDocument {
+                            deleted_at: None,
+                            ..trashed
+                        },
+                    );
+                    ledger.restore_transactions(|t| t.items.iter().any(|item| item.product == *id), deleted_at);
+                    Ok(product)
+                }
+                _ => Err(ProductRepositoryError::ProductNotInTrash),
+            })
+            .map_err(storage_error)?
+    }
+
+    async fn purge_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<Vec<Product>, ProductRepositoryError> {
+        self.store
+            .write(|ledger| {
+                let purged: Vec<Product> = trashed(&ledger.products)
+                    .filter(|(_, deleted_at)| *deleted_at < cutoff)
+                    .filter_map(|(product, _)| ledger.product(product))
+                    .collect();
+                let keys: BTreeSet<String> = drain_older_than(&mut ledger.products, cutoff)
+                    .iter()
+                    .map(|product| product.key())
+                    .collect();
+
+                ledger.remove_transactions_holding(&keys);
+                Ok(purged)
+            })
+            .map_err(storage_error)?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::ProductRepositoryEventSourcedImpl;
+    use crate::{
+        adapters::repositories::{BrandRepositoryEventSourcedImpl, CategoryRepositoryEventSourcedImpl},
+        infrastructures::data_sources::EventStore,
+    };
+    use expense_tracking::domain::{
+        entities::{Brand, Category, Product},
+        repositories::{BrandRepository, CategoryRepository, DeleteBehaviour, ProductRepository, ProductRepositoryError},
+    };
+
+    async fn given_repository_with_references(
+        directory: &tempfile::TempDir,
+        brand: &Brand,
+        category: &Category,
+    ) -> ProductRepositoryEventSourcedImpl {
+        let event_store: EventStore = EventStore::open(directory.path()).unwrap();
+
+        BrandRepositoryEventSourcedImpl::new(event_store.clone())
+            .create(brand)
+            .await
+            .unwrap();
+        CategoryRepositoryEventSourcedImpl::new(event_store.clone())
+            .create_or_update(category)
+            .await
+            .unwrap();
+
+        ProductRepositoryEventSourcedImpl::new(event_store)
+    }
+
+    #[tokio::test]
+    async fn create_or_update_should_require_existing_brand_and_category() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let brand: Brand = Brand::new("Nestlé".to_owned());
+        let category: Category = Category::new(None, "Dairy".to_owned());
+        let mut repository: ProductRepositoryEventSourcedImpl = given_repository_with_references(&directory, &brand, &category).await;
+
+        let orphan: Product = Product::new(None, "Milk".to_owned(), Brand::new("Unknown".to_owned()), category.clone());
+        let result: Result<Option<Product>, ProductRepositoryError> = repository.create_or_update(&orphan).await;
+        let expected: Result<Option<Product>, ProductRepositoryError> = Err(ProductRepositoryError::BrandNotFound);
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+
+        let product: Product = Product::new(None, "Milk".to_owned(), brand, category);
+        assert_eq!(repository.create_or_update(&product).await, Ok(None));
+        assert_eq!(repository.retrieve_all().await, Ok(vec![product]));
+    }
+
+    #[tokio::test]
+    async fn brand_cascade_should_trash_products_and_purge_should_remove_them() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let brand: Brand = Brand::new("Nestlé".to_owned());
+        let category: Category = Category::new(None, "Dairy".to_owned());
+        let mut repository: ProductRepositoryEventSourcedImpl = given_repository_with_references(&directory, &brand, &category).await;
+        let product: Product = Product::new(None, "Milk".to_owned(), brand.clone(), category);
+        repository.create_or_update(&product).await.unwrap();
+
+        let brands: BrandRepositoryEventSourcedImpl = BrandRepositoryEventSourcedImpl::new(repository.store.clone());
+        brands.delete(&brand, DeleteBehaviour::Cascade).await.unwrap();
+
+        assert_eq!(repository.retrieve_all().await, Ok(vec![]));
+        assert_eq!(repository.restore(&product.id).await, Err(ProductRepositoryError::BrandNotFound));
+
+        brands.purge_older_than(Utc::now() + Duration::days(1)).await.unwrap();
+
+        let result: Result<Vec<Product>, ProductRepositoryError> = repository.purge_older_than(Utc::now() + Duration::days(1)).await;
+        let expected: Result<Vec<Product>, ProductRepositoryError> = Ok(vec![]);
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(repository.list_trashed().await, Ok(vec![]));
+    }
+}
@@ -0,0 +1,257 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use uuid_b64::UuidB64;
+
+use expense_tracking::domain::{
+    entities::{NamePolicy, Store},
+    repositories::{DeleteBehaviour, StoreRepository, StoreRepositoryError, Trashed},
+};
+
+use crate::infrastructures::data_sources::{
+    EventLedger, EventStore, EventStoreError, StoreDocument, active, drain_older_than, is_active, trashed,
+};
+
+#[derive(Debug, Clone)]
+pub struct StoreRepositoryEventSourcedImpl {
+    store: EventStore,
+    name_policy: NamePolicy,
+}
+
+impl StoreRepositoryEventSourcedImpl {
+    pub fn new(store: EventStore) -> Self {
+        Self {
+            store,
+            name_policy: NamePolicy::default(),
+        }
+    }
+
+    pub fn with_name_policy(self, name_policy: NamePolicy) -> Self {
+        Self { name_policy, ..self }
+    }
+
+    /// Whether another store, even a trashed one, bears the name of `store` under the name policy.
+    fn is_name_taken(&self, ledger: &EventLedger, store: &Store) -> bool {
+        let id: String = store.id.to_string();
+
+        ledger
+            .stores
+            .iter()
+            .any(|(key, existing)| key != &id && self.name_policy.matches(&existing.name, &store.name))
+    }
+}
+
+fn storage_error(error: EventStoreError) -> StoreRepositoryError {
+    StoreRepositoryError::UnableToAccessStorage(format!("{:?}", error))
+}
+
+#[async_trait]
+impl StoreRepository for StoreRepositoryEventSourcedImpl {
+    async fn create_or_update(&mut self, store: &Store) -> Result<Option<Store>, StoreRepositoryError> {
+        self.store
+            .write(|ledger| {
+                if self.is_name_taken(ledger, store) {
+                    return Err(StoreRepositoryError::StoreAlreadyExists);
+                }
+
+                match ledger.stores.get_mut(&store.id.to_string()) {
+                    Some(existing) if existing.deleted_at.is_some() => Err(StoreRepositoryError::StoreAlreadyExists),
+                    Some(existing) if existing.version != store.version => Err(StoreRepositoryError::VersionConflict {
+                        current_version: existing.version,
+                    }),
+                    Some(existing) => {
+                        let previous: Store = Store::from(&*existing);
+
+                        existing.name = store.name.clone();
+                        existing.version += 1;
+                        Ok(Some(previous))
+                    }
+                    None => {
+                        ledger.stores.insert(store.id.to_string(), StoreDocument::from(store));
+                        Ok(None)
+                    }
+                }
+            })
+            .map_err(storage_error)?
+    }
+
+    async fn retrieve_all(&self) -> Result<Vec<Store>, StoreRepositoryError> {
+        self.store
+            .read(|ledger| active(&ledger.stores).map(Store::from).collect())
+            .map_err(storage_error)
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Store, StoreRepositoryError>> {
+        self.store
+            .stream_active(|ledger| &ledger.stores, |_, store| Some(Store::from(store)), storage_error)
+    }
+
+    async fn delete(&mut self, id: &UuidB64, behaviour: DeleteBehaviour<Store>) -> Result<Store, StoreRepositoryError> {
+        self.store
+            .write(|ledger| {
+                if !is_active(&ledger.stores, &id.to_string()) {
+                    return Err(StoreRepositoryError::StoreNotFound);
+                }
+
+                let now: DateTime<Utc> = Utc::now();
+
+                match &behaviour {
+                    DeleteBehaviour::Restrict => {
+                        if active(&ledger.transactions).any(|transaction| transaction.store == *id) {
+                            return Err(StoreRepositoryError::StoreStillReferenced);
+                        }
+                    }
+                    DeleteBehaviour::Cascade => {
+                        for transaction in ledger
+                            .transactions
+                            .values_mut()
+                            .filter(|transaction| transaction.store == *id && transaction.deleted_at.is_none())
+                        {
+                            transaction.deleted_at = Some(now);
+                        }
+                    }
+                    DeleteBehaviour::Reassign(replacement) => {
+                        if replacement.id == *id || !is_active(&ledger.stores, &replacement.id.to_string()) {
+                            return Err(StoreRepositoryError::ReplacementStoreNotFound);
+                        }
+
+                        for transaction in ledger.transactions.values_mut().filter(|transaction| transaction.store == *id) {
+                            transaction.store = replacement.id;
+                        }
+                    }
+                }
+
+                let removed: &mut StoreDocument = ledger.stores.get_mut(&id.to_string()).ok_or(StoreRepositoryError::StoreNotFound)?;
+                removed.deleted_at = Some(now);
+                Ok(Store::from(&*removed))
+            })
+            .map_err(storage_error)?
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Store>>, StoreRepositoryError> {
+        self.store
+            .read(|ledger| {
+                trashed(&ledger.stores)
+                    .map(|(store, deleted_at)| Trashed::new(Store::from(store), deleted_at))
+                    .collect()
+            })
+            .map_err(storage_error)
+    }
+
+    async fn restore(&mut self, id: &UuidB64) -> Result<Store, StoreRepositoryError> {
+        self.store
+            .write(|ledger| {
+                let trashed: &mut StoreDocument = match ledger.stores.get_mut(&id.to_string()) {
+                    Some(trashed) if trashed.deleted_at.is_some() => trashed,
+                    _ => return Err(StoreRepositoryError::StoreNotInTrash),
+                };
+                let deleted_at: Option<DateTime<Utc>> = trashed.deleted_at.take();
+                let restored: Store = Store::from(&*trashed);
+
+                if let Some(deleted_at) = deleted_at {
+                    ledger.restore_transactions(|transaction| transaction.store == *id, deleted_at);
+                }
+
+                Ok(restored)
+            })
+            .map_err(storage_error)?
+    }
+
+    async fn purge_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<Vec<Store>, StoreRepositoryError> {
+        self.store
+            .write(|ledger| {
+                let purged: Vec<StoreDocument> = drain_older_than(&mut ledger.stores, cutoff);
+
+                ledger
+                    .transactions
+                    .retain(|_, transaction| purged.iter().all(|store| store.id != transaction.store));
+                Ok(purged.iter().map(Store::from).collect())
+            })
+            .map_err(storage_error)?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::StoreRepositoryEventSourcedImpl;
+    use crate::infrastructures::data_sources::EventStore;
+    use expense_tracking::domain::{
+        entities::Store,
+        repositories::{DeleteBehaviour, StoreRepository, StoreRepositoryError},
+    };
+
+    fn given_empty_repository(directory: &tempfile::TempDir) -> StoreRepositoryEventSourcedImpl {
+        StoreRepositoryEventSourcedImpl::new(EventStore::open(directory.path()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn create_or_update_should_insert_then_update_with_new_version() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let store: Store = Store::new(None, "Migros".to_owned());
+        let mut repository: StoreRepositoryEventSourcedImpl = given_empty_repository(&directory);
+
+        assert_eq!(repository.create_or_update(&store).await, Ok(None));
+
+        let renamed: Store = Store {
+            name: "Migros City".to_owned(),
+            ..store.clone()
+        };
+        let result: Result<Option<Store>, StoreRepositoryError> = repository.create_or_update(&renamed).await;
+        let expected: Result<Option<Store>, StoreRepositoryError> = Ok(Some(store.clone()));
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+
+        assert_eq!(
+            repository.retrieve_all().await,
+            Ok(vec![Store {
+                version: 1,
+                ..renamed.clone()
+            }])
+        );
+        assert_eq!(
+            repository.create_or_update(&renamed).await,
+            Err(StoreRepositoryError::VersionConflict { current_version: 1 })
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_with_reassign_should_require_active_replacement() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let store: Store = Store::new(None, "Migros".to_owned());
+        let replacement: Store = Store::new(None, "Coop".to_owned());
+        let mut repository: StoreRepositoryEventSourcedImpl = given_empty_repository(&directory);
+        repository.create_or_update(&store).await.unwrap();
+
+        let result: Result<Store, StoreRepositoryError> =
+            repository.delete(&store.id, DeleteBehaviour::Reassign(replacement.clone())).await;
+        let expected: Result<Store, StoreRepositoryError> = Err(StoreRepositoryError::ReplacementStoreNotFound);
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+
+        repository.create_or_update(&replacement).await.unwrap();
+
+        let result: Result<Store, StoreRepositoryError> = repository.delete(&store.id, DeleteBehaviour::Reassign(replacement)).await;
+        let expected: Result<Store, StoreRepositoryError> = Ok(store);
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn delete_restore_and_purge_should_move_store_through_trash() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let store: Store = Store::new(None, "Migros".to_owned());
+        let mut repository: StoreRepositoryEventSourcedImpl = given_empty_repository(&directory);
+        repository.create_or_update(&store).await.unwrap();
+
+        assert_eq!(repository.delete(&store.id, DeleteBehaviour::Restrict).await, Ok(store.clone()));
+        assert_eq!(
+            repository.create_or_update(&store).await,
+            Err(StoreRepositoryError::StoreAlreadyExists)
+        );
+        assert_eq!(repository.restore(&store.id).await, Ok(store.clone()));
+        assert_eq!(repository.restore(&store.id).await, Err(StoreRepositoryError::StoreNotInTrash));
+
+        assert_eq!(repository.delete(&store.id, DeleteBehaviour::Restrict).await, Ok(store.clone()));
+        assert_eq!(repository.purge_older_than(Utc::now() + Duration::days(1)).await, Ok(vec![store]));
+        assert_eq!(repository.list_trashed().await, Ok(vec![]));
+    }
+}
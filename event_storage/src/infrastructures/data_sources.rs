@@ -0,0 +1,13 @@
+mod event_documents;
+mod event_store;
+mod events;
+
+pub(crate) use event_documents::*;
+pub use event_documents::{
+    BrandDocument, CategoryDocument, ItemDocument, ProductDocument, StoreDocument, TransactionDocument, UnitDocument,
+};
+pub use event_store::EVENT_STORE_FORMAT_VERSION;
+pub use event_store::EventStore;
+pub use event_store::EventStoreError;
+pub use events::Event;
+pub use events::EventRecord;
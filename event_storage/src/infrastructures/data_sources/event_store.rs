@@ -0,0 +1,656 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    ops::Bound,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use storage_files::infrastructures::files::{
+    DirectoryLock, DirectoryLockError, FORMAT_FILE, format_migrations, paged_stream, read_format_version, sync_directory, write_atomically,
+    write_format_version,
+};
+use storage_migrations::infrastructures::migrations::{MigrationPlan, MigrationRegistry};
+
+use crate::infrastructures::data_sources::{Document, Event, EventLedger, EventRecord};
+
+const LOG_FILE: &str = "events.jsonl";
+const SNAPSHOT_FILE: &str = "snapshot.json";
+const HISTORY_DIRECTORY: &str = "history";
+const DEFAULT_COMPACTION_THRESHOLD: usize = 1000;
+
+/// Version of the log and snapshot layout, recorded in `format.json`. Stores written before it existed are version 0.
+pub const EVENT_STORE_FORMAT_VERSION: u32 = 1;
+
+/// An append-only log of [`Event`]s, one JSON record per line, with the current ledger rebuilt by replaying it.
+///
+/// Once enough events piled up the ledger is compacted into `snapshot.json` and the log segment is moved to
+/// `history/`, so reopening only replays what happened since the last snapshot while the full history stays
+/// available through [`EventStore::history`]. Stores written by an older version are migrated when opened, archived
+/// segments included.
+#[derive(Debug, Clone)]
+pub struct EventStore {
+    root: PathBuf,
+    state: Arc<Mutex<EventStoreState>>,
+    _lock: Arc<DirectoryLock>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventStoreError {
+    UnableToOpen(String),
+    AlreadyLocked,
+    UnableToRead(String),
+    DanglingReference(String),
+    UnableToWrite(String),
+    UnableToMigrate(String),
+}
+
+#[derive(Debug)]
+struct EventStoreState {
+    ledger: EventLedger,
+    log: File,
+    next_sequence: u64,
+    events_since_snapshot: usize,
+    compaction_threshold: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Snapshot {
+    sequence: u64,
+    ledger: EventLedger,
+}
+
+impl EventStore {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, EventStoreError> {
+        let root: PathBuf = path.as_ref().to_path_buf();
+
+        fs::create_dir_all(root.join(HISTORY_DIRECTORY)).map_err(|e| EventStoreError::UnableToOpen(e.to_string()))?;
+
+        let lock: DirectoryLock = DirectoryLock::acquire(&root).map_err(|e| match e {
+            DirectoryLockError::AlreadyLocked => EventStoreError::AlreadyLocked,
+            DirectoryLockError::UnableToLock(details) => EventStoreError::UnableToOpen(details),
+        })?;
+
+        migrate(&root)?;
+
+        let snapshot: Snapshot = read_snapshot(&root)?;
+        let mut ledger: EventLedger = snapshot.ledger;
+        let mut next_sequence: u64 = snapshot.sequence + 1;
+        let mut events_since_snapshot: usize = 0;
+
+        for record in read_log(&root.join(LOG_FILE), true)?
+            .into_iter()
+            .filter(|r| r.sequence > snapshot.sequence)
+        {
+            ledger.apply(&record.event);
+            next_sequence = record.sequence + 1;
+            events_since_snapshot += 1;
+        }
+
+        if let Some(reference) = ledger.dangling_reference() {
+            return Err(EventStoreError::DanglingReference(reference));
+        }
+
+        Ok(Self {
+            state: Arc::new(Mutex::new(EventStoreState {
+                ledger,
+                log: open_log(&root).map_err(|e| EventStoreError::UnableToOpen(e.to_string()))?,
+                next_sequence,
+                events_since_snapshot,
+                compaction_threshold: DEFAULT_COMPACTION_THRESHOLD,
+            })),
+            root,
+            _lock: Arc::new(lock),
+        })
+    }
+
+    /// Compacts automatically once `threshold` events were appended since the last snapshot.
+    pub fn with_compaction_threshold(self, threshold: usize) -> Self {
+        if let Ok(mut state) = self.state.lock() {
+            state.compaction_threshold = threshold.max(1);
+        }
+
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.root
+    }
+
+    /// What opening the store would migrate, without touching it.
+    pub fn migration_plan(path: impl AsRef<Path>) -> Result<MigrationPlan, EventStoreError> {
+        migrations()
+            .plan(format_version(path.as_ref())?.unwrap_or(EVENT_STORE_FORMAT_VERSION))
+            .map_err(|e| EventStoreError::UnableToMigrate(format!("{:?}", e)))
+    }
+
+    /// Every event ever recorded, oldest first, including those already folded into a snapshot.
+    pub fn history(&self) -> Result<Vec<EventRecord>, EventStoreError> {
+        let _state = self.state.lock().map_err(|e| EventStoreError::UnableToRead(e.to_string()))?;
+
+        let segments: Vec<PathBuf> = segment_paths(&self.root)?;
+
+        // A crash between writing the snapshot and rotating the log can leave the same records in two segments.
+        let mut records: Vec<EventRecord> = Vec::new();
+        for segment in segments {
+            for record in read_log(&self.root.join(segment), false)? {
+                if records.last().is_none_or(|last| record.sequence > last.sequence) {
+                    records.push(record);
+                }
+            }
+        }
+
+        Ok(records)
+    }
+
+    /// Folds the ledger into a snapshot and starts a fresh log segment.
+    pub fn compact(&self) -> Result<(), EventStoreError> {
+        let mut state = self.state.lock().map_err(|e| EventStoreError::UnableToWrite(e.to_string()))?;
+
+        self.compact_locked(&mut state)
+    }
+
+    pub(crate) fn read<T>(&self, f: impl FnOnce(&EventLedger) -> T) -> Result<T, EventStoreError> {
+        let state = self.state.lock().map_err(|e| EventStoreError::UnableToRead(e.to_string()))?;
+
+        Ok(f(&state.ledger))
+    }
+
+    /// Streams the active documents of the replayed `collection` through `to_entity`, one [`paged_stream`] page per
+    /// lock of the state.
+    pub(crate) fn stream_active<'a, D, T, E>(
+        &'a self,
+        collection: fn(&EventLedger) -> &BTreeMap<String, D>,
+        to_entity: fn(&EventLedger, &D) -> Option<T>,
+        to_error: fn(EventStoreError) -> E,
+    ) -> BoxStream<'a, Result<T, E>>
+    where
+        D: Document + 'a,
+        T: Send + 'a,
+        E: Send + 'a,
+    {
+        paged_stream(move |after: Option<&str>, limit: usize| {
+            self.read(|ledger| {
+                let after: Bound<&str> = after.map_or(Bound::Unbounded, Bound::Excluded);
+
+                collection(ledger)
+                    .range::<str, _>((after, Bound::Unbounded))
+                    .filter(|(_, document)| document.deleted_at().is_none())
+                    .take(limit)
+                    .map(|(key, document)| (key.clone(), to_entity(ledger, document)))
+                    .collect()
+            })
+            .map_err(to_error)
+        })
+    }
+
+    /// Applies `f` to a copy of the ledger and, when it succeeds, appends the events describing the change before
+    /// making the copy current. A failing `f` leaves both the log and the in-memory ledger untouched.
+    pub(crate) fn write<T, E>(&self, f: impl FnOnce(&mut EventLedger) -> Result<T, E>) -> Result<Result<T, E>, EventStoreError> {
+        let mut state = self.state.lock().map_err(|e| EventStoreError::UnableToWrite(e.to_string()))?;
+        let mut staged: EventLedger = state.ledger.clone();
+
+        let result: Result<T, E> = f(&mut staged);
+
+        if result.is_ok() {
+            let events: Vec<Event> = state.ledger.diff(&staged);
+
+            if events.is_empty() {
+                return Ok(result);
+            }
+
+            append(&mut state, events)?;
+            state.ledger = staged;
+
+            if state.events_since_snapshot >= state.compaction_threshold {
+                self.compact_locked(&mut state)?;
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn compact_locked(&self, state: &mut EventStoreState) -> Result<(), EventStoreError> {
+        let sequence: u64 = state.next_sequence - 1;
+        let snapshot: Snapshot = Snapshot {
+            sequence,
+            ledger: state.ledger.clone(),
+        };
+
+        let mut contents: Vec<u8> = serde_json::to_vec(&snapshot).map_err(|e| EventStoreError::UnableToWrite(e.to_string()))?;
+        contents.push(b'\n');
+        write_atomically(&self.root, SNAPSHOT_FILE, &contents).map_err(|e| EventStoreError::UnableToWrite(e.to_string()))?;
+
+        if state.events_since_snapshot > 0 {
+            let first: u64 = sequence + 1 - state.events_since_snapshot as u64;
+            let segment: PathBuf = self
+                .root
+                .join(HISTORY_DIRECTORY)
+                .join(format!("events-{:020}-{:020}.jsonl", first, sequence));
+
+            fs::rename(self.root.join(LOG_FILE), segment).map_err(|e| EventStoreError::UnableToWrite(e.to_string()))?;
+            state.log = open_log(&self.root).map_err(|e| EventStoreError::UnableToWrite(e.to_string()))?;
+            sync_directory(&self.root).map_err(|e| EventStoreError::UnableToWrite(e.to_string()))?;
+        }
+
+        state.events_since_snapshot = 0;
+        Ok(())
+    }
+}
+
+fn migrations() -> MigrationRegistry {
+    format_migrations(EVENT_STORE_FORMAT_VERSION)
+}
+
+/// The version recorded in `format.json`, a store holding a log or a snapshot without it predates versioning.
+fn format_version(root: &Path) -> Result<Option<u32>, EventStoreError> {
+    read_format_version(root, || root.join(LOG_FILE).exists() || root.join(SNAPSHOT_FILE).exists())
+        .map_err(|e| EventStoreError::UnableToRead(format!("{}: {}", FORMAT_FILE, e)))
+}
+
+/// Upgrades the snapshot and every log segment, handed to the migrations as
+/// `{ "snapshot": ..., "segments": [{ "file": ..., "records": [...] }] }`, then records the current version.
+fn migrate(root: &Path) -> Result<(), EventStoreError> {
+    let format_version: Option<u32> = format_version(root)?;
+
+    if format_version == Some(EVENT_STORE_FORMAT_VERSION) {
+        return Ok(());
+    }
+
+    let migrations: MigrationRegistry = migrations();
+    let from_version: u32 = format_version.unwrap_or(EVENT_STORE_FORMAT_VERSION);
+    let plan: MigrationPlan = migrations
+        .plan(from_version)
+        .map_err(|e| EventStoreError::UnableToMigrate(format!("{:?}", e)))?;
+
+    if !plan.is_empty() {
+        let mut document: Value = json!({
+            "snapshot": read_raw_snapshot(root)?,
+            "segments": segment_paths(root)?
+                .into_iter()
+                .map(|path| Ok(json!({ "file": path, "records": read_raw_log(&root.join(&path))? })))
+                .collect::<Result<Vec<Value>, EventStoreError>>()?,
+        });
+
+        migrations
+            .upgrade(&mut document, from_version)
+            .map_err(|e| EventStoreError::UnableToMigrate(format!("{:?}", e)))?;
+
+        write_raw_document(root, document)?;
+    }
+
+    write_format_version(root, EVENT_STORE_FORMAT_VERSION).map_err(|e| EventStoreError::UnableToWrite(e.to_string()))
+}
+
+/// Archived segments oldest first, then the active log, relative to the root of the store.
+fn segment_paths(root: &Path) -> Result<Vec<PathBuf>, EventStoreError> {
+    let mut segments: Vec<PathBuf> = fs::read_dir(root.join(HISTORY_DIRECTORY))
+        .map_err(|e| EventStoreError::UnableToRead(e.to_string()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "jsonl"))
+        .filter_map(|path| path.strip_prefix(root).ok().map(Path::to_path_buf))
+        .collect();
+    segments.sort();
+    segments.push(PathBuf::from(LOG_FILE));
+
+    Ok(segments)
+}
+
+fn read_raw_snapshot(root: &Path) -> Result<Value, EventStoreError> {
+    match fs::read(root.join(SNAPSHOT_FILE)) {
+        Ok(contents) => serde_json::from_slice(&contents).map_err(|e| EventStoreError::UnableToRead(format!("{}: {}", SNAPSHOT_FILE, e))),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Value::Null),
+        Err(e) => Err(EventStoreError::UnableToRead(format!("{}: {}", SNAPSHOT_FILE, e))),
+    }
+}
+
+/// Reads every complete line of a segment as raw JSON, a torn last line is left for [`read_log`] to repair.
+fn read_raw_log(path: &Path) -> Result<Vec<Value>, EventStoreError> {
+    let contents: String = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(EventStoreError::UnableToRead(e.to_string())),
+    };
+
+    contents
+        .split_inclusive('\n')
+        .filter(|line| line.ends_with('\n'))
+        .map(|line| serde_json::from_str(line).map_err(|e| EventStoreError::UnableToRead(format!("{}: {}", path.display(), e))))
+        .collect()
+}
+
+fn write_raw_document(root: &Path, mut document: Value) -> Result<(), EventStoreError> {
+    let snapshot: Value = document["snapshot"].take();
+
+    if !snapshot.is_null() {
+        let mut contents: Vec<u8> = serde_json::to_vec(&snapshot).map_err(|e| EventStoreError::UnableToWrite(e.to_string()))?;
+        contents.push(b'\n');
+        write_atomically(root, SNAPSHOT_FILE, &contents).map_err(|e| EventStoreError::UnableToWrite(e.to_string()))?;
+    }
+
+    for segment in document["segments"].as_array().into_iter().flatten() {
+        let path: PathBuf = root.join(segment["file"].as_str().unwrap_or(LOG_FILE));
+        let mut contents: Vec<u8> = Vec::new();
+
+        for record in segment["records"].as_array().into_iter().flatten() {
+            serde_json::to_writer(&mut contents, record).map_err(|e| EventStoreError::UnableToWrite(e.to_string()))?;
+            contents.push(b'\n');
+        }
+
+        let (directory, file_name): (&Path, String) = match (path.parent(), path.file_name()) {
+            (Some(directory), Some(file_name)) => (directory, file_name.to_string_lossy().into_owned()),
+            _ => return Err(EventStoreError::UnableToWrite(format!("{}: not a file", path.display()))),
+        };
+
+        if !contents.is_empty() || path.exists() {
+            write_atomically(directory, &file_name, &contents).map_err(|e| EventStoreError::UnableToWrite(e.to_string()))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn append(state: &mut EventStoreState, events: Vec<Event>) -> Result<(), EventStoreError> {
+    let recorded_at: DateTime<Utc> = Utc::now();
+    let mut lines: Vec<u8> = Vec::new();
+
+    for (offset, event) in events.into_iter().enumerate() {
+        let record: EventRecord = EventRecord {
+            sequence: state.next_sequence + offset as u64,
+            recorded_at,
+            event,
+        };
+
+        serde_json::to_writer(&mut lines, &record).map_err(|e| EventStoreError::UnableToWrite(e.to_string()))?;
+        lines.push(b'\n');
+        state.events_since_snapshot += 1;
+    }
+
+    state
+        .log
+        .write_all(&lines)
+        .map_err(|e| EventStoreError::UnableToWrite(e.to_string()))?;
+    state.log.sync_data().map_err(|e| EventStoreError::UnableToWrite(e.to_string()))?;
+    state.next_sequence += lines.iter().filter(|b| **b == b'\n').count() as u64;
+
+    Ok(())
+}
+
+fn read_snapshot(root: &Path) -> Result<Snapshot, EventStoreError> {
+    match fs::read_to_string(root.join(SNAPSHOT_FILE)) {
+        Ok(contents) => serde_json::from_str(&contents).map_err(|e| EventStoreError::UnableToRead(format!("{}: {}", SNAPSHOT_FILE, e))),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Snapshot {
+            sequence: 0,
+            ledger: EventLedger::default(),
+        }),
+        Err(e) => Err(EventStoreError::UnableToRead(format!("{}: {}", SNAPSHOT_FILE, e))),
+    }
+}
+
+/// Reads a log segment. A torn last line, left behind by a crash in the middle of an append, is dropped from the
+/// file when `repair` is set and ignored otherwise; anything else that fails to parse is an error.
+fn read_log(path: &Path, repair: bool) -> Result<Vec<EventRecord>, EventStoreError> {
+    let contents: String = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(EventStoreError::UnableToRead(e.to_string())),
+    };
+
+    let mut records: Vec<EventRecord> = Vec::new();
+    let mut valid_length: usize = 0;
+
+    for line in contents.split_inclusive('\n') {
+        match serde_json::from_str::<EventRecord>(line) {
+            Ok(record) if line.ends_with('\n') => {
+                records.push(record);
+                valid_length += line.len();
+            }
+            _ if valid_length + line.len() == contents.len() => break,
+            Err(e) => return Err(EventStoreError::UnableToRead(format!("{}: {}", path.display(), e))),
+            Ok(_) => unreachable!("only the last line can lack a line break"),
+        }
+    }
+
+    if repair && valid_length < contents.len() {
+        OpenOptions::new()
+            .write(true)
+            .open(path)
+            .and_then(|file| file.set_len(valid_length as u64))
+            .map_err(|e| EventStoreError::UnableToWrite(e.to_string()))?;
+    }
+
+    Ok(records)
+}
+
+fn open_log(root: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(root.join(LOG_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, io::Write};
+
+    use chrono::Utc;
+    use futures::{StreamExt, stream::BoxStream};
+
+    use storage_files::infrastructures::files::FORMAT_FILE;
+
+    use super::{EVENT_STORE_FORMAT_VERSION, EventStore, EventStoreError, HISTORY_DIRECTORY, LOG_FILE};
+    use crate::infrastructures::data_sources::{BrandDocument, Event, EventRecord};
+
+    fn insert_brand(store: &EventStore, name: &str) {
+        store
+            .write(|ledger| {
+                ledger.brands.insert(
+                    name.to_owned(),
+                    BrandDocument {
+                        name: name.to_owned(),
+                        version: 0,
+                        deleted_at: None,
+                    },
+                );
+                Ok::<(), ()>(())
+            })
+            .unwrap()
+            .unwrap();
+    }
+
+    fn given_fixture(version: u32) -> tempfile::TempDir {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let fixture: std::path::PathBuf =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("fixtures/event_store/v{}", version));
+
+        copy_directory(&fixture, directory.path());
+
+        directory
+    }
+
+    fn copy_directory(from: &std::path::Path, to: &std::path::Path) {
+        for entry in fs::read_dir(from).unwrap() {
+            let entry: fs::DirEntry = entry.unwrap();
+
+            if entry.file_type().unwrap().is_dir() {
+                fs::create_dir_all(to.join(entry.file_name())).unwrap();
+                copy_directory(&entry.path(), &to.join(entry.file_name()));
+            } else {
+                fs::copy(entry.path(), to.join(entry.file_name())).unwrap();
+            }
+        }
+    }
+
+    fn brand_names(store: &EventStore) -> Vec<String> {
+        store.read(|ledger| ledger.brands.keys().cloned().collect()).unwrap()
+    }
+
+    #[test]
+    fn open_should_refuse_a_directory_locked_by_another_writer() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let _writer: EventStore = EventStore::open(directory.path()).unwrap();
+
+        let result: Result<EventStore, EventStoreError> = EventStore::open(directory.path());
+
+        assert!(
+            matches!(result, Err(EventStoreError::AlreadyLocked)),
+            "Expected AlreadyLocked, but got {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn open_should_replay_the_log() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let store: EventStore = EventStore::open(directory.path()).unwrap();
+        insert_brand(&store, "Nestlé");
+        insert_brand(&store, "Danone");
+        drop(store);
+
+        let result: Vec<String> = brand_names(&EventStore::open(directory.path()).unwrap());
+        let expected: Vec<String> = vec!["Danone".to_owned(), "Nestlé".to_owned()];
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn stream_active_should_page_through_documents_written_while_streaming() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let store: EventStore = EventStore::open(directory.path()).unwrap();
+        for i in 0..250 {
+            insert_brand(&store, &format!("Brand {:03}", i));
+        }
+
+        let mut stream: BoxStream<'_, Result<String, EventStoreError>> =
+            store.stream_active(|ledger| &ledger.brands, |_, brand| Some(brand.name.clone()), |e| e);
+        let first: String = stream.next().await.unwrap().unwrap();
+        store
+            .write(|ledger| {
+                ledger.brands.get_mut("Brand 200").unwrap().deleted_at = Some(Utc::now());
+                Ok::<(), ()>(())
+            })
+            .unwrap()
+            .unwrap();
+        insert_brand(&store, "Brand 999");
+        let rest: Vec<Result<String, EventStoreError>> = stream.collect().await;
+
+        let result: Result<Vec<String>, EventStoreError> = std::iter::once(Ok(first)).chain(rest).collect();
+        let expected: Result<Vec<String>, EventStoreError> = Ok((0..250)
+            .filter(|i| *i != 200)
+            .map(|i| format!("Brand {:03}", i))
+            .chain(std::iter::once("Brand 999".to_owned()))
+            .collect());
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[test]
+    fn write_should_not_append_anything_when_the_change_fails_or_is_empty() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let store: EventStore = EventStore::open(directory.path()).unwrap();
+
+        let result: Result<Result<(), &str>, EventStoreError> = store.write(|ledger| {
+            ledger.brands.clear();
+            Err("rejected")
+        });
+        store.write(|_| Ok::<(), ()>(())).unwrap().unwrap();
+
+        assert_eq!(result, Ok(Err("rejected")));
+        assert_eq!(store.history(), Ok(vec![]));
+    }
+
+    #[test]
+    fn compaction_should_move_the_log_to_history_and_reopen_from_the_snapshot() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let store: EventStore = EventStore::open(directory.path()).unwrap().with_compaction_threshold(2);
+        insert_brand(&store, "Nestlé");
+        insert_brand(&store, "Danone");
+        insert_brand(&store, "Lindt");
+
+        assert_eq!(fs::read_dir(directory.path().join(HISTORY_DIRECTORY)).unwrap().count(), 1);
+        assert_eq!(fs::read_to_string(directory.path().join(LOG_FILE)).unwrap().lines().count(), 1);
+
+        let history: Vec<(u64, Event)> = store.history().unwrap().into_iter().map(|r| (r.sequence, r.event)).collect();
+        assert_eq!(history.iter().map(|(sequence, _)| *sequence).collect::<Vec<u64>>(), vec![1, 2, 3]);
+        assert!(matches!(&history[2].1, Event::BrandCreated(brand) if brand.name == "Lindt"));
+
+        drop(store);
+        let result: Vec<String> = brand_names(&EventStore::open(directory.path()).unwrap());
+        let expected: Vec<String> = vec!["Danone".to_owned(), "Lindt".to_owned(), "Nestlé".to_owned()];
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[test]
+    fn open_should_drop_a_torn_last_line() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let store: EventStore = EventStore::open(directory.path()).unwrap();
+        insert_brand(&store, "Nestlé");
+        drop(store);
+
+        let mut log: fs::File = fs::OpenOptions::new().append(true).open(directory.path().join(LOG_FILE)).unwrap();
+        log.write_all(b"{\"sequence\":2,\"recorded_at\":").unwrap();
+        drop(log);
+
+        let reopened: EventStore = EventStore::open(directory.path()).unwrap();
+        insert_brand(&reopened, "Danone");
+
+        let result: Vec<u64> = reopened.history().unwrap().iter().map(|r: &EventRecord| r.sequence).collect();
+
+        assert_eq!(result, vec![1, 2], "Expected {:?}, but got {:?}", vec![1, 2], result);
+    }
+
+    #[test]
+    fn open_should_migrate_every_historical_fixture() {
+        for version in 0..=EVENT_STORE_FORMAT_VERSION {
+            let directory: tempfile::TempDir = given_fixture(version);
+
+            let plan: usize = EventStore::migration_plan(directory.path()).unwrap().steps.len();
+            assert_eq!(
+                plan,
+                (EVENT_STORE_FORMAT_VERSION - version) as usize,
+                "Unexpected plan for v{}",
+                version
+            );
+
+            let store: EventStore = EventStore::open(directory.path()).unwrap();
+
+            let result: (usize, usize, usize) = store
+                .read(|ledger| {
+                    (
+                        ledger.brands.len(),
+                        ledger.products.len(),
+                        ledger.transactions.values().map(|t| t.items.len()).sum(),
+                    )
+                })
+                .unwrap();
+            let expected: (usize, usize, usize) = (1, 1, 2);
+
+            assert_eq!(result, expected, "Expected {:?} for v{}, but got {:?}", expected, version, result);
+
+            let sequences: Vec<u64> = store.history().unwrap().iter().map(|r: &EventRecord| r.sequence).collect();
+            assert_eq!(sequences, vec![1, 2, 3, 4, 5], "Unexpected history for v{}", version);
+            assert!(EventStore::migration_plan(directory.path()).unwrap().is_empty());
+            assert!(
+                fs::read_to_string(directory.path().join(FORMAT_FILE))
+                    .unwrap()
+                    .contains(&format!("\"format_version\": {}", EVENT_STORE_FORMAT_VERSION))
+            );
+        }
+    }
+
+    #[test]
+    fn open_should_refuse_a_directory_from_a_newer_version() {
+        let directory: tempfile::TempDir = given_fixture(EVENT_STORE_FORMAT_VERSION);
+        fs::write(directory.path().join(FORMAT_FILE), "{ \"format_version\": 99 }").unwrap();
+
+        let result: Result<EventStore, EventStoreError> = EventStore::open(directory.path());
+
+        assert!(
+            matches!(result, Err(EventStoreError::UnableToMigrate(_))),
+            "Expected UnableToMigrate, but got {:?}",
+            result
+        );
+    }
+}
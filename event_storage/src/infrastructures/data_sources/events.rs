@@ -0,0 +1,220 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::infrastructures::data_sources::{
+    BrandDocument, CategoryDocument, Document, EventLedger, ProductDocument, StoreDocument, TransactionDocument,
+};
+
+/// A single change to the ledger, carrying the full document it leaves behind so that replaying never has to
+/// re-run any repository logic.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum Event {
+    BrandCreated(BrandDocument),
+    BrandUpdated(BrandDocument),
+    BrandDeleted(BrandDocument),
+    BrandRestored(BrandDocument),
+    BrandPurged(String),
+    CategoryCreated(CategoryDocument),
+    CategoryUpdated(CategoryDocument),
+    CategoryDeleted(CategoryDocument),
+    CategoryRestored(CategoryDocument),
+    CategoryPurged(String),
+    StoreCreated(StoreDocument),
+    StoreUpdated(StoreDocument),
+    StoreDeleted(StoreDocument),
+    StoreRestored(StoreDocument),
+    StorePurged(String),
+    ProductCreated(ProductDocument),
+    ProductUpdated(ProductDocument),
+    ProductDeleted(ProductDocument),
+    ProductRestored(ProductDocument),
+    ProductPurged(String),
+    TransactionCreated(TransactionDocument),
+    TransactionUpdated(TransactionDocument),
+    TransactionDeleted(TransactionDocument),
+    TransactionRestored(TransactionDocument),
+    TransactionPurged(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventRecord {
+    pub sequence: u64,
+    pub recorded_at: DateTime<Utc>,
+    pub event: Event,
+}
+
+enum Change<D> {
+    Created(D),
+    Updated(D),
+    Deleted(D),
+    Restored(D),
+    Purged(String),
+}
+
+impl EventLedger {
+    /// Lists the events that turn `self` into `staged`, parents before children so the log reads naturally.
+    pub(crate) fn diff(&self, staged: &EventLedger) -> Vec<Event> {
+        let mut events: Vec<Event> = Vec::new();
+
+        diff_documents(&self.brands, &staged.brands, &mut events, |change| match change {
+            Change::Created(document) => Event::BrandCreated(document),
+            Change::Updated(document) => Event::BrandUpdated(document),
+            Change::Deleted(document) => Event::BrandDeleted(document),
+            Change::Restored(document) => Event::BrandRestored(document),
+            Change::Purged(key) => Event::BrandPurged(key),
+        });
+        diff_documents(&self.categories, &staged.categories, &mut events, |change| match change {
+            Change::Created(document) => Event::CategoryCreated(document),
+            Change::Updated(document) => Event::CategoryUpdated(document),
+            Change::Deleted(document) => Event::CategoryDeleted(document),
+            Change::Restored(document) => Event::CategoryRestored(document),
+            Change::Purged(key) => Event::CategoryPurged(key),
+        });
+        diff_documents(&self.stores, &staged.stores, &mut events, |change| match change {
+            Change::Created(document) => Event::StoreCreated(document),
+            Change::Updated(document) => Event::StoreUpdated(document),
+            Change::Deleted(document) => Event::StoreDeleted(document),
+            Change::Restored(document) => Event::StoreRestored(document),
+            Change::Purged(key) => Event::StorePurged(key),
+        });
+        diff_documents(&self.products, &staged.products, &mut events, |change| match change {
+            Change::Created(document) => Event::ProductCreated(document),
+            Change::Updated(document) => Event::ProductUpdated(document),
+            Change::Deleted(document) => Event::ProductDeleted(document),
+            Change::Restored(document) => Event::ProductRestored(document),
+            Change::Purged(key) => Event::ProductPurged(key),
+        });
+        diff_documents(&self.transactions, &staged.transactions, &mut events, |change| match change {
+            Change::Created(document) => Event::TransactionCreated(document),
+            Change::Updated(document) => Event::TransactionUpdated(document),
+            Change::Deleted(document) => Event::TransactionDeleted(document),
+            Change::Restored(document) => Event::TransactionRestored(document),
+            Change::Purged(key) => Event::TransactionPurged(key),
+        });
+
+        events
+    }
+
+    pub(crate) fn apply(&mut self, event: &Event) {
+        match event {
+            Event::BrandCreated(document)
+            | Event::BrandUpdated(document)
+            | Event::BrandDeleted(document)
+            | Event::BrandRestored(document) => {
+                self.brands.insert(document.key(), document.clone());
+            }
+            Event::BrandPurged(key) => {
+                self.brands.remove(key);
+            }
+            Event::CategoryCreated(document)
+            | Event::CategoryUpdated(document)
+            | Event::CategoryDeleted(document)
+            | Event::CategoryRestored(document) => {
+                self.categories.insert(document.key(), document.clone());
+            }
+            Event::CategoryPurged(key) => {
+                self.categories.remove(key);
+            }
+            Event::StoreCreated(document)
+            | Event::StoreUpdated(document)
+            | Event::StoreDeleted(document)
+            | Event::StoreRestored(document) => {
+                self.stores.insert(document.key(), document.clone());
+            }
+            Event::StorePurged(key) => {
+                self.stores.remove(key);
+            }
+            Event::ProductCreated(document)
+            | Event::ProductUpdated(document)
+            | Event::ProductDeleted(document)
+            | Event::ProductRestored(document) => {
+                self.products.insert(document.key(), document.clone());
+            }
+            Event::ProductPurged(key) => {
+                self.products.remove(key);
+            }
+            Event::TransactionCreated(document)
+            | Event::TransactionUpdated(document)
+            | Event::TransactionDeleted(document)
+            | Event::TransactionRestored(document) => {
+                self.transactions.insert(document.key(), document.clone());
+            }
+            Event::TransactionPurged(key) => {
+                self.transactions.remove(key);
+            }
+        }
+    }
+}
+
+fn diff_documents<D: Document>(
+    current: &BTreeMap<String, D>,
+    staged: &BTreeMap<String, D>,
+    events: &mut Vec<Event>,
+    to_event: impl Fn(Change<D>) -> Event,
+) {
+    for (key, document) in staged {
+        let change: Option<Change<D>> = match current.get(key) {
+            None => Some(Change::Created(document.clone())),
+            Some(previous) if previous == document => None,
+            Some(previous) => match (previous.deleted_at(), document.deleted_at()) {
+                (None, Some(_)) => Some(Change::Deleted(document.clone())),
+                (Some(_), None) => Some(Change::Restored(document.clone())),
+                _ => Some(Change::Updated(document.clone())),
+            },
+        };
+
+        events.extend(change.map(&to_event));
+    }
+
+    events.extend(
+        current
+            .keys()
+            .filter(|key| !staged.contains_key(*key))
+            .map(|key| to_event(Change::Purged(key.clone()))),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::Event;
+    use crate::infrastructures::data_sources::{BrandDocument, EventLedger};
+
+    fn brand_document(name: &str) -> BrandDocument {
+        BrandDocument {
+            name: name.to_owned(),
+            version: 0,
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn diff_should_describe_every_kind_of_change_and_apply_should_replay_it() {
+        let mut current: EventLedger = EventLedger::default();
+        for name in ["Danone", "Migros", "Nestlé"] {
+            current.brands.insert(name.to_owned(), brand_document(name));
+        }
+
+        let mut staged: EventLedger = current.clone();
+        staged.brands.remove("Danone");
+        staged.brands.insert("Lindt".to_owned(), brand_document("Lindt"));
+        staged.brands.get_mut("Migros").unwrap().deleted_at = Some(Utc::now());
+        staged.brands.get_mut("Nestlé").unwrap().version = 1;
+
+        let result: Vec<Event> = current.diff(&staged);
+        let expected: Vec<Event> = vec![
+            Event::BrandCreated(staged.brands["Lindt"].clone()),
+            Event::BrandDeleted(staged.brands["Migros"].clone()),
+            Event::BrandUpdated(staged.brands["Nestlé"].clone()),
+            Event::BrandPurged("Danone".to_owned()),
+        ];
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+
+        result.iter().for_each(|event| current.apply(event));
+        assert_eq!(current, staged);
+    }
+}
@@ -1,3 +1,5 @@
 pub mod entities;
+pub mod reports;
 pub mod repositories;
+pub mod search;
 pub mod use_cases;
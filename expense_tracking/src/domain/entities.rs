@@ -1,6 +1,7 @@
 mod brand;
 mod category;
 mod item;
+mod name_policy;
 mod product;
 mod store;
 mod transaction;
@@ -9,7 +10,8 @@ mod unit;
 pub use brand::Brand;
 pub use category::Category;
 pub use item::Item;
+pub use name_policy::NamePolicy;
 pub use product::Product;
 pub use store::Store;
 pub use transaction::Transaction;
-use unit::Unit;
+pub use unit::Unit;
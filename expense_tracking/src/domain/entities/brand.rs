@@ -1,10 +1,11 @@
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct Brand {
     pub name: String,
+    pub version: u64,
 }
 
 impl Brand {
     pub fn new(name: String) -> Self {
-        Self { name }
+        Self { name, version: 0 }
     }
 }
@@ -22,6 +22,22 @@ impl Item {
         }
     }
 
+    pub fn id(&self) -> UuidB64 {
+        self.id
+    }
+
+    pub fn product(&self) -> &Product {
+        &self.product
+    }
+
+    pub fn unit(&self) -> &Unit {
+        &self.unit
+    }
+
+    pub fn unitary_price(&self) -> f64 {
+        self.unitary_price
+    }
+
     pub fn calculate_full_price(&self) -> f64 {
         match self.unit {
             Unit::None => self.unitary_price,
@@ -0,0 +1,85 @@
+use unicode_normalization::{UnicodeNormalization, char::is_combining_mark};
+
+/// Decides when two names designate the same entity. Names are compared once composed (NFC), trimmed and case
+/// folded, and optionally stripped of their accents, while the entity keeps the spelling it was created with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NamePolicy {
+    fold_accents: bool,
+}
+
+impl Default for NamePolicy {
+    fn default() -> Self {
+        Self { fold_accents: true }
+    }
+}
+
+impl NamePolicy {
+    pub fn new(fold_accents: bool) -> Self {
+        Self { fold_accents }
+    }
+
+    pub fn normalize(&self, name: &str) -> String {
+        let folded: String = name
+            .trim()
+            .nfc()
+            .flat_map(char::to_lowercase)
+            .map(|c| if c == 'ß' { "ss".to_owned() } else { c.to_string() })
+            .collect();
+
+        match self.fold_accents {
+            true => folded.nfd().filter(|c| !is_combining_mark(*c)).nfc().collect(),
+            false => folded.nfc().collect(),
+        }
+    }
+
+    pub fn matches(&self, left: &str, right: &str) -> bool {
+        self.normalize(left) == self.normalize(right)
+    }
+
+    /// The first of `candidates` designating the same entity as `name`.
+    pub fn find<'a>(&self, name: &str, candidates: impl IntoIterator<Item = &'a String>) -> Option<&'a String> {
+        let normalized: String = self.normalize(name);
+
+        candidates.into_iter().find(|candidate| self.normalize(candidate) == normalized)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NamePolicy;
+
+    macro_rules! normalize {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (policy, input, expected): (NamePolicy, &str, &str) = $value;
+
+                let result: String = policy.normalize(input);
+
+                assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+            }
+        )*
+        }
+    }
+
+    normalize! {
+        normalize_should_trim: (NamePolicy::default(), "  Migros \t", "migros"),
+        normalize_should_case_fold: (NamePolicy::default(), "STRASSE Straße", "strasse strasse"),
+        normalize_should_fold_accents: (NamePolicy::default(), "Nestlé", "nestle"),
+        normalize_should_keep_accents_when_not_folding: (NamePolicy::new(false), "NESTLÉ", "nestlé"),
+        normalize_should_compose_decomposed_accents: (NamePolicy::new(false), "Nestle\u{301}", "nestl\u{e9}"),
+    }
+
+    #[test]
+    fn find_should_return_the_stored_spelling() {
+        let policy: NamePolicy = NamePolicy::default();
+        let candidates: Vec<String> = vec!["Migros".to_owned(), "Nestlé".to_owned()];
+
+        let result: Option<&String> = policy.find("nestle ", &candidates);
+        let expected: Option<&String> = Some(&candidates[1]);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(NamePolicy::new(false).find("nestle", &candidates), None);
+    }
+}
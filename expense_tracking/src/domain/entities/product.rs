@@ -10,6 +10,7 @@ pub struct Product {
     pub name: String,
     pub brand: Brand,
     pub category: Category,
+    pub version: u64,
 }
 
 impl Product {
@@ -19,6 +20,7 @@ impl Product {
             name,
             brand,
             category,
+            version: 0,
         }
     }
 }
@@ -5,6 +5,7 @@ use uuid_b64::UuidB64;
 pub struct Store {
     pub id: UuidB64,
     pub name: String,
+    pub version: u64,
 }
 
 impl Store {
@@ -12,6 +13,7 @@ impl Store {
         Self {
             id: id.unwrap_or_else(|| UuidB64::from(Uuid::new_v4())),
             name,
+            version: 0,
         }
     }
 }
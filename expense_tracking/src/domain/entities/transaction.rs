@@ -13,20 +13,17 @@ pub struct Transaction {
     pub items: Vec<Item>,
     pub store: Store,
     pub datetime: DateTime<Utc>,
+    pub version: u64,
 }
 
 impl Transaction {
-    pub fn new(
-        id: Option<UuidB64>,
-        items: Vec<Item>,
-        store: Store,
-        datetime: DateTime<Utc>,
-    ) -> Self {
+    pub fn new(id: Option<UuidB64>, items: Vec<Item>, store: Store, datetime: DateTime<Utc>) -> Self {
         Self {
             id: id.unwrap_or_else(|| UuidB64::from(Uuid::new_v4())),
             items,
             store,
             datetime,
+            version: 0,
         }
     }
 
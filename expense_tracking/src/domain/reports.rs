@@ -0,0 +1,21 @@
+mod price_history;
+mod price_index;
+mod spending_report;
+mod time_bucket;
+
+pub use price_history::PriceHistory;
+pub use price_history::PriceHistoryBuilder;
+pub use price_history::PriceMeasure;
+pub use price_history::PriceObservation;
+pub use price_index::CategoryIndex;
+pub use price_index::IndexFormula;
+pub use price_index::IndexPoint;
+pub use price_index::IndexSeries;
+pub use price_index::InflationIndex;
+pub use price_index::InflationIndexBuilder;
+pub use spending_report::GroupKey;
+pub use spending_report::ReportDimension;
+pub use spending_report::SpendingGroup;
+pub use spending_report::SpendingReport;
+pub use spending_report::SpendingReportBuilder;
+pub use time_bucket::TimeBucket;
@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use uuid_b64::UuidB64;
+
+use crate::domain::entities::{Product, Store, Transaction, Unit};
+
+/// What a unitary price is paid for. Prices are only comparable within the same measure, an item bought by the piece
+/// and the same product bought by weight make two histories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PriceMeasure {
+    Piece,
+    Kilogram,
+    Liter,
+}
+
+impl From<&Unit> for PriceMeasure {
+    fn from(value: &Unit) -> Self {
+        match value {
+            Unit::None | Unit::Quantity(_) => PriceMeasure::Piece,
+            Unit::Kilograms(_) => PriceMeasure::Kilogram,
+            Unit::Liters(_) => PriceMeasure::Liter,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceObservation {
+    pub datetime: DateTime<Utc>,
+    pub store: Store,
+    pub price: f64,
+}
+
+/// The prices a product was bought at, oldest first, along with the latest price seen at each store, cheapest first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceHistory {
+    pub product: Product,
+    pub measure: PriceMeasure,
+    pub observations: Vec<PriceObservation>,
+    pub latest_per_store: Vec<PriceObservation>,
+}
+
+impl PriceHistory {
+    /// Gathers a history per product and measure from the items of `transactions`, restricted to `product` and
+    /// `store` when given. Histories are ordered by product name.
+    pub fn build(transactions: &[Transaction], product: Option<&UuidB64>, store: Option<&UuidB64>) -> Vec<Self> {
+        let mut builder: PriceHistoryBuilder = PriceHistoryBuilder::new(product.copied(), store.copied());
+        transactions.iter().for_each(|transaction| builder.add(transaction));
+
+        builder.build()
+    }
+
+    pub fn min(&self) -> Option<&PriceObservation> {
+        self.observations.iter().min_by(|a, b| a.price.total_cmp(&b.price))
+    }
+
+    pub fn max(&self) -> Option<&PriceObservation> {
+        self.observations.iter().max_by(|a, b| a.price.total_cmp(&b.price))
+    }
+
+    pub fn last(&self) -> Option<&PriceObservation> {
+        self.observations.last()
+    }
+
+    /// The store whose latest price is the lowest.
+    pub fn cheapest_store(&self) -> Option<&Store> {
+        self.latest_per_store.first().map(|o| &o.store)
+    }
+}
+
+/// Builds [`PriceHistory`]s a transaction at a time, so that transactions can be streamed into the histories instead of
+/// being loaded all at once.
+#[derive(Debug, Clone)]
+pub struct PriceHistoryBuilder {
+    product: Option<UuidB64>,
+    store: Option<UuidB64>,
+    series: HashMap<(UuidB64, PriceMeasure), (Product, Vec<PriceObservation>)>,
+}
+
+impl PriceHistoryBuilder {
+    /// Histories restricted to `product` and `store` when given.
+    pub fn new(product: Option<UuidB64>, store: Option<UuidB64>) -> Self {
+        Self {
+            product,
+            store,
+            series: HashMap::new(),
+        }
+    }
+
+    /// Records the price of every item of `transaction` in the history of its product and measure.
+    pub fn add(&mut self, transaction: &Transaction) {
+        if self.store.is_some_and(|store| transaction.store.id != store) {
+            return;
+        }
+
+        for item in transaction
+            .items
+            .iter()
+            .filter(|i| self.product.is_none_or(|p| i.product().id == p))
+        {
+            let (_, observations) = self
+                .series
+                .entry((item.product().id, PriceMeasure::from(item.unit())))
+                .or_insert_with(|| (item.product().clone(), vec![]));
+
+            observations.push(PriceObservation {
+                datetime: transaction.datetime,
+                store: transaction.store.clone(),
+                price: item.unitary_price(),
+            });
+        }
+    }
+
+    /// Histories are ordered by product name.
+    pub fn build(self) -> Vec<PriceHistory> {
+        let mut histories: Vec<PriceHistory> = self
+            .series
+            .into_iter()
+            .map(|((_, measure), (product, mut observations))| {
+                observations.sort_by_key(|o| o.datetime);
+
+                let mut latest: HashMap<UuidB64, &PriceObservation> = HashMap::new();
+                for observation in &observations {
+                    latest.insert(observation.store.id, observation);
+                }
+                let mut latest_per_store: Vec<PriceObservation> = latest.into_values().cloned().collect();
+                latest_per_store.sort_by(|a, b| a.price.total_cmp(&b.price).then(b.datetime.cmp(&a.datetime)));
+
+                PriceHistory {
+                    product,
+                    measure,
+                    observations,
+                    latest_per_store,
+                }
+            })
+            .collect();
+        histories.sort_by(|a, b| {
+            a.product
+                .name
+                .cmp(&b.product.name)
+                .then(a.product.id.cmp(&b.product.id))
+                .then(a.measure.cmp(&b.measure))
+        });
+
+        histories
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+
+    use crate::domain::{
+        entities::{Brand, Category, Item, Product, Store, Transaction, Unit},
+        reports::{PriceHistory, PriceMeasure},
+    };
+
+    struct Ledger {
+        migros: Store,
+        coop: Store,
+        apples: Product,
+        milk: Product,
+        transactions: Vec<Transaction>,
+    }
+
+    fn at(datetime: &str) -> DateTime<Utc> {
+        datetime.parse().unwrap()
+    }
+
+    fn given_ledger() -> Ledger {
+        let migros: Store = Store::new(None, "Migros".to_owned());
+        let coop: Store = Store::new(None, "Coop".to_owned());
+        let apples: Product = Product::new(
+            None,
+            "Apples".to_owned(),
+            Brand::new("Migros Bio".to_owned()),
+            Category::new(None, "Fruits".to_owned()),
+        );
+        let milk: Product = Product::new(
+            None,
+            "Milk".to_owned(),
+            Brand::new("Emmi".to_owned()),
+            Category::new(None, "Dairy".to_owned()),
+        );
+
+        let purchase = |store: &Store, datetime: &str, items: Vec<Item>| Transaction::new(None, items, store.clone(), at(datetime));
+        let transactions: Vec<Transaction> = vec![
+            purchase(
+                &coop,
+                "2024-03-12T10:00:00Z",
+                vec![Item::new(None, apples.clone(), Unit::Kilograms(1.), 4.1)],
+            ),
+            purchase(
+                &migros,
+                "2024-03-04T10:00:00Z",
+                vec![
+                    Item::new(None, apples.clone(), Unit::Kilograms(1.5), 4.2),
+                    Item::new(None, milk.clone(), Unit::Liters(1.), 1.6),
+                ],
+            ),
+            purchase(
+                &migros,
+                "2024-04-02T09:00:00Z",
+                vec![Item::new(None, apples.clone(), Unit::Kilograms(2.), 3.9)],
+            ),
+            purchase(
+                &coop,
+                "2024-04-20T17:00:00Z",
+                vec![Item::new(None, apples.clone(), Unit::Kilograms(1.), 4.4)],
+            ),
+            purchase(
+                &coop,
+                "2024-04-21T17:00:00Z",
+                vec![Item::new(None, apples.clone(), Unit::Quantity(6.), 0.7)],
+            ),
+        ];
+
+        Ledger {
+            migros,
+            coop,
+            apples,
+            milk,
+            transactions,
+        }
+    }
+
+    #[test]
+    fn build_should_split_histories_per_product_and_measure() {
+        let ledger: Ledger = given_ledger();
+
+        let result: Vec<(String, PriceMeasure, usize)> = PriceHistory::build(&ledger.transactions, None, None)
+            .iter()
+            .map(|h| (h.product.name.clone(), h.measure, h.observations.len()))
+            .collect();
+        let expected: Vec<(String, PriceMeasure, usize)> = vec![
+            ("Apples".to_owned(), PriceMeasure::Piece, 1),
+            ("Apples".to_owned(), PriceMeasure::Kilogram, 4),
+            ("Milk".to_owned(), PriceMeasure::Liter, 1),
+        ];
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[test]
+    fn build_should_order_observations_and_summarize_prices() {
+        let ledger: Ledger = given_ledger();
+
+        let histories: Vec<PriceHistory> = PriceHistory::build(&ledger.transactions, Some(&ledger.apples.id), None);
+        let history: &PriceHistory = histories.iter().find(|h| h.measure == PriceMeasure::Kilogram).unwrap();
+        let prices: Vec<f64> = history.observations.iter().map(|o| o.price).collect();
+
+        assert_eq!(prices, vec![4.2, 4.1, 3.9, 4.4], "Expected prices by date, but got {:?}", prices);
+        assert_eq!(history.min().map(|o| o.price), Some(3.9));
+        assert_eq!(history.max().map(|o| o.price), Some(4.4));
+        assert_eq!(history.last().map(|o| &o.store), Some(&ledger.coop));
+    }
+
+    #[test]
+    fn build_should_find_the_store_currently_cheapest() {
+        let ledger: Ledger = given_ledger();
+
+        let histories: Vec<PriceHistory> = PriceHistory::build(&ledger.transactions, Some(&ledger.apples.id), None);
+        let history: &PriceHistory = histories.iter().find(|h| h.measure == PriceMeasure::Kilogram).unwrap();
+        let latest: Vec<(String, f64)> = history.latest_per_store.iter().map(|o| (o.store.name.clone(), o.price)).collect();
+        let expected: Vec<(String, f64)> = vec![("Migros".to_owned(), 3.9), ("Coop".to_owned(), 4.4)];
+
+        assert_eq!(latest, expected, "Expected {:?}, but got {:?}", expected, latest);
+        assert_eq!(history.cheapest_store(), Some(&ledger.migros));
+    }
+
+    #[test]
+    fn build_should_restrict_to_a_store() {
+        let ledger: Ledger = given_ledger();
+
+        let result: Vec<PriceHistory> = PriceHistory::build(&ledger.transactions, Some(&ledger.milk.id), Some(&ledger.coop.id));
+
+        assert!(result.is_empty(), "Expected no history, but got {:?}", result);
+    }
+}
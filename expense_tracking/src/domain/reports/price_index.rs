@@ -0,0 +1,359 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Months, NaiveDate, Utc};
+use uuid_b64::UuidB64;
+
+use crate::domain::{
+    entities::{Category, Item, Transaction, Unit},
+    reports::{PriceMeasure, TimeBucket},
+};
+
+/// How two consecutive months are compared. Laspeyres weighs prices by the quantities bought in the earlier month,
+/// Fisher takes the geometric mean of that and of the Paasche index, weighed by the quantities of the later month.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexFormula {
+    Laspeyres,
+    Fisher,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexPoint {
+    pub month: NaiveDate,
+    pub value: f64,
+    /// How many products bought in both this month and the previous one the value was chained from.
+    pub compared: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexSeries {
+    pub points: Vec<IndexPoint>,
+}
+
+impl IndexSeries {
+    /// The change of the index over the twelve months up to the latest one, in percent, comparable with an official
+    /// inflation rate.
+    pub fn annual_rate(&self) -> Option<f64> {
+        let last: &IndexPoint = self.points.last()?;
+        let year_before: NaiveDate = last.month.checked_sub_months(Months::new(12))?;
+        let first: &IndexPoint = self.points.iter().find(|p| p.month == year_before)?;
+
+        Some(round_to_hundredths((last.value / first.value - 1.0) * 100.0))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoryIndex {
+    pub category: Category,
+    pub series: IndexSeries,
+}
+
+/// A chained price index over our own purchases, set to 100 on the first month something was bought.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InflationIndex {
+    pub formula: IndexFormula,
+    pub overall: IndexSeries,
+    pub per_category: Vec<CategoryIndex>,
+}
+
+type Basket = HashMap<(UuidB64, PriceMeasure), (f64, f64)>;
+
+impl InflationIndex {
+    /// Chains the index month after month over the transactions made between `from`, included, and `to`, excluded.
+    /// Each link only compares products bought by the same measure in both months, at the average price paid for
+    /// them, so products bought once do not move the index. Months without purchases are skipped.
+    pub fn build(transactions: &[Transaction], from: DateTime<Utc>, to: DateTime<Utc>, formula: IndexFormula) -> Self {
+        let mut builder: InflationIndexBuilder = InflationIndexBuilder::new(from, to, formula);
+        transactions.iter().for_each(|transaction| builder.add(transaction));
+
+        builder.build()
+    }
+}
+
+/// Builds an [`InflationIndex`] a transaction at a time, keeping only the monthly baskets, so that transactions can be
+/// streamed into the index instead of being loaded all at once.
+#[derive(Debug, Clone)]
+pub struct InflationIndexBuilder {
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    formula: IndexFormula,
+    overall: BTreeMap<NaiveDate, Basket>,
+    categories: HashMap<UuidB64, (Category, BTreeMap<NaiveDate, Basket>)>,
+}
+
+impl InflationIndexBuilder {
+    /// Index over the transactions made between `from`, included, and `to`, excluded.
+    pub fn new(from: DateTime<Utc>, to: DateTime<Utc>, formula: IndexFormula) -> Self {
+        Self {
+            from,
+            to,
+            formula,
+            overall: BTreeMap::new(),
+            categories: HashMap::new(),
+        }
+    }
+
+    /// Adds the items of `transaction` to the baskets of its month, unless it was made outside the range.
+    pub fn add(&mut self, transaction: &Transaction) {
+        if transaction.datetime < self.from || transaction.datetime >= self.to {
+            return;
+        }
+
+        let month: NaiveDate = TimeBucket::Month.start_of(transaction.datetime);
+
+        for item in &transaction.items {
+            add(self.overall.entry(month).or_default(), item);
+
+            let category: &Category = &item.product().category;
+            let (_, months) = self
+                .categories
+                .entry(category.id)
+                .or_insert_with(|| (category.clone(), BTreeMap::new()));
+            add(months.entry(month).or_default(), item);
+        }
+    }
+
+    pub fn build(self) -> InflationIndex {
+        let formula: IndexFormula = self.formula;
+        let mut per_category: Vec<CategoryIndex> = self
+            .categories
+            .into_values()
+            .map(|(category, months)| CategoryIndex {
+                category,
+                series: chain(&months, formula),
+            })
+            .collect();
+        per_category.sort_by(|a, b| a.category.name.cmp(&b.category.name).then(a.category.id.cmp(&b.category.id)));
+
+        InflationIndex {
+            formula,
+            overall: chain(&self.overall, formula),
+            per_category,
+        }
+    }
+}
+
+fn quantity(unit: &Unit) -> f64 {
+    match unit {
+        Unit::None => 1.0,
+        Unit::Quantity(amount) => *amount,
+        Unit::Kilograms(weight) => *weight,
+        Unit::Liters(volume) => *volume,
+    }
+}
+
+fn add(basket: &mut Basket, item: &Item) {
+    let bought: f64 = quantity(item.unit());
+    if bought <= 0.0 {
+        return;
+    }
+
+    let (spent, total) = basket.entry((item.product().id, PriceMeasure::from(item.unit()))).or_default();
+    *spent += item.unitary_price() * bought;
+    *total += bought;
+}
+
+fn chain(months: &BTreeMap<NaiveDate, Basket>, formula: IndexFormula) -> IndexSeries {
+    let mut points: Vec<IndexPoint> = Vec::with_capacity(months.len());
+    let mut value: f64 = 100.0;
+    let mut previous: Option<&Basket> = None;
+
+    for (month, basket) in months {
+        let compared: usize = match previous {
+            Some(earlier) => match link(earlier, basket, formula) {
+                Some((ratio, compared)) => {
+                    value *= ratio;
+                    compared
+                }
+                None => 0,
+            },
+            None => 0,
+        };
+
+        points.push(IndexPoint {
+            month: *month,
+            value: round_to_hundredths(value),
+            compared,
+        });
+        previous = Some(basket);
+    }
+
+    IndexSeries { points }
+}
+
+/// The price ratio between two months with the number of products it compares, none if they have none in common.
+fn link(earlier: &Basket, later: &Basket, formula: IndexFormula) -> Option<(f64, usize)> {
+    let (mut laspeyres_later, mut laspeyres_earlier) = (0.0, 0.0);
+    let (mut paasche_later, mut paasche_earlier) = (0.0, 0.0);
+    let mut compared: usize = 0;
+
+    for (key, (earlier_spent, earlier_quantity)) in earlier {
+        let Some((later_spent, later_quantity)) = later.get(key) else {
+            continue;
+        };
+        let (earlier_price, later_price) = (earlier_spent / earlier_quantity, later_spent / later_quantity);
+
+        laspeyres_later += later_price * earlier_quantity;
+        laspeyres_earlier += earlier_price * earlier_quantity;
+        paasche_later += later_price * later_quantity;
+        paasche_earlier += earlier_price * later_quantity;
+        compared += 1;
+    }
+
+    if compared == 0 || laspeyres_earlier == 0.0 || paasche_earlier == 0.0 {
+        return None;
+    }
+
+    let laspeyres: f64 = laspeyres_later / laspeyres_earlier;
+    let ratio: f64 = match formula {
+        IndexFormula::Laspeyres => laspeyres,
+        IndexFormula::Fisher => (laspeyres * paasche_later / paasche_earlier).sqrt(),
+    };
+
+    Some((ratio, compared))
+}
+
+fn round_to_hundredths(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, NaiveDate, Utc};
+
+    use crate::domain::{
+        entities::{Brand, Category, Item, Product, Store, Transaction, Unit},
+        reports::{IndexFormula, InflationIndex},
+    };
+
+    fn at(datetime: &str) -> DateTime<Utc> {
+        datetime.parse().unwrap()
+    }
+
+    fn month(date: &str) -> NaiveDate {
+        date.parse().unwrap()
+    }
+
+    fn given_transactions() -> Vec<Transaction> {
+        let store: Store = Store::new(None, "Migros".to_owned());
+        let dairy: Category = Category::new(None, "Dairy".to_owned());
+        let bakery: Category = Category::new(None, "Bakery".to_owned());
+        let milk: Product = Product::new(None, "Milk".to_owned(), Brand::new("Emmi".to_owned()), dairy);
+        let bread: Product = Product::new(None, "Bread".to_owned(), Brand::new("Jowa".to_owned()), bakery.clone());
+        let cake: Product = Product::new(None, "Cake".to_owned(), Brand::new("Jowa".to_owned()), bakery);
+
+        let purchase = |datetime: &str, items: Vec<Item>| Transaction::new(None, items, store.clone(), at(datetime));
+        vec![
+            purchase(
+                "2024-01-10T10:00:00Z",
+                vec![
+                    Item::new(None, milk.clone(), Unit::Liters(2.), 1.5),
+                    Item::new(None, bread.clone(), Unit::Quantity(1.), 3.0),
+                ],
+            ),
+            purchase(
+                "2024-02-12T10:00:00Z",
+                vec![
+                    Item::new(None, milk.clone(), Unit::Liters(4.), 1.8),
+                    Item::new(None, bread.clone(), Unit::Quantity(1.), 3.0),
+                    Item::new(None, cake, Unit::None, 12.0),
+                ],
+            ),
+            purchase("2024-04-02T10:00:00Z", vec![Item::new(None, milk, Unit::Liters(2.), 1.8)]),
+            purchase("2024-05-02T10:00:00Z", vec![Item::new(None, bread, Unit::Quantity(2.), 3.3)]),
+        ]
+    }
+
+    #[test]
+    fn build_with_laspeyres_should_chain_overall_index() {
+        let transactions: Vec<Transaction> = given_transactions();
+
+        let result: InflationIndex = InflationIndex::build(
+            &transactions,
+            at("2024-01-01T00:00:00Z"),
+            at("2025-01-01T00:00:00Z"),
+            IndexFormula::Laspeyres,
+        );
+        let points: Vec<(NaiveDate, f64, usize)> = result.overall.points.iter().map(|p| (p.month, p.value, p.compared)).collect();
+        let expected: Vec<(NaiveDate, f64, usize)> = vec![
+            (month("2024-01-01"), 100.0, 0),
+            (month("2024-02-01"), 110.0, 2),
+            (month("2024-04-01"), 110.0, 1),
+            (month("2024-05-01"), 110.0, 0),
+        ];
+
+        assert_eq!(points, expected, "Expected {:?}, but got {:?}", expected, points);
+    }
+
+    #[test]
+    fn build_with_fisher_should_weigh_both_months() {
+        let transactions: Vec<Transaction> = given_transactions();
+
+        let result: InflationIndex = InflationIndex::build(
+            &transactions,
+            at("2024-01-01T00:00:00Z"),
+            at("2024-03-01T00:00:00Z"),
+            IndexFormula::Fisher,
+        );
+        let values: Vec<f64> = result.overall.points.iter().map(|p| p.value).collect();
+
+        assert_eq!(values, vec![100.0, 111.65], "Expected Fisher values, but got {:?}", values);
+    }
+
+    #[test]
+    fn build_should_chain_an_index_per_category() {
+        let transactions: Vec<Transaction> = given_transactions();
+
+        let result: InflationIndex = InflationIndex::build(
+            &transactions,
+            at("2024-01-01T00:00:00Z"),
+            at("2025-01-01T00:00:00Z"),
+            IndexFormula::Laspeyres,
+        );
+        let series: Vec<(String, Vec<f64>)> = result
+            .per_category
+            .iter()
+            .map(|c| (c.category.name.clone(), c.series.points.iter().map(|p| p.value).collect()))
+            .collect();
+        let expected: Vec<(String, Vec<f64>)> = vec![
+            ("Bakery".to_owned(), vec![100.0, 100.0, 110.0]),
+            ("Dairy".to_owned(), vec![100.0, 120.0, 120.0]),
+        ];
+
+        assert_eq!(series, expected, "Expected {:?}, but got {:?}", expected, series);
+    }
+
+    #[test]
+    fn annual_rate_should_compare_with_the_same_month_a_year_before() {
+        let store: Store = Store::new(None, "Coop".to_owned());
+        let rice: Product = Product::new(
+            None,
+            "Rice".to_owned(),
+            Brand::new("Uncle Ben's".to_owned()),
+            Category::new(None, "Pantry".to_owned()),
+        );
+        let transactions: Vec<Transaction> = [
+            ("2023-03-15T10:00:00Z", 2.0),
+            ("2023-09-15T10:00:00Z", 2.1),
+            ("2024-03-15T10:00:00Z", 2.06),
+        ]
+        .iter()
+        .map(|(datetime, price)| {
+            Transaction::new(
+                None,
+                vec![Item::new(None, rice.clone(), Unit::Kilograms(1.), *price)],
+                store.clone(),
+                at(datetime),
+            )
+        })
+        .collect();
+
+        let result: InflationIndex = InflationIndex::build(
+            &transactions,
+            at("2023-01-01T00:00:00Z"),
+            at("2025-01-01T00:00:00Z"),
+            IndexFormula::Laspeyres,
+        );
+
+        assert_eq!(result.overall.annual_rate(), Some(3.0));
+    }
+}
@@ -0,0 +1,368 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use uuid_b64::UuidB64;
+
+use crate::domain::{
+    entities::{Item, Transaction},
+    reports::TimeBucket,
+};
+
+/// What spending can be grouped by. Dimensions combine, grouping by store then month gives a group per store and
+/// month.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReportDimension {
+    Category,
+    Store,
+    Brand,
+    Product,
+    Period(TimeBucket),
+}
+
+/// Where a group stands along one dimension.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GroupKey {
+    Category { id: UuidB64, name: String },
+    Store { id: UuidB64, name: String },
+    Brand { name: String },
+    Product { id: UuidB64, name: String },
+    Period { start: NaiveDate },
+}
+
+impl GroupKey {
+    fn of(dimension: &ReportDimension, transaction: &Transaction, item: &Item) -> Self {
+        match dimension {
+            ReportDimension::Category => GroupKey::Category {
+                id: item.product().category.id,
+                name: item.product().category.name.clone(),
+            },
+            ReportDimension::Store => GroupKey::Store {
+                id: transaction.store.id,
+                name: transaction.store.name.clone(),
+            },
+            ReportDimension::Brand => GroupKey::Brand {
+                name: item.product().brand.name.clone(),
+            },
+            ReportDimension::Product => GroupKey::Product {
+                id: item.product().id,
+                name: item.product().name.clone(),
+            },
+            ReportDimension::Period(bucket) => GroupKey::Period {
+                start: bucket.start_of(transaction.datetime),
+            },
+        }
+    }
+
+    /// The name of the entity, or the first day of the period formatted as `YYYY-MM-DD` so labels sort
+    /// chronologically.
+    pub fn label(&self) -> String {
+        match self {
+            GroupKey::Category { name, .. } | GroupKey::Store { name, .. } | GroupKey::Brand { name } | GroupKey::Product { name, .. } => {
+                name.clone()
+            }
+            GroupKey::Period { start } => start.format("%Y-%m-%d").to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpendingGroup {
+    pub keys: Vec<GroupKey>,
+    pub total: f64,
+    pub items: usize,
+    pub transactions: usize,
+}
+
+impl SpendingGroup {
+    pub fn average_per_item(&self) -> f64 {
+        average(self.total, self.items)
+    }
+
+    pub fn average_per_transaction(&self) -> f64 {
+        average(self.total, self.transactions)
+    }
+}
+
+/// Spending between `from`, included, and `to`, excluded. Groups are ordered by their labels, dimension after
+/// dimension.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpendingReport {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub dimensions: Vec<ReportDimension>,
+    pub groups: Vec<SpendingGroup>,
+    pub total: f64,
+    pub transactions: usize,
+}
+
+impl SpendingReport {
+    /// Sums the full price of every item of `transactions` made within the range into its group. Amounts are
+    /// rounded to the cent once summed, like transaction totals are.
+    pub fn build(transactions: &[Transaction], from: DateTime<Utc>, to: DateTime<Utc>, dimensions: Vec<ReportDimension>) -> Self {
+        let mut builder: SpendingReportBuilder = SpendingReportBuilder::new(from, to, dimensions);
+        transactions.iter().for_each(|transaction| builder.add(transaction));
+
+        builder.build()
+    }
+}
+
+/// Builds a [`SpendingReport`] a transaction at a time, so that transactions can be streamed into the report instead of
+/// being loaded all at once. Only the sums of the groups are kept along the way.
+#[derive(Debug, Clone)]
+pub struct SpendingReportBuilder {
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    dimensions: Vec<ReportDimension>,
+    sums: HashMap<Vec<GroupKey>, (f64, usize, usize)>,
+    total: f64,
+    transactions: usize,
+}
+
+impl SpendingReportBuilder {
+    pub fn new(from: DateTime<Utc>, to: DateTime<Utc>, dimensions: Vec<ReportDimension>) -> Self {
+        Self {
+            from,
+            to,
+            dimensions,
+            sums: HashMap::new(),
+            total: 0.0,
+            transactions: 0,
+        }
+    }
+
+    /// Sums the full price of every item of `transaction` into its group, unless it was made outside the range.
+    pub fn add(&mut self, transaction: &Transaction) {
+        if transaction.datetime < self.from || transaction.datetime >= self.to {
+            return;
+        }
+        self.transactions += 1;
+
+        let mut touched: HashSet<Vec<GroupKey>> = HashSet::new();
+        for item in &transaction.items {
+            let keys: Vec<GroupKey> = self.dimensions.iter().map(|d| GroupKey::of(d, transaction, item)).collect();
+            let (group_total, items, group_transactions) = self.sums.entry(keys.clone()).or_default();
+
+            *group_total += item.calculate_full_price();
+            *items += 1;
+            if touched.insert(keys) {
+                *group_transactions += 1;
+            }
+            self.total += item.calculate_full_price();
+        }
+    }
+
+    /// Amounts are rounded to the cent once summed, like transaction totals are.
+    pub fn build(self) -> SpendingReport {
+        let mut groups: Vec<SpendingGroup> = self
+            .sums
+            .into_iter()
+            .map(|(keys, (total, items, transactions))| SpendingGroup {
+                keys,
+                total: round_to_cents(total),
+                items,
+                transactions,
+            })
+            .collect();
+        groups.sort_by_cached_key(|g| g.keys.iter().map(GroupKey::label).collect::<Vec<String>>());
+
+        SpendingReport {
+            from: self.from,
+            to: self.to,
+            dimensions: self.dimensions,
+            groups,
+            total: round_to_cents(self.total),
+            transactions: self.transactions,
+        }
+    }
+}
+
+fn round_to_cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+fn average(total: f64, count: usize) -> f64 {
+    match count {
+        0 => 0.0,
+        count => round_to_cents(total / count as f64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+
+    use crate::domain::{
+        entities::{Brand, Category, Item, Product, Store, Transaction, Unit},
+        reports::{GroupKey, ReportDimension, SpendingGroup, SpendingReport, TimeBucket},
+    };
+
+    struct Ledger {
+        migros: Store,
+        coop: Store,
+        dairy: Category,
+        fruits: Category,
+        transactions: Vec<Transaction>,
+    }
+
+    fn at(datetime: &str) -> DateTime<Utc> {
+        datetime.parse().unwrap()
+    }
+
+    fn given_ledger() -> Ledger {
+        let migros: Store = Store::new(None, "Migros".to_owned());
+        let coop: Store = Store::new(None, "Coop".to_owned());
+        let dairy: Category = Category::new(None, "Dairy".to_owned());
+        let fruits: Category = Category::new(None, "Fruits".to_owned());
+        let yogurt: Product = Product::new(None, "Yogurt".to_owned(), Brand::new("Emmi".to_owned()), dairy.clone());
+        let milk: Product = Product::new(None, "Milk".to_owned(), Brand::new("Emmi".to_owned()), dairy.clone());
+        let apples: Product = Product::new(None, "Apples".to_owned(), Brand::new("Migros Bio".to_owned()), fruits.clone());
+
+        let transactions: Vec<Transaction> = vec![
+            Transaction::new(
+                None,
+                vec![
+                    Item::new(None, yogurt.clone(), Unit::Quantity(3.), 0.85),
+                    Item::new(None, apples.clone(), Unit::Kilograms(1.5), 4.2),
+                ],
+                migros.clone(),
+                at("2024-03-04T10:00:00Z"),
+            ),
+            Transaction::new(
+                None,
+                vec![Item::new(None, milk, Unit::Liters(2.), 1.6)],
+                coop.clone(),
+                at("2024-03-20T18:30:00Z"),
+            ),
+            Transaction::new(
+                None,
+                vec![Item::new(None, apples, Unit::Kilograms(1.), 3.9)],
+                migros.clone(),
+                at("2024-04-02T09:15:00Z"),
+            ),
+            Transaction::new(
+                None,
+                vec![Item::new(None, yogurt, Unit::None, 0.85)],
+                coop.clone(),
+                at("2024-05-01T00:00:00Z"),
+            ),
+        ];
+
+        Ledger {
+            migros,
+            coop,
+            dairy,
+            fruits,
+            transactions,
+        }
+    }
+
+    #[test]
+    fn build_without_dimension_should_sum_the_range() {
+        let ledger: Ledger = given_ledger();
+
+        let result: SpendingReport =
+            SpendingReport::build(&ledger.transactions, at("2024-03-01T00:00:00Z"), at("2024-05-01T00:00:00Z"), vec![]);
+        let expected: Vec<SpendingGroup> = vec![SpendingGroup {
+            keys: vec![],
+            total: 15.95,
+            items: 4,
+            transactions: 3,
+        }];
+
+        assert_eq!(result.groups, expected, "Expected {:?}, but got {:?}", expected, result.groups);
+        assert_eq!(result.total, 15.95);
+        assert_eq!(result.transactions, 3);
+    }
+
+    #[test]
+    fn build_should_group_by_category() {
+        let ledger: Ledger = given_ledger();
+
+        let result: SpendingReport = SpendingReport::build(
+            &ledger.transactions,
+            at("2024-01-01T00:00:00Z"),
+            at("2025-01-01T00:00:00Z"),
+            vec![ReportDimension::Category],
+        );
+        let expected: Vec<SpendingGroup> = vec![
+            SpendingGroup {
+                keys: vec![GroupKey::Category {
+                    id: ledger.dairy.id,
+                    name: "Dairy".to_owned(),
+                }],
+                total: 6.6,
+                items: 3,
+                transactions: 3,
+            },
+            SpendingGroup {
+                keys: vec![GroupKey::Category {
+                    id: ledger.fruits.id,
+                    name: "Fruits".to_owned(),
+                }],
+                total: 10.2,
+                items: 2,
+                transactions: 2,
+            },
+        ];
+
+        assert_eq!(result.groups, expected, "Expected {:?}, but got {:?}", expected, result.groups);
+        assert_eq!(result.groups[0].average_per_item(), 2.2);
+    }
+
+    #[test]
+    fn build_should_combine_store_and_month() {
+        let ledger: Ledger = given_ledger();
+
+        let result: SpendingReport = SpendingReport::build(
+            &ledger.transactions,
+            at("2024-01-01T00:00:00Z"),
+            at("2025-01-01T00:00:00Z"),
+            vec![ReportDimension::Store, ReportDimension::Period(TimeBucket::Month)],
+        );
+        let labels: Vec<(Vec<String>, f64)> = result
+            .groups
+            .iter()
+            .map(|g| (g.keys.iter().map(GroupKey::label).collect(), g.total))
+            .collect();
+        let expected: Vec<(Vec<String>, f64)> = vec![
+            (vec![ledger.coop.name.clone(), "2024-03-01".to_owned()], 3.2),
+            (vec![ledger.coop.name.clone(), "2024-05-01".to_owned()], 0.85),
+            (vec![ledger.migros.name.clone(), "2024-03-01".to_owned()], 8.85),
+            (vec![ledger.migros.name.clone(), "2024-04-01".to_owned()], 3.9),
+        ];
+
+        assert_eq!(labels, expected, "Expected {:?}, but got {:?}", expected, labels);
+    }
+
+    #[test]
+    fn build_should_average_brand_spending_per_transaction() {
+        let ledger: Ledger = given_ledger();
+
+        let result: SpendingReport = SpendingReport::build(
+            &ledger.transactions,
+            at("2024-01-01T00:00:00Z"),
+            at("2025-01-01T00:00:00Z"),
+            vec![ReportDimension::Brand],
+        );
+        let emmi: &SpendingGroup = &result.groups[0];
+
+        assert_eq!(emmi.keys, vec![GroupKey::Brand { name: "Emmi".to_owned() }]);
+        assert_eq!((emmi.total, emmi.transactions), (6.6, 3));
+        assert_eq!(emmi.average_per_transaction(), 2.2);
+    }
+
+    #[test]
+    fn build_over_empty_range_should_have_no_group() {
+        let ledger: Ledger = given_ledger();
+
+        let result: SpendingReport = SpendingReport::build(
+            &ledger.transactions,
+            at("2023-01-01T00:00:00Z"),
+            at("2024-01-01T00:00:00Z"),
+            vec![ReportDimension::Product],
+        );
+
+        assert!(result.groups.is_empty(), "Expected no group, but got {:?}", result.groups);
+        assert_eq!((result.total, result.transactions), (0.0, 0));
+    }
+}
@@ -0,0 +1,57 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+
+/// A period spending is summed over. Weeks start on Monday, as in ISO 8601.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimeBucket {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl TimeBucket {
+    /// The first day of the bucket holding `datetime`.
+    pub fn start_of(&self, datetime: DateTime<Utc>) -> NaiveDate {
+        let day: NaiveDate = datetime.date_naive();
+
+        match self {
+            TimeBucket::Day => day,
+            TimeBucket::Week => day - Duration::days(day.weekday().num_days_from_monday() as i64),
+            TimeBucket::Month => day.with_day(1).unwrap_or(day),
+            TimeBucket::Year => day.with_ordinal(1).unwrap_or(day),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, NaiveDate, Utc};
+
+    use super::TimeBucket;
+
+    macro_rules! start_of {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (bucket, datetime, expected): (TimeBucket, &str, &str) = $value;
+                let datetime: DateTime<Utc> = datetime.parse().unwrap();
+                let expected: NaiveDate = expected.parse().unwrap();
+
+                let result: NaiveDate = bucket.start_of(datetime);
+
+                assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+            }
+        )*
+        }
+    }
+
+    start_of! {
+        day_should_start_at_its_date: (TimeBucket::Day, "2024-02-29T23:59:59Z", "2024-02-29"),
+        week_should_start_on_monday: (TimeBucket::Week, "2024-03-03T10:00:00Z", "2024-02-26"),
+        week_should_start_on_the_same_monday: (TimeBucket::Week, "2024-02-26T00:00:00Z", "2024-02-26"),
+        week_should_span_years: (TimeBucket::Week, "2025-01-01T08:00:00Z", "2024-12-30"),
+        month_should_start_on_its_first_day: (TimeBucket::Month, "2024-02-29T12:00:00Z", "2024-02-01"),
+        year_should_start_on_january_first: (TimeBucket::Year, "2024-11-17T12:00:00Z", "2024-01-01"),
+    }
+}
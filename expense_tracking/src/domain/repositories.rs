@@ -1,17 +1,38 @@
+mod audit_log;
+mod audited_brand_repository;
+mod audited_transaction_repository;
 mod brand_repository;
 mod category_repository;
+mod delete_behaviour;
 mod product_repository;
 mod store_repository;
 mod transaction_repository;
+mod trashed;
 
+pub use audit_log::AuditAction;
+pub use audit_log::AuditEntry;
+pub use audit_log::AuditFailure;
+pub use audit_log::AuditLog;
+pub use audit_log::AuditLogError;
+pub use audited_brand_repository::AuditedBrandRepository;
+pub use audited_transaction_repository::AuditedTransactionRepository;
 pub use brand_repository::BrandRepository;
 pub use brand_repository::BrandRepositoryCreateError;
+pub use brand_repository::BrandRepositoryDeleteError;
+pub use brand_repository::BrandRepositoryRestoreError;
 pub use brand_repository::BrandRepositoryRetrieveAllError;
+pub use brand_repository::BrandRepositoryUpdateError;
 pub use category_repository::CategoryRepository;
 pub use category_repository::CategoryRepositoryError;
+pub use category_repository::SharedCategoryRepository;
+pub use delete_behaviour::DeleteBehaviour;
 pub use product_repository::ProductRepository;
 pub use product_repository::ProductRepositoryError;
+pub use product_repository::SharedProductRepository;
+pub use store_repository::SharedStoreRepository;
 pub use store_repository::StoreRepository;
 pub use store_repository::StoreRepositoryError;
+pub use transaction_repository::SharedTransactionRepository;
 pub use transaction_repository::TransactionRepository;
 pub use transaction_repository::TransactionRepositoryError;
+pub use trashed::Trashed;
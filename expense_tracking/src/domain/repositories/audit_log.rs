@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+#[async_trait]
+pub trait AuditLog<T>: std::fmt::Debug + Send + Sync {
+    async fn record(&self, entry: AuditEntry<T>) -> Result<(), AuditLogError>;
+
+    async fn history(&self, entity_key: &str) -> Result<Vec<AuditEntry<T>>, AuditLogError>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuditLogError {
+    UnableToRecordEntry(String),
+    UnableToRetrieveHistory(String),
+}
+
+/// An entry an audited repository could not record, although the change it describes was saved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditFailure<T> {
+    pub entry: AuditEntry<T>,
+    pub error: AuditLogError,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AuditAction {
+    Created,
+    Updated,
+    Deleted,
+    Restored,
+    Purged,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditEntry<T> {
+    pub entity_key: String,
+    pub action: AuditAction,
+    pub actor: String,
+    pub recorded_at: DateTime<Utc>,
+    pub before: Option<T>,
+    pub after: Option<T>,
+}
+
+impl<T: Clone> AuditEntry<T> {
+    pub fn new(
+        entity_key: String,
+        action: AuditAction,
+        actor: String,
+        recorded_at: DateTime<Utc>,
+        before: Option<T>,
+        after: Option<T>,
+    ) -> Self {
+        Self {
+            entity_key,
+            action,
+            actor,
+            recorded_at,
+            before,
+            after,
+        }
+    }
+
+    pub fn state_as_of(history: &[AuditEntry<T>], at: DateTime<Utc>) -> Option<T> {
+        history
+            .iter()
+            .filter(|e| e.recorded_at <= at)
+            .max_by_key(|e| e.recorded_at)
+            .and_then(|e| e.after.clone())
+    }
+}
@@ -0,0 +1,480 @@
+use std::sync::{Arc, Mutex, PoisonError};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+
+use crate::domain::{
+    entities::{Brand, NamePolicy},
+    repositories::{
+        AuditAction, AuditEntry, AuditFailure, AuditLog, AuditLogError, BrandRepository, BrandRepositoryCreateError,
+        BrandRepositoryDeleteError, BrandRepositoryRestoreError, BrandRepositoryRetrieveAllError, BrandRepositoryUpdateError,
+        DeleteBehaviour, Trashed,
+    },
+};
+
+/// Records every change to the brands in an [`AuditLog`].
+///
+/// A change saved by the inner repository is reported as a success even when its entry cannot be recorded, the entry
+/// is then kept aside until [`AuditedBrandRepository::take_audit_failures`] hands it over.
+///
+/// Entries are keyed by the brand name as normalized by the [`NamePolicy`], the way the repositories tell brands apart.
+#[derive(Debug)]
+pub struct AuditedBrandRepository {
+    inner: Arc<dyn BrandRepository>,
+    audit_log: Arc<dyn AuditLog<Brand>>,
+    actor: String,
+    name_policy: NamePolicy,
+    failures: Mutex<Vec<AuditFailure<Brand>>>,
+}
+
+impl AuditedBrandRepository {
+    pub fn new(inner: Arc<dyn BrandRepository>, audit_log: Arc<dyn AuditLog<Brand>>, actor: String) -> Self {
+        Self {
+            inner,
+            audit_log,
+            actor,
+            name_policy: NamePolicy::default(),
+            failures: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn with_name_policy(self, name_policy: NamePolicy) -> Self {
+        Self { name_policy, ..self }
+    }
+
+    /// The entries that could not be recorded since the last call, oldest first.
+    pub fn take_audit_failures(&self) -> Vec<AuditFailure<Brand>> {
+        std::mem::take(&mut *self.failures.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    pub async fn history_of(&self, brand: &Brand) -> Result<Vec<AuditEntry<Brand>>, AuditLogError> {
+        self.audit_log.history(&self.name_policy.normalize(&brand.name)).await
+    }
+
+    pub async fn as_of(&self, brand: &Brand, at: DateTime<Utc>) -> Result<Option<Brand>, AuditLogError> {
+        let history: Vec<AuditEntry<Brand>> = self.history_of(brand).await?;
+
+        Ok(AuditEntry::state_as_of(&history, at))
+    }
+
+    async fn record(&self, action: AuditAction, before: Option<Brand>, after: Option<Brand>) {
+        let entity_key: String = after
+            .as_ref()
+            .or(before.as_ref())
+            .map(|b| self.name_policy.normalize(&b.name))
+            .unwrap_or_default();
+        let entry: AuditEntry<Brand> = AuditEntry::new(entity_key, action, self.actor.clone(), Utc::now(), before, after);
+
+        if let Err(error) = self.audit_log.record(entry.clone()).await {
+            self.failures
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(AuditFailure { entry, error });
+        }
+    }
+}
+
+#[async_trait]
+impl BrandRepository for AuditedBrandRepository {
+    async fn create(&self, brand: &Brand) -> Result<Brand, BrandRepositoryCreateError> {
+        let created: Brand = self.inner.create(brand).await?;
+
+        self.record(AuditAction::Created, None, Some(created.clone())).await;
+        Ok(created)
+    }
+
+    async fn retrieve_all(&self) -> Result<Vec<Brand>, BrandRepositoryRetrieveAllError> {
+        self.inner.retrieve_all().await
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Brand, BrandRepositoryRetrieveAllError>> {
+        self.inner.stream_all()
+    }
+
+    async fn update(&self, brand: &Brand) -> Result<Brand, BrandRepositoryUpdateError> {
+        let updated: Brand = self.inner.update(brand).await?;
+        // Only the version the caller held can have been updated, under the name the repository stores.
+        let before: Brand = Brand {
+            version: brand.version,
+            ..updated.clone()
+        };
+
+        self.record(AuditAction::Updated, Some(before), Some(updated.clone())).await;
+        Ok(updated)
+    }
+
+    async fn delete(&self, brand: &Brand, behaviour: DeleteBehaviour<Brand>) -> Result<Brand, BrandRepositoryDeleteError> {
+        let deleted: Brand = self.inner.delete(brand, behaviour).await?;
+
+        self.record(AuditAction::Deleted, Some(deleted.clone()), None).await;
+        Ok(deleted)
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Brand>>, BrandRepositoryRetrieveAllError> {
+        self.inner.list_trashed().await
+    }
+
+    async fn restore(&self, brand: &Brand) -> Result<Brand, BrandRepositoryRestoreError> {
+        let restored: Brand = self.inner.restore(brand).await?;
+
+        self.record(AuditAction::Restored, None, Some(restored.clone())).await;
+        Ok(restored)
+    }
+
+    async fn purge_older_than(&self, cutoff: DateTime<Utc>) -> Result<Vec<Brand>, BrandRepositoryDeleteError> {
+        let purged: Vec<Brand> = self.inner.purge_older_than(cutoff).await?;
+
+        for brand in &purged {
+            self.record(AuditAction::Purged, Some(brand.clone()), None).await;
+        }
+
+        Ok(purged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use chrono::{DateTime, Duration, Utc};
+    use futures::stream::BoxStream;
+
+    use crate::domain::{
+        entities::{Brand, NamePolicy},
+        repositories::{
+            AuditAction, AuditEntry, AuditFailure, AuditLog, AuditLogError, AuditedBrandRepository, BrandRepository,
+            BrandRepositoryCreateError, BrandRepositoryDeleteError, BrandRepositoryRestoreError, BrandRepositoryRetrieveAllError,
+            BrandRepositoryUpdateError, DeleteBehaviour, Trashed,
+        },
+    };
+
+    fn given_audited_repository(
+        brand_repository: BrandRepositoryMockImplementation,
+    ) -> (AuditedBrandRepository, Arc<AuditLogMockImplementation>) {
+        given_audited_repository_with(brand_repository, AuditLogMockImplementation::new())
+    }
+
+    fn given_audited_repository_with(
+        brand_repository: BrandRepositoryMockImplementation,
+        audit_log: AuditLogMockImplementation,
+    ) -> (AuditedBrandRepository, Arc<AuditLogMockImplementation>) {
+        let audit_log: Arc<AuditLogMockImplementation> = Arc::new(audit_log);
+        let repository: AuditedBrandRepository = AuditedBrandRepository::new(
+            Arc::new(brand_repository),
+            Arc::clone(&audit_log) as Arc<dyn AuditLog<Brand>>,
+            "alice".to_owned(),
+        );
+
+        (repository, audit_log)
+    }
+
+    #[tokio::test]
+    async fn create_should_record_created_entry() {
+        let brand: Brand = Brand::new("Berneice Tonkinson".to_owned());
+        let (repository, audit_log) = given_audited_repository(BrandRepositoryMockImplementation {
+            on_create: Some(Ok(brand.clone())),
+            ..BrandRepositoryMockImplementation::none()
+        });
+
+        assert_eq!(repository.create(&brand).await, Ok(brand.clone()));
+
+        let entries: Vec<AuditEntry<Brand>> = audit_log.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].entity_key, NamePolicy::default().normalize(&brand.name));
+        assert_eq!(entries[0].action, AuditAction::Created);
+        assert_eq!(entries[0].actor, "alice");
+        assert_eq!(entries[0].before, None);
+        assert_eq!(entries[0].after, Some(brand));
+    }
+
+    #[tokio::test]
+    async fn failed_create_should_not_record_anything() {
+        let (repository, audit_log) = given_audited_repository(BrandRepositoryMockImplementation {
+            on_create: Some(Err(BrandRepositoryCreateError::BrandAlreadyExists)),
+            ..BrandRepositoryMockImplementation::none()
+        });
+
+        assert_eq!(
+            repository.create(&Brand::new("Berneice Tonkinson".to_owned())).await,
+            Err(BrandRepositoryCreateError::BrandAlreadyExists)
+        );
+        assert!(audit_log.entries().is_empty());
+    }
+
+    #[tokio::test]
+    async fn create_should_succeed_and_keep_the_entry_aside_when_the_audit_log_fails() {
+        let brand: Brand = Brand::new("Berneice Tonkinson".to_owned());
+        let (repository, _) = given_audited_repository_with(
+            BrandRepositoryMockImplementation {
+                on_create: Some(Ok(brand.clone())),
+                ..BrandRepositoryMockImplementation::none()
+            },
+            AuditLogMockImplementation::failing(),
+        );
+
+        assert_eq!(repository.create(&brand).await, Ok(brand.clone()));
+
+        let failures: Vec<AuditFailure<Brand>> = repository.take_audit_failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].entry.action, AuditAction::Created);
+        assert_eq!(failures[0].entry.after, Some(brand));
+        assert_eq!(failures[0].error, AuditLogError::UnableToRecordEntry("disk full".to_owned()));
+        assert!(repository.take_audit_failures().is_empty());
+    }
+
+    #[tokio::test]
+    async fn update_should_succeed_and_keep_the_entry_aside_when_the_audit_log_fails() {
+        let before: Brand = Brand::new("Berneice Tonkinson".to_owned());
+        let after: Brand = Brand {
+            version: 1,
+            ..before.clone()
+        };
+        let (repository, _) = given_audited_repository_with(
+            BrandRepositoryMockImplementation {
+                on_update: Some(Ok(after.clone())),
+                ..BrandRepositoryMockImplementation::none()
+            },
+            AuditLogMockImplementation::failing(),
+        );
+
+        assert_eq!(repository.update(&before).await, Ok(after.clone()));
+
+        let failures: Vec<AuditFailure<Brand>> = repository.take_audit_failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].entry.before, Some(before));
+        assert_eq!(failures[0].entry.after, Some(after));
+    }
+
+    #[tokio::test]
+    async fn update_should_record_before_and_after_snapshots() {
+        let before: Brand = Brand::new("Berneice Tonkinson".to_owned());
+        let after: Brand = Brand {
+            version: 1,
+            ..before.clone()
+        };
+        let (repository, audit_log) = given_audited_repository(BrandRepositoryMockImplementation {
+            on_update: Some(Ok(after.clone())),
+            ..BrandRepositoryMockImplementation::none()
+        });
+
+        assert_eq!(repository.update(&before).await, Ok(after.clone()));
+
+        let entries: Vec<AuditEntry<Brand>> = audit_log.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, AuditAction::Updated);
+        assert_eq!(entries[0].before, Some(before));
+        assert_eq!(entries[0].after, Some(after));
+    }
+
+    #[tokio::test]
+    async fn delete_should_record_deleted_entry() {
+        let brand: Brand = Brand::new("Berneice Tonkinson".to_owned());
+        let (repository, audit_log) = given_audited_repository(BrandRepositoryMockImplementation {
+            on_delete: Some(Ok(brand.clone())),
+            ..BrandRepositoryMockImplementation::none()
+        });
+
+        assert_eq!(repository.delete(&brand, DeleteBehaviour::Restrict).await, Ok(brand.clone()));
+
+        let entries: Vec<AuditEntry<Brand>> = audit_log.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, AuditAction::Deleted);
+        assert_eq!(entries[0].before, Some(brand));
+        assert_eq!(entries[0].after, None);
+    }
+
+    #[tokio::test]
+    async fn history_of_should_only_list_entries_of_the_given_brand() {
+        let brand: Brand = Brand::new("Berneice Tonkinson".to_owned());
+        let other: Brand = Brand::new("Ewa Vocelka".to_owned());
+        let (repository, audit_log) = given_audited_repository(BrandRepositoryMockImplementation::none());
+        let now: DateTime<Utc> = Utc::now();
+
+        audit_log.push(AuditEntry::new(
+            NamePolicy::default().normalize(&brand.name),
+            AuditAction::Created,
+            "alice".to_owned(),
+            now,
+            None,
+            Some(brand.clone()),
+        ));
+        audit_log.push(AuditEntry::new(
+            NamePolicy::default().normalize(&other.name),
+            AuditAction::Created,
+            "bob".to_owned(),
+            now,
+            None,
+            Some(other.clone()),
+        ));
+
+        let history: Vec<AuditEntry<Brand>> = repository.history_of(&brand).await.unwrap();
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].after, Some(brand));
+    }
+
+    #[tokio::test]
+    async fn as_of_should_reconstruct_brand_at_given_date() {
+        let created: Brand = Brand::new("Berneice Tonkinson".to_owned());
+        let updated: Brand = Brand {
+            version: 1,
+            ..created.clone()
+        };
+        let (repository, audit_log) = given_audited_repository(BrandRepositoryMockImplementation::none());
+        let created_at: DateTime<Utc> = Utc::now() - Duration::days(10);
+        let updated_at: DateTime<Utc> = created_at + Duration::days(2);
+        let deleted_at: DateTime<Utc> = updated_at + Duration::days(2);
+
+        audit_log.push(AuditEntry::new(
+            NamePolicy::default().normalize(&created.name),
+            AuditAction::Created,
+            "alice".to_owned(),
+            created_at,
+            None,
+            Some(created.clone()),
+        ));
+        audit_log.push(AuditEntry::new(
+            NamePolicy::default().normalize(&created.name),
+            AuditAction::Updated,
+            "bob".to_owned(),
+            updated_at,
+            Some(created.clone()),
+            Some(updated.clone()),
+        ));
+        audit_log.push(AuditEntry::new(
+            NamePolicy::default().normalize(&created.name),
+            AuditAction::Deleted,
+            "alice".to_owned(),
+            deleted_at,
+            Some(updated.clone()),
+            None,
+        ));
+
+        assert_eq!(repository.as_of(&created, created_at - Duration::days(1)).await, Ok(None));
+        assert_eq!(repository.as_of(&created, created_at).await, Ok(Some(created.clone())));
+        assert_eq!(repository.as_of(&created, updated_at + Duration::hours(1)).await, Ok(Some(updated)));
+        assert_eq!(repository.as_of(&created, deleted_at + Duration::hours(1)).await, Ok(None));
+    }
+
+    #[tokio::test]
+    async fn history_of_should_find_the_brand_under_another_spelling_of_its_name() {
+        let brand: Brand = Brand::new("Coca-Cola".to_owned());
+        let (repository, _) = given_audited_repository(BrandRepositoryMockImplementation {
+            on_create: Some(Ok(brand.clone())),
+            ..BrandRepositoryMockImplementation::none()
+        });
+
+        assert_eq!(repository.create(&brand).await, Ok(brand.clone()));
+
+        let result: Vec<Option<Brand>> = repository
+            .history_of(&Brand::new("  coca-cola ".to_owned()))
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| e.after)
+            .collect();
+        let expected: Vec<Option<Brand>> = vec![Some(brand)];
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[derive(Debug)]
+    struct BrandRepositoryMockImplementation {
+        on_create: Option<Result<Brand, BrandRepositoryCreateError>>,
+        on_update: Option<Result<Brand, BrandRepositoryUpdateError>>,
+        on_delete: Option<Result<Brand, BrandRepositoryDeleteError>>,
+    }
+
+    impl BrandRepositoryMockImplementation {
+        fn none() -> Self {
+            Self {
+                on_create: None,
+                on_update: None,
+                on_delete: None,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl BrandRepository for BrandRepositoryMockImplementation {
+        async fn create(&self, _: &Brand) -> Result<Brand, BrandRepositoryCreateError> {
+            self.on_create.clone().unwrap_or_else(|| todo!())
+        }
+
+        async fn retrieve_all(&self) -> Result<Vec<Brand>, BrandRepositoryRetrieveAllError> {
+            todo!()
+        }
+
+        fn stream_all(&self) -> BoxStream<'_, Result<Brand, BrandRepositoryRetrieveAllError>> {
+            todo!()
+        }
+
+        async fn update(&self, _: &Brand) -> Result<Brand, BrandRepositoryUpdateError> {
+            self.on_update.clone().unwrap_or_else(|| todo!())
+        }
+
+        async fn delete(&self, _: &Brand, _: DeleteBehaviour<Brand>) -> Result<Brand, BrandRepositoryDeleteError> {
+            self.on_delete.clone().unwrap_or_else(|| todo!())
+        }
+
+        async fn list_trashed(&self) -> Result<Vec<Trashed<Brand>>, BrandRepositoryRetrieveAllError> {
+            todo!()
+        }
+
+        async fn restore(&self, _: &Brand) -> Result<Brand, BrandRepositoryRestoreError> {
+            todo!()
+        }
+
+        async fn purge_older_than(&self, _: DateTime<Utc>) -> Result<Vec<Brand>, BrandRepositoryDeleteError> {
+            todo!()
+        }
+    }
+
+    #[derive(Debug)]
+    struct AuditLogMockImplementation {
+        entries: Mutex<Vec<AuditEntry<Brand>>>,
+        failing: bool,
+    }
+
+    impl AuditLogMockImplementation {
+        fn new() -> Self {
+            Self {
+                entries: Mutex::new(Vec::new()),
+                failing: false,
+            }
+        }
+
+        fn failing() -> Self {
+            Self {
+                failing: true,
+                ..Self::new()
+            }
+        }
+
+        fn push(&self, entry: AuditEntry<Brand>) {
+            self.entries.lock().unwrap().push(entry);
+        }
+
+        fn entries(&self) -> Vec<AuditEntry<Brand>> {
+            self.entries.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl AuditLog<Brand> for AuditLogMockImplementation {
+        async fn record(&self, entry: AuditEntry<Brand>) -> Result<(), AuditLogError> {
+            if self.failing {
+                return Err(AuditLogError::UnableToRecordEntry("disk full".to_owned()));
+            }
+
+            self.push(entry);
+            Ok(())
+        }
+
+        async fn history(&self, entity_key: &str) -> Result<Vec<AuditEntry<Brand>>, AuditLogError> {
+            Ok(self.entries().into_iter().filter(|e| e.entity_key == entity_key).collect())
+        }
+    }
+}
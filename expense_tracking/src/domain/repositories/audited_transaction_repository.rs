@@ -0,0 +1,292 @@
+use std::sync::{Arc, Mutex, PoisonError};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use uuid_b64::UuidB64;
+
+use crate::domain::{
+    entities::Transaction,
+    repositories::{
+        AuditAction, AuditEntry, AuditFailure, AuditLog, AuditLogError, TransactionRepository, TransactionRepositoryError, Trashed,
+    },
+};
+
+/// Records every change to the transactions in an [`AuditLog`], keeping the entries it could not record aside like
+/// [`AuditedBrandRepository`](crate::domain::repositories::AuditedBrandRepository) does.
+pub struct AuditedTransactionRepository {
+    inner: Box<dyn TransactionRepository + Send + Sync>,
+    audit_log: Arc<dyn AuditLog<Transaction>>,
+    actor: String,
+    failures: Mutex<Vec<AuditFailure<Transaction>>>,
+}
+
+impl AuditedTransactionRepository {
+    pub fn new(inner: Box<dyn TransactionRepository + Send + Sync>, audit_log: Arc<dyn AuditLog<Transaction>>, actor: String) -> Self {
+        Self {
+            inner,
+            audit_log,
+            actor,
+            failures: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The entries that could not be recorded since the last call, oldest first.
+    pub fn take_audit_failures(&self) -> Vec<AuditFailure<Transaction>> {
+        std::mem::take(&mut *self.failures.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    pub async fn history_of(&self, id: &UuidB64) -> Result<Vec<AuditEntry<Transaction>>, AuditLogError> {
+        self.audit_log.history(&id.to_string()).await
+    }
+
+    pub async fn as_of(&self, id: &UuidB64, at: DateTime<Utc>) -> Result<Option<Transaction>, AuditLogError> {
+        let history: Vec<AuditEntry<Transaction>> = self.history_of(id).await?;
+
+        Ok(AuditEntry::state_as_of(&history, at))
+    }
+
+    async fn record(&self, action: AuditAction, before: Option<Transaction>, after: Option<Transaction>) {
+        let entity_key: String = after.as_ref().or(before.as_ref()).map(|t| t.id.to_string()).unwrap_or_default();
+        let entry: AuditEntry<Transaction> = AuditEntry::new(entity_key, action, self.actor.clone(), Utc::now(), before, after);
+
+        if let Err(error) = self.audit_log.record(entry.clone()).await {
+            self.failures
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(AuditFailure { entry, error });
+        }
+    }
+}
+
+#[async_trait]
+impl TransactionRepository for AuditedTransactionRepository {
+    async fn create_or_update(&mut self, transaction: &Transaction) -> Result<Option<Transaction>, TransactionRepositoryError> {
+        let previous: Option<Transaction> = self.inner.create_or_update(transaction).await?;
+
+        let (action, stored): (AuditAction, Transaction) = match &previous {
+            Some(p) => (
+                AuditAction::Updated,
+                Transaction {
+                    version: p.version + 1,
+                    ..transaction.clone()
+                },
+            ),
+            None => (AuditAction::Created, transaction.clone()),
+        };
+
+        self.record(action, previous.clone(), Some(stored)).await;
+        Ok(previous)
+    }
+
+    async fn retrieve_all(&self) -> Result<Vec<Transaction>, TransactionRepositoryError> {
+        self.inner.retrieve_all().await
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Transaction, TransactionRepositoryError>> {
+        self.inner.stream_all()
+    }
+
+    async fn delete(&mut self, id: &UuidB64) -> Result<Transaction, TransactionRepositoryError> {
+        let deleted: Transaction = self.inner.delete(id).await?;
+
+        self.record(AuditAction::Deleted, Some(deleted.clone()), None).await;
+        Ok(deleted)
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Transaction>>, TransactionRepositoryError> {
+        self.inner.list_trashed().await
+    }
+
+    async fn restore(&mut self, id: &UuidB64) -> Result<Transaction, TransactionRepositoryError> {
+        let restored: Transaction = self.inner.restore(id).await?;
+
+        self.record(AuditAction::Restored, None, Some(restored.clone())).await;
+        Ok(restored)
+    }
+
+    async fn purge_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<Vec<Transaction>, TransactionRepositoryError> {
+        let purged: Vec<Transaction> = self.inner.purge_older_than(cutoff).await?;
+
+        for transaction in &purged {
+            self.record(AuditAction::Purged, Some(transaction.clone()), None).await;
+        }
+
+        Ok(purged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use futures::stream::BoxStream;
+    use uuid_b64::UuidB64;
+
+    use crate::domain::{
+        entities::{Store, Transaction},
+        repositories::{
+            AuditAction, AuditEntry, AuditLog, AuditLogError, AuditedTransactionRepository, TransactionRepository,
+            TransactionRepositoryError, Trashed,
+        },
+    };
+
+    fn given_transaction() -> Transaction {
+        Transaction::new(None, vec![], Store::new(None, "Some Store".to_owned()), DateTime::default())
+    }
+
+    fn given_audited_repository(
+        transaction_repository: TransactionRepositoryMockImplementation,
+    ) -> (AuditedTransactionRepository, Arc<AuditLogMockImplementation>) {
+        let audit_log: Arc<AuditLogMockImplementation> = Arc::new(AuditLogMockImplementation::new());
+        let repository: AuditedTransactionRepository = AuditedTransactionRepository::new(
+            Box::new(transaction_repository),
+            Arc::clone(&audit_log) as Arc<dyn AuditLog<Transaction>>,
+            "bob".to_owned(),
+        );
+
+        (repository, audit_log)
+    }
+
+    #[tokio::test]
+    async fn create_or_update_new_transaction_should_record_created_entry() {
+        let transaction: Transaction = given_transaction();
+        let (mut repository, audit_log) = given_audited_repository(TransactionRepositoryMockImplementation {
+            on_create_or_update: Some(Ok(None)),
+            on_delete: None,
+        });
+
+        assert_eq!(repository.create_or_update(&transaction).await, Ok(None));
+
+        let entries: Vec<AuditEntry<Transaction>> = audit_log.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].entity_key, transaction.id.to_string());
+        assert_eq!(entries[0].action, AuditAction::Created);
+        assert_eq!(entries[0].actor, "bob");
+        assert_eq!(entries[0].before, None);
+        assert_eq!(entries[0].after, Some(transaction));
+    }
+
+    #[tokio::test]
+    async fn create_or_update_existing_transaction_should_record_before_and_after() {
+        let before: Transaction = given_transaction();
+        let changed: Transaction = Transaction {
+            datetime: Utc::now(),
+            ..before.clone()
+        };
+        let (mut repository, audit_log) = given_audited_repository(TransactionRepositoryMockImplementation {
+            on_create_or_update: Some(Ok(Some(before.clone()))),
+            on_delete: None,
+        });
+
+        assert_eq!(repository.create_or_update(&changed).await, Ok(Some(before.clone())));
+
+        let entries: Vec<AuditEntry<Transaction>> = audit_log.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, AuditAction::Updated);
+        assert_eq!(entries[0].before, Some(before));
+        assert_eq!(entries[0].after, Some(Transaction { version: 1, ..changed }));
+    }
+
+    #[tokio::test]
+    async fn delete_should_record_deleted_entry_and_as_of_should_see_it() {
+        let transaction: Transaction = given_transaction();
+        let (mut repository, audit_log) = given_audited_repository(TransactionRepositoryMockImplementation {
+            on_create_or_update: Some(Ok(None)),
+            on_delete: Some(Ok(transaction.clone())),
+        });
+
+        assert!(repository.create_or_update(&transaction).await.is_ok());
+        let between: DateTime<Utc> = audit_log.entries()[0].recorded_at;
+        assert_eq!(repository.delete(&transaction.id).await, Ok(transaction.clone()));
+
+        let history: Vec<AuditEntry<Transaction>> = repository.history_of(&transaction.id).await.unwrap();
+        assert_eq!(
+            history.iter().map(|e| e.action).collect::<Vec<AuditAction>>(),
+            vec![AuditAction::Created, AuditAction::Deleted]
+        );
+        assert_eq!(repository.as_of(&transaction.id, between).await, Ok(Some(transaction.clone())));
+        assert_eq!(repository.as_of(&transaction.id, Utc::now()).await, Ok(None));
+    }
+
+    #[tokio::test]
+    async fn failed_delete_should_not_record_anything() {
+        let (mut repository, audit_log) = given_audited_repository(TransactionRepositoryMockImplementation {
+            on_create_or_update: None,
+            on_delete: Some(Err(TransactionRepositoryError::TransactionNotFound)),
+        });
+
+        assert_eq!(
+            repository.delete(&given_transaction().id).await,
+            Err(TransactionRepositoryError::TransactionNotFound)
+        );
+        assert!(audit_log.entries().is_empty());
+    }
+
+    struct TransactionRepositoryMockImplementation {
+        on_create_or_update: Option<Result<Option<Transaction>, TransactionRepositoryError>>,
+        on_delete: Option<Result<Transaction, TransactionRepositoryError>>,
+    }
+
+    #[async_trait]
+    impl TransactionRepository for TransactionRepositoryMockImplementation {
+        async fn create_or_update(&mut self, _: &Transaction) -> Result<Option<Transaction>, TransactionRepositoryError> {
+            self.on_create_or_update.take().unwrap_or_else(|| todo!())
+        }
+
+        async fn retrieve_all(&self) -> Result<Vec<Transaction>, TransactionRepositoryError> {
+            todo!()
+        }
+
+        fn stream_all(&self) -> BoxStream<'_, Result<Transaction, TransactionRepositoryError>> {
+            todo!()
+        }
+
+        async fn delete(&mut self, _: &UuidB64) -> Result<Transaction, TransactionRepositoryError> {
+            self.on_delete.take().unwrap_or_else(|| todo!())
+        }
+
+        async fn list_trashed(&self) -> Result<Vec<Trashed<Transaction>>, TransactionRepositoryError> {
+            todo!()
+        }
+
+        async fn restore(&mut self, _: &UuidB64) -> Result<Transaction, TransactionRepositoryError> {
+            todo!()
+        }
+
+        async fn purge_older_than(&mut self, _: DateTime<Utc>) -> Result<Vec<Transaction>, TransactionRepositoryError> {
+            todo!()
+        }
+    }
+
+    #[derive(Debug)]
+    struct AuditLogMockImplementation {
+        entries: Mutex<Vec<AuditEntry<Transaction>>>,
+    }
+
+    impl AuditLogMockImplementation {
+        fn new() -> Self {
+            Self {
+                entries: Mutex::new(Vec::new()),
+            }
+        }
+
+        fn entries(&self) -> Vec<AuditEntry<Transaction>> {
+            self.entries.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl AuditLog<Transaction> for AuditLogMockImplementation {
+        async fn record(&self, entry: AuditEntry<Transaction>) -> Result<(), AuditLogError> {
+            self.entries.lock().unwrap().push(entry);
+            Ok(())
+        }
+
+        async fn history(&self, entity_key: &str) -> Result<Vec<AuditEntry<Transaction>>, AuditLogError> {
+            Ok(self.entries().into_iter().filter(|e| e.entity_key == entity_key).collect())
+        }
+    }
+}
@@ -1,12 +1,29 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
 
-use crate::domain::entities::Brand;
+use crate::domain::{
+    entities::Brand,
+    repositories::{DeleteBehaviour, Trashed},
+};
 
 #[async_trait]
 pub trait BrandRepository: std::fmt::Debug + Send + Sync {
     async fn create(&self, brand: &Brand) -> Result<Brand, BrandRepositoryCreateError>;
 
     async fn retrieve_all(&self) -> Result<Vec<Brand>, BrandRepositoryRetrieveAllError>;
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Brand, BrandRepositoryRetrieveAllError>>;
+
+    async fn update(&self, brand: &Brand) -> Result<Brand, BrandRepositoryUpdateError>;
+
+    async fn delete(&self, brand: &Brand, behaviour: DeleteBehaviour<Brand>) -> Result<Brand, BrandRepositoryDeleteError>;
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Brand>>, BrandRepositoryRetrieveAllError>;
+
+    async fn restore(&self, brand: &Brand) -> Result<Brand, BrandRepositoryRestoreError>;
+
+    async fn purge_older_than(&self, cutoff: DateTime<Utc>) -> Result<Vec<Brand>, BrandRepositoryDeleteError>;
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -19,3 +36,25 @@ pub enum BrandRepositoryCreateError {
 pub enum BrandRepositoryRetrieveAllError {
     UnableToRetrieveBrands(String),
 }
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BrandRepositoryUpdateError {
+    UnableToUpdateBrand(String),
+    BrandNotFound,
+    VersionConflict { current_version: u64 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BrandRepositoryDeleteError {
+    UnableToDeleteBrand(String),
+    BrandNotFound,
+    BrandStillReferenced,
+    ReplacementBrandNotFound,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BrandRepositoryRestoreError {
+    UnableToRestoreBrand(String),
+    BrandNotInTrash,
+    BrandAlreadyExists,
+}
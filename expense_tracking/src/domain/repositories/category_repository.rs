@@ -1,16 +1,45 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use tokio::sync::RwLock;
+use uuid_b64::UuidB64;
+
+use crate::domain::{
+    entities::Category,
+    repositories::{DeleteBehaviour, Trashed},
+};
 
-use crate::domain::entities::Category;
+/// A category repository shared by several use cases, the lock serializing their writes. A use case holding several
+/// of these locks takes them in the order categories, stores, products then transactions, so that two use cases never
+/// wait on each other.
+pub type SharedCategoryRepository = Arc<RwLock<Box<dyn CategoryRepository + Send + Sync>>>;
 
 #[async_trait]
 pub trait CategoryRepository {
-    async fn create_or_update(
-        &mut self,
-        category: &Category,
-    ) -> Result<Option<Category>, CategoryRepositoryError>;
+    async fn create_or_update(&mut self, category: &Category) -> Result<Option<Category>, CategoryRepositoryError>;
 
     async fn retrieve_all(&self) -> Result<Vec<Category>, CategoryRepositoryError>;
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Category, CategoryRepositoryError>>;
+
+    async fn delete(&mut self, id: &UuidB64, behaviour: DeleteBehaviour<Category>) -> Result<Category, CategoryRepositoryError>;
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Category>>, CategoryRepositoryError>;
+
+    async fn restore(&mut self, id: &UuidB64) -> Result<Category, CategoryRepositoryError>;
+
+    async fn purge_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<Vec<Category>, CategoryRepositoryError>;
 }
 
 #[derive(Debug, PartialEq)]
-pub enum CategoryRepositoryError {}
+pub enum CategoryRepositoryError {
+    UnableToAccessStorage(String),
+    CategoryNotFound,
+    CategoryNotInTrash,
+    CategoryAlreadyExists,
+    VersionConflict { current_version: u64 },
+    CategoryStillReferenced,
+    ReplacementCategoryNotFound,
+}
@@ -0,0 +1,6 @@
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeleteBehaviour<T> {
+    Restrict,
+    Cascade,
+    Reassign(T),
+}
@@ -1,16 +1,46 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use tokio::sync::RwLock;
+use uuid_b64::UuidB64;
+
+use crate::domain::{
+    entities::Product,
+    repositories::{DeleteBehaviour, Trashed},
+};
 
-use crate::domain::entities::Product;
+/// A product repository shared by several use cases, the lock serializing their writes. Locked after the stores, see
+/// [`SharedCategoryRepository`](crate::domain::repositories::SharedCategoryRepository).
+pub type SharedProductRepository = Arc<RwLock<Box<dyn ProductRepository + Send + Sync>>>;
 
 #[async_trait]
 pub trait ProductRepository {
-    async fn create_or_update(
-        &mut self,
-        product: &Product,
-    ) -> Result<Option<Product>, ProductRepositoryError>;
+    async fn create_or_update(&mut self, product: &Product) -> Result<Option<Product>, ProductRepositoryError>;
 
     async fn retrieve_all(&self) -> Result<Vec<Product>, ProductRepositoryError>;
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Product, ProductRepositoryError>>;
+
+    async fn delete(&mut self, id: &UuidB64, behaviour: DeleteBehaviour<Product>) -> Result<Product, ProductRepositoryError>;
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Product>>, ProductRepositoryError>;
+
+    async fn restore(&mut self, id: &UuidB64) -> Result<Product, ProductRepositoryError>;
+
+    async fn purge_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<Vec<Product>, ProductRepositoryError>;
 }
 
 #[derive(Debug, PartialEq)]
-pub enum ProductRepositoryError {}
+pub enum ProductRepositoryError {
+    UnableToAccessStorage(String),
+    ProductNotFound,
+    ProductNotInTrash,
+    ProductAlreadyExists,
+    VersionConflict { current_version: u64 },
+    ProductStillReferenced,
+    ReplacementProductNotFound,
+    BrandNotFound,
+    CategoryNotFound,
+}
@@ -1,16 +1,44 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use tokio::sync::RwLock;
+use uuid_b64::UuidB64;
+
+use crate::domain::{
+    entities::Store,
+    repositories::{DeleteBehaviour, Trashed},
+};
 
-use crate::domain::entities::Store;
+/// A store repository shared by several use cases, the lock serializing their writes. Locked after the categories, see
+/// [`SharedCategoryRepository`](crate::domain::repositories::SharedCategoryRepository).
+pub type SharedStoreRepository = Arc<RwLock<Box<dyn StoreRepository + Send + Sync>>>;
 
 #[async_trait]
 pub trait StoreRepository {
-    async fn create_or_update(
-        &mut self,
-        category: &Store,
-    ) -> Result<Option<Store>, StoreRepositoryError>;
+    async fn create_or_update(&mut self, category: &Store) -> Result<Option<Store>, StoreRepositoryError>;
 
     async fn retrieve_all(&self) -> Result<Vec<Store>, StoreRepositoryError>;
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Store, StoreRepositoryError>>;
+
+    async fn delete(&mut self, id: &UuidB64, behaviour: DeleteBehaviour<Store>) -> Result<Store, StoreRepositoryError>;
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Store>>, StoreRepositoryError>;
+
+    async fn restore(&mut self, id: &UuidB64) -> Result<Store, StoreRepositoryError>;
+
+    async fn purge_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<Vec<Store>, StoreRepositoryError>;
 }
 
 #[derive(Debug, PartialEq)]
-pub enum StoreRepositoryError {}
+pub enum StoreRepositoryError {
+    UnableToAccessStorage(String),
+    StoreNotFound,
+    StoreNotInTrash,
+    StoreAlreadyExists,
+    VersionConflict { current_version: u64 },
+    StoreStillReferenced,
+    ReplacementStoreNotFound,
+}
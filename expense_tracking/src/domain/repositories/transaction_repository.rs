@@ -1,16 +1,41 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use tokio::sync::RwLock;
+use uuid_b64::UuidB64;
+
+use crate::domain::{entities::Transaction, repositories::Trashed};
 
-use crate::domain::entities::Transaction;
+/// A transaction repository shared by several use cases, the lock serializing their writes. Locked last, see
+/// [`SharedCategoryRepository`](crate::domain::repositories::SharedCategoryRepository).
+pub type SharedTransactionRepository = Arc<RwLock<Box<dyn TransactionRepository + Send + Sync>>>;
 
 #[async_trait]
 pub trait TransactionRepository {
-    async fn create_or_update(
-        &mut self,
-        category: &Transaction,
-    ) -> Result<Option<Transaction>, TransactionRepositoryError>;
+    async fn create_or_update(&mut self, category: &Transaction) -> Result<Option<Transaction>, TransactionRepositoryError>;
 
     async fn retrieve_all(&self) -> Result<Vec<Transaction>, TransactionRepositoryError>;
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Transaction, TransactionRepositoryError>>;
+
+    async fn delete(&mut self, id: &UuidB64) -> Result<Transaction, TransactionRepositoryError>;
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Transaction>>, TransactionRepositoryError>;
+
+    async fn restore(&mut self, id: &UuidB64) -> Result<Transaction, TransactionRepositoryError>;
+
+    async fn purge_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<Vec<Transaction>, TransactionRepositoryError>;
 }
 
 #[derive(Debug, PartialEq)]
-pub enum TransactionRepositoryError {}
+pub enum TransactionRepositoryError {
+    UnableToAccessStorage(String),
+    TransactionNotFound,
+    TransactionNotInTrash,
+    TransactionAlreadyExists,
+    VersionConflict { current_version: u64 },
+    StoreNotFound,
+    ProductNotFound,
+}
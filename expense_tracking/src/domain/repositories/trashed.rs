@@ -0,0 +1,13 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trashed<T> {
+    pub entity: T,
+    pub trashed_at: DateTime<Utc>,
+}
+
+impl<T> Trashed<T> {
+    pub fn new(entity: T, trashed_at: DateTime<Utc>) -> Self {
+        Self { entity, trashed_at }
+    }
+}
@@ -0,0 +1,20 @@
+mod indexed_brand_repository;
+mod indexed_category_repository;
+mod indexed_product_repository;
+mod indexed_store_repository;
+mod search_index;
+mod search_service;
+mod text_folding;
+
+pub use indexed_brand_repository::IndexedBrandRepository;
+pub use indexed_category_repository::IndexedCategoryRepository;
+pub use indexed_product_repository::IndexedProductRepository;
+pub use indexed_store_repository::IndexedStoreRepository;
+pub use search_index::SearchDocument;
+pub use search_index::SearchEntityKind;
+pub use search_index::SearchHit;
+pub use search_index::SearchIndex;
+pub use search_service::SearchService;
+pub use text_folding::edit_distance;
+pub use text_folding::fold;
+pub use text_folding::tokenize;
@@ -0,0 +1,85 @@
+use std::sync::{Arc, Mutex, PoisonError};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+
+use crate::domain::{
+    entities::Brand,
+    repositories::{
+        BrandRepository, BrandRepositoryCreateError, BrandRepositoryDeleteError, BrandRepositoryRestoreError,
+        BrandRepositoryRetrieveAllError, BrandRepositoryUpdateError, DeleteBehaviour, Trashed,
+    },
+    search::{SearchDocument, SearchEntityKind, SearchIndex},
+};
+
+#[derive(Debug)]
+pub struct IndexedBrandRepository {
+    inner: Arc<dyn BrandRepository>,
+    index: Arc<Mutex<SearchIndex>>,
+}
+
+impl IndexedBrandRepository {
+    pub fn new(inner: Arc<dyn BrandRepository>, index: Arc<Mutex<SearchIndex>>) -> Self {
+        Self { inner, index }
+    }
+}
+
+#[async_trait]
+impl BrandRepository for IndexedBrandRepository {
+    async fn create(&self, brand: &Brand) -> Result<Brand, BrandRepositoryCreateError> {
+        let created: Brand = self.inner.create(brand).await?;
+
+        self.index
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .upsert(SearchDocument::from(&created));
+        Ok(created)
+    }
+
+    async fn retrieve_all(&self) -> Result<Vec<Brand>, BrandRepositoryRetrieveAllError> {
+        self.inner.retrieve_all().await
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Brand, BrandRepositoryRetrieveAllError>> {
+        self.inner.stream_all()
+    }
+
+    async fn update(&self, brand: &Brand) -> Result<Brand, BrandRepositoryUpdateError> {
+        let updated: Brand = self.inner.update(brand).await?;
+
+        self.index
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .upsert(SearchDocument::from(&updated));
+        Ok(updated)
+    }
+
+    async fn delete(&self, brand: &Brand, behaviour: DeleteBehaviour<Brand>) -> Result<Brand, BrandRepositoryDeleteError> {
+        let deleted: Brand = self.inner.delete(brand, behaviour).await?;
+
+        self.index
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(SearchEntityKind::Brand, &deleted.name);
+        Ok(deleted)
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Brand>>, BrandRepositoryRetrieveAllError> {
+        self.inner.list_trashed().await
+    }
+
+    async fn restore(&self, brand: &Brand) -> Result<Brand, BrandRepositoryRestoreError> {
+        let restored: Brand = self.inner.restore(brand).await?;
+
+        self.index
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .upsert(SearchDocument::from(&restored));
+        Ok(restored)
+    }
+
+    async fn purge_older_than(&self, cutoff: DateTime<Utc>) -> Result<Vec<Brand>, BrandRepositoryDeleteError> {
+        self.inner.purge_older_than(cutoff).await
+    }
+}
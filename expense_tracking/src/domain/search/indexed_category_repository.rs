@@ -0,0 +1,72 @@
+use std::sync::{Arc, Mutex, PoisonError};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use uuid_b64::UuidB64;
+
+use crate::domain::{
+    entities::Category,
+    repositories::{CategoryRepository, CategoryRepositoryError, DeleteBehaviour, Trashed},
+    search::{SearchDocument, SearchEntityKind, SearchIndex},
+};
+
+pub struct IndexedCategoryRepository {
+    inner: Box<dyn CategoryRepository + Send + Sync>,
+    index: Arc<Mutex<SearchIndex>>,
+}
+
+impl IndexedCategoryRepository {
+    pub fn new(inner: Box<dyn CategoryRepository + Send + Sync>, index: Arc<Mutex<SearchIndex>>) -> Self {
+        Self { inner, index }
+    }
+}
+
+#[async_trait]
+impl CategoryRepository for IndexedCategoryRepository {
+    async fn create_or_update(&mut self, category: &Category) -> Result<Option<Category>, CategoryRepositoryError> {
+        let previous: Option<Category> = self.inner.create_or_update(category).await?;
+
+        self.index
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .upsert(SearchDocument::from(category));
+        Ok(previous)
+    }
+
+    async fn retrieve_all(&self) -> Result<Vec<Category>, CategoryRepositoryError> {
+        self.inner.retrieve_all().await
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Category, CategoryRepositoryError>> {
+        self.inner.stream_all()
+    }
+
+    async fn delete(&mut self, id: &UuidB64, behaviour: DeleteBehaviour<Category>) -> Result<Category, CategoryRepositoryError> {
+        let deleted: Category = self.inner.delete(id, behaviour).await?;
+
+        self.index
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(SearchEntityKind::Category, &id.to_string());
+        Ok(deleted)
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Category>>, CategoryRepositoryError> {
+        self.inner.list_trashed().await
+    }
+
+    async fn restore(&mut self, id: &UuidB64) -> Result<Category, CategoryRepositoryError> {
+        let restored: Category = self.inner.restore(id).await?;
+
+        self.index
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .upsert(SearchDocument::from(&restored));
+        Ok(restored)
+    }
+
+    async fn purge_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<Vec<Category>, CategoryRepositoryError> {
+        self.inner.purge_older_than(cutoff).await
+    }
+}
@@ -0,0 +1,72 @@
+use std::sync::{Arc, Mutex, PoisonError};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use uuid_b64::UuidB64;
+
+use crate::domain::{
+    entities::Product,
+    repositories::{DeleteBehaviour, ProductRepository, ProductRepositoryError, Trashed},
+    search::{SearchDocument, SearchEntityKind, SearchIndex},
+};
+
+pub struct IndexedProductRepository {
+    inner: Box<dyn ProductRepository + Send + Sync>,
+    index: Arc<Mutex<SearchIndex>>,
+}
+
+impl IndexedProductRepository {
+    pub fn new(inner: Box<dyn ProductRepository + Send + Sync>, index: Arc<Mutex<SearchIndex>>) -> Self {
+        Self { inner, index }
+    }
+}
+
+#[async_trait]
+impl ProductRepository for IndexedProductRepository {
+    async fn create_or_update(&mut self, product: &Product) -> Result<Option<Product>, ProductRepositoryError> {
+        let previous: Option<Product> = self.inner.create_or_update(product).await?;
+
+        self.index
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .upsert(SearchDocument::from(product));
+        Ok(previous)
+    }
+
+    async fn retrieve_all(&self) -> Result<Vec<Product>, ProductRepositoryError> {
+        self.inner.retrieve_all().await
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Product, ProductRepositoryError>> {
+        self.inner.stream_all()
+    }
+
+    async fn delete(&mut self, id: &UuidB64, behaviour: DeleteBehaviour<Product>) -> Result<Product, ProductRepositoryError> {
+        let deleted: Product = self.inner.delete(id, behaviour).await?;
+
+        self.index
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(SearchEntityKind::Product, &id.to_string());
+        Ok(deleted)
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Product>>, ProductRepositoryError> {
+        self.inner.list_trashed().await
+    }
+
+    async fn restore(&mut self, id: &UuidB64) -> Result<Product, ProductRepositoryError> {
+        let restored: Product = self.inner.restore(id).await?;
+
+        self.index
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .upsert(SearchDocument::from(&restored));
+        Ok(restored)
+    }
+
+    async fn purge_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<Vec<Product>, ProductRepositoryError> {
+        self.inner.purge_older_than(cutoff).await
+    }
+}
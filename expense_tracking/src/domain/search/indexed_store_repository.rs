@@ -0,0 +1,201 @@
+use std::sync::{Arc, Mutex, PoisonError};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use uuid_b64::UuidB64;
+
+use crate::domain::{
+    entities::Store,
+    repositories::{DeleteBehaviour, StoreRepository, StoreRepositoryError, Trashed},
+    search::{SearchDocument, SearchEntityKind, SearchIndex},
+};
+
+pub struct IndexedStoreRepository {
+    inner: Box<dyn StoreRepository + Send + Sync>,
+    index: Arc<Mutex<SearchIndex>>,
+}
+
+impl IndexedStoreRepository {
+    pub fn new(inner: Box<dyn StoreRepository + Send + Sync>, index: Arc<Mutex<SearchIndex>>) -> Self {
+        Self { inner, index }
+    }
+}
+
+#[async_trait]
+impl StoreRepository for IndexedStoreRepository {
+    async fn create_or_update(&mut self, store: &Store) -> Result<Option<Store>, StoreRepositoryError> {
+        let previous: Option<Store> = self.inner.create_or_update(store).await?;
+
+        self.index
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .upsert(SearchDocument::from(store));
+        Ok(previous)
+    }
+
+    async fn retrieve_all(&self) -> Result<Vec<Store>, StoreRepositoryError> {
+        self.inner.retrieve_all().await
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Store, StoreRepositoryError>> {
+        self.inner.stream_all()
+    }
+
+    async fn delete(&mut self, id: &UuidB64, behaviour: DeleteBehaviour<Store>) -> Result<Store, StoreRepositoryError> {
+        let deleted: Store = self.inner.delete(id, behaviour).await?;
+
+        self.index
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(SearchEntityKind::Store, &id.to_string());
+        Ok(deleted)
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Store>>, StoreRepositoryError> {
+        self.inner.list_trashed().await
+    }
+
+    async fn restore(&mut self, id: &UuidB64) -> Result<Store, StoreRepositoryError> {
+        let restored: Store = self.inner.restore(id).await?;
+
+        self.index
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .upsert(SearchDocument::from(&restored));
+        Ok(restored)
+    }
+
+    async fn purge_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<Vec<Store>, StoreRepositoryError> {
+        self.inner.purge_older_than(cutoff).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex, PoisonError};
+
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use futures::stream::BoxStream;
+    use uuid_b64::UuidB64;
+
+    use crate::domain::{
+        entities::Store,
+        repositories::{DeleteBehaviour, StoreRepository, StoreRepositoryError, Trashed},
+        search::{IndexedStoreRepository, SearchIndex},
+    };
+
+    fn given_indexed_repository(store_repository: StoreRepositoryMockImplementation) -> (IndexedStoreRepository, Arc<Mutex<SearchIndex>>) {
+        let index: Arc<Mutex<SearchIndex>> = Arc::new(Mutex::new(SearchIndex::new()));
+
+        (IndexedStoreRepository::new(Box::new(store_repository), Arc::clone(&index)), index)
+    }
+
+    #[tokio::test]
+    async fn create_or_update_should_index_store() {
+        let store: Store = Store::new(None, "Migros Zürich".to_owned());
+        let (mut repository, index) = given_indexed_repository(StoreRepositoryMockImplementation {
+            on_create_or_update: Some(Ok(None)),
+            on_delete: None,
+        });
+
+        assert_eq!(repository.create_or_update(&store).await, Ok(None));
+
+        let result: Vec<String> = index.lock().unwrap().search("zurich", 10).into_iter().map(|h| h.key).collect();
+        let expected: Vec<String> = vec![store.id.to_string()];
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn failed_create_or_update_should_not_index_store() {
+        let (mut repository, index) = given_indexed_repository(StoreRepositoryMockImplementation {
+            on_create_or_update: Some(Err(StoreRepositoryError::VersionConflict { current_version: 3 })),
+            on_delete: None,
+        });
+
+        assert!(repository.create_or_update(&Store::new(None, "Coop".to_owned())).await.is_err());
+        assert!(index.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn delete_should_remove_store_from_index() {
+        let store: Store = Store::new(None, "Coop".to_owned());
+        let (mut repository, index) = given_indexed_repository(StoreRepositoryMockImplementation {
+            on_create_or_update: Some(Ok(None)),
+            on_delete: Some(Ok(store.clone())),
+        });
+
+        assert!(repository.create_or_update(&store).await.is_ok());
+        assert_eq!(repository.delete(&store.id, DeleteBehaviour::Restrict).await, Ok(store));
+        assert!(index.lock().unwrap().search("coop", 10).is_empty());
+    }
+
+    #[tokio::test]
+    async fn create_or_update_should_index_store_after_a_panic_poisoned_the_index() {
+        let store: Store = Store::new(None, "Migros Zürich".to_owned());
+        let (mut repository, index) = given_indexed_repository(StoreRepositoryMockImplementation {
+            on_create_or_update: Some(Ok(None)),
+            on_delete: None,
+        });
+        let poisoning_index: Arc<Mutex<SearchIndex>> = Arc::clone(&index);
+
+        assert!(
+            std::thread::spawn(move || {
+                let _guard = poisoning_index.lock().unwrap();
+                panic!("poison the index");
+            })
+            .join()
+            .is_err()
+        );
+        assert_eq!(repository.create_or_update(&store).await, Ok(None));
+
+        let result: Vec<String> = index
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .search("zurich", 10)
+            .into_iter()
+            .map(|h| h.key)
+            .collect();
+        let expected: Vec<String> = vec![store.id.to_string()];
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    struct StoreRepositoryMockImplementation {
+        on_create_or_update: Option<Result<Option<Store>, StoreRepositoryError>>,
+        on_delete: Option<Result<Store, StoreRepositoryError>>,
+    }
+
+    #[async_trait]
+    impl StoreRepository for StoreRepositoryMockImplementation {
+        async fn create_or_update(&mut self, _: &Store) -> Result<Option<Store>, StoreRepositoryError> {
+            self.on_create_or_update.take().unwrap_or_else(|| todo!())
+        }
+
+        async fn retrieve_all(&self) -> Result<Vec<Store>, StoreRepositoryError> {
+            todo!()
+        }
+
+        fn stream_all(&self) -> BoxStream<'_, Result<Store, StoreRepositoryError>> {
+            todo!()
+        }
+
+        async fn delete(&mut self, _: &UuidB64, _: DeleteBehaviour<Store>) -> Result<Store, StoreRepositoryError> {
+            self.on_delete.take().unwrap_or_else(|| todo!())
+        }
+
+        async fn list_trashed(&self) -> Result<Vec<Trashed<Store>>, StoreRepositoryError> {
+            todo!()
+        }
+
+        async fn restore(&mut self, _: &UuidB64) -> Result<Store, StoreRepositoryError> {
+            todo!()
+        }
+
+        async fn purge_older_than(&mut self, _: DateTime<Utc>) -> Result<Vec<Store>, StoreRepositoryError> {
+            todo!()
+        }
+    }
+}
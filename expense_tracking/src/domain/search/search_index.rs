@@ -0,0 +1,429 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::domain::{
+    entities::{Brand, Category, Product, Store},
+    search::{edit_distance, tokenize},
+};
+
+const EXACT_MATCH_SCORE: u32 = 100;
+const PREFIX_MATCH_SCORE: u32 = 80;
+const TYPO_MATCH_SCORE: u32 = 60;
+const TYPO_PREFIX_MATCH_SCORE: u32 = 50;
+const EXACT_TITLE_BONUS: u32 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SearchEntityKind {
+    Brand,
+    Category,
+    Product,
+    Store,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchDocument {
+    pub kind: SearchEntityKind,
+    pub key: String,
+    pub title: String,
+    pub context: Vec<String>,
+    /// The documents whose titles the context repeats, in the same order, so that renaming one of them reindexes this
+    /// one.
+    pub references: Vec<(SearchEntityKind, String)>,
+}
+
+impl SearchDocument {
+    pub fn new(kind: SearchEntityKind, key: String, title: String, context: Vec<String>) -> Self {
+        Self {
+            kind,
+            key,
+            title,
+            context,
+            references: vec![],
+        }
+    }
+
+    pub fn with_references(self, references: Vec<(SearchEntityKind, String)>) -> Self {
+        Self { references, ..self }
+    }
+}
+
+impl From<&Brand> for SearchDocument {
+    fn from(brand: &Brand) -> Self {
+        Self::new(SearchEntityKind::Brand, brand.name.clone(), brand.name.clone(), vec![])
+    }
+}
+
+impl From<&Category> for SearchDocument {
+    fn from(category: &Category) -> Self {
+        Self::new(SearchEntityKind::Category, category.id.to_string(), category.name.clone(), vec![])
+    }
+}
+
+impl From<&Product> for SearchDocument {
+    fn from(product: &Product) -> Self {
+        Self::new(
+            SearchEntityKind::Product,
+            product.id.to_string(),
+            product.name.clone(),
+            vec![product.brand.name.clone(), product.category.name.clone()],
+        )
+        .with_references(vec![
+            (SearchEntityKind::Brand, product.brand.name.clone()),
+            (SearchEntityKind::Category, product.category.id.to_string()),
+        ])
+    }
+}
+
+impl From<&Store> for SearchDocument {
+    fn from(store: &Store) -> Self {
+        Self::new(SearchEntityKind::Store, store.id.to_string(), store.name.clone(), vec![])
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchHit {
+    pub kind: SearchEntityKind,
+    pub key: String,
+    pub title: String,
+    pub score: u32,
+}
+
+type DocumentId = (SearchEntityKind, String);
+
+#[derive(Debug)]
+struct IndexedDocument {
+    document: SearchDocument,
+    folded_title: String,
+    title_tokens: HashSet<String>,
+    tokens: HashSet<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct SearchIndex {
+    documents: HashMap<DocumentId, IndexedDocument>,
+    postings: BTreeMap<String, HashSet<DocumentId>>,
+    referrers: HashMap<DocumentId, HashSet<DocumentId>>,
+}
+
+impl SearchIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Indexes `document`, replacing any previous version, and refreshes the context of the documents referencing it
+    /// when its title changed.
+    pub fn upsert(&mut self, document: SearchDocument) {
+        let id: DocumentId = (document.kind, document.key.clone());
+        let title: String = document.title.clone();
+
+        self.index(document);
+
+        let referrers: Vec<DocumentId> = self.referrers.get(&id).into_iter().flatten().cloned().collect();
+        for referrer in referrers {
+            if let Some(mut document) = self.documents.get(&referrer).map(|indexed| indexed.document.clone()) {
+                for (reference, context) in document.references.iter().zip(document.context.iter_mut()) {
+                    if *reference == id {
+                        *context = title.clone();
+                    }
+                }
+
+                self.index(document);
+            }
+        }
+    }
+
+    fn index(&mut self, document: SearchDocument) {
+        let id: DocumentId = (document.kind, document.key.clone());
+        self.remove(id.0, &id.1);
+
+        for reference in &document.references {
+            self.referrers.entry(reference.clone()).or_default().insert(id.clone());
+        }
+
+        let title_tokens: HashSet<String> = tokenize(&document.title).into_iter().collect();
+        let tokens: HashSet<String> = title_tokens
+            .iter()
+            .cloned()
+            .chain(document.context.iter().flat_map(|c| tokenize(c)))
+            .collect();
+
+        for token in &tokens {
+            self.postings.entry(token.clone()).or_default().insert(id.clone());
+        }
+
+        self.documents.insert(
+            id,
+            IndexedDocument {
+                folded_title: tokenize(&document.title).join(" "),
+                document,
+                title_tokens,
+                tokens,
+            },
+        );
+    }
+
+    pub fn remove(&mut self, kind: SearchEntityKind, key: &str) -> Option<SearchDocument> {
+        let id: DocumentId = (kind, key.to_owned());
+        let removed: IndexedDocument = self.documents.remove(&id)?;
+
+        for token in &removed.tokens {
+            if let Some(ids) = self.postings.get_mut(token) {
+                ids.remove(&id);
+
+                if ids.is_empty() {
+                    self.postings.remove(token);
+                }
+            }
+        }
+
+        for reference in &removed.document.references {
+            if let Some(ids) = self.referrers.get_mut(reference) {
+                ids.remove(&id);
+
+                if ids.is_empty() {
+                    self.referrers.remove(reference);
+                }
+            }
+        }
+
+        Some(removed.document)
+    }
+
+    pub fn clear(&mut self) {
+        self.documents.clear();
+        self.postings.clear();
+        self.referrers.clear();
+    }
+
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit> {
+        let query_tokens: Vec<String> = tokenize(query);
+
+        if query_tokens.is_empty() {
+            return vec![];
+        }
+
+        let mut scores: HashMap<&DocumentId, u32> = HashMap::new();
+
+        for (position, query_token) in query_tokens.iter().enumerate() {
+            let mut token_scores: HashMap<&DocumentId, u32> = HashMap::new();
+
+            for (token, score) in self.matching_tokens(query_token) {
+                for id in &self.postings[&token] {
+                    let weighted: u32 = if self.documents[id].title_tokens.contains(&token) {
+                        score
+                    } else {
+                        score / 2
+                    };
+
+                    let best: &mut u32 = token_scores.entry(id).or_default();
+                    *best = (*best).max(weighted);
+                }
+            }
+
+            if position == 0 {
+                scores = token_scores;
+            } else {
+                scores = scores
+                    .into_iter()
+                    .filter_map(|(id, total)| token_scores.get(id).map(|score| (id, total + score)))
+                    .collect();
+            }
+
+            if scores.is_empty() {
+                return vec![];
+            }
+        }
+
+        let folded_query: String = query_tokens.join(" ");
+        let mut hits: Vec<SearchHit> = scores
+            .into_iter()
+            .map(|(id, total)| {
+                let indexed: &IndexedDocument = &self.documents[id];
+                let bonus: u32 = if indexed.folded_title == folded_query {
+                    EXACT_TITLE_BONUS
+                } else {
+                    0
+                };
+
+                SearchHit {
+                    kind: indexed.document.kind,
+                    key: indexed.document.key.clone(),
+                    title: indexed.document.title.clone(),
+                    score: total / query_tokens.len() as u32 + bonus,
+                }
+            })
+            .collect();
+
+        hits.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then(a.title.len().cmp(&b.title.len()))
+                .then(a.title.cmp(&b.title))
+                .then(a.kind.cmp(&b.kind))
+                .then(a.key.cmp(&b.key))
+        });
+        hits.truncate(limit);
+        hits
+    }
+
+    fn matching_tokens(&self, query_token: &str) -> Vec<(String, u32)> {
+        let tolerance: usize = typo_tolerance(query_token);
+        let query_length: usize = query_token.chars().count();
+
+        self.postings
+            .keys()
+            .filter_map(|token| {
+                if token == query_token {
+                    return Some((token.clone(), EXACT_MATCH_SCORE));
+                }
+
+                if token.starts_with(query_token) {
+                    return Some((token.clone(), PREFIX_MATCH_SCORE));
+                }
+
+                if tolerance == 0 {
+                    return None;
+                }
+
+                if edit_distance(query_token, token) <= tolerance {
+                    return Some((token.clone(), TYPO_MATCH_SCORE));
+                }
+
+                let prefix: String = token.chars().take(query_length).collect();
+                if prefix.chars().count() == query_length && edit_distance(query_token, &prefix) <= tolerance {
+                    return Some((token.clone(), TYPO_PREFIX_MATCH_SCORE));
+                }
+
+                None
+            })
+            .collect()
+    }
+}
+
+fn typo_tolerance(token: &str) -> usize {
+    match token.chars().count() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SearchDocument, SearchEntityKind, SearchHit, SearchIndex};
+    use crate::domain::entities::{Brand, Category, Product, Store};
+
+    fn given_index() -> (SearchIndex, Product) {
+        let nestle: Brand = Brand::new("Nestlé".to_owned());
+        let dairy: Category = Category::new(None, "Dairy".to_owned());
+        let chocolate_milk: Product = Product::new(None, "Chocolate Milk 1L".to_owned(), nestle.clone(), dairy.clone());
+        let milk: Product = Product::new(None, "Milk".to_owned(), Brand::new("Emmi".to_owned()), dairy.clone());
+        let chocolate: Product = Product::new(
+            None,
+            "Dark Chocolate".to_owned(),
+            Brand::new("Lindt".to_owned()),
+            Category::default(),
+        );
+
+        let mut index: SearchIndex = SearchIndex::new();
+        index.upsert(SearchDocument::from(&nestle));
+        index.upsert(SearchDocument::from(&dairy));
+        index.upsert(SearchDocument::from(&chocolate_milk));
+        index.upsert(SearchDocument::from(&milk));
+        index.upsert(SearchDocument::from(&chocolate));
+        index.upsert(SearchDocument::from(&Store::new(None, "Migros Zürich".to_owned())));
+
+        (index, chocolate_milk)
+    }
+
+    fn titles(hits: &[SearchHit]) -> Vec<&str> {
+        hits.iter().map(|h| h.title.as_str()).collect()
+    }
+
+    macro_rules! search {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (query, expected): (&str, Vec<&str>) = $value;
+                let (index, _) = given_index();
+
+                let hits: Vec<SearchHit> = index.search(query, 10);
+                let result: Vec<&str> = titles(&hits);
+
+                assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+            }
+        )*
+        }
+    }
+
+    search! {
+        search_should_match_prefixes_of_all_query_tokens: ("choc milk", vec!["Chocolate Milk 1L"]),
+        search_should_fold_accents_and_case: ("NESTLE", vec!["Nestlé", "Chocolate Milk 1L"]),
+        search_should_fold_accents_in_query: ("zurich", vec!["Migros Zürich"]),
+        search_should_tolerate_typos: ("chocolat mlik", vec!["Chocolate Milk 1L"]),
+        search_should_tolerate_typos_in_prefixes: ("chco", vec!["Dark Chocolate", "Chocolate Milk 1L"]),
+        search_should_rank_exact_title_first: ("milk", vec!["Milk", "Chocolate Milk 1L"]),
+        search_should_rank_title_matches_above_context_matches: ("dairy", vec!["Dairy", "Milk", "Chocolate Milk 1L"]),
+        search_should_not_tolerate_typos_in_short_tokens: ("mlk", Vec::<&str>::new()),
+        search_with_empty_query_should_find_nothing: ("  ", Vec::<&str>::new()),
+        search_with_unmatched_token_should_find_nothing: ("choc tea", Vec::<&str>::new()),
+    }
+
+    #[test]
+    fn search_should_respect_limit() {
+        let (index, _) = given_index();
+
+        let result: usize = index.search("choc", 1).len();
+
+        assert_eq!(result, 1, "Expected {:?}, but got {:?}", 1, result);
+    }
+
+    #[test]
+    fn upsert_should_replace_existing_document() {
+        let (mut index, mut product) = given_index();
+        product.name = "Strawberry Milk 1L".to_owned();
+
+        index.upsert(SearchDocument::from(&product));
+
+        assert!(index.search("chocolate milk", 10).is_empty());
+        assert_eq!(titles(&index.search("strawberry", 10)), vec!["Strawberry Milk 1L"]);
+        assert_eq!(index.len(), 6);
+    }
+
+    #[test]
+    fn remove_should_drop_document_from_results() {
+        let (mut index, product) = given_index();
+
+        let removed: Option<SearchDocument> = index.remove(SearchEntityKind::Product, &product.id.to_string());
+
+        assert_eq!(removed, Some(SearchDocument::from(&product)));
+        assert!(index.search("choc milk", 10).is_empty());
+        assert_eq!(index.remove(SearchEntityKind::Product, &product.id.to_string()), None);
+    }
+
+    #[test]
+    fn upsert_of_a_renamed_category_should_reindex_its_products() {
+        let (mut index, product) = given_index();
+        let renamed: Category = Category {
+            name: "Milk Drinks".to_owned(),
+            ..product.category.clone()
+        };
+
+        index.upsert(SearchDocument::from(&renamed));
+
+        let hits: Vec<SearchHit> = index.search("drinks", 10);
+        let result: Vec<&str> = titles(&hits);
+        let expected: Vec<&str> = vec!["Milk Drinks", "Milk", "Chocolate Milk 1L"];
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert!(index.search("dairy", 10).is_empty());
+    }
+}
@@ -0,0 +1,140 @@
+use std::{
+    future,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use futures::{StreamExt, stream::BoxStream};
+
+use crate::domain::{
+    repositories::{BrandRepository, CategoryRepository, ProductRepository, StoreRepository},
+    search::{SearchDocument, SearchHit, SearchIndex},
+};
+
+/// The index is only a cache of what the repositories hold, so a panic while it was locked does not stop it from being used:
+/// the poisoned lock is taken over rather than failing every later write and search.
+#[derive(Debug, Clone, Default)]
+pub struct SearchService {
+    index: Arc<Mutex<SearchIndex>>,
+}
+
+impl SearchService {
+    pub fn new(index: Arc<Mutex<SearchIndex>>) -> Self {
+        Self { index }
+    }
+
+    /// Indexes every brand, category, store and product the repositories hold, leaving out those that cannot be read.
+    pub async fn build_from(
+        brands: &dyn BrandRepository,
+        categories: &dyn CategoryRepository,
+        stores: &dyn StoreRepository,
+        products: &dyn ProductRepository,
+    ) -> Self {
+        let search_service: SearchService = SearchService::default();
+
+        search_service.index_all(brands.stream_all()).await;
+        search_service.index_all(categories.stream_all()).await;
+        search_service.index_all(stores.stream_all()).await;
+        search_service.index_all(products.stream_all()).await;
+        search_service
+    }
+
+    pub fn index(&self) -> Arc<Mutex<SearchIndex>> {
+        Arc::clone(&self.index)
+    }
+
+    pub fn rebuild(&self, documents: impl IntoIterator<Item = SearchDocument>) {
+        let mut index = self.index.lock().unwrap_or_else(PoisonError::into_inner);
+
+        index.clear();
+        for document in documents {
+            index.upsert(document);
+        }
+    }
+
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit> {
+        self.index.lock().unwrap_or_else(PoisonError::into_inner).search(query, limit)
+    }
+
+    async fn index_all<T, E>(&self, entities: BoxStream<'_, Result<T, E>>)
+    where
+        for<'a> SearchDocument: From<&'a T>,
+    {
+        entities
+            .filter_map(|entity| future::ready(entity.ok()))
+            .for_each(|entity| {
+                self.index
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .upsert(SearchDocument::from(&entity));
+                future::ready(())
+            })
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{
+        entities::{Brand, Category, Product, Store},
+        search::{SearchDocument, SearchEntityKind, SearchHit, SearchService},
+        use_cases::repository_fakes::{BrandRepositoryFake, CategoryRepositoryFake, ProductRepositoryFake, StoreRepositoryFake},
+    };
+
+    #[test]
+    fn rebuild_should_replace_indexed_documents() {
+        let service: SearchService = SearchService::default();
+        let store: Store = Store::new(None, "Coop".to_owned());
+
+        service.rebuild(vec![SearchDocument::from(&Brand::new("Coop Naturaplan".to_owned()))]);
+        service.rebuild(vec![SearchDocument::from(&store)]);
+
+        let result: Vec<SearchHit> = service.search("coop", 10);
+        let expected: Vec<SearchHit> = vec![SearchHit {
+            kind: SearchEntityKind::Store,
+            key: store.id.to_string(),
+            title: "Coop".to_owned(),
+            score: 200,
+        }];
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[test]
+    fn search_should_find_product_by_partial_name() {
+        let service: SearchService = SearchService::default();
+        let product: Product = Product::new(
+            None,
+            "Chocolate Milk 1L".to_owned(),
+            Brand::new("Nestlé".to_owned()),
+            Category::new(None, "Dairy".to_owned()),
+        );
+
+        service.rebuild(vec![SearchDocument::from(&product)]);
+
+        let result: Vec<String> = service.search("choc milk", 10).into_iter().map(|h| h.key).collect();
+        let expected: Vec<String> = vec![product.id.to_string()];
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn build_from_should_index_every_readable_repository() {
+        let brand: Brand = Brand::new("Coop Naturaplan".to_owned());
+        let store: Store = Store::new(None, "Coop".to_owned());
+
+        let service: SearchService = SearchService::build_from(
+            &BrandRepositoryFake::with(vec![brand.clone()]),
+            &CategoryRepositoryFake::failing("Unable to read categories"),
+            &StoreRepositoryFake::with(vec![store.clone()]),
+            &ProductRepositoryFake::with(vec![]),
+        )
+        .await;
+
+        let mut result: Vec<String> = service.search("coop", 10).into_iter().map(|h| h.key).collect();
+        result.sort();
+        let mut expected: Vec<String> = vec![brand.name, store.id.to_string()];
+        expected.sort();
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+}
@@ -0,0 +1,104 @@
+use unicode_normalization::UnicodeNormalization;
+
+use crate::domain::entities::NamePolicy;
+
+/// Folds `text` as the default [`NamePolicy`] normalizes names, once compatibility characters such as ligatures are
+/// decomposed, so that searching agrees with the repositories on which names are the same.
+pub fn fold(text: &str) -> String {
+    NamePolicy::default().normalize(&text.nfkc().collect::<String>())
+}
+
+pub fn tokenize(text: &str) -> Vec<String> {
+    fold(text)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+/// Damerau-Levenshtein distance between `a` and `b`, a swap of two neighbouring characters counting as one edit.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut distances: Vec<Vec<usize>> = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in distances[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost: usize = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+
+            distances[i][j] = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distances[i][j] = distances[i][j].min(distances[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{edit_distance, fold, tokenize};
+
+    macro_rules! fold {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (input, expected): (&str, &str) = $value;
+
+                let result: String = fold(input);
+
+                assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+            }
+        )*
+        }
+    }
+
+    fold! {
+        fold_should_lowercase: ("Chocolate MILK", "chocolate milk"),
+        fold_should_remove_accents: ("Nestlé Crème Brûlée", "nestle creme brulee"),
+        fold_should_casefold_sharp_s: ("Straße", "strasse"),
+        fold_should_decompose_compatibility_characters: ("ﬁne", "fine"),
+    }
+
+    #[test]
+    fn tokenize_should_split_on_non_alphanumeric_characters() {
+        let result: Vec<String> = tokenize("Chocolate-Milk 1L, (Nestlé)");
+        let expected: Vec<String> = vec!["chocolate".to_owned(), "milk".to_owned(), "1l".to_owned(), "nestle".to_owned()];
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    macro_rules! edit_distance {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (a, b, expected): (&str, &str, usize) = $value;
+
+                let result: usize = edit_distance(a, b);
+
+                assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+            }
+        )*
+        }
+    }
+
+    edit_distance! {
+        edit_distance_of_equal_strings_should_be_zero: ("milk", "milk", 0),
+        edit_distance_should_count_substitutions: ("milk", "silk", 1),
+        edit_distance_should_count_transpositions_once: ("milk", "mlik", 1),
+        edit_distance_should_count_insertions_and_deletions: ("chocolate", "choclatte", 2),
+    }
+}
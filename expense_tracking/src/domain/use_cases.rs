@@ -1,6 +1,55 @@
 mod add_new_brand;
+mod add_new_category;
+mod add_new_product;
+mod add_new_store;
+mod compute_inflation_index;
+mod delete_category;
+mod delete_product;
+mod delete_store;
+mod entity_reference;
+mod find_duplicate_candidates;
+mod generate_spending_report;
+mod merge_duplicates;
+mod record_purchase;
+#[cfg(test)]
+pub(crate) mod repository_fakes;
 mod retrieve_all_brands_use_case;
+mod retrieve_all_categories;
+mod retrieve_all_products;
+mod retrieve_all_stores;
+mod retrieve_price_history;
+mod update_category;
+mod update_product;
+mod update_store;
 
 pub use add_new_brand::{AddNewBrandInteractor, AddNewBrandInteractorError, AddNewBrandOutputPort};
+pub use add_new_category::{AddNewCategoryInteractor, AddNewCategoryInteractorError, AddNewCategoryOutputPort};
+pub use add_new_product::{AddNewProductInteractor, AddNewProductInteractorError, AddNewProductOutputPort};
+pub use add_new_store::{AddNewStoreInteractor, AddNewStoreInteractorError, AddNewStoreOutputPort};
+pub use compute_inflation_index::{ComputeInflationIndexInteractor, ComputeInflationIndexInteractorError, ComputeInflationIndexOutputPort};
+pub use delete_category::{DeleteCategoryInteractor, DeleteCategoryInteractorError, DeleteCategoryOutputPort};
+pub use delete_product::{DeleteProductInteractor, DeleteProductInteractorError, DeleteProductOutputPort};
+pub use delete_store::{DeleteStoreInteractor, DeleteStoreInteractorError, DeleteStoreOutputPort};
+pub use entity_reference::EntityReference;
+pub use find_duplicate_candidates::{
+    DuplicateCandidate, FindDuplicateCandidatesInteractor, FindDuplicateCandidatesInteractorError, FindDuplicateCandidatesOutputPort,
+};
+pub use generate_spending_report::{
+    GenerateSpendingReportInteractor, GenerateSpendingReportInteractorError, GenerateSpendingReportOutputPort,
+};
+pub use merge_duplicates::{
+    MergeDuplicatesInteractor, MergeDuplicatesInteractorError, MergeDuplicatesOutputPort, MergeMode, MergeReport, MergeRequest,
+    MergeableKind,
+};
+pub use record_purchase::{
+    Receipt, ReceiptLine, RecordPurchaseInteractor, RecordPurchaseInteractorError, RecordPurchaseOutputPort, RecordedPurchase,
+};
 pub use retrieve_all_brands_use_case::RetrieveAllBrandsUseCase;
 pub use retrieve_all_brands_use_case::RetrieveAllBrandsUseCaseError;
+pub use retrieve_all_categories::{RetrieveAllCategoriesInteractor, RetrieveAllCategoriesInteractorError, RetrieveAllCategoriesOutputPort};
+pub use retrieve_all_products::{RetrieveAllProductsInteractor, RetrieveAllProductsInteractorError, RetrieveAllProductsOutputPort};
+pub use retrieve_all_stores::{RetrieveAllStoresInteractor, RetrieveAllStoresInteractorError, RetrieveAllStoresOutputPort};
+pub use retrieve_price_history::{RetrievePriceHistoryInteractor, RetrievePriceHistoryInteractorError, RetrievePriceHistoryOutputPort};
+pub use update_category::{UpdateCategoryInteractor, UpdateCategoryInteractorError, UpdateCategoryOutputPort};
+pub use update_product::{UpdateProductInteractor, UpdateProductInteractorError, UpdateProductOutputPort};
+pub use update_store::{UpdateStoreInteractor, UpdateStoreInteractorError, UpdateStoreOutputPort};
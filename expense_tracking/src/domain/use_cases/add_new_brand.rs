@@ -61,10 +61,15 @@ impl From<BrandRepositoryCreateError> for AddNewBrandInteractorError {
 #[cfg(test)]
 mod tests {
     use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use futures::stream::BoxStream;
 
     use crate::domain::{
         entities::Brand,
-        repositories::{BrandRepository, BrandRepositoryCreateError, BrandRepositoryRetrieveAllError},
+        repositories::{
+            BrandRepository, BrandRepositoryCreateError, BrandRepositoryDeleteError, BrandRepositoryRestoreError,
+            BrandRepositoryRetrieveAllError, BrandRepositoryUpdateError, DeleteBehaviour, Trashed,
+        },
         use_cases::AddNewBrandOutputPort,
     };
     use std::sync::Arc;
@@ -162,6 +167,30 @@ mod tests {
         async fn retrieve_all(&self) -> Result<Vec<Brand>, BrandRepositoryRetrieveAllError> {
             todo!()
         }
+
+        fn stream_all(&self) -> BoxStream<'_, Result<Brand, BrandRepositoryRetrieveAllError>> {
+            todo!()
+        }
+
+        async fn update(&self, _: &Brand) -> Result<Brand, BrandRepositoryUpdateError> {
+            todo!()
+        }
+
+        async fn delete(&self, _: &Brand, _: DeleteBehaviour<Brand>) -> Result<Brand, BrandRepositoryDeleteError> {
+            todo!()
+        }
+
+        async fn list_trashed(&self) -> Result<Vec<Trashed<Brand>>, BrandRepositoryRetrieveAllError> {
+            todo!()
+        }
+
+        async fn restore(&self, _: &Brand) -> Result<Brand, BrandRepositoryRestoreError> {
+            todo!()
+        }
+
+        async fn purge_older_than(&self, _: DateTime<Utc>) -> Result<Vec<Brand>, BrandRepositoryDeleteError> {
+            todo!()
+        }
     }
 
     struct NoOpUseCaseOutputPort {}
@@ -0,0 +1,135 @@
+use std::{any::Any, sync::Arc};
+
+use crate::domain::{
+    entities::Category,
+    repositories::{CategoryRepositoryError, SharedCategoryRepository},
+};
+
+pub trait AddNewCategoryOutputPort<T: Any>: Send + Sync {
+    fn apply(&self, _: Result<Category, AddNewCategoryInteractorError>) -> T;
+}
+
+pub struct AddNewCategoryInteractor<Output: Any> {
+    category_repository: SharedCategoryRepository,
+    presenter: Arc<dyn AddNewCategoryOutputPort<Output>>,
+}
+
+impl<Output: Any> AddNewCategoryInteractor<Output> {
+    pub fn new(category_repository: SharedCategoryRepository, presenter: Arc<dyn AddNewCategoryOutputPort<Output>>) -> Self {
+        Self {
+            category_repository,
+            presenter,
+        }
+    }
+
+    pub async fn execute(&self, name: String) -> Output {
+        self.presenter.apply(self.add(name).await)
+    }
+
+    /// A name another category already holds, even spelled differently, is refused by the repository under its name policy.
+    async fn add(&self, name: String) -> Result<Category, AddNewCategoryInteractorError> {
+        if name.trim().is_empty() {
+            return Err(AddNewCategoryInteractorError::InvalidName(format!(
+                "The name '{}' is not valid",
+                name
+            )));
+        }
+
+        let category: Category = Category::new(None, name);
+        self.category_repository.write().await.create_or_update(&category).await?;
+
+        Ok(category)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AddNewCategoryInteractorError {
+    InvalidName(String),
+    CategoryAlreadyExists,
+    UnableToSaveCategory(String),
+}
+
+impl From<CategoryRepositoryError> for AddNewCategoryInteractorError {
+    fn from(value: CategoryRepositoryError) -> Self {
+        match value {
+            CategoryRepositoryError::CategoryAlreadyExists => AddNewCategoryInteractorError::CategoryAlreadyExists,
+            CategoryRepositoryError::UnableToAccessStorage(details) => AddNewCategoryInteractorError::UnableToSaveCategory(details),
+            e => AddNewCategoryInteractorError::UnableToSaveCategory(format!("{:?}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::domain::{
+        entities::Category,
+        repositories::SharedCategoryRepository,
+        use_cases::{AddNewCategoryOutputPort, repository_fakes::CategoryRepositoryFake},
+    };
+
+    use super::{AddNewCategoryInteractor, AddNewCategoryInteractorError};
+
+    fn given_interactor(
+        category_repository: &SharedCategoryRepository,
+    ) -> AddNewCategoryInteractor<Result<Category, AddNewCategoryInteractorError>> {
+        AddNewCategoryInteractor::new(Arc::clone(category_repository), Arc::new(NoOpUseCaseOutputPort {}))
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_given_invalid_name() {
+        let category_repository: SharedCategoryRepository = CategoryRepositoryFake::default().shared();
+        let use_case: AddNewCategoryInteractor<Result<Category, AddNewCategoryInteractorError>> = given_interactor(&category_repository);
+
+        let result: Result<Category, AddNewCategoryInteractorError> = use_case.execute("   ".to_owned()).await;
+        let expected: Result<Category, AddNewCategoryInteractorError> =
+            Err(AddNewCategoryInteractorError::InvalidName("The name '   ' is not valid".to_owned()));
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_category_already_exists_under_another_spelling() {
+        let category_repository: SharedCategoryRepository =
+            CategoryRepositoryFake::with(vec![Category::new(None, "Épicerie".to_owned())]).shared();
+        let use_case: AddNewCategoryInteractor<Result<Category, AddNewCategoryInteractorError>> = given_interactor(&category_repository);
+
+        let result: Result<Category, AddNewCategoryInteractorError> = use_case.execute(" EPICERIE".to_owned()).await;
+        let expected: Result<Category, AddNewCategoryInteractorError> = Err(AddNewCategoryInteractorError::CategoryAlreadyExists);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_unable_to_save() {
+        let category_repository: SharedCategoryRepository = CategoryRepositoryFake::failing("Disk is full").shared();
+        let use_case: AddNewCategoryInteractor<Result<Category, AddNewCategoryInteractorError>> = given_interactor(&category_repository);
+
+        let result: Result<Category, AddNewCategoryInteractorError> = use_case.execute("Fruits".to_owned()).await;
+        let expected: Result<Category, AddNewCategoryInteractorError> =
+            Err(AddNewCategoryInteractorError::UnableToSaveCategory("Disk is full".to_owned()));
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn should_save_and_return_category_if_success() {
+        let category_repository: SharedCategoryRepository = CategoryRepositoryFake::default().shared();
+        let use_case: AddNewCategoryInteractor<Result<Category, AddNewCategoryInteractorError>> = given_interactor(&category_repository);
+
+        let result: Category = use_case.execute("Fruits".to_owned()).await.unwrap();
+        let stored: Vec<Category> = category_repository.read().await.retrieve_all().await.unwrap();
+
+        assert_eq!(result.name, "Fruits");
+        assert_eq!(stored, vec![result.clone()], "Expected {:?}, but got {:?}", vec![result], stored);
+    }
+
+    struct NoOpUseCaseOutputPort {}
+
+    impl AddNewCategoryOutputPort<Result<Category, AddNewCategoryInteractorError>> for NoOpUseCaseOutputPort {
+        fn apply(&self, result: Result<Category, AddNewCategoryInteractorError>) -> Result<Category, AddNewCategoryInteractorError> {
+            result
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use std::{any::Any, sync::Arc};
+
+use futures::{TryStreamExt, future};
+
+use crate::domain::{
+    entities::{Brand, Category, NamePolicy, Product},
+    repositories::{
+        BrandRepository, BrandRepositoryRetrieveAllError, CategoryRepositoryError, ProductRepositoryError, SharedCategoryRepository,
+        SharedProductRepository,
+    },
+    use_cases::EntityReference,
+};
+
+pub trait AddNewProductOutputPort<T: Any>: Send + Sync {
+    fn apply(&self, _: Result<Product, AddNewProductInteractorError>) -> T;
+}
+
+pub struct AddNewProductInteractor<Output: Any> {
+    brand_repository: Arc<dyn BrandRepository>,
+    category_repository: SharedCategoryRepository,
+    product_repository: SharedProductRepository,
+    presenter: Arc<dyn AddNewProductOutputPort<Output>>,
+    name_policy: NamePolicy,
+}
+
+impl<Output: Any> AddNewProductInteractor<Output> {
+    pub fn new(
+        brand_repository: Arc<dyn BrandRepository>,
+        category_repository: SharedCategoryRepository,
+        product_repository: SharedProductRepository,
+        presenter: Arc<dyn AddNewProductOutputPort<Output>>,
+    ) -> Self {
+        Self {
+            brand_repository,
+            category_repository,
+            product_repository,
+            presenter,
+            name_policy: NamePolicy::default(),
+        }
+    }
+
+    pub fn with_name_policy(self, name_policy: NamePolicy) -> Self {
+        Self { name_policy, ..self }
+    }
+
+    /// Adds a product of `brand`, which is designated by its name since it is how brands are identified, filed
+    /// under `category`. Two products are duplicates when they share both their name and their brand, which the
+    /// repository refuses.
+    pub async fn execute(&self, name: String, brand: String, category: EntityReference) -> Output {
+        self.presenter.apply(self.add(name, brand, category).await)
+    }
+
+    async fn add(&self, name: String, brand: String, category: EntityReference) -> Result<Product, AddNewProductInteractorError> {
+        if name.trim().is_empty() {
+            return Err(AddNewProductInteractorError::InvalidName(format!(
+                "The name '{}' is not valid",
+                name
+            )));
+        }
+
+        let brand: Brand = self
+            .brand_repository
+            .stream_all()
+            .try_filter(|b| future::ready(self.name_policy.matches(&b.name, &brand)))
+            .try_next()
+            .await?
+            .ok_or(AddNewProductInteractorError::BrandNotFound)?;
+        let category: Category = self
+            .category_repository
+            .read()
+            .await
+            .stream_all()
+            .try_filter(|c| future::ready(category.designates(&c.id, &c.name, &self.name_policy)))
+            .try_next()
+            .await?
+            .ok_or(AddNewProductInteractorError::CategoryNotFound)?;
+
+        let product: Product = Product::new(None, name, brand, category);
+        self.product_repository.write().await.create_or_update(&product).await?;
+
+        Ok(product)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AddNewProductInteractorError {
+    InvalidName(String),
+    BrandNotFound,
+    CategoryNotFound,
+    ProductAlreadyExists,
+    UnableToSaveProduct(String),
+}
+
+impl From<BrandRepositoryRetrieveAllError> for AddNewProductInteractorError {
+    fn from(value: BrandRepositoryRetrieveAllError) -> Self {
+        match value {
+            BrandRepositoryRetrieveAllError::UnableToRetrieveBrands(details) => AddNewProductInteractorError::UnableToSaveProduct(details),
+        }
+    }
+}
+
+impl From<CategoryRepositoryError> for AddNewProductInteractorError {
+    fn from(value: CategoryRepositoryError) -> Self {
+        match value {
+            CategoryRepositoryError::UnableToAccessStorage(details) => AddNewProductInteractorError::UnableToSaveProduct(details),
+            e => AddNewProductInteractorError::UnableToSaveProduct(format!("{:?}", e)),
+        }
+    }
+}
+
+impl From<ProductRepositoryError> for AddNewProductInteractorError {
+    fn from(value: ProductRepositoryError) -> Self {
+        match value {
+            ProductRepositoryError::BrandNotFound => AddNewProductInteractorError::BrandNotFound,
+            ProductRepositoryError::CategoryNotFound => AddNewProductInteractorError::CategoryNotFound,
+            ProductRepositoryError::ProductAlreadyExists => AddNewProductInteractorError::ProductAlreadyExists,
+            ProductRepositoryError::UnableToAccessStorage(details) => AddNewProductInteractorError::UnableToSaveProduct(details),
+            e => AddNewProductInteractorError::UnableToSaveProduct(format!("{:?}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::domain::{
+        entities::{Brand, Category, Product},
+        repositories::SharedProductRepository,
+        use_cases::{
+            AddNewProductOutputPort, EntityReference,
+            repository_fakes::{BrandRepositoryFake, CategoryRepositoryFake, ProductRepositoryFake},
+        },
+    };
+
+    use super::{AddNewProductInteractor, AddNewProductInteractorError};
+
+    fn given_interactor(
+        dairy: &Category,
+        product_repository: &SharedProductRepository,
+    ) -> AddNewProductInteractor<Result<Product, AddNewProductInteractorError>> {
+        AddNewProductInteractor::new(
+            Arc::new(BrandRepositoryFake::with(vec![
+                Brand::new("Nestlé".to_owned()),
+                Brand::new("Emmi".to_owned()),
+            ])),
+            CategoryRepositoryFake::with(vec![dairy.clone()]).shared(),
+            Arc::clone(product_repository),
+            Arc::new(NoOpUseCaseOutputPort {}),
+        )
+    }
+
+    macro_rules! failing_additions {
+        ($($name:ident: ($a:expr, $b:expr),)*) => {
+        $(
+            #[tokio::test]
+            async fn $name() {
+                let dairy: Category = Category::new(None, "Dairy".to_owned());
+                let existing: Product = Product::new(None, "Yogurt".to_owned(), Brand::new("Emmi".to_owned()), dairy.clone());
+                let product_repository: SharedProductRepository = ProductRepositoryFake::with(vec![existing]).shared();
+                let use_case: AddNewProductInteractor<Result<Product, AddNewProductInteractorError>> = given_interactor(&dairy, &product_repository);
+                let (name, brand, category): (&str, &str, EntityReference) = $a;
+                let expected: Result<Product, AddNewProductInteractorError> = $b;
+
+                let result: Result<Product, AddNewProductInteractorError> = use_case.execute(name.to_owned(), brand.to_owned(), category).await;
+
+                assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+            }
+        )*
+        }
+    }
+
+    failing_additions! {
+        should_fail_if_given_invalid_name: (("  ", "Emmi", EntityReference::Name("Dairy".to_owned())), Err(AddNewProductInteractorError::InvalidName("The name '  ' is not valid".to_owned()))),
+        should_fail_if_brand_is_unknown: (("Cheese", "Lindt", EntityReference::Name("Dairy".to_owned())), Err(AddNewProductInteractorError::BrandNotFound)),
+        should_fail_if_category_name_is_unknown: (("Cheese", "Emmi", EntityReference::Name("Fruits".to_owned())), Err(AddNewProductInteractorError::CategoryNotFound)),
+        should_fail_if_category_id_is_unknown: (("Cheese", "Emmi", EntityReference::Id(Category::default().id)), Err(AddNewProductInteractorError::CategoryNotFound)),
+        should_fail_if_brand_already_has_product: (("YOGURT", "emmi", EntityReference::Name("Dairy".to_owned())), Err(AddNewProductInteractorError::ProductAlreadyExists)),
+    }
+
+    #[tokio::test]
+    async fn should_resolve_brand_and_category_by_name() {
+        let dairy: Category = Category::new(None, "Dairy".to_owned());
+        let product_repository: SharedProductRepository = ProductRepositoryFake::default().shared();
+        let use_case: AddNewProductInteractor<Result<Product, AddNewProductInteractorError>> =
+            given_interactor(&dairy, &product_repository);
+
+        let result: Product = use_case
+            .execute("Yogurt".to_owned(), "nestle".to_owned(), EntityReference::Name(" dairy".to_owned()))
+            .await
+            .unwrap();
+
+        assert_eq!(result.brand, Brand::new("Nestlé".to_owned()));
+        assert_eq!(result.category, dairy);
+    }
+
+    #[tokio::test]
+    async fn should_resolve_category_by_id_and_save_product() {
+        let dairy: Category = Category::new(None, "Dairy".to_owned());
+        let product_repository: SharedProductRepository = ProductRepositoryFake::default().shared();
+        let use_case: AddNewProductInteractor<Result<Product, AddNewProductInteractorError>> =
+            given_interactor(&dairy, &product_repository);
+
+        let result: Product = use_case
+            .execute("Yogurt".to_owned(), "Emmi".to_owned(), EntityReference::Id(dairy.id))
+            .await
+            .unwrap();
+        let stored: Vec<Product> = product_repository.read().await.retrieve_all().await.unwrap();
+
+        assert_eq!(result.category, dairy);
+        assert_eq!(stored, vec![result.clone()], "Expected {:?}, but got {:?}", vec![result], stored);
+    }
+
+    struct NoOpUseCaseOutputPort {}
+
+    impl AddNewProductOutputPort<Result<Product, AddNewProductInteractorError>> for NoOpUseCaseOutputPort {
+        fn apply(&self, result: Result<Product, AddNewProductInteractorError>) -> Result<Product, AddNewProductInteractorError> {
+            result
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use std::{any::Any, sync::Arc};
+
+use crate::domain::{
+    entities::Store,
+    repositories::{SharedStoreRepository, StoreRepositoryError},
+};
+
+pub trait AddNewStoreOutputPort<T: Any>: Send + Sync {
+    fn apply(&self, _: Result<Store, AddNewStoreInteractorError>) -> T;
+}
+
+pub struct AddNewStoreInteractor<Output: Any> {
+    store_repository: SharedStoreRepository,
+    presenter: Arc<dyn AddNewStoreOutputPort<Output>>,
+}
+
+impl<Output: Any> AddNewStoreInteractor<Output> {
+    pub fn new(store_repository: SharedStoreRepository, presenter: Arc<dyn AddNewStoreOutputPort<Output>>) -> Self {
+        Self {
+            store_repository,
+            presenter,
+        }
+    }
+
+    pub async fn execute(&self, name: String) -> Output {
+        self.presenter.apply(self.add(name).await)
+    }
+
+    /// A name another store already holds, even spelled differently, is refused by the repository under its name policy.
+    async fn add(&self, name: String) -> Result<Store, AddNewStoreInteractorError> {
+        if name.trim().is_empty() {
+            return Err(AddNewStoreInteractorError::InvalidName(format!("The name '{}' is not valid", name)));
+        }
+
+        let store: Store = Store::new(None, name);
+        self.store_repository.write().await.create_or_update(&store).await?;
+
+        Ok(store)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AddNewStoreInteractorError {
+    InvalidName(String),
+    StoreAlreadyExists,
+    UnableToSaveStore(String),
+}
+
+impl From<StoreRepositoryError> for AddNewStoreInteractorError {
+    fn from(value: StoreRepositoryError) -> Self {
+        match value {
+            StoreRepositoryError::StoreAlreadyExists => AddNewStoreInteractorError::StoreAlreadyExists,
+            StoreRepositoryError::UnableToAccessStorage(details) => AddNewStoreInteractorError::UnableToSaveStore(details),
+            e => AddNewStoreInteractorError::UnableToSaveStore(format!("{:?}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::domain::{
+        entities::Store,
+        repositories::SharedStoreRepository,
+        use_cases::{AddNewStoreOutputPort, repository_fakes::StoreRepositoryFake},
+    };
+
+    use super::{AddNewStoreInteractor, AddNewStoreInteractorError};
+
+    fn given_interactor(store_repository: &SharedStoreRepository) -> AddNewStoreInteractor<Result<Store, AddNewStoreInteractorError>> {
+        AddNewStoreInteractor::new(Arc::clone(store_repository), Arc::new(NoOpUseCaseOutputPort {}))
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_given_invalid_name() {
+        let store_repository: SharedStoreRepository = StoreRepositoryFake::default().shared();
+        let use_case: AddNewStoreInteractor<Result<Store, AddNewStoreInteractorError>> = given_interactor(&store_repository);
+
+        let result: Result<Store, AddNewStoreInteractorError> = use_case.execute("   ".to_owned()).await;
+        let expected: Result<Store, AddNewStoreInteractorError> =
+            Err(AddNewStoreInteractorError::InvalidName("The name '   ' is not valid".to_owned()));
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_store_already_exists_under_another_spelling() {
+        let store_repository: SharedStoreRepository = StoreRepositoryFake::with(vec![Store::new(None, "Coop Pronto".to_owned())]).shared();
+        let use_case: AddNewStoreInteractor<Result<Store, AddNewStoreInteractorError>> = given_interactor(&store_repository);
+
+        let result: Result<Store, AddNewStoreInteractorError> = use_case.execute(" coop pronto".to_owned()).await;
+        let expected: Result<Store, AddNewStoreInteractorError> = Err(AddNewStoreInteractorError::StoreAlreadyExists);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_unable_to_save() {
+        let store_repository: SharedStoreRepository = StoreRepositoryFake::failing("Disk is full").shared();
+        let use_case: AddNewStoreInteractor<Result<Store, AddNewStoreInteractorError>> = given_interactor(&store_repository);
+
+        let result: Result<Store, AddNewStoreInteractorError> = use_case.execute("Migros".to_owned()).await;
+        let expected: Result<Store, AddNewStoreInteractorError> =
+            Err(AddNewStoreInteractorError::UnableToSaveStore("Disk is full".to_owned()));
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn should_save_and_return_store_if_success() {
+        let store_repository: SharedStoreRepository = StoreRepositoryFake::default().shared();
+        let use_case: AddNewStoreInteractor<Result<Store, AddNewStoreInteractorError>> = given_interactor(&store_repository);
+
+        let result: Store = use_case.execute("Migros".to_owned()).await.unwrap();
+        let stored: Vec<Store> = store_repository.read().await.retrieve_all().await.unwrap();
+
+        assert_eq!(result.name, "Migros");
+        assert_eq!(stored, vec![result.clone()], "Expected {:?}, but got {:?}", vec![result], stored);
+    }
+
+    struct NoOpUseCaseOutputPort {}
+
+    impl AddNewStoreOutputPort<Result<Store, AddNewStoreInteractorError>> for NoOpUseCaseOutputPort {
+        fn apply(&self, result: Result<Store, AddNewStoreInteractorError>) -> Result<Store, AddNewStoreInteractorError> {
+            result
+        }
+    }
+}
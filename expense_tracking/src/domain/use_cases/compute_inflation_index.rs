@@ -0,0 +1,168 @@
+use std::{any::Any, sync::Arc};
+
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+
+use crate::domain::{
+    reports::{IndexFormula, InflationIndex, InflationIndexBuilder},
+    repositories::{SharedTransactionRepository, TransactionRepositoryError},
+};
+
+pub trait ComputeInflationIndexOutputPort<T: Any>: Send + Sync {
+    fn apply(&self, _: Result<InflationIndex, ComputeInflationIndexInteractorError>) -> T;
+}
+
+pub struct ComputeInflationIndexInteractor<Output: Any> {
+    transaction_repository: SharedTransactionRepository,
+    presenter: Arc<dyn ComputeInflationIndexOutputPort<Output>>,
+}
+
+impl<Output: Any> ComputeInflationIndexInteractor<Output> {
+    pub fn new(transaction_repository: SharedTransactionRepository, presenter: Arc<dyn ComputeInflationIndexOutputPort<Output>>) -> Self {
+        Self {
+            transaction_repository,
+            presenter,
+        }
+    }
+
+    /// Computes the monthly index of the prices paid between `from`, included, and `to`, excluded.
+    pub async fn execute(&self, from: DateTime<Utc>, to: DateTime<Utc>, formula: IndexFormula) -> Output {
+        self.presenter.apply(self.compute(from, to, formula).await)
+    }
+
+    async fn compute(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        formula: IndexFormula,
+    ) -> Result<InflationIndex, ComputeInflationIndexInteractorError> {
+        if from >= to {
+            return Err(ComputeInflationIndexInteractorError::InvalidRange);
+        }
+
+        let repository = self.transaction_repository.read().await;
+        let builder: InflationIndexBuilder = repository
+            .stream_all()
+            .try_fold(
+                InflationIndexBuilder::new(from, to, formula),
+                |mut builder, transaction| async move {
+                    builder.add(&transaction);
+                    Ok(builder)
+                },
+            )
+            .await?;
+
+        Ok(builder.build())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ComputeInflationIndexInteractorError {
+    InvalidRange,
+    UnableToRetrieveTransactions(String),
+}
+
+impl From<TransactionRepositoryError> for ComputeInflationIndexInteractorError {
+    fn from(value: TransactionRepositoryError) -> Self {
+        match value {
+            TransactionRepositoryError::UnableToAccessStorage(details) => {
+                ComputeInflationIndexInteractorError::UnableToRetrieveTransactions(details)
+            }
+            e => ComputeInflationIndexInteractorError::UnableToRetrieveTransactions(format!("{:?}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::{DateTime, Utc};
+
+    use crate::domain::{
+        entities::{Brand, Category, Item, Product, Store, Transaction, Unit},
+        reports::{IndexFormula, InflationIndex},
+        use_cases::{ComputeInflationIndexOutputPort, repository_fakes::TransactionRepositoryFake},
+    };
+
+    use super::{ComputeInflationIndexInteractor, ComputeInflationIndexInteractorError};
+
+    type Index = Result<InflationIndex, ComputeInflationIndexInteractorError>;
+
+    #[tokio::test]
+    async fn execute_should_return_err_if_range_is_reversed() {
+        let use_case: ComputeInflationIndexInteractor<Index> =
+            ComputeInflationIndexInteractor::new(TransactionRepositoryFake::default().shared(), Arc::new(NoOpUseCaseOutputPort {}));
+
+        let result: Index = use_case
+            .execute(at("2024-06-01T00:00:00Z"), at("2024-01-01T00:00:00Z"), IndexFormula::Laspeyres)
+            .await;
+        let expected: Index = Err(ComputeInflationIndexInteractorError::InvalidRange);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn execute_should_return_err_if_unable_to_retrieve_transactions() {
+        let use_case: ComputeInflationIndexInteractor<Index> = ComputeInflationIndexInteractor::new(
+            TransactionRepositoryFake::failing("Aenean vel orci").shared(),
+            Arc::new(NoOpUseCaseOutputPort {}),
+        );
+
+        let result: Index = use_case
+            .execute(at("2024-01-01T00:00:00Z"), at("2024-06-01T00:00:00Z"), IndexFormula::Fisher)
+            .await;
+        let expected: Index = Err(ComputeInflationIndexInteractorError::UnableToRetrieveTransactions(
+            "Aenean vel orci".to_owned(),
+        ));
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn execute_should_index_the_prices_paid() {
+        let store: Store = Store::new(None, "Coop".to_owned());
+        let coffee: Product = Product::new(
+            None,
+            "Coffee".to_owned(),
+            Brand::new("Chicco d'Oro".to_owned()),
+            Category::new(None, "Pantry".to_owned()),
+        );
+        let transactions: Vec<Transaction> = [("2024-01-08T10:00:00Z", 8.0), ("2024-02-08T10:00:00Z", 8.4)]
+            .iter()
+            .map(|(datetime, price)| {
+                Transaction::new(
+                    None,
+                    vec![Item::new(None, coffee.clone(), Unit::Kilograms(0.5), *price)],
+                    store.clone(),
+                    at(datetime),
+                )
+            })
+            .collect();
+        let use_case: ComputeInflationIndexInteractor<Index> = ComputeInflationIndexInteractor::new(
+            TransactionRepositoryFake::with(transactions).shared(),
+            Arc::new(NoOpUseCaseOutputPort {}),
+        );
+
+        let result: InflationIndex = use_case
+            .execute(at("2024-01-01T00:00:00Z"), at("2024-06-01T00:00:00Z"), IndexFormula::Laspeyres)
+            .await
+            .unwrap();
+        let values: Vec<f64> = result.overall.points.iter().map(|p| p.value).collect();
+        let expected: Vec<f64> = vec![100.0, 105.0];
+
+        assert_eq!(values, expected, "Expected {:?}, but got {:?}", expected, values);
+    }
+
+    fn at(datetime: &str) -> DateTime<Utc> {
+        datetime.parse().unwrap()
+    }
+
+    struct NoOpUseCaseOutputPort {}
+
+    impl ComputeInflationIndexOutputPort<Index> for NoOpUseCaseOutputPort {
+        fn apply(&self, result: Index) -> Index {
+            result
+        }
+    }
+}
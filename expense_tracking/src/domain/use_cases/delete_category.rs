@@ -0,0 +1,129 @@
+use std::{any::Any, sync::Arc};
+
+use uuid_b64::UuidB64;
+
+use crate::domain::{
+    entities::Category,
+    repositories::{CategoryRepositoryError, DeleteBehaviour, SharedCategoryRepository},
+};
+
+pub trait DeleteCategoryOutputPort<T: Any>: Send + Sync {
+    fn apply(&self, _: Result<Category, DeleteCategoryInteractorError>) -> T;
+}
+
+pub struct DeleteCategoryInteractor<Output: Any> {
+    category_repository: SharedCategoryRepository,
+    presenter: Arc<dyn DeleteCategoryOutputPort<Output>>,
+}
+
+impl<Output: Any> DeleteCategoryInteractor<Output> {
+    pub fn new(category_repository: SharedCategoryRepository, presenter: Arc<dyn DeleteCategoryOutputPort<Output>>) -> Self {
+        Self {
+            category_repository,
+            presenter,
+        }
+    }
+
+    /// Moves the category to the trash, `behaviour` deciding what happens to the products filed under it.
+    pub async fn execute(&self, id: UuidB64, behaviour: DeleteBehaviour<Category>) -> Output {
+        let result: Result<Category, DeleteCategoryInteractorError> = self
+            .category_repository
+            .write()
+            .await
+            .delete(&id, behaviour)
+            .await
+            .map_err(|e: CategoryRepositoryError| e.into());
+
+        self.presenter.apply(result)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DeleteCategoryInteractorError {
+    CategoryNotFound,
+    CategoryStillReferenced,
+    ReplacementCategoryNotFound,
+    UnableToDeleteCategory(String),
+}
+
+impl From<CategoryRepositoryError> for DeleteCategoryInteractorError {
+    fn from(value: CategoryRepositoryError) -> Self {
+        match value {
+            CategoryRepositoryError::CategoryNotFound => DeleteCategoryInteractorError::CategoryNotFound,
+            CategoryRepositoryError::CategoryStillReferenced => DeleteCategoryInteractorError::CategoryStillReferenced,
+            CategoryRepositoryError::ReplacementCategoryNotFound => DeleteCategoryInteractorError::ReplacementCategoryNotFound,
+            CategoryRepositoryError::UnableToAccessStorage(details) => DeleteCategoryInteractorError::UnableToDeleteCategory(details),
+            e => DeleteCategoryInteractorError::UnableToDeleteCategory(format!("{:?}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::domain::{
+        entities::Category,
+        repositories::{CategoryRepositoryError, DeleteBehaviour, SharedCategoryRepository},
+        use_cases::{DeleteCategoryOutputPort, repository_fakes::CategoryRepositoryFake},
+    };
+
+    use super::{DeleteCategoryInteractor, DeleteCategoryInteractorError};
+
+    macro_rules! error_conversion {
+        ($($name:ident: ($a:expr, $b:expr),)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let error: CategoryRepositoryError = $a;
+                let expected: DeleteCategoryInteractorError = $b;
+
+                let result: DeleteCategoryInteractorError = error.into();
+
+                assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+            }
+        )*
+        }
+    }
+
+    error_conversion! {
+        should_convert_still_referenced: (CategoryRepositoryError::CategoryStillReferenced, DeleteCategoryInteractorError::CategoryStillReferenced),
+        should_convert_replacement_not_found: (CategoryRepositoryError::ReplacementCategoryNotFound, DeleteCategoryInteractorError::ReplacementCategoryNotFound),
+        should_convert_storage_failure: (CategoryRepositoryError::UnableToAccessStorage("Locked".to_owned()), DeleteCategoryInteractorError::UnableToDeleteCategory("Locked".to_owned())),
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_category_does_not_exist() {
+        let category_repository: SharedCategoryRepository = CategoryRepositoryFake::default().shared();
+        let use_case: DeleteCategoryInteractor<Result<Category, DeleteCategoryInteractorError>> =
+            DeleteCategoryInteractor::new(Arc::clone(&category_repository), Arc::new(NoOpUseCaseOutputPort {}));
+
+        let result: Result<Category, DeleteCategoryInteractorError> =
+            use_case.execute(Category::default().id, DeleteBehaviour::Restrict).await;
+        let expected: Result<Category, DeleteCategoryInteractorError> = Err(DeleteCategoryInteractorError::CategoryNotFound);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn should_return_deleted_category_if_success() {
+        let fruits: Category = Category::new(None, "Fruits".to_owned());
+        let category_repository: SharedCategoryRepository = CategoryRepositoryFake::with(vec![fruits.clone()]).shared();
+        let use_case: DeleteCategoryInteractor<Result<Category, DeleteCategoryInteractorError>> =
+            DeleteCategoryInteractor::new(Arc::clone(&category_repository), Arc::new(NoOpUseCaseOutputPort {}));
+
+        let result: Result<Category, DeleteCategoryInteractorError> = use_case.execute(fruits.id, DeleteBehaviour::Restrict).await;
+        let expected: Result<Category, DeleteCategoryInteractorError> = Ok(fruits);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert!(category_repository.read().await.retrieve_all().await.unwrap().is_empty());
+    }
+
+    struct NoOpUseCaseOutputPort {}
+
+    impl DeleteCategoryOutputPort<Result<Category, DeleteCategoryInteractorError>> for NoOpUseCaseOutputPort {
+        fn apply(&self, result: Result<Category, DeleteCategoryInteractorError>) -> Result<Category, DeleteCategoryInteractorError> {
+            result
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use std::{any::Any, sync::Arc};
+
+use uuid_b64::UuidB64;
+
+use crate::domain::{
+    entities::Product,
+    repositories::{DeleteBehaviour, ProductRepositoryError, SharedProductRepository},
+};
+
+pub trait DeleteProductOutputPort<T: Any>: Send + Sync {
+    fn apply(&self, _: Result<Product, DeleteProductInteractorError>) -> T;
+}
+
+pub struct DeleteProductInteractor<Output: Any> {
+    product_repository: SharedProductRepository,
+    presenter: Arc<dyn DeleteProductOutputPort<Output>>,
+}
+
+impl<Output: Any> DeleteProductInteractor<Output> {
+    pub fn new(product_repository: SharedProductRepository, presenter: Arc<dyn DeleteProductOutputPort<Output>>) -> Self {
+        Self {
+            product_repository,
+            presenter,
+        }
+    }
+
+    /// Moves the product to the trash, `behaviour` deciding what happens to the transactions listing it.
+    pub async fn execute(&self, id: UuidB64, behaviour: DeleteBehaviour<Product>) -> Output {
+        let result: Result<Product, DeleteProductInteractorError> = self
+            .product_repository
+            .write()
+            .await
+            .delete(&id, behaviour)
+            .await
+            .map_err(|e: ProductRepositoryError| e.into());
+
+        self.presenter.apply(result)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DeleteProductInteractorError {
+    ProductNotFound,
+    ProductStillReferenced,
+    ReplacementProductNotFound,
+    UnableToDeleteProduct(String),
+}
+
+impl From<ProductRepositoryError> for DeleteProductInteractorError {
+    fn from(value: ProductRepositoryError) -> Self {
+        match value {
+            ProductRepositoryError::ProductNotFound => DeleteProductInteractorError::ProductNotFound,
+            ProductRepositoryError::ProductStillReferenced => DeleteProductInteractorError::ProductStillReferenced,
+            ProductRepositoryError::ReplacementProductNotFound => DeleteProductInteractorError::ReplacementProductNotFound,
+            ProductRepositoryError::UnableToAccessStorage(details) => DeleteProductInteractorError::UnableToDeleteProduct(details),
+            e => DeleteProductInteractorError::UnableToDeleteProduct(format!("{:?}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::domain::{
+        entities::{Brand, Category, Product},
+        repositories::{DeleteBehaviour, ProductRepositoryError, SharedProductRepository},
+        use_cases::{DeleteProductOutputPort, repository_fakes::ProductRepositoryFake},
+    };
+
+    use super::{DeleteProductInteractor, DeleteProductInteractorError};
+
+    macro_rules! error_conversion {
+        ($($name:ident: ($a:expr, $b:expr),)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let error: ProductRepositoryError = $a;
+                let expected: DeleteProductInteractorError = $b;
+
+                let result: DeleteProductInteractorError = error.into();
+
+                assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+            }
+        )*
+        }
+    }
+
+    error_conversion! {
+        should_convert_still_referenced: (ProductRepositoryError::ProductStillReferenced, DeleteProductInteractorError::ProductStillReferenced),
+        should_convert_replacement_not_found: (ProductRepositoryError::ReplacementProductNotFound, DeleteProductInteractorError::ReplacementProductNotFound),
+        should_convert_storage_failure: (ProductRepositoryError::UnableToAccessStorage("Locked".to_owned()), DeleteProductInteractorError::UnableToDeleteProduct("Locked".to_owned())),
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_product_does_not_exist() {
+        let product_repository: SharedProductRepository = ProductRepositoryFake::default().shared();
+        let use_case: DeleteProductInteractor<Result<Product, DeleteProductInteractorError>> =
+            DeleteProductInteractor::new(Arc::clone(&product_repository), Arc::new(NoOpUseCaseOutputPort {}));
+
+        let result: Result<Product, DeleteProductInteractorError> =
+            use_case.execute(Product::default().id, DeleteBehaviour::Restrict).await;
+        let expected: Result<Product, DeleteProductInteractorError> = Err(DeleteProductInteractorError::ProductNotFound);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn should_return_deleted_product_if_success() {
+        let yogurt: Product = Product::new(None, "Yogurt".to_owned(), Brand::new("Emmi".to_owned()), Category::default());
+        let product_repository: SharedProductRepository = ProductRepositoryFake::with(vec![yogurt.clone()]).shared();
+        let use_case: DeleteProductInteractor<Result<Product, DeleteProductInteractorError>> =
+            DeleteProductInteractor::new(Arc::clone(&product_repository), Arc::new(NoOpUseCaseOutputPort {}));
+
+        let result: Result<Product, DeleteProductInteractorError> = use_case.execute(yogurt.id, DeleteBehaviour::Restrict).await;
+        let expected: Result<Product, DeleteProductInteractorError> = Ok(yogurt);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert!(product_repository.read().await.retrieve_all().await.unwrap().is_empty());
+    }
+
+    struct NoOpUseCaseOutputPort {}
+
+    impl DeleteProductOutputPort<Result<Product, DeleteProductInteractorError>> for NoOpUseCaseOutputPort {
+        fn apply(&self, result: Result<Product, DeleteProductInteractorError>) -> Result<Product, DeleteProductInteractorError> {
+            result
+        }
+    }
+}
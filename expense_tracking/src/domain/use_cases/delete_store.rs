@@ -0,0 +1,128 @@
+use std::{any::Any, sync::Arc};
+
+use uuid_b64::UuidB64;
+
+use crate::domain::{
+    entities::Store,
+    repositories::{DeleteBehaviour, SharedStoreRepository, StoreRepositoryError},
+};
+
+pub trait DeleteStoreOutputPort<T: Any>: Send + Sync {
+    fn apply(&self, _: Result<Store, DeleteStoreInteractorError>) -> T;
+}
+
+pub struct DeleteStoreInteractor<Output: Any> {
+    store_repository: SharedStoreRepository,
+    presenter: Arc<dyn DeleteStoreOutputPort<Output>>,
+}
+
+impl<Output: Any> DeleteStoreInteractor<Output> {
+    pub fn new(store_repository: SharedStoreRepository, presenter: Arc<dyn DeleteStoreOutputPort<Output>>) -> Self {
+        Self {
+            store_repository,
+            presenter,
+        }
+    }
+
+    /// Moves the store to the trash, `behaviour` deciding what happens to the transactions made there.
+    pub async fn execute(&self, id: UuidB64, behaviour: DeleteBehaviour<Store>) -> Output {
+        let result: Result<Store, DeleteStoreInteractorError> = self
+            .store_repository
+            .write()
+            .await
+            .delete(&id, behaviour)
+            .await
+            .map_err(|e: StoreRepositoryError| e.into());
+
+        self.presenter.apply(result)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DeleteStoreInteractorError {
+    StoreNotFound,
+    StoreStillReferenced,
+    ReplacementStoreNotFound,
+    UnableToDeleteStore(String),
+}
+
+impl From<StoreRepositoryError> for DeleteStoreInteractorError {
+    fn from(value: StoreRepositoryError) -> Self {
+        match value {
+            StoreRepositoryError::StoreNotFound => DeleteStoreInteractorError::StoreNotFound,
+            StoreRepositoryError::StoreStillReferenced => DeleteStoreInteractorError::StoreStillReferenced,
+            StoreRepositoryError::ReplacementStoreNotFound => DeleteStoreInteractorError::ReplacementStoreNotFound,
+            StoreRepositoryError::UnableToAccessStorage(details) => DeleteStoreInteractorError::UnableToDeleteStore(details),
+            e => DeleteStoreInteractorError::UnableToDeleteStore(format!("{:?}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::domain::{
+        entities::Store,
+        repositories::{DeleteBehaviour, SharedStoreRepository, StoreRepositoryError},
+        use_cases::{DeleteStoreOutputPort, repository_fakes::StoreRepositoryFake},
+    };
+
+    use super::{DeleteStoreInteractor, DeleteStoreInteractorError};
+
+    macro_rules! error_conversion {
+        ($($name:ident: ($a:expr, $b:expr),)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let error: StoreRepositoryError = $a;
+                let expected: DeleteStoreInteractorError = $b;
+
+                let result: DeleteStoreInteractorError = error.into();
+
+                assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+            }
+        )*
+        }
+    }
+
+    error_conversion! {
+        should_convert_still_referenced: (StoreRepositoryError::StoreStillReferenced, DeleteStoreInteractorError::StoreStillReferenced),
+        should_convert_replacement_not_found: (StoreRepositoryError::ReplacementStoreNotFound, DeleteStoreInteractorError::ReplacementStoreNotFound),
+        should_convert_storage_failure: (StoreRepositoryError::UnableToAccessStorage("Locked".to_owned()), DeleteStoreInteractorError::UnableToDeleteStore("Locked".to_owned())),
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_store_does_not_exist() {
+        let store_repository: SharedStoreRepository = StoreRepositoryFake::default().shared();
+        let use_case: DeleteStoreInteractor<Result<Store, DeleteStoreInteractorError>> =
+            DeleteStoreInteractor::new(Arc::clone(&store_repository), Arc::new(NoOpUseCaseOutputPort {}));
+
+        let result: Result<Store, DeleteStoreInteractorError> = use_case.execute(Store::default().id, DeleteBehaviour::Restrict).await;
+        let expected: Result<Store, DeleteStoreInteractorError> = Err(DeleteStoreInteractorError::StoreNotFound);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn should_return_deleted_store_if_success() {
+        let migros: Store = Store::new(None, "Migros".to_owned());
+        let store_repository: SharedStoreRepository = StoreRepositoryFake::with(vec![migros.clone()]).shared();
+        let use_case: DeleteStoreInteractor<Result<Store, DeleteStoreInteractorError>> =
+            DeleteStoreInteractor::new(Arc::clone(&store_repository), Arc::new(NoOpUseCaseOutputPort {}));
+
+        let result: Result<Store, DeleteStoreInteractorError> = use_case.execute(migros.id, DeleteBehaviour::Restrict).await;
+        let expected: Result<Store, DeleteStoreInteractorError> = Ok(migros);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert!(store_repository.read().await.retrieve_all().await.unwrap().is_empty());
+    }
+
+    struct NoOpUseCaseOutputPort {}
+
+    impl DeleteStoreOutputPort<Result<Store, DeleteStoreInteractorError>> for NoOpUseCaseOutputPort {
+        fn apply(&self, result: Result<Store, DeleteStoreInteractorError>) -> Result<Store, DeleteStoreInteractorError> {
+            result
+        }
+    }
+}
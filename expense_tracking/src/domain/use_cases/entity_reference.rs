@@ -0,0 +1,19 @@
+use uuid_b64::UuidB64;
+
+use crate::domain::entities::NamePolicy;
+
+/// Designates an existing entity the way a front-end knows it, either by its id or by its name.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EntityReference {
+    Id(UuidB64),
+    Name(String),
+}
+
+impl EntityReference {
+    pub fn designates(&self, id: &UuidB64, name: &str, name_policy: &NamePolicy) -> bool {
+        match self {
+            EntityReference::Id(reference) => reference == id,
+            EntityReference::Name(reference) => name_policy.matches(reference, name),
+        }
+    }
+}
@@ -0,0 +1,305 @@
+use std::{any::Any, collections::BTreeMap, sync::Arc};
+
+use futures::{TryStreamExt, future};
+
+use crate::domain::{
+    repositories::{
+        BrandRepository, BrandRepositoryRetrieveAllError, CategoryRepositoryError, ProductRepositoryError, SharedCategoryRepository,
+        SharedProductRepository, SharedStoreRepository, StoreRepositoryError,
+    },
+    search::{edit_distance, tokenize},
+    use_cases::MergeableKind,
+};
+
+/// An entity which may duplicate others, designated by the key a `MergeRequest` expects.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateCandidate {
+    pub key: String,
+    pub name: String,
+}
+
+pub trait FindDuplicateCandidatesOutputPort<T: Any>: Send + Sync {
+    fn apply(&self, _: Result<Vec<Vec<DuplicateCandidate>>, FindDuplicateCandidatesInteractorError>) -> T;
+}
+
+pub struct FindDuplicateCandidatesInteractor<Output: Any> {
+    brand_repository: Arc<dyn BrandRepository>,
+    category_repository: SharedCategoryRepository,
+    store_repository: SharedStoreRepository,
+    product_repository: SharedProductRepository,
+    presenter: Arc<dyn FindDuplicateCandidatesOutputPort<Output>>,
+}
+
+impl<Output: Any> FindDuplicateCandidatesInteractor<Output> {
+    pub fn new(
+        brand_repository: Arc<dyn BrandRepository>,
+        category_repository: SharedCategoryRepository,
+        store_repository: SharedStoreRepository,
+        product_repository: SharedProductRepository,
+        presenter: Arc<dyn FindDuplicateCandidatesOutputPort<Output>>,
+    ) -> Self {
+        Self {
+            brand_repository,
+            category_repository,
+            store_repository,
+            product_repository,
+            presenter,
+        }
+    }
+
+    /// Groups the entities of `kind` whose names look alike, leaving out those without any look-alike. Products
+    /// are only compared with the products of the same brand.
+    pub async fn execute(&self, kind: MergeableKind) -> Output {
+        self.presenter.apply(self.find(kind).await)
+    }
+
+    async fn find(&self, kind: MergeableKind) -> Result<Vec<Vec<DuplicateCandidate>>, FindDuplicateCandidatesInteractorError> {
+        let scopes: Vec<Vec<DuplicateCandidate>> = match kind {
+            MergeableKind::Brand => vec![
+                self.brand_repository
+                    .stream_all()
+                    .map_ok(|b| DuplicateCandidate {
+                        key: b.name.clone(),
+                        name: b.name,
+                    })
+                    .try_collect()
+                    .await?,
+            ],
+            MergeableKind::Category => vec![
+                self.category_repository
+                    .read()
+                    .await
+                    .stream_all()
+                    .map_ok(|c| DuplicateCandidate {
+                        key: c.id.to_string(),
+                        name: c.name,
+                    })
+                    .try_collect()
+                    .await?,
+            ],
+            MergeableKind::Store => vec![
+                self.store_repository
+                    .read()
+                    .await
+                    .stream_all()
+                    .map_ok(|s| DuplicateCandidate {
+                        key: s.id.to_string(),
+                        name: s.name,
+                    })
+                    .try_collect()
+                    .await?,
+            ],
+            MergeableKind::Product => {
+                let by_brand: BTreeMap<String, Vec<DuplicateCandidate>> = self
+                    .product_repository
+                    .read()
+                    .await
+                    .stream_all()
+                    .try_fold(
+                        BTreeMap::new(),
+                        |mut by_brand: BTreeMap<String, Vec<DuplicateCandidate>>, product| {
+                            by_brand.entry(compact(&product.brand.name)).or_default().push(DuplicateCandidate {
+                                key: product.id.to_string(),
+                                name: product.name,
+                            });
+                            future::ready(Ok(by_brand))
+                        },
+                    )
+                    .await?;
+
+                by_brand.into_values().collect()
+            }
+        };
+
+        Ok(scopes.into_iter().flat_map(group_look_alikes).collect())
+    }
+}
+
+/// Spelling variants such as "Coca Cola", "Coca-Cola" and "CocaCola" share the same compact form.
+fn compact(name: &str) -> String {
+    tokenize(name).concat()
+}
+
+/// Two compact names look alike when a few typos apart, the longer the names the more typos.
+fn look_alike(a: &str, b: &str) -> bool {
+    let tolerance: usize = match a.chars().count().min(b.chars().count()) {
+        0..=4 => 0,
+        5..=9 => 1,
+        _ => 2,
+    };
+
+    edit_distance(a, b) <= tolerance
+}
+
+/// Gathers `candidates` in groups of look-alikes, a candidate joining a group as soon as it looks like one of its
+/// members.
+fn group_look_alikes(candidates: Vec<DuplicateCandidate>) -> Vec<Vec<DuplicateCandidate>> {
+    let compacts: Vec<String> = candidates.iter().map(|c| compact(&c.name)).collect();
+    let mut groups: Vec<usize> = (0..candidates.len()).collect();
+
+    fn root(groups: &mut [usize], mut i: usize) -> usize {
+        while groups[i] != i {
+            groups[i] = groups[groups[i]];
+            i = groups[i];
+        }
+        i
+    }
+
+    for i in 0..candidates.len() {
+        for j in (i + 1)..candidates.len() {
+            if look_alike(&compacts[i], &compacts[j]) {
+                let (a, b): (usize, usize) = (root(&mut groups, i), root(&mut groups, j));
+                groups[b] = a;
+            }
+        }
+    }
+
+    let mut gathered: BTreeMap<usize, Vec<DuplicateCandidate>> = BTreeMap::new();
+    for (i, candidate) in candidates.into_iter().enumerate() {
+        let group: usize = root(&mut groups, i);
+        gathered.entry(group).or_default().push(candidate);
+    }
+
+    gathered.into_values().filter(|group| group.len() > 1).collect()
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FindDuplicateCandidatesInteractorError {
+    UnableToRetrieveCandidates(String),
+}
+
+impl From<BrandRepositoryRetrieveAllError> for FindDuplicateCandidatesInteractorError {
+    fn from(value: BrandRepositoryRetrieveAllError) -> Self {
+        match value {
+            BrandRepositoryRetrieveAllError::UnableToRetrieveBrands(details) => {
+                FindDuplicateCandidatesInteractorError::UnableToRetrieveCandidates(details)
+            }
+        }
+    }
+}
+
+impl From<CategoryRepositoryError> for FindDuplicateCandidatesInteractorError {
+    fn from(value: CategoryRepositoryError) -> Self {
+        match value {
+            CategoryRepositoryError::UnableToAccessStorage(details) => {
+                FindDuplicateCandidatesInteractorError::UnableToRetrieveCandidates(details)
+            }
+            e => FindDuplicateCandidatesInteractorError::UnableToRetrieveCandidates(format!("{:?}", e)),
+        }
+    }
+}
+
+impl From<StoreRepositoryError> for FindDuplicateCandidatesInteractorError {
+    fn from(value: StoreRepositoryError) -> Self {
+        match value {
+            StoreRepositoryError::UnableToAccessStorage(details) => {
+                FindDuplicateCandidatesInteractorError::UnableToRetrieveCandidates(details)
+            }
+            e => FindDuplicateCandidatesInteractorError::UnableToRetrieveCandidates(format!("{:?}", e)),
+        }
+    }
+}
+
+impl From<ProductRepositoryError> for FindDuplicateCandidatesInteractorError {
+    fn from(value: ProductRepositoryError) -> Self {
+        match value {
+            ProductRepositoryError::UnableToAccessStorage(details) => {
+                FindDuplicateCandidatesInteractorError::UnableToRetrieveCandidates(details)
+            }
+            e => FindDuplicateCandidatesInteractorError::UnableToRetrieveCandidates(format!("{:?}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::domain::{
+        entities::{Brand, Category, Product},
+        use_cases::{
+            FindDuplicateCandidatesOutputPort, MergeableKind,
+            repository_fakes::{BrandRepositoryFake, CategoryRepositoryFake, ProductRepositoryFake, StoreRepositoryFake},
+        },
+    };
+
+    use super::{DuplicateCandidate, FindDuplicateCandidatesInteractor, FindDuplicateCandidatesInteractorError, group_look_alikes};
+
+    fn candidates(names: &[&str]) -> Vec<DuplicateCandidate> {
+        names
+            .iter()
+            .map(|n| DuplicateCandidate {
+                key: n.to_string(),
+                name: n.to_string(),
+            })
+            .collect()
+    }
+
+    macro_rules! group_look_alikes {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (names, expected): (Vec<&str>, Vec<Vec<&str>>) = $value;
+                let expected: Vec<Vec<DuplicateCandidate>> = expected.iter().map(|g| candidates(g)).collect();
+
+                let result: Vec<Vec<DuplicateCandidate>> = group_look_alikes(candidates(&names));
+
+                assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+            }
+        )*
+        }
+    }
+
+    group_look_alikes! {
+        should_group_spelling_variants: (vec!["Coca Cola", "Migros", "Coca-Cola", "CocaCola"], vec![vec!["Coca Cola", "Coca-Cola", "CocaCola"]]),
+        should_group_accent_and_case_variants: (vec!["Nestlé", "NESTLE"], vec![vec!["Nestlé", "NESTLE"]]),
+        should_group_typos_of_long_names: (vec!["Emmentaler", "Emmenthaler", "Emmi"], vec![vec!["Emmentaler", "Emmenthaler"]]),
+        should_keep_short_names_apart: (vec!["Coop", "Coup", "Spar"], vec![]),
+        should_form_one_group_per_look_alike_family: (vec!["Denner", "Denner Express", "Dener", "Denner-Express"], vec![vec!["Denner", "Dener"], vec!["Denner Express", "Denner-Express"]]),
+    }
+
+    #[tokio::test]
+    async fn products_should_only_be_compared_within_their_brand() {
+        let drinks: Category = Category::new(None, "Drinks".to_owned());
+        let coke: Product = Product::new(None, "Coke Zero".to_owned(), Brand::new("Coca Cola".to_owned()), drinks.clone());
+        let coke_zero: Product = Product::new(None, "Coke-Zero".to_owned(), Brand::new("Coca-Cola".to_owned()), drinks.clone());
+        let pepsi_zero: Product = Product::new(None, "Coke Zero".to_owned(), Brand::new("Pepsi".to_owned()), drinks);
+        let use_case: FindDuplicateCandidatesInteractor<Result<Vec<Vec<DuplicateCandidate>>, FindDuplicateCandidatesInteractorError>> =
+            FindDuplicateCandidatesInteractor::new(
+                Arc::new(BrandRepositoryFake::with(vec![])),
+                CategoryRepositoryFake::default().shared(),
+                StoreRepositoryFake::default().shared(),
+                ProductRepositoryFake::with(vec![coke.clone(), coke_zero.clone(), pepsi_zero]).shared(),
+                Arc::new(NoOpUseCaseOutputPort {}),
+            );
+
+        let result: Result<Vec<Vec<DuplicateCandidate>>, FindDuplicateCandidatesInteractorError> =
+            use_case.execute(MergeableKind::Product).await;
+        let expected: Result<Vec<Vec<DuplicateCandidate>>, FindDuplicateCandidatesInteractorError> = Ok(vec![vec![
+            DuplicateCandidate {
+                key: coke.id.to_string(),
+                name: coke.name,
+            },
+            DuplicateCandidate {
+                key: coke_zero.id.to_string(),
+                name: coke_zero.name,
+            },
+        ]]);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    struct NoOpUseCaseOutputPort {}
+
+    impl FindDuplicateCandidatesOutputPort<Result<Vec<Vec<DuplicateCandidate>>, FindDuplicateCandidatesInteractorError>>
+        for NoOpUseCaseOutputPort
+    {
+        fn apply(
+            &self,
+            result: Result<Vec<Vec<DuplicateCandidate>>, FindDuplicateCandidatesInteractorError>,
+        ) -> Result<Vec<Vec<DuplicateCandidate>>, FindDuplicateCandidatesInteractorError> {
+            result
+        }
+    }
+}
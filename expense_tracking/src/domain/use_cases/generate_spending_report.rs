@@ -0,0 +1,177 @@
+use std::{any::Any, sync::Arc};
+
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+
+use crate::domain::{
+    reports::{ReportDimension, SpendingReport, SpendingReportBuilder},
+    repositories::{SharedTransactionRepository, TransactionRepositoryError},
+};
+
+pub trait GenerateSpendingReportOutputPort<T: Any>: Send + Sync {
+    fn apply(&self, _: Result<SpendingReport, GenerateSpendingReportInteractorError>) -> T;
+}
+
+pub struct GenerateSpendingReportInteractor<Output: Any> {
+    transaction_repository: SharedTransactionRepository,
+    presenter: Arc<dyn GenerateSpendingReportOutputPort<Output>>,
+}
+
+impl<Output: Any> GenerateSpendingReportInteractor<Output> {
+    pub fn new(transaction_repository: SharedTransactionRepository, presenter: Arc<dyn GenerateSpendingReportOutputPort<Output>>) -> Self {
+        Self {
+            transaction_repository,
+            presenter,
+        }
+    }
+
+    /// Reports the spending between `from`, included, and `to`, excluded, grouped along `dimensions` in order.
+    pub async fn execute(&self, from: DateTime<Utc>, to: DateTime<Utc>, dimensions: Vec<ReportDimension>) -> Output {
+        self.presenter.apply(self.generate(from, to, dimensions).await)
+    }
+
+    async fn generate(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        dimensions: Vec<ReportDimension>,
+    ) -> Result<SpendingReport, GenerateSpendingReportInteractorError> {
+        if from >= to {
+            return Err(GenerateSpendingReportInteractorError::InvalidRange);
+        }
+
+        let repository = self.transaction_repository.read().await;
+        let builder: SpendingReportBuilder = repository
+            .stream_all()
+            .try_fold(
+                SpendingReportBuilder::new(from, to, dimensions),
+                |mut builder, transaction| async move {
+                    builder.add(&transaction);
+                    Ok(builder)
+                },
+            )
+            .await?;
+
+        Ok(builder.build())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum GenerateSpendingReportInteractorError {
+    InvalidRange,
+    UnableToRetrieveTransactions(String),
+}
+
+impl From<TransactionRepositoryError> for GenerateSpendingReportInteractorError {
+    fn from(value: TransactionRepositoryError) -> Self {
+        match value {
+            TransactionRepositoryError::UnableToAccessStorage(details) => {
+                GenerateSpendingReportInteractorError::UnableToRetrieveTransactions(details)
+            }
+            e => GenerateSpendingReportInteractorError::UnableToRetrieveTransactions(format!("{:?}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::{DateTime, Utc};
+
+    use crate::domain::{
+        entities::{Brand, Category, Item, Product, Store, Transaction, Unit},
+        reports::{GroupKey, ReportDimension, SpendingReport, TimeBucket},
+        use_cases::{GenerateSpendingReportOutputPort, repository_fakes::TransactionRepositoryFake},
+    };
+
+    use super::{GenerateSpendingReportInteractor, GenerateSpendingReportInteractorError};
+
+    type Report = Result<SpendingReport, GenerateSpendingReportInteractorError>;
+
+    #[tokio::test]
+    async fn execute_should_return_err_if_range_is_empty() {
+        let use_case: GenerateSpendingReportInteractor<Report> =
+            GenerateSpendingReportInteractor::new(TransactionRepositoryFake::default().shared(), Arc::new(NoOpUseCaseOutputPort {}));
+
+        let result: Report = use_case
+            .execute(at("2024-03-01T00:00:00Z"), at("2024-03-01T00:00:00Z"), vec![])
+            .await;
+        let expected: Report = Err(GenerateSpendingReportInteractorError::InvalidRange);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn execute_should_return_err_if_unable_to_retrieve_transactions() {
+        let use_case: GenerateSpendingReportInteractor<Report> = GenerateSpendingReportInteractor::new(
+            TransactionRepositoryFake::failing("Aenean vel orci").shared(),
+            Arc::new(NoOpUseCaseOutputPort {}),
+        );
+
+        let result: Report = use_case
+            .execute(at("2024-03-01T00:00:00Z"), at("2024-04-01T00:00:00Z"), vec![])
+            .await;
+        let expected: Report = Err(GenerateSpendingReportInteractorError::UnableToRetrieveTransactions(
+            "Aenean vel orci".to_owned(),
+        ));
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn execute_should_report_weekly_spending_within_range() {
+        let store: Store = Store::new(None, "Migros".to_owned());
+        let bread: Product = Product::new(
+            None,
+            "Bread".to_owned(),
+            Brand::new("Jowa".to_owned()),
+            Category::new(None, "Bakery".to_owned()),
+        );
+        let transactions: Vec<Transaction> = ["2024-02-28T08:00:00Z", "2024-03-04T08:00:00Z", "2024-03-06T08:00:00Z"]
+            .iter()
+            .map(|datetime| {
+                Transaction::new(
+                    None,
+                    vec![Item::new(None, bread.clone(), Unit::Quantity(2.), 2.95)],
+                    store.clone(),
+                    at(datetime),
+                )
+            })
+            .collect();
+        let use_case: GenerateSpendingReportInteractor<Report> = GenerateSpendingReportInteractor::new(
+            TransactionRepositoryFake::with(transactions).shared(),
+            Arc::new(NoOpUseCaseOutputPort {}),
+        );
+
+        let result: SpendingReport = use_case
+            .execute(
+                at("2024-03-01T00:00:00Z"),
+                at("2024-04-01T00:00:00Z"),
+                vec![ReportDimension::Period(TimeBucket::Week)],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.groups.len(), 1, "Expected a single week, but got {:?}", result.groups);
+        assert_eq!(
+            result.groups[0].keys,
+            vec![GroupKey::Period {
+                start: "2024-03-04".parse().unwrap()
+            }]
+        );
+        assert_eq!((result.total, result.groups[0].transactions), (11.8, 2));
+    }
+
+    fn at(datetime: &str) -> DateTime<Utc> {
+        datetime.parse().unwrap()
+    }
+
+    struct NoOpUseCaseOutputPort {}
+
+    impl GenerateSpendingReportOutputPort<Report> for NoOpUseCaseOutputPort {
+        fn apply(&self, result: Report) -> Report {
+            result
+        }
+    }
+}
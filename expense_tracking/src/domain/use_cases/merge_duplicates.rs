@@ -0,0 +1,655 @@
+use std::{any::Any, fmt::Debug, sync::Arc};
+
+use uuid_b64::UuidB64;
+
+use crate::domain::{
+    entities::{Brand, Category, NamePolicy, Product, Store, Transaction},
+    repositories::{
+        BrandRepository, BrandRepositoryDeleteError, BrandRepositoryRetrieveAllError, CategoryRepositoryError, DeleteBehaviour,
+        ProductRepositoryError, SharedCategoryRepository, SharedProductRepository, SharedStoreRepository, SharedTransactionRepository,
+        StoreRepositoryError, TransactionRepositoryError,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MergeableKind {
+    Brand,
+    Category,
+    Store,
+    Product,
+}
+
+/// Entities are designated by their key, which is the name of a brand and the id of anything else.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeRequest {
+    pub kind: MergeableKind,
+    pub survivor: String,
+    pub duplicates: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeMode {
+    Preview,
+    Apply,
+}
+
+/// What a merge changes: the duplicates, named as they were, and the records whose reference to them is rewritten.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeReport {
+    pub kind: MergeableKind,
+    pub survivor: String,
+    pub duplicates: Vec<String>,
+    pub affected_products: Vec<Product>,
+    pub affected_transactions: Vec<Transaction>,
+    pub applied: bool,
+}
+
+pub trait MergeDuplicatesOutputPort<T: Any>: Send + Sync {
+    fn apply(&self, _: Result<MergeReport, MergeDuplicatesInteractorError>) -> T;
+}
+
+pub struct MergeDuplicatesInteractor<Output: Any> {
+    brand_repository: Arc<dyn BrandRepository>,
+    category_repository: SharedCategoryRepository,
+    store_repository: SharedStoreRepository,
+    product_repository: SharedProductRepository,
+    transaction_repository: SharedTransactionRepository,
+    presenter: Arc<dyn MergeDuplicatesOutputPort<Output>>,
+    name_policy: NamePolicy,
+}
+
+impl<Output: Any> MergeDuplicatesInteractor<Output> {
+    pub fn new(
+        brand_repository: Arc<dyn BrandRepository>,
+        category_repository: SharedCategoryRepository,
+        store_repository: SharedStoreRepository,
+        product_repository: SharedProductRepository,
+        transaction_repository: SharedTransactionRepository,
+        presenter: Arc<dyn MergeDuplicatesOutputPort<Output>>,
+    ) -> Self {
+        Self {
+            brand_repository,
+            category_repository,
+            store_repository,
+            product_repository,
+            transaction_repository,
+            presenter,
+            name_policy: NamePolicy::default(),
+        }
+    }
+
+    pub fn with_name_policy(self, name_policy: NamePolicy) -> Self {
+        Self { name_policy, ..self }
+    }
+
+    /// Folds the duplicates of `request` into its survivor. Every duplicate is deleted with its references
+    /// reassigned to the survivor, so it lands in the trash and can be restored, though its references stay
+    /// rewritten. A preview only reports what applying would change.
+    ///
+    /// The whole request is checked before the first delete. Should a delete still fail, the duplicates merged
+    /// until then are reported with the one that failed, since they stay merged.
+    pub async fn execute(&self, request: MergeRequest, mode: MergeMode) -> Output {
+        self.presenter.apply(self.merge(request, mode).await)
+    }
+
+    async fn merge(&self, request: MergeRequest, mode: MergeMode) -> Result<MergeReport, MergeDuplicatesInteractorError> {
+        if request.duplicates.is_empty() {
+            return Err(MergeDuplicatesInteractorError::NothingToMerge);
+        }
+
+        let mut category_repository = self.category_repository.write().await;
+        let mut store_repository = self.store_repository.write().await;
+        let mut product_repository = self.product_repository.write().await;
+        let transaction_repository = self.transaction_repository.read().await;
+
+        let (survivor, duplicates, affected_products, affected_transactions) = match request.kind {
+            MergeableKind::Brand => {
+                let brands: Vec<Brand> = self.brand_repository.retrieve_all().await?;
+                let (survivor, duplicates) = designate(
+                    &brands,
+                    &request,
+                    |b, wanted| self.name_policy.matches(&b.name, wanted),
+                    |b| b.name.clone(),
+                )?;
+                let affected_products: Vec<Product> = product_repository
+                    .retrieve_all()
+                    .await?
+                    .into_iter()
+                    .filter(|p| duplicates.iter().any(|d| self.name_policy.matches(&d.name, &p.brand.name)))
+                    .collect();
+
+                if mode == MergeMode::Apply {
+                    for (index, duplicate) in duplicates.iter().enumerate() {
+                        if let Err(e) = self
+                            .brand_repository
+                            .delete(duplicate, DeleteBehaviour::Reassign(survivor.clone()))
+                            .await
+                        {
+                            return Err(partially_merged(&duplicates, index, e, |b| &b.name));
+                        }
+                    }
+                }
+
+                (survivor.name, names(&duplicates, |b| &b.name), affected_products, vec![])
+            }
+            MergeableKind::Category => {
+                let categories: Vec<Category> = category_repository.retrieve_all().await?;
+                let (survivor, duplicates) =
+                    designate(&categories, &request, |c, wanted| c.id.to_string() == wanted, |c| c.id.to_string())?;
+                let affected_products: Vec<Product> = product_repository
+                    .retrieve_all()
+                    .await?
+                    .into_iter()
+                    .filter(|p| duplicates.iter().any(|d| d.id == p.category.id))
+                    .collect();
+
+                if mode == MergeMode::Apply {
+                    for (index, duplicate) in duplicates.iter().enumerate() {
+                        if let Err(e) = category_repository
+                            .delete(&duplicate.id, DeleteBehaviour::Reassign(survivor.clone()))
+                            .await
+                        {
+                            return Err(partially_merged(&duplicates, index, e, |c| &c.name));
+                        }
+                    }
+                }
+
+                (survivor.name, names(&duplicates, |c| &c.name), affected_products, vec![])
+            }
+            MergeableKind::Store => {
+                let stores: Vec<Store> = store_repository.retrieve_all().await?;
+                let (survivor, duplicates) = designate(&stores, &request, |s, wanted| s.id.to_string() == wanted, |s| s.id.to_string())?;
+                let affected_transactions: Vec<Transaction> = transaction_repository
+                    .retrieve_all()
+                    .await?
+                    .into_iter()
+                    .filter(|t| duplicates.iter().any(|d| d.id == t.store.id))
+                    .collect();
+
+                if mode == MergeMode::Apply {
+                    for (index, duplicate) in duplicates.iter().enumerate() {
+                        if let Err(e) = store_repository
+                            .delete(&duplicate.id, DeleteBehaviour::Reassign(survivor.clone()))
+                            .await
+                        {
+                            return Err(partially_merged(&duplicates, index, e, |s| &s.name));
+                        }
+                    }
+                }
+
+                (survivor.name, names(&duplicates, |s| &s.name), vec![], affected_transactions)
+            }
+            MergeableKind::Product => {
+                let products: Vec<Product> = product_repository.retrieve_all().await?;
+                let (survivor, duplicates) = designate(&products, &request, |p, wanted| p.id.to_string() == wanted, |p| p.id.to_string())?;
+                let duplicate_ids: Vec<UuidB64> = duplicates.iter().map(|d| d.id).collect();
+                let affected_transactions: Vec<Transaction> = transaction_repository
+                    .retrieve_all()
+                    .await?
+                    .into_iter()
+                    .filter(|t| t.items.iter().any(|i| duplicate_ids.contains(&i.product().id)))
+                    .collect();
+
+                if mode == MergeMode::Apply {
+                    for (index, duplicate) in duplicates.iter().enumerate() {
+                        if let Err(e) = product_repository
+                            .delete(&duplicate.id, DeleteBehaviour::Reassign(survivor.clone()))
+                            .await
+                        {
+                            return Err(partially_merged(&duplicates, index, e, |p| &p.name));
+                        }
+                    }
+                }
+
+                (survivor.name, names(&duplicates, |p| &p.name), vec![], affected_transactions)
+            }
+        };
+
+        Ok(MergeReport {
+            kind: request.kind,
+            survivor,
+            duplicates,
+            affected_products,
+            affected_transactions,
+            applied: mode == MergeMode::Apply,
+        })
+    }
+}
+
+/// Picks the survivor and the duplicates of `request` among `entities`, making sure each designates a distinct entity
+/// before anything is merged. `designates` tells whether an entity is the one a key of the request asks for, and `key`
+/// identifies the entity found.
+fn designate<T: Clone>(
+    entities: &[T],
+    request: &MergeRequest,
+    designates: impl Fn(&T, &str) -> bool,
+    key: impl Fn(&T) -> String,
+) -> Result<(T, Vec<T>), MergeDuplicatesInteractorError> {
+    let find = |wanted: &String| -> Result<T, MergeDuplicatesInteractorError> {
+        entities
+            .iter()
+            .find(|e| designates(e, wanted))
+            .cloned()
+            .ok_or_else(|| MergeDuplicatesInteractorError::EntityNotFound(wanted.clone()))
+    };
+
+    let survivor: T = find(&request.survivor)?;
+    let duplicates: Vec<T> = request.duplicates.iter().map(find).collect::<Result<_, _>>()?;
+
+    let mut keys: Vec<String> = vec![key(&survivor)];
+    for (wanted, duplicate) in request.duplicates.iter().zip(&duplicates) {
+        let duplicate_key: String = key(duplicate);
+
+        if duplicate_key == keys[0] {
+            return Err(MergeDuplicatesInteractorError::SurvivorAmongDuplicates);
+        }
+        if keys.contains(&duplicate_key) {
+            return Err(MergeDuplicatesInteractorError::DuplicateListedTwice(wanted.clone()));
+        }
+        keys.push(duplicate_key);
+    }
+
+    Ok((survivor, duplicates))
+}
+
+/// The error of a merge whose delete of the duplicate at `failed` went wrong. Nothing is merged yet when the first one
+/// fails, otherwise the duplicates before it stay merged.
+fn partially_merged<T, E>(duplicates: &[T], failed: usize, error: E, name: impl Fn(&T) -> &String) -> MergeDuplicatesInteractorError
+where
+    E: Debug + Into<MergeDuplicatesInteractorError>,
+{
+    match failed {
+        0 => error.into(),
+        _ => MergeDuplicatesInteractorError::PartiallyMerged {
+            merged: names(&duplicates[..failed], &name),
+            failed: name(&duplicates[failed]).clone(),
+            reason: format!("{:?}", error),
+        },
+    }
+}
+
+fn names<T>(entities: &[T], name: impl Fn(&T) -> &String) -> Vec<String> {
+    entities.iter().map(|e| name(e).clone()).collect()
+}
+
+#[derive(Debug, PartialEq)]
+pub enum MergeDuplicatesInteractorError {
+    NothingToMerge,
+    SurvivorAmongDuplicates,
+    DuplicateListedTwice(String),
+    EntityNotFound(String),
+    UnableToMerge(String),
+    PartiallyMerged {
+        merged: Vec<String>,
+        failed: String,
+        reason: String,
+    },
+}
+
+impl From<BrandRepositoryRetrieveAllError> for MergeDuplicatesInteractorError {
+    fn from(value: BrandRepositoryRetrieveAllError) -> Self {
+        match value {
+            BrandRepositoryRetrieveAllError::UnableToRetrieveBrands(details) => MergeDuplicatesInteractorError::UnableToMerge(details),
+        }
+    }
+}
+
+impl From<BrandRepositoryDeleteError> for MergeDuplicatesInteractorError {
+    fn from(value: BrandRepositoryDeleteError) -> Self {
+        MergeDuplicatesInteractorError::UnableToMerge(format!("{:?}", value))
+    }
+}
+
+impl From<CategoryRepositoryError> for MergeDuplicatesInteractorError {
+    fn from(value: CategoryRepositoryError) -> Self {
+        match value {
+            CategoryRepositoryError::UnableToAccessStorage(details) => MergeDuplicatesInteractorError::UnableToMerge(details),
+            e => MergeDuplicatesInteractorError::UnableToMerge(format!("{:?}", e)),
+        }
+    }
+}
+
+impl From<StoreRepositoryError> for MergeDuplicatesInteractorError {
+    fn from(value: StoreRepositoryError) -> Self {
+        match value {
+            StoreRepositoryError::UnableToAccessStorage(details) => MergeDuplicatesInteractorError::UnableToMerge(details),
+            e => MergeDuplicatesInteractorError::UnableToMerge(format!("{:?}", e)),
+        }
+    }
+}
+
+impl From<ProductRepositoryError> for MergeDuplicatesInteractorError {
+    fn from(value: ProductRepositoryError) -> Self {
+        match value {
+            ProductRepositoryError::UnableToAccessStorage(details) => MergeDuplicatesInteractorError::UnableToMerge(details),
+            e => MergeDuplicatesInteractorError::UnableToMerge(format!("{:?}", e)),
+        }
+    }
+}
+
+impl From<TransactionRepositoryError> for MergeDuplicatesInteractorError {
+    fn from(value: TransactionRepositoryError) -> Self {
+        match value {
+            TransactionRepositoryError::UnableToAccessStorage(details) => MergeDuplicatesInteractorError::UnableToMerge(details),
+            e => MergeDuplicatesInteractorError::UnableToMerge(format!("{:?}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use chrono::DateTime;
+
+    use crate::domain::{
+        entities::{Brand, Category, Item, Product, Store, Transaction, Unit},
+        repositories::{
+            BrandRepository, SharedCategoryRepository, SharedProductRepository, SharedStoreRepository, SharedTransactionRepository,
+        },
+        use_cases::{
+            MergeDuplicatesOutputPort, Receipt, ReceiptLine, RecordPurchaseInteractor, RecordPurchaseInteractorError,
+            RecordPurchaseOutputPort, RecordedPurchase,
+            repository_fakes::{
+                BrandRepositoryFake, CategoryRepositoryFake, ProductRepositoryFake, StoreRepositoryFake, TransactionRepositoryFake,
+            },
+        },
+    };
+
+    use super::{MergeDuplicatesInteractor, MergeDuplicatesInteractorError, MergeMode, MergeReport, MergeRequest, MergeableKind};
+
+    struct Ledger {
+        brands: Arc<dyn BrandRepository>,
+        stores: SharedStoreRepository,
+        products: SharedProductRepository,
+        coop: Store,
+        coop_city: Store,
+        coke: Product,
+        cola: Product,
+        purchase: Transaction,
+    }
+
+    impl Ledger {
+        fn new() -> Self {
+            let drinks: Category = Category::new(None, "Drinks".to_owned());
+            let coop: Store = Store::new(None, "Coop".to_owned());
+            let coop_city: Store = Store::new(None, "Coop City".to_owned());
+            let coke: Product = Product::new(None, "Coke".to_owned(), Brand::new("Coca Cola".to_owned()), drinks.clone());
+            let cola: Product = Product::new(None, "Coke".to_owned(), Brand::new("Coca-Cola".to_owned()), drinks.clone());
+            let purchase: Transaction = Transaction::new(
+                None,
+                vec![Item::new(None, cola.clone(), Unit::Quantity(2.), 1.5)],
+                coop_city.clone(),
+                DateTime::default(),
+            );
+
+            Self {
+                brands: Arc::new(BrandRepositoryFake::with(vec![
+                    Brand::new("Coca Cola".to_owned()),
+                    Brand::new("Coca-Cola".to_owned()),
+                ])),
+                stores: StoreRepositoryFake::with(vec![coop.clone(), coop_city.clone()]).shared(),
+                products: ProductRepositoryFake::with(vec![coke.clone(), cola.clone()]).shared(),
+                coop,
+                coop_city,
+                coke,
+                cola,
+                purchase,
+            }
+        }
+
+        fn interactor(&self) -> MergeDuplicatesInteractor<Result<MergeReport, MergeDuplicatesInteractorError>> {
+            MergeDuplicatesInteractor::new(
+                Arc::clone(&self.brands),
+                CategoryRepositoryFake::default().shared(),
+                Arc::clone(&self.stores),
+                Arc::clone(&self.products),
+                TransactionRepositoryFake::with(vec![self.purchase.clone()]).shared(),
+                Arc::new(NoOpUseCaseOutputPort {}),
+            )
+        }
+    }
+
+    #[tokio::test]
+    async fn preview_should_list_affected_records_without_merging() {
+        let ledger: Ledger = Ledger::new();
+        let request: MergeRequest = MergeRequest {
+            kind: MergeableKind::Brand,
+            survivor: "Coca Cola".to_owned(),
+            duplicates: vec!["Coca-Cola".to_owned()],
+        };
+
+        let result: Result<MergeReport, MergeDuplicatesInteractorError> = ledger.interactor().execute(request, MergeMode::Preview).await;
+        let expected: Result<MergeReport, MergeDuplicatesInteractorError> = Ok(MergeReport {
+            kind: MergeableKind::Brand,
+            survivor: "Coca Cola".to_owned(),
+            duplicates: vec!["Coca-Cola".to_owned()],
+            affected_products: vec![ledger.cola.clone()],
+            affected_transactions: vec![],
+            applied: false,
+        });
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(ledger.brands.retrieve_all().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn preview_should_designate_brands_under_any_spelling() {
+        let ledger: Ledger = Ledger::new();
+        let request: MergeRequest = MergeRequest {
+            kind: MergeableKind::Brand,
+            survivor: "coca cola".to_owned(),
+            duplicates: vec![" COCA-COLA ".to_owned()],
+        };
+
+        let result: Result<MergeReport, MergeDuplicatesInteractorError> = ledger.interactor().execute(request, MergeMode::Preview).await;
+        let expected: Result<MergeReport, MergeDuplicatesInteractorError> = Ok(MergeReport {
+            kind: MergeableKind::Brand,
+            survivor: "Coca Cola".to_owned(),
+            duplicates: vec!["Coca-Cola".to_owned()],
+            affected_products: vec![ledger.cola.clone()],
+            affected_transactions: vec![],
+            applied: false,
+        });
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn apply_should_remove_duplicate_store_and_report_its_transactions() {
+        let ledger: Ledger = Ledger::new();
+        let request: MergeRequest = MergeRequest {
+            kind: MergeableKind::Store,
+            survivor: ledger.coop.id.to_string(),
+            duplicates: vec![ledger.coop_city.id.to_string()],
+        };
+
+        let result: MergeReport = ledger.interactor().execute(request, MergeMode::Apply).await.unwrap();
+        let stores: Vec<Store> = ledger.stores.read().await.retrieve_all().await.unwrap();
+
+        assert_eq!(result.duplicates, vec!["Coop City".to_owned()]);
+        assert_eq!(result.affected_transactions, vec![ledger.purchase.clone()]);
+        assert!(result.applied);
+        assert_eq!(
+            stores,
+            vec![ledger.coop.clone()],
+            "Expected {:?}, but got {:?}",
+            vec![ledger.coop],
+            stores
+        );
+    }
+
+    #[tokio::test]
+    async fn apply_should_reassign_items_of_duplicate_product() {
+        let ledger: Ledger = Ledger::new();
+        let request: MergeRequest = MergeRequest {
+            kind: MergeableKind::Product,
+            survivor: ledger.coke.id.to_string(),
+            duplicates: vec![ledger.cola.id.to_string()],
+        };
+
+        let result: MergeReport = ledger.interactor().execute(request, MergeMode::Apply).await.unwrap();
+        let products: Vec<Product> = ledger.products.read().await.retrieve_all().await.unwrap();
+
+        assert_eq!(result.affected_transactions, vec![ledger.purchase.clone()]);
+        assert_eq!(
+            products,
+            vec![ledger.coke.clone()],
+            "Expected {:?}, but got {:?}",
+            vec![ledger.coke],
+            products
+        );
+    }
+
+    #[tokio::test]
+    async fn apply_should_report_the_duplicates_merged_before_a_failing_delete() {
+        let coop_pronto: Store = Store::new(None, "Coop Pronto".to_owned());
+        let mut ledger: Ledger = Ledger::new();
+        ledger.stores = StoreRepositoryFake::with(vec![ledger.coop.clone(), ledger.coop_city.clone(), coop_pronto.clone()])
+            .refusing_to_delete(coop_pronto.id)
+            .shared();
+        let request: MergeRequest = MergeRequest {
+            kind: MergeableKind::Store,
+            survivor: ledger.coop.id.to_string(),
+            duplicates: vec![ledger.coop_city.id.to_string(), coop_pronto.id.to_string()],
+        };
+
+        let result: Result<MergeReport, MergeDuplicatesInteractorError> = ledger.interactor().execute(request, MergeMode::Apply).await;
+        let expected: Result<MergeReport, MergeDuplicatesInteractorError> = Err(MergeDuplicatesInteractorError::PartiallyMerged {
+            merged: vec!["Coop City".to_owned()],
+            failed: "Coop Pronto".to_owned(),
+            reason: "UnableToAccessStorage(\"Unable to delete\")".to_owned(),
+        });
+        let stores: Vec<Store> = ledger.stores.read().await.retrieve_all().await.unwrap();
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(stores, vec![ledger.coop, coop_pronto]);
+    }
+
+    #[tokio::test]
+    async fn apply_should_merge_nothing_when_the_first_delete_fails() {
+        let mut ledger: Ledger = Ledger::new();
+        ledger.stores = StoreRepositoryFake::with(vec![ledger.coop.clone(), ledger.coop_city.clone()])
+            .refusing_to_delete(ledger.coop_city.id)
+            .shared();
+        let request: MergeRequest = MergeRequest {
+            kind: MergeableKind::Store,
+            survivor: ledger.coop.id.to_string(),
+            duplicates: vec![ledger.coop_city.id.to_string()],
+        };
+
+        let result: Result<MergeReport, MergeDuplicatesInteractorError> = ledger.interactor().execute(request, MergeMode::Apply).await;
+        let expected: Result<MergeReport, MergeDuplicatesInteractorError> =
+            Err(MergeDuplicatesInteractorError::UnableToMerge("Unable to delete".to_owned()));
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    macro_rules! invalid_requests {
+        ($($name:ident: ($a:expr, $b:expr),)*) => {
+        $(
+            #[tokio::test]
+            async fn $name() {
+                let ledger: Ledger = Ledger::new();
+                let (survivor, duplicates): (&str, Vec<&str>) = $a;
+                let request: MergeRequest = MergeRequest {
+                    kind: MergeableKind::Brand,
+                    survivor: survivor.to_owned(),
+                    duplicates: duplicates.into_iter().map(str::to_owned).collect(),
+                };
+                let expected: Result<MergeReport, MergeDuplicatesInteractorError> = Err($b);
+
+                let result: Result<MergeReport, MergeDuplicatesInteractorError> = ledger.interactor().execute(request, MergeMode::Apply).await;
+
+                assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+                assert_eq!(ledger.brands.retrieve_all().await.unwrap().len(), 2);
+            }
+        )*
+        }
+    }
+
+    invalid_requests! {
+        should_refuse_merge_without_duplicates: (("Coca Cola", vec![]), MergeDuplicatesInteractorError::NothingToMerge),
+        should_refuse_survivor_among_duplicates: (("Coca Cola", vec!["Coca-Cola", "Coca Cola"]), MergeDuplicatesInteractorError::SurvivorAmongDuplicates),
+        should_refuse_survivor_among_duplicates_under_another_spelling: (("Coca Cola", vec!["coca cola"]), MergeDuplicatesInteractorError::SurvivorAmongDuplicates),
+        should_refuse_duplicate_listed_twice: (("Coca Cola", vec!["Coca-Cola", "coca-cola"]), MergeDuplicatesInteractorError::DuplicateListedTwice("coca-cola".to_owned())),
+        should_refuse_unknown_survivor: (("Pepsi", vec!["Coca-Cola"]), MergeDuplicatesInteractorError::EntityNotFound("Pepsi".to_owned())),
+        should_refuse_unknown_duplicate: (("Coca Cola", vec!["Coca-Cola", "CocaCola"]), MergeDuplicatesInteractorError::EntityNotFound("CocaCola".to_owned())),
+    }
+
+    #[tokio::test]
+    async fn apply_should_not_deadlock_with_a_purchase_recorded_meanwhile() {
+        let ledger: Ledger = Ledger::new();
+        let categories: SharedCategoryRepository = CategoryRepositoryFake::default().shared();
+        let transactions: SharedTransactionRepository = TransactionRepositoryFake::with(vec![ledger.purchase.clone()]).shared();
+        let merging: MergeDuplicatesInteractor<Result<MergeReport, MergeDuplicatesInteractorError>> = MergeDuplicatesInteractor::new(
+            Arc::clone(&ledger.brands),
+            Arc::clone(&categories),
+            Arc::clone(&ledger.stores),
+            Arc::clone(&ledger.products),
+            Arc::clone(&transactions),
+            Arc::new(NoOpUseCaseOutputPort {}),
+        );
+        let recording: RecordPurchaseInteractor<Result<RecordedPurchase, RecordPurchaseInteractorError>> = RecordPurchaseInteractor::new(
+            Arc::clone(&ledger.brands),
+            categories,
+            Arc::clone(&ledger.stores),
+            Arc::clone(&ledger.products),
+            transactions,
+            Arc::new(NoOpUseCaseOutputPort {}),
+        );
+        let request: MergeRequest = MergeRequest {
+            kind: MergeableKind::Store,
+            survivor: ledger.coop.id.to_string(),
+            duplicates: vec![ledger.coop_city.id.to_string()],
+        };
+        let receipt: Receipt = Receipt {
+            store: "Coop".to_owned(),
+            datetime: DateTime::default(),
+            lines: vec![ReceiptLine {
+                product: "Coke".to_owned(),
+                brand: "Coca Cola".to_owned(),
+                category: "Drinks".to_owned(),
+                unit: Unit::Quantity(1.),
+                unitary_price: 1.5,
+            }],
+        };
+
+        // Both use cases queue up behind the held store lock, the purchase first, so that each would hold a lock
+        // the other waits for if they took them in different orders.
+        let store_lock = ledger.stores.write().await;
+        let recorded = tokio::spawn(async move { recording.execute(receipt).await });
+        tokio::task::yield_now().await;
+        let merged = tokio::spawn(async move { merging.execute(request, MergeMode::Apply).await });
+        tokio::task::yield_now().await;
+        drop(store_lock);
+
+        let result = tokio::time::timeout(Duration::from_secs(5), async { (recorded.await.unwrap(), merged.await.unwrap()) }).await;
+
+        assert!(
+            matches!(result, Ok((Ok(_), Ok(_)))),
+            "Expected both use cases to complete, but got {:?}",
+            result
+        );
+    }
+
+    struct NoOpUseCaseOutputPort {}
+
+    impl MergeDuplicatesOutputPort<Result<MergeReport, MergeDuplicatesInteractorError>> for NoOpUseCaseOutputPort {
+        fn apply(
+            &self,
+            result: Result<MergeReport, MergeDuplicatesInteractorError>,
+        ) -> Result<MergeReport, MergeDuplicatesInteractorError> {
+            result
+        }
+    }
+
+    impl RecordPurchaseOutputPort<Result<RecordedPurchase, RecordPurchaseInteractorError>> for NoOpUseCaseOutputPort {
+        fn apply(
+            &self,
+            result: Result<RecordedPurchase, RecordPurchaseInteractorError>,
+        ) -> Result<RecordedPurchase, RecordPurchaseInteractorError> {
+            result
+        }
+    }
+}
@@ -0,0 +1,470 @@
+use std::{any::Any, sync::Arc};
+
+use chrono::{DateTime, Utc};
+use futures::{TryStreamExt, future};
+
+use crate::domain::{
+    entities::{Brand, Category, Item, NamePolicy, Product, Store, Transaction, Unit},
+    repositories::{
+        BrandRepository, BrandRepositoryCreateError, BrandRepositoryRetrieveAllError, CategoryRepositoryError, ProductRepositoryError,
+        SharedCategoryRepository, SharedProductRepository, SharedStoreRepository, SharedTransactionRepository, StoreRepositoryError,
+        TransactionRepositoryError,
+    },
+};
+
+/// A receipt as entered by hand or read from a scan, every entity designated by its name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Receipt {
+    pub store: String,
+    pub datetime: DateTime<Utc>,
+    pub lines: Vec<ReceiptLine>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReceiptLine {
+    pub product: String,
+    pub brand: String,
+    pub category: String,
+    pub unit: Unit,
+    pub unitary_price: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedPurchase {
+    pub transaction: Transaction,
+    pub total: f64,
+}
+
+pub trait RecordPurchaseOutputPort<T: Any>: Send + Sync {
+    fn apply(&self, _: Result<RecordedPurchase, RecordPurchaseInteractorError>) -> T;
+}
+
+pub struct RecordPurchaseInteractor<Output: Any> {
+    brand_repository: Arc<dyn BrandRepository>,
+    category_repository: SharedCategoryRepository,
+    store_repository: SharedStoreRepository,
+    product_repository: SharedProductRepository,
+    transaction_repository: SharedTransactionRepository,
+    presenter: Arc<dyn RecordPurchaseOutputPort<Output>>,
+    name_policy: NamePolicy,
+}
+
+impl<Output: Any> RecordPurchaseInteractor<Output> {
+    pub fn new(
+        brand_repository: Arc<dyn BrandRepository>,
+        category_repository: SharedCategoryRepository,
+        store_repository: SharedStoreRepository,
+        product_repository: SharedProductRepository,
+        transaction_repository: SharedTransactionRepository,
+        presenter: Arc<dyn RecordPurchaseOutputPort<Output>>,
+    ) -> Self {
+        Self {
+            brand_repository,
+            category_repository,
+            store_repository,
+            product_repository,
+            transaction_repository,
+            presenter,
+            name_policy: NamePolicy::default(),
+        }
+    }
+
+    pub fn with_name_policy(self, name_policy: NamePolicy) -> Self {
+        Self { name_policy, ..self }
+    }
+
+    /// Records `receipt` as a transaction, creating the store, brands, categories and products it mentions which
+    /// do not exist yet. The whole receipt is validated before anything is written, but entities created before a
+    /// storage failure are kept since they are valid on their own. A known product keeps its category, the one
+    /// written on the line only files new products.
+    pub async fn execute(&self, receipt: Receipt) -> Output {
+        self.presenter.apply(self.record(receipt).await)
+    }
+
+    async fn record(&self, receipt: Receipt) -> Result<RecordedPurchase, RecordPurchaseInteractorError> {
+        validate(&receipt)?;
+
+        let mut category_repository = self.category_repository.write().await;
+        let mut store_repository = self.store_repository.write().await;
+        let mut product_repository = self.product_repository.write().await;
+
+        let known_store: Option<Store> = store_repository
+            .stream_all()
+            .try_filter(|s| future::ready(self.name_policy.matches(&s.name, &receipt.store)))
+            .try_next()
+            .await?;
+        let store: Store = match known_store {
+            Some(store) => store,
+            None => {
+                let store: Store = Store::new(None, receipt.store);
+                store_repository.create_or_update(&store).await?;
+                store
+            }
+        };
+
+        // Only the entities the receipt names are kept, the rest of each table is streamed past.
+        let lines: &[ReceiptLine] = &receipt.lines;
+        let mut brands: Vec<Brand> = self
+            .brand_repository
+            .stream_all()
+            .try_filter(|b| future::ready(lines.iter().any(|l| self.name_policy.matches(&b.name, &l.brand))))
+            .try_collect()
+            .await?;
+        let mut categories: Vec<Category> = category_repository
+            .stream_all()
+            .try_filter(|c| future::ready(lines.iter().any(|l| self.name_policy.matches(&c.name, &l.category))))
+            .try_collect()
+            .await?;
+        let mut products: Vec<Product> = product_repository
+            .stream_all()
+            .try_filter(|p| future::ready(lines.iter().any(|l| self.name_policy.matches(&p.name, &l.product))))
+            .try_collect()
+            .await?;
+        let mut items: Vec<Item> = Vec::with_capacity(receipt.lines.len());
+
+        for line in receipt.lines {
+            let brand: Brand = match brands.iter().find(|b| self.name_policy.matches(&b.name, &line.brand)) {
+                Some(brand) => brand.clone(),
+                None => {
+                    let brand: Brand = self.brand_repository.create(&Brand::new(line.brand)).await?;
+                    brands.push(brand.clone());
+                    brand
+                }
+            };
+
+            let product: Product = match products
+                .iter()
+                .find(|p| self.name_policy.matches(&p.name, &line.product) && self.name_policy.matches(&p.brand.name, &brand.name))
+            {
+                Some(product) => product.clone(),
+                None => {
+                    let category: Category = match categories.iter().find(|c| self.name_policy.matches(&c.name, &line.category)) {
+                        Some(category) => category.clone(),
+                        None => {
+                            let category: Category = Category::new(None, line.category);
+                            category_repository.create_or_update(&category).await?;
+                            categories.push(category.clone());
+                            category
+                        }
+                    };
+
+                    let product: Product = Product::new(None, line.product, brand, category);
+                    product_repository.create_or_update(&product).await?;
+                    products.push(product.clone());
+                    product
+                }
+            };
+
+            items.push(Item::new(None, product, line.unit, line.unitary_price));
+        }
+
+        let transaction: Transaction = Transaction::new(None, items, store, receipt.datetime);
+        self.transaction_repository.write().await.create_or_update(&transaction).await?;
+
+        Ok(RecordedPurchase {
+            total: transaction.calculate_total(),
+            transaction,
+        })
+    }
+}
+
+fn validate(receipt: &Receipt) -> Result<(), RecordPurchaseInteractorError> {
+    if receipt.lines.is_empty() {
+        return Err(RecordPurchaseInteractorError::EmptyReceipt);
+    }
+
+    let mut names = std::iter::once(&receipt.store).chain(receipt.lines.iter().flat_map(|l| [&l.product, &l.brand, &l.category]));
+    if let Some(name) = names.find(|n| n.trim().is_empty()) {
+        return Err(RecordPurchaseInteractorError::InvalidName(format!(
+            "The name '{}' is not valid",
+            name
+        )));
+    }
+
+    for line in &receipt.lines {
+        let amount: f64 = match line.unit {
+            Unit::None => 1.0,
+            Unit::Quantity(amount) | Unit::Kilograms(amount) | Unit::Liters(amount) => amount,
+        };
+
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(RecordPurchaseInteractorError::InvalidAmount(format!(
+                "The amount of '{}' must be positive",
+                line.product
+            )));
+        }
+        if !line.unitary_price.is_finite() || line.unitary_price < 0.0 {
+            return Err(RecordPurchaseInteractorError::InvalidAmount(format!(
+                "The price of '{}' cannot be negative",
+                line.product
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RecordPurchaseInteractorError {
+    EmptyReceipt,
+    InvalidName(String),
+    InvalidAmount(String),
+    UnableToRecordPurchase(String),
+}
+
+impl From<BrandRepositoryRetrieveAllError> for RecordPurchaseInteractorError {
+    fn from(value: BrandRepositoryRetrieveAllError) -> Self {
+        match value {
+            BrandRepositoryRetrieveAllError::UnableToRetrieveBrands(details) => {
+                RecordPurchaseInteractorError::UnableToRecordPurchase(details)
+            }
+        }
+    }
+}
+
+impl From<BrandRepositoryCreateError> for RecordPurchaseInteractorError {
+    fn from(value: BrandRepositoryCreateError) -> Self {
+        match value {
+            BrandRepositoryCreateError::UnableToSaveBrand(details) => RecordPurchaseInteractorError::UnableToRecordPurchase(details),
+            e => RecordPurchaseInteractorError::UnableToRecordPurchase(format!("{:?}", e)),
+        }
+    }
+}
+
+impl From<CategoryRepositoryError> for RecordPurchaseInteractorError {
+    fn from(value: CategoryRepositoryError) -> Self {
+        match value {
+            CategoryRepositoryError::UnableToAccessStorage(details) => RecordPurchaseInteractorError::UnableToRecordPurchase(details),
+            e => RecordPurchaseInteractorError::UnableToRecordPurchase(format!("{:?}", e)),
+        }
+    }
+}
+
+impl From<StoreRepositoryError> for RecordPurchaseInteractorError {
+    fn from(value: StoreRepositoryError) -> Self {
+        match value {
+            StoreRepositoryError::UnableToAccessStorage(details) => RecordPurchaseInteractorError::UnableToRecordPurchase(details),
+            e => RecordPurchaseInteractorError::UnableToRecordPurchase(format!("{:?}", e)),
+        }
+    }
+}
+
+impl From<ProductRepositoryError> for RecordPurchaseInteractorError {
+    fn from(value: ProductRepositoryError) -> Self {
+        match value {
+            ProductRepositoryError::UnableToAccessStorage(details) => RecordPurchaseInteractorError::UnableToRecordPurchase(details),
+            e => RecordPurchaseInteractorError::UnableToRecordPurchase(format!("{:?}", e)),
+        }
+    }
+}
+
+impl From<TransactionRepositoryError> for RecordPurchaseInteractorError {
+    fn from(value: TransactionRepositoryError) -> Self {
+        match value {
+            TransactionRepositoryError::UnableToAccessStorage(details) => RecordPurchaseInteractorError::UnableToRecordPurchase(details),
+            e => RecordPurchaseInteractorError::UnableToRecordPurchase(format!("{:?}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::{DateTime, Utc};
+
+    use crate::domain::{
+        entities::{Brand, Category, Product, Store, Unit},
+        repositories::{
+            BrandRepository, SharedCategoryRepository, SharedProductRepository, SharedStoreRepository, SharedTransactionRepository,
+        },
+        use_cases::{
+            RecordPurchaseOutputPort,
+            repository_fakes::{
+                BrandRepositoryFake, CategoryRepositoryFake, ProductRepositoryFake, StoreRepositoryFake, TransactionRepositoryFake,
+            },
+        },
+    };
+
+    use super::{Receipt, ReceiptLine, RecordPurchaseInteractor, RecordPurchaseInteractorError, RecordedPurchase};
+
+    struct Repositories {
+        brands: Arc<dyn BrandRepository>,
+        categories: SharedCategoryRepository,
+        stores: SharedStoreRepository,
+        products: SharedProductRepository,
+        transactions: SharedTransactionRepository,
+    }
+
+    impl Repositories {
+        fn empty() -> Self {
+            Self::with(StoreRepositoryFake::default(), ProductRepositoryFake::default())
+        }
+
+        fn with(stores: StoreRepositoryFake, products: ProductRepositoryFake) -> Self {
+            Self {
+                brands: Arc::new(BrandRepositoryFake::with(vec![Brand::new("Emmi".to_owned())])),
+                categories: CategoryRepositoryFake::with(vec![Category::new(None, "Dairy".to_owned())]).shared(),
+                stores: stores.shared(),
+                products: products.shared(),
+                transactions: TransactionRepositoryFake::default().shared(),
+            }
+        }
+
+        fn interactor(&self) -> RecordPurchaseInteractor<Result<RecordedPurchase, RecordPurchaseInteractorError>> {
+            RecordPurchaseInteractor::new(
+                Arc::clone(&self.brands),
+                Arc::clone(&self.categories),
+                Arc::clone(&self.stores),
+                Arc::clone(&self.products),
+                Arc::clone(&self.transactions),
+                Arc::new(NoOpUseCaseOutputPort {}),
+            )
+        }
+    }
+
+    fn line(product: &str, brand: &str, category: &str, unit: Unit, unitary_price: f64) -> ReceiptLine {
+        ReceiptLine {
+            product: product.to_owned(),
+            brand: brand.to_owned(),
+            category: category.to_owned(),
+            unit,
+            unitary_price,
+        }
+    }
+
+    fn receipt(store: &str, lines: Vec<ReceiptLine>) -> Receipt {
+        Receipt {
+            store: store.to_owned(),
+            datetime: DateTime::<Utc>::default(),
+            lines,
+        }
+    }
+
+    macro_rules! invalid_receipts {
+        ($($name:ident: ($a:expr, $b:expr),)*) => {
+        $(
+            #[tokio::test]
+            async fn $name() {
+                let repositories: Repositories = Repositories::empty();
+                let receipt: Receipt = $a;
+                let expected: Result<RecordedPurchase, RecordPurchaseInteractorError> = Err($b);
+
+                let result: Result<RecordedPurchase, RecordPurchaseInteractorError> = repositories.interactor().execute(receipt).await;
+
+                assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+                assert!(repositories.stores.read().await.retrieve_all().await.unwrap().is_empty());
+            }
+        )*
+        }
+    }
+
+    invalid_receipts! {
+        should_refuse_empty_receipt: (receipt("Migros", vec![]), RecordPurchaseInteractorError::EmptyReceipt),
+        should_refuse_blank_store: (receipt(" ", vec![line("Yogurt", "Emmi", "Dairy", Unit::None, 1.2)]), RecordPurchaseInteractorError::InvalidName("The name ' ' is not valid".to_owned())),
+        should_refuse_blank_brand: (receipt("Migros", vec![line("Yogurt", "", "Dairy", Unit::None, 1.2)]), RecordPurchaseInteractorError::InvalidName("The name '' is not valid".to_owned())),
+        should_refuse_zero_amount: (receipt("Migros", vec![line("Yogurt", "Emmi", "Dairy", Unit::Quantity(0.), 1.2)]), RecordPurchaseInteractorError::InvalidAmount("The amount of 'Yogurt' must be positive".to_owned())),
+        should_refuse_negative_price: (receipt("Migros", vec![line("Yogurt", "Emmi", "Dairy", Unit::Kilograms(0.5), -3.)]), RecordPurchaseInteractorError::InvalidAmount("The price of 'Yogurt' cannot be negative".to_owned())),
+    }
+
+    #[tokio::test]
+    async fn should_create_missing_entities_once_and_compute_total() {
+        let repositories: Repositories = Repositories::empty();
+        let receipt: Receipt = receipt(
+            "Migros",
+            vec![
+                line("Yogurt", "Emmi", "dairy", Unit::Quantity(3.), 0.85),
+                line("Apples", "Migros Bio", "Fruits", Unit::Kilograms(1.5), 4.2),
+                line("yogurt", "EMMI", "Dairy", Unit::None, 0.85),
+            ],
+        );
+
+        let result: RecordedPurchase = repositories.interactor().execute(receipt).await.unwrap();
+
+        let stores: Vec<Store> = repositories.stores.read().await.retrieve_all().await.unwrap();
+        let brands: Vec<String> = repositories
+            .brands
+            .retrieve_all()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|b| b.name)
+            .collect();
+        let categories: Vec<String> = repositories
+            .categories
+            .read()
+            .await
+            .retrieve_all()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        let products: Vec<Product> = repositories.products.read().await.retrieve_all().await.unwrap();
+
+        assert_eq!(result.total, 9.7, "Expected {}, but got {}", 9.7, result.total);
+        assert_eq!(stores, vec![result.transaction.store.clone()]);
+        assert_eq!(brands, vec!["Emmi".to_owned(), "Migros Bio".to_owned()]);
+        assert_eq!(categories, vec!["Dairy".to_owned(), "Fruits".to_owned()]);
+        assert_eq!(products.len(), 2, "Expected 2 products, but got {:?}", products);
+        assert_eq!(result.transaction.items[0].product(), result.transaction.items[2].product());
+        assert_eq!(
+            repositories.transactions.read().await.retrieve_all().await.unwrap(),
+            vec![result.transaction]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_reuse_existing_store_and_product_under_another_spelling() {
+        let migros: Store = Store::new(None, "Migros".to_owned());
+        let yogurt: Product = Product::new(
+            None,
+            "Yogourt nature".to_owned(),
+            Brand::new("Emmi".to_owned()),
+            Category::new(None, "Dairy".to_owned()),
+        );
+        let repositories: Repositories = Repositories::with(
+            StoreRepositoryFake::with(vec![migros.clone()]),
+            ProductRepositoryFake::with(vec![yogurt.clone()]),
+        );
+
+        let result: RecordedPurchase = repositories
+            .interactor()
+            .execute(receipt(" MIGROS", vec![line("yogourt NATURE", "emmi", "Snacks", Unit::None, 1.1)]))
+            .await
+            .unwrap();
+
+        assert_eq!(result.transaction.store, migros);
+        assert_eq!(result.transaction.items[0].product(), &yogurt);
+        assert_eq!(repositories.categories.read().await.retrieve_all().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_unable_to_save() {
+        let repositories: Repositories = Repositories::with(
+            StoreRepositoryFake::failing("Read-only file system"),
+            ProductRepositoryFake::default(),
+        );
+
+        let result: Result<RecordedPurchase, RecordPurchaseInteractorError> = repositories
+            .interactor()
+            .execute(receipt("Migros", vec![line("Yogurt", "Emmi", "Dairy", Unit::None, 1.2)]))
+            .await;
+        let expected: Result<RecordedPurchase, RecordPurchaseInteractorError> = Err(RecordPurchaseInteractorError::UnableToRecordPurchase(
+            "Read-only file system".to_owned(),
+        ));
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    struct NoOpUseCaseOutputPort {}
+
+    impl RecordPurchaseOutputPort<Result<RecordedPurchase, RecordPurchaseInteractorError>> for NoOpUseCaseOutputPort {
+        fn apply(
+            &self,
+            result: Result<RecordedPurchase, RecordPurchaseInteractorError>,
+        ) -> Result<RecordedPurchase, RecordPurchaseInteractorError> {
+            result
+        }
+    }
+}
@@ -0,0 +1,308 @@
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::{
+    StreamExt,
+    stream::{self, BoxStream},
+};
+use tokio::sync::RwLock;
+use uuid_b64::UuidB64;
+
+use crate::domain::{
+    entities::{Brand, Category, NamePolicy, Product, Store, Transaction},
+    repositories::{
+        BrandRepository, BrandRepositoryCreateError, BrandRepositoryDeleteError, BrandRepositoryRestoreError,
+        BrandRepositoryRetrieveAllError, BrandRepositoryUpdateError, CategoryRepository, CategoryRepositoryError, DeleteBehaviour,
+        ProductRepository, ProductRepositoryError, StoreRepository, StoreRepositoryError, TransactionRepository,
+        TransactionRepositoryError, Trashed,
+    },
+};
+
+/// Generates a repository keeping its entities in a vector, bumping versions on update and refusing an entity named
+/// like another one under `$same_name` like the real ones. A fake built with `failing` refuses every call as if its
+/// storage was unreachable.
+macro_rules! fake_repository {
+    (
+        $fake:ident,
+        $entity:ident,
+        $repository:ident,
+        $error:ident,
+        $not_found:ident,
+        $replacement_not_found:ident,
+        $already_exists:ident,
+        $same_name:expr
+    ) => {
+        #[derive(Default)]
+        pub struct $fake {
+            entities: Vec<$entity>,
+            failure: Option<String>,
+            undeletable: Option<UuidB64>,
+        }
+
+        impl $fake {
+            pub fn with(entities: Vec<$entity>) -> Self {
+                Self {
+                    entities,
+                    ..Self::default()
+                }
+            }
+
+            pub fn failing(details: &str) -> Self {
+                Self {
+                    failure: Some(details.to_owned()),
+                    ..Self::default()
+                }
+            }
+
+            pub fn shared(self) -> Arc<RwLock<Box<dyn $repository + Send + Sync>>> {
+                Arc::new(RwLock::new(Box::new(self)))
+            }
+
+            fn check(&self) -> Result<(), $error> {
+                match &self.failure {
+                    Some(details) => Err($error::UnableToAccessStorage(details.clone())),
+                    None => Ok(()),
+                }
+            }
+        }
+
+        #[async_trait]
+        impl $repository for $fake {
+            async fn create_or_update(&mut self, entity: &$entity) -> Result<Option<$entity>, $error> {
+                self.check()?;
+
+                let same_name: fn(&$entity, &$entity) -> bool = $same_name;
+                if self.entities.iter().any(|e| e.id != entity.id && same_name(e, entity)) {
+                    return Err($error::$already_exists);
+                }
+
+                match self.entities.iter_mut().find(|e| e.id == entity.id) {
+                    Some(existing) if existing.version != entity.version => Err($error::VersionConflict {
+                        current_version: existing.version,
+                    }),
+                    Some(existing) => {
+                        let previous: $entity = existing.clone();
+                        *existing = $entity {
+                            version: previous.version + 1,
+                            ..entity.clone()
+                        };
+                        Ok(Some(previous))
+                    }
+                    None => {
+                        self.entities.push(entity.clone());
+                        Ok(None)
+                    }
+                }
+            }
+
+            async fn retrieve_all(&self) -> Result<Vec<$entity>, $error> {
+                self.check()?;
+
+                Ok(self.entities.clone())
+            }
+
+            fn stream_all(&self) -> BoxStream<'_, Result<$entity, $error>> {
+                let entities: Vec<Result<$entity, $error>> = match self.check() {
+                    Ok(()) => self.entities.iter().cloned().map(Ok).collect(),
+                    Err(e) => vec![Err(e)],
+                };
+
+                stream::iter(entities).boxed()
+            }
+
+            async fn delete(&mut self, id: &UuidB64, behaviour: DeleteBehaviour<$entity>) -> Result<$entity, $error> {
+                self.check()?;
+
+                if self.undeletable == Some(*id) {
+                    return Err($error::UnableToAccessStorage("Unable to delete".to_owned()));
+                }
+
+                if let DeleteBehaviour::Reassign(replacement) = behaviour {
+                    if replacement.id == *id || !self.entities.iter().any(|e| e.id == replacement.id) {
+                        return Err($error::$replacement_not_found);
+                    }
+                }
+
+                let position: usize = self.entities.iter().position(|e| e.id == *id).ok_or($error::$not_found)?;
+                Ok(self.entities.remove(position))
+            }
+
+            async fn list_trashed(&self) -> Result<Vec<Trashed<$entity>>, $error> {
+                todo!()
+            }
+
+            async fn restore(&mut self, _: &UuidB64) -> Result<$entity, $error> {
+                todo!()
+            }
+
+            async fn purge_older_than(&mut self, _: DateTime<Utc>) -> Result<Vec<$entity>, $error> {
+                todo!()
+            }
+        }
+    };
+}
+
+fake_repository!(
+    CategoryRepositoryFake,
+    Category,
+    CategoryRepository,
+    CategoryRepositoryError,
+    CategoryNotFound,
+    ReplacementCategoryNotFound,
+    CategoryAlreadyExists,
+    |a, b| NamePolicy::default().matches(&a.name, &b.name)
+);
+fake_repository!(
+    StoreRepositoryFake,
+    Store,
+    StoreRepository,
+    StoreRepositoryError,
+    StoreNotFound,
+    ReplacementStoreNotFound,
+    StoreAlreadyExists,
+    |a, b| NamePolicy::default().matches(&a.name, &b.name)
+);
+impl StoreRepositoryFake {
+    /// A fake whose storage only fails to delete the store `id`.
+    pub fn refusing_to_delete(self, id: UuidB64) -> Self {
+        Self {
+            undeletable: Some(id),
+            ..self
+        }
+    }
+}
+
+fake_repository!(
+    ProductRepositoryFake,
+    Product,
+    ProductRepository,
+    ProductRepositoryError,
+    ProductNotFound,
+    ReplacementProductNotFound,
+    ProductAlreadyExists,
+    |a, b| NamePolicy::default().matches(&a.brand.name, &b.brand.name) && NamePolicy::default().matches(&a.name, &b.name)
+);
+
+#[derive(Debug)]
+pub struct BrandRepositoryFake {
+    brands: Mutex<Vec<Brand>>,
+}
+
+impl BrandRepositoryFake {
+    pub fn with(brands: Vec<Brand>) -> Self {
+        Self {
+            brands: Mutex::new(brands),
+        }
+    }
+}
+
+#[async_trait]
+impl BrandRepository for BrandRepositoryFake {
+    async fn create(&self, brand: &Brand) -> Result<Brand, BrandRepositoryCreateError> {
+        self.brands.lock().unwrap().push(brand.clone());
+        Ok(brand.clone())
+    }
+
+    async fn retrieve_all(&self) -> Result<Vec<Brand>, BrandRepositoryRetrieveAllError> {
+        Ok(self.brands.lock().unwrap().clone())
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Brand, BrandRepositoryRetrieveAllError>> {
+        let brands: Vec<Brand> = self.brands.lock().unwrap().clone();
+
+        stream::iter(brands.into_iter().map(Ok)).boxed()
+    }
+
+    async fn update(&self, _: &Brand) -> Result<Brand, BrandRepositoryUpdateError> {
+        todo!()
+    }
+
+    async fn delete(&self, brand: &Brand, _: DeleteBehaviour<Brand>) -> Result<Brand, BrandRepositoryDeleteError> {
+        let mut brands = self.brands.lock().unwrap();
+        let position: usize = brands
+            .iter()
+            .position(|b| b.name == brand.name)
+            .ok_or(BrandRepositoryDeleteError::BrandNotFound)?;
+
+        Ok(brands.remove(position))
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Brand>>, BrandRepositoryRetrieveAllError> {
+        todo!()
+    }
+
+    async fn restore(&self, _: &Brand) -> Result<Brand, BrandRepositoryRestoreError> {
+        todo!()
+    }
+
+    async fn purge_older_than(&self, _: DateTime<Utc>) -> Result<Vec<Brand>, BrandRepositoryDeleteError> {
+        todo!()
+    }
+}
+
+#[derive(Default)]
+pub struct TransactionRepositoryFake {
+    transactions: Vec<Transaction>,
+    failure: Option<String>,
+}
+
+impl TransactionRepositoryFake {
+    pub fn with(transactions: Vec<Transaction>) -> Self {
+        Self {
+            transactions,
+            failure: None,
+        }
+    }
+
+    pub fn failing(details: &str) -> Self {
+        Self {
+            transactions: vec![],
+            failure: Some(details.to_owned()),
+        }
+    }
+
+    pub fn shared(self) -> Arc<RwLock<Box<dyn TransactionRepository + Send + Sync>>> {
+        Arc::new(RwLock::new(Box::new(self)))
+    }
+}
+
+#[async_trait]
+impl TransactionRepository for TransactionRepositoryFake {
+    async fn create_or_update(&mut self, transaction: &Transaction) -> Result<Option<Transaction>, TransactionRepositoryError> {
+        self.transactions.push(transaction.clone());
+        Ok(None)
+    }
+
+    async fn retrieve_all(&self) -> Result<Vec<Transaction>, TransactionRepositoryError> {
+        match &self.failure {
+            Some(details) => Err(TransactionRepositoryError::UnableToAccessStorage(details.clone())),
+            None => Ok(self.transactions.clone()),
+        }
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Transaction, TransactionRepositoryError>> {
+        let transactions: Vec<Result<Transaction, TransactionRepositoryError>> = match &self.failure {
+            Some(details) => vec![Err(TransactionRepositoryError::UnableToAccessStorage(details.clone()))],
+            None => self.transactions.iter().cloned().map(Ok).collect(),
+        };
+
+        stream::iter(transactions).boxed()
+    }
+
+    async fn delete(&mut self, _: &UuidB64) -> Result<Transaction, TransactionRepositoryError> {
+        todo!()
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Transaction>>, TransactionRepositoryError> {
+        todo!()
+    }
+
+    async fn restore(&mut self, _: &UuidB64) -> Result<Transaction, TransactionRepositoryError> {
+        todo!()
+    }
+
+    async fn purge_older_than(&mut self, _: DateTime<Utc>) -> Result<Vec<Transaction>, TransactionRepositoryError> {
+        todo!()
+    }
+}
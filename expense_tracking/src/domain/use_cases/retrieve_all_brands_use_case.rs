@@ -40,10 +40,15 @@ impl From<BrandRepositoryRetrieveAllError> for RetrieveAllBrandsUseCaseError {
 mod tests {
     use crate::domain::{
         entities::Brand,
-        repositories::{BrandRepository, BrandRepositoryCreateError, BrandRepositoryRetrieveAllError},
+        repositories::{
+            BrandRepository, BrandRepositoryCreateError, BrandRepositoryDeleteError, BrandRepositoryRestoreError,
+            BrandRepositoryRetrieveAllError, BrandRepositoryUpdateError, DeleteBehaviour, Trashed,
+        },
         use_cases::retrieve_all_brands_use_case::{RetrieveAllBrandsUseCase, RetrieveAllBrandsUseCaseError},
     };
     use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use futures::stream::BoxStream;
 
     use std::sync::Arc;
 
@@ -106,5 +111,29 @@ mod tests {
         async fn retrieve_all(&self) -> Result<Vec<Brand>, BrandRepositoryRetrieveAllError> {
             self.on_retrieve_all.clone().unwrap_or_else(|| todo!())
         }
+
+        fn stream_all(&self) -> BoxStream<'_, Result<Brand, BrandRepositoryRetrieveAllError>> {
+            todo!()
+        }
+
+        async fn update(&self, _: &Brand) -> Result<Brand, BrandRepositoryUpdateError> {
+            todo!()
+        }
+
+        async fn delete(&self, _: &Brand, _: DeleteBehaviour<Brand>) -> Result<Brand, BrandRepositoryDeleteError> {
+            todo!()
+        }
+
+        async fn list_trashed(&self) -> Result<Vec<Trashed<Brand>>, BrandRepositoryRetrieveAllError> {
+            todo!()
+        }
+
+        async fn restore(&self, _: &Brand) -> Result<Brand, BrandRepositoryRestoreError> {
+            todo!()
+        }
+
+        async fn purge_older_than(&self, _: DateTime<Utc>) -> Result<Vec<Brand>, BrandRepositoryDeleteError> {
+            todo!()
+        }
     }
 }
@@ -0,0 +1,115 @@
+use std::{any::Any, sync::Arc};
+
+use crate::domain::{
+    entities::Category,
+    repositories::{CategoryRepositoryError, SharedCategoryRepository},
+};
+
+pub trait RetrieveAllCategoriesOutputPort<T: Any>: Send + Sync {
+    fn apply(&self, _: Result<Vec<Category>, RetrieveAllCategoriesInteractorError>) -> T;
+}
+
+pub struct RetrieveAllCategoriesInteractor<Output: Any> {
+    category_repository: SharedCategoryRepository,
+    presenter: Arc<dyn RetrieveAllCategoriesOutputPort<Output>>,
+}
+
+impl<Output: Any> RetrieveAllCategoriesInteractor<Output> {
+    pub fn new(category_repository: SharedCategoryRepository, presenter: Arc<dyn RetrieveAllCategoriesOutputPort<Output>>) -> Self {
+        Self {
+            category_repository,
+            presenter,
+        }
+    }
+
+    pub async fn execute(&self) -> Output {
+        let result: Result<Vec<Category>, RetrieveAllCategoriesInteractorError> = self
+            .category_repository
+            .read()
+            .await
+            .retrieve_all()
+            .await
+            .map_err(|e: CategoryRepositoryError| e.into());
+
+        self.presenter.apply(result)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RetrieveAllCategoriesInteractorError {
+    UnableToRetrieveCategories(String),
+}
+
+impl From<CategoryRepositoryError> for RetrieveAllCategoriesInteractorError {
+    fn from(value: CategoryRepositoryError) -> Self {
+        match value {
+            CategoryRepositoryError::UnableToAccessStorage(details) => {
+                RetrieveAllCategoriesInteractorError::UnableToRetrieveCategories(details)
+            }
+            e => RetrieveAllCategoriesInteractorError::UnableToRetrieveCategories(format!("{:?}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use uuid::Uuid;
+    use uuid_b64::UuidB64;
+
+    use crate::domain::{
+        entities::Category,
+        use_cases::{RetrieveAllCategoriesOutputPort, repository_fakes::CategoryRepositoryFake},
+    };
+
+    use super::{RetrieveAllCategoriesInteractor, RetrieveAllCategoriesInteractorError};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: ($a:expr, $b:expr))*) => {
+            $(
+                #[tokio::test]
+                async fn $name() {
+                    let category_repository: CategoryRepositoryFake = $a;
+                    let expected: Result<Vec<Category>, RetrieveAllCategoriesInteractorError> = $b;
+
+                    let use_case: RetrieveAllCategoriesInteractor<Result<Vec<Category>, RetrieveAllCategoriesInteractorError>> =
+                        RetrieveAllCategoriesInteractor::new(category_repository.shared(), Arc::new(NoOpUseCaseOutputPort {}));
+
+                    let result: Result<Vec<Category>, RetrieveAllCategoriesInteractorError> = use_case.execute().await;
+
+                    assert_eq!(
+                        result, expected,
+                        "Expected {:?}, but got {:?}",
+                        expected, result
+                    )
+                }
+            )*
+        }
+    }
+
+    parameterized_tests! {
+        should_return_err_if_unable_to_retrieve_categories: (
+            CategoryRepositoryFake::failing("Nibh ipsum consequat nisl vel"), Err(RetrieveAllCategoriesInteractorError::UnableToRetrieveCategories("Nibh ipsum consequat nisl vel".to_owned()))
+        )
+        should_return_ok_no_categories: (CategoryRepositoryFake::default(), Ok(vec![]))
+        should_return_ok_multiple_categories: (
+            CategoryRepositoryFake::with(vec![category(1, "Fruits"), category(2, "Dairy")]), Ok(vec![category(1, "Fruits"), category(2, "Dairy")])
+        )
+    }
+
+    fn category(id: u128, name: &str) -> Category {
+        Category::new(Some(UuidB64::from(Uuid::from_u128(id))), name.to_owned())
+    }
+
+    struct NoOpUseCaseOutputPort {}
+
+    impl RetrieveAllCategoriesOutputPort<Result<Vec<Category>, RetrieveAllCategoriesInteractorError>> for NoOpUseCaseOutputPort {
+        fn apply(
+            &self,
+            result: Result<Vec<Category>, RetrieveAllCategoriesInteractorError>,
+        ) -> Result<Vec<Category>, RetrieveAllCategoriesInteractorError> {
+            result
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::{any::Any, sync::Arc};
+
+use crate::domain::{
+    entities::Product,
+    repositories::{ProductRepositoryError, SharedProductRepository},
+};
+
+pub trait RetrieveAllProductsOutputPort<T: Any>: Send + Sync {
+    fn apply(&self, _: Result<Vec<Product>, RetrieveAllProductsInteractorError>) -> T;
+}
+
+pub struct RetrieveAllProductsInteractor<Output: Any> {
+    product_repository: SharedProductRepository,
+    presenter: Arc<dyn RetrieveAllProductsOutputPort<Output>>,
+}
+
+impl<Output: Any> RetrieveAllProductsInteractor<Output> {
+    pub fn new(product_repository: SharedProductRepository, presenter: Arc<dyn RetrieveAllProductsOutputPort<Output>>) -> Self {
+        Self {
+            product_repository,
+            presenter,
+        }
+    }
+
+    pub async fn execute(&self) -> Output {
+        let result: Result<Vec<Product>, RetrieveAllProductsInteractorError> = self
+            .product_repository
+            .read()
+            .await
+            .retrieve_all()
+            .await
+            .map_err(|e: ProductRepositoryError| e.into());
+
+        self.presenter.apply(result)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RetrieveAllProductsInteractorError {
+    UnableToRetrieveProducts(String),
+}
+
+impl From<ProductRepositoryError> for RetrieveAllProductsInteractorError {
+    fn from(value: ProductRepositoryError) -> Self {
+        match value {
+            ProductRepositoryError::UnableToAccessStorage(details) => RetrieveAllProductsInteractorError::UnableToRetrieveProducts(details),
+            e => RetrieveAllProductsInteractorError::UnableToRetrieveProducts(format!("{:?}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use uuid::Uuid;
+    use uuid_b64::UuidB64;
+
+    use crate::domain::{
+        entities::{Brand, Category, Product},
+        use_cases::{RetrieveAllProductsOutputPort, repository_fakes::ProductRepositoryFake},
+    };
+
+    use super::{RetrieveAllProductsInteractor, RetrieveAllProductsInteractorError};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: ($a:expr, $b:expr))*) => {
+            $(
+                #[tokio::test]
+                async fn $name() {
+                    let product_repository: ProductRepositoryFake = $a;
+                    let expected: Result<Vec<Product>, RetrieveAllProductsInteractorError> = $b;
+
+                    let use_case: RetrieveAllProductsInteractor<Result<Vec<Product>, RetrieveAllProductsInteractorError>> =
+                        RetrieveAllProductsInteractor::new(product_repository.shared(), Arc::new(NoOpUseCaseOutputPort {}));
+
+                    let result: Result<Vec<Product>, RetrieveAllProductsInteractorError> = use_case.execute().await;
+
+                    assert_eq!(
+                        result, expected,
+                        "Expected {:?}, but got {:?}",
+                        expected, result
+                    )
+                }
+            )*
+        }
+    }
+
+    parameterized_tests! {
+        should_return_err_if_unable_to_retrieve_products: (
+            ProductRepositoryFake::failing("Nibh ipsum consequat nisl vel"), Err(RetrieveAllProductsInteractorError::UnableToRetrieveProducts("Nibh ipsum consequat nisl vel".to_owned()))
+        )
+        should_return_ok_no_products: (ProductRepositoryFake::default(), Ok(vec![]))
+        should_return_ok_multiple_products: (
+            ProductRepositoryFake::with(vec![product(1, "Yogurt"), product(2, "Cheese")]), Ok(vec![product(1, "Yogurt"), product(2, "Cheese")])
+        )
+    }
+
+    fn product(id: u128, name: &str) -> Product {
+        Product::new(
+            Some(UuidB64::from(Uuid::from_u128(id))),
+            name.to_owned(),
+            Brand::new("Emmi".to_owned()),
+            Category::new(Some(UuidB64::from(Uuid::nil())), "Dairy".to_owned()),
+        )
+    }
+
+    struct NoOpUseCaseOutputPort {}
+
+    impl RetrieveAllProductsOutputPort<Result<Vec<Product>, RetrieveAllProductsInteractorError>> for NoOpUseCaseOutputPort {
+        fn apply(
+            &self,
+            result: Result<Vec<Product>, RetrieveAllProductsInteractorError>,
+        ) -> Result<Vec<Product>, RetrieveAllProductsInteractorError> {
+            result
+        }
+    }
+}
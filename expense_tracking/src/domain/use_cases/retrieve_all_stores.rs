@@ -0,0 +1,113 @@
+use std::{any::Any, sync::Arc};
+
+use crate::domain::{
+    entities::Store,
+    repositories::{SharedStoreRepository, StoreRepositoryError},
+};
+
+pub trait RetrieveAllStoresOutputPort<T: Any>: Send + Sync {
+    fn apply(&self, _: Result<Vec<Store>, RetrieveAllStoresInteractorError>) -> T;
+}
+
+pub struct RetrieveAllStoresInteractor<Output: Any> {
+    store_repository: SharedStoreRepository,
+    presenter: Arc<dyn RetrieveAllStoresOutputPort<Output>>,
+}
+
+impl<Output: Any> RetrieveAllStoresInteractor<Output> {
+    pub fn new(store_repository: SharedStoreRepository, presenter: Arc<dyn RetrieveAllStoresOutputPort<Output>>) -> Self {
+        Self {
+            store_repository,
+            presenter,
+        }
+    }
+
+    pub async fn execute(&self) -> Output {
+        let result: Result<Vec<Store>, RetrieveAllStoresInteractorError> = self
+            .store_repository
+            .read()
+            .await
+            .retrieve_all()
+            .await
+            .map_err(|e: StoreRepositoryError| e.into());
+
+        self.presenter.apply(result)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RetrieveAllStoresInteractorError {
+    UnableToRetrieveStores(String),
+}
+
+impl From<StoreRepositoryError> for RetrieveAllStoresInteractorError {
+    fn from(value: StoreRepositoryError) -> Self {
+        match value {
+            StoreRepositoryError::UnableToAccessStorage(details) => RetrieveAllStoresInteractorError::UnableToRetrieveStores(details),
+            e => RetrieveAllStoresInteractorError::UnableToRetrieveStores(format!("{:?}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use uuid::Uuid;
+    use uuid_b64::UuidB64;
+
+    use crate::domain::{
+        entities::Store,
+        use_cases::{RetrieveAllStoresOutputPort, repository_fakes::StoreRepositoryFake},
+    };
+
+    use super::{RetrieveAllStoresInteractor, RetrieveAllStoresInteractorError};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: ($a:expr, $b:expr))*) => {
+            $(
+                #[tokio::test]
+                async fn $name() {
+                    let store_repository: StoreRepositoryFake = $a;
+                    let expected: Result<Vec<Store>, RetrieveAllStoresInteractorError> = $b;
+
+                    let use_case: RetrieveAllStoresInteractor<Result<Vec<Store>, RetrieveAllStoresInteractorError>> =
+                        RetrieveAllStoresInteractor::new(store_repository.shared(), Arc::new(NoOpUseCaseOutputPort {}));
+
+                    let result: Result<Vec<Store>, RetrieveAllStoresInteractorError> = use_case.execute().await;
+
+                    assert_eq!(
+                        result, expected,
+                        "Expected {:?}, but got {:?}",
+                        expected, result
+                    )
+                }
+            )*
+        }
+    }
+
+    parameterized_tests! {
+        should_return_err_if_unable_to_retrieve_stores: (
+            StoreRepositoryFake::failing("Nibh ipsum consequat nisl vel"), Err(RetrieveAllStoresInteractorError::UnableToRetrieveStores("Nibh ipsum consequat nisl vel".to_owned()))
+        )
+        should_return_ok_no_stores: (StoreRepositoryFake::default(), Ok(vec![]))
+        should_return_ok_multiple_stores: (
+            StoreRepositoryFake::with(vec![store(1, "Migros"), store(2, "Denner")]), Ok(vec![store(1, "Migros"), store(2, "Denner")])
+        )
+    }
+
+    fn store(id: u128, name: &str) -> Store {
+        Store::new(Some(UuidB64::from(Uuid::from_u128(id))), name.to_owned())
+    }
+
+    struct NoOpUseCaseOutputPort {}
+
+    impl RetrieveAllStoresOutputPort<Result<Vec<Store>, RetrieveAllStoresInteractorError>> for NoOpUseCaseOutputPort {
+        fn apply(
+            &self,
+            result: Result<Vec<Store>, RetrieveAllStoresInteractorError>,
+        ) -> Result<Vec<Store>, RetrieveAllStoresInteractorError> {
+            result
+        }
+    }
+}
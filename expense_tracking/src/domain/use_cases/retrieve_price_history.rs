@@ -0,0 +1,139 @@
+use std::{any::Any, sync::Arc};
+
+use futures::TryStreamExt;
+use uuid_b64::UuidB64;
+
+use crate::domain::{
+    reports::{PriceHistory, PriceHistoryBuilder},
+    repositories::{SharedTransactionRepository, TransactionRepositoryError},
+};
+
+pub trait RetrievePriceHistoryOutputPort<T: Any>: Send + Sync {
+    fn apply(&self, _: Result<Vec<PriceHistory>, RetrievePriceHistoryInteractorError>) -> T;
+}
+
+pub struct RetrievePriceHistoryInteractor<Output: Any> {
+    transaction_repository: SharedTransactionRepository,
+    presenter: Arc<dyn RetrievePriceHistoryOutputPort<Output>>,
+}
+
+impl<Output: Any> RetrievePriceHistoryInteractor<Output> {
+    pub fn new(transaction_repository: SharedTransactionRepository, presenter: Arc<dyn RetrievePriceHistoryOutputPort<Output>>) -> Self {
+        Self {
+            transaction_repository,
+            presenter,
+        }
+    }
+
+    /// Retrieves the price history of every product, or of `product` only, as observed at any store or at `store`
+    /// only.
+    pub async fn execute(&self, product: Option<UuidB64>, store: Option<UuidB64>) -> Output {
+        let repository = self.transaction_repository.read().await;
+        let result: Result<Vec<PriceHistory>, RetrievePriceHistoryInteractorError> = repository
+            .stream_all()
+            .try_fold(PriceHistoryBuilder::new(product, store), |mut builder, transaction| async move {
+                builder.add(&transaction);
+                Ok(builder)
+            })
+            .await
+            .map(PriceHistoryBuilder::build)
+            .map_err(|e: TransactionRepositoryError| e.into());
+
+        self.presenter.apply(result)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RetrievePriceHistoryInteractorError {
+    UnableToRetrieveTransactions(String),
+}
+
+impl From<TransactionRepositoryError> for RetrievePriceHistoryInteractorError {
+    fn from(value: TransactionRepositoryError) -> Self {
+        match value {
+            TransactionRepositoryError::UnableToAccessStorage(details) => {
+                RetrievePriceHistoryInteractorError::UnableToRetrieveTransactions(details)
+            }
+            e => RetrievePriceHistoryInteractorError::UnableToRetrieveTransactions(format!("{:?}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::{DateTime, Utc};
+
+    use crate::domain::{
+        entities::{Brand, Category, Item, Product, Store, Transaction, Unit},
+        reports::PriceHistory,
+        use_cases::{RetrievePriceHistoryOutputPort, repository_fakes::TransactionRepositoryFake},
+    };
+
+    use super::{RetrievePriceHistoryInteractor, RetrievePriceHistoryInteractorError};
+
+    type Histories = Result<Vec<PriceHistory>, RetrievePriceHistoryInteractorError>;
+
+    #[tokio::test]
+    async fn execute_should_return_err_if_unable_to_retrieve_transactions() {
+        let use_case: RetrievePriceHistoryInteractor<Histories> = RetrievePriceHistoryInteractor::new(
+            TransactionRepositoryFake::failing("Aenean vel orci").shared(),
+            Arc::new(NoOpUseCaseOutputPort {}),
+        );
+
+        let result: Histories = use_case.execute(None, None).await;
+        let expected: Histories = Err(RetrievePriceHistoryInteractorError::UnableToRetrieveTransactions(
+            "Aenean vel orci".to_owned(),
+        ));
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn execute_should_return_the_history_of_the_product() {
+        let store: Store = Store::new(None, "Migros".to_owned());
+        let category: Category = Category::new(None, "Bakery".to_owned());
+        let bread: Product = Product::new(None, "Bread".to_owned(), Brand::new("Jowa".to_owned()), category.clone());
+        let croissant: Product = Product::new(None, "Croissant".to_owned(), Brand::new("Jowa".to_owned()), category);
+        let transactions: Vec<Transaction> = vec![
+            Transaction::new(
+                None,
+                vec![
+                    Item::new(None, bread.clone(), Unit::None, 2.95),
+                    Item::new(None, croissant, Unit::Quantity(4.), 1.1),
+                ],
+                store.clone(),
+                at("2024-03-04T08:00:00Z"),
+            ),
+            Transaction::new(
+                None,
+                vec![Item::new(None, bread.clone(), Unit::None, 3.1)],
+                store,
+                at("2024-04-04T08:00:00Z"),
+            ),
+        ];
+        let use_case: RetrievePriceHistoryInteractor<Histories> = RetrievePriceHistoryInteractor::new(
+            TransactionRepositoryFake::with(transactions).shared(),
+            Arc::new(NoOpUseCaseOutputPort {}),
+        );
+
+        let result: Vec<PriceHistory> = use_case.execute(Some(bread.id), None).await.unwrap();
+
+        assert_eq!(result.len(), 1, "Expected a single history, but got {:?}", result);
+        assert_eq!(result[0].product, bread);
+        assert_eq!(result[0].last().map(|o| o.price), Some(3.1));
+    }
+
+    fn at(datetime: &str) -> DateTime<Utc> {
+        datetime.parse().unwrap()
+    }
+
+    struct NoOpUseCaseOutputPort {}
+
+    impl RetrievePriceHistoryOutputPort<Histories> for NoOpUseCaseOutputPort {
+        fn apply(&self, result: Histories) -> Histories {
+            result
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use std::{any::Any, sync::Arc};
+
+use crate::domain::{
+    entities::{Category, NamePolicy},
+    repositories::{CategoryRepositoryError, SharedCategoryRepository},
+};
+
+pub trait UpdateCategoryOutputPort<T: Any>: Send + Sync {
+    fn apply(&self, _: Result<Category, UpdateCategoryInteractorError>) -> T;
+}
+
+pub struct UpdateCategoryInteractor<Output: Any> {
+    category_repository: SharedCategoryRepository,
+    presenter: Arc<dyn UpdateCategoryOutputPort<Output>>,
+    name_policy: NamePolicy,
+}
+
+impl<Output: Any> UpdateCategoryInteractor<Output> {
+    pub fn new(category_repository: SharedCategoryRepository, presenter: Arc<dyn UpdateCategoryOutputPort<Output>>) -> Self {
+        Self {
+            category_repository,
+            presenter,
+            name_policy: NamePolicy::default(),
+        }
+    }
+
+    pub fn with_name_policy(self, name_policy: NamePolicy) -> Self {
+        Self { name_policy, ..self }
+    }
+
+    /// Saves `category` over the stored one, which must still be at the same version.
+    pub async fn execute(&self, category: Category) -> Output {
+        self.presenter.apply(self.update(category).await)
+    }
+
+    async fn update(&self, category: Category) -> Result<Category, UpdateCategoryInteractorError> {
+        if category.name.trim().is_empty() {
+            return Err(UpdateCategoryInteractorError::InvalidName(format!(
+                "The name '{}' is not valid",
+                category.name
+            )));
+        }
+
+        let mut category_repository = self.category_repository.write().await;
+
+        let categories: Vec<Category> = category_repository.retrieve_all().await?;
+        if !categories.iter().any(|c| c.id == category.id) {
+            return Err(UpdateCategoryInteractorError::CategoryNotFound);
+        }
+        if categories
+            .iter()
+            .any(|c| c.id != category.id && self.name_policy.matches(&c.name, &category.name))
+        {
+            return Err(UpdateCategoryInteractorError::CategoryAlreadyExists);
+        }
+
+        category_repository.create_or_update(&category).await?;
+
+        Ok(Category {
+            version: category.version + 1,
+            ..category
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum UpdateCategoryInteractorError {
+    InvalidName(String),
+    CategoryNotFound,
+    CategoryAlreadyExists,
+    VersionConflict { current_version: u64 },
+    UnableToSaveCategory(String),
+}
+
+impl From<CategoryRepositoryError> for UpdateCategoryInteractorError {
+    fn from(value: CategoryRepositoryError) -> Self {
+        match value {
+            CategoryRepositoryError::CategoryNotFound => UpdateCategoryInteractorError::CategoryNotFound,
+            CategoryRepositoryError::CategoryAlreadyExists => UpdateCategoryInteractorError::CategoryAlreadyExists,
+            CategoryRepositoryError::VersionConflict { current_version } => {
+                UpdateCategoryInteractorError::VersionConflict { current_version }
+            }
+            CategoryRepositoryError::UnableToAccessStorage(details) => UpdateCategoryInteractorError::UnableToSaveCategory(details),
+            e => UpdateCategoryInteractorError::UnableToSaveCategory(format!("{:?}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::domain::{
+        entities::Category,
+        repositories::SharedCategoryRepository,
+        use_cases::{UpdateCategoryOutputPort, repository_fakes::CategoryRepositoryFake},
+    };
+
+    use super::{UpdateCategoryInteractor, UpdateCategoryInteractorError};
+
+    fn given_interactor(
+        category_repository: &SharedCategoryRepository,
+    ) -> UpdateCategoryInteractor<Result<Category, UpdateCategoryInteractorError>> {
+        UpdateCategoryInteractor::new(Arc::clone(category_repository), Arc::new(NoOpUseCaseOutputPort {}))
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_given_invalid_name() {
+        let fruits: Category = Category::new(None, "Fruits".to_owned());
+        let category_repository: SharedCategoryRepository = CategoryRepositoryFake::with(vec![fruits.clone()]).shared();
+        let use_case: UpdateCategoryInteractor<Result<Category, UpdateCategoryInteractorError>> = given_interactor(&category_repository);
+
+        let result: Result<Category, UpdateCategoryInteractorError> = use_case
+            .execute(Category {
+                name: "".to_owned(),
+                ..fruits
+            })
+            .await;
+        let expected: Result<Category, UpdateCategoryInteractorError> =
+            Err(UpdateCategoryInteractorError::InvalidName("The name '' is not valid".to_owned()));
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_category_does_not_exist() {
+        let category_repository: SharedCategoryRepository = CategoryRepositoryFake::default().shared();
+        let use_case: UpdateCategoryInteractor<Result<Category, UpdateCategoryInteractorError>> = given_interactor(&category_repository);
+
+        let result: Result<Category, UpdateCategoryInteractorError> = use_case.execute(Category::new(None, "Fruits".to_owned())).await;
+        let expected: Result<Category, UpdateCategoryInteractorError> = Err(UpdateCategoryInteractorError::CategoryNotFound);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_renamed_after_another_category() {
+        let fruits: Category = Category::new(None, "Fruits".to_owned());
+        let category_repository: SharedCategoryRepository =
+            CategoryRepositoryFake::with(vec![fruits.clone(), Category::new(None, "Dairy".to_owned())]).shared();
+        let use_case: UpdateCategoryInteractor<Result<Category, UpdateCategoryInteractorError>> = given_interactor(&category_repository);
+
+        let result: Result<Category, UpdateCategoryInteractorError> = use_case
+            .execute(Category {
+                name: "DAIRY".to_owned(),
+                ..fruits
+            })
+            .await;
+        let expected: Result<Category, UpdateCategoryInteractorError> = Err(UpdateCategoryInteractorError::CategoryAlreadyExists);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_category_changed_meanwhile() {
+        let fruits: Category = Category::new(None, "Fruits".to_owned());
+        let category_repository: SharedCategoryRepository = CategoryRepositoryFake::with(vec![Category {
+            version: 2,
+            ..fruits.clone()
+        }])
+        .shared();
+        let use_case: UpdateCategoryInteractor<Result<Category, UpdateCategoryInteractorError>> = given_interactor(&category_repository);
+
+        let result: Result<Category, UpdateCategoryInteractorError> = use_case.execute(fruits).await;
+        let expected: Result<Category, UpdateCategoryInteractorError> =
+            Err(UpdateCategoryInteractorError::VersionConflict { current_version: 2 });
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn should_return_category_at_its_new_version_if_success() {
+        let fruits: Category = Category::new(None, "Fruits".to_owned());
+        let category_repository: SharedCategoryRepository = CategoryRepositoryFake::with(vec![fruits.clone()]).shared();
+        let use_case: UpdateCategoryInteractor<Result<Category, UpdateCategoryInteractorError>> = given_interactor(&category_repository);
+
+        let result: Result<Category, UpdateCategoryInteractorError> = use_case
+            .execute(Category {
+                name: "fruits".to_owned(),
+                ..fruits.clone()
+            })
+            .await;
+        let expected: Category = Category {
+            name: "fruits".to_owned(),
+            version: 1,
+            ..fruits
+        };
+        let stored: Vec<Category> = category_repository.read().await.retrieve_all().await.unwrap();
+
+        assert_eq!(result, Ok(expected.clone()), "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(
+            stored,
+            vec![expected.clone()],
+            "Expected {:?}, but got {:?}",
+            vec![expected],
+            stored
+        );
+    }
+
+    struct NoOpUseCaseOutputPort {}
+
+    impl UpdateCategoryOutputPort<Result<Category, UpdateCategoryInteractorError>> for NoOpUseCaseOutputPort {
+        fn apply(&self, result: Result<Category, UpdateCategoryInteractorError>) -> Result<Category, UpdateCategoryInteractorError> {
+            result
+        }
+    }
+}
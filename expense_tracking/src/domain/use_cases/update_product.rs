@@ -0,0 +1,225 @@
+use std::{any::Any, sync::Arc};
+
+use crate::domain::{
+    entities::{NamePolicy, Product},
+    repositories::{ProductRepositoryError, SharedProductRepository},
+};
+
+pub trait UpdateProductOutputPort<T: Any>: Send + Sync {
+    fn apply(&self, _: Result<Product, UpdateProductInteractorError>) -> T;
+}
+
+pub struct UpdateProductInteractor<Output: Any> {
+    product_repository: SharedProductRepository,
+    presenter: Arc<dyn UpdateProductOutputPort<Output>>,
+    name_policy: NamePolicy,
+}
+
+impl<Output: Any> UpdateProductInteractor<Output> {
+    pub fn new(product_repository: SharedProductRepository, presenter: Arc<dyn UpdateProductOutputPort<Output>>) -> Self {
+        Self {
+            product_repository,
+            presenter,
+            name_policy: NamePolicy::default(),
+        }
+    }
+
+    pub fn with_name_policy(self, name_policy: NamePolicy) -> Self {
+        Self { name_policy, ..self }
+    }
+
+    /// Saves `product` over the stored one, which must still be at the same version. Its brand and category must
+    /// exist, which the repository checks.
+    pub async fn execute(&self, product: Product) -> Output {
+        self.presenter.apply(self.update(product).await)
+    }
+
+    async fn update(&self, product: Product) -> Result<Product, UpdateProductInteractorError> {
+        if product.name.trim().is_empty() {
+            return Err(UpdateProductInteractorError::InvalidName(format!(
+                "The name '{}' is not valid",
+                product.name
+            )));
+        }
+
+        let mut product_repository = self.product_repository.write().await;
+
+        let products: Vec<Product> = product_repository.retrieve_all().await?;
+        if !products.iter().any(|p| p.id == product.id) {
+            return Err(UpdateProductInteractorError::ProductNotFound);
+        }
+        if products.iter().any(|p| {
+            p.id != product.id
+                && self.name_policy.matches(&p.name, &product.name)
+                && self.name_policy.matches(&p.brand.name, &product.brand.name)
+        }) {
+            return Err(UpdateProductInteractorError::ProductAlreadyExists);
+        }
+
+        product_repository.create_or_update(&product).await?;
+
+        Ok(Product {
+            version: product.version + 1,
+            ..product
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum UpdateProductInteractorError {
+    InvalidName(String),
+    ProductNotFound,
+    ProductAlreadyExists,
+    BrandNotFound,
+    CategoryNotFound,
+    VersionConflict { current_version: u64 },
+    UnableToSaveProduct(String),
+}
+
+impl From<ProductRepositoryError> for UpdateProductInteractorError {
+    fn from(value: ProductRepositoryError) -> Self {
+        match value {
+            ProductRepositoryError::ProductNotFound => UpdateProductInteractorError::ProductNotFound,
+            ProductRepositoryError::ProductAlreadyExists => UpdateProductInteractorError::ProductAlreadyExists,
+            ProductRepositoryError::BrandNotFound => UpdateProductInteractorError::BrandNotFound,
+            ProductRepositoryError::CategoryNotFound => UpdateProductInteractorError::CategoryNotFound,
+            ProductRepositoryError::VersionConflict { current_version } => {
+                UpdateProductInteractorError::VersionConflict { current_version }
+            }
+            ProductRepositoryError::UnableToAccessStorage(details) => UpdateProductInteractorError::UnableToSaveProduct(details),
+            e => UpdateProductInteractorError::UnableToSaveProduct(format!("{:?}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::domain::{
+        entities::{Brand, Category, Product},
+        repositories::{ProductRepositoryError, SharedProductRepository},
+        use_cases::{UpdateProductOutputPort, repository_fakes::ProductRepositoryFake},
+    };
+
+    use super::{UpdateProductInteractor, UpdateProductInteractorError};
+
+    fn given_interactor(
+        product_repository: &SharedProductRepository,
+    ) -> UpdateProductInteractor<Result<Product, UpdateProductInteractorError>> {
+        UpdateProductInteractor::new(Arc::clone(product_repository), Arc::new(NoOpUseCaseOutputPort {}))
+    }
+
+    fn product(name: &str, brand: &str) -> Product {
+        Product::new(None, name.to_owned(), Brand::new(brand.to_owned()), Category::default())
+    }
+
+    #[test]
+    fn should_convert_dangling_references() {
+        let result: Vec<UpdateProductInteractorError> =
+            vec![ProductRepositoryError::BrandNotFound, ProductRepositoryError::CategoryNotFound]
+                .into_iter()
+                .map(UpdateProductInteractorError::from)
+                .collect();
+        let expected: Vec<UpdateProductInteractorError> = vec![
+            UpdateProductInteractorError::BrandNotFound,
+            UpdateProductInteractorError::CategoryNotFound,
+        ];
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_given_invalid_name() {
+        let yogurt: Product = product("Yogurt", "Emmi");
+        let product_repository: SharedProductRepository = ProductRepositoryFake::with(vec![yogurt.clone()]).shared();
+        let use_case: UpdateProductInteractor<Result<Product, UpdateProductInteractorError>> = given_interactor(&product_repository);
+
+        let result: Result<Product, UpdateProductInteractorError> = use_case
+            .execute(Product {
+                name: " ".to_owned(),
+                ..yogurt
+            })
+            .await;
+        let expected: Result<Product, UpdateProductInteractorError> =
+            Err(UpdateProductInteractorError::InvalidName("The name ' ' is not valid".to_owned()));
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_product_does_not_exist() {
+        let product_repository: SharedProductRepository = ProductRepositoryFake::default().shared();
+        let use_case: UpdateProductInteractor<Result<Product, UpdateProductInteractorError>> = given_interactor(&product_repository);
+
+        let result: Result<Product, UpdateProductInteractorError> = use_case.execute(product("Yogurt", "Emmi")).await;
+        let expected: Result<Product, UpdateProductInteractorError> = Err(UpdateProductInteractorError::ProductNotFound);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_renamed_after_another_product_of_the_brand() {
+        let yogurt: Product = product("Yogurt", "Emmi");
+        let product_repository: SharedProductRepository =
+            ProductRepositoryFake::with(vec![yogurt.clone(), product("Cheese", "Emmi")]).shared();
+        let use_case: UpdateProductInteractor<Result<Product, UpdateProductInteractorError>> = given_interactor(&product_repository);
+
+        let result: Result<Product, UpdateProductInteractorError> = use_case
+            .execute(Product {
+                name: "cheese".to_owned(),
+                ..yogurt
+            })
+            .await;
+        let expected: Result<Product, UpdateProductInteractorError> = Err(UpdateProductInteractorError::ProductAlreadyExists);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn should_allow_name_of_another_brand_product() {
+        let yogurt: Product = product("Yogurt", "Emmi");
+        let product_repository: SharedProductRepository =
+            ProductRepositoryFake::with(vec![yogurt.clone(), product("Cheese", "Nestlé")]).shared();
+        let use_case: UpdateProductInteractor<Result<Product, UpdateProductInteractorError>> = given_interactor(&product_repository);
+
+        let result: Result<Product, UpdateProductInteractorError> = use_case
+            .execute(Product {
+                name: "Cheese".to_owned(),
+                ..yogurt.clone()
+            })
+            .await;
+        let expected: Result<Product, UpdateProductInteractorError> = Ok(Product {
+            name: "Cheese".to_owned(),
+            version: 1,
+            ..yogurt
+        });
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_product_changed_meanwhile() {
+        let yogurt: Product = product("Yogurt", "Emmi");
+        let product_repository: SharedProductRepository = ProductRepositoryFake::with(vec![Product {
+            version: 4,
+            ..yogurt.clone()
+        }])
+        .shared();
+        let use_case: UpdateProductInteractor<Result<Product, UpdateProductInteractorError>> = given_interactor(&product_repository);
+
+        let result: Result<Product, UpdateProductInteractorError> = use_case.execute(yogurt).await;
+        let expected: Result<Product, UpdateProductInteractorError> =
+            Err(UpdateProductInteractorError::VersionConflict { current_version: 4 });
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    struct NoOpUseCaseOutputPort {}
+
+    impl UpdateProductOutputPort<Result<Product, UpdateProductInteractorError>> for NoOpUseCaseOutputPort {
+        fn apply(&self, result: Result<Product, UpdateProductInteractorError>) -> Result<Product, UpdateProductInteractorError> {
+            result
+        }
+    }
+}
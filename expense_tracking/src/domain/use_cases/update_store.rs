@@ -0,0 +1,202 @@
+use std::{any::Any, sync::Arc};
+
+use crate::domain::{
+    entities::{NamePolicy, Store},
+    repositories::{SharedStoreRepository, StoreRepositoryError},
+};
+
+pub trait UpdateStoreOutputPort<T: Any>: Send + Sync {
+    fn apply(&self, _: Result<Store, UpdateStoreInteractorError>) -> T;
+}
+
+pub struct UpdateStoreInteractor<Output: Any> {
+    store_repository: SharedStoreRepository,
+    presenter: Arc<dyn UpdateStoreOutputPort<Output>>,
+    name_policy: NamePolicy,
+}
+
+impl<Output: Any> UpdateStoreInteractor<Output> {
+    pub fn new(store_repository: SharedStoreRepository, presenter: Arc<dyn UpdateStoreOutputPort<Output>>) -> Self {
+        Self {
+            store_repository,
+            presenter,
+            name_policy: NamePolicy::default(),
+        }
+    }
+
+    pub fn with_name_policy(self, name_policy: NamePolicy) -> Self {
+        Self { name_policy, ..self }
+    }
+
+    /// Saves `store` over the stored one, which must still be at the same version.
+    pub async fn execute(&self, store: Store) -> Output {
+        self.presenter.apply(self.update(store).await)
+    }
+
+    async fn update(&self, store: Store) -> Result<Store, UpdateStoreInteractorError> {
+        if store.name.trim().is_empty() {
+            return Err(UpdateStoreInteractorError::InvalidName(format!(
+                "The name '{}' is not valid",
+                store.name
+            )));
+        }
+
+        let mut store_repository = self.store_repository.write().await;
+
+        let stores: Vec<Store> = store_repository.retrieve_all().await?;
+        if !stores.iter().any(|c| c.id == store.id) {
+            return Err(UpdateStoreInteractorError::StoreNotFound);
+        }
+        if stores
+            .iter()
+            .any(|c| c.id != store.id && self.name_policy.matches(&c.name, &store.name))
+        {
+            return Err(UpdateStoreInteractorError::StoreAlreadyExists);
+        }
+
+        store_repository.create_or_update(&store).await?;
+
+        Ok(Store {
+            version: store.version + 1,
+            ..store
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum UpdateStoreInteractorError {
+    InvalidName(String),
+    StoreNotFound,
+    StoreAlreadyExists,
+    VersionConflict { current_version: u64 },
+    UnableToSaveStore(String),
+}
+
+impl From<StoreRepositoryError> for UpdateStoreInteractorError {
+    fn from(value: StoreRepositoryError) -> Self {
+        match value {
+            StoreRepositoryError::StoreNotFound => UpdateStoreInteractorError::StoreNotFound,
+            StoreRepositoryError::StoreAlreadyExists => UpdateStoreInteractorError::StoreAlreadyExists,
+            StoreRepositoryError::VersionConflict { current_version } => UpdateStoreInteractorError::VersionConflict { current_version },
+            StoreRepositoryError::UnableToAccessStorage(details) => UpdateStoreInteractorError::UnableToSaveStore(details),
+            e => UpdateStoreInteractorError::UnableToSaveStore(format!("{:?}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::domain::{
+        entities::Store,
+        repositories::SharedStoreRepository,
+        use_cases::{UpdateStoreOutputPort, repository_fakes::StoreRepositoryFake},
+    };
+
+    use super::{UpdateStoreInteractor, UpdateStoreInteractorError};
+
+    fn given_interactor(store_repository: &SharedStoreRepository) -> UpdateStoreInteractor<Result<Store, UpdateStoreInteractorError>> {
+        UpdateStoreInteractor::new(Arc::clone(store_repository), Arc::new(NoOpUseCaseOutputPort {}))
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_given_invalid_name() {
+        let migros: Store = Store::new(None, "Migros".to_owned());
+        let store_repository: SharedStoreRepository = StoreRepositoryFake::with(vec![migros.clone()]).shared();
+        let use_case: UpdateStoreInteractor<Result<Store, UpdateStoreInteractorError>> = given_interactor(&store_repository);
+
+        let result: Result<Store, UpdateStoreInteractorError> = use_case
+            .execute(Store {
+                name: "".to_owned(),
+                ..migros
+            })
+            .await;
+        let expected: Result<Store, UpdateStoreInteractorError> =
+            Err(UpdateStoreInteractorError::InvalidName("The name '' is not valid".to_owned()));
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_store_does_not_exist() {
+        let store_repository: SharedStoreRepository = StoreRepositoryFake::default().shared();
+        let use_case: UpdateStoreInteractor<Result<Store, UpdateStoreInteractorError>> = given_interactor(&store_repository);
+
+        let result: Result<Store, UpdateStoreInteractorError> = use_case.execute(Store::new(None, "Migros".to_owned())).await;
+        let expected: Result<Store, UpdateStoreInteractorError> = Err(UpdateStoreInteractorError::StoreNotFound);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_renamed_after_another_store() {
+        let migros: Store = Store::new(None, "Migros".to_owned());
+        let store_repository: SharedStoreRepository =
+            StoreRepositoryFake::with(vec![migros.clone(), Store::new(None, "Denner".to_owned())]).shared();
+        let use_case: UpdateStoreInteractor<Result<Store, UpdateStoreInteractorError>> = given_interactor(&store_repository);
+
+        let result: Result<Store, UpdateStoreInteractorError> = use_case
+            .execute(Store {
+                name: "DENNER".to_owned(),
+                ..migros
+            })
+            .await;
+        let expected: Result<Store, UpdateStoreInteractorError> = Err(UpdateStoreInteractorError::StoreAlreadyExists);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_store_changed_meanwhile() {
+        let migros: Store = Store::new(None, "Migros".to_owned());
+        let store_repository: SharedStoreRepository = StoreRepositoryFake::with(vec![Store {
+            version: 2,
+            ..migros.clone()
+        }])
+        .shared();
+        let use_case: UpdateStoreInteractor<Result<Store, UpdateStoreInteractorError>> = given_interactor(&store_repository);
+
+        let result: Result<Store, UpdateStoreInteractorError> = use_case.execute(migros).await;
+        let expected: Result<Store, UpdateStoreInteractorError> = Err(UpdateStoreInteractorError::VersionConflict { current_version: 2 });
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn should_return_store_at_its_new_version_if_success() {
+        let migros: Store = Store::new(None, "Migros".to_owned());
+        let store_repository: SharedStoreRepository = StoreRepositoryFake::with(vec![migros.clone()]).shared();
+        let use_case: UpdateStoreInteractor<Result<Store, UpdateStoreInteractorError>> = given_interactor(&store_repository);
+
+        let result: Result<Store, UpdateStoreInteractorError> = use_case
+            .execute(Store {
+                name: "migros".to_owned(),
+                ..migros.clone()
+            })
+            .await;
+        let expected: Store = Store {
+            name: "migros".to_owned(),
+            version: 1,
+            ..migros
+        };
+        let stored: Vec<Store> = store_repository.read().await.retrieve_all().await.unwrap();
+
+        assert_eq!(result, Ok(expected.clone()), "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(
+            stored,
+            vec![expected.clone()],
+            "Expected {:?}, but got {:?}",
+            vec![expected],
+            stored
+        );
+    }
+
+    struct NoOpUseCaseOutputPort {}
+
+    impl UpdateStoreOutputPort<Result<Store, UpdateStoreInteractorError>> for NoOpUseCaseOutputPort {
+        fn apply(&self, result: Result<Store, UpdateStoreInteractorError>) -> Result<Store, UpdateStoreInteractorError> {
+            result
+        }
+    }
+}
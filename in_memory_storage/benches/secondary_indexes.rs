@@ -0,0 +1,82 @@
+use std::hint::black_box;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+use in_memory_storage::infrastructures::data_sources::{InMemoryCache, StoreModel, TransactionModel};
+
+const SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+const STORES: usize = 100;
+
+fn given_start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap()
+}
+
+fn given_cache(size: usize) -> InMemoryCache {
+    let mut cache: InMemoryCache = InMemoryCache::new();
+
+    (0..STORES).for_each(|index| {
+        cache.upsert_store(StoreModel::new(format!("store-{:06}", index), format!("Store {}", index)));
+    });
+    (0..size).for_each(|index| {
+        cache.upsert_transaction(TransactionModel::new(
+            format!("transaction-{:08}", index),
+            vec![],
+            format!("store-{:06}", index % STORES),
+            (given_start() + Duration::hours(index as i64)).to_rfc3339(),
+        ));
+    });
+
+    cache
+}
+
+fn transactions_by_store(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("transactions_by_store");
+
+    for size in SIZES {
+        let cache: InMemoryCache = given_cache(size);
+        let store_key: String = format!("store-{:06}", STORES / 2);
+
+        group.bench_with_input(BenchmarkId::new("index", size), &store_key, |bencher, store_key| {
+            bencher.iter(|| cache.get_transactions_by_store(black_box(store_key)).count())
+        });
+        group.bench_with_input(BenchmarkId::new("scan", size), &store_key, |bencher, store_key| {
+            bencher.iter(|| {
+                cache
+                    .get_all_transactions()
+                    .filter(|transaction| &transaction.store_key == black_box(store_key))
+                    .count()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn transactions_between(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("transactions_between");
+
+    for size in SIZES {
+        let cache: InMemoryCache = given_cache(size);
+        let from: DateTime<Utc> = given_start() + Duration::hours(size as i64 / 2);
+        let to: DateTime<Utc> = from + Duration::days(1);
+
+        group.bench_with_input(BenchmarkId::new("index", size), &(from, to), |bencher, (from, to)| {
+            bencher.iter(|| cache.get_transactions_between(black_box(*from)..black_box(*to)).count())
+        });
+        group.bench_with_input(BenchmarkId::new("scan", size), &(from, to), |bencher, (from, to)| {
+            bencher.iter(|| {
+                cache
+                    .get_all_transactions()
+                    .filter_map(|transaction| DateTime::parse_from_rfc3339(&transaction.datetime).ok())
+                    .filter(|datetime| (black_box(*from)..black_box(*to)).contains(&datetime.with_timezone(&Utc)))
+                    .count()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, transactions_by_store, transactions_between);
+criterion_main!(benches);
@@ -0,0 +1,13 @@
+mod brand_mapper;
+mod category_mapper;
+mod item_mapper;
+mod product_mapper;
+mod store_mapper;
+mod transaction_mapper;
+
+pub use brand_mapper::BrandMapper;
+pub use category_mapper::CategoryMapper;
+pub use item_mapper::ItemMapper;
+pub use product_mapper::ProductMapper;
+pub use store_mapper::StoreMapper;
+pub use transaction_mapper::TransactionMapper;
@@ -0,0 +1,21 @@
+use expense_tracking::domain::entities::Brand;
+
+use crate::infrastructures::data_sources::BrandModel;
+
+pub struct BrandMapper;
+
+impl BrandMapper {
+    pub fn to_model(brand: &Brand) -> BrandModel {
+        BrandModel {
+            version: brand.version,
+            ..BrandModel::new(brand.name.clone(), brand.name.clone())
+        }
+    }
+
+    pub fn to_entity(model: &BrandModel) -> Brand {
+        Brand {
+            version: model.version,
+            ..Brand::new(model.name.clone())
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use uuid_b64::UuidB64;
+
+use expense_tracking::domain::entities::Category;
+
+use crate::infrastructures::data_sources::{CategoryModel, IntegrityError};
+
+pub struct CategoryMapper;
+
+impl CategoryMapper {
+    pub fn to_model(category: &Category) -> CategoryModel {
+        CategoryModel {
+            version: category.version,
+            ..CategoryModel::new(category.id.to_string(), category.name.clone())
+        }
+    }
+
+    pub fn to_entity(model: &CategoryModel) -> Result<Category, IntegrityError> {
+        let id: UuidB64 = model.key.parse().map_err(|_| IntegrityError::MalformedValue(model.key.clone()))?;
+
+        Ok(Category {
+            version: model.version,
+            ..Category::new(Some(id), model.name.clone())
+        })
+    }
+}
@@ -0,0 +1,49 @@
+use uuid_b64::UuidB64;
+
+use expense_tracking::domain::entities::{Item, Product, Unit};
+
+use crate::{
+    adapters::mappers::ProductMapper,
+    infrastructures::data_sources::{InMemoryCache, IntegrityError, ItemModel, UnitModel},
+};
+
+pub struct ItemMapper;
+
+impl ItemMapper {
+    pub fn to_model(item: &Item) -> ItemModel {
+        ItemModel::new(
+            item.id().to_string(),
+            item.product().id.to_string(),
+            Self::unit_to_model(item.unit()),
+            item.unitary_price(),
+        )
+    }
+
+    pub fn to_entity(cache: &InMemoryCache, model: &ItemModel) -> Result<Item, IntegrityError> {
+        let id: UuidB64 = model.key.parse().map_err(|_| IntegrityError::MalformedValue(model.key.clone()))?;
+        let product: Product = match cache.get_single_product(&model.product_key) {
+            Some(product) => ProductMapper::to_entity(cache, &product)?,
+            None => return Err(IntegrityError::DanglingReference(model.product_key.clone())),
+        };
+
+        Ok(Item::new(Some(id), product, Self::unit_to_entity(&model.unit), model.unitary_price))
+    }
+
+    fn unit_to_model(unit: &Unit) -> UnitModel {
+        match unit {
+            Unit::None => UnitModel::None,
+            Unit::Quantity(amount) => UnitModel::Quantity(*amount),
+            Unit::Kilograms(weight) => UnitModel::Kilograms(*weight),
+            Unit::Liters(volume) => UnitModel::Liters(*volume),
+        }
+    }
+
+    fn unit_to_entity(unit: &UnitModel) -> Unit {
+        match unit {
+            UnitModel::None => Unit::None,
+            UnitModel::Quantity(amount) => Unit::Quantity(*amount),
+            UnitModel::Kilograms(weight) => Unit::Kilograms(*weight),
+            UnitModel::Liters(volume) => Unit::Liters(*volume),
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use uuid_b64::UuidB64;
+
+use expense_tracking::domain::entities::{Brand, Category, Product};
+
+use crate::{
+    adapters::mappers::{BrandMapper, CategoryMapper},
+    infrastructures::data_sources::{InMemoryCache, IntegrityError, ProductModel},
+};
+
+pub struct ProductMapper;
+
+impl ProductMapper {
+    pub fn to_model(product: &Product) -> ProductModel {
+        ProductModel {
+            version: product.version,
+            ..ProductModel::new(
+                product.id.to_string(),
+                product.name.clone(),
+                product.brand.name.clone(),
+                product.category.id.to_string(),
+            )
+        }
+    }
+
+    /// Resolves the brand and category through the cache, so the product always reflects their current state.
+    pub fn to_entity(cache: &InMemoryCache, model: &ProductModel) -> Result<Product, IntegrityError> {
+        let id: UuidB64 = model.key.parse().map_err(|_| IntegrityError::MalformedValue(model.key.clone()))?;
+        let brand: Brand = cache
+            .get_single_brand(&model.brand_key)
+            .map(|b| BrandMapper::to_entity(&b))
+            .ok_or_else(|| IntegrityError::DanglingReference(model.brand_key.clone()))?;
+        let category: Category = match cache.get_single_category(&model.category_key) {
+            Some(category) => CategoryMapper::to_entity(&category)?,
+            None => return Err(IntegrityError::DanglingReference(model.category_key.clone())),
+        };
+
+        Ok(Product {
+            version: model.version,
+            ..Product::new(Some(id), model.name.clone(), brand, category)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProductMapper;
+    use crate::{
+        adapters::mappers::{BrandMapper, CategoryMapper},
+        infrastructures::data_sources::{BrandModel, InMemoryCache, IntegrityError, ProductModel},
+    };
+    use expense_tracking::domain::entities::{Brand, Category, Product};
+
+    #[test]
+    fn to_entity_should_round_trip_through_the_cache() {
+        let product: Product = Product {
+            version: 3,
+            ..Product::new(
+                None,
+                "Milk".to_owned(),
+                Brand::new("Nestlé".to_owned()),
+                Category::new(None, "Dairy".to_owned()),
+            )
+        };
+        let mut cache: InMemoryCache = InMemoryCache::new();
+        cache.upsert_brand(BrandMapper::to_model(&product.brand));
+        cache.upsert_category(CategoryMapper::to_model(&product.category));
+
+        let result: Result<Product, IntegrityError> = ProductMapper::to_entity(&cache, &ProductMapper::to_model(&product));
+        let expected: Result<Product, IntegrityError> = Ok(product);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[test]
+    fn to_entity_should_reflect_the_current_brand() {
+        let product: Product = Product::new(
+            None,
+            "Milk".to_owned(),
+            Brand::new("Nestlé".to_owned()),
+            Category::new(None, "Dairy".to_owned()),
+        );
+        let mut cache: InMemoryCache = InMemoryCache::new();
+        cache.upsert_brand(BrandMapper::to_model(&product.brand));
+        cache.upsert_category(CategoryMapper::to_model(&product.category));
+
+        cache.upsert_brand(BrandModel {
+            version: 1,
+            ..BrandMapper::to_model(&product.brand)
+        });
+
+        let result: Result<u64, IntegrityError> =
+            ProductMapper::to_entity(&cache, &ProductMapper::to_model(&product)).map(|p| p.brand.version);
+
+        assert_eq!(result, Ok(1), "Expected {:?}, but got {:?}", Ok::<u64, IntegrityError>(1), result);
+    }
+
+    #[test]
+    fn to_entity_with_missing_brand_should_report_dangling_reference() {
+        let product: Product = Product::new(
+            None,
+            "Milk".to_owned(),
+            Brand::new("Nestlé".to_owned()),
+            Category::new(None, "Dairy".to_owned()),
+        );
+        let model: ProductModel = ProductMapper::to_model(&product);
+
+        let result: Result<Product, IntegrityError> = ProductMapper::to_entity(&InMemoryCache::new(), &model);
+        let expected: Result<Product, IntegrityError> = Err(IntegrityError::DanglingReference("Nestlé".to_owned()));
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+}
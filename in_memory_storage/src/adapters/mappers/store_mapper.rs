@@ -0,0 +1,25 @@
+use uuid_b64::UuidB64;
+
+use expense_tracking::domain::entities::Store;
+
+use crate::infrastructures::data_sources::{IntegrityError, StoreModel};
+
+pub struct StoreMapper;
+
+impl StoreMapper {
+    pub fn to_model(store: &Store) -> StoreModel {
+        StoreModel {
+            version: store.version,
+            ..StoreModel::new(store.id.to_string(), store.name.clone())
+        }
+    }
+
+    pub fn to_entity(model: &StoreModel) -> Result<Store, IntegrityError> {
+        let id: UuidB64 = model.key.parse().map_err(|_| IntegrityError::MalformedValue(model.key.clone()))?;
+
+        Ok(Store {
+            version: model.version,
+            ..Store::new(Some(id), model.name.clone())
+        })
+    }
+}
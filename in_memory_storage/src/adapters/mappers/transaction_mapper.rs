@@ -0,0 +1,99 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use uuid_b64::UuidB64;
+
+use expense_tracking::domain::entities::{Item, Store, Transaction};
+
+use crate::{
+    adapters::mappers::{ItemMapper, StoreMapper},
+    infrastructures::data_sources::{InMemoryCache, IntegrityError, ItemModel, TransactionModel},
+};
+
+pub struct TransactionMapper;
+
+impl TransactionMapper {
+    pub fn to_model(transaction: &Transaction) -> (TransactionModel, Vec<ItemModel>) {
+        let items: Vec<ItemModel> = transaction.items.iter().map(ItemMapper::to_model).collect();
+        let model: TransactionModel = TransactionModel {
+            version: transaction.version,
+            ..TransactionModel::new(
+                transaction.id.to_string(),
+                items.iter().map(|i| i.key.clone()).collect(),
+                transaction.store.id.to_string(),
+                transaction.datetime.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+            )
+        };
+
+        (model, items)
+    }
+
+    pub fn to_entity(cache: &InMemoryCache, model: &TransactionModel) -> Result<Transaction, IntegrityError> {
+        let id: UuidB64 = model.key.parse().map_err(|_| IntegrityError::MalformedValue(model.key.clone()))?;
+        let datetime: DateTime<Utc> = DateTime::parse_from_rfc3339(&model.datetime)
+            .map(|d| d.with_timezone(&Utc))
+            .map_err(|_| IntegrityError::MalformedValue(model.datetime.clone()))?;
+        let store: Store = match cache.get_single_store(&model.store_key) {
+            Some(store) => StoreMapper::to_entity(&store)?,
+            None => return Err(IntegrityError::DanglingReference(model.store_key.clone())),
+        };
+        let items: Vec<Item> = model
+            .item_keys
+            .iter()
+            .map(|key| match cache.get_single_item(key) {
+                Some(item) => ItemMapper::to_entity(cache, &item),
+                None => Err(IntegrityError::DanglingReference(key.clone())),
+            })
+            .collect::<Result<Vec<Item>, IntegrityError>>()?;
+
+        Ok(Transaction {
+            version: model.version,
+            ..Transaction::new(Some(id), items, store, datetime)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+
+    use super::TransactionMapper;
+    use crate::{
+        adapters::mappers::{BrandMapper, CategoryMapper, ProductMapper, StoreMapper},
+        infrastructures::data_sources::{InMemoryCache, IntegrityError, ItemModel, TransactionModel},
+    };
+    use expense_tracking::domain::entities::{Brand, Category, Item, Product, Store, Transaction, Unit};
+
+    #[test]
+    fn to_entity_should_round_trip_items_in_order() {
+        let product: Product = Product::new(
+            None,
+            "Milk".to_owned(),
+            Brand::new("Nestlé".to_owned()),
+            Category::new(None, "Dairy".to_owned()),
+        );
+        let transaction: Transaction = Transaction::new(
+            None,
+            vec![
+                Item::new(None, product.clone(), Unit::Quantity(2.), 1.95),
+                Item::new(None, product.clone(), Unit::Liters(1.5), 2.4),
+                Item::new(None, product.clone(), Unit::None, 0.5),
+            ],
+            Store::new(None, "Migros".to_owned()),
+            DateTime::parse_from_rfc3339("2025-03-14T09:26:53.589793Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        );
+        let mut cache: InMemoryCache = InMemoryCache::new();
+        cache.upsert_brand(BrandMapper::to_model(&product.brand));
+        cache.upsert_category(CategoryMapper::to_model(&product.category));
+        cache.upsert_product(ProductMapper::to_model(&product));
+        cache.upsert_store(StoreMapper::to_model(&transaction.store));
+
+        let (model, items): (TransactionModel, Vec<ItemModel>) = TransactionMapper::to_model(&transaction);
+        cache.replace_transaction(model.clone(), items).unwrap();
+
+        let result: Result<Transaction, IntegrityError> = TransactionMapper::to_entity(&cache, &model);
+        let expected: Result<Transaction, IntegrityError> = Ok(transaction);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+}
@@ -1,5 +1,15 @@
+mod audit_log_in_memory_impl;
 mod brand_repository_in_memory_impl;
+mod category_repository_in_memory_impl;
+#[cfg(test)]
+mod conformance_tests;
+mod product_repository_in_memory_impl;
 mod store_repository_in_memory_impl;
+mod transaction_repository_in_memory_impl;
 
+pub use audit_log_in_memory_impl::AuditLogInMemoryImpl;
 pub use brand_repository_in_memory_impl::BrandRepositoryInMemoryImpl;
+pub use category_repository_in_memory_impl::CategoryRepositoryInMemoryImpl;
+pub use product_repository_in_memory_impl::ProductRepositoryInMemoryImpl;
 pub use store_repository_in_memory_impl::StoreRepositoryInMemoryImpl;
+pub use transaction_repository_in_memory_impl::TransactionRepositoryInMemoryImpl;
@@ -0,0 +1,112 @@
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+
+use expense_tracking::domain::repositories::{AuditEntry, AuditLog, AuditLogError};
+
+#[derive(Debug)]
+pub struct AuditLogInMemoryImpl<T> {
+    entries: Mutex<Vec<AuditEntry<T>>>,
+}
+
+impl<T> AuditLogInMemoryImpl<T> {
+    pub fn new() -> Self {
+        Self {
+            entries: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl<T> Default for AuditLogInMemoryImpl<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl<T: std::fmt::Debug + Clone + Send + Sync> AuditLog<T> for AuditLogInMemoryImpl<T> {
+    async fn record(&self, entry: AuditEntry<T>) -> Result<(), AuditLogError> {
+        self.entries
+            .lock()
+            .map_err(|e| AuditLogError::UnableToRecordEntry(e.to_string()))?
+            .push(entry);
+        Ok(())
+    }
+
+    async fn history(&self, entity_key: &str) -> Result<Vec<AuditEntry<T>>, AuditLogError> {
+        Ok(self
+            .entries
+            .lock()
+            .map_err(|e| AuditLogError::UnableToRetrieveHistory(e.to_string()))?
+            .iter()
+            .filter(|e| e.entity_key == entity_key)
+            .cloned()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, Utc};
+
+    use super::AuditLogInMemoryImpl;
+    use expense_tracking::domain::{
+        entities::Brand,
+        repositories::{AuditAction, AuditEntry, AuditLog, AuditLogError},
+    };
+
+    #[tokio::test]
+    async fn history_of_unknown_key_should_be_empty() {
+        let audit_log: AuditLogInMemoryImpl<Brand> = AuditLogInMemoryImpl::new();
+
+        let result: Result<Vec<AuditEntry<Brand>>, AuditLogError> = audit_log.history("Unknown").await;
+        let expected: Result<Vec<AuditEntry<Brand>>, AuditLogError> = Ok(vec![]);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn history_should_list_recorded_entries_of_key_in_order() {
+        let audit_log: AuditLogInMemoryImpl<Brand> = AuditLogInMemoryImpl::new();
+        let brand: Brand = Brand::new("Some Brand".to_owned());
+        let updated: Brand = Brand {
+            version: 1,
+            ..brand.clone()
+        };
+        let now: DateTime<Utc> = Utc::now();
+
+        let created: AuditEntry<Brand> = AuditEntry::new(
+            brand.name.clone(),
+            AuditAction::Created,
+            "alice".to_owned(),
+            now,
+            None,
+            Some(brand.clone()),
+        );
+        let other: AuditEntry<Brand> = AuditEntry::new(
+            "Other Brand".to_owned(),
+            AuditAction::Created,
+            "alice".to_owned(),
+            now,
+            None,
+            Some(Brand::new("Other Brand".to_owned())),
+        );
+        let changed: AuditEntry<Brand> = AuditEntry::new(
+            brand.name.clone(),
+            AuditAction::Updated,
+            "bob".to_owned(),
+            now + Duration::seconds(1),
+            Some(brand.clone()),
+            Some(updated),
+        );
+
+        audit_log.record(created.clone()).await.unwrap();
+        audit_log.record(other).await.unwrap();
+        audit_log.record(changed.clone()).await.unwrap();
+
+        let result: Result<Vec<AuditEntry<Brand>>, AuditLogError> = audit_log.history(&brand.name).await;
+        let expected: Result<Vec<AuditEntry<Brand>>, AuditLogError> = Ok(vec![created, changed]);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+}
@@ -1,59 +1,205 @@
-use std::{
-    collections::HashMap,
-    sync::{Arc, Mutex},
-};
+use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::{
+    StreamExt,
+    stream::{self, BoxStream},
+};
+use tokio::sync::RwLock;
 
 use expense_tracking::domain::{
     entities::Brand,
-    repositories::{BrandRepository, BrandRepositoryCreateError, BrandRepositoryRetrieveAllError},
+    repositories::{
+        BrandRepository, BrandRepositoryCreateError, BrandRepositoryDeleteError, BrandRepositoryRestoreError,
+        BrandRepositoryRetrieveAllError, BrandRepositoryUpdateError, DeleteBehaviour, Trashed,
+    },
+};
+
+use crate::{
+    adapters::mappers::BrandMapper,
+    infrastructures::data_sources::{BrandModel, InMemoryCache, IntegrityError},
 };
 
 #[derive(Debug)]
 pub struct BrandRepositoryInMemoryImpl {
-    hash_map: Arc<Mutex<HashMap<String, Brand>>>,
+    cache: Arc<RwLock<InMemoryCache>>,
 }
 
 impl BrandRepositoryInMemoryImpl {
-    pub fn new(hash_map: Arc<Mutex<HashMap<String, Brand>>>) -> Self {
-        Self { hash_map }
+    pub fn new(cache: Arc<RwLock<InMemoryCache>>) -> Self {
+        Self { cache }
+    }
+
+    /// The key under which the cache stores the brand named `name`, or `name` itself for an unknown brand.
+    fn stored_name(cache: &InMemoryCache, name: &str) -> String {
+        cache
+            .get_brands_by_name(name)
+            .next()
+            .map_or_else(|| name.to_owned(), |b| b.key.clone())
     }
 }
 
 #[async_trait]
 impl BrandRepository for BrandRepositoryInMemoryImpl {
     async fn create(&self, brand: &Brand) -> Result<Brand, BrandRepositoryCreateError> {
-        if self.hash_map.lock().unwrap().contains_key(&brand.name) {
-            return Err(BrandRepositoryCreateError::BrandAlreadyExists);
-        }
-
-        self.hash_map.lock().unwrap().insert(brand.name.clone(), brand.clone());
+        self.cache
+            .write()
+            .await
+            .insert_brand_if_absent(BrandMapper::to_model(brand))
+            .map_err(|e| match e {
+                IntegrityError::AlreadyExists(_) => BrandRepositoryCreateError::BrandAlreadyExists,
+                e => BrandRepositoryCreateError::UnableToSaveBrand(format!("{:?}", e)),
+            })?;
         Ok(brand.clone())
     }
 
     async fn retrieve_all(&self) -> Result<Vec<Brand>, BrandRepositoryRetrieveAllError> {
-        Ok(self.hash_map.lock().unwrap().values().cloned().collect())
+        Ok(self.cache.read().await.get_all_brands().map(BrandMapper::to_entity).collect())
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Brand, BrandRepositoryRetrieveAllError>> {
+        stream::once(async move {
+            self.cache
+                .read()
+                .await
+                .get_all_brands()
+                .map(|b| b.key.clone())
+                .collect::<Vec<String>>()
+        })
+        .flat_map(stream::iter)
+        .filter_map(move |key| async move {
+            self.cache
+                .read()
+                .await
+                .get_single_brand(&key)
+                .filter(|b| b.deleted_at.is_none())
+                .map(|b| Ok(BrandMapper::to_entity(&b)))
+        })
+        .boxed()
+    }
+
+    async fn update(&self, brand: &Brand) -> Result<Brand, BrandRepositoryUpdateError> {
+        let mut cache = self.cache.write().await;
+        let name: String = Self::stored_name(&cache, &brand.name);
+
+        let current_version: u64 = match cache.get_single_brand(&name) {
+            Some(existing) if existing.deleted_at.is_none() => existing.version,
+            _ => return Err(BrandRepositoryUpdateError::BrandNotFound),
+        };
+
+        if current_version != brand.version {
+            return Err(BrandRepositoryUpdateError::VersionConflict { current_version });
+        }
+
+        let updated: Brand = Brand {
+            version: current_version + 1,
+            ..Brand::new(name)
+        };
+
+        cache.upsert_brand(BrandMapper::to_model(&updated));
+        Ok(updated)
+    }
+
+    async fn delete(&self, brand: &Brand, behaviour: DeleteBehaviour<Brand>) -> Result<Brand, BrandRepositoryDeleteError> {
+        let mut cache = self.cache.write().await;
+        let name: String = Self::stored_name(&cache, &brand.name);
+        let behaviour: DeleteBehaviour<String> = match behaviour {
+            DeleteBehaviour::Restrict => DeleteBehaviour::Restrict,
+            DeleteBehaviour::Cascade => DeleteBehaviour::Cascade,
+            DeleteBehaviour::Reassign(replacement) => DeleteBehaviour::Reassign(Self::stored_name(&cache, &replacement.name)),
+        };
+
+        let removed: BrandModel = cache.delete_brand(&name, behaviour, Utc::now()).map_err(|e| match e {
+            IntegrityError::NotFound(_) => BrandRepositoryDeleteError::BrandNotFound,
+            IntegrityError::StillReferenced(_) => BrandRepositoryDeleteError::BrandStillReferenced,
+            IntegrityError::ReplacementNotFound(_) => BrandRepositoryDeleteError::ReplacementBrandNotFound,
+            e => BrandRepositoryDeleteError::UnableToDeleteBrand(format!("{:?}", e)),
+        })?;
+        Ok(BrandMapper::to_entity(&removed))
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Brand>>, BrandRepositoryRetrieveAllError> {
+        Ok(self
+            .cache
+            .read()
+            .await
+            .get_trashed_brands()
+            .map(|b| Trashed::new(BrandMapper::to_entity(b), b.deleted_at.unwrap_or_default()))
+            .collect())
+    }
+
+    async fn restore(&self, brand: &Brand) -> Result<Brand, BrandRepositoryRestoreError> {
+        let mut cache = self.cache.write().await;
+
+        let name: String = match cache
+            .get_trashed_brands()
+            .find(|b| cache.name_policy().matches(&b.name, &brand.name))
+        {
+            Some(trashed) => trashed.key.clone(),
+            None => return Err(BrandRepositoryRestoreError::BrandNotInTrash),
+        };
+
+        cache
+            .restore_brand(&name)
+            .map(|restored| BrandMapper::to_entity(&restored))
+            .map_err(|e| match e {
+                IntegrityError::NotFound(_) => BrandRepositoryRestoreError::BrandNotInTrash,
+                e => BrandRepositoryRestoreError::UnableToRestoreBrand(format!("{:?}", e)),
+            })
+    }
+
+    async fn purge_older_than(&self, cutoff: DateTime<Utc>) -> Result<Vec<Brand>, BrandRepositoryDeleteError> {
+        Ok(self
+            .cache
+            .write()
+            .await
+            .purge_brands(cutoff)
+            .iter()
+            .map(BrandMapper::to_entity)
+            .collect())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{
-        collections::HashMap,
-        sync::{Arc, Mutex},
-    };
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    use chrono::{Duration, Utc};
+    use futures::{StreamExt, stream::BoxStream};
 
     use super::BrandRepositoryInMemoryImpl;
+    use crate::{
+        adapters::mappers::{BrandMapper, CategoryMapper, ProductMapper},
+        infrastructures::data_sources::InMemoryCache,
+    };
     use expense_tracking::domain::{
-        entities::Brand,
-        repositories::{BrandRepository, BrandRepositoryCreateError, BrandRepositoryRetrieveAllError},
+        entities::{Brand, Category, Product},
+        repositories::{
+            BrandRepository, BrandRepositoryCreateError, BrandRepositoryDeleteError, BrandRepositoryRestoreError,
+            BrandRepositoryRetrieveAllError, BrandRepositoryUpdateError, DeleteBehaviour, Trashed,
+        },
     };
 
     fn given_new_brand() -> Brand {
         Brand::new(String::default())
     }
 
+    fn given_cache_with(brands: Vec<Brand>) -> Arc<RwLock<InMemoryCache>> {
+        let mut cache: InMemoryCache = InMemoryCache::new();
+
+        brands.iter().for_each(|b| {
+            cache.upsert_brand(BrandMapper::to_model(b));
+        });
+
+        Arc::new(RwLock::new(cache))
+    }
+
+    fn given_repository_with(brands: Vec<Brand>) -> BrandRepositoryInMemoryImpl {
+        BrandRepositoryInMemoryImpl::new(given_cache_with(brands))
+    }
+
     macro_rules! retrieve_all {
         ($($name:ident: $value:expr,)*) => {
         $(
@@ -61,12 +207,7 @@ mod tests {
             async fn $name() {
                 let brands: &Vec<Brand> = $value;
 
-                let data: Arc<Mutex<HashMap<String, Brand>>> = Arc::new(Mutex::new(brands
-                    .into_iter()
-                    .map(|b| (b.name.clone(), b.clone()))
-                    .collect::<HashMap<String, Brand>>()));
-
-                let repository: BrandRepositoryInMemoryImpl = BrandRepositoryInMemoryImpl::new(Arc::clone(&data));
+                let repository: BrandRepositoryInMemoryImpl = given_repository_with(brands.clone());
 
                 let result: Result<Vec<Brand>, BrandRepositoryRetrieveAllError> =
                     repository.retrieve_all().await;
@@ -95,11 +236,70 @@ mod tests {
         multiple_brands: &vec![Brand::new("Otto Shuff".to_owned()), Brand::new("Signe Dadlani".to_owned()), Brand::new("Randal Tuong".to_owned())],
     }
 
+    macro_rules! stream_all {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[tokio::test]
+            async fn $name() {
+                let brands: &Vec<Brand> = $value;
+
+                let repository: BrandRepositoryInMemoryImpl = given_repository_with(brands.clone());
+
+                let result: Result<Vec<Brand>, BrandRepositoryRetrieveAllError> =
+                    repository.stream_all().collect::<Vec<_>>().await.into_iter().collect();
+
+                assert!(result.is_ok(), "Expected Ok, got {:?}", result);
+
+                let mut expected_brands: Vec<Brand> = brands.clone();
+                expected_brands.sort_by(|a, b| a.name.cmp(&b.name));
+
+                let mut sorted_result: Vec<Brand> = result.unwrap();
+                sorted_result.sort_by(|a, b| a.name.cmp(&b.name));
+
+                assert_eq!(
+                    sorted_result, expected_brands,
+                    "Expected {:?}, but got {:?}",
+                    expected_brands, sorted_result
+                )
+            }
+        )*
+        }
+    }
+
+    stream_all! {
+        stream_no_brands: &vec![],
+        stream_one_brand: &vec![Brand::new("Mora Radunz".to_owned())],
+        stream_multiple_brands: &vec![Brand::new("Otto Shuff".to_owned()), Brand::new("Signe Dadlani".to_owned()), Brand::new("Randal Tuong".to_owned())],
+    }
+
+    #[tokio::test]
+    async fn stream_all_should_skip_brands_removed_while_streaming() {
+        let cache: Arc<RwLock<InMemoryCache>> =
+            given_cache_with(vec![Brand::new("Otto Shuff".to_owned()), Brand::new("Signe Dadlani".to_owned())]);
+        let repository: BrandRepositoryInMemoryImpl = BrandRepositoryInMemoryImpl::new(Arc::clone(&cache));
+
+        let mut stream: BoxStream<'_, Result<Brand, BrandRepositoryRetrieveAllError>> = repository.stream_all();
+        let first: Brand = stream.next().await.unwrap().unwrap();
+        let other: String = ["Otto Shuff", "Signe Dadlani"]
+            .into_iter()
+            .find(|name| *name != first.name)
+            .unwrap()
+            .to_owned();
+        cache
+            .write()
+            .await
+            .delete_brand(&other, DeleteBehaviour::Restrict, Utc::now())
+            .unwrap();
+
+        let result: Option<Result<Brand, BrandRepositoryRetrieveAllError>> = stream.next().await;
+
+        assert_eq!(result, None, "Expected {:?}, but got {:?}", None::<Brand>, result);
+    }
+
     #[tokio::test]
     async fn add_new_brand_given_empty_repository() {
         let brand: Brand = Brand::new("New Brand".into());
-        let data: Arc<Mutex<HashMap<String, Brand>>> = Arc::new(Mutex::new(HashMap::new()));
-        let repository: BrandRepositoryInMemoryImpl = BrandRepositoryInMemoryImpl::new(Arc::clone(&data));
+        let repository: BrandRepositoryInMemoryImpl = given_repository_with(vec![]);
 
         let result: Result<Brand, BrandRepositoryCreateError> = repository.create(&brand).await;
         let expected: Result<Brand, BrandRepositoryCreateError> = Ok(brand.clone());
@@ -109,16 +309,9 @@ mod tests {
 
     #[tokio::test]
     async fn add_new_brand_given_full_repository() {
-        let data: Arc<Mutex<HashMap<String, Brand>>> = Arc::new(Mutex::new(
-            vec![given_new_brand(), given_new_brand(), given_new_brand()]
-                .into_iter()
-                .map(|b| (b.name.clone(), b.clone()))
-                .collect::<HashMap<String, Brand>>(),
-        ));
-
         let brand: Brand = Brand::new("New Brand".into());
 
-        let repository: BrandRepositoryInMemoryImpl = BrandRepositoryInMemoryImpl::new(Arc::clone(&data));
+        let repository: BrandRepositoryInMemoryImpl = given_repository_with(vec![given_new_brand(), given_new_brand(), given_new_brand()]);
 
         let result: Result<Brand, BrandRepositoryCreateError> = repository.create(&brand).await;
         let expected: Result<Brand, BrandRepositoryCreateError> = Ok(brand);
@@ -130,18 +323,250 @@ mod tests {
     async fn add_existing_brand_given_full_repository() {
         let existing_brand: Brand = Brand::new("Existing Brand".to_owned());
 
-        let data: Arc<Mutex<HashMap<String, Brand>>> = Arc::new(Mutex::new(
-            vec![given_new_brand(), existing_brand.clone(), given_new_brand()]
-                .into_iter()
-                .map(|b| (b.name.clone(), b.clone()))
-                .collect::<HashMap<String, Brand>>(),
-        ));
-
-        let repository: BrandRepositoryInMemoryImpl = BrandRepositoryInMemoryImpl::new(Arc::clone(&data));
+        let repository: BrandRepositoryInMemoryImpl =
+            given_repository_with(vec![given_new_brand(), existing_brand.clone(), given_new_brand()]);
 
         let result: Result<Brand, BrandRepositoryCreateError> = repository.create(&existing_brand).await;
         let expected: Result<Brand, BrandRepositoryCreateError> = Err(BrandRepositoryCreateError::BrandAlreadyExists);
 
         assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
     }
+
+    macro_rules! delete {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[tokio::test]
+            async fn $name() {
+                let (brands, target, behaviour, expected): (Vec<Brand>, Brand, DeleteBehaviour<Brand>, Result<Brand, BrandRepositoryDeleteError>) = $value;
+
+                let repository: BrandRepositoryInMemoryImpl = given_repository_with(brands);
+
+                let result: Result<Brand, BrandRepositoryDeleteError> = repository.delete(&target, behaviour).await;
+
+                assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+            }
+        )*
+        }
+    }
+
+    delete! {
+        delete_given_empty_repository_should_fail: (
+            vec![], Brand::new("Sau Hollinger".to_owned()), DeleteBehaviour::Restrict, Err(BrandRepositoryDeleteError::BrandNotFound)
+        ),
+        delete_non_existing_brand_should_fail: (
+            vec![Brand::new("Kip Tabar".to_owned())], Brand::new("Sau Hollinger".to_owned()), DeleteBehaviour::Cascade, Err(BrandRepositoryDeleteError::BrandNotFound)
+        ),
+        delete_existing_brand_should_return_it: (
+            vec![Brand::new("Kip Tabar".to_owned()), Brand::new("Sau Hollinger".to_owned())], Brand::new("Sau Hollinger".to_owned()), DeleteBehaviour::Restrict, Ok(Brand::new("Sau Hollinger".to_owned()))
+        ),
+        delete_reassigning_to_existing_brand_should_return_it: (
+            vec![Brand::new("Kip Tabar".to_owned()), Brand::new("Sau Hollinger".to_owned())], Brand::new("Sau Hollinger".to_owned()), DeleteBehaviour::Reassign(Brand::new("Kip Tabar".to_owned())), Ok(Brand::new("Sau Hollinger".to_owned()))
+        ),
+        delete_reassigning_to_non_existing_brand_should_fail: (
+            vec![Brand::new("Sau Hollinger".to_owned())], Brand::new("Sau Hollinger".to_owned()), DeleteBehaviour::Reassign(Brand::new("Kip Tabar".to_owned())), Err(BrandRepositoryDeleteError::ReplacementBrandNotFound)
+        ),
+        delete_reassigning_to_itself_should_fail: (
+            vec![Brand::new("Sau Hollinger".to_owned())], Brand::new("Sau Hollinger".to_owned()), DeleteBehaviour::Reassign(Brand::new("Sau Hollinger".to_owned())), Err(BrandRepositoryDeleteError::ReplacementBrandNotFound)
+        ),
+    }
+
+    #[tokio::test]
+    async fn update_non_existing_brand_should_fail() {
+        let repository: BrandRepositoryInMemoryImpl = given_repository_with(vec![Brand::new("Kip Tabar".to_owned())]);
+
+        let result: Result<Brand, BrandRepositoryUpdateError> = repository.update(&Brand::new("Sau Hollinger".to_owned())).await;
+        let expected: Result<Brand, BrandRepositoryUpdateError> = Err(BrandRepositoryUpdateError::BrandNotFound);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn update_should_increment_version() {
+        let brand: Brand = Brand::new("Sau Hollinger".to_owned());
+        let repository: BrandRepositoryInMemoryImpl = given_repository_with(vec![brand.clone()]);
+
+        let first: Result<Brand, BrandRepositoryUpdateError> = repository.update(&brand).await;
+        assert_eq!(first.as_ref().map(|b| b.version), Ok(1));
+
+        let second: Result<Brand, BrandRepositoryUpdateError> = repository.update(&first.unwrap()).await;
+        assert_eq!(second.map(|b| b.version), Ok(2));
+    }
+
+    #[tokio::test]
+    async fn update_with_stale_version_should_report_current_version() {
+        let brand: Brand = Brand::new("Sau Hollinger".to_owned());
+        let repository: BrandRepositoryInMemoryImpl = given_repository_with(vec![brand.clone()]);
+
+        assert!(repository.update(&brand).await.is_ok());
+
+        let result: Result<Brand, BrandRepositoryUpdateError> = repository.update(&brand).await;
+        let expected: Result<Brand, BrandRepositoryUpdateError> = Err(BrandRepositoryUpdateError::VersionConflict { current_version: 1 });
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn delete_should_move_brand_to_trash() {
+        let brand: Brand = Brand::new("Sau Hollinger".to_owned());
+        let repository: BrandRepositoryInMemoryImpl = given_repository_with(vec![brand.clone(), Brand::new("Kip Tabar".to_owned())]);
+
+        assert!(repository.delete(&brand, DeleteBehaviour::Restrict).await.is_ok());
+
+        assert!(!repository.retrieve_all().await.unwrap().contains(&brand));
+
+        let trashed: Vec<Trashed<Brand>> = repository.list_trashed().await.unwrap();
+        assert_eq!(trashed.iter().map(|t| t.entity.clone()).collect::<Vec<Brand>>(), vec![brand]);
+    }
+
+    #[tokio::test]
+    async fn restore_should_bring_trashed_brand_back() {
+        let brand: Brand = Brand::new("Sau Hollinger".to_owned());
+        let repository: BrandRepositoryInMemoryImpl = given_repository_with(vec![brand.clone()]);
+
+        assert!(repository.delete(&brand, DeleteBehaviour::Restrict).await.is_ok());
+
+        let result: Result<Brand, BrandRepositoryRestoreError> = repository.restore(&brand).await;
+
+        assert_eq!(result, Ok(brand.clone()));
+        assert_eq!(repository.retrieve_all().await, Ok(vec![brand]));
+        assert_eq!(repository.list_trashed().await, Ok(vec![]));
+    }
+
+    #[tokio::test]
+    async fn restore_brand_not_in_trash_should_fail() {
+        let brand: Brand = Brand::new("Sau Hollinger".to_owned());
+        let repository: BrandRepositoryInMemoryImpl = given_repository_with(vec![brand.clone()]);
+
+        let result: Result<Brand, BrandRepositoryRestoreError> = repository.restore(&brand).await;
+        let expected: Result<Brand, BrandRepositoryRestoreError> = Err(BrandRepositoryRestoreError::BrandNotInTrash);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn create_brand_named_like_a_trashed_one_should_fail() {
+        let brand: Brand = Brand::new("Sau Hollinger".to_owned());
+        let repository: BrandRepositoryInMemoryImpl = given_repository_with(vec![brand.clone()]);
+
+        assert!(repository.delete(&brand, DeleteBehaviour::Restrict).await.is_ok());
+
+        let result: Result<Brand, BrandRepositoryCreateError> = repository.create(&brand).await;
+        let expected: Result<Brand, BrandRepositoryCreateError> = Err(BrandRepositoryCreateError::BrandAlreadyExists);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(repository.restore(&brand).await, Ok(brand));
+    }
+
+    #[tokio::test]
+    async fn trash_should_be_shared_by_every_repository_over_the_cache() {
+        let brand: Brand = Brand::new("Sau Hollinger".to_owned());
+        let cache: Arc<RwLock<InMemoryCache>> = given_cache_with(vec![brand.clone()]);
+
+        assert!(
+            BrandRepositoryInMemoryImpl::new(Arc::clone(&cache))
+                .delete(&brand, DeleteBehaviour::Restrict)
+                .await
+                .is_ok()
+        );
+
+        let repository: BrandRepositoryInMemoryImpl = BrandRepositoryInMemoryImpl::new(cache);
+        let result: Vec<Brand> = repository.list_trashed().await.unwrap().into_iter().map(|t| t.entity).collect();
+
+        assert_eq!(result, vec![brand.clone()], "Expected {:?}, but got {:?}", vec![brand], result);
+        assert_eq!(repository.restore(&brand).await, Ok(brand));
+    }
+
+    #[tokio::test]
+    async fn purge_older_than_should_only_remove_expired_brands() {
+        let brand: Brand = Brand::new("Sau Hollinger".to_owned());
+        let repository: BrandRepositoryInMemoryImpl = given_repository_with(vec![brand.clone()]);
+
+        assert!(repository.delete(&brand, DeleteBehaviour::Restrict).await.is_ok());
+
+        assert_eq!(repository.purge_older_than(Utc::now() - Duration::days(30)).await, Ok(vec![]));
+        assert_eq!(repository.list_trashed().await.map(|t| t.len()), Ok(1));
+
+        assert_eq!(
+            repository.purge_older_than(Utc::now() + Duration::seconds(1)).await,
+            Ok(vec![brand])
+        );
+        assert_eq!(repository.list_trashed().await, Ok(vec![]));
+    }
+
+    fn given_cache_with_product_of(brand: &Brand) -> Arc<RwLock<InMemoryCache>> {
+        let category: Category = Category::new(None, "Dairy".to_owned());
+        let cache: Arc<RwLock<InMemoryCache>> = given_cache_with(vec![brand.clone(), Brand::new("Kip Tabar".to_owned())]);
+
+        let mut guard = cache.try_write().unwrap();
+        guard.upsert_category(CategoryMapper::to_model(&category));
+        guard.upsert_product(ProductMapper::to_model(&Product::new(
+            None,
+            "Milk".to_owned(),
+            brand.clone(),
+            category,
+        )));
+        drop(guard);
+        cache
+    }
+
+    #[tokio::test]
+    async fn delete_referenced_brand_with_restrict_should_fail() {
+        let brand: Brand = Brand::new("Sau Hollinger".to_owned());
+        let repository: BrandRepositoryInMemoryImpl = BrandRepositoryInMemoryImpl::new(given_cache_with_product_of(&brand));
+
+        let result: Result<Brand, BrandRepositoryDeleteError> = repository.delete(&brand, DeleteBehaviour::Restrict).await;
+        let expected: Result<Brand, BrandRepositoryDeleteError> = Err(BrandRepositoryDeleteError::BrandStillReferenced);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn delete_referenced_brand_with_cascade_should_trash_its_products_until_restored() {
+        let brand: Brand = Brand::new("Sau Hollinger".to_owned());
+        let cache: Arc<RwLock<InMemoryCache>> = given_cache_with_product_of(&brand);
+        let repository: BrandRepositoryInMemoryImpl = BrandRepositoryInMemoryImpl::new(Arc::clone(&cache));
+
+        assert_eq!(repository.delete(&brand, DeleteBehaviour::Cascade).await, Ok(brand.clone()));
+
+        let result: usize = cache.read().await.get_all_products().count();
+
+        assert_eq!(result, 0, "Expected {:?}, but got {:?}", 0, result);
+        assert_eq!(repository.restore(&brand).await, Ok(brand));
+        assert_eq!(cache.read().await.get_all_products().count(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+    async fn parallel_creates_under_any_spelling_should_never_duplicate_a_brand() {
+        const NAMES: usize = 32;
+        const ATTEMPTS: usize = 64 * NAMES;
+        let repository: Arc<BrandRepositoryInMemoryImpl> = Arc::new(given_repository_with(vec![]));
+
+        let tasks = (0..ATTEMPTS).map(|attempt| {
+            let repository: Arc<BrandRepositoryInMemoryImpl> = Arc::clone(&repository);
+            let name: String = match attempt % 3 {
+                0 => format!("Brand {}", attempt % NAMES),
+                1 => format!(" BRAND {}", attempt % NAMES),
+                _ => format!("brand {} ", attempt % NAMES),
+            };
+
+            tokio::spawn(async move { repository.create(&Brand::new(name)).await })
+        });
+        let results: Vec<Result<Brand, BrandRepositoryCreateError>> = futures::future::join_all(tasks)
+            .await
+            .into_iter()
+            .map(|task| task.unwrap())
+            .collect();
+
+        let result: usize = results.iter().filter(|result| result.is_ok()).count();
+
+        assert_eq!(result, NAMES, "Expected {:?}, but got {:?}", NAMES, result);
+        assert!(
+            results
+                .iter()
+                .all(|result| matches!(result, Ok(_) | Err(BrandRepositoryCreateError::BrandAlreadyExists))),
+            "Unexpected results {:?}",
+            results
+        );
+        assert_eq!(repository.retrieve_all().await.map(|brands| brands.len()), Ok(NAMES));
+    }
 }
@@ -1,48 +1,152 @@
-use std::collections::HashMap;
+use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::{
+    StreamExt,
+    stream::{self, BoxStream},
+};
+use tokio::sync::RwLock;
 use uuid_b64::UuidB64;
 
 use expense_tracking::domain::{
     entities::Category,
-    repositories::{CategoryRepository, CategoryRepositoryError},
+    repositories::{CategoryRepository, CategoryRepositoryError, DeleteBehaviour, Trashed},
+};
+
+use crate::{
+    adapters::mappers::CategoryMapper,
+    infrastructures::data_sources::{CategoryModel, InMemoryCache, IntegrityError},
 };
 
-#[derive(Debug, Clone, Default, PartialEq)]
+#[derive(Debug, Default)]
 pub struct CategoryRepositoryInMemoryImpl {
-    hash_map: HashMap<UuidB64, Category>,
+    cache: Arc<RwLock<InMemoryCache>>,
 }
 
 impl CategoryRepositoryInMemoryImpl {
-    pub fn new(hash_map: HashMap<UuidB64, Category>) -> Self {
-        Self { hash_map }
+    pub fn new(cache: Arc<RwLock<InMemoryCache>>) -> Self {
+        Self { cache }
+    }
+
+    fn to_entity(model: &CategoryModel) -> Result<Category, CategoryRepositoryError> {
+        CategoryMapper::to_entity(model).map_err(|e| CategoryRepositoryError::UnableToAccessStorage(format!("{:?}", e)))
+    }
+
+    fn to_trashed(model: &CategoryModel) -> Result<Trashed<Category>, CategoryRepositoryError> {
+        Ok(Trashed::new(Self::to_entity(model)?, model.deleted_at.unwrap_or_default()))
     }
 }
 
 #[async_trait]
 impl CategoryRepository for CategoryRepositoryInMemoryImpl {
-    async fn create_or_update(
-        &mut self,
-        category: &Category,
-    ) -> Result<Option<Category>, CategoryRepositoryError> {
-        Ok(self.hash_map.insert(category.id, category.clone()))
+    async fn create_or_update(&mut self, category: &Category) -> Result<Option<Category>, CategoryRepositoryError> {
+        let mut cache = self.cache.write().await;
+
+        let version: u64 = match cache.get_single_category(&category.id.to_string()) {
+            Some(existing) if existing.deleted_at.is_some() => return Err(CategoryRepositoryError::CategoryAlreadyExists),
+            Some(existing) if existing.version != category.version => {
+                return Err(CategoryRepositoryError::VersionConflict {
+                    current_version: existing.version,
+                });
+            }
+            Some(existing) => existing.version + 1,
+            None => category.version,
+        };
+        let model: CategoryModel = CategoryMapper::to_model(&Category {
+            version,
+            ..category.clone()
+        });
+
+        if cache.is_category_name_taken(&model) {
+            return Err(CategoryRepositoryError::CategoryAlreadyExists);
+        }
+
+        cache.upsert_category(model).map(|previous| Self::to_entity(&previous)).transpose()
     }
 
     async fn retrieve_all(&self) -> Result<Vec<Category>, CategoryRepositoryError> {
-        Ok(self.hash_map.values().cloned().collect())
+        self.cache.read().await.get_all_categories().map(Self::to_entity).collect()
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Category, CategoryRepositoryError>> {
+        stream::once(async move {
+            self.cache
+                .read()
+                .await
+                .get_all_categories()
+                .map(|c| c.key.clone())
+                .collect::<Vec<String>>()
+        })
+        .flat_map(stream::iter)
+        .filter_map(move |key| async move {
+            self.cache
+                .read()
+                .await
+                .get_single_category(&key)
+                .filter(|c| c.deleted_at.is_none())
+                .map(|c| Self::to_entity(&c))
+        })
+        .boxed()
+    }
+
+    async fn delete(&mut self, id: &UuidB64, behaviour: DeleteBehaviour<Category>) -> Result<Category, CategoryRepositoryError> {
+        let behaviour: DeleteBehaviour<String> = match behaviour {
+            DeleteBehaviour::Restrict => DeleteBehaviour::Restrict,
+            DeleteBehaviour::Cascade => DeleteBehaviour::Cascade,
+            DeleteBehaviour::Reassign(replacement) => DeleteBehaviour::Reassign(replacement.id.to_string()),
+        };
+
+        let removed: CategoryModel = self
+            .cache
+            .write()
+            .await
+            .delete_category(&id.to_string(), behaviour, Utc::now())
+            .map_err(|e| match e {
+                IntegrityError::NotFound(_) => CategoryRepositoryError::CategoryNotFound,
+                IntegrityError::StillReferenced(_) => CategoryRepositoryError::CategoryStillReferenced,
+                IntegrityError::ReplacementNotFound(_) => CategoryRepositoryError::ReplacementCategoryNotFound,
+                e => CategoryRepositoryError::UnableToAccessStorage(format!("{:?}", e)),
+            })?;
+        Self::to_entity(&removed)
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Category>>, CategoryRepositoryError> {
+        self.cache.read().await.get_trashed_categories().map(Self::to_trashed).collect()
+    }
+
+    async fn restore(&mut self, id: &UuidB64) -> Result<Category, CategoryRepositoryError> {
+        let restored: CategoryModel = self.cache.write().await.restore_category(&id.to_string()).map_err(|e| match e {
+            IntegrityError::NotFound(_) => CategoryRepositoryError::CategoryNotInTrash,
+            e => CategoryRepositoryError::UnableToAccessStorage(format!("{:?}", e)),
+        })?;
+
+        Self::to_entity(&restored)
+    }
+
+    async fn purge_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<Vec<Category>, CategoryRepositoryError> {
+        self.cache
+            .write()
+            .await
+            .purge_categories(cutoff)
+            .iter()
+            .map(Self::to_entity)
+            .collect()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
 
-    use uuid_b64::UuidB64;
+    use chrono::{Duration, Utc};
 
     use super::CategoryRepositoryInMemoryImpl;
+    use crate::{adapters::mappers::CategoryMapper, infrastructures::data_sources::InMemoryCache};
     use expense_tracking::domain::{
         entities::Category,
-        repositories::{CategoryRepository, CategoryRepositoryError},
+        repositories::{CategoryRepository, CategoryRepositoryError, DeleteBehaviour},
     };
 
     fn given_empty_repository() -> CategoryRepositoryInMemoryImpl {
@@ -50,12 +154,13 @@ mod tests {
     }
 
     fn given_repository_with(categorys: Vec<Category>) -> CategoryRepositoryInMemoryImpl {
-        CategoryRepositoryInMemoryImpl::new(
-            categorys
-                .into_iter()
-                .map(|b| (b.id, b))
-                .collect::<HashMap<UuidB64, Category>>(),
-        )
+        let mut cache: InMemoryCache = InMemoryCache::new();
+
+        categorys.iter().for_each(|c| {
+            cache.upsert_category(CategoryMapper::to_model(c));
+        });
+
+        CategoryRepositoryInMemoryImpl::new(Arc::new(RwLock::new(cache)))
     }
 
     fn given_new_category() -> Category {
@@ -103,57 +208,59 @@ mod tests {
         let category: Category = Category::new(None, "New Category".into());
         let mut repository: CategoryRepositoryInMemoryImpl = given_empty_repository();
 
-        let result: Result<Option<Category>, CategoryRepositoryError> =
-            repository.create_or_update(&category).await;
+        let result: Result<Option<Category>, CategoryRepositoryError> = repository.create_or_update(&category).await;
         let expected: Result<Option<Category>, CategoryRepositoryError> = Ok(None);
 
-        assert_eq!(
-            result, expected,
-            "Expected {:?}, but got {:?}",
-            expected, result
-        );
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
     }
 
     #[tokio::test]
     async fn create_or_update_should_add_new_category_given_full_repository() {
         let category: Category = Category::new(None, "New Category".into());
-        let mut repository: CategoryRepositoryInMemoryImpl = given_repository_with(vec![
-            given_new_category(),
-            given_new_category(),
-            given_new_category(),
-        ]);
-
-        let result: Result<Option<Category>, CategoryRepositoryError> =
-            repository.create_or_update(&category).await;
+        let mut repository: CategoryRepositoryInMemoryImpl =
+            given_repository_with(vec![given_new_category(), given_new_category(), given_new_category()]);
+
+        let result: Result<Option<Category>, CategoryRepositoryError> = repository.create_or_update(&category).await;
         let expected: Result<Option<Category>, CategoryRepositoryError> = Ok(None);
 
-        assert_eq!(
-            result, expected,
-            "Expected {:?}, but got {:?}",
-            expected, result
-        );
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
     }
 
     #[tokio::test]
     async fn create_or_update_should_update_category_given_full_repository() {
         let old_category: Category = Category::new(None, "New Category".into());
-        let updated_category: Category =
-            Category::new(Some(old_category.id), "New Updated Category".into());
+        let updated_category: Category = Category::new(Some(old_category.id), "New Updated Category".into());
 
-        let mut repository: CategoryRepositoryInMemoryImpl = given_repository_with(vec![
-            given_new_category(),
-            old_category.clone(),
-            given_new_category(),
-        ]);
+        let mut repository: CategoryRepositoryInMemoryImpl =
+            given_repository_with(vec![given_new_category(), old_category.clone(), given_new_category()]);
 
-        let result: Result<Option<Category>, CategoryRepositoryError> =
-            repository.create_or_update(&updated_category).await;
+        let result: Result<Option<Category>, CategoryRepositoryError> = repository.create_or_update(&updated_category).await;
         let expected: Result<Option<Category>, CategoryRepositoryError> = Ok(Some(old_category));
 
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn delete_restore_and_purge_should_move_category_through_trash() {
+        let category: Category = Category::new(None, "Dairy".into());
+        let mut repository: CategoryRepositoryInMemoryImpl = given_repository_with(vec![category.clone()]);
+
+        assert_eq!(
+            repository.delete(&category.id, DeleteBehaviour::Restrict).await,
+            Ok(category.clone())
+        );
+        assert_eq!(repository.retrieve_all().await, Ok(vec![]));
+        assert_eq!(repository.restore(&category.id).await, Ok(category.clone()));
+        assert_eq!(
+            repository.restore(&category.id).await,
+            Err(CategoryRepositoryError::CategoryNotInTrash)
+        );
+
+        assert!(repository.delete(&category.id, DeleteBehaviour::Restrict).await.is_ok());
         assert_eq!(
-            result, expected,
-            "Expected {:?}, but got {:?}",
-            expected, result
+            repository.purge_older_than(Utc::now() + Duration::seconds(1)).await,
+            Ok(vec![category])
         );
+        assert_eq!(repository.list_trashed().await, Ok(vec![]));
     }
 }
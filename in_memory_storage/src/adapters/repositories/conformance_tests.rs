@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use repository_conformance::{repository_conformance_suite, suites::RepositoryBackend};
+use tokio::sync::RwLock;
+
+use crate::{
+    adapters::repositories::{
+        BrandRepositoryInMemoryImpl, CategoryRepositoryInMemoryImpl, ProductRepositoryInMemoryImpl, StoreRepositoryInMemoryImpl,
+        TransactionRepositoryInMemoryImpl,
+    },
+    infrastructures::data_sources::InMemoryCache,
+};
+
+struct InMemoryBackend {
+    cache: Arc<RwLock<InMemoryCache>>,
+}
+
+impl InMemoryBackend {
+    fn new() -> Self {
+        Self {
+            cache: Arc::new(RwLock::new(InMemoryCache::new())),
+        }
+    }
+}
+
+impl RepositoryBackend for InMemoryBackend {
+    type Brands = BrandRepositoryInMemoryImpl;
+    type Categories = CategoryRepositoryInMemoryImpl;
+    type Stores = StoreRepositoryInMemoryImpl;
+    type Products = ProductRepositoryInMemoryImpl;
+    type Transactions = TransactionRepositoryInMemoryImpl;
+
+    fn brands(&self) -> Self::Brands {
+        BrandRepositoryInMemoryImpl::new(Arc::clone(&self.cache))
+    }
+
+    fn categories(&self) -> Self::Categories {
+        CategoryRepositoryInMemoryImpl::new(Arc::clone(&self.cache))
+    }
+
+    fn stores(&self) -> Self::Stores {
+        StoreRepositoryInMemoryImpl::new(Arc::clone(&self.cache))
+    }
+
+    fn products(&self) -> Self::Products {
+        ProductRepositoryInMemoryImpl::new(Arc::clone(&self.cache))
+    }
+
+    fn transactions(&self) -> Self::Transactions {
+        TransactionRepositoryInMemoryImpl::new(Arc::clone(&self.cache))
+    }
+}
+
+repository_conformance_suite!(InMemoryBackend::new());
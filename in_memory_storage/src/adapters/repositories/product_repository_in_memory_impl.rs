@@ -1,61 +1,196 @@
-use std::collections::HashMap;
+use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::{
+    StreamExt,
+    stream::{self, BoxStream},
+};
+use tokio::sync::RwLock;
 use uuid_b64::UuidB64;
 
 use expense_tracking::domain::{
     entities::Product,
-    repositories::{ProductRepository, ProductRepositoryError},
+    repositories::{DeleteBehaviour, ProductRepository, ProductRepositoryError, Trashed},
+};
+
+use crate::{
+    adapters::mappers::ProductMapper,
+    infrastructures::data_sources::{InMemoryCache, IntegrityError, ProductModel},
 };
 
-#[derive(Debug, Clone, Default, PartialEq)]
+#[derive(Debug, Default)]
 pub struct ProductRepositoryInMemoryImpl {
-    hash_map: HashMap<UuidB64, Product>,
+    cache: Arc<RwLock<InMemoryCache>>,
 }
 
 impl ProductRepositoryInMemoryImpl {
-    pub fn new(hash_map: HashMap<UuidB64, Product>) -> Self {
-        Self { hash_map }
+    pub fn new(cache: Arc<RwLock<InMemoryCache>>) -> Self {
+        Self { cache }
+    }
+
+    fn to_entity(cache: &InMemoryCache, model: &ProductModel) -> Result<Product, ProductRepositoryError> {
+        ProductMapper::to_entity(cache, model).map_err(|e| ProductRepositoryError::UnableToAccessStorage(format!("{:?}", e)))
+    }
+
+    fn check_references(cache: &InMemoryCache, model: &ProductModel) -> Result<(), ProductRepositoryError> {
+        cache.check_product_references(model).map_err(|e| Self::to_error(model, e))
+    }
+
+    fn to_error(model: &ProductModel, error: IntegrityError) -> ProductRepositoryError {
+        match error {
+            IntegrityError::AlreadyExists(_) => ProductRepositoryError::ProductAlreadyExists,
+            IntegrityError::DanglingReference(key) if key == model.brand_key => ProductRepositoryError::BrandNotFound,
+            IntegrityError::DanglingReference(_) => ProductRepositoryError::CategoryNotFound,
+            e => ProductRepositoryError::UnableToAccessStorage(format!("{:?}", e)),
+        }
     }
 }
 
 #[async_trait]
 impl ProductRepository for ProductRepositoryInMemoryImpl {
-    async fn create_or_update(
-        &mut self,
-        product: &Product,
-    ) -> Result<Option<Product>, ProductRepositoryError> {
-        Ok(self.hash_map.insert(product.id, product.clone()))
+    async fn create_or_update(&mut self, product: &Product) -> Result<Option<Product>, ProductRepositoryError> {
+        let mut cache = self.cache.write().await;
+        let model: ProductModel = ProductMapper::to_model(product);
+
+        Self::check_references(&cache, &model)?;
+
+        let previous: Option<Product> = match cache.get_single_product(&model.key) {
+            Some(existing) if existing.deleted_at.is_some() => return Err(ProductRepositoryError::ProductAlreadyExists),
+            Some(existing) if existing.version != product.version => {
+                return Err(ProductRepositoryError::VersionConflict {
+                    current_version: existing.version,
+                });
+            }
+            Some(existing) => Some(Self::to_entity(&cache, &existing)?),
+            None => None,
+        };
+
+        if cache.is_product_name_taken(&model) {
+            return Err(ProductRepositoryError::ProductAlreadyExists);
+        }
+
+        cache.upsert_product(ProductModel {
+            version: previous.as_ref().map_or(product.version, |p| p.version + 1),
+            ..model
+        });
+        Ok(previous)
     }
 
     async fn retrieve_all(&self) -> Result<Vec<Product>, ProductRepositoryError> {
-        Ok(self.hash_map.values().cloned().collect())
+        let cache = self.cache.read().await;
+
+        cache.get_all_products().map(|p| Self::to_entity(&cache, p)).collect()
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Product, ProductRepositoryError>> {
+        stream::once(async move {
+            self.cache
+                .read()
+                .await
+                .get_all_products()
+                .map(|p| p.key.clone())
+                .collect::<Vec<String>>()
+        })
+        .flat_map(stream::iter)
+        .filter_map(move |key| async move {
+            let cache = self.cache.read().await;
+
+            cache
+                .get_single_product(&key)
+                .filter(|p| p.deleted_at.is_none())
+                .map(|p| Self::to_entity(&cache, &p))
+        })
+        .boxed()
+    }
+
+    async fn delete(&mut self, id: &UuidB64, behaviour: DeleteBehaviour<Product>) -> Result<Product, ProductRepositoryError> {
+        let mut cache = self.cache.write().await;
+        let behaviour: DeleteBehaviour<String> = match behaviour {
+            DeleteBehaviour::Restrict => DeleteBehaviour::Restrict,
+            DeleteBehaviour::Cascade => DeleteBehaviour::Cascade,
+            DeleteBehaviour::Reassign(replacement) => DeleteBehaviour::Reassign(replacement.id.to_string()),
+        };
+
+        let removed: ProductModel = cache.delete_product(&id.to_string(), behaviour, Utc::now()).map_err(|e| match e {
+            IntegrityError::NotFound(_) => ProductRepositoryError::ProductNotFound,
+            IntegrityError::StillReferenced(_) => ProductRepositoryError::ProductStillReferenced,
+            IntegrityError::ReplacementNotFound(_) => ProductRepositoryError::ReplacementProductNotFound,
+            e => ProductRepositoryError::UnableToAccessStorage(format!("{:?}", e)),
+        })?;
+        Self::to_entity(&cache, &removed)
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Product>>, ProductRepositoryError> {
+        let cache = self.cache.read().await;
+
+        cache
+            .get_trashed_products()
+            .map(|p| Ok(Trashed::new(Self::to_entity(&cache, p)?, p.deleted_at.unwrap_or_default())))
+            .collect()
+    }
+
+    async fn restore(&mut self, id: &UuidB64) -> Result<Product, ProductRepositoryError> {
+        let mut cache = self.cache.write().await;
+
+        let trashed: ProductModel = cache
+            .get_single_product(&id.to_string())
+            .ok_or(ProductRepositoryError::ProductNotInTrash)?;
+        let restored: ProductModel = cache.restore_product(&trashed.key).map_err(|e| match e {
+            IntegrityError::NotFound(_) => ProductRepositoryError::ProductNotInTrash,
+            e => Self::to_error(&trashed, e),
+        })?;
+        Self::to_entity(&cache, &restored)
+    }
+
+    async fn purge_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<Vec<Product>, ProductRepositoryError> {
+        let mut cache = self.cache.write().await;
+
+        let purged: Vec<ProductModel> = cache.purge_products(cutoff);
+        purged.iter().map(|p| Self::to_entity(&cache, p)).collect()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
 
-    use uuid_b64::UuidB64;
+    use chrono::{Duration, Utc};
 
     use super::ProductRepositoryInMemoryImpl;
+    use crate::{
+        adapters::{
+            mappers::{BrandMapper, CategoryMapper, ProductMapper},
+            repositories::{BrandRepositoryInMemoryImpl, CategoryRepositoryInMemoryImpl},
+        },
+        infrastructures::data_sources::InMemoryCache,
+    };
     use expense_tracking::domain::{
         entities::{Brand, Category, Product},
-        repositories::{ProductRepository, ProductRepositoryError},
+        repositories::{BrandRepository, CategoryRepository, DeleteBehaviour, ProductRepository, ProductRepositoryError},
     };
 
     fn given_empty_repository() -> ProductRepositoryInMemoryImpl {
         given_repository_with(Vec::new())
     }
 
+    fn given_cache_with(products: Vec<Product>) -> Arc<RwLock<InMemoryCache>> {
+        let mut cache: InMemoryCache = InMemoryCache::new();
+
+        cache.upsert_brand(BrandMapper::to_model(&given_new_brand()));
+        cache.upsert_category(CategoryMapper::to_model(&given_new_category()));
+        products.iter().for_each(|p| {
+            cache.upsert_brand(BrandMapper::to_model(&p.brand));
+            cache.upsert_category(CategoryMapper::to_model(&p.category));
+            cache.upsert_product(ProductMapper::to_model(p));
+        });
+
+        Arc::new(RwLock::new(cache))
+    }
+
     fn given_repository_with(products: Vec<Product>) -> ProductRepositoryInMemoryImpl {
-        ProductRepositoryInMemoryImpl::new(
-            products
-                .into_iter()
-                .map(|b| (b.id, b))
-                .collect::<HashMap<UuidB64, Product>>(),
-        )
+        ProductRepositoryInMemoryImpl::new(given_cache_with(products))
     }
 
     fn given_new_brand() -> Brand {
@@ -67,12 +202,7 @@ mod tests {
     }
 
     fn given_new_product() -> Product {
-        Product::new(
-            None,
-            String::default(),
-            given_new_brand(),
-            given_new_category(),
-        )
+        Product::new(None, String::default(), given_new_brand(), Category::new(None, String::default()))
     }
 
     macro_rules! retrieve_all {
@@ -113,79 +243,142 @@ mod tests {
 
     #[tokio::test]
     async fn create_or_update_should_add_new_product_given_empty_repository() {
-        let product: Product = Product::new(
-            None,
-            "New Product".into(),
-            given_new_brand(),
-            given_new_category(),
-        );
+        let product: Product = Product::new(None, "New Product".into(), given_new_brand(), given_new_category());
         let mut repository: ProductRepositoryInMemoryImpl = given_empty_repository();
+        repository
+            .cache
+            .write()
+            .await
+            .upsert_category(CategoryMapper::to_model(&product.category));
 
-        let result: Result<Option<Product>, ProductRepositoryError> =
-            repository.create_or_update(&product).await;
+        let result: Result<Option<Product>, ProductRepositoryError> = repository.create_or_update(&product).await;
         let expected: Result<Option<Product>, ProductRepositoryError> = Ok(None);
 
-        assert_eq!(
-            result, expected,
-            "Expected {:?}, but got {:?}",
-            expected, result
-        );
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
     }
 
     #[tokio::test]
     async fn create_or_update_should_add_new_product_given_full_repository() {
-        let product: Product = Product::new(
-            None,
-            "New Product".into(),
-            given_new_brand(),
-            given_new_category(),
-        );
-        let mut repository: ProductRepositoryInMemoryImpl = given_repository_with(vec![
-            given_new_product(),
-            given_new_product(),
-            given_new_product(),
-        ]);
-
-        let result: Result<Option<Product>, ProductRepositoryError> =
-            repository.create_or_update(&product).await;
+        let product: Product = Product::new(None, "New Product".into(), given_new_brand(), given_new_category());
+        let mut repository: ProductRepositoryInMemoryImpl =
+            given_repository_with(vec![given_new_product(), given_new_product(), given_new_product()]);
+        repository
+            .cache
+            .write()
+            .await
+            .upsert_category(CategoryMapper::to_model(&product.category));
+
+        let result: Result<Option<Product>, ProductRepositoryError> = repository.create_or_update(&product).await;
         let expected: Result<Option<Product>, ProductRepositoryError> = Ok(None);
 
-        assert_eq!(
-            result, expected,
-            "Expected {:?}, but got {:?}",
-            expected, result
-        );
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
     }
 
     #[tokio::test]
     async fn create_or_update_should_update_product_given_full_repository() {
-        let old_product: Product = Product::new(
-            None,
-            "New Product".into(),
-            given_new_brand(),
-            given_new_category(),
-        );
+        let old_product: Product = Product::new(None, "New Product".into(), given_new_brand(), given_new_category());
         let updated_product: Product = Product::new(
             Some(old_product.id),
             "New Updated Product".into(),
             given_new_brand(),
-            given_new_category(),
+            old_product.category.clone(),
         );
 
-        let mut repository: ProductRepositoryInMemoryImpl = given_repository_with(vec![
-            given_new_product(),
-            old_product.clone(),
-            given_new_product(),
-        ]);
+        let mut repository: ProductRepositoryInMemoryImpl =
+            given_repository_with(vec![given_new_product(), old_product.clone(), given_new_product()]);
 
-        let result: Result<Option<Product>, ProductRepositoryError> =
-            repository.create_or_update(&updated_product).await;
+        let result: Result<Option<Product>, ProductRepositoryError> = repository.create_or_update(&updated_product).await;
         let expected: Result<Option<Product>, ProductRepositoryError> = Ok(Some(old_product));
 
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn create_or_update_with_missing_references_should_fail() {
+        let mut repository: ProductRepositoryInMemoryImpl = given_empty_repository();
+
+        let orphan_brand: Product = Product::new(None, "Milk".into(), Brand::new("Unknown".into()), given_new_category());
+        let result: Result<Option<Product>, ProductRepositoryError> = repository.create_or_update(&orphan_brand).await;
+        let expected: Result<Option<Product>, ProductRepositoryError> = Err(ProductRepositoryError::BrandNotFound);
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+
+        let orphan_category: Product = Product::new(None, "Milk".into(), given_new_brand(), given_new_category());
+        let result: Result<Option<Product>, ProductRepositoryError> = repository.create_or_update(&orphan_category).await;
+        let expected: Result<Option<Product>, ProductRepositoryError> = Err(ProductRepositoryError::CategoryNotFound);
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn retrieve_all_should_reflect_edits_made_through_other_repositories() {
+        let product: Product = Product::new(
+            None,
+            "Milk".into(),
+            Brand::new("Nestlé".into()),
+            Category::new(None, "Dairy".into()),
+        );
+        let cache: Arc<RwLock<InMemoryCache>> = given_cache_with(vec![product.clone()]);
+        let repository: ProductRepositoryInMemoryImpl = ProductRepositoryInMemoryImpl::new(Arc::clone(&cache));
+
+        let brand: Brand = BrandRepositoryInMemoryImpl::new(Arc::clone(&cache))
+            .update(&product.brand)
+            .await
+            .unwrap();
+        let category: Category = Category {
+            name: "Dairy Products".into(),
+            ..product.category.clone()
+        };
+        CategoryRepositoryInMemoryImpl::new(Arc::clone(&cache))
+            .create_or_update(&category)
+            .await
+            .unwrap();
+
+        let result: Result<Vec<Product>, ProductRepositoryError> = repository.retrieve_all().await;
+        let expected: Result<Vec<Product>, ProductRepositoryError> = Ok(vec![Product {
+            brand,
+            category: Category { version: 1, ..category },
+            ..product
+        }]);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn delete_restore_and_purge_should_move_product_through_trash() {
+        let product: Product = given_new_product();
+        let mut repository: ProductRepositoryInMemoryImpl = given_repository_with(vec![product.clone()]);
+
+        assert_eq!(repository.delete(&product.id, DeleteBehaviour::Restrict).await, Ok(product.clone()));
+        assert_eq!(repository.retrieve_all().await, Ok(vec![]));
+        assert_eq!(repository.restore(&product.id).await, Ok(product.clone()));
+        assert_eq!(
+            repository.restore(&product.id).await,
+            Err(ProductRepositoryError::ProductNotInTrash)
+        );
+
+        assert!(repository.delete(&product.id, DeleteBehaviour::Restrict).await.is_ok());
         assert_eq!(
-            result, expected,
-            "Expected {:?}, but got {:?}",
-            expected, result
+            repository.purge_older_than(Utc::now() + Duration::seconds(1)).await,
+            Ok(vec![product])
         );
+        assert_eq!(repository.list_trashed().await, Ok(vec![]));
+    }
+
+    #[tokio::test]
+    async fn restore_product_whose_category_was_trashed_should_fail() {
+        let product: Product = given_new_product();
+        let mut repository: ProductRepositoryInMemoryImpl = given_repository_with(vec![product.clone()]);
+
+        assert!(repository.delete(&product.id, DeleteBehaviour::Restrict).await.is_ok());
+        repository
+            .cache
+            .write()
+            .await
+            .delete_category(&product.category.id.to_string(), DeleteBehaviour::Restrict, Utc::now())
+            .unwrap();
+
+        let result: Result<Product, ProductRepositoryError> = repository.restore(&product.id).await;
+        let expected: Result<Product, ProductRepositoryError> = Err(ProductRepositoryError::CategoryNotFound);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
     }
 }
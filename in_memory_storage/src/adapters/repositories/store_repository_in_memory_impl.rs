@@ -1,45 +1,144 @@
-use std::collections::HashMap;
+use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::{
+    StreamExt,
+    stream::{self, BoxStream},
+};
+use tokio::sync::RwLock;
 use uuid_b64::UuidB64;
 
 use expense_tracking::domain::{
     entities::Store,
-    repositories::{StoreRepository, StoreRepositoryError},
+    repositories::{DeleteBehaviour, StoreRepository, StoreRepositoryError, Trashed},
+};
+
+use crate::{
+    adapters::mappers::StoreMapper,
+    infrastructures::data_sources::{InMemoryCache, IntegrityError, StoreModel},
 };
 
-#[derive(Debug, Clone, Default, PartialEq)]
+#[derive(Debug, Default)]
 pub struct StoreRepositoryInMemoryImpl {
-    hash_map: HashMap<UuidB64, Store>,
+    cache: Arc<RwLock<InMemoryCache>>,
 }
 
 impl StoreRepositoryInMemoryImpl {
-    pub fn new(hash_map: HashMap<UuidB64, Store>) -> Self {
-        Self { hash_map }
+    pub fn new(cache: Arc<RwLock<InMemoryCache>>) -> Self {
+        Self { cache }
+    }
+
+    fn to_entity(model: &StoreModel) -> Result<Store, StoreRepositoryError> {
+        StoreMapper::to_entity(model).map_err(|e| StoreRepositoryError::UnableToAccessStorage(format!("{:?}", e)))
+    }
+
+    fn to_trashed(model: &StoreModel) -> Result<Trashed<Store>, StoreRepositoryError> {
+        Ok(Trashed::new(Self::to_entity(model)?, model.deleted_at.unwrap_or_default()))
     }
 }
 
 #[async_trait]
 impl StoreRepository for StoreRepositoryInMemoryImpl {
     async fn create_or_update(&mut self, store: &Store) -> Result<Option<Store>, StoreRepositoryError> {
-        Ok(self.hash_map.insert(store.id, store.clone()))
+        let mut cache = self.cache.write().await;
+
+        let version: u64 = match cache.get_single_store(&store.id.to_string()) {
+            Some(existing) if existing.deleted_at.is_some() => return Err(StoreRepositoryError::StoreAlreadyExists),
+            Some(existing) if existing.version != store.version => {
+                return Err(StoreRepositoryError::VersionConflict {
+                    current_version: existing.version,
+                });
+            }
+            Some(existing) => existing.version + 1,
+            None => store.version,
+        };
+        let model: StoreModel = StoreMapper::to_model(&Store { version, ..store.clone() });
+
+        if cache.is_store_name_taken(&model) {
+            return Err(StoreRepositoryError::StoreAlreadyExists);
+        }
+
+        cache.upsert_store(model).map(|previous| Self::to_entity(&previous)).transpose()
     }
 
     async fn retrieve_all(&self) -> Result<Vec<Store>, StoreRepositoryError> {
-        Ok(self.hash_map.values().cloned().collect())
+        self.cache.read().await.get_all_stores().map(Self::to_entity).collect()
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Store, StoreRepositoryError>> {
+        stream::once(async move {
+            self.cache
+                .read()
+                .await
+                .get_all_stores()
+                .map(|s| s.key.clone())
+                .collect::<Vec<String>>()
+        })
+        .flat_map(stream::iter)
+        .filter_map(move |key| async move {
+            self.cache
+                .read()
+                .await
+                .get_single_store(&key)
+                .filter(|s| s.deleted_at.is_none())
+                .map(|s| Self::to_entity(&s))
+        })
+        .boxed()
+    }
+
+    async fn delete(&mut self, id: &UuidB64, behaviour: DeleteBehaviour<Store>) -> Result<Store, StoreRepositoryError> {
+        let behaviour: DeleteBehaviour<String> = match behaviour {
+            DeleteBehaviour::Restrict => DeleteBehaviour::Restrict,
+            DeleteBehaviour::Cascade => DeleteBehaviour::Cascade,
+            DeleteBehaviour::Reassign(replacement) => DeleteBehaviour::Reassign(replacement.id.to_string()),
+        };
+
+        let removed: StoreModel = self
+            .cache
+            .write()
+            .await
+            .delete_store(&id.to_string(), behaviour, Utc::now())
+            .map_err(|e| match e {
+                IntegrityError::NotFound(_) => StoreRepositoryError::StoreNotFound,
+                IntegrityError::StillReferenced(_) => StoreRepositoryError::StoreStillReferenced,
+                IntegrityError::ReplacementNotFound(_) => StoreRepositoryError::ReplacementStoreNotFound,
+                e => StoreRepositoryError::UnableToAccessStorage(format!("{:?}", e)),
+            })?;
+        Self::to_entity(&removed)
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Store>>, StoreRepositoryError> {
+        self.cache.read().await.get_trashed_stores().map(Self::to_trashed).collect()
+    }
+
+    async fn restore(&mut self, id: &UuidB64) -> Result<Store, StoreRepositoryError> {
+        let restored: StoreModel = self.cache.write().await.restore_store(&id.to_string()).map_err(|e| match e {
+            IntegrityError::NotFound(_) => StoreRepositoryError::StoreNotInTrash,
+            e => StoreRepositoryError::UnableToAccessStorage(format!("{:?}", e)),
+        })?;
+
+        Self::to_entity(&restored)
+    }
+
+    async fn purge_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<Vec<Store>, StoreRepositoryError> {
+        self.cache.write().await.purge_stores(cutoff).iter().map(Self::to_entity).collect()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
 
-    use uuid_b64::UuidB64;
+    use chrono::{Duration, Utc};
+    use futures::StreamExt;
 
     use super::StoreRepositoryInMemoryImpl;
+    use crate::{adapters::mappers::StoreMapper, infrastructures::data_sources::InMemoryCache};
     use expense_tracking::domain::{
         entities::Store,
-        repositories::{StoreRepository, StoreRepositoryError},
+        repositories::{DeleteBehaviour, StoreRepository, StoreRepositoryError, Trashed},
     };
 
     fn given_empty_repository() -> StoreRepositoryInMemoryImpl {
@@ -47,7 +146,13 @@ mod tests {
     }
 
     fn given_repository_with(stores: Vec<Store>) -> StoreRepositoryInMemoryImpl {
-        StoreRepositoryInMemoryImpl::new(stores.into_iter().map(|b| (b.id, b)).collect::<HashMap<UuidB64, Store>>())
+        let mut cache: InMemoryCache = InMemoryCache::new();
+
+        stores.iter().for_each(|s| {
+            cache.upsert_store(StoreMapper::to_model(s));
+        });
+
+        StoreRepositoryInMemoryImpl::new(Arc::new(RwLock::new(cache)))
     }
 
     fn given_new_store() -> Store {
@@ -90,6 +195,42 @@ mod tests {
         multiple_stores: &vec![given_new_store(), given_new_store(), given_new_store()],
     }
 
+    macro_rules! stream_all {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[tokio::test]
+            async fn $name() {
+                let stores: &Vec<Store> = $value;
+
+                let repository: StoreRepositoryInMemoryImpl = given_repository_with(stores.clone());
+
+                let result: Result<Vec<Store>, StoreRepositoryError> =
+                    repository.stream_all().collect::<Vec<_>>().await.into_iter().collect();
+
+                assert!(result.is_ok(), "Expected Ok, got {:?}", result);
+
+                let mut expected_stores: Vec<Store> = stores.clone();
+                expected_stores.sort_by(|a, b| a.id.cmp(&b.id));
+
+                let mut sorted_result: Vec<Store> = result.unwrap();
+                sorted_result.sort_by(|a, b| a.id.cmp(&b.id));
+
+                assert_eq!(
+                    sorted_result, expected_stores,
+                    "Expected {:?}, but got {:?}",
+                    expected_stores, sorted_result
+                )
+            }
+        )*
+        }
+    }
+
+    stream_all! {
+        stream_no_stores: &vec![],
+        stream_one_store: &vec![given_new_store()],
+        stream_multiple_stores: &vec![given_new_store(), given_new_store(), given_new_store()],
+    }
+
     #[tokio::test]
     async fn create_or_update_should_add_new_store_given_empty_repository() {
         let store: Store = Store::new(None, "New Store".into());
@@ -126,4 +267,145 @@ mod tests {
 
         assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
     }
+
+    #[tokio::test]
+    async fn delete_should_fail_given_empty_repository() {
+        let store: Store = Store::new(None, "Some Store".into());
+        let mut repository: StoreRepositoryInMemoryImpl = given_empty_repository();
+
+        let result: Result<Store, StoreRepositoryError> = repository.delete(&store.id, DeleteBehaviour::Restrict).await;
+        let expected: Result<Store, StoreRepositoryError> = Err(StoreRepositoryError::StoreNotFound);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn delete_should_remove_existing_store() {
+        let store: Store = Store::new(None, "Some Store".into());
+        let mut repository: StoreRepositoryInMemoryImpl = given_repository_with(vec![given_new_store(), store.clone(), given_new_store()]);
+
+        let result: Result<Store, StoreRepositoryError> = repository.delete(&store.id, DeleteBehaviour::Cascade).await;
+        let expected: Result<Store, StoreRepositoryError> = Ok(store.clone());
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert!(!repository.retrieve_all().await.unwrap().contains(&store));
+    }
+
+    #[tokio::test]
+    async fn delete_should_fail_reassigning_to_non_existing_store() {
+        let store: Store = Store::new(None, "Some Store".into());
+        let mut repository: StoreRepositoryInMemoryImpl = given_repository_with(vec![given_new_store(), store.clone()]);
+
+        let result: Result<Store, StoreRepositoryError> = repository.delete(&store.id, DeleteBehaviour::Reassign(given_new_store())).await;
+        let expected: Result<Store, StoreRepositoryError> = Err(StoreRepositoryError::ReplacementStoreNotFound);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert!(repository.retrieve_all().await.unwrap().contains(&store));
+    }
+
+    #[tokio::test]
+    async fn delete_should_remove_store_reassigning_to_existing_store() {
+        let store: Store = Store::new(None, "Some Store".into());
+        let replacement: Store = Store::new(None, "Some Other Store".into());
+        let mut repository: StoreRepositoryInMemoryImpl = given_repository_with(vec![replacement.clone(), store.clone()]);
+
+        let result: Result<Store, StoreRepositoryError> = repository.delete(&store.id, DeleteBehaviour::Reassign(replacement)).await;
+        let expected: Result<Store, StoreRepositoryError> = Ok(store);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn create_or_update_should_increment_version_of_existing_store() {
+        let store: Store = Store::new(None, "New Store".into());
+        let mut repository: StoreRepositoryInMemoryImpl = given_repository_with(vec![store.clone()]);
+
+        assert_eq!(repository.create_or_update(&store).await, Ok(Some(store.clone())));
+
+        let stored: Vec<Store> = repository.retrieve_all().await.unwrap();
+        assert_eq!(stored.iter().map(|s| s.version).collect::<Vec<u64>>(), vec![1]);
+    }
+
+    #[tokio::test]
+    async fn create_or_update_should_reject_stale_store() {
+        let store: Store = Store::new(None, "New Store".into());
+        let mut repository: StoreRepositoryInMemoryImpl = given_repository_with(vec![store.clone()]);
+
+        assert!(repository.create_or_update(&store).await.is_ok());
+
+        let stale: Store = Store::new(Some(store.id), "Stale Store".into());
+        let result: Result<Option<Store>, StoreRepositoryError> = repository.create_or_update(&stale).await;
+        let expected: Result<Option<Store>, StoreRepositoryError> = Err(StoreRepositoryError::VersionConflict { current_version: 1 });
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn delete_should_move_store_to_trash() {
+        let store: Store = Store::new(None, "Some Store".into());
+        let mut repository: StoreRepositoryInMemoryImpl = given_repository_with(vec![given_new_store(), store.clone()]);
+
+        assert!(repository.delete(&store.id, DeleteBehaviour::Restrict).await.is_ok());
+
+        assert!(!repository.retrieve_all().await.unwrap().contains(&store));
+
+        let trashed: Vec<Trashed<Store>> = repository.list_trashed().await.unwrap();
+        assert_eq!(trashed.iter().map(|t| t.entity.clone()).collect::<Vec<Store>>(), vec![store]);
+    }
+
+    #[tokio::test]
+    async fn restore_should_bring_trashed_store_back() {
+        let store: Store = Store::new(None, "Some Store".into());
+        let mut repository: StoreRepositoryInMemoryImpl = given_repository_with(vec![store.clone()]);
+
+        assert!(repository.delete(&store.id, DeleteBehaviour::Restrict).await.is_ok());
+
+        let result: Result<Store, StoreRepositoryError> = repository.restore(&store.id).await;
+
+        assert_eq!(result, Ok(store.clone()));
+        assert_eq!(repository.retrieve_all().await, Ok(vec![store]));
+        assert_eq!(repository.list_trashed().await, Ok(vec![]));
+    }
+
+    #[tokio::test]
+    async fn restore_store_not_in_trash_should_fail() {
+        let store: Store = Store::new(None, "Some Store".into());
+        let mut repository: StoreRepositoryInMemoryImpl = given_repository_with(vec![store.clone()]);
+
+        let result: Result<Store, StoreRepositoryError> = repository.restore(&store.id).await;
+        let expected: Result<Store, StoreRepositoryError> = Err(StoreRepositoryError::StoreNotInTrash);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn create_or_update_of_a_trashed_store_should_fail() {
+        let store: Store = Store::new(None, "Some Store".into());
+        let mut repository: StoreRepositoryInMemoryImpl = given_repository_with(vec![store.clone()]);
+
+        assert!(repository.delete(&store.id, DeleteBehaviour::Restrict).await.is_ok());
+
+        let result: Result<Option<Store>, StoreRepositoryError> = repository.create_or_update(&store).await;
+        let expected: Result<Option<Store>, StoreRepositoryError> = Err(StoreRepositoryError::StoreAlreadyExists);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(repository.restore(&store.id).await, Ok(store));
+    }
+
+    #[tokio::test]
+    async fn purge_older_than_should_only_remove_expired_stores() {
+        let store: Store = Store::new(None, "Some Store".into());
+        let mut repository: StoreRepositoryInMemoryImpl = given_repository_with(vec![store.clone()]);
+
+        assert!(repository.delete(&store.id, DeleteBehaviour::Restrict).await.is_ok());
+
+        assert_eq!(repository.purge_older_than(Utc::now() - Duration::days(30)).await, Ok(vec![]));
+        assert_eq!(repository.list_trashed().await.map(|t| t.len()), Ok(1));
+
+        assert_eq!(
+            repository.purge_older_than(Utc::now() + Duration::seconds(1)).await,
+            Ok(vec![store])
+        );
+        assert_eq!(repository.list_trashed().await, Ok(vec![]));
+    }
 }
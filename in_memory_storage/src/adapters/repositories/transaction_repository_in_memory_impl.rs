@@ -1,67 +1,202 @@
-use std::collections::HashMap;
+use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::{
+    StreamExt,
+    stream::{self, BoxStream},
+};
+use tokio::sync::RwLock;
 use uuid_b64::UuidB64;
 
 use expense_tracking::domain::{
     entities::Transaction,
-    repositories::{TransactionRepository, TransactionRepositoryError},
+    repositories::{TransactionRepository, TransactionRepositoryError, Trashed},
+};
+
+use crate::{
+    adapters::mappers::TransactionMapper,
+    infrastructures::data_sources::{InMemoryCache, IntegrityError, ItemModel, TransactionModel},
 };
 
-#[derive(Debug, Clone, Default, PartialEq)]
+#[derive(Debug, Default)]
 pub struct TransactionRepositoryInMemoryImpl {
-    hash_map: HashMap<UuidB64, Transaction>,
+    cache: Arc<RwLock<InMemoryCache>>,
 }
 
 impl TransactionRepositoryInMemoryImpl {
-    pub fn new(hash_map: HashMap<UuidB64, Transaction>) -> Self {
-        Self { hash_map }
+    pub fn new(cache: Arc<RwLock<InMemoryCache>>) -> Self {
+        Self { cache }
+    }
+
+    fn to_entity(cache: &InMemoryCache, model: &TransactionModel) -> Result<Transaction, TransactionRepositoryError> {
+        TransactionMapper::to_entity(cache, model).map_err(|e| TransactionRepositoryError::UnableToAccessStorage(format!("{:?}", e)))
+    }
+
+    fn replace(cache: &mut InMemoryCache, transaction: TransactionModel, items: Vec<ItemModel>) -> Result<(), TransactionRepositoryError> {
+        let store_key: String = transaction.store_key.clone();
+
+        cache
+            .replace_transaction(transaction, items)
+            .map(|_| ())
+            .map_err(|e| Self::to_error(&store_key, e))
+    }
+
+    fn to_error(store_key: &str, error: IntegrityError) -> TransactionRepositoryError {
+        match error {
+            IntegrityError::DanglingReference(key) if key == store_key => TransactionRepositoryError::StoreNotFound,
+            IntegrityError::DanglingReference(_) => TransactionRepositoryError::ProductNotFound,
+            e => TransactionRepositoryError::UnableToAccessStorage(format!("{:?}", e)),
+        }
     }
 }
 
 #[async_trait]
 impl TransactionRepository for TransactionRepositoryInMemoryImpl {
-    async fn create_or_update(
-        &mut self,
-        transaction: &Transaction,
-    ) -> Result<Option<Transaction>, TransactionRepositoryError> {
-        Ok(self.hash_map.insert(transaction.id, transaction.clone()))
+    async fn create_or_update(&mut self, transaction: &Transaction) -> Result<Option<Transaction>, TransactionRepositoryError> {
+        let mut cache = self.cache.write().await;
+        let (model, items): (TransactionModel, Vec<ItemModel>) = TransactionMapper::to_model(transaction);
+
+        let previous: Option<Transaction> = match cache.get_single_transaction(&model.key) {
+            Some(existing) if existing.deleted_at.is_some() => return Err(TransactionRepositoryError::TransactionAlreadyExists),
+            Some(existing) if existing.version != transaction.version => {
+                return Err(TransactionRepositoryError::VersionConflict {
+                    current_version: existing.version,
+                });
+            }
+            Some(existing) => Some(Self::to_entity(&cache, &existing)?),
+            None => None,
+        };
+
+        let model: TransactionModel = TransactionModel {
+            version: previous.as_ref().map_or(transaction.version, |t| t.version + 1),
+            ..model
+        };
+
+        Self::replace(&mut cache, model, items)?;
+        Ok(previous)
     }
 
     async fn retrieve_all(&self) -> Result<Vec<Transaction>, TransactionRepositoryError> {
-        Ok(self.hash_map.values().cloned().collect())
+        let cache = self.cache.read().await;
+
+        cache.get_all_transactions().map(|t| Self::to_entity(&cache, t)).collect()
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Transaction, TransactionRepositoryError>> {
+        stream::once(async move {
+            self.cache
+                .read()
+                .await
+                .get_all_transactions()
+                .map(|t| t.key.clone())
+                .collect::<Vec<String>>()
+        })
+        .flat_map(stream::iter)
+        .filter_map(move |key| async move {
+            let cache = self.cache.read().await;
+
+            cache
+                .get_single_transaction(&key)
+                .filter(|t| t.deleted_at.is_none())
+                .map(|t| Self::to_entity(&cache, &t))
+        })
+        .boxed()
+    }
+
+    async fn delete(&mut self, id: &UuidB64) -> Result<Transaction, TransactionRepositoryError> {
+        let mut cache = self.cache.write().await;
+
+        let removed: TransactionModel = cache
+            .delete_transaction(&id.to_string(), Utc::now())
+            .map_err(|_| TransactionRepositoryError::TransactionNotFound)?;
+        Self::to_entity(&cache, &removed)
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Transaction>>, TransactionRepositoryError> {
+        let cache = self.cache.read().await;
+
+        cache
+            .get_trashed_transactions()
+            .map(|t| Ok(Trashed::new(Self::to_entity(&cache, t)?, t.deleted_at.unwrap_or_default())))
+            .collect()
+    }
+
+    async fn restore(&mut self, id: &UuidB64) -> Result<Transaction, TransactionRepositoryError> {
+        let mut cache = self.cache.write().await;
+
+        let trashed: TransactionModel = cache
+            .get_single_transaction(&id.to_string())
+            .ok_or(TransactionRepositoryError::TransactionNotInTrash)?;
+        let restored: TransactionModel = cache.restore_transaction(&trashed.key).map_err(|e| match e {
+            IntegrityError::NotFound(_) => TransactionRepositoryError::TransactionNotInTrash,
+            e => Self::to_error(&trashed.store_key, e),
+        })?;
+        Self::to_entity(&cache, &restored)
+    }
+
+    /// The purged transactions are read before their items go away with them.
+    async fn purge_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<Vec<Transaction>, TransactionRepositoryError> {
+        let mut cache = self.cache.write().await;
+
+        let purged: Vec<Transaction> = cache
+            .get_trashed_transactions()
+            .filter(|t| t.deleted_at.is_some_and(|deleted_at| deleted_at < cutoff))
+            .map(|t| Self::to_entity(&cache, t))
+            .collect::<Result<Vec<Transaction>, TransactionRepositoryError>>()?;
+
+        cache.purge_transactions(cutoff);
+        Ok(purged)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
 
-    use uuid_b64::UuidB64;
+    use chrono::{DateTime, Duration, Utc};
 
     use super::TransactionRepositoryInMemoryImpl;
-    use chrono::DateTime;
+    use crate::{
+        adapters::mappers::{BrandMapper, CategoryMapper, ProductMapper, StoreMapper, TransactionMapper},
+        infrastructures::data_sources::InMemoryCache,
+    };
     use expense_tracking::domain::{
-        entities::{Item, Store, Transaction},
-        repositories::{TransactionRepository, TransactionRepositoryError},
+        entities::{Item, Product, Store, Transaction},
+        repositories::{DeleteBehaviour, TransactionRepository, TransactionRepositoryError},
     };
 
     fn given_empty_repository() -> TransactionRepositoryInMemoryImpl {
         given_repository_with(Vec::new())
     }
 
+    fn given_references_of(cache: &mut InMemoryCache, transaction: &Transaction) {
+        cache.upsert_store(StoreMapper::to_model(&transaction.store));
+        transaction.items.iter().map(Item::product).for_each(|p| {
+            cache.upsert_brand(BrandMapper::to_model(&p.brand));
+            cache.upsert_category(CategoryMapper::to_model(&p.category));
+            cache.upsert_product(ProductMapper::to_model(p));
+        });
+    }
+
     fn given_repository_with(transactions: Vec<Transaction>) -> TransactionRepositoryInMemoryImpl {
-        TransactionRepositoryInMemoryImpl::new(
-            transactions
-                .into_iter()
-                .map(|b| (b.id, b))
-                .collect::<HashMap<UuidB64, Transaction>>(),
-        )
+        let mut cache: InMemoryCache = InMemoryCache::new();
+
+        transactions.iter().for_each(|t| {
+            given_references_of(&mut cache, t);
+
+            let (model, items) = TransactionMapper::to_model(t);
+            cache.replace_transaction(model, items).unwrap();
+        });
+
+        TransactionRepositoryInMemoryImpl::new(Arc::new(RwLock::new(cache)))
     }
 
     fn given_new_item() -> Item {
         Item::default()
     }
+
     fn given_new_store() -> Store {
         Store::default()
     }
@@ -119,16 +254,12 @@ mod tests {
             DateTime::default(),
         );
         let mut repository: TransactionRepositoryInMemoryImpl = given_empty_repository();
+        given_references_of(&mut *repository.cache.write().await, &transaction);
 
-        let result: Result<Option<Transaction>, TransactionRepositoryError> =
-            repository.create_or_update(&transaction).await;
+        let result: Result<Option<Transaction>, TransactionRepositoryError> = repository.create_or_update(&transaction).await;
         let expected: Result<Option<Transaction>, TransactionRepositoryError> = Ok(None);
 
-        assert_eq!(
-            result, expected,
-            "Expected {:?}, but got {:?}",
-            expected, result
-        );
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
     }
 
     #[tokio::test]
@@ -144,16 +275,12 @@ mod tests {
             given_new_transaction(vec![given_new_item(), given_new_item(), given_new_item()]),
             given_new_transaction(vec![given_new_item(), given_new_item(), given_new_item()]),
         ]);
+        given_references_of(&mut *repository.cache.write().await, &transaction);
 
-        let result: Result<Option<Transaction>, TransactionRepositoryError> =
-            repository.create_or_update(&transaction).await;
+        let result: Result<Option<Transaction>, TransactionRepositoryError> = repository.create_or_update(&transaction).await;
         let expected: Result<Option<Transaction>, TransactionRepositoryError> = Ok(None);
 
-        assert_eq!(
-            result, expected,
-            "Expected {:?}, but got {:?}",
-            expected, result
-        );
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
     }
 
     #[tokio::test]
@@ -177,21 +304,99 @@ mod tests {
             given_new_transaction(vec![given_new_item(), given_new_item(), given_new_item()]),
         ]);
 
-        let result: Result<Option<Transaction>, TransactionRepositoryError> =
-            repository.create_or_update(&updated_transaction).await;
-        let expected: Result<Option<Transaction>, TransactionRepositoryError> =
-            Ok(Some(old_transaction));
+        let result: Result<Option<Transaction>, TransactionRepositoryError> = repository.create_or_update(&updated_transaction).await;
+        let expected: Result<Option<Transaction>, TransactionRepositoryError> = Ok(Some(old_transaction));
 
-        assert_eq!(
-            result, expected,
-            "Expected {:?}, but got {:?}",
-            expected, result
-        );
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
         let mut transactions = repository.retrieve_all().await.unwrap();
         transactions.retain(|e| e.id == updated_transaction.id);
         assert_eq!(
-            transactions[0], updated_transaction,
+            transactions[0],
+            Transaction {
+                version: 1,
+                ..updated_transaction
+            },
             "Updated transaction not found"
         );
     }
+
+    #[tokio::test]
+    async fn create_or_update_with_missing_references_should_fail() {
+        let transaction: Transaction = given_new_transaction(vec![given_new_item()]);
+        let mut repository: TransactionRepositoryInMemoryImpl = given_empty_repository();
+
+        let result: Result<Option<Transaction>, TransactionRepositoryError> = repository.create_or_update(&transaction).await;
+        let expected: Result<Option<Transaction>, TransactionRepositoryError> = Err(TransactionRepositoryError::StoreNotFound);
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+
+        repository
+            .cache
+            .write()
+            .await
+            .upsert_store(StoreMapper::to_model(&transaction.store));
+
+        let result: Result<Option<Transaction>, TransactionRepositoryError> = repository.create_or_update(&transaction).await;
+        let expected: Result<Option<Transaction>, TransactionRepositoryError> = Err(TransactionRepositoryError::ProductNotFound);
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn retrieve_all_should_reflect_renamed_product() {
+        let transaction: Transaction = given_new_transaction(vec![given_new_item()]);
+        let repository: TransactionRepositoryInMemoryImpl = given_repository_with(vec![transaction.clone()]);
+
+        let renamed: Product = Product {
+            name: "Oat Milk".into(),
+            ..transaction.items[0].product().clone()
+        };
+        repository.cache.write().await.upsert_product(ProductMapper::to_model(&renamed));
+
+        let result: Result<Vec<String>, TransactionRepositoryError> = repository
+            .retrieve_all()
+            .await
+            .map(|t| t.iter().flat_map(|t| t.items.iter()).map(|i| i.product().name.clone()).collect());
+        let expected: Result<Vec<String>, TransactionRepositoryError> = Ok(vec!["Oat Milk".to_owned()]);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn delete_restore_and_purge_should_move_transaction_through_trash() {
+        let transaction: Transaction = given_new_transaction(vec![given_new_item(), given_new_item()]);
+        let mut repository: TransactionRepositoryInMemoryImpl = given_repository_with(vec![transaction.clone()]);
+
+        assert_eq!(repository.delete(&transaction.id).await, Ok(transaction.clone()));
+        assert_eq!(repository.retrieve_all().await, Ok(vec![]));
+        assert_eq!(repository.restore(&transaction.id).await, Ok(transaction.clone()));
+        assert_eq!(
+            repository.restore(&transaction.id).await,
+            Err(TransactionRepositoryError::TransactionNotInTrash)
+        );
+
+        assert!(repository.delete(&transaction.id).await.is_ok());
+        assert_eq!(
+            repository.purge_older_than(Utc::now() + Duration::seconds(1)).await,
+            Ok(vec![transaction])
+        );
+        assert_eq!(repository.list_trashed().await, Ok(vec![]));
+    }
+
+    #[tokio::test]
+    async fn restore_transaction_whose_store_was_trashed_should_fail() {
+        let transaction: Transaction = given_new_transaction(vec![given_new_item()]);
+        let mut repository: TransactionRepositoryInMemoryImpl = given_repository_with(vec![transaction.clone()]);
+
+        assert!(repository.delete(&transaction.id).await.is_ok());
+        repository
+            .cache
+            .write()
+            .await
+            .delete_store(&transaction.store.id.to_string(), DeleteBehaviour::Restrict, Utc::now())
+            .unwrap();
+
+        let result: Result<Transaction, TransactionRepositoryError> = repository.restore(&transaction.id).await;
+        let expected: Result<Transaction, TransactionRepositoryError> = Err(TransactionRepositoryError::StoreNotFound);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
 }
@@ -1 +1,17 @@
 mod in_memory_cache;
+mod in_memory_snapshot;
+mod secondary_index;
+
+pub use in_memory_cache::BrandModel;
+pub use in_memory_cache::CategoryModel;
+pub use in_memory_cache::InMemoryCache;
+pub use in_memory_cache::IntegrityError;
+pub use in_memory_cache::ItemModel;
+pub use in_memory_cache::ProductModel;
+pub use in_memory_cache::StoreModel;
+pub use in_memory_cache::TransactionModel;
+pub use in_memory_cache::UnitModel;
+pub use in_memory_snapshot::InMemorySnapshot;
+pub use in_memory_snapshot::SNAPSHOT_FORMAT_VERSION;
+pub use in_memory_snapshot::SnapshotError;
+pub(crate) use secondary_index::SecondaryIndex;
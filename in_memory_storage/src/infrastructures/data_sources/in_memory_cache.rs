@@ -1,50 +1,107 @@
-use std::collections::HashMap;
-
-#[derive(Debug)]
-struct InMemoryCache {
-    stores: HashMap<String, StoreModel>,
-    brands: HashMap<String, BrandModel>,
-    categories: HashMap<String, CategoryModel>,
-    products: HashMap<String, ProductModel>,
-    items: HashMap<String, ItemModel>,
-    transactions: HashMap<String, TransactionModel>,
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::RangeBounds,
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use expense_tracking::domain::{entities::NamePolicy, repositories::DeleteBehaviour};
+
+use crate::infrastructures::data_sources::SecondaryIndex;
+
+/// Every entity of the in-memory storage, keyed and ordered by the string form of its key so that listings come out
+/// in the same order as from the other backends.
+///
+/// Names, the brand and the category of products, the store and the date of transactions, the product of items and the
+/// transactions holding each item are indexed as well. Every change goes through the methods below, which keep those indexes in step with the entities.
+/// Names are indexed once normalized by the cache's [`NamePolicy`], so lookups by name ignore case, spacing and, by
+/// default, accents.
+///
+/// Deleted entities stay where they are with their `deleted_at` set until they are purged, the way the SQLite storage
+/// keeps its rows. Listings and lookups by name skip them, lookups by key still find them so the trash can be listed
+/// and restored by every repository sharing the cache.
+#[derive(Debug, Default)]
+pub struct InMemoryCache {
+    stores: BTreeMap<String, StoreModel>,
+    brands: BTreeMap<String, BrandModel>,
+    categories: BTreeMap<String, CategoryModel>,
+    products: BTreeMap<String, ProductModel>,
+    items: BTreeMap<String, ItemModel>,
+    transactions: BTreeMap<String, TransactionModel>,
+    store_names: SecondaryIndex<String>,
+    brand_names: SecondaryIndex<String>,
+    category_names: SecondaryIndex<String>,
+    product_names: SecondaryIndex<String>,
+    products_by_brand: SecondaryIndex<String>,
+    products_by_category: SecondaryIndex<String>,
+    items_by_product: SecondaryIndex<String>,
+    transactions_by_item: SecondaryIndex<String>,
+    transactions_by_store: SecondaryIndex<String>,
+    transactions_by_date: SecondaryIndex<DateTime<Utc>>,
+    name_policy: NamePolicy,
 }
 
 impl InMemoryCache {
-    fn new() -> Self {
-        Self {
-            stores: HashMap::new(),
-            brands: HashMap::new(),
-            categories: HashMap::new(),
-            products: HashMap::new(),
-            items: HashMap::new(),
-            transactions: HashMap::new(),
-        }
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Must be set before anything is stored, since the names already indexed are not normalized again.
+    pub fn with_name_policy(self, name_policy: NamePolicy) -> Self {
+        Self { name_policy, ..self }
+    }
+
+    pub fn name_policy(&self) -> &NamePolicy {
+        &self.name_policy
+    }
+
+    pub fn get_all_stores(&self) -> impl Iterator<Item = &StoreModel> {
+        self.stores.values().filter(|s| s.deleted_at.is_none())
+    }
+
+    pub fn get_all_brands(&self) -> impl Iterator<Item = &BrandModel> {
+        self.brands.values().filter(|b| b.deleted_at.is_none())
+    }
+
+    pub fn get_all_categories(&self) -> impl Iterator<Item = &CategoryModel> {
+        self.categories.values().filter(|c| c.deleted_at.is_none())
+    }
+
+    pub fn get_all_products(&self) -> impl Iterator<Item = &ProductModel> {
+        self.products.values().filter(|p| p.deleted_at.is_none())
+    }
+
+    /// Every item, those of trashed transactions included.
+    pub fn get_all_items(&self) -> impl ExactSizeIterator<Item = &ItemModel> {
+        self.items.values()
     }
 
-    pub fn get_all_stores(&self) -> Vec<StoreModel> {
-        self.stores.clone().into_values().collect()
+    pub fn get_all_transactions(&self) -> impl Iterator<Item = &TransactionModel> {
+        self.transactions.values().filter(|t| t.deleted_at.is_none())
     }
 
-    pub fn get_all_brands(&self) -> Vec<BrandModel> {
-        self.brands.clone().into_values().collect()
+    pub fn get_trashed_stores(&self) -> impl Iterator<Item = &StoreModel> {
+        self.stores.values().filter(|s| s.deleted_at.is_some())
     }
 
-    pub fn get_all_categories(&self) -> Vec<CategoryModel> {
-        self.categories.clone().into_values().collect()
+    pub fn get_trashed_brands(&self) -> impl Iterator<Item = &BrandModel> {
+        self.brands.values().filter(|b| b.deleted_at.is_some())
     }
 
-    pub fn get_all_products(&self) -> Vec<ProductModel> {
-        self.products.clone().into_values().collect()
+    pub fn get_trashed_categories(&self) -> impl Iterator<Item = &CategoryModel> {
+        self.categories.values().filter(|c| c.deleted_at.is_some())
     }
 
-    pub fn get_all_items(&self) -> Vec<ItemModel> {
-        self.items.clone().into_values().collect()
+    pub fn get_trashed_products(&self) -> impl Iterator<Item = &ProductModel> {
+        self.products.values().filter(|p| p.deleted_at.is_some())
     }
-    pub fn get_all_transactions(&self) -> Vec<TransactionModel> {
-        self.transactions.clone().into_values().collect()
+
+    pub fn get_trashed_transactions(&self) -> impl Iterator<Item = &TransactionModel> {
+        self.transactions.values().filter(|t| t.deleted_at.is_some())
     }
 
+    /// Finds the store whether it is trashed or not, see its `deleted_at`.
     pub fn get_single_store(&self, key: &String) -> Option<StoreModel> {
         self.stores.get(key).cloned()
     }
@@ -69,96 +126,925 @@ impl InMemoryCache {
         self.transactions.get(key).cloned()
     }
 
+    pub fn get_stores_by_name(&self, name: &str) -> impl Iterator<Item = &StoreModel> {
+        self.store_names
+            .get(&self.name_policy.normalize(name))
+            .filter_map(|key| self.stores.get(key))
+            .filter(|s| s.deleted_at.is_none())
+    }
+
+    pub fn get_brands_by_name(&self, name: &str) -> impl Iterator<Item = &BrandModel> {
+        self.brand_names
+            .get(&self.name_policy.normalize(name))
+            .filter_map(|key| self.brands.get(key))
+            .filter(|b| b.deleted_at.is_none())
+    }
+
+    pub fn get_categories_by_name(&self, name: &str) -> impl Iterator<Item = &CategoryModel> {
+        self.category_names
+            .get(&self.name_policy.normalize(name))
+            .filter_map(|key| self.categories.get(key))
+            .filter(|c| c.deleted_at.is_none())
+    }
+
+    pub fn get_products_by_name(&self, name: &str) -> impl Iterator<Item = &ProductModel> {
+        self.product_names
+            .get(&self.name_policy.normalize(name))
+            .filter_map(|key| self.products.get(key))
+            .filter(|p| p.deleted_at.is_none())
+    }
+
+    pub fn get_products_by_brand(&self, brand_key: &str) -> impl Iterator<Item = &ProductModel> {
+        self.products_by_brand
+            .get(brand_key)
+            .filter_map(|key| self.products.get(key))
+            .filter(|p| p.deleted_at.is_none())
+    }
+
+    pub fn get_products_by_category(&self, category_key: &str) -> impl Iterator<Item = &ProductModel> {
+        self.products_by_category
+            .get(category_key)
+            .filter_map(|key| self.products.get(key))
+            .filter(|p| p.deleted_at.is_none())
+    }
+
+    pub fn get_items_by_product(&self, product_key: &str) -> impl Iterator<Item = &ItemModel> {
+        self.items_by_product.get(product_key).filter_map(|key| self.items.get(key))
+    }
+
+    pub fn get_transactions_by_store(&self, store_key: &str) -> impl Iterator<Item = &TransactionModel> {
+        self.transactions_by_store
+            .get(store_key)
+            .filter_map(|key| self.transactions.get(key))
+            .filter(|t| t.deleted_at.is_none())
+    }
+
+    /// Transactions dated within `range`, oldest first. Transactions whose date cannot be parsed are never listed.
+    pub fn get_transactions_between(&self, range: impl RangeBounds<DateTime<Utc>>) -> impl Iterator<Item = &TransactionModel> {
+        self.transactions_by_date
+            .range(range)
+            .filter_map(|key| self.transactions.get(key))
+            .filter(|t| t.deleted_at.is_none())
+    }
+
     pub fn upsert_store(&mut self, store: StoreModel) -> Option<StoreModel> {
-        self.stores.insert(store.key.clone(), store)
+        let previous: Option<StoreModel> = self.remove_store(&store.key);
+
+        self.store_names.insert(self.name_policy.normalize(&store.name), &store.key);
+        self.stores.insert(store.key.clone(), store);
+        previous
     }
 
     pub fn upsert_brand(&mut self, brand: BrandModel) -> Option<BrandModel> {
-        self.brands.insert(brand.key.clone(), brand)
+        let previous: Option<BrandModel> = self.remove_brand(&brand.key);
+
+        self.brand_names.insert(self.name_policy.normalize(&brand.name), &brand.key);
+        self.brands.insert(brand.key.clone(), brand);
+        previous
     }
 
     pub fn upsert_category(&mut self, category: CategoryModel) -> Option<CategoryModel> {
-        self.categories.insert(category.key.clone(), category)
+        let previous: Option<CategoryModel> = self.remove_category(&category.key);
+
+        self.category_names
+            .insert(self.name_policy.normalize(&category.name), &category.key);
+        self.categories.insert(category.key.clone(), category);
+        previous
     }
 
     pub fn upsert_product(&mut self, product: ProductModel) -> Option<ProductModel> {
-        self.products.insert(product.key.clone(), product)
+        let previous: Option<ProductModel> = self.remove_product(&product.key);
+
+        self.product_names.insert(self.name_policy.normalize(&product.name), &product.key);
+        self.products_by_brand.insert(product.brand_key.clone(), &product.key);
+        self.products_by_category.insert(product.category_key.clone(), &product.key);
+        self.products.insert(product.key.clone(), product);
+        previous
     }
 
     pub fn upsert_item(&mut self, item: ItemModel) -> Option<ItemModel> {
-        self.items.insert(item.key.clone(), item)
+        let previous: Option<ItemModel> = self.remove_item(&item.key);
+
+        self.items_by_product.insert(item.product_key.clone(), &item.key);
+        self.items.insert(item.key.clone(), item);
+        previous
     }
 
     pub fn upsert_transaction(&mut self, transaction: TransactionModel) -> Option<TransactionModel> {
-        self.transactions.insert(transaction.key.clone(), transaction)
+        let previous: Option<TransactionModel> = self.remove_transaction(&transaction.key);
+
+        transaction.item_keys.iter().for_each(|item_key| {
+            self.transactions_by_item.insert(item_key.clone(), &transaction.key);
+        });
+        self.transactions_by_store.insert(transaction.store_key.clone(), &transaction.key);
+        if let Some(datetime) = parse_datetime(&transaction.datetime) {
+            self.transactions_by_date.insert(datetime, &transaction.key);
+        }
+        self.transactions.insert(transaction.key.clone(), transaction);
+        previous
+    }
+
+    /// Whether another store, even a trashed one, bears the name of `store` under the name policy.
+    pub fn is_store_name_taken(&self, store: &StoreModel) -> bool {
+        self.store_names
+            .get(&self.name_policy.normalize(&store.name))
+            .any(|key| key != &store.key)
+    }
+
+    /// Whether another category, even a trashed one, bears the name of `category` under the name policy.
+    pub fn is_category_name_taken(&self, category: &CategoryModel) -> bool {
+        self.category_names
+            .get(&self.name_policy.normalize(&category.name))
+            .any(|key| key != &category.key)
+    }
+
+    /// Whether another product of the same brand, even a trashed one, bears the name of `product` under the name policy.
+    pub fn is_product_name_taken(&self, product: &ProductModel) -> bool {
+        self.product_names
+            .get(&self.name_policy.normalize(&product.name))
+            .filter_map(|key| self.products.get(key))
+            .any(|p| p.key != product.key && p.brand_key == product.brand_key)
+    }
+
+    /// Stores `brand` unless a brand with the same name, as normalized by the name policy, is already stored, even in
+    /// the trash. Checking and inserting happen under the same borrow, so two writers can never both create the brand.
+    pub fn insert_brand_if_absent(&mut self, brand: BrandModel) -> Result<(), IntegrityError> {
+        if let Some(existing) = self.brand_names.get(&self.name_policy.normalize(&brand.name)).next() {
+            return Err(IntegrityError::AlreadyExists(existing.clone()));
+        }
+
+        self.upsert_brand(brand);
+        Ok(())
+    }
+
+    pub fn insert_store_if_absent(&mut self, store: StoreModel) -> Result<(), IntegrityError> {
+        if self.stores.contains_key(&store.key) {
+            return Err(IntegrityError::AlreadyExists(store.key));
+        }
+
+        self.upsert_store(store);
+        Ok(())
+    }
+
+    pub fn insert_category_if_absent(&mut self, category: CategoryModel) -> Result<(), IntegrityError> {
+        if self.categories.contains_key(&category.key) {
+            return Err(IntegrityError::AlreadyExists(category.key));
+        }
+
+        self.upsert_category(category);
+        Ok(())
+    }
+
+    pub fn insert_product_if_absent(&mut self, product: ProductModel) -> Result<(), IntegrityError> {
+        if self.products.contains_key(&product.key) {
+            return Err(IntegrityError::AlreadyExists(product.key));
+        }
+
+        self.check_product_references(&product)?;
+        self.upsert_product(product);
+        Ok(())
+    }
+
+    /// A trashed product may refer to a trashed brand or category, any other product only to active ones.
+    pub fn check_product_references(&self, product: &ProductModel) -> Result<(), IntegrityError> {
+        let trashed: bool = product.deleted_at.is_some();
+
+        if !Self::resolves(&self.brands, &product.brand_key, trashed) {
+            return Err(IntegrityError::DanglingReference(product.brand_key.clone()));
+        }
+
+        if !Self::resolves(&self.categories, &product.category_key, trashed) {
+            return Err(IntegrityError::DanglingReference(product.category_key.clone()));
+        }
+
+        Ok(())
+    }
+
+    pub fn check_item_references(&self, item: &ItemModel) -> Result<(), IntegrityError> {
+        if !self.products.contains_key(&item.product_key) {
+            return Err(IntegrityError::DanglingReference(item.product_key.clone()));
+        }
+
+        Ok(())
+    }
+
+    /// A trashed transaction may refer to a trashed store or products, any other transaction only to active ones.
+    pub fn check_transaction_references(&self, transaction: &TransactionModel) -> Result<(), IntegrityError> {
+        let trashed: bool = transaction.deleted_at.is_some();
+
+        if !Self::resolves(&self.stores, &transaction.store_key, trashed) {
+            return Err(IntegrityError::DanglingReference(transaction.store_key.clone()));
+        }
+
+        transaction
+            .item_keys
+            .iter()
+            .try_for_each(|item_key| match self.items.get(item_key) {
+                None => Err(IntegrityError::DanglingReference(item_key.clone())),
+                Some(item) if !Self::resolves(&self.products, &item.product_key, trashed) => {
+                    Err(IntegrityError::DanglingReference(item.product_key.clone()))
+                }
+                Some(_) => Ok(()),
+            })
+    }
+
+    /// Checks the references of every entity, trashed ones included.
+    pub fn check_references(&self) -> Result<(), IntegrityError> {
+        self.products
+            .values()
+            .try_for_each(|product| self.check_product_references(product))?;
+        self.items.values().try_for_each(|item| self.check_item_references(item))?;
+        self.transactions
+            .values()
+            .try_for_each(|transaction| self.check_transaction_references(transaction))
+    }
+
+    /// Moves the brand to the trash. With [`DeleteBehaviour::Cascade`] its products and the transactions holding them
+    /// go along, stamped with the same `deleted_at` so that restoring the brand brings them back.
+    pub fn delete_brand(
+        &mut self,
+        key: &String,
+        behaviour: DeleteBehaviour<String>,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<BrandModel, IntegrityError> {
+        if !Self::resolves(&self.brands, key, false) {
+            return Err(IntegrityError::NotFound(key.clone()));
+        }
+
+        let referencing: Vec<String> = self.get_products_by_brand(key).map(|p| p.key.clone()).collect();
+
+        match behaviour {
+            DeleteBehaviour::Restrict if !referencing.is_empty() => return Err(IntegrityError::StillReferenced(referencing)),
+            DeleteBehaviour::Restrict => {}
+            DeleteBehaviour::Cascade => referencing
+                .iter()
+                .for_each(|product_key| self.trash_product_cascading(product_key, deleted_at)),
+            DeleteBehaviour::Reassign(replacement) => {
+                if &replacement == key || !Self::resolves(&self.brands, &replacement, false) {
+                    return Err(IntegrityError::ReplacementNotFound(replacement));
+                }
+
+                for product in self
+                    .products_by_brand
+                    .get(key.as_str())
+                    .filter_map(|product_key| self.products.get(product_key).cloned())
+                    .collect::<Vec<ProductModel>>()
+                {
+                    self.upsert_product(ProductModel {
+                        brand_key: replacement.clone(),
+                        ..product
+                    });
+                }
+            }
+        }
+
+        Self::trash(&mut self.brands, key, deleted_at).ok_or(IntegrityError::NotFound(key.clone()))
+    }
+
+    pub fn delete_category(
+        &mut self,
+        key: &String,
+        behaviour: DeleteBehaviour<String>,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<CategoryModel, IntegrityError> {
+        if !Self::resolves(&self.categories, key, false) {
+            return Err(IntegrityError::NotFound(key.clone()));
+        }
+
+        let referencing: Vec<String> = self.get_products_by_category(key).map(|p| p.key.clone()).collect();
+
+        match behaviour {
+            DeleteBehaviour::Restrict if !referencing.is_empty() => return Err(IntegrityError::StillReferenced(referencing)),
+            DeleteBehaviour::Restrict => {}
+            DeleteBehaviour::Cascade => referencing
+                .iter()
+                .for_each(|product_key| self.trash_product_cascading(product_key, deleted_at)),
+            DeleteBehaviour::Reassign(replacement) => {
+                if &replacement == key || !Self::resolves(&self.categories, &replacement, false) {
+                    return Err(IntegrityError::ReplacementNotFound(replacement));
+                }
+
+                for product in self
+                    .products_by_category
+                    .get(key.as_str())
+                    .filter_map(|product_key| self.products.get(product_key).cloned())
+                    .collect::<Vec<ProductModel>>()
+                {
+                    self.upsert_product(ProductModel {
+                        category_key: replacement.clone(),
+                        ..product
+                    });
+                }
+            }
+        }
+
+        Self::trash(&mut self.categories, key, deleted_at).ok_or(IntegrityError::NotFound(key.clone()))
+    }
+
+    /// Moves the product to the trash, referenced as long as an active transaction holds one of its items.
+    pub fn delete_product(
+        &mut self,
+        key: &String,
+        behaviour: DeleteBehaviour<String>,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<ProductModel, IntegrityError> {
+        if !Self::resolves(&self.products, key, false) {
+            return Err(IntegrityError::NotFound(key.clone()));
+        }
+
+        let referencing: Vec<String> = self
+            .items_by_product
+            .get(key.as_str())
+            .filter(|item_key| {
+                self.transactions_by_item
+                    .get(item_key.as_str())
+                    .any(|transaction_key| Self::resolves(&self.transactions, transaction_key, false))
+            })
+            .cloned()
+            .collect();
+
+        match behaviour {
+            DeleteBehaviour::Restrict if !referencing.is_empty() => return Err(IntegrityError::StillReferenced(referencing)),
+            DeleteBehaviour::Restrict => {}
+            DeleteBehaviour::Cascade => self.trash_transactions_holding(key, deleted_at),
+            DeleteBehaviour::Reassign(replacement) => {
+                if &replacement == key || !Self::resolves(&self.products, &replacement, false) {
+                    return Err(IntegrityError::ReplacementNotFound(replacement));
+                }
+
+                for item in self.get_items_by_product(key).cloned().collect::<Vec<ItemModel>>() {
+                    self.upsert_item(ItemModel {
+                        product_key: replacement.clone(),
+                        ..item
+                    });
+                }
+            }
+        }
+
+        Self::trash(&mut self.products, key, deleted_at).ok_or(IntegrityError::NotFound(key.clone()))
+    }
+
+    pub fn delete_store(
+        &mut self,
+        key: &String,
+        behaviour: DeleteBehaviour<String>,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<StoreModel, IntegrityError> {
+        if !Self::resolves(&self.stores, key, false) {
+            return Err(IntegrityError::NotFound(key.clone()));
+        }
+
+        let referencing: Vec<String> = self.get_transactions_by_store(key).map(|t| t.key.clone()).collect();
+
+        match behaviour {
+            DeleteBehaviour::Restrict if !referencing.is_empty() => return Err(IntegrityError::StillReferenced(referencing)),
+            DeleteBehaviour::Restrict => {}
+            DeleteBehaviour::Cascade => referencing.iter().for_each(|transaction_key| {
+                Self::trash(&mut self.transactions, transaction_key, deleted_at);
+            }),
+            DeleteBehaviour::Reassign(replacement) => {
+                if &replacement == key || !Self::resolves(&self.stores, &replacement, false) {
+                    return Err(IntegrityError::ReplacementNotFound(replacement));
+                }
+
+                for transaction in self
+                    .transactions_by_store
+                    .get(key.as_str())
+                    .filter_map(|transaction_key| self.transactions.get(transaction_key).cloned())
+                    .collect::<Vec<TransactionModel>>()
+                {
+                    self.upsert_transaction(TransactionModel {
+                        store_key: replacement.clone(),
+                        ..transaction
+                    });
+                }
+            }
+        }
+
+        Self::trash(&mut self.stores, key, deleted_at).ok_or(IntegrityError::NotFound(key.clone()))
+    }
+
+    /// Moves the transaction to the trash, its items stay with it.
+    pub fn delete_transaction(&mut self, key: &String, deleted_at: DateTime<Utc>) -> Result<TransactionModel, IntegrityError> {
+        Self::trash(&mut self.transactions, key, deleted_at).ok_or(IntegrityError::NotFound(key.clone()))
+    }
+
+    /// Takes the brand out of the trash, along with the products and transactions trashed with it by a cascading
+    /// delete. Fails with [`IntegrityError::NotFound`] when the brand is not in the trash.
+    pub fn restore_brand(&mut self, key: &String) -> Result<BrandModel, IntegrityError> {
+        let restored: BrandModel = Self::restore(&mut self.brands, key).ok_or(IntegrityError::NotFound(key.clone()))?;
+        let deleted_at: Option<DateTime<Utc>> = restored.deleted_at;
+
+        let products: Vec<String> = self
+            .products_by_brand
+            .get(key.as_str())
+            .filter(|product_key| {
+                self.products
+                    .get(*product_key)
+                    .is_some_and(|p| p.deleted_at.is_some() && p.deleted_at == deleted_at)
+            })
+            .cloned()
+            .collect();
+        products
+            .iter()
+            .for_each(|product_key| self.restore_product_cascading(product_key, deleted_at));
+
+        Ok(BrandModel {
+            deleted_at: None,
+            ..restored
+        })
+    }
+
+    pub fn restore_category(&mut self, key: &String) -> Result<CategoryModel, IntegrityError> {
+        let restored: CategoryModel = Self::restore(&mut self.categories, key).ok_or(IntegrityError::NotFound(key.clone()))?;
+        let deleted_at: Option<DateTime<Utc>> = restored.deleted_at;
+
+        let products: Vec<String> = self
+            .products_by_category
+            .get(key.as_str())
+            .filter(|product_key| {
+                self.products
+                    .get(*product_key)
+                    .is_some_and(|p| p.deleted_at.is_some() && p.deleted_at == deleted_at)
+            })
+            .cloned()
+            .collect();
+        products
+            .iter()
+            .for_each(|product_key| self.restore_product_cascading(product_key, deleted_at));
+
+        Ok(CategoryModel {
+            deleted_at: None,
+            ..restored
+        })
+    }
+
+    /// Fails with [`IntegrityError::DanglingReference`] while the brand or the category of the product is trashed.
+    pub fn restore_product(&mut self, key: &String) -> Result<ProductModel, IntegrityError> {
+        let trashed: ProductModel = self
+            .products
+            .get(key)
+            .filter(|p| p.deleted_at.is_some())
+            .cloned()
+            .ok_or(IntegrityError::NotFound(key.clone()))?;
+
+        self.check_product_references(&ProductModel {
+            deleted_at: None,
+            ..trashed.clone()
+        })?;
+        self.restore_product_cascading(key, trashed.deleted_at);
+
+        Ok(ProductModel {
+            deleted_at: None,
+            ..trashed
+        })
+    }
+
+    pub fn restore_store(&mut self, key: &String) -> Result<StoreModel, IntegrityError> {
+        let restored: StoreModel = Self::restore(&mut self.stores, key).ok_or(IntegrityError::NotFound(key.clone()))?;
+
+        let transactions: Vec<String> = self.transactions_by_store.get(key.as_str()).cloned().collect();
+        self.restore_transactions(&transactions, restored.deleted_at);
+
+        Ok(StoreModel {
+            deleted_at: None,
+            ..restored
+        })
+    }
+
+    /// Fails with [`IntegrityError::DanglingReference`] while the store or a product of the transaction is trashed.
+    pub fn restore_transaction(&mut self, key: &String) -> Result<TransactionModel, IntegrityError> {
+        let trashed: TransactionModel = self
+            .transactions
+            .get(key)
+            .filter(|t| t.deleted_at.is_some())
+            .cloned()
+            .ok_or(IntegrityError::NotFound(key.clone()))?;
+
+        self.check_transaction_references(&TransactionModel {
+            deleted_at: None,
+            ..trashed.clone()
+        })?;
+        Self::restore(&mut self.transactions, key);
+
+        Ok(TransactionModel {
+            deleted_at: None,
+            ..trashed
+        })
+    }
+
+    /// Removes for good the brands trashed before `cutoff`, along with the trashed products referring to them.
+    pub fn purge_brands(&mut self, cutoff: DateTime<Utc>) -> Vec<BrandModel> {
+        Self::expired(&self.brands, cutoff)
+            .iter()
+            .filter_map(|key| {
+                let products: Vec<String> = self.products_by_brand.get(key.as_str()).cloned().collect();
+                products.iter().for_each(|product_key| self.remove_product_purging(product_key));
+                self.remove_brand(key)
+            })
+            .collect()
+    }
+
+    pub fn purge_categories(&mut self, cutoff: DateTime<Utc>) -> Vec<CategoryModel> {
+        Self::expired(&self.categories, cutoff)
+            .iter()
+            .filter_map(|key| {
+                let products: Vec<String> = self.products_by_category.get(key.as_str()).cloned().collect();
+                products.iter().for_each(|product_key| self.remove_product_purging(product_key));
+                self.remove_category(key)
+            })
+            .collect()
+    }
+
+    pub fn purge_products(&mut self, cutoff: DateTime<Utc>) -> Vec<ProductModel> {
+        Self::expired(&self.products, cutoff)
+            .iter()
+            .filter_map(|key| {
+                let product: Option<ProductModel> = self.get_single_product(key);
+                self.remove_product_purging(key);
+                product
+            })
+            .collect()
+    }
+
+    pub fn purge_stores(&mut self, cutoff: DateTime<Utc>) -> Vec<StoreModel> {
+        Self::expired(&self.stores, cutoff)
+            .iter()
+            .filter_map(|key| {
+                let transactions: Vec<String> = self.transactions_by_store.get(key.as_str()).cloned().collect();
+                transactions.iter().for_each(|transaction_key| {
+                    self.remove_transaction_cascading(transaction_key);
+                });
+                self.remove_store(key)
+            })
+            .collect()
+    }
+
+    pub fn purge_transactions(&mut self, cutoff: DateTime<Utc>) -> Vec<TransactionModel> {
+        Self::expired(&self.transactions, cutoff)
+            .iter()
+            .filter_map(|key| self.remove_transaction_cascading(key))
+            .collect()
+    }
+
+    pub fn replace_transaction(
+        &mut self,
+        transaction: TransactionModel,
+        items: Vec<ItemModel>,
+    ) -> Result<Option<TransactionModel>, IntegrityError> {
+        if !Self::resolves(&self.stores, &transaction.store_key, false) {
+            return Err(IntegrityError::DanglingReference(transaction.store_key.clone()));
+        }
+
+        if let Some(item) = items.iter().find(|i| !Self::resolves(&self.products, &i.product_key, false)) {
+            return Err(IntegrityError::DanglingReference(item.product_key.clone()));
+        }
+
+        if let Some(missing) = transaction.item_keys.iter().find(|k| !items.iter().any(|i| &i.key == *k)) {
+            return Err(IntegrityError::DanglingReference(missing.clone()));
+        }
+
+        if let Some(previous) = self.transactions.get(&transaction.key) {
+            let stale: Vec<String> = previous
+                .item_keys
+                .iter()
+                .filter(|k| !transaction.item_keys.contains(k))
+                .cloned()
+                .collect();
+
+            stale.iter().for_each(|item_key| {
+                self.remove_item(item_key);
+            });
+        }
+
+        items.into_iter().for_each(|item| {
+            self.upsert_item(item);
+        });
+
+        Ok(self.upsert_transaction(transaction))
+    }
+
+    fn remove_store(&mut self, key: &String) -> Option<StoreModel> {
+        let store: StoreModel = self.stores.remove(key)?;
+
+        self.store_names.remove(&self.name_policy.normalize(&store.name), &store.key);
+        Some(store)
+    }
+
+    fn remove_brand(&mut self, key: &String) -> Option<BrandModel> {
+        let brand: BrandModel = self.brands.remove(key)?;
+
+        self.brand_names.remove(&self.name_policy.normalize(&brand.name), &brand.key);
+        Some(brand)
+    }
+
+    fn remove_category(&mut self, key: &String) -> Option<CategoryModel> {
+        let category: CategoryModel = self.categories.remove(key)?;
+
+        self.category_names
+            .remove(&self.name_policy.normalize(&category.name), &category.key);
+        Some(category)
+    }
+
+    fn remove_product(&mut self, key: &String) -> Option<ProductModel> {
+        let product: ProductModel = self.products.remove(key)?;
+
+        self.product_names.remove(&self.name_policy.normalize(&product.name), &product.key);
+        self.products_by_brand.remove(&product.brand_key, &product.key);
+        self.products_by_category.remove(&product.category_key, &product.key);
+        Some(product)
+    }
+
+    fn remove_item(&mut self, key: &String) -> Option<ItemModel> {
+        let item: ItemModel = self.items.remove(key)?;
+
+        self.items_by_product.remove(&item.product_key, &item.key);
+        Some(item)
+    }
+
+    fn remove_transaction(&mut self, key: &String) -> Option<TransactionModel> {
+        let transaction: TransactionModel = self.transactions.remove(key)?;
+
+        transaction.item_keys.iter().for_each(|item_key| {
+            self.transactions_by_item.remove(item_key, &transaction.key);
+        });
+        self.transactions_by_store.remove(&transaction.store_key, &transaction.key);
+        if let Some(datetime) = parse_datetime(&transaction.datetime) {
+            self.transactions_by_date.remove(&datetime, &transaction.key);
+        }
+        Some(transaction)
+    }
+
+    fn remove_transaction_cascading(&mut self, key: &String) -> Option<TransactionModel> {
+        let transaction: TransactionModel = self.remove_transaction(key)?;
+
+        transaction.item_keys.iter().for_each(|item_key| {
+            self.remove_item(item_key);
+        });
+
+        Some(transaction)
+    }
+
+    fn remove_product_purging(&mut self, key: &String) {
+        self.transactions_holding(key).iter().for_each(|transaction_key| {
+            self.remove_transaction_cascading(transaction_key);
+        });
+
+        let item_keys: Vec<String> = self.items_by_product.get(key.as_str()).cloned().collect();
+        item_keys.iter().for_each(|item_key| {
+            self.remove_item(item_key);
+        });
+
+        self.remove_product(key);
+    }
+
+    fn trash_product_cascading(&mut self, key: &String, deleted_at: DateTime<Utc>) {
+        self.trash_transactions_holding(key, deleted_at);
+        Self::trash(&mut self.products, key, deleted_at);
+    }
+
+    fn trash_transactions_holding(&mut self, product_key: &str, deleted_at: DateTime<Utc>) {
+        self.transactions_holding(product_key).iter().for_each(|transaction_key| {
+            Self::trash(&mut self.transactions, transaction_key, deleted_at);
+        });
+    }
+
+    /// Restores the product unless its brand or category is still trashed, then the transactions holding it that
+    /// were trashed at the same time.
+    fn restore_product_cascading(&mut self, key: &String, deleted_at: Option<DateTime<Utc>>) {
+        let restorable: bool = self
+            .products
+            .get(key)
+            .is_some_and(|p| Self::resolves(&self.brands, &p.brand_key, false) && Self::resolves(&self.categories, &p.category_key, false));
+
+        if restorable {
+            Self::restore(&mut self.products, key);
+
+            let transactions: Vec<String> = self.transactions_holding(key);
+            self.restore_transactions(&transactions, deleted_at);
+        }
     }
+
+    /// Restores those of `keys` trashed at `deleted_at` whose store and products are all active again.
+    fn restore_transactions(&mut self, keys: &[String], deleted_at: Option<DateTime<Utc>>) {
+        let restorable: Vec<String> = keys
+            .iter()
+            .filter_map(|key| self.transactions.get(key))
+            .filter(|t| t.deleted_at.is_some() && t.deleted_at == deleted_at)
+            .filter(|t| {
+                self.check_transaction_references(&TransactionModel {
+                    deleted_at: None,
+                    ..(*t).clone()
+                })
+                .is_ok()
+            })
+            .map(|t| t.key.clone())
+            .collect();
+
+        restorable.iter().for_each(|key| {
+            Self::restore(&mut self.transactions, key);
+        });
+    }
+
+    /// Keys of the transactions, trashed or not, holding an item of the product.
+    fn transactions_holding(&self, product_key: &str) -> Vec<String> {
+        self.items_by_product
+            .get(product_key)
+            .flat_map(|item_key| self.transactions_by_item.get(item_key.as_str()))
+            .cloned()
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Whether `key` designates an active entity of `models`, or a trashed one as well when `include_trashed` is set.
+    fn resolves<M: Trashable>(models: &BTreeMap<String, M>, key: &String, include_trashed: bool) -> bool {
+        models.get(key).is_some_and(|m| include_trashed || m.deleted_at().is_none())
+    }
+
+    /// Stamps the active entity with `deleted_at` and returns it, `None` when there is no such active entity.
+    fn trash<M: Trashable + Clone>(models: &mut BTreeMap<String, M>, key: &String, deleted_at: DateTime<Utc>) -> Option<M> {
+        let model: &mut M = models.get_mut(key).filter(|m| m.deleted_at().is_none())?;
+
+        model.set_deleted_at(Some(deleted_at));
+        Some(model.clone())
+    }
+
+    /// Clears the stamp of the trashed entity and returns it as it was in the trash.
+    fn restore<M: Trashable + Clone>(models: &mut BTreeMap<String, M>, key: &String) -> Option<M> {
+        let model: &mut M = models.get_mut(key).filter(|m| m.deleted_at().is_some())?;
+        let trashed: M = model.clone();
+
+        model.set_deleted_at(None);
+        Some(trashed)
+    }
+
+    fn expired<M: Trashable>(models: &BTreeMap<String, M>, cutoff: DateTime<Utc>) -> Vec<String> {
+        models
+            .iter()
+            .filter(|(_, m)| m.deleted_at().is_some_and(|deleted_at| deleted_at < cutoff))
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+}
+
+fn parse_datetime(datetime: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(datetime)
+        .ok()
+        .map(|datetime| datetime.with_timezone(&Utc))
+}
+
+/// The models which can be moved to the trash.
+trait Trashable {
+    fn deleted_at(&self) -> Option<DateTime<Utc>>;
+
+    fn set_deleted_at(&mut self, deleted_at: Option<DateTime<Utc>>);
 }
 
 #[derive(Debug, PartialEq, Clone)]
-struct StoreModel {
-    key: String,
-    name: String,
+pub enum IntegrityError {
+    NotFound(String),
+    AlreadyExists(String),
+    StillReferenced(Vec<String>),
+    ReplacementNotFound(String),
+    DanglingReference(String),
+    MalformedValue(String),
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct StoreModel {
+    pub key: String,
+    pub name: String,
+    pub version: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl StoreModel {
-    fn new(key: String, name: String) -> Self {
-        Self { key, name }
+    pub fn new(key: String, name: String) -> Self {
+        Self {
+            key,
+            name,
+            version: 0,
+            deleted_at: None,
+        }
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
-struct BrandModel {
-    key: String,
-    name: String,
+impl Trashable for StoreModel {
+    fn deleted_at(&self) -> Option<DateTime<Utc>> {
+        self.deleted_at
+    }
+
+    fn set_deleted_at(&mut self, deleted_at: Option<DateTime<Utc>>) {
+        self.deleted_at = deleted_at;
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BrandModel {
+    pub key: String,
+    pub name: String,
+    pub version: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl BrandModel {
-    fn new(key: String, name: String) -> Self {
-        Self { key, name }
+    pub fn new(key: String, name: String) -> Self {
+        Self {
+            key,
+            name,
+            version: 0,
+            deleted_at: None,
+        }
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
-struct CategoryModel {
-    key: String,
-    name: String,
+impl Trashable for BrandModel {
+    fn deleted_at(&self) -> Option<DateTime<Utc>> {
+        self.deleted_at
+    }
+
+    fn set_deleted_at(&mut self, deleted_at: Option<DateTime<Utc>>) {
+        self.deleted_at = deleted_at;
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct CategoryModel {
+    pub key: String,
+    pub name: String,
+    pub version: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl CategoryModel {
-    fn new(new: String, to_string: String) -> Self {
-        Self { key: new, name: to_string }
+    pub fn new(key: String, name: String) -> Self {
+        Self {
+            key,
+            name,
+            version: 0,
+            deleted_at: None,
+        }
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
-struct ProductModel {
-    key: String,
-    name: String,
-    brand_key: String,
-    category_key: String,
+impl Trashable for CategoryModel {
+    fn deleted_at(&self) -> Option<DateTime<Utc>> {
+        self.deleted_at
+    }
+
+    fn set_deleted_at(&mut self, deleted_at: Option<DateTime<Utc>>) {
+        self.deleted_at = deleted_at;
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ProductModel {
+    pub key: String,
+    pub name: String,
+    pub brand_key: String,
+    pub category_key: String,
+    pub version: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl ProductModel {
-    fn new(key: String, name: String, brand_key: String, category_key: String) -> Self {
+    pub fn new(key: String, name: String, brand_key: String, category_key: String) -> Self {
         Self {
             key,
             name,
             brand_key,
             category_key,
+            version: 0,
+            deleted_at: None,
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl Trashable for ProductModel {
+    fn deleted_at(&self) -> Option<DateTime<Utc>> {
+        self.deleted_at
+    }
+
+    fn set_deleted_at(&mut self, deleted_at: Option<DateTime<Utc>>) {
+        self.deleted_at = deleted_at;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ItemModel {
-    key: String,
-    product_key: String,
-    unit: UnitModel,
-    unitary_price: f64,
+    pub key: String,
+    pub product_key: String,
+    pub unit: UnitModel,
+    pub unitary_price: f64,
 }
 
 impl ItemModel {
-    fn new(key: String, product_key: String, unit: UnitModel, unitary_price: f64) -> Self {
+    pub fn new(key: String, product_key: String, unit: UnitModel, unitary_price: f64) -> Self {
         Self {
             key,
             product_key,
@@ -168,38 +1054,58 @@ impl ItemModel {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-enum UnitModel {
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "amount", rename_all = "snake_case")]
+pub enum UnitModel {
     None,
     Quantity(f64),
     Kilograms(f64),
     Liters(f64),
 }
 
-#[derive(Debug, Clone, PartialEq)]
-struct TransactionModel {
-    key: String,
-    item_keys: Vec<String>,
-    store_key: String,
-    datetime: String,
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionModel {
+    pub key: String,
+    pub item_keys: Vec<String>,
+    pub store_key: String,
+    pub datetime: String,
+    pub version: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl TransactionModel {
-    fn new(key: String, item_keys: Vec<String>, store_key: String, datetime: String) -> Self {
+    pub fn new(key: String, item_keys: Vec<String>, store_key: String, datetime: String) -> Self {
         Self {
             key,
             item_keys,
             store_key,
             datetime,
+            version: 0,
+            deleted_at: None,
         }
     }
 }
 
+impl Trashable for TransactionModel {
+    fn deleted_at(&self) -> Option<DateTime<Utc>> {
+        self.deleted_at
+    }
+
+    fn set_deleted_at(&mut self, deleted_at: Option<DateTime<Utc>>) {
+        self.deleted_at = deleted_at;
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
+    use chrono::{DateTime, TimeZone, Utc};
+
+    use expense_tracking::domain::{entities::NamePolicy, repositories::DeleteBehaviour};
+
     use crate::infrastructures::data_sources::in_memory_cache::{
-        BrandModel, CategoryModel, ItemModel, ProductModel, TransactionModel, UnitModel,
+        BrandModel, CategoryModel, IntegrityError, ItemModel, ProductModel, TransactionModel, UnitModel,
     };
 
     use super::{InMemoryCache, StoreModel};
@@ -208,10 +1114,10 @@ mod tests {
     fn new_cache_no_data() {
         let cache: InMemoryCache = InMemoryCache::new();
 
-        assert!(cache.get_all_stores().is_empty());
-        assert!(cache.get_all_brands().is_empty());
-        assert!(cache.get_all_categories().is_empty());
-        assert!(cache.get_all_products().is_empty());
+        assert!(cache.get_all_stores().next().is_none());
+        assert!(cache.get_all_brands().next().is_none());
+        assert!(cache.get_all_categories().next().is_none());
+        assert!(cache.get_all_products().next().is_none());
     }
 
     #[test]
@@ -221,9 +1127,9 @@ mod tests {
 
         assert_eq!(cache.upsert_store(store_model.clone()), None);
 
-        let all_stores: Vec<StoreModel> = cache.get_all_stores();
+        let all_stores: Vec<&StoreModel> = cache.get_all_stores().collect();
         assert_eq!(all_stores.len(), 1);
-        assert!(all_stores.contains(&store_model));
+        assert!(all_stores.contains(&&store_model));
     }
 
     #[test]
@@ -237,9 +1143,9 @@ mod tests {
         assert_eq!(cache.upsert_store(existing_store.clone()), None);
         assert_eq!(cache.upsert_store(updated_store.clone()), Some(existing_store.clone()));
 
-        let all_stores: Vec<StoreModel> = cache.get_all_stores();
+        let all_stores: Vec<&StoreModel> = cache.get_all_stores().collect();
         assert_eq!(all_stores.len(), 1);
-        assert!(all_stores.contains(&updated_store));
+        assert!(all_stores.contains(&&updated_store));
     }
 
     #[test]
@@ -250,17 +1156,17 @@ mod tests {
         let store_2: StoreModel = StoreModel::new("487DACC8-C56D-49C6-A7CE-C82E0300AB6F".to_string(), "Some Other Store".to_string());
         let store_3: StoreModel = StoreModel::new("E02D350D-280D-403C-8A4B-57220A2EF84A".to_string(), "Yet Another Store".to_string());
 
-        assert!(cache.get_all_stores().is_empty());
+        assert!(cache.get_all_stores().next().is_none());
 
         assert_eq!(cache.upsert_store(store_1.clone()), None);
         assert_eq!(cache.upsert_store(store_2.clone()), None);
         assert_eq!(cache.upsert_store(store_3.clone()), None);
 
-        let stores: Vec<StoreModel> = cache.get_all_stores();
+        let stores: Vec<&StoreModel> = cache.get_all_stores().collect();
         assert_eq!(stores.len(), 3);
-        assert!(stores.contains(&store_1));
-        assert!(stores.contains(&store_2));
-        assert!(stores.contains(&store_3));
+        assert!(stores.contains(&&store_1));
+        assert!(stores.contains(&&store_2));
+        assert!(stores.contains(&&store_3));
     }
 
     #[test]
@@ -303,9 +1209,9 @@ mod tests {
 
         assert_eq!(cache.upsert_brand(brand_model.clone()), None);
 
-        let all_brands: Vec<BrandModel> = cache.get_all_brands();
+        let all_brands: Vec<&BrandModel> = cache.get_all_brands().collect();
         assert_eq!(all_brands.len(), 1);
-        assert!(all_brands.contains(&brand_model));
+        assert!(all_brands.contains(&&brand_model));
     }
 
     #[test]
@@ -319,9 +1225,9 @@ mod tests {
         assert_eq!(cache.upsert_brand(existing_brand.clone()), None);
         assert_eq!(cache.upsert_brand(updated_brand.clone()), Some(existing_brand.clone()));
 
-        let all_brands: Vec<BrandModel> = cache.get_all_brands();
+        let all_brands: Vec<&BrandModel> = cache.get_all_brands().collect();
         assert_eq!(all_brands.len(), 1);
-        assert!(all_brands.contains(&updated_brand));
+        assert!(all_brands.contains(&&updated_brand));
     }
 
     #[test]
@@ -332,17 +1238,17 @@ mod tests {
         let brand_2: BrandModel = BrandModel::new("A12E6B36-2D05-4B83-A42E-B3A6DE1D1417".to_string(), "Some Other Brand".to_string());
         let brand_3: BrandModel = BrandModel::new("D2F5A1BA-D902-4405-A4A6-3708575AA2AA".to_string(), "Yet Another Brand".to_string());
 
-        assert!(cache.get_all_brands().is_empty());
+        assert!(cache.get_all_brands().next().is_none());
 
         assert_eq!(cache.upsert_brand(brand_1.clone()), None);
         assert_eq!(cache.upsert_brand(brand_2.clone()), None);
         assert_eq!(cache.upsert_brand(brand_3.clone()), None);
 
-        let brands: Vec<BrandModel> = cache.get_all_brands();
+        let brands: Vec<&BrandModel> = cache.get_all_brands().collect();
         assert_eq!(brands.len(), 3);
-        assert!(brands.contains(&brand_1));
-        assert!(brands.contains(&brand_2));
-        assert!(brands.contains(&brand_3));
+        assert!(brands.contains(&&brand_1));
+        assert!(brands.contains(&&brand_2));
+        assert!(brands.contains(&&brand_3));
     }
 
     #[test]
@@ -386,9 +1292,9 @@ mod tests {
 
         assert_eq!(cache.upsert_category(category_model.clone()), None);
 
-        let all_categories: Vec<CategoryModel> = cache.get_all_categories();
+        let all_categories: Vec<&CategoryModel> = cache.get_all_categories().collect();
         assert_eq!(all_categories.len(), 1);
-        assert!(all_categories.contains(&category_model));
+        assert!(all_categories.contains(&&category_model));
     }
 
     #[test]
@@ -402,9 +1308,9 @@ mod tests {
         assert_eq!(cache.upsert_category(existing_category.clone()), None);
         assert_eq!(cache.upsert_category(updated_category.clone()), Some(existing_category.clone()));
 
-        let all_categories: Vec<CategoryModel> = cache.get_all_categories();
+        let all_categories: Vec<&CategoryModel> = cache.get_all_categories().collect();
         assert_eq!(all_categories.len(), 1);
-        assert!(all_categories.contains(&updated_category));
+        assert!(all_categories.contains(&&updated_category));
     }
 
     #[test]
@@ -421,17 +1327,17 @@ mod tests {
             "Yet Another Category".to_string(),
         );
 
-        assert!(cache.get_all_categories().is_empty());
+        assert!(cache.get_all_categories().next().is_none());
 
         assert_eq!(cache.upsert_category(category_1.clone()), None);
         assert_eq!(cache.upsert_category(category_2.clone()), None);
         assert_eq!(cache.upsert_category(category_3.clone()), None);
 
-        let categories: Vec<CategoryModel> = cache.get_all_categories();
+        let categories: Vec<&CategoryModel> = cache.get_all_categories().collect();
         assert_eq!(categories.len(), 3);
-        assert!(categories.contains(&category_1));
-        assert!(categories.contains(&category_2));
-        assert!(categories.contains(&category_3));
+        assert!(categories.contains(&&category_1));
+        assert!(categories.contains(&&category_2));
+        assert!(categories.contains(&&category_3));
     }
 
     #[test]
@@ -491,9 +1397,9 @@ mod tests {
 
         assert_eq!(cache.upsert_product(product_model.clone()), None);
 
-        let all_products: Vec<ProductModel> = cache.get_all_products();
+        let all_products: Vec<&ProductModel> = cache.get_all_products().collect();
         assert_eq!(all_products.len(), 1);
-        assert!(all_products.contains(&product_model));
+        assert!(all_products.contains(&&product_model));
     }
 
     #[test]
@@ -516,9 +1422,9 @@ mod tests {
         assert_eq!(cache.upsert_product(existing_product.clone()), None);
         assert_eq!(cache.upsert_product(updated_product.clone()), Some(existing_product.clone()));
 
-        let all_products: Vec<ProductModel> = cache.get_all_products();
+        let all_products: Vec<&ProductModel> = cache.get_all_products().collect();
         assert_eq!(all_products.len(), 1);
-        assert!(all_products.contains(&updated_product));
+        assert!(all_products.contains(&&updated_product));
     }
 
     #[test]
@@ -544,17 +1450,17 @@ mod tests {
             "0C124495-284C-43CD-B339-1A5973B8BAE7".to_string(),
         );
 
-        assert!(cache.get_all_products().is_empty());
+        assert!(cache.get_all_products().next().is_none());
 
         assert_eq!(cache.upsert_product(product_1.clone()), None);
         assert_eq!(cache.upsert_product(product_2.clone()), None);
         assert_eq!(cache.upsert_product(product_3.clone()), None);
 
-        let products: Vec<ProductModel> = cache.get_all_products();
+        let products: Vec<&ProductModel> = cache.get_all_products().collect();
         assert_eq!(products.len(), 3);
-        assert!(products.contains(&product_1));
-        assert!(products.contains(&product_2));
-        assert!(products.contains(&product_3));
+        assert!(products.contains(&&product_1));
+        assert!(products.contains(&&product_2));
+        assert!(products.contains(&&product_3));
     }
 
     #[test]
@@ -632,9 +1538,9 @@ mod tests {
 
         assert_eq!(cache.upsert_item(item_model.clone()), None);
 
-        let all_items: Vec<ItemModel> = cache.get_all_items();
+        let all_items: Vec<&ItemModel> = cache.get_all_items().collect();
         assert_eq!(all_items.len(), 1);
-        assert!(all_items.contains(&item_model));
+        assert!(all_items.contains(&&item_model));
     }
 
     #[test]
@@ -658,9 +1564,9 @@ mod tests {
         assert_eq!(cache.upsert_item(existing_item.clone()), None);
         assert_eq!(cache.upsert_item(updated_item.clone()), Some(existing_item.clone()));
 
-        let all_items: Vec<ItemModel> = cache.get_all_items();
+        let all_items: Vec<&ItemModel> = cache.get_all_items().collect();
         assert_eq!(all_items.len(), 1);
-        assert!(all_items.contains(&updated_item));
+        assert!(all_items.contains(&&updated_item));
     }
 
     #[test]
@@ -686,17 +1592,17 @@ mod tests {
             40.56,
         );
 
-        assert!(cache.get_all_items().is_empty());
+        assert!(cache.get_all_items().next().is_none());
 
         assert_eq!(cache.upsert_item(item_1.clone()), None);
         assert_eq!(cache.upsert_item(item_2.clone()), None);
         assert_eq!(cache.upsert_item(item_3.clone()), None);
 
-        let items: Vec<ItemModel> = cache.get_all_items();
+        let items: Vec<&ItemModel> = cache.get_all_items().collect();
         assert_eq!(items.len(), 3);
-        assert!(items.contains(&item_1));
-        assert!(items.contains(&item_2));
-        assert!(items.contains(&item_3));
+        assert!(items.contains(&&item_1));
+        assert!(items.contains(&&item_2));
+        assert!(items.contains(&&item_3));
     }
 
     #[test]
@@ -774,9 +1680,9 @@ mod tests {
 
         assert_eq!(cache.upsert_transaction(transaction_model.clone()), None);
 
-        let all_transactions: Vec<TransactionModel> = cache.get_all_transactions();
+        let all_transactions: Vec<&TransactionModel> = cache.get_all_transactions().collect();
         assert_eq!(all_transactions.len(), 1);
-        assert!(all_transactions.contains(&transaction_model));
+        assert!(all_transactions.contains(&&transaction_model));
     }
 
     #[test]
@@ -796,9 +1702,9 @@ mod tests {
             Some(existing_transaction.clone())
         );
 
-        let all_transactions: Vec<TransactionModel> = cache.get_all_transactions();
+        let all_transactions: Vec<&TransactionModel> = cache.get_all_transactions().collect();
         assert_eq!(all_transactions.len(), 1);
-        assert!(all_transactions.contains(&updated_transaction));
+        assert!(all_transactions.contains(&&updated_transaction));
     }
 
     #[test]
@@ -828,17 +1734,17 @@ mod tests {
             "2020-11-18T02:08:09Z".to_string(),
         );
 
-        assert!(cache.get_all_transactions().is_empty());
+        assert!(cache.get_all_transactions().next().is_none());
 
         assert_eq!(cache.upsert_transaction(transaction_1.clone()), None);
         assert_eq!(cache.upsert_transaction(transaction_2.clone()), None);
         assert_eq!(cache.upsert_transaction(transaction_3.clone()), None);
 
-        let transactions: Vec<TransactionModel> = cache.get_all_transactions();
+        let transactions: Vec<&TransactionModel> = cache.get_all_transactions().collect();
         assert_eq!(transactions.len(), 3);
-        assert!(transactions.contains(&transaction_1));
-        assert!(transactions.contains(&transaction_2));
-        assert!(transactions.contains(&transaction_3));
+        assert!(transactions.contains(&&transaction_1));
+        assert!(transactions.contains(&&transaction_2));
+        assert!(transactions.contains(&&transaction_3));
     }
 
     #[test]
@@ -911,4 +1817,802 @@ mod tests {
         let transaction: Option<TransactionModel> = cache.get_single_transaction(&target_key);
         assert_eq!(transaction, Some(transaction_1));
     }
+
+    const BRAND_1: &str = "5A1E2F4C-5B30-4D8B-8A4E-3C0A6F1F9B21";
+    const BRAND_2: &str = "0C7D3B6E-2E4A-4E0B-9B57-6A3E1D9C8F42";
+    const BRAND_3: &str = "E2B4F0A1-7C6D-4F3E-8D21-9A5B0C3E7D63";
+    const CATEGORY_1: &str = "8F3A1C2D-4E5B-4A6C-9D7E-0F1A2B3C4D84";
+    const CATEGORY_2: &str = "1B2C3D4E-5F60-4718-8293-A4B5C6D7E8F5";
+    const PRODUCT_1: &str = "3C4D5E6F-7081-4293-A4B5-C6D7E8F90A16";
+    const PRODUCT_2: &str = "4D5E6F70-8192-43A4-B5C6-D7E8F90A1B27";
+    const PRODUCT_3: &str = "5E6F7081-92A3-44B5-C6D7-E8F90A1B2C38";
+    const ITEM_1: &str = "6F708192-A3B4-45C6-D7E8-F90A1B2C3D49";
+    const ITEM_2: &str = "708192A3-B4C5-46D7-E8F9-0A1B2C3D4E5A";
+    const STORE_1: &str = "8192A3B4-C5D6-47E8-F90A-1B2C3D4E5F6B";
+    const STORE_2: &str = "92A3B4C5-D6E7-48F9-0A1B-2C3D4E5F607C";
+    const TRANSACTION_1: &str = "A3B4C5D6-E7F8-490A-1B2C-3D4E5F60718D";
+
+    fn given_cache_with_references() -> InMemoryCache {
+        let mut cache: InMemoryCache = InMemoryCache::new();
+
+        cache.upsert_brand(BrandModel::new(BRAND_1.to_string(), "Some Brand".to_string()));
+        cache.upsert_brand(BrandModel::new(BRAND_2.to_string(), "Some Other Brand".to_string()));
+        cache.upsert_brand(BrandModel::new(BRAND_3.to_string(), "Yet Another Brand".to_string()));
+        cache.upsert_category(CategoryModel::new(CATEGORY_1.to_string(), "Some Category".to_string()));
+        cache.upsert_category(CategoryModel::new(CATEGORY_2.to_string(), "Some Other Category".to_string()));
+        cache.upsert_product(ProductModel::new(
+            PRODUCT_1.to_string(),
+            "Some Product".to_string(),
+            BRAND_1.to_string(),
+            CATEGORY_1.to_string(),
+        ));
+        cache.upsert_product(ProductModel::new(
+            PRODUCT_2.to_string(),
+            "Some Other Product".to_string(),
+            BRAND_1.to_string(),
+            CATEGORY_1.to_string(),
+        ));
+        cache.upsert_product(ProductModel::new(
+            PRODUCT_3.to_string(),
+            "Yet Another Product".to_string(),
+            BRAND_2.to_string(),
+            CATEGORY_1.to_string(),
+        ));
+        cache.upsert_item(ItemModel::new(ITEM_1.to_string(), PRODUCT_1.to_string(), UnitModel::None, 12.50));
+        cache.upsert_item(ItemModel::new(
+            ITEM_2.to_string(),
+            PRODUCT_3.to_string(),
+            UnitModel::Kilograms(1.5),
+            4.20,
+        ));
+        cache.upsert_store(StoreModel::new(STORE_1.to_string(), "Some Store".to_string()));
+        cache.upsert_store(StoreModel::new(STORE_2.to_string(), "Some Other Store".to_string()));
+        cache.upsert_transaction(TransactionModel::new(
+            TRANSACTION_1.to_string(),
+            vec![ITEM_1.to_string(), ITEM_2.to_string()],
+            STORE_1.to_string(),
+            "2024-02-11T10:15:00Z".to_string(),
+        ));
+
+        cache
+    }
+
+    fn given_deletion_time() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn delete_non_existing_brand_return_not_found() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+        let key: String = "F0E1D2C3-B4A5-4697-8889-7A6B5C4D3E2F".to_string();
+
+        assert_eq!(
+            cache.delete_brand(&key, DeleteBehaviour::Cascade, given_deletion_time()),
+            Err(IntegrityError::NotFound(key.clone()))
+        );
+    }
+
+    #[test]
+    fn delete_unreferenced_brand_with_restrict_move_it_to_the_trash() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+        let trashed: BrandModel = BrandModel {
+            deleted_at: Some(given_deletion_time()),
+            ..BrandModel::new(BRAND_3.to_string(), "Yet Another Brand".to_string())
+        };
+
+        assert_eq!(
+            cache.delete_brand(&BRAND_3.to_string(), DeleteBehaviour::Restrict, given_deletion_time()),
+            Ok(trashed.clone())
+        );
+        assert_eq!(cache.get_single_brand(&BRAND_3.to_string()), Some(trashed.clone()));
+        assert_eq!(cache.get_trashed_brands().collect::<Vec<&BrandModel>>(), vec![&trashed]);
+        assert_eq!(cache.get_all_brands().count(), 2);
+        assert_eq!(cache.get_brands_by_name("Yet Another Brand").count(), 0);
+    }
+
+    #[test]
+    fn delete_trashed_brand_return_not_found() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+
+        assert!(
+            cache
+                .delete_brand(&BRAND_3.to_string(), DeleteBehaviour::Restrict, given_deletion_time())
+                .is_ok()
+        );
+        assert_eq!(
+            cache.delete_brand(&BRAND_3.to_string(), DeleteBehaviour::Restrict, Utc::now()),
+            Err(IntegrityError::NotFound(BRAND_3.to_string()))
+        );
+        assert_eq!(
+            cache.get_single_brand(&BRAND_3.to_string()).and_then(|b| b.deleted_at),
+            Some(given_deletion_time())
+        );
+    }
+
+    #[test]
+    fn delete_referenced_brand_with_restrict_return_referencing_products() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+
+        assert_eq!(
+            cache.delete_brand(&BRAND_1.to_string(), DeleteBehaviour::Restrict, given_deletion_time()),
+            Err(IntegrityError::StillReferenced(vec![PRODUCT_1.to_string(), PRODUCT_2.to_string()]))
+        );
+        assert!(cache.get_single_brand(&BRAND_1.to_string()).is_some());
+        assert_eq!(cache.get_all_products().count(), 3);
+    }
+
+    #[test]
+    fn delete_referenced_brand_with_cascade_trash_its_products_and_their_transactions() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+
+        assert!(
+            cache
+                .delete_brand(&BRAND_1.to_string(), DeleteBehaviour::Cascade, given_deletion_time())
+                .is_ok()
+        );
+
+        let result: Vec<String> = cache.get_trashed_products().map(|p| p.key.clone()).collect();
+        let expected: Vec<String> = vec![PRODUCT_1.to_string(), PRODUCT_2.to_string()];
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert!(cache.get_trashed_products().all(|p| p.deleted_at == Some(given_deletion_time())));
+        assert_eq!(
+            cache.get_single_transaction(&TRANSACTION_1.to_string()).and_then(|t| t.deleted_at),
+            Some(given_deletion_time())
+        );
+        assert_eq!(
+            cache.get_single_transaction(&TRANSACTION_1.to_string()).map(|t| t.item_keys),
+            Some(vec![ITEM_1.to_string(), ITEM_2.to_string()])
+        );
+        assert_eq!(cache.get_all_items().len(), 2);
+        assert_eq!(cache.get_all_products().count(), 1);
+    }
+
+    #[test]
+    fn restore_brand_bring_back_what_its_cascading_delete_trashed() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+        cache
+            .delete_brand(&BRAND_1.to_string(), DeleteBehaviour::Cascade, given_deletion_time())
+            .unwrap();
+
+        assert_eq!(
+            cache.restore_brand(&BRAND_1.to_string()),
+            Ok(BrandModel::new(BRAND_1.to_string(), "Some Brand".to_string()))
+        );
+        assert_eq!(cache.get_all_products().count(), 3);
+        assert_eq!(cache.get_all_transactions().count(), 1);
+        assert_eq!(
+            cache.restore_brand(&BRAND_1.to_string()),
+            Err(IntegrityError::NotFound(BRAND_1.to_string()))
+        );
+    }
+
+    #[test]
+    fn restore_brand_leave_what_was_trashed_at_another_time() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+        cache.delete_transaction(&TRANSACTION_1.to_string(), Utc::now()).unwrap();
+        cache
+            .delete_product(&PRODUCT_1.to_string(), DeleteBehaviour::Restrict, Utc::now())
+            .unwrap();
+        cache
+            .delete_brand(&BRAND_1.to_string(), DeleteBehaviour::Cascade, given_deletion_time())
+            .unwrap();
+
+        assert!(cache.restore_brand(&BRAND_1.to_string()).is_ok());
+
+        let result: Vec<String> = cache.get_trashed_products().map(|p| p.key.clone()).collect();
+        let expected: Vec<String> = vec![PRODUCT_1.to_string()];
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(cache.get_all_transactions().count(), 0);
+    }
+
+    #[test]
+    fn delete_referenced_brand_with_reassign_move_products_to_replacement() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+
+        assert!(
+            cache
+                .delete_brand(
+                    &BRAND_1.to_string(),
+                    DeleteBehaviour::Reassign(BRAND_2.to_string()),
+                    given_deletion_time()
+                )
+                .is_ok()
+        );
+
+        assert_eq!(cache.get_all_brands().count(), 2);
+        assert_eq!(cache.get_all_products().count(), 3);
+        assert!(cache.get_all_products().all(|p| p.brand_key == BRAND_2));
+    }
+
+    #[test]
+    fn delete_brand_with_reassign_to_missing_replacement_keep_everything() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+        let replacement: String = "0A0B0C0D-0E0F-4011-9213-141516171819".to_string();
+
+        assert_eq!(
+            cache.delete_brand(
+                &BRAND_1.to_string(),
+                DeleteBehaviour::Reassign(replacement.clone()),
+                given_deletion_time()
+            ),
+            Err(IntegrityError::ReplacementNotFound(replacement))
+        );
+        assert!(cache.get_single_brand(&BRAND_1.to_string()).is_some());
+        assert_eq!(
+            cache.get_single_product(&PRODUCT_1.to_string()).map(|p| p.brand_key),
+            Some(BRAND_1.to_string())
+        );
+    }
+
+    #[test]
+    fn delete_brand_with_reassign_to_itself_return_replacement_not_found() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+
+        assert_eq!(
+            cache.delete_brand(
+                &BRAND_1.to_string(),
+                DeleteBehaviour::Reassign(BRAND_1.to_string()),
+                given_deletion_time()
+            ),
+            Err(IntegrityError::ReplacementNotFound(BRAND_1.to_string()))
+        );
+    }
+
+    #[test]
+    fn delete_referenced_category_with_restrict_return_referencing_products() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+
+        assert_eq!(
+            cache.delete_category(&CATEGORY_1.to_string(), DeleteBehaviour::Restrict, given_deletion_time()),
+            Err(IntegrityError::StillReferenced(vec![
+                PRODUCT_1.to_string(),
+                PRODUCT_2.to_string(),
+                PRODUCT_3.to_string()
+            ]))
+        );
+    }
+
+    #[test]
+    fn delete_referenced_category_with_cascade_trash_products_and_transactions_until_restored() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+
+        assert!(
+            cache
+                .delete_category(&CATEGORY_1.to_string(), DeleteBehaviour::Cascade, given_deletion_time())
+                .is_ok()
+        );
+
+        assert!(cache.get_all_products().next().is_none());
+        assert!(cache.get_all_transactions().next().is_none());
+        assert_eq!(cache.get_all_items().len(), 2);
+
+        assert!(cache.restore_category(&CATEGORY_1.to_string()).is_ok());
+
+        assert_eq!(cache.get_all_products().count(), 3);
+        assert_eq!(cache.get_all_transactions().count(), 1);
+    }
+
+    #[test]
+    fn delete_referenced_category_with_reassign_move_products_to_replacement() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+
+        assert!(
+            cache
+                .delete_category(
+                    &CATEGORY_1.to_string(),
+                    DeleteBehaviour::Reassign(CATEGORY_2.to_string()),
+                    given_deletion_time()
+                )
+                .is_ok()
+        );
+
+        assert!(cache.get_all_products().all(|p| p.category_key == CATEGORY_2));
+    }
+
+    #[test]
+    fn delete_referenced_product_with_restrict_return_referencing_items() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+
+        assert_eq!(
+            cache.delete_product(&PRODUCT_1.to_string(), DeleteBehaviour::Restrict, given_deletion_time()),
+            Err(IntegrityError::StillReferenced(vec![ITEM_1.to_string()]))
+        );
+    }
+
+    #[test]
+    fn delete_unreferenced_product_with_restrict_remove_it() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+
+        assert!(
+            cache
+                .delete_product(&PRODUCT_2.to_string(), DeleteBehaviour::Restrict, given_deletion_time())
+                .is_ok()
+        );
+        assert_eq!(
+            cache.get_single_product(&PRODUCT_2.to_string()).and_then(|p| p.deleted_at),
+            Some(given_deletion_time())
+        );
+    }
+
+    #[test]
+    fn delete_product_held_by_trashed_transactions_only_with_restrict_trash_it() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+        cache.delete_transaction(&TRANSACTION_1.to_string(), Utc::now()).unwrap();
+
+        assert!(
+            cache
+                .delete_product(&PRODUCT_1.to_string(), DeleteBehaviour::Restrict, given_deletion_time())
+                .is_ok()
+        );
+        assert_eq!(
+            cache.restore_transaction(&TRANSACTION_1.to_string()),
+            Err(IntegrityError::DanglingReference(PRODUCT_1.to_string()))
+        );
+    }
+
+    #[test]
+    fn restore_product_of_trashed_brand_return_dangling_reference() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+        cache
+            .delete_product(&PRODUCT_2.to_string(), DeleteBehaviour::Restrict, given_deletion_time())
+            .unwrap();
+        cache
+            .delete_brand(&BRAND_1.to_string(), DeleteBehaviour::Cascade, Utc::now())
+            .unwrap();
+
+        assert_eq!(
+            cache.restore_product(&PRODUCT_2.to_string()),
+            Err(IntegrityError::DanglingReference(BRAND_1.to_string()))
+        );
+        assert_eq!(
+            cache.restore_product(&PRODUCT_3.to_string()),
+            Err(IntegrityError::NotFound(PRODUCT_3.to_string()))
+        );
+    }
+
+    #[test]
+    fn delete_referenced_product_with_cascade_trash_the_transactions_holding_it() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+
+        assert!(
+            cache
+                .delete_product(&PRODUCT_3.to_string(), DeleteBehaviour::Cascade, given_deletion_time())
+                .is_ok()
+        );
+        assert_eq!(cache.get_all_transactions().count(), 0);
+
+        assert!(cache.restore_product(&PRODUCT_3.to_string()).is_ok());
+        assert_eq!(cache.get_all_transactions().count(), 1);
+    }
+
+    #[test]
+    fn delete_referenced_product_with_reassign_move_items_to_replacement() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+
+        assert!(
+            cache
+                .delete_product(
+                    &PRODUCT_1.to_string(),
+                    DeleteBehaviour::Reassign(PRODUCT_2.to_string()),
+                    given_deletion_time()
+                )
+                .is_ok()
+        );
+
+        assert_eq!(
+            cache.get_single_item(&ITEM_1.to_string()).map(|i| i.product_key),
+            Some(PRODUCT_2.to_string())
+        );
+    }
+
+    #[test]
+    fn delete_referenced_store_with_restrict_return_referencing_transactions() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+
+        assert_eq!(
+            cache.delete_store(&STORE_1.to_string(), DeleteBehaviour::Restrict, given_deletion_time()),
+            Err(IntegrityError::StillReferenced(vec![TRANSACTION_1.to_string()]))
+        );
+    }
+
+    #[test]
+    fn delete_referenced_store_with_cascade_trash_its_transactions_until_restored() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+
+        assert!(
+            cache
+                .delete_store(&STORE_1.to_string(), DeleteBehaviour::Cascade, given_deletion_time())
+                .is_ok()
+        );
+
+        assert!(cache.get_all_transactions().next().is_none());
+        assert_eq!(cache.get_all_items().len(), 2);
+        assert_eq!(cache.get_all_products().count(), 3);
+
+        assert!(cache.restore_store(&STORE_1.to_string()).is_ok());
+
+        assert_eq!(cache.get_all_transactions().count(), 1);
+    }
+
+    #[test]
+    fn delete_referenced_store_with_reassign_move_transactions_to_replacement() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+
+        assert!(
+            cache
+                .delete_store(
+                    &STORE_1.to_string(),
+                    DeleteBehaviour::Reassign(STORE_2.to_string()),
+                    given_deletion_time()
+                )
+                .is_ok()
+        );
+
+        assert_eq!(
+            cache.get_single_transaction(&TRANSACTION_1.to_string()).map(|t| t.store_key),
+            Some(STORE_2.to_string())
+        );
+    }
+
+    #[test]
+    fn delete_transaction_move_it_to_the_trash_with_its_items() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+
+        assert!(cache.delete_transaction(&TRANSACTION_1.to_string(), given_deletion_time()).is_ok());
+
+        assert!(cache.get_all_transactions().next().is_none());
+        assert_eq!(cache.get_trashed_transactions().count(), 1);
+        assert_eq!(cache.get_all_items().len(), 2);
+        assert_eq!(
+            cache.delete_transaction(&TRANSACTION_1.to_string(), given_deletion_time()),
+            Err(IntegrityError::NotFound(TRANSACTION_1.to_string()))
+        );
+    }
+
+    #[test]
+    fn purge_remove_what_was_trashed_before_the_cutoff_with_its_trashed_dependents() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+        cache
+            .delete_brand(&BRAND_1.to_string(), DeleteBehaviour::Cascade, given_deletion_time())
+            .unwrap();
+
+        assert_eq!(cache.purge_brands(given_deletion_time()), vec![]);
+
+        let result: Vec<String> = cache
+            .purge_brands(given_deletion_time() + chrono::Duration::seconds(1))
+            .into_iter()
+            .map(|b| b.key)
+            .collect();
+        let expected: Vec<String> = vec![BRAND_1.to_string()];
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(cache.get_trashed_products().count(), 0);
+        assert_eq!(cache.get_trashed_transactions().count(), 0);
+        assert_eq!(cache.get_all_items().len(), 0);
+        assert_eq!(cache.check_references(), Ok(()));
+    }
+
+    #[test]
+    fn replace_transaction_drop_items_no_longer_listed() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+        let new_item: ItemModel = ItemModel::new(
+            "D6E7F809-1A2B-43C4-9D5E-6F708192A3B4".to_string(),
+            PRODUCT_2.to_string(),
+            UnitModel::Kilograms(0.5),
+            3.2,
+        );
+        let transaction: TransactionModel = TransactionModel::new(
+            TRANSACTION_1.to_string(),
+            vec![new_item.key.clone()],
+            STORE_1.to_string(),
+            "2024-02-11T10:15:00Z".to_string(),
+        );
+
+        let previous: Option<TransactionModel> = cache.get_single_transaction(&TRANSACTION_1.to_string());
+        let result: Result<Option<TransactionModel>, IntegrityError> =
+            cache.replace_transaction(transaction.clone(), vec![new_item.clone()]);
+
+        assert_eq!(result, Ok(previous), "Expected previous transaction, but got {:?}", result);
+        assert_eq!(cache.get_all_items().cloned().collect::<Vec<ItemModel>>(), vec![new_item]);
+        assert_eq!(cache.get_single_transaction(&TRANSACTION_1.to_string()), Some(transaction));
+    }
+
+    #[test]
+    fn replace_transaction_with_missing_product_leave_cache_untouched() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+        let missing_product: String = "BBBBBBBB-CCCC-4DDD-8EEE-FFFFFFFFFFFF".to_string();
+        let item: ItemModel = ItemModel::new(
+            "D6E7F809-1A2B-43C4-9D5E-6F708192A3B4".to_string(),
+            missing_product.clone(),
+            UnitModel::None,
+            1.0,
+        );
+        let transaction: TransactionModel = TransactionModel::new(
+            TRANSACTION_1.to_string(),
+            vec![item.key.clone()],
+            STORE_1.to_string(),
+            "2024-02-11T10:15:00Z".to_string(),
+        );
+
+        assert_eq!(
+            cache.replace_transaction(transaction, vec![item]),
+            Err(IntegrityError::DanglingReference(missing_product))
+        );
+        assert_eq!(
+            cache.get_single_transaction(&TRANSACTION_1.to_string()).map(|t| t.item_keys),
+            Some(vec![ITEM_1.to_string(), ITEM_2.to_string()])
+        );
+        assert_eq!(cache.get_all_items().len(), 2);
+    }
+
+    #[test]
+    fn check_product_with_missing_brand_return_dangling_reference() {
+        let cache: InMemoryCache = given_cache_with_references();
+        let missing_brand: String = "11111111-2222-4333-8444-555555555555".to_string();
+        let product: ProductModel = ProductModel::new(
+            "B4C5D6E7-F809-41A2-B3C4-D5E6F708192A".to_string(),
+            "Some Product".to_string(),
+            missing_brand.clone(),
+            CATEGORY_1.to_string(),
+        );
+
+        assert_eq!(
+            cache.check_product_references(&product),
+            Err(IntegrityError::DanglingReference(missing_brand))
+        );
+    }
+
+    #[test]
+    fn check_product_with_missing_category_return_dangling_reference() {
+        let cache: InMemoryCache = given_cache_with_references();
+        let missing_category: String = "66666666-7777-4888-9999-AAAAAAAAAAAA".to_string();
+        let product: ProductModel = ProductModel::new(
+            "B4C5D6E7-F809-41A2-B3C4-D5E6F708192A".to_string(),
+            "Some Product".to_string(),
+            BRAND_1.to_string(),
+            missing_category.clone(),
+        );
+
+        assert_eq!(
+            cache.check_product_references(&product),
+            Err(IntegrityError::DanglingReference(missing_category))
+        );
+    }
+
+    #[test]
+    fn check_item_with_missing_product_return_dangling_reference() {
+        let cache: InMemoryCache = given_cache_with_references();
+        let missing_product: String = "BBBBBBBB-CCCC-4DDD-8EEE-FFFFFFFFFFFF".to_string();
+        let item: ItemModel = ItemModel::new(
+            "C5D6E7F8-0912-43B4-C5D6-E7F8091A2B3C".to_string(),
+            missing_product.clone(),
+            UnitModel::None,
+            1.0,
+        );
+
+        assert_eq!(
+            cache.check_item_references(&item),
+            Err(IntegrityError::DanglingReference(missing_product))
+        );
+        assert_eq!(
+            cache.check_item_references(&cache.get_single_item(&ITEM_1.to_string()).unwrap()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn check_transaction_with_missing_references_return_dangling_reference() {
+        let cache: InMemoryCache = given_cache_with_references();
+        let missing_store: String = "12121212-3434-4565-8787-909090909090".to_string();
+        let missing_item: String = "ABABABAB-CDCD-4EFE-8A8A-B9B9B9B9B9B9".to_string();
+
+        let with_missing_store: TransactionModel = TransactionModel::new(
+            TRANSACTION_1.to_string(),
+            vec![],
+            missing_store.clone(),
+            "2024-02-11T10:15:00Z".to_string(),
+        );
+        let with_missing_item: TransactionModel = TransactionModel::new(
+            TRANSACTION_1.to_string(),
+            vec![ITEM_1.to_string(), missing_item.clone()],
+            STORE_1.to_string(),
+            "2024-02-11T10:15:00Z".to_string(),
+        );
+
+        assert_eq!(
+            cache.check_transaction_references(&with_missing_store),
+            Err(IntegrityError::DanglingReference(missing_store))
+        );
+        assert_eq!(
+            cache.check_transaction_references(&with_missing_item),
+            Err(IntegrityError::DanglingReference(missing_item))
+        );
+        assert_eq!(
+            cache.check_transaction_references(&cache.get_single_transaction(&TRANSACTION_1.to_string()).unwrap()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn upsert_renamed_store_move_it_in_the_name_index() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+
+        cache.upsert_store(StoreModel::new(STORE_1.to_string(), "Renamed Store".to_string()));
+
+        let result: Vec<String> = cache.get_stores_by_name("Renamed Store").map(|s| s.key.clone()).collect();
+        let expected: Vec<String> = vec![STORE_1.to_string()];
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(cache.get_stores_by_name("Some Store").count(), 0);
+    }
+
+    #[test]
+    fn get_by_name_return_every_entity_sharing_the_name() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+
+        cache.upsert_brand(BrandModel::new(BRAND_2.to_string(), "Some Brand".to_string()));
+
+        let result: Vec<String> = cache.get_brands_by_name("Some Brand").map(|b| b.key.clone()).collect();
+        let expected: Vec<String> = vec![BRAND_2.to_string(), BRAND_1.to_string()];
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(cache.get_categories_by_name("Some Category").count(), 1);
+        assert_eq!(cache.get_products_by_name("Yet Another Product").count(), 1);
+    }
+
+    #[test]
+    fn get_by_name_follow_the_name_policy() {
+        let mut folding: InMemoryCache = InMemoryCache::new();
+        let mut strict: InMemoryCache = InMemoryCache::new().with_name_policy(NamePolicy::new(false));
+
+        folding.upsert_brand(BrandModel::new("Nestlé".to_string(), "Nestlé".to_string()));
+        strict.upsert_brand(BrandModel::new("Nestlé".to_string(), "Nestlé".to_string()));
+
+        let result: Vec<String> = folding.get_brands_by_name(" NESTLE").map(|b| b.name.clone()).collect();
+        let expected: Vec<String> = vec!["Nestlé".to_string()];
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(strict.get_brands_by_name("NESTLÉ").count(), 1);
+        assert_eq!(strict.get_brands_by_name("Nestle").count(), 0);
+    }
+
+    #[test]
+    fn insert_if_absent_refuse_an_existing_entity() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+
+        assert_eq!(
+            cache.insert_brand_if_absent(BrandModel::new("some brand".to_string(), " SOME BRAND".to_string())),
+            Err(IntegrityError::AlreadyExists(BRAND_1.to_string()))
+        );
+        assert_eq!(
+            cache.insert_store_if_absent(StoreModel::new(STORE_1.to_string(), "Renamed Store".to_string())),
+            Err(IntegrityError::AlreadyExists(STORE_1.to_string()))
+        );
+        assert_eq!(
+            cache.get_single_store(&STORE_1.to_string()).map(|s| s.name),
+            Some("Some Store".to_string())
+        );
+        assert_eq!(cache.get_all_brands().count(), 3);
+    }
+
+    #[test]
+    fn insert_brand_if_absent_refuse_the_name_of_a_trashed_brand() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+        cache
+            .delete_brand(&BRAND_3.to_string(), DeleteBehaviour::Restrict, given_deletion_time())
+            .unwrap();
+
+        assert_eq!(
+            cache.insert_brand_if_absent(BrandModel::new("Yet Another Brand".to_string(), "Yet Another Brand".to_string())),
+            Err(IntegrityError::AlreadyExists(BRAND_3.to_string()))
+        );
+    }
+
+    #[test]
+    fn delete_referenced_store_with_cascade_hide_it_from_every_lookup() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+
+        assert!(
+            cache
+                .delete_store(&STORE_1.to_string(), DeleteBehaviour::Cascade, given_deletion_time())
+                .is_ok()
+        );
+
+        assert_eq!(cache.get_stores_by_name("Some Store").count(), 0);
+        assert_eq!(cache.get_transactions_by_store(STORE_1).count(), 0);
+        assert_eq!(cache.get_transactions_between(..).count(), 0);
+    }
+
+    #[test]
+    fn delete_with_reassign_move_references_in_the_indexes() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+
+        assert!(
+            cache
+                .delete_product(
+                    &PRODUCT_1.to_string(),
+                    DeleteBehaviour::Reassign(PRODUCT_2.to_string()),
+                    given_deletion_time()
+                )
+                .is_ok()
+        );
+        assert!(
+            cache
+                .delete_store(
+                    &STORE_1.to_string(),
+                    DeleteBehaviour::Reassign(STORE_2.to_string()),
+                    given_deletion_time()
+                )
+                .is_ok()
+        );
+
+        let items: Vec<String> = cache.get_items_by_product(PRODUCT_2).map(|i| i.key.clone()).collect();
+        let transactions: Vec<String> = cache.get_transactions_by_store(STORE_2).map(|t| t.key.clone()).collect();
+
+        assert_eq!(items, vec![ITEM_1.to_string()], "Expected {:?}, but got {:?}", vec![ITEM_1], items);
+        assert_eq!(
+            transactions,
+            vec![TRANSACTION_1.to_string()],
+            "Expected {:?}, but got {:?}",
+            vec![TRANSACTION_1],
+            transactions
+        );
+        assert_eq!(cache.get_items_by_product(PRODUCT_1).count(), 0);
+        assert_eq!(cache.get_transactions_by_store(STORE_1).count(), 0);
+        assert_eq!(cache.get_products_by_name("Some Product").count(), 0);
+    }
+
+    #[test]
+    fn delete_brand_and_category_with_reassign_move_products_in_the_indexes() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+
+        assert!(
+            cache
+                .delete_brand(
+                    &BRAND_1.to_string(),
+                    DeleteBehaviour::Reassign(BRAND_2.to_string()),
+                    given_deletion_time()
+                )
+                .is_ok()
+        );
+        assert!(
+            cache
+                .delete_category(
+                    &CATEGORY_1.to_string(),
+                    DeleteBehaviour::Reassign(CATEGORY_2.to_string()),
+                    given_deletion_time()
+                )
+                .is_ok()
+        );
+
+        let by_brand: Vec<String> = cache.get_products_by_brand(BRAND_2).map(|p| p.key.clone()).collect();
+        let by_category: Vec<String> = cache.get_products_by_category(CATEGORY_2).map(|p| p.key.clone()).collect();
+        let expected: Vec<String> = vec![PRODUCT_1.to_string(), PRODUCT_2.to_string(), PRODUCT_3.to_string()];
+
+        assert_eq!(by_brand, expected, "Expected {:?}, but got {:?}", expected, by_brand);
+        assert_eq!(by_category, expected, "Expected {:?}, but got {:?}", expected, by_category);
+        assert_eq!(cache.get_products_by_brand(BRAND_1).count(), 0);
+        assert_eq!(cache.get_products_by_category(CATEGORY_1).count(), 0);
+    }
+
+    #[test]
+    fn get_transactions_between_follow_date_changes() {
+        let mut cache: InMemoryCache = given_cache_with_references();
+        let february: DateTime<Utc> = Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap();
+        let march: DateTime<Utc> = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+
+        assert_eq!(cache.get_transactions_between(february..march).count(), 1);
+
+        let mut moved: TransactionModel = cache.get_single_transaction(&TRANSACTION_1.to_string()).unwrap();
+        moved.datetime = "2024-03-02T08:00:00+01:00".to_string();
+        cache.upsert_transaction(moved);
+
+        let result: Vec<String> = cache.get_transactions_between(march..).map(|t| t.key.clone()).collect();
+        let expected: Vec<String> = vec![TRANSACTION_1.to_string()];
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(cache.get_transactions_between(february..march).count(), 0);
+    }
 }
@@ -0,0 +1,480 @@
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use storage_encryption::infrastructures::encryption::{Cipher, CipherError};
+use storage_migrations::infrastructures::migrations::{Migration, MigrationError, MigrationPlan, MigrationRegistry};
+use tokio::{sync::RwLock, task::JoinHandle};
+
+use crate::infrastructures::data_sources::{
+    BrandModel, CategoryModel, InMemoryCache, IntegrityError, ItemModel, ProductModel, StoreModel, TransactionModel,
+};
+
+/// Version written into every snapshot, bumped whenever the layout of the models changes.
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 2;
+
+/// A single JSON file holding the whole content of an [`InMemoryCache`].
+///
+/// Snapshots are written atomically, so a crash while saving leaves the previous snapshot in place. With a
+/// [`Cipher`] the file is sealed with it, a plaintext snapshot is still loaded and gets encrypted on the next save.
+#[derive(Debug, Clone)]
+pub struct InMemorySnapshot {
+    path: PathBuf,
+    cipher: Option<Cipher>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnapshotError {
+    UnableToRead(String),
+    UnableToWrite(String),
+    UnsupportedVersion(u32),
+    DanglingReference(String),
+    PassphraseRequired,
+    WrongPassphrase,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SnapshotDocument {
+    format_version: u32,
+    brands: Vec<BrandModel>,
+    categories: Vec<CategoryModel>,
+    stores: Vec<StoreModel>,
+    products: Vec<ProductModel>,
+    items: Vec<ItemModel>,
+    transactions: Vec<TransactionModel>,
+}
+
+impl InMemorySnapshot {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            cipher: None,
+        }
+    }
+
+    pub fn with_cipher(self, cipher: Cipher) -> Self {
+        Self {
+            cipher: Some(cipher),
+            ..self
+        }
+    }
+
+    /// Whether the snapshot file exists and is sealed, in which case a passphrase is needed to load it.
+    pub fn is_encrypted(&self) -> Result<bool, SnapshotError> {
+        match fs::read(&self.path) {
+            Ok(contents) => Ok(Cipher::is_sealed(&contents)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(SnapshotError::UnableToRead(e.to_string())),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Rebuilds a cache from the snapshot file, or returns an empty one when no snapshot was saved yet.
+    ///
+    /// Snapshots written by an older version are upgraded in memory, the file itself is left as it is until the next
+    /// save or an explicit [`InMemorySnapshot::migrate`].
+    pub fn load(&self) -> Result<InMemoryCache, SnapshotError> {
+        let Some(mut document) = self.read_document()? else {
+            return Ok(InMemoryCache::new());
+        };
+
+        let from_version: u32 = format_version(&document)?;
+
+        migrations().upgrade(&mut document, from_version).map_err(migration_failure)?;
+        document["format_version"] = Value::from(SNAPSHOT_FORMAT_VERSION);
+
+        let document: SnapshotDocument = serde_json::from_value(document).map_err(|e| SnapshotError::UnableToRead(e.to_string()))?;
+
+        let mut cache: InMemoryCache = InMemoryCache::new();
+
+        document.brands.into_iter().for_each(|brand| {
+            cache.upsert_brand(brand);
+        });
+        document.categories.into_iter().for_each(|category| {
+            cache.upsert_category(category);
+        });
+        document.stores.into_iter().for_each(|store| {
+            cache.upsert_store(store);
+        });
+        document.products.into_iter().for_each(|product| {
+            cache.upsert_product(product);
+        });
+        document.items.into_iter().for_each(|item| {
+            cache.upsert_item(item);
+        });
+        document.transactions.into_iter().for_each(|transaction| {
+            cache.upsert_transaction(transaction);
+        });
+
+        cache.check_references().map_err(|e| match e {
+            IntegrityError::DanglingReference(key) => SnapshotError::DanglingReference(key),
+            e => SnapshotError::UnableToRead(format!("{:?}", e)),
+        })?;
+
+        Ok(cache)
+    }
+
+    /// What [`InMemorySnapshot::migrate`] would do to the snapshot file, nothing when there is no file yet.
+    pub fn migration_plan(&self) -> Result<MigrationPlan, SnapshotError> {
+        let from_version: u32 = match self.read_document()? {
+            Some(document) => format_version(&document)?,
+            None => SNAPSHOT_FORMAT_VERSION,
+        };
+
+        migrations().plan(from_version).map_err(migration_failure)
+    }
+
+    /// Rewrites the snapshot file in the current format when it was written by an older version.
+    pub fn migrate(&self) -> Result<MigrationPlan, SnapshotError> {
+        let plan: MigrationPlan = self.migration_plan()?;
+
+        if !plan.is_empty() {
+            self.save(&self.load()?)?;
+        }
+
+        Ok(plan)
+    }
+
+    pub fn save(&self, cache: &InMemoryCache) -> Result<(), SnapshotError> {
+        let document: SnapshotDocument = SnapshotDocument {
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            brands: sorted_by_key(cache.get_all_brands().chain(cache.get_trashed_brands()), |b| &b.key),
+            categories: sorted_by_key(cache.get_all_categories().chain(cache.get_trashed_categories()), |c| &c.key),
+            stores: sorted_by_key(cache.get_all_stores().chain(cache.get_trashed_stores()), |s| &s.key),
+            products: sorted_by_key(cache.get_all_products().chain(cache.get_trashed_products()), |p| &p.key),
+            items: sorted_by_key(cache.get_all_items(), |i| &i.key),
+            transactions: sorted_by_key(cache.get_all_transactions().chain(cache.get_trashed_transactions()), |t| &t.key),
+        };
+
+        let mut contents: Vec<u8> = serde_json::to_vec_pretty(&document).map_err(|e| SnapshotError::UnableToWrite(e.to_string()))?;
+        contents.push(b'\n');
+
+        if let Some(cipher) = &self.cipher {
+            contents = cipher
+                .seal(&contents)
+                .map_err(|e| SnapshotError::UnableToWrite(format!("{:?}", e)))?;
+        }
+
+        write_atomically(&self.path, &contents).map_err(|e| SnapshotError::UnableToWrite(e.to_string()))
+    }
+
+    /// Loads the snapshot and saves it back sealed with `cipher`, or in plaintext when `cipher` is `None`.
+    pub fn rekey(&self, cipher: Option<Cipher>) -> Result<InMemorySnapshot, SnapshotError> {
+        let cache: InMemoryCache = self.load()?;
+        let rekeyed: InMemorySnapshot = Self {
+            path: self.path.clone(),
+            cipher,
+        };
+
+        rekeyed.save(&cache)?;
+        Ok(rekeyed)
+    }
+
+    /// Saves the cache every `period` until a save fails, in which case the task ends with that error.
+    pub fn spawn_periodic_save(&self, cache: Arc<RwLock<InMemoryCache>>, period: Duration) -> JoinHandle<Result<(), SnapshotError>> {
+        let snapshot: InMemorySnapshot = self.clone();
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+
+                snapshot.save(&*cache.read().await)?;
+            }
+        })
+    }
+}
+
+impl InMemorySnapshot {
+    fn read_document(&self) -> Result<Option<Value>, SnapshotError> {
+        let contents: Vec<u8> = match fs::read(&self.path) {
+            Ok(contents) if Cipher::is_sealed(&contents) => match &self.cipher {
+                Some(cipher) => cipher.open(&contents).map_err(|e| match e {
+                    CipherError::WrongPassphrase => SnapshotError::WrongPassphrase,
+                    e => SnapshotError::UnableToRead(format!("{:?}", e)),
+                })?,
+                None => return Err(SnapshotError::PassphraseRequired),
+            },
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(SnapshotError::UnableToRead(e.to_string())),
+        };
+
+        serde_json::from_slice(&contents)
+            .map(Some)
+            .map_err(|e| SnapshotError::UnableToRead(e.to_string()))
+    }
+}
+
+/// Steps upgrading a snapshot document to [`SNAPSHOT_FORMAT_VERSION`].
+fn migrations() -> MigrationRegistry {
+    MigrationRegistry::new(SNAPSHOT_FORMAT_VERSION).with(Migration::new(1, "Keep trashed entities, marked by their deleted_at", |_| Ok(())))
+}
+
+fn format_version(document: &Value) -> Result<u32, SnapshotError> {
+    document["format_version"]
+        .as_u64()
+        .and_then(|version| u32::try_from(version).ok())
+        .ok_or_else(|| SnapshotError::UnableToRead("missing format_version".to_owned()))
+}
+
+fn migration_failure(error: MigrationError) -> SnapshotError {
+    match error {
+        MigrationError::NewerThanSupported { version, .. } | MigrationError::MissingStep(version) => {
+            SnapshotError::UnsupportedVersion(version)
+        }
+        MigrationError::StepFailed { from_version, reason } => {
+            SnapshotError::UnableToRead(format!("unable to upgrade from version {}: {}", from_version, reason))
+        }
+    }
+}
+
+fn sorted_by_key<'a, M: Clone + 'a>(models: impl Iterator<Item = &'a M>, key: impl Fn(&M) -> &String) -> Vec<M> {
+    let mut models: Vec<M> = models.cloned().collect();
+    models.sort_by(|a, b| key(a).cmp(key(b)));
+    models
+}
+
+fn write_atomically(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let file_name: String = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "snapshot path has no file name"))?
+        .to_string_lossy()
+        .into_owned();
+    let temporary_path: PathBuf = path.with_file_name(format!(".{}.tmp", file_name));
+
+    let mut temporary: File = File::create(&temporary_path)?;
+    temporary.write_all(contents)?;
+    temporary.sync_all()?;
+    drop(temporary);
+
+    fs::rename(&temporary_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, sync::Arc, time::Duration};
+
+    use chrono::{DateTime, TimeZone, Utc};
+    use expense_tracking::domain::repositories::DeleteBehaviour;
+    use storage_encryption::infrastructures::encryption::{Cipher, KeyDerivationCost};
+    use tokio::sync::RwLock;
+
+    use super::{InMemorySnapshot, SNAPSHOT_FORMAT_VERSION, SnapshotError};
+    use crate::infrastructures::data_sources::{
+        BrandModel, CategoryModel, InMemoryCache, ItemModel, ProductModel, StoreModel, TransactionModel, UnitModel,
+    };
+
+    fn given_cache_with_transaction() -> InMemoryCache {
+        let mut cache: InMemoryCache = InMemoryCache::new();
+
+        cache.upsert_brand(BrandModel::new("Nestlé".to_owned(), "Nestlé".to_owned()));
+        cache.upsert_category(CategoryModel::new("category".to_owned(), "Dairy".to_owned()));
+        cache.upsert_store(StoreModel::new("store".to_owned(), "Migros".to_owned()));
+        cache.upsert_product(ProductModel::new(
+            "product".to_owned(),
+            "Milk".to_owned(),
+            "Nestlé".to_owned(),
+            "category".to_owned(),
+        ));
+        cache
+            .replace_transaction(
+                TransactionModel::new(
+                    "transaction".to_owned(),
+                    vec!["item".to_owned()],
+                    "store".to_owned(),
+                    "2025-03-14T09:26:53Z".to_owned(),
+                ),
+                vec![ItemModel::new(
+                    "item".to_owned(),
+                    "product".to_owned(),
+                    UnitModel::Liters(1.5),
+                    1.95,
+                )],
+            )
+            .unwrap();
+
+        cache
+    }
+
+    #[test]
+    fn load_without_snapshot_file_should_return_empty_cache() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+
+        let result: Result<usize, SnapshotError> = InMemorySnapshot::new(directory.path().join("snapshot.json"))
+            .load()
+            .map(|cache| cache.get_all_brands().count());
+
+        assert_eq!(result, Ok(0), "Expected {:?}, but got {:?}", Ok::<usize, SnapshotError>(0), result);
+    }
+
+    #[test]
+    fn save_then_load_should_restore_every_entity_and_relation() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let snapshot: InMemorySnapshot = InMemorySnapshot::new(directory.path().join("snapshot.json"));
+        let cache: InMemoryCache = given_cache_with_transaction();
+
+        snapshot.save(&cache).unwrap();
+        let loaded: InMemoryCache = snapshot.load().unwrap();
+
+        let key: String = "transaction".to_owned();
+        assert_eq!(loaded.get_single_transaction(&key), cache.get_single_transaction(&key));
+        assert_eq!(
+            loaded.get_single_item(&"item".to_owned()),
+            cache.get_single_item(&"item".to_owned())
+        );
+        assert_eq!(
+            loaded.get_single_product(&"product".to_owned()),
+            cache.get_single_product(&"product".to_owned())
+        );
+        assert_eq!(loaded.get_all_brands().count(), 1);
+        assert_eq!(loaded.get_all_categories().count(), 1);
+        assert_eq!(loaded.get_all_stores().count(), 1);
+    }
+
+    #[test]
+    fn save_then_load_should_keep_the_trash() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let snapshot: InMemorySnapshot = InMemorySnapshot::new(directory.path().join("snapshot.json"));
+        let deleted_at: DateTime<Utc> = Utc.with_ymd_and_hms(2025, 3, 15, 8, 0, 0).unwrap();
+        let mut cache: InMemoryCache = given_cache_with_transaction();
+        cache
+            .delete_store(&"store".to_owned(), DeleteBehaviour::Cascade, deleted_at)
+            .unwrap();
+
+        snapshot.save(&cache).unwrap();
+        let mut loaded: InMemoryCache = snapshot.load().unwrap();
+
+        let result: Vec<Option<DateTime<Utc>>> = loaded.get_trashed_transactions().map(|t| t.deleted_at).collect();
+        let expected: Vec<Option<DateTime<Utc>>> = vec![Some(deleted_at)];
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(loaded.get_all_stores().count(), 0);
+        assert!(loaded.restore_store(&"store".to_owned()).is_ok());
+        assert_eq!(loaded.get_all_transactions().count(), 1);
+    }
+
+    #[test]
+    fn load_should_reject_snapshots_of_another_version() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let snapshot: InMemorySnapshot = InMemorySnapshot::new(directory.path().join("snapshot.json"));
+        snapshot.save(&InMemoryCache::new()).unwrap();
+
+        let contents: String = fs::read_to_string(snapshot.path()).unwrap().replace(
+            &format!("\"format_version\": {}", SNAPSHOT_FORMAT_VERSION),
+            "\"format_version\": 99",
+        );
+        fs::write(snapshot.path(), contents).unwrap();
+
+        let result: Result<usize, SnapshotError> = snapshot.load().map(|cache| cache.get_all_brands().count());
+        let expected: Result<usize, SnapshotError> = Err(SnapshotError::UnsupportedVersion(99));
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[test]
+    fn load_should_reject_snapshots_with_dangling_references() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let snapshot: InMemorySnapshot = InMemorySnapshot::new(directory.path().join("snapshot.json"));
+        let mut cache: InMemoryCache = InMemoryCache::new();
+        cache.upsert_product(ProductModel::new(
+            "product".to_owned(),
+            "Milk".to_owned(),
+            "Missing".to_owned(),
+            "category".to_owned(),
+        ));
+        snapshot.save(&cache).unwrap();
+
+        let result: Result<usize, SnapshotError> = snapshot.load().map(|cache| cache.get_all_products().count());
+        let expected: Result<usize, SnapshotError> = Err(SnapshotError::DanglingReference("Missing".to_owned()));
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[test]
+    fn encrypted_snapshot_should_need_the_right_passphrase_until_rekeyed_to_plaintext() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let cheap: KeyDerivationCost = KeyDerivationCost {
+            memory_kib: 8,
+            iterations: 1,
+            parallelism: 1,
+        };
+        let plain: InMemorySnapshot = InMemorySnapshot::new(directory.path().join("snapshot.json"));
+        let encrypted: InMemorySnapshot = plain.clone().with_cipher(Cipher::new("correct horse").with_cost(cheap));
+        encrypted.save(&given_cache_with_transaction()).unwrap();
+
+        assert!(!fs::read_to_string(plain.path()).is_ok_and(|contents| contents.contains("Migros")));
+        assert_eq!(plain.is_encrypted(), Ok(true));
+        assert_eq!(plain.load().err(), Some(SnapshotError::PassphraseRequired));
+        assert_eq!(
+            plain.clone().with_cipher(Cipher::new("battery staple")).load().err(),
+            Some(SnapshotError::WrongPassphrase)
+        );
+        assert_eq!(encrypted.load().map(|cache| cache.get_all_stores().count()), Ok(1));
+
+        let decrypted: InMemorySnapshot = encrypted.rekey(None).unwrap();
+
+        let result: Result<usize, SnapshotError> = plain.load().map(|cache| cache.get_all_stores().count());
+        assert_eq!(result, Ok(1), "Expected {:?}, but got {:?}", Ok::<usize, SnapshotError>(1), result);
+        assert_eq!(decrypted.is_encrypted(), Ok(false));
+    }
+
+    #[test]
+    fn load_should_upgrade_every_historical_fixture() {
+        for version in 1..=SNAPSHOT_FORMAT_VERSION {
+            let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+            let snapshot: InMemorySnapshot = InMemorySnapshot::new(directory.path().join("snapshot.json"));
+            fs::copy(
+                std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("fixtures/snapshots/v{}.json", version)),
+                snapshot.path(),
+            )
+            .unwrap();
+
+            let plan: usize = snapshot.migrate().unwrap().steps.len();
+            assert_eq!(
+                plan,
+                (SNAPSHOT_FORMAT_VERSION - version) as usize,
+                "Unexpected plan for v{}",
+                version
+            );
+
+            let result: Result<(usize, usize), SnapshotError> = snapshot
+                .load()
+                .map(|cache| (cache.get_all_products().count(), cache.get_all_items().len()));
+            let expected: Result<(usize, usize), SnapshotError> = Ok((1, 1));
+
+            assert_eq!(result, expected, "Expected {:?} for v{}, but got {:?}", expected, version, result);
+            assert!(snapshot.migration_plan().unwrap().is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn spawn_periodic_save_should_write_the_latest_state() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let snapshot: InMemorySnapshot = InMemorySnapshot::new(directory.path().join("snapshot.json"));
+        let cache: Arc<RwLock<InMemoryCache>> = Arc::new(RwLock::new(InMemoryCache::new()));
+
+        let task = snapshot.spawn_periodic_save(Arc::clone(&cache), Duration::from_millis(10));
+        cache
+            .write()
+            .await
+            .upsert_brand(BrandModel::new("Nestlé".to_owned(), "Nestlé".to_owned()));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        task.abort();
+
+        let result: Result<usize, SnapshotError> = snapshot.load().map(|cache| cache.get_all_brands().count());
+
+        assert_eq!(result, Ok(1), "Expected {:?}, but got {:?}", Ok::<usize, SnapshotError>(1), result);
+    }
+}
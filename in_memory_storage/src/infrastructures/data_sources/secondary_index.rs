@@ -0,0 +1,81 @@
+use std::{
+    borrow::Borrow,
+    collections::{BTreeMap, BTreeSet},
+    ops::RangeBounds,
+};
+
+/// Maps a value derived from an entity (its name, its store, its date...) to the keys of every entity having it.
+#[derive(Debug, Clone)]
+pub(crate) struct SecondaryIndex<V: Ord> {
+    entries: BTreeMap<V, BTreeSet<String>>,
+}
+
+impl<V: Ord> Default for SecondaryIndex<V> {
+    fn default() -> Self {
+        Self { entries: BTreeMap::new() }
+    }
+}
+
+impl<V: Ord> SecondaryIndex<V> {
+    pub fn insert(&mut self, value: V, key: &str) {
+        self.entries.entry(value).or_default().insert(key.to_owned());
+    }
+
+    pub fn remove(&mut self, value: &V, key: &str) {
+        if let Some(keys) = self.entries.get_mut(value) {
+            keys.remove(key);
+
+            if keys.is_empty() {
+                self.entries.remove(value);
+            }
+        }
+    }
+
+    /// Keys indexed under `value`, in key order.
+    pub fn get<'a, Q: Ord + ?Sized>(&'a self, value: &Q) -> impl Iterator<Item = &'a String> + use<'a, V, Q>
+    where
+        V: Borrow<Q>,
+    {
+        self.entries.get(value).into_iter().flatten()
+    }
+
+    /// Keys indexed under any value within `range`, ordered by value then key.
+    pub fn range(&self, range: impl RangeBounds<V>) -> impl Iterator<Item = &String> {
+        self.entries.range(range).flat_map(|(_, keys)| keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SecondaryIndex;
+
+    #[test]
+    fn remove_should_only_drop_the_given_key() {
+        let mut index: SecondaryIndex<String> = SecondaryIndex::default();
+        index.insert("Migros".to_owned(), "b");
+        index.insert("Migros".to_owned(), "a");
+        index.insert("Coop".to_owned(), "c");
+
+        index.remove(&"Migros".to_owned(), "b");
+        index.remove(&"Coop".to_owned(), "c");
+
+        let result: Vec<&String> = index.get("Migros").collect();
+
+        assert_eq!(result, vec!["a"], "Expected {:?}, but got {:?}", vec!["a"], result);
+        assert_eq!(index.get("Coop").count(), 0);
+        assert!(!index.entries.contains_key("Coop"));
+    }
+
+    #[test]
+    fn range_should_list_keys_ordered_by_value() {
+        let mut index: SecondaryIndex<u32> = SecondaryIndex::default();
+        index.insert(3, "c");
+        index.insert(1, "a");
+        index.insert(2, "b");
+        index.insert(5, "e");
+
+        let result: Vec<&String> = index.range(2..5).collect();
+
+        assert_eq!(result, vec!["b", "c"], "Expected {:?}, but got {:?}", vec!["b", "c"], result);
+    }
+}
@@ -0,0 +1,13 @@
+mod brand_repository_json_impl;
+mod category_repository_json_impl;
+#[cfg(test)]
+mod conformance_tests;
+mod product_repository_json_impl;
+mod store_repository_json_impl;
+mod transaction_repository_json_impl;
+
+pub use brand_repository_json_impl::BrandRepositoryJsonImpl;
+pub use category_repository_json_impl::CategoryRepositoryJsonImpl;
+pub use product_repository_json_impl::ProductRepositoryJsonImpl;
+pub use store_repository_json_impl::StoreRepositoryJsonImpl;
+pub use transaction_repository_json_impl::TransactionRepositoryJsonImpl;
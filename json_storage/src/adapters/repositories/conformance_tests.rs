@@ -0,0 +1,55 @@
+use repository_conformance::{repository_conformance_suite, suites::RepositoryBackend};
+
+use crate::{
+    adapters::repositories::{
+        BrandRepositoryJsonImpl, CategoryRepositoryJsonImpl, ProductRepositoryJsonImpl, StoreRepositoryJsonImpl,
+        TransactionRepositoryJsonImpl,
+    },
+    infrastructures::data_sources::JsonDirectory,
+};
+
+struct JsonBackend {
+    directory: JsonDirectory,
+    _root: tempfile::TempDir,
+}
+
+impl JsonBackend {
+    fn new() -> Self {
+        let root: tempfile::TempDir = tempfile::tempdir().unwrap();
+
+        Self {
+            directory: JsonDirectory::open(root.path()).unwrap(),
+            _root: root,
+        }
+    }
+}
+
+impl RepositoryBackend for JsonBackend {
+    type Brands = BrandRepositoryJsonImpl;
+    type Categories = CategoryRepositoryJsonImpl;
+    type Stores = StoreRepositoryJsonImpl;
+    type Products = ProductRepositoryJsonImpl;
+    type Transactions = TransactionRepositoryJsonImpl;
+
+    fn brands(&self) -> Self::Brands {
+        BrandRepositoryJsonImpl::new(self.directory.clone())
+    }
+
+    fn categories(&self) -> Self::Categories {
+        CategoryRepositoryJsonImpl::new(self.directory.clone())
+    }
+
+    fn stores(&self) -> Self::Stores {
+        StoreRepositoryJsonImpl::new(self.directory.clone())
+    }
+
+    fn products(&self) -> Self::Products {
+        ProductRepositoryJsonImpl::new(self.directory.clone())
+    }
+
+    fn transactions(&self) -> Self::Transactions {
+        TransactionRepositoryJsonImpl::new(self.directory.clone())
+    }
+}
+
+repository_conformance_suite!(JsonBackend::new());
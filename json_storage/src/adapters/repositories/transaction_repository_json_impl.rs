@@ -0,0 +1,360 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use uuid_b64::UuidB64;
+
+use expense_tracking::domain::{
+    entities::Transaction,
+    repositories::{TransactionRepository, TransactionRepositoryError, Trashed},
+};
+
+use crate::infrastructures::data_sources::{
+    Document, JsonDirectory, JsonDirectoryError, JsonLedger, TransactionDocument, active, drain_older_than, is_active, trashed,
+};
+
+#[derive(Debug, Clone)]
+pub struct TransactionRepositoryJsonImpl {
+    directory: JsonDirectory,
+}
+
+impl TransactionRepositoryJsonImpl {
+    pub fn new(directory: JsonDirectory) -> Self {
+        Self { directory }
+    }
+}
+
+fn storage_error(error: JsonDirectoryError) -> TransactionRepositoryError {
+    TransactionRepositoryError::UnableToAccessStorage(format!("{:?}", error))
+}
+
+fn check_references(ledger: &JsonLedger, transaction: &TransactionDocument) -> Result<(), TransactionRepositoryError> {
+    if !is_active(&ledger.stores, &transaction.store.to_string()) {
+        return Err(TransactionRepositoryError::StoreNotFound);
+    }
+
+    if transaction
+        .items
+        .iter()
+        .any(|item| !is_active(&ledger.products, &item.product.to_string()))
+    {
+        return Err(TransactionRepositoryError::ProductNotFound);
+    }
+
+    Ok(())
+}
+
+fn find_transaction(ledger: &JsonLedger, id: &UuidB64) -> Option<(TransactionDocument, Transaction)> {
+    let document: &TransactionDocument = ledger.transactions.get(&id.to_string())?;
+
+    Some((document.clone(), ledger.transaction(document)?))
+}
+
+#[async_trait]
+impl TransactionRepository for TransactionRepositoryJsonImpl {
+    async fn create_or_update(&mut self, transaction: &Transaction) -> Result<Option<Transaction>, TransactionRepositoryError> {
+        self.directory
+            .write(|ledger| {
+                let document: TransactionDocument = TransactionDocument::from(transaction);
+
+                check_references(ledger, &document)?;
+
+                match find_transaction(ledger, &transaction.id) {
+                    Some((existing, _)) if existing.deleted_at.is_some() => Err(TransactionRepositoryError::TransactionAlreadyExists),
+                    Some((existing, _)) if existing.version != transaction.version => Err(TransactionRepositoryError::VersionConflict {
+                        current_version: existing.version,
+                    }),
+                    Some((existing, previous)) => {
+                        ledger.transactions.insert(
+                            document.key(),
+                            TransactionDocument {
+                                version: existing.version + 1,
+                                ..document
+                            },
+                        );
+                        Ok(Some(previous))
+                    }
+                    None => {
+                        ledger.transactions.insert(document.key(), document);
+                        Ok(None)
+                    }
+                }
+            })
+            .map_err(storage_error)?
+    }
+
+    async fn retrieve_all(&self) -> Result<Vec<Transaction>, TransactionRepositoryError> {
+        self.directory
+            .read(|ledger| {
+                active(&ledger.transactions)
+                    .filter_map(|transaction| ledger.transaction(transaction))
+                    .collect()
+            })
+            .map_err(storage_error)
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Transaction, TransactionRepositoryError>> {
+        self.directory
+            .stream_active(|ledger| &ledger.transactions, JsonLedger::transaction, storage_error)
+    }
+
+    async fn delete(&mut self, id: &UuidB64) -> Result<Transaction, TransactionRepositoryError> {
+        self.directory
+            .write(|ledger| match find_transaction(ledger, id) {
+                Some((existing, removed)) if existing.deleted_at.is_none() => {
+                    ledger.transactions.insert(
+                        existing.key(),
+                        TransactionDocument {
+                            deleted_at: Some(Utc::now()),
+                            ..existing
+                        },
+                    );
+                    Ok(removed)
+                }
+                _ => Err(TransactionRepositoryError::TransactionNotFound),
+            })
+            .map_err(storage_error)?
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Transaction>>, TransactionRepositoryError> {
+        self.directory
+            .read(|ledger| {
+                trashed(&ledger.transactions)
+                    .filter_map(|(transaction, deleted_at)| Some(Trashed::new(ledger.transaction(transaction)?, deleted_at)))
+                    .collect()
+            })
+            .map_err(storage_error)
+    }
+
+    async fn restore(&mut self, id: &UuidB64) -> Result<Transaction, TransactionRepositoryError> {
+        self.directory
+            .write(|ledger| match find_transaction(ledger, id) {
+                Some((trashed, transaction)) if trashed.deleted_at.is_some() => {
+                    check_references(ledger, &trashed)?;
+
+                    ledger.transactions.insert(
+                        trashed.key(),
+                        TransactionDocument {
+                            deleted_at: None,
+                            ..trashed
+                        },
+                    );
+                    Ok(transaction)
+                }
+                _ => Err(TransactionRepositoryError::TransactionNotInTrash),
+            })
+            .map_err(storage_error)?
+    }
+
+    async fn purge_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<Vec<Transaction>, TransactionRepositoryError> {
+        self.directory
+            .write(|ledger| {
+                let purged: Vec<Transaction> = trashed(&ledger.transactions)
+                    .filter(|(_, deleted_at)| *deleted_at < cutoff)
+                    .filter_map(|(transaction, _)| ledger.transaction(transaction))
+                    .collect();
+
+                drain_older_than(&mut ledger.transactions, cutoff);
+                Ok(purged)
+            })
+            .map_err(storage_error)?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use chrono::{DateTime, Duration, Utc};
+    use futures::StreamExt;
+
+    use super::TransactionRepositoryJsonImpl;
+    use crate::{
+        adapters::repositories::{BrandRepositoryJsonImpl, CategoryRepositoryJsonImpl, ProductRepositoryJsonImpl, StoreRepositoryJsonImpl},
+        infrastructures::data_sources::JsonDirectory,
+    };
+    use expense_tracking::domain::{
+        entities::{Brand, Category, Item, Product, Store, Transaction, Unit},
+        repositories::{
+            BrandRepository, CategoryRepository, DeleteBehaviour, ProductRepository, StoreRepository, StoreRepositoryError,
+            TransactionRepository, TransactionRepositoryError,
+        },
+    };
+
+    struct Fixture {
+        directory: tempfile::TempDir,
+        json_directory: JsonDirectory,
+        store: Store,
+        product: Product,
+    }
+
+    async fn given_fixture() -> Fixture {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let json_directory: JsonDirectory = JsonDirectory::open(directory.path()).unwrap();
+        let brand: Brand = Brand::new("Nestlé".to_owned());
+        let category: Category = Category::new(None, "Dairy".to_owned());
+        let product: Product = Product::new(None, "Chocolate Milk 1L".to_owned(), brand.clone(), category.clone());
+        let store: Store = Store::new(None, "Migros".to_owned());
+
+        BrandRepositoryJsonImpl::new(json_directory.clone()).create(&brand).await.unwrap();
+        CategoryRepositoryJsonImpl::new(json_directory.clone())
+            .create_or_update(&category)
+            .await
+            .unwrap();
+        ProductRepositoryJsonImpl::new(json_directory.clone())
+            .create_or_update(&product)
+            .await
+            .unwrap();
+        StoreRepositoryJsonImpl::new(json_directory.clone())
+            .create_or_update(&store)
+            .await
+            .unwrap();
+
+        Fixture {
+            directory,
+            json_directory,
+            store,
+            product,
+        }
+    }
+
+    fn given_transaction(fixture: &Fixture) -> Transaction {
+        Transaction::new(
+            None,
+            vec![
+                Item::new(None, fixture.product.clone(), Unit::Quantity(2.), 1.95),
+                Item::new(None, fixture.product.clone(), Unit::Liters(1.5), 2.4),
+                Item::new(None, fixture.product.clone(), Unit::None, 0.5),
+            ],
+            fixture.store.clone(),
+            DateTime::parse_from_rfc3339("2025-03-14T09:26:53.589793Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        )
+    }
+
+    #[tokio::test]
+    async fn create_or_update_should_persist_transaction_with_items_in_order_across_reopen() {
+        let fixture: Fixture = given_fixture().await;
+        let transaction: Transaction = given_transaction(&fixture);
+        let mut repository: TransactionRepositoryJsonImpl = TransactionRepositoryJsonImpl::new(fixture.json_directory.clone());
+
+        assert_eq!(repository.create_or_update(&transaction).await, Ok(None));
+
+        drop(repository);
+        drop(fixture.json_directory);
+        let reopened: TransactionRepositoryJsonImpl =
+            TransactionRepositoryJsonImpl::new(JsonDirectory::open(fixture.directory.path()).unwrap());
+
+        let result: Result<Vec<Transaction>, TransactionRepositoryError> = reopened.retrieve_all().await;
+        let expected: Result<Vec<Transaction>, TransactionRepositoryError> = Ok(vec![transaction]);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn create_or_update_should_write_units_as_readable_json() {
+        let fixture: Fixture = given_fixture().await;
+        let mut repository: TransactionRepositoryJsonImpl = TransactionRepositoryJsonImpl::new(fixture.json_directory.clone());
+        repository.create_or_update(&given_transaction(&fixture)).await.unwrap();
+
+        let result: String = fs::read_to_string(fixture.directory.path().join("transactions.json")).unwrap();
+
+        for expected in [
+            "\"datetime\": \"2025-03-14T09:26:53.589793Z\"",
+            "\"kind\": \"quantity\",\n          \"amount\": 2.0",
+            "\"kind\": \"liters\",\n          \"amount\": 1.5",
+            "\"kind\": \"none\"\n",
+        ] {
+            assert!(result.contains(expected), "Expected {:?} in {}", expected, result);
+        }
+    }
+
+    #[tokio::test]
+    async fn create_or_update_existing_transaction_should_replace_items() {
+        let fixture: Fixture = given_fixture().await;
+        let transaction: Transaction = given_transaction(&fixture);
+        let mut repository: TransactionRepositoryJsonImpl = TransactionRepositoryJsonImpl::new(fixture.json_directory.clone());
+        repository.create_or_update(&transaction).await.unwrap();
+
+        let changed: Transaction = Transaction {
+            items: vec![transaction.items[0].clone()],
+            ..transaction.clone()
+        };
+
+        let result: Result<Option<Transaction>, TransactionRepositoryError> = repository.create_or_update(&changed).await;
+        let expected: Result<Option<Transaction>, TransactionRepositoryError> = Ok(Some(transaction));
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+
+        assert_eq!(repository.retrieve_all().await, Ok(vec![Transaction { version: 1, ..changed }]));
+    }
+
+    #[tokio::test]
+    async fn create_or_update_with_unknown_store_should_fail() {
+        let fixture: Fixture = given_fixture().await;
+        let transaction: Transaction = Transaction {
+            store: Store::new(None, "Unknown".to_owned()),
+            ..given_transaction(&fixture)
+        };
+        let mut repository: TransactionRepositoryJsonImpl = TransactionRepositoryJsonImpl::new(fixture.json_directory.clone());
+
+        let result: Result<Option<Transaction>, TransactionRepositoryError> = repository.create_or_update(&transaction).await;
+        let expected: Result<Option<Transaction>, TransactionRepositoryError> = Err(TransactionRepositoryError::StoreNotFound);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn stream_all_should_yield_every_transaction() {
+        let fixture: Fixture = given_fixture().await;
+        let mut repository: TransactionRepositoryJsonImpl = TransactionRepositoryJsonImpl::new(fixture.json_directory.clone());
+        let mut transactions: Vec<Transaction> = (0..20).map(|_| given_transaction(&fixture)).collect();
+
+        for transaction in &transactions {
+            repository.create_or_update(transaction).await.unwrap();
+        }
+        transactions.sort_by_key(|t| t.id.to_string());
+
+        let result: Result<Vec<Transaction>, TransactionRepositoryError> =
+            repository.stream_all().collect::<Vec<_>>().await.into_iter().collect();
+        let expected: Result<Vec<Transaction>, TransactionRepositoryError> = Ok(transactions);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn store_with_active_transactions_should_not_be_deleted_with_restrict() {
+        let fixture: Fixture = given_fixture().await;
+        let mut repository: TransactionRepositoryJsonImpl = TransactionRepositoryJsonImpl::new(fixture.json_directory.clone());
+        repository.create_or_update(&given_transaction(&fixture)).await.unwrap();
+
+        let result: Result<Store, StoreRepositoryError> = StoreRepositoryJsonImpl::new(fixture.json_directory.clone())
+            .delete(&fixture.store.id, DeleteBehaviour::Restrict)
+            .await;
+        let expected: Result<Store, StoreRepositoryError> = Err(StoreRepositoryError::StoreStillReferenced);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn delete_restore_and_purge_should_move_transaction_through_trash() {
+        let fixture: Fixture = given_fixture().await;
+        let transaction: Transaction = given_transaction(&fixture);
+        let mut repository: TransactionRepositoryJsonImpl = TransactionRepositoryJsonImpl::new(fixture.json_directory.clone());
+        repository.create_or_update(&transaction).await.unwrap();
+
+        assert_eq!(repository.delete(&transaction.id).await, Ok(transaction.clone()));
+        assert_eq!(repository.retrieve_all().await, Ok(vec![]));
+        assert_eq!(repository.restore(&transaction.id).await, Ok(transaction.clone()));
+        assert_eq!(
+            repository.restore(&transaction.id).await,
+            Err(TransactionRepositoryError::TransactionNotInTrash)
+        );
+
+        assert_eq!(repository.delete(&transaction.id).await, Ok(transaction.clone()));
+        assert_eq!(
+            repository.purge_older_than(Utc::now() + Duration::days(1)).await,
+            Ok(vec![transaction])
+        );
+        assert_eq!(repository.list_trashed().await, Ok(vec![]));
+    }
+}
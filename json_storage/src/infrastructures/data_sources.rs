@@ -0,0 +1,7 @@
+mod json_directory;
+mod json_documents;
+
+pub use json_directory::JSON_DIRECTORY_FORMAT_VERSION;
+pub use json_directory::JsonDirectory;
+pub use json_directory::JsonDirectoryError;
+pub(crate) use json_documents::*;
@@ -0,0 +1,620 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    ops::Bound,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use futures::stream::BoxStream;
+use serde_json::{Value, json};
+use storage_encryption::infrastructures::encryption::{Cipher, CipherError};
+use storage_files::infrastructures::files::{
+    DirectoryLock, DirectoryLockError, FORMAT_FILE, format_migrations, paged_stream, read_format_version, write_atomically,
+    write_format_version,
+};
+use storage_migrations::infrastructures::migrations::{MigrationPlan, MigrationRegistry};
+
+use crate::infrastructures::data_sources::{Document, JsonLedger};
+
+const BRANDS_FILE: &str = "brands.json";
+const CATEGORIES_FILE: &str = "categories.json";
+const STORES_FILE: &str = "stores.json";
+const PRODUCTS_FILE: &str = "products.json";
+const TRANSACTIONS_FILE: &str = "transactions.json";
+const COLLECTIONS: [(&str, &str); 5] = [
+    ("brands", BRANDS_FILE),
+    ("categories", CATEGORIES_FILE),
+    ("stores", STORES_FILE),
+    ("products", PRODUCTS_FILE),
+    ("transactions", TRANSACTIONS_FILE),
+];
+
+/// Version of the directory layout, recorded in `format.json`. Directories written before it existed are version 0.
+pub const JSON_DIRECTORY_FORMAT_VERSION: u32 = 1;
+
+/// A directory holding one pretty-printed JSON file per collection, sorted by key so that diffs stay small.
+///
+/// The whole ledger is kept in memory and every change is written back atomically, file by file. An exclusive
+/// lock on `.lock` keeps other processes from writing to the same directory while it is open. When opened with a
+/// [`Cipher`] every file is sealed with it as it gets written; [`JsonDirectory::rekey`] rewrites them all at once.
+/// Directories written by an older version are migrated when opened.
+#[derive(Debug, Clone)]
+pub struct JsonDirectory {
+    root: PathBuf,
+    ledger: Arc<Mutex<JsonLedger>>,
+    cipher: Option<Cipher>,
+    _lock: Arc<DirectoryLock>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonDirectoryError {
+    UnableToOpen(String),
+    AlreadyLocked,
+    UnableToRead(String),
+    DanglingReference(String),
+    UnableToWrite(String),
+    PassphraseRequired,
+    WrongPassphrase,
+    UnableToMigrate(String),
+}
+
+impl JsonDirectory {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, JsonDirectoryError> {
+        Self::open_with(path.as_ref(), None, &[])
+    }
+
+    pub fn open_encrypted(path: impl AsRef<Path>, cipher: Cipher) -> Result<Self, JsonDirectoryError> {
+        Self::open_with(path.as_ref(), Some(cipher.clone()), &[cipher])
+    }
+
+    /// Whether any file of the directory is sealed, in which case a passphrase is needed to open it.
+    pub fn is_encrypted(path: impl AsRef<Path>) -> Result<bool, JsonDirectoryError> {
+        for (_, file_name) in COLLECTIONS {
+            match fs::read(path.as_ref().join(file_name)) {
+                Ok(contents) if Cipher::is_sealed(&contents) => return Ok(true),
+                Ok(_) => {}
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(JsonDirectoryError::UnableToRead(format!("{}: {}", file_name, e))),
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Rewrites every file sealed with `new`, or in plaintext when `new` is `None`.
+    ///
+    /// Files already sealed with `new` are accepted too, so an interrupted rekey can simply be run again.
+    pub fn rekey(path: impl AsRef<Path>, current: Option<Cipher>, new: Option<Cipher>) -> Result<(), JsonDirectoryError> {
+        let candidates: Vec<Cipher> = current.into_iter().chain(new.clone()).collect();
+        let directory: JsonDirectory = Self::open_with(path.as_ref(), new, &candidates)?;
+
+        write_all_collections(&directory.root, directory.cipher.as_ref(), &directory.read(JsonLedger::clone)?)
+    }
+
+    /// What opening the directory would migrate, without touching it.
+    pub fn migration_plan(path: impl AsRef<Path>) -> Result<MigrationPlan, JsonDirectoryError> {
+        migrations()
+            .plan(format_version(path.as_ref())?.unwrap_or(JSON_DIRECTORY_FORMAT_VERSION))
+            .map_err(|e| JsonDirectoryError::UnableToMigrate(format!("{:?}", e)))
+    }
+
+    fn open_with(root: &Path, cipher: Option<Cipher>, candidates: &[Cipher]) -> Result<Self, JsonDirectoryError> {
+        let root: PathBuf = root.to_path_buf();
+
+        fs::create_dir_all(&root).map_err(|e| JsonDirectoryError::UnableToOpen(e.to_string()))?;
+
+        let lock: DirectoryLock = DirectoryLock::acquire(&root).map_err(|e| match e {
+            DirectoryLockError::AlreadyLocked => JsonDirectoryError::AlreadyLocked,
+            DirectoryLockError::UnableToLock(details) => JsonDirectoryError::UnableToOpen(details),
+        })?;
+
+        let format_version: Option<u32> = format_version(&root)?;
+        let mut document: Value = json!({});
+        for (name, file_name) in COLLECTIONS {
+            document[name] = read_collection(&root, file_name, candidates)?;
+        }
+
+        let plan: MigrationPlan = migrations()
+            .upgrade(&mut document, format_version.unwrap_or(JSON_DIRECTORY_FORMAT_VERSION))
+            .map_err(|e| JsonDirectoryError::UnableToMigrate(format!("{:?}", e)))?;
+
+        let ledger: JsonLedger = JsonLedger {
+            brands: parse_collection(&mut document, "brands")?,
+            categories: parse_collection(&mut document, "categories")?,
+            stores: parse_collection(&mut document, "stores")?,
+            products: parse_collection(&mut document, "products")?,
+            transactions: parse_collection(&mut document, "transactions")?,
+        };
+
+        if let Some(reference) = ledger.dangling_reference() {
+            return Err(JsonDirectoryError::DanglingReference(reference));
+        }
+
+        if !plan.is_empty() {
+            write_all_collections(&root, cipher.as_ref(), &ledger)?;
+        }
+
+        if format_version != Some(JSON_DIRECTORY_FORMAT_VERSION) {
+            write_format_version(&root, JSON_DIRECTORY_FORMAT_VERSION)
+                .map_err(|e| JsonDirectoryError::UnableToWrite(format!("{}: {}", FORMAT_FILE, e)))?;
+        }
+
+        Ok(Self {
+            root,
+            ledger: Arc::new(Mutex::new(ledger)),
+            cipher,
+            _lock: Arc::new(lock),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.root
+    }
+
+    pub(crate) fn read<T>(&self, f: impl FnOnce(&JsonLedger) -> T) -> Result<T, JsonDirectoryError> {
+        let ledger = self.ledger.lock().map_err(|e| JsonDirectoryError::UnableToRead(e.to_string()))?;
+
+        Ok(f(&ledger))
+    }
+
+    /// Streams the active documents of `collection` through `to_entity`, holding the ledger only while reading one
+    /// [`paged_stream`] page so that writers are not held back by a slow consumer.
+    pub(crate) fn stream_active<'a, D, T, E>(
+        &'a self,
+        collection: fn(&JsonLedger) -> &BTreeMap<String, D>,
+        to_entity: fn(&JsonLedger, &D) -> Option<T>,
+        to_error: fn(JsonDirectoryError) -> E,
+    ) -> BoxStream<'a, Result<T, E>>
+    where
+        D: Document + 'a,
+        T: Send + 'a,
+        E: Send + 'a,
+    {
+        paged_stream(move |after: Option<&str>, limit: usize| {
+            self.read(|ledger| {
+                let after: Bound<&str> = after.map_or(Bound::Unbounded, Bound::Excluded);
+
+                collection(ledger)
+                    .range::<str, _>((after, Bound::Unbounded))
+                    .filter(|(_, document)| document.deleted_at().is_none())
+                    .take(limit)
+                    .map(|(key, document)| (key.clone(), to_entity(ledger, document)))
+                    .collect()
+            })
+            .map_err(to_error)
+        })
+    }
+
+    /// Applies `f` to a copy of the ledger and, when it succeeds, persists the collections it changed before
+    /// making the copy current. A failing `f` leaves both the files and the in-memory ledger untouched.
+    pub(crate) fn write<T, E>(&self, f: impl FnOnce(&mut JsonLedger) -> Result<T, E>) -> Result<Result<T, E>, JsonDirectoryError> {
+        let mut ledger = self.ledger.lock().map_err(|e| JsonDirectoryError::UnableToWrite(e.to_string()))?;
+        let mut staged: JsonLedger = ledger.clone();
+
+        let result: Result<T, E> = f(&mut staged);
+
+        if result.is_ok() {
+            self.persist_changes(&ledger, &staged)?;
+            *ledger = staged;
+        }
+
+        Ok(result)
+    }
+
+    /// Writes the collections `staged` changed. Every file is replaced atomically, but not all of them at once, so the
+    /// order keeps what is on disk consistent should the process stop in between: a change removing documents writes
+    /// the collections referring to others first, from transactions to brands, any other change the referenced ones
+    /// first.
+    fn persist_changes(&self, current: &JsonLedger, staged: &JsonLedger) -> Result<(), JsonDirectoryError> {
+        let cipher: Option<&Cipher> = self.cipher.as_ref();
+        let writes: [&dyn Fn() -> Result<(), JsonDirectoryError>; 5] = [
+            &|| write_changed_collection(&self.root, BRANDS_FILE, cipher, &current.brands, &staged.brands),
+            &|| write_changed_collection(&self.root, CATEGORIES_FILE, cipher, &current.categories, &staged.categories),
+            &|| write_changed_collection(&self.root, STORES_FILE, cipher, &current.stores, &staged.stores),
+            &|| write_changed_collection(&self.root, PRODUCTS_FILE, cipher, &current.products, &staged.products),
+            &|| write_changed_collection(&self.root, TRANSACTIONS_FILE, cipher, &current.transactions, &staged.transactions),
+        ];
+
+        if removes_documents(current, staged) {
+            writes.iter().rev().try_for_each(|write| write())
+        } else {
+            writes.iter().try_for_each(|write| write())
+        }
+    }
+}
+
+fn removes_documents(current: &JsonLedger, staged: &JsonLedger) -> bool {
+    fn removes<D>(current: &BTreeMap<String, D>, staged: &BTreeMap<String, D>) -> bool {
+        current.keys().any(|key| !staged.contains_key(key))
+    }
+
+    removes(&current.brands, &staged.brands)
+        || removes(&current.categories, &staged.categories)
+        || removes(&current.stores, &staged.stores)
+        || removes(&current.products, &staged.products)
+        || removes(&current.transactions, &staged.transactions)
+}
+
+fn migrations() -> MigrationRegistry {
+    format_migrations(JSON_DIRECTORY_FORMAT_VERSION)
+}
+
+/// The version recorded in `format.json`, a directory holding collections without it predates versioning.
+fn format_version(root: &Path) -> Result<Option<u32>, JsonDirectoryError> {
+    read_format_version(root, || COLLECTIONS.iter().any(|(_, file_name)| root.join(file_name).exists()))
+        .map_err(|e| JsonDirectoryError::UnableToRead(format!("{}: {}", FORMAT_FILE, e)))
+}
+
+fn read_collection(root: &Path, file_name: &str, candidates: &[Cipher]) -> Result<Value, JsonDirectoryError> {
+    let contents: Vec<u8> = match fs::read(root.join(file_name)) {
+        Ok(contents) if Cipher::is_sealed(&contents) => unseal(&contents, file_name, candidates)?,
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(json!([])),
+        Err(e) => return Err(JsonDirectoryError::UnableToRead(format!("{}: {}", file_name, e))),
+    };
+
+    serde_json::from_slice(&contents).map_err(|e| JsonDirectoryError::UnableToRead(format!("{}: {}", file_name, e)))
+}
+
+fn parse_collection<D: Document>(document: &mut Value, name: &str) -> Result<BTreeMap<String, D>, JsonDirectoryError> {
+    let documents: Vec<D> =
+        serde_json::from_value(document[name].take()).map_err(|e| JsonDirectoryError::UnableToRead(format!("{}: {}", name, e)))?;
+
+    Ok(documents.into_iter().map(|document| (document.key(), document)).collect())
+}
+
+fn unseal(contents: &[u8], file_name: &str, candidates: &[Cipher]) -> Result<Vec<u8>, JsonDirectoryError> {
+    let mut error: JsonDirectoryError = JsonDirectoryError::PassphraseRequired;
+
+    for cipher in candidates {
+        match cipher.open(contents) {
+            Ok(plaintext) => return Ok(plaintext),
+            Err(CipherError::WrongPassphrase) => error = JsonDirectoryError::WrongPassphrase,
+            Err(e) => return Err(JsonDirectoryError::UnableToRead(format!("{}: {:?}", file_name, e))),
+        }
+    }
+
+    Err(error)
+}
+
+fn write_all_collections(root: &Path, cipher: Option<&Cipher>, ledger: &JsonLedger) -> Result<(), JsonDirectoryError> {
+    write_collection(root, BRANDS_FILE, cipher, &ledger.brands)?;
+    write_collection(root, CATEGORIES_FILE, cipher, &ledger.categories)?;
+    write_collection(root, STORES_FILE, cipher, &ledger.stores)?;
+    write_collection(root, PRODUCTS_FILE, cipher, &ledger.products)?;
+    write_collection(root, TRANSACTIONS_FILE, cipher, &ledger.transactions)
+}
+
+fn write_changed_collection<D: Document + PartialEq>(
+    root: &Path,
+    file_name: &str,
+    cipher: Option<&Cipher>,
+    current: &BTreeMap<String, D>,
+    staged: &BTreeMap<String, D>,
+) -> Result<(), JsonDirectoryError> {
+    if current == staged {
+        return Ok(());
+    }
+
+    write_collection(root, file_name, cipher, staged)
+}
+
+fn write_collection<D: Document>(
+    root: &Path,
+    file_name: &str,
+    cipher: Option<&Cipher>,
+    documents: &BTreeMap<String, D>,
+) -> Result<(), JsonDirectoryError> {
+    let mut contents: Vec<u8> = serde_json::to_vec_pretty(&documents.values().collect::<Vec<&D>>())
+        .map_err(|e| JsonDirectoryError::UnableToWrite(format!("{}: {}", file_name, e)))?;
+    contents.push(b'\n');
+
+    if let Some(cipher) = cipher {
+        contents = cipher
+            .seal(&contents)
+            .map_err(|e| JsonDirectoryError::UnableToWrite(format!("{}: {:?}", file_name, e)))?;
+    }
+
+    write_atomically(root, file_name, &contents).map_err(|e| JsonDirectoryError::UnableToWrite(format!("{}: {}", file_name, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, fs};
+
+    use chrono::Utc;
+    use futures::{StreamExt, stream::BoxStream};
+    use uuid_b64::UuidB64;
+
+    use storage_encryption::infrastructures::encryption::{Cipher, KeyDerivationCost};
+
+    use storage_files::infrastructures::files::FORMAT_FILE;
+
+    use super::{BRANDS_FILE, JSON_DIRECTORY_FORMAT_VERSION, JsonDirectory, JsonDirectoryError, PRODUCTS_FILE};
+    use crate::infrastructures::data_sources::{BrandDocument, CategoryDocument, ProductDocument};
+
+    fn given_fixture(version: u32) -> tempfile::TempDir {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let fixture: std::path::PathBuf =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("fixtures/json_directory/v{}", version));
+
+        for entry in fs::read_dir(fixture).unwrap() {
+            let entry: fs::DirEntry = entry.unwrap();
+            fs::copy(entry.path(), directory.path().join(entry.file_name())).unwrap();
+        }
+
+        directory
+    }
+
+    fn brand_document(name: &str) -> BrandDocument {
+        BrandDocument {
+            name: name.to_owned(),
+            version: 0,
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn open_should_refuse_a_directory_locked_by_another_writer() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let _writer: JsonDirectory = JsonDirectory::open(directory.path()).unwrap();
+
+        let result: Result<JsonDirectory, JsonDirectoryError> = JsonDirectory::open(directory.path());
+
+        assert!(
+            matches!(result, Err(JsonDirectoryError::AlreadyLocked)),
+            "Expected AlreadyLocked, but got {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn open_should_release_the_lock_when_dropped() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        drop(JsonDirectory::open(directory.path()).unwrap());
+
+        let result: Result<JsonDirectory, JsonDirectoryError> = JsonDirectory::open(directory.path());
+
+        assert!(result.is_ok(), "Expected Ok, but got {:?}", result);
+    }
+
+    #[test]
+    fn write_should_persist_sorted_pretty_json_and_reload_it() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let json_directory: JsonDirectory = JsonDirectory::open(directory.path()).unwrap();
+
+        json_directory
+            .write(|ledger| {
+                for name in ["Nestlé", "Danone"] {
+                    ledger.brands.insert(name.to_owned(), brand_document(name));
+                }
+                Ok::<(), ()>(())
+            })
+            .unwrap()
+            .unwrap();
+
+        let result: String = fs::read_to_string(directory.path().join(BRANDS_FILE)).unwrap();
+        let expected: &str =
+            "[\n  {\n    \"name\": \"Danone\",\n    \"version\": 0\n  },\n  {\n    \"name\": \"Nestlé\",\n    \"version\": 0\n  }\n]\n";
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert!(!directory.path().join(PRODUCTS_FILE).exists());
+
+        drop(json_directory);
+        let reloaded: BTreeMap<String, BrandDocument> = JsonDirectory::open(directory.path())
+            .unwrap()
+            .read(|ledger| ledger.brands.clone())
+            .unwrap();
+        assert_eq!(reloaded.keys().collect::<Vec<&String>>(), vec!["Danone", "Nestlé"]);
+    }
+
+    #[tokio::test]
+    async fn stream_active_should_page_through_documents_written_while_streaming() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let json_directory: JsonDirectory = JsonDirectory::open(directory.path()).unwrap();
+        json_directory
+            .write(|ledger| {
+                for i in 0..250 {
+                    let name: String = format!("Brand {:03}", i);
+                    ledger.brands.insert(name.clone(), brand_document(&name));
+                }
+                Ok::<(), ()>(())
+            })
+            .unwrap()
+            .unwrap();
+
+        let mut stream: BoxStream<'_, Result<String, JsonDirectoryError>> =
+            json_directory.stream_active(|ledger| &ledger.brands, |_, brand| Some(brand.name.clone()), |e| e);
+        let first: String = stream.next().await.unwrap().unwrap();
+        json_directory
+            .write(|ledger| {
+                ledger.brands.get_mut("Brand 200").unwrap().deleted_at = Some(Utc::now());
+                ledger.brands.insert("Brand 999".to_owned(), brand_document("Brand 999"));
+                Ok::<(), ()>(())
+            })
+            .unwrap()
+            .unwrap();
+        let rest: Vec<Result<String, JsonDirectoryError>> = stream.collect().await;
+
+        let result: Result<Vec<String>, JsonDirectoryError> = std::iter::once(Ok(first)).chain(rest).collect();
+        let expected: Result<Vec<String>, JsonDirectoryError> = Ok((0..250)
+            .filter(|i| *i != 200)
+            .map(|i| format!("Brand {:03}", i))
+            .chain(std::iter::once("Brand 999".to_owned()))
+            .collect());
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[test]
+    fn write_should_leave_files_untouched_when_the_change_fails() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let json_directory: JsonDirectory = JsonDirectory::open(directory.path()).unwrap();
+
+        let result: Result<Result<(), &str>, JsonDirectoryError> = json_directory.write(|ledger| {
+            ledger.brands.insert("Nestlé".to_owned(), brand_document("Nestlé"));
+            Err("rejected")
+        });
+
+        assert_eq!(result, Ok(Err("rejected")));
+        assert!(!directory.path().join(BRANDS_FILE).exists());
+        assert_eq!(json_directory.read(|ledger| ledger.brands.len()), Ok(0));
+    }
+
+    #[test]
+    fn open_should_reject_files_with_dangling_references() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let product: ProductDocument = ProductDocument {
+            id: UuidB64::new(),
+            name: "Milk".to_owned(),
+            brand: "Missing".to_owned(),
+            category: UuidB64::new(),
+            version: 0,
+            deleted_at: None,
+        };
+        fs::write(directory.path().join(PRODUCTS_FILE), serde_json::to_string(&[product]).unwrap()).unwrap();
+
+        let result: Result<JsonDirectory, JsonDirectoryError> = JsonDirectory::open(directory.path());
+
+        assert!(
+            matches!(result, Err(JsonDirectoryError::DanglingReference(ref reference)) if reference == "Missing"),
+            "Expected DanglingReference, but got {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn write_should_leave_a_reopenable_directory_when_stopped_halfway_through_a_removal() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let json_directory: JsonDirectory = JsonDirectory::open(directory.path()).unwrap();
+        let category: CategoryDocument = CategoryDocument {
+            id: UuidB64::new(),
+            name: "Dairy".to_owned(),
+            version: 0,
+            deleted_at: None,
+        };
+        let product: ProductDocument = ProductDocument {
+            id: UuidB64::new(),
+            name: "Milk".to_owned(),
+            brand: "Nestlé".to_owned(),
+            category: category.id,
+            version: 0,
+            deleted_at: None,
+        };
+        json_directory
+            .write(|ledger| {
+                ledger.brands.insert("Nestlé".to_owned(), brand_document("Nestlé"));
+                ledger.categories.insert(category.id.to_string(), category.clone());
+                ledger.products.insert(product.id.to_string(), product.clone());
+                Ok::<(), ()>(())
+            })
+            .unwrap()
+            .unwrap();
+
+        // A directory in the way of the temporary file makes the write stop before the products are replaced.
+        fs::create_dir(directory.path().join(format!(".{}.tmp", PRODUCTS_FILE))).unwrap();
+        let result: Result<Result<(), ()>, JsonDirectoryError> = json_directory.write(|ledger| {
+            ledger.brands.remove("Nestlé");
+            ledger.products.remove(&product.id.to_string());
+            Ok(())
+        });
+        assert!(
+            matches!(result, Err(JsonDirectoryError::UnableToWrite(_))),
+            "Expected UnableToWrite, but got {:?}",
+            result
+        );
+        drop(json_directory);
+
+        let result: Result<Vec<String>, JsonDirectoryError> =
+            JsonDirectory::open(directory.path()).and_then(|reopened| reopened.read(|ledger| ledger.brands.keys().cloned().collect()));
+        let expected: Result<Vec<String>, JsonDirectoryError> = Ok(vec!["Nestlé".to_owned()]);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[test]
+    fn rekey_should_reseal_every_file_with_the_new_passphrase() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let cheap: KeyDerivationCost = KeyDerivationCost {
+            memory_kib: 8,
+            iterations: 1,
+            parallelism: 1,
+        };
+        let json_directory: JsonDirectory = JsonDirectory::open(directory.path()).unwrap();
+        json_directory
+            .write(|ledger| {
+                ledger.brands.insert("Nestlé".to_owned(), brand_document("Nestlé"));
+                Ok::<(), ()>(())
+            })
+            .unwrap()
+            .unwrap();
+        drop(json_directory);
+
+        JsonDirectory::rekey(directory.path(), None, Some(Cipher::new("correct horse").with_cost(cheap))).unwrap();
+
+        assert_eq!(JsonDirectory::is_encrypted(directory.path()), Ok(true));
+        assert!(!fs::read(directory.path().join(BRANDS_FILE)).unwrap().starts_with(b"["));
+        assert_eq!(
+            JsonDirectory::open(directory.path()).err(),
+            Some(JsonDirectoryError::PassphraseRequired)
+        );
+        assert_eq!(
+            JsonDirectory::open_encrypted(directory.path(), Cipher::new("battery staple")).err(),
+            Some(JsonDirectoryError::WrongPassphrase)
+        );
+
+        JsonDirectory::rekey(
+            directory.path(),
+            Some(Cipher::new("correct horse")),
+            Some(Cipher::new("battery staple").with_cost(cheap)),
+        )
+        .unwrap();
+
+        let result: Result<usize, JsonDirectoryError> = JsonDirectory::open_encrypted(directory.path(), Cipher::new("battery staple"))
+            .and_then(|json_directory| json_directory.read(|ledger| ledger.brands.len()));
+        let expected: Result<usize, JsonDirectoryError> = Ok(1);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[test]
+    fn open_should_migrate_every_historical_fixture() {
+        for version in 0..=JSON_DIRECTORY_FORMAT_VERSION {
+            let directory: tempfile::TempDir = given_fixture(version);
+
+            let plan: usize = JsonDirectory::migration_plan(directory.path()).unwrap().steps.len();
+            assert_eq!(
+                plan,
+                (JSON_DIRECTORY_FORMAT_VERSION - version) as usize,
+                "Unexpected plan for v{}",
+                version
+            );
+
+            let result: Result<(usize, usize), JsonDirectoryError> = JsonDirectory::open(directory.path()).and_then(|json_directory| {
+                json_directory.read(|ledger| (ledger.brands.len(), ledger.transactions.values().map(|t| t.items.len()).sum()))
+            });
+            let expected: Result<(usize, usize), JsonDirectoryError> = Ok((1, 2));
+
+            assert_eq!(result, expected, "Expected {:?} for v{}, but got {:?}", expected, version, result);
+            assert!(JsonDirectory::migration_plan(directory.path()).unwrap().is_empty());
+            assert!(
+                fs::read_to_string(directory.path().join(FORMAT_FILE))
+                    .unwrap()
+                    .contains(&format!("\"format_version\": {}", JSON_DIRECTORY_FORMAT_VERSION))
+            );
+        }
+    }
+
+    #[test]
+    fn open_should_refuse_a_directory_from_a_newer_version() {
+        let directory: tempfile::TempDir = given_fixture(JSON_DIRECTORY_FORMAT_VERSION);
+        fs::write(directory.path().join(FORMAT_FILE), "{ \"format_version\": 99 }").unwrap();
+
+        let result: Result<JsonDirectory, JsonDirectoryError> = JsonDirectory::open(directory.path());
+
+        assert!(
+            matches!(result, Err(JsonDirectoryError::UnableToMigrate(_))),
+            "Expected UnableToMigrate, but got {:?}",
+            result
+        );
+    }
+}
@@ -0,0 +1,406 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use uuid_b64::UuidB64;
+
+use expense_tracking::domain::entities::{Brand, Category, Item, Product, Store, Transaction, Unit};
+
+pub(crate) trait Document: std::fmt::Debug + Clone + PartialEq + Serialize + DeserializeOwned {
+    fn key(&self) -> String;
+
+    fn deleted_at(&self) -> Option<DateTime<Utc>>;
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct BrandDocument {
+    pub name: String,
+    pub version: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct CategoryDocument {
+    pub id: UuidB64,
+    pub name: String,
+    pub version: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct StoreDocument {
+    pub id: UuidB64,
+    pub name: String,
+    pub version: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ProductDocument {
+    pub id: UuidB64,
+    pub name: String,
+    pub brand: String,
+    pub category: UuidB64,
+    pub version: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct TransactionDocument {
+    pub id: UuidB64,
+    pub store: UuidB64,
+    pub datetime: DateTime<Utc>,
+    pub version: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub items: Vec<ItemDocument>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ItemDocument {
+    pub id: UuidB64,
+    pub product: UuidB64,
+    pub unit: UnitDocument,
+    pub unitary_price: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "amount", rename_all = "snake_case")]
+pub(crate) enum UnitDocument {
+    None,
+    Quantity(f64),
+    Kilograms(f64),
+    Liters(f64),
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct JsonLedger {
+    pub brands: BTreeMap<String, BrandDocument>,
+    pub categories: BTreeMap<String, CategoryDocument>,
+    pub stores: BTreeMap<String, StoreDocument>,
+    pub products: BTreeMap<String, ProductDocument>,
+    pub transactions: BTreeMap<String, TransactionDocument>,
+}
+
+impl Document for BrandDocument {
+    fn key(&self) -> String {
+        self.name.clone()
+    }
+
+    fn deleted_at(&self) -> Option<DateTime<Utc>> {
+        self.deleted_at
+    }
+}
+
+impl Document for CategoryDocument {
+    fn key(&self) -> String {
+        self.id.to_string()
+    }
+
+    fn deleted_at(&self) -> Option<DateTime<Utc>> {
+        self.deleted_at
+    }
+}
+
+impl Document for StoreDocument {
+    fn key(&self) -> String {
+        self.id.to_string()
+    }
+
+    fn deleted_at(&self) -> Option<DateTime<Utc>> {
+        self.deleted_at
+    }
+}
+
+impl Document for ProductDocument {
+    fn key(&self) -> String {
+        self.id.to_string()
+    }
+
+    fn deleted_at(&self) -> Option<DateTime<Utc>> {
+        self.deleted_at
+    }
+}
+
+impl Document for TransactionDocument {
+    fn key(&self) -> String {
+        self.id.to_string()
+    }
+
+    fn deleted_at(&self) -> Option<DateTime<Utc>> {
+        self.deleted_at
+    }
+}
+
+impl From<&Brand> for BrandDocument {
+    fn from(brand: &Brand) -> Self {
+        Self {
+            name: brand.name.clone(),
+            version: brand.version,
+            deleted_at: None,
+        }
+    }
+}
+
+impl From<&BrandDocument> for Brand {
+    fn from(document: &BrandDocument) -> Self {
+        Brand {
+            version: document.version,
+            ..Brand::new(document.name.clone())
+        }
+    }
+}
+
+impl From<&Category> for CategoryDocument {
+    fn from(category: &Category) -> Self {
+        Self {
+            id: category.id,
+            name: category.name.clone(),
+            version: category.version,
+            deleted_at: None,
+        }
+    }
+}
+
+impl From<&CategoryDocument> for Category {
+    fn from(document: &CategoryDocument) -> Self {
+        Category {
+            version: document.version,
+            ..Category::new(Some(document.id), document.name.clone())
+        }
+    }
+}
+
+impl From<&Store> for StoreDocument {
+    fn from(store: &Store) -> Self {
+        Self {
+            id: store.id,
+            name: store.name.clone(),
+            version: store.version,
+            deleted_at: None,
+        }
+    }
+}
+
+impl From<&StoreDocument> for Store {
+    fn from(document: &StoreDocument) -> Self {
+        Store {
+            version: document.version,
+            ..Store::new(Some(document.id), document.name.clone())
+        }
+    }
+}
+
+impl From<&Product> for ProductDocument {
+    fn from(product: &Product) -> Self {
+        Self {
+            id: product.id,
+            name: product.name.clone(),
+            brand: product.brand.name.clone(),
+            category: product.category.id,
+            version: product.version,
+            deleted_at: None,
+        }
+    }
+}
+
+impl From<&Transaction> for TransactionDocument {
+    fn from(transaction: &Transaction) -> Self {
+        Self {
+            id: transaction.id,
+            store: transaction.store.id,
+            datetime: transaction.datetime,
+            version: transaction.version,
+            deleted_at: None,
+            items: transaction.items.iter().map(ItemDocument::from).collect(),
+        }
+    }
+}
+
+impl From<&Item> for ItemDocument {
+    fn from(item: &Item) -> Self {
+        Self {
+            id: item.id(),
+            product: item.product().id,
+            unit: UnitDocument::from(item.unit()),
+            unitary_price: item.unitary_price(),
+        }
+    }
+}
+
+impl From<&Unit> for UnitDocument {
+    fn from(unit: &Unit) -> Self {
+        match unit {
+            Unit::None => UnitDocument::None,
+            Unit::Quantity(amount) => UnitDocument::Quantity(*amount),
+            Unit::Kilograms(weight) => UnitDocument::Kilograms(*weight),
+            Unit::Liters(volume) => UnitDocument::Liters(*volume),
+        }
+    }
+}
+
+impl From<&UnitDocument> for Unit {
+    fn from(document: &UnitDocument) -> Self {
+        match document {
+            UnitDocument::None => Unit::None,
+            UnitDocument::Quantity(amount) => Unit::Quantity(*amount),
+            UnitDocument::Kilograms(weight) => Unit::Kilograms(*weight),
+            UnitDocument::Liters(volume) => Unit::Liters(*volume),
+        }
+    }
+}
+
+impl JsonLedger {
+    pub fn product(&self, document: &ProductDocument) -> Option<Product> {
+        let brand: &BrandDocument = self.brands.get(&document.brand)?;
+        let category: &CategoryDocument = self.categories.get(&document.category.to_string())?;
+
+        Some(Product {
+            version: document.version,
+            ..Product::new(Some(document.id), document.name.clone(), brand.into(), category.into())
+        })
+    }
+
+    pub fn transaction(&self, document: &TransactionDocument) -> Option<Transaction> {
+        let store: &StoreDocument = self.stores.get(&document.store.to_string())?;
+        let items: Vec<Item> = document
+            .items
+            .iter()
+            .map(|item| {
+                let product: Product = self.product(self.products.get(&item.product.to_string())?)?;
+
+                Some(Item::new(Some(item.id), product, Unit::from(&item.unit), item.unitary_price))
+            })
+            .collect::<Option<Vec<Item>>>()?;
+
+        Some(Transaction {
+            version: document.version,
+            ..Transaction::new(Some(document.id), items, store.into(), document.datetime)
+        })
+    }
+
+    pub fn dangling_reference(&self) -> Option<String> {
+        let product_reference = self.products.values().find_map(|product| {
+            if !self.brands.contains_key(&product.brand) {
+                Some(product.brand.clone())
+            } else if !self.categories.contains_key(&product.category.to_string()) {
+                Some(product.category.to_string())
+            } else {
+                None
+            }
+        });
+
+        product_reference.or_else(|| {
+            self.transactions.values().find_map(|transaction| {
+                if !self.stores.contains_key(&transaction.store.to_string()) {
+                    return Some(transaction.store.to_string());
+                }
+
+                transaction
+                    .items
+                    .iter()
+                    .map(|item| item.product.to_string())
+                    .find(|product| !self.products.contains_key(product))
+            })
+        })
+    }
+
+    /// Trashes the active products matching `referencing` at `now`, along with the active transactions holding them.
+    pub fn cascade_products(&mut self, referencing: impl Fn(&ProductDocument) -> bool, now: DateTime<Utc>) {
+        let affected: BTreeSet<String> = active(&self.products).filter(|p| referencing(p)).map(Document::key).collect();
+
+        self.cascade_transactions(&affected, now);
+
+        for product in self.products.values_mut().filter(|p| affected.contains(&p.key())) {
+            product.deleted_at = Some(now);
+        }
+    }
+
+    /// Trashes the active transactions holding one of `products` at `now`.
+    pub fn cascade_transactions(&mut self, products: &BTreeSet<String>, now: DateTime<Utc>) {
+        for transaction in self
+            .transactions
+            .values_mut()
+            .filter(|t| t.deleted_at.is_none() && holds_any(t, products))
+        {
+            transaction.deleted_at = Some(now);
+        }
+    }
+
+    /// Restores the products matching `referencing` that were trashed at `deleted_at`, once their brand and category
+    /// are active, then the transactions trashed with them.
+    pub fn restore_products(&mut self, referencing: impl Fn(&ProductDocument) -> bool, deleted_at: DateTime<Utc>) {
+        let restored: BTreeSet<String> = self
+            .products
+            .values()
+            .filter(|p| referencing(p) && p.deleted_at == Some(deleted_at))
+            .filter(|p| is_active(&self.brands, &p.brand) && is_active(&self.categories, &p.category.to_string()))
+            .map(Document::key)
+            .collect();
+
+        for product in self.products.values_mut().filter(|p| restored.contains(&p.key())) {
+            product.deleted_at = None;
+        }
+
+        self.restore_transactions(|t| holds_any(t, &restored), deleted_at);
+    }
+
+    /// Restores the transactions matching `matching` that were trashed at `deleted_at`, leaving those whose store or
+    /// products are still in the trash.
+    pub fn restore_transactions(&mut self, matching: impl Fn(&TransactionDocument) -> bool, deleted_at: DateTime<Utc>) {
+        let restored: BTreeSet<String> = self
+            .transactions
+            .values()
+            .filter(|t| matching(t) && t.deleted_at == Some(deleted_at) && is_active(&self.stores, &t.store.to_string()))
+            .filter(|t| t.items.iter().all(|item| is_active(&self.products, &item.product.to_string())))
+            .map(Document::key)
+            .collect();
+
+        for transaction in self.transactions.values_mut().filter(|t| restored.contains(&t.key())) {
+            transaction.deleted_at = None;
+        }
+    }
+
+    pub fn remove_products(&mut self, referencing: impl Fn(&ProductDocument) -> bool) {
+        let removed: BTreeSet<String> = self.products.values().filter(|p| referencing(p)).map(Document::key).collect();
+
+        self.remove_transactions_holding(&removed);
+        self.products.retain(|key, _| !removed.contains(key));
+    }
+
+    /// Removes the transactions holding one of `products`, trashed along with them.
+    pub fn remove_transactions_holding(&mut self, products: &BTreeSet<String>) {
+        self.transactions.retain(|_, transaction| !holds_any(transaction, products));
+    }
+}
+
+fn holds_any(transaction: &TransactionDocument, products: &BTreeSet<String>) -> bool {
+    transaction.items.iter().any(|item| products.contains(&item.product.to_string()))
+}
+
+pub(crate) fn is_active<D: Document>(documents: &BTreeMap<String, D>, key: &str) -> bool {
+    documents.get(key).is_some_and(|document| document.deleted_at().is_none())
+}
+
+pub(crate) fn active<D: Document>(documents: &BTreeMap<String, D>) -> impl Iterator<Item = &D> {
+    documents.values().filter(|document| document.deleted_at().is_none())
+}
+
+pub(crate) fn trashed<D: Document>(documents: &BTreeMap<String, D>) -> impl Iterator<Item = (&D, DateTime<Utc>)> {
+    documents
+        .values()
+        .filter_map(|document| document.deleted_at().map(|deleted_at| (document, deleted_at)))
+}
+
+pub(crate) fn drain_older_than<D: Document>(documents: &mut BTreeMap<String, D>, cutoff: DateTime<Utc>) -> Vec<D> {
+    let expired: Vec<String> = trashed(documents)
+        .filter(|(_, deleted_at)| *deleted_at < cutoff)
+        .map(|(document, _)| document.key())
+        .collect();
+
+    expired.iter().filter_map(|key| documents.remove(key)).collect()
+}
@@ -0,0 +1,152 @@
+use std::{
+    env,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    process,
+    sync::Arc,
+    time::Duration,
+};
+
+use clap::Parser;
+use in_memory_storage::{
+    adapters::repositories::{
+        BrandRepositoryInMemoryImpl, CategoryRepositoryInMemoryImpl, ProductRepositoryInMemoryImpl, StoreRepositoryInMemoryImpl,
+        TransactionRepositoryInMemoryImpl,
+    },
+    infrastructures::data_sources::{InMemoryCache, InMemorySnapshot},
+};
+use remote_storage::infrastructures::server::{ServedRepositories, repository_router};
+use sqlite_storage::{
+    adapters::repositories::{
+        BrandRepositorySqliteImpl, CategoryRepositorySqliteImpl, ProductRepositorySqliteImpl, StoreRepositorySqliteImpl,
+        TransactionRepositorySqliteImpl,
+    },
+    infrastructures::data_sources::SqliteDatabase,
+};
+use storage_encryption::infrastructures::encryption::Cipher;
+use tokio::{net::TcpListener, sync::RwLock};
+
+const PASSPHRASE_VARIABLE: &str = "FROSTY_PINE_PASSPHRASE";
+const TOKEN_VARIABLE: &str = "FROSTY_PINE_TOKEN";
+const SNAPSHOT_PERIOD: Duration = Duration::from_secs(60);
+
+#[derive(Parser, Debug)]
+#[command(name = "frosty-pine-server")]
+#[command(version, about = "Shares one ledger with every front-end through a JSON API", long_about = None)]
+struct ServerArgs {
+    /// Address to listen on, use 0.0.0.0 to accept front-ends from the local network
+    #[arg(long, default_value = "127.0.0.1:7878")]
+    address: SocketAddr,
+
+    /// SQLite file to keep data in, data only lives in memory when omitted
+    #[arg(long)]
+    database: Option<PathBuf>,
+
+    /// Snapshot file the in-memory data is loaded from at startup and saved back to, ignored with --database
+    #[arg(long, conflicts_with = "database")]
+    snapshot: Option<PathBuf>,
+
+    /// Token front-ends must send to be served, read from FROSTY_PINE_TOKEN when omitted
+    #[arg(long)]
+    token: Option<String>,
+}
+
+#[tokio::main()]
+async fn main() {
+    let server_args: ServerArgs = ServerArgs::parse();
+    let access_token: String = access_token(server_args.token.clone());
+
+    let snapshot: Option<InMemorySnapshot> = server_args.snapshot.as_ref().map(|path| {
+        let snapshot: InMemorySnapshot = InMemorySnapshot::new(path);
+        match snapshot.is_encrypted() {
+            Ok(true) => snapshot.with_cipher(passphrase_cipher()),
+            Ok(false) => snapshot,
+            Err(e) => exit_with(&format!("Unable to load snapshot: {:?}", e)),
+        }
+    });
+    let cache: Arc<RwLock<InMemoryCache>> = Arc::new(RwLock::new(match &snapshot {
+        Some(snapshot) => snapshot
+            .load()
+            .unwrap_or_else(|e| exit_with(&format!("Unable to load snapshot: {:?}", e))),
+        None => InMemoryCache::new(),
+    }));
+
+    let repositories: ServedRepositories = match &server_args.database {
+        Some(path) => sqlite_repositories(open_database(path)),
+        None => in_memory_repositories(&cache),
+    };
+    let periodic_save = snapshot
+        .as_ref()
+        .map(|snapshot| snapshot.spawn_periodic_save(Arc::clone(&cache), SNAPSHOT_PERIOD));
+
+    let listener: TcpListener = TcpListener::bind(server_args.address)
+        .await
+        .unwrap_or_else(|e| exit_with(&format!("Unable to listen on {}: {}", server_args.address, e)));
+    println!("Serving on http://{}", server_args.address);
+
+    axum::serve(listener, repository_router(repositories, access_token))
+        .with_graceful_shutdown(async {
+            tokio::signal::ctrl_c().await.ok();
+        })
+        .await
+        .expect("Unable to serve");
+
+    if let Some(periodic_save) = periodic_save {
+        periodic_save.abort();
+    }
+
+    if let Some(snapshot) = snapshot {
+        snapshot.save(&*cache.read().await).expect("Unable to save snapshot");
+    }
+}
+
+fn open_database(path: &Path) -> SqliteDatabase {
+    let encrypted: bool = SqliteDatabase::is_encrypted(path).unwrap_or_else(|e| exit_with(&format!("Unable to open database: {:?}", e)));
+
+    match encrypted {
+        true => SqliteDatabase::open_encrypted(path, passphrase_cipher()),
+        false => SqliteDatabase::open(path),
+    }
+    .unwrap_or_else(|e| exit_with(&format!("Unable to open database: {:?}", e)))
+}
+
+fn sqlite_repositories(database: SqliteDatabase) -> ServedRepositories {
+    ServedRepositories {
+        brands: Box::new(BrandRepositorySqliteImpl::new(database.clone())),
+        categories: Box::new(CategoryRepositorySqliteImpl::new(database.clone())),
+        stores: Box::new(StoreRepositorySqliteImpl::new(database.clone())),
+        products: Box::new(ProductRepositorySqliteImpl::new(database.clone())),
+        transactions: Box::new(TransactionRepositorySqliteImpl::new(database)),
+    }
+}
+
+fn in_memory_repositories(cache: &Arc<RwLock<InMemoryCache>>) -> ServedRepositories {
+    ServedRepositories {
+        brands: Box::new(BrandRepositoryInMemoryImpl::new(Arc::clone(cache))),
+        categories: Box::new(CategoryRepositoryInMemoryImpl::new(Arc::clone(cache))),
+        stores: Box::new(StoreRepositoryInMemoryImpl::new(Arc::clone(cache))),
+        products: Box::new(ProductRepositoryInMemoryImpl::new(Arc::clone(cache))),
+        transactions: Box::new(TransactionRepositoryInMemoryImpl::new(Arc::clone(cache))),
+    }
+}
+
+/// Anyone able to reach the server could otherwise read and change the ledger, so it never serves without a token.
+fn access_token(token: Option<String>) -> String {
+    match token.or_else(|| env::var(TOKEN_VARIABLE).ok()).filter(|token| !token.is_empty()) {
+        Some(token) => token,
+        None => exit_with(&format!("Set --token or {} to the token front-ends must send", TOKEN_VARIABLE)),
+    }
+}
+
+/// The server runs unattended, so an encrypted file can only be opened with `FROSTY_PINE_PASSPHRASE` set.
+fn passphrase_cipher() -> Cipher {
+    match env::var(PASSPHRASE_VARIABLE) {
+        Ok(passphrase) => Cipher::new(passphrase),
+        Err(_) => exit_with(&format!("The file is encrypted, set {} to its passphrase", PASSPHRASE_VARIABLE)),
+    }
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(1)
+}
@@ -0,0 +1,13 @@
+mod brand_repository_remote_impl;
+mod category_repository_remote_impl;
+#[cfg(test)]
+mod conformance_tests;
+mod product_repository_remote_impl;
+mod store_repository_remote_impl;
+mod transaction_repository_remote_impl;
+
+pub use brand_repository_remote_impl::BrandRepositoryRemoteImpl;
+pub use category_repository_remote_impl::CategoryRepositoryRemoteImpl;
+pub use product_repository_remote_impl::ProductRepositoryRemoteImpl;
+pub use store_repository_remote_impl::StoreRepositoryRemoteImpl;
+pub use transaction_repository_remote_impl::TransactionRepositoryRemoteImpl;
@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::{StreamExt, stream::BoxStream};
+use reqwest::Method;
+
+use expense_tracking::domain::{
+    entities::Brand,
+    repositories::{
+        BrandRepository, BrandRepositoryCreateError, BrandRepositoryDeleteError, BrandRepositoryRestoreError,
+        BrandRepositoryRetrieveAllError, BrandRepositoryUpdateError, DeleteBehaviour, Trashed,
+    },
+};
+
+use crate::infrastructures::{
+    api::{BrandDeletionDocument, BrandDocument, DeleteBehaviourDocument, PurgeQuery, TrashedDocument},
+    data_sources::RemoteClient,
+};
+
+#[derive(Debug, Clone)]
+pub struct BrandRepositoryRemoteImpl {
+    client: RemoteClient,
+}
+
+impl BrandRepositoryRemoteImpl {
+    pub fn new(client: RemoteClient) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl BrandRepository for BrandRepositoryRemoteImpl {
+    async fn create(&self, brand: &Brand) -> Result<Brand, BrandRepositoryCreateError> {
+        let request = self.client.request(Method::POST, "/brands").json(&BrandDocument::from(brand));
+        let created: BrandDocument = self.client.call(request).await?;
+
+        Ok(Brand::from(&created))
+    }
+
+    async fn retrieve_all(&self) -> Result<Vec<Brand>, BrandRepositoryRetrieveAllError> {
+        let brands: Vec<BrandDocument> = self.client.call(self.client.request(Method::GET, "/brands")).await?;
+
+        Ok(brands.iter().map(Brand::from).collect())
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Brand, BrandRepositoryRetrieveAllError>> {
+        self.client
+            .paged("/brands", |brand: &BrandDocument| brand.name.clone())
+            .map(|document| document.map(|d| Brand::from(&d)).map_err(BrandRepositoryRetrieveAllError::from))
+            .boxed()
+    }
+
+    async fn update(&self, brand: &Brand) -> Result<Brand, BrandRepositoryUpdateError> {
+        let request = self.client.request(Method::PUT, "/brands").json(&BrandDocument::from(brand));
+        let updated: BrandDocument = self.client.call(request).await?;
+
+        Ok(Brand::from(&updated))
+    }
+
+    async fn delete(&self, brand: &Brand, behaviour: DeleteBehaviour<Brand>) -> Result<Brand, BrandRepositoryDeleteError> {
+        let deletion: BrandDeletionDocument = BrandDeletionDocument {
+            brand: BrandDocument::from(brand),
+            behaviour: DeleteBehaviourDocument::from(&behaviour),
+        };
+        let deleted: BrandDocument = self
+            .client
+            .call(self.client.request(Method::DELETE, "/brands").json(&deletion))
+            .await?;
+
+        Ok(Brand::from(&deleted))
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Brand>>, BrandRepositoryRetrieveAllError> {
+        let trashed: Vec<TrashedDocument<BrandDocument>> = self.client.call(self.client.request(Method::GET, "/brands/trash")).await?;
+
+        Ok(trashed.iter().map(Trashed::from).collect())
+    }
+
+    async fn restore(&self, brand: &Brand) -> Result<Brand, BrandRepositoryRestoreError> {
+        let request = self
+            .client
+            .request(Method::POST, "/brands/trash/restore")
+            .json(&BrandDocument::from(brand));
+        let restored: BrandDocument = self.client.call(request).await?;
+
+        Ok(Brand::from(&restored))
+    }
+
+    async fn purge_older_than(&self, cutoff: DateTime<Utc>) -> Result<Vec<Brand>, BrandRepositoryDeleteError> {
+        let request = self
+            .client
+            .request(Method::DELETE, "/brands/trash")
+            .query(&PurgeQuery { older_than: cutoff });
+        let purged: Vec<BrandDocument> = self.client.call(request).await?;
+
+        Ok(purged.iter().map(Brand::from).collect())
+    }
+}
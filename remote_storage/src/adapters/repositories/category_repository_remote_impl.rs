@@ -0,0 +1,86 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::{StreamExt, stream::BoxStream};
+use reqwest::Method;
+use uuid_b64::UuidB64;
+
+use expense_tracking::domain::{
+    entities::Category,
+    repositories::{CategoryRepository, CategoryRepositoryError, DeleteBehaviour, Trashed},
+};
+
+use crate::infrastructures::{
+    api::{CategoryDocument, DeleteBehaviourDocument, PurgeQuery, TrashedDocument},
+    data_sources::RemoteClient,
+};
+
+#[derive(Debug, Clone)]
+pub struct CategoryRepositoryRemoteImpl {
+    client: RemoteClient,
+}
+
+impl CategoryRepositoryRemoteImpl {
+    pub fn new(client: RemoteClient) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl CategoryRepository for CategoryRepositoryRemoteImpl {
+    async fn create_or_update(&mut self, category: &Category) -> Result<Option<Category>, CategoryRepositoryError> {
+        let request = self
+            .client
+            .request(Method::PUT, "/categories")
+            .json(&CategoryDocument::from(category));
+        let previous: Option<CategoryDocument> = self.client.call(request).await?;
+
+        Ok(previous.as_ref().map(Category::from))
+    }
+
+    async fn retrieve_all(&self) -> Result<Vec<Category>, CategoryRepositoryError> {
+        let categories: Vec<CategoryDocument> = self.client.call(self.client.request(Method::GET, "/categories")).await?;
+
+        Ok(categories.iter().map(Category::from).collect())
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Category, CategoryRepositoryError>> {
+        self.client
+            .paged("/categories", |category: &CategoryDocument| category.id.to_string())
+            .map(|document| document.map(|d| Category::from(&d)).map_err(CategoryRepositoryError::from))
+            .boxed()
+    }
+
+    async fn delete(&mut self, id: &UuidB64, behaviour: DeleteBehaviour<Category>) -> Result<Category, CategoryRepositoryError> {
+        let request = self
+            .client
+            .request(Method::DELETE, &format!("/categories/{}", id))
+            .json(&DeleteBehaviourDocument::<CategoryDocument>::from(&behaviour));
+        let deleted: CategoryDocument = self.client.call(request).await?;
+
+        Ok(Category::from(&deleted))
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Category>>, CategoryRepositoryError> {
+        let trashed: Vec<TrashedDocument<CategoryDocument>> =
+            self.client.call(self.client.request(Method::GET, "/categories/trash")).await?;
+
+        Ok(trashed.iter().map(Trashed::from).collect())
+    }
+
+    async fn restore(&mut self, id: &UuidB64) -> Result<Category, CategoryRepositoryError> {
+        let request = self.client.request(Method::POST, &format!("/categories/trash/{}/restore", id));
+        let restored: CategoryDocument = self.client.call(request).await?;
+
+        Ok(Category::from(&restored))
+    }
+
+    async fn purge_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<Vec<Category>, CategoryRepositoryError> {
+        let request = self
+            .client
+            .request(Method::DELETE, "/categories/trash")
+            .query(&PurgeQuery { older_than: cutoff });
+        let purged: Vec<CategoryDocument> = self.client.call(request).await?;
+
+        Ok(purged.iter().map(Category::from).collect())
+    }
+}
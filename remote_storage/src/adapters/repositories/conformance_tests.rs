@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use in_memory_storage::{
+    adapters::repositories::{
+        BrandRepositoryInMemoryImpl, CategoryRepositoryInMemoryImpl, ProductRepositoryInMemoryImpl, StoreRepositoryInMemoryImpl,
+        TransactionRepositoryInMemoryImpl,
+    },
+    infrastructures::data_sources::InMemoryCache,
+};
+use repository_conformance::{repository_conformance_suite, suites::RepositoryBackend};
+use tokio::sync::RwLock;
+
+use expense_tracking::domain::{
+    entities::Store,
+    repositories::{StoreRepository, StoreRepositoryError},
+};
+
+use crate::{
+    adapters::repositories::{
+        BrandRepositoryRemoteImpl, CategoryRepositoryRemoteImpl, ProductRepositoryRemoteImpl, StoreRepositoryRemoteImpl,
+        TransactionRepositoryRemoteImpl,
+    },
+    infrastructures::{
+        data_sources::RemoteClient,
+        server::{ServedRepositories, repository_router},
+    },
+};
+
+const ACCESS_TOKEN: &str = "conformance-token";
+
+/// Remote repositories talking to a server on a free local port, which keeps its data in memory.
+struct RemoteBackend {
+    client: RemoteClient,
+}
+
+impl RemoteBackend {
+    fn start() -> Self {
+        let cache: Arc<RwLock<InMemoryCache>> = Arc::new(RwLock::new(InMemoryCache::new()));
+        let repositories: ServedRepositories = ServedRepositories {
+            brands: Box::new(BrandRepositoryInMemoryImpl::new(Arc::clone(&cache))),
+            categories: Box::new(CategoryRepositoryInMemoryImpl::new(Arc::clone(&cache))),
+            stores: Box::new(StoreRepositoryInMemoryImpl::new(Arc::clone(&cache))),
+            products: Box::new(ProductRepositoryInMemoryImpl::new(Arc::clone(&cache))),
+            transactions: Box::new(TransactionRepositoryInMemoryImpl::new(cache)),
+        };
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.set_nonblocking(true).unwrap();
+        let address = listener.local_addr().unwrap();
+        let listener = tokio::net::TcpListener::from_std(listener).unwrap();
+        tokio::spawn(async move { axum::serve(listener, repository_router(repositories, ACCESS_TOKEN)).await });
+
+        Self {
+            client: RemoteClient::new(format!("http://{}", address)).with_access_token(ACCESS_TOKEN),
+        }
+    }
+}
+
+impl RepositoryBackend for RemoteBackend {
+    type Brands = BrandRepositoryRemoteImpl;
+    type Categories = CategoryRepositoryRemoteImpl;
+    type Stores = StoreRepositoryRemoteImpl;
+    type Products = ProductRepositoryRemoteImpl;
+    type Transactions = TransactionRepositoryRemoteImpl;
+
+    fn brands(&self) -> Self::Brands {
+        BrandRepositoryRemoteImpl::new(self.client.clone())
+    }
+
+    fn categories(&self) -> Self::Categories {
+        CategoryRepositoryRemoteImpl::new(self.client.clone())
+    }
+
+    fn stores(&self) -> Self::Stores {
+        StoreRepositoryRemoteImpl::new(self.client.clone())
+    }
+
+    fn products(&self) -> Self::Products {
+        ProductRepositoryRemoteImpl::new(self.client.clone())
+    }
+
+    fn transactions(&self) -> Self::Transactions {
+        TransactionRepositoryRemoteImpl::new(self.client.clone())
+    }
+}
+
+repository_conformance_suite!(RemoteBackend::start());
+
+#[tokio::test]
+async fn requests_without_the_access_token_should_be_refused() {
+    let backend: RemoteBackend = RemoteBackend::start();
+
+    for client in [
+        RemoteClient::new(backend.client.base_url()),
+        RemoteClient::new(backend.client.base_url()).with_access_token("wrong-token"),
+    ] {
+        let result: Result<Vec<Store>, StoreRepositoryError> = StoreRepositoryRemoteImpl::new(client).retrieve_all().await;
+        let expected: Result<Vec<Store>, StoreRepositoryError> =
+            Err(StoreRepositoryError::UnableToAccessStorage("Unauthorized".to_owned()));
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+    assert_eq!(backend.stores().retrieve_all().await, Ok(vec![]));
+}
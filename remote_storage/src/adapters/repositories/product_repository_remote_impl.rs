@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::{StreamExt, stream::BoxStream};
+use reqwest::Method;
+use uuid_b64::UuidB64;
+
+use expense_tracking::domain::{
+    entities::Product,
+    repositories::{DeleteBehaviour, ProductRepository, ProductRepositoryError, Trashed},
+};
+
+use crate::infrastructures::{
+    api::{DeleteBehaviourDocument, ProductDocument, PurgeQuery, TrashedDocument},
+    data_sources::RemoteClient,
+};
+
+#[derive(Debug, Clone)]
+pub struct ProductRepositoryRemoteImpl {
+    client: RemoteClient,
+}
+
+impl ProductRepositoryRemoteImpl {
+    pub fn new(client: RemoteClient) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl ProductRepository for ProductRepositoryRemoteImpl {
+    async fn create_or_update(&mut self, product: &Product) -> Result<Option<Product>, ProductRepositoryError> {
+        let request = self.client.request(Method::PUT, "/products").json(&ProductDocument::from(product));
+        let previous: Option<ProductDocument> = self.client.call(request).await?;
+
+        Ok(previous.as_ref().map(Product::from))
+    }
+
+    async fn retrieve_all(&self) -> Result<Vec<Product>, ProductRepositoryError> {
+        let products: Vec<ProductDocument> = self.client.call(self.client.request(Method::GET, "/products")).await?;
+
+        Ok(products.iter().map(Product::from).collect())
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Product, ProductRepositoryError>> {
+        self.client
+            .paged("/products", |product: &ProductDocument| product.id.to_string())
+            .map(|document| document.map(|d| Product::from(&d)).map_err(ProductRepositoryError::from))
+            .boxed()
+    }
+
+    async fn delete(&mut self, id: &UuidB64, behaviour: DeleteBehaviour<Product>) -> Result<Product, ProductRepositoryError> {
+        let request = self
+            .client
+            .request(Method::DELETE, &format!("/products/{}", id))
+            .json(&DeleteBehaviourDocument::<ProductDocument>::from(&behaviour));
+        let deleted: ProductDocument = self.client.call(request).await?;
+
+        Ok(Product::from(&deleted))
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Product>>, ProductRepositoryError> {
+        let trashed: Vec<TrashedDocument<ProductDocument>> = self.client.call(self.client.request(Method::GET, "/products/trash")).await?;
+
+        Ok(trashed.iter().map(Trashed::from).collect())
+    }
+
+    async fn restore(&mut self, id: &UuidB64) -> Result<Product, ProductRepositoryError> {
+        let request = self.client.request(Method::POST, &format!("/products/trash/{}/restore", id));
+        let restored: ProductDocument = self.client.call(request).await?;
+
+        Ok(Product::from(&restored))
+    }
+
+    async fn purge_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<Vec<Product>, ProductRepositoryError> {
+        let request = self
+            .client
+            .request(Method::DELETE, "/products/trash")
+            .query(&PurgeQuery { older_than: cutoff });
+        let purged: Vec<ProductDocument> = self.client.call(request).await?;
+
+        Ok(purged.iter().map(Product::from).collect())
+    }
+}
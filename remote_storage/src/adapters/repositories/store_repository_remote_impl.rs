@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::{StreamExt, stream::BoxStream};
+use reqwest::Method;
+use uuid_b64::UuidB64;
+
+use expense_tracking::domain::{
+    entities::Store,
+    repositories::{DeleteBehaviour, StoreRepository, StoreRepositoryError, Trashed},
+};
+
+use crate::infrastructures::{
+    api::{DeleteBehaviourDocument, PurgeQuery, StoreDocument, TrashedDocument},
+    data_sources::RemoteClient,
+};
+
+#[derive(Debug, Clone)]
+pub struct StoreRepositoryRemoteImpl {
+    client: RemoteClient,
+}
+
+impl StoreRepositoryRemoteImpl {
+    pub fn new(client: RemoteClient) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl StoreRepository for StoreRepositoryRemoteImpl {
+    async fn create_or_update(&mut self, store: &Store) -> Result<Option<Store>, StoreRepositoryError> {
+        let request = self.client.request(Method::PUT, "/stores").json(&StoreDocument::from(store));
+        let previous: Option<StoreDocument> = self.client.call(request).await?;
+
+        Ok(previous.as_ref().map(Store::from))
+    }
+
+    async fn retrieve_all(&self) -> Result<Vec<Store>, StoreRepositoryError> {
+        let stores: Vec<StoreDocument> = self.client.call(self.client.request(Method::GET, "/stores")).await?;
+
+        Ok(stores.iter().map(Store::from).collect())
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Store, StoreRepositoryError>> {
+        self.client
+            .paged("/stores", |store: &StoreDocument| store.id.to_string())
+            .map(|document| document.map(|d| Store::from(&d)).map_err(StoreRepositoryError::from))
+            .boxed()
+    }
+
+    async fn delete(&mut self, id: &UuidB64, behaviour: DeleteBehaviour<Store>) -> Result<Store, StoreRepositoryError> {
+        let request = self
+            .client
+            .request(Method::DELETE, &format!("/stores/{}", id))
+            .json(&DeleteBehaviourDocument::<StoreDocument>::from(&behaviour));
+        let deleted: StoreDocument = self.client.call(request).await?;
+
+        Ok(Store::from(&deleted))
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Store>>, StoreRepositoryError> {
+        let trashed: Vec<TrashedDocument<StoreDocument>> = self.client.call(self.client.request(Method::GET, "/stores/trash")).await?;
+
+        Ok(trashed.iter().map(Trashed::from).collect())
+    }
+
+    async fn restore(&mut self, id: &UuidB64) -> Result<Store, StoreRepositoryError> {
+        let request = self.client.request(Method::POST, &format!("/stores/trash/{}/restore", id));
+        let restored: StoreDocument = self.client.call(request).await?;
+
+        Ok(Store::from(&restored))
+    }
+
+    async fn purge_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<Vec<Store>, StoreRepositoryError> {
+        let request = self
+            .client
+            .request(Method::DELETE, "/stores/trash")
+            .query(&PurgeQuery { older_than: cutoff });
+        let purged: Vec<StoreDocument> = self.client.call(request).await?;
+
+        Ok(purged.iter().map(Store::from).collect())
+    }
+}
@@ -0,0 +1,83 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::{StreamExt, stream::BoxStream};
+use reqwest::Method;
+use uuid_b64::UuidB64;
+
+use expense_tracking::domain::{
+    entities::Transaction,
+    repositories::{TransactionRepository, TransactionRepositoryError, Trashed},
+};
+
+use crate::infrastructures::{
+    api::{PurgeQuery, TransactionDocument, TrashedDocument},
+    data_sources::RemoteClient,
+};
+
+#[derive(Debug, Clone)]
+pub struct TransactionRepositoryRemoteImpl {
+    client: RemoteClient,
+}
+
+impl TransactionRepositoryRemoteImpl {
+    pub fn new(client: RemoteClient) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl TransactionRepository for TransactionRepositoryRemoteImpl {
+    async fn create_or_update(&mut self, transaction: &Transaction) -> Result<Option<Transaction>, TransactionRepositoryError> {
+        let request = self
+            .client
+            .request(Method::PUT, "/transactions")
+            .json(&TransactionDocument::from(transaction));
+        let previous: Option<TransactionDocument> = self.client.call(request).await?;
+
+        Ok(previous.as_ref().map(Transaction::from))
+    }
+
+    async fn retrieve_all(&self) -> Result<Vec<Transaction>, TransactionRepositoryError> {
+        let transactions: Vec<TransactionDocument> = self.client.call(self.client.request(Method::GET, "/transactions")).await?;
+
+        Ok(transactions.iter().map(Transaction::from).collect())
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Transaction, TransactionRepositoryError>> {
+        self.client
+            .paged("/transactions", |transaction: &TransactionDocument| transaction.id.to_string())
+            .map(|document| document.map(|d| Transaction::from(&d)).map_err(TransactionRepositoryError::from))
+            .boxed()
+    }
+
+    async fn delete(&mut self, id: &UuidB64) -> Result<Transaction, TransactionRepositoryError> {
+        let request = self.client.request(Method::DELETE, &format!("/transactions/{}", id));
+        let deleted: TransactionDocument = self.client.call(request).await?;
+
+        Ok(Transaction::from(&deleted))
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Transaction>>, TransactionRepositoryError> {
+        let trashed: Vec<TrashedDocument<TransactionDocument>> =
+            self.client.call(self.client.request(Method::GET, "/transactions/trash")).await?;
+
+        Ok(trashed.iter().map(Trashed::from).collect())
+    }
+
+    async fn restore(&mut self, id: &UuidB64) -> Result<Transaction, TransactionRepositoryError> {
+        let request = self.client.request(Method::POST, &format!("/transactions/trash/{}/restore", id));
+        let restored: TransactionDocument = self.client.call(request).await?;
+
+        Ok(Transaction::from(&restored))
+    }
+
+    async fn purge_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<Vec<Transaction>, TransactionRepositoryError> {
+        let request = self
+            .client
+            .request(Method::DELETE, "/transactions/trash")
+            .query(&PurgeQuery { older_than: cutoff });
+        let purged: Vec<TransactionDocument> = self.client.call(request).await?;
+
+        Ok(purged.iter().map(Transaction::from).collect())
+    }
+}
@@ -0,0 +1,3 @@
+pub mod api;
+pub mod data_sources;
+pub mod server;
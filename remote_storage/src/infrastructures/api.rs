@@ -0,0 +1,16 @@
+mod api_documents;
+mod api_error;
+
+pub use api_documents::BrandDeletionDocument;
+pub use api_documents::BrandDocument;
+pub use api_documents::CategoryDocument;
+pub use api_documents::DeleteBehaviourDocument;
+pub use api_documents::ItemDocument;
+pub use api_documents::PageQuery;
+pub use api_documents::ProductDocument;
+pub use api_documents::PurgeQuery;
+pub use api_documents::StoreDocument;
+pub use api_documents::TransactionDocument;
+pub use api_documents::TrashedDocument;
+pub use api_documents::UnitDocument;
+pub use api_error::ApiError;
@@ -0,0 +1,301 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid_b64::UuidB64;
+
+use expense_tracking::domain::{
+    entities::{Brand, Category, Item, Product, Store, Transaction, Unit},
+    repositories::{DeleteBehaviour, Trashed},
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrandDocument {
+    pub name: String,
+    pub version: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CategoryDocument {
+    pub id: UuidB64,
+    pub name: String,
+    pub version: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoreDocument {
+    pub id: UuidB64,
+    pub name: String,
+    pub version: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProductDocument {
+    pub id: UuidB64,
+    pub name: String,
+    pub brand: BrandDocument,
+    pub category: CategoryDocument,
+    pub version: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionDocument {
+    pub id: UuidB64,
+    pub store: StoreDocument,
+    pub datetime: DateTime<Utc>,
+    pub version: u64,
+    pub items: Vec<ItemDocument>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ItemDocument {
+    pub id: UuidB64,
+    pub product: ProductDocument,
+    pub unit: UnitDocument,
+    pub unitary_price: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "amount", rename_all = "snake_case")]
+pub enum UnitDocument {
+    None,
+    Quantity(f64),
+    Kilograms(f64),
+    Liters(f64),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrashedDocument<D> {
+    pub entity: D,
+    pub trashed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "behaviour", content = "replacement", rename_all = "snake_case")]
+pub enum DeleteBehaviourDocument<D> {
+    Restrict,
+    Cascade,
+    Reassign(D),
+}
+
+/// Brands are identified by name, which is sent in the body rather than in the path.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrandDeletionDocument {
+    pub brand: BrandDocument,
+    pub behaviour: DeleteBehaviourDocument<BrandDocument>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PurgeQuery {
+    pub older_than: DateTime<Utc>,
+}
+
+/// Asks for the `limit` entities keyed right after `after`, brands by name and the others by id. Without a `limit`
+/// every entity is listed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PageQuery {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+impl From<&Brand> for BrandDocument {
+    fn from(brand: &Brand) -> Self {
+        Self {
+            name: brand.name.clone(),
+            version: brand.version,
+        }
+    }
+}
+
+impl From<&BrandDocument> for Brand {
+    fn from(document: &BrandDocument) -> Self {
+        Brand {
+            version: document.version,
+            ..Brand::new(document.name.clone())
+        }
+    }
+}
+
+impl From<&Category> for CategoryDocument {
+    fn from(category: &Category) -> Self {
+        Self {
+            id: category.id,
+            name: category.name.clone(),
+            version: category.version,
+        }
+    }
+}
+
+impl From<&CategoryDocument> for Category {
+    fn from(document: &CategoryDocument) -> Self {
+        Category {
+            version: document.version,
+            ..Category::new(Some(document.id), document.name.clone())
+        }
+    }
+}
+
+impl From<&Store> for StoreDocument {
+    fn from(store: &Store) -> Self {
+        Self {
+            id: store.id,
+            name: store.name.clone(),
+            version: store.version,
+        }
+    }
+}
+
+impl From<&StoreDocument> for Store {
+    fn from(document: &StoreDocument) -> Self {
+        Store {
+            version: document.version,
+            ..Store::new(Some(document.id), document.name.clone())
+        }
+    }
+}
+
+impl From<&Product> for ProductDocument {
+    fn from(product: &Product) -> Self {
+        Self {
+            id: product.id,
+            name: product.name.clone(),
+            brand: BrandDocument::from(&product.brand),
+            category: CategoryDocument::from(&product.category),
+            version: product.version,
+        }
+    }
+}
+
+impl From<&ProductDocument> for Product {
+    fn from(document: &ProductDocument) -> Self {
+        Product {
+            version: document.version,
+            ..Product::new(
+                Some(document.id),
+                document.name.clone(),
+                Brand::from(&document.brand),
+                Category::from(&document.category),
+            )
+        }
+    }
+}
+
+impl From<&Transaction> for TransactionDocument {
+    fn from(transaction: &Transaction) -> Self {
+        Self {
+            id: transaction.id,
+            store: StoreDocument::from(&transaction.store),
+            datetime: transaction.datetime,
+            version: transaction.version,
+            items: transaction.items.iter().map(ItemDocument::from).collect(),
+        }
+    }
+}
+
+impl From<&TransactionDocument> for Transaction {
+    fn from(document: &TransactionDocument) -> Self {
+        Transaction {
+            version: document.version,
+            ..Transaction::new(
+                Some(document.id),
+                document.items.iter().map(Item::from).collect(),
+                Store::from(&document.store),
+                document.datetime,
+            )
+        }
+    }
+}
+
+impl From<&Item> for ItemDocument {
+    fn from(item: &Item) -> Self {
+        Self {
+            id: item.id(),
+            product: ProductDocument::from(item.product()),
+            unit: UnitDocument::from(item.unit()),
+            unitary_price: item.unitary_price(),
+        }
+    }
+}
+
+impl From<&ItemDocument> for Item {
+    fn from(document: &ItemDocument) -> Self {
+        Item::new(
+            Some(document.id),
+            Product::from(&document.product),
+            Unit::from(&document.unit),
+            document.unitary_price,
+        )
+    }
+}
+
+impl From<&Unit> for UnitDocument {
+    fn from(unit: &Unit) -> Self {
+        match unit {
+            Unit::None => UnitDocument::None,
+            Unit::Quantity(amount) => UnitDocument::Quantity(*amount),
+            Unit::Kilograms(amount) => UnitDocument::Kilograms(*amount),
+            Unit::Liters(amount) => UnitDocument::Liters(*amount),
+        }
+    }
+}
+
+impl From<&UnitDocument> for Unit {
+    fn from(document: &UnitDocument) -> Self {
+        match document {
+            UnitDocument::None => Unit::None,
+            UnitDocument::Quantity(amount) => Unit::Quantity(*amount),
+            UnitDocument::Kilograms(amount) => Unit::Kilograms(*amount),
+            UnitDocument::Liters(amount) => Unit::Liters(*amount),
+        }
+    }
+}
+
+impl<T, D> From<&Trashed<T>> for TrashedDocument<D>
+where
+    D: for<'a> From<&'a T>,
+{
+    fn from(trashed: &Trashed<T>) -> Self {
+        Self {
+            entity: D::from(&trashed.entity),
+            trashed_at: trashed.trashed_at,
+        }
+    }
+}
+
+impl<T, D> From<&TrashedDocument<D>> for Trashed<T>
+where
+    T: for<'a> From<&'a D>,
+{
+    fn from(document: &TrashedDocument<D>) -> Self {
+        Trashed {
+            entity: T::from(&document.entity),
+            trashed_at: document.trashed_at,
+        }
+    }
+}
+
+impl<T, D> From<&DeleteBehaviour<T>> for DeleteBehaviourDocument<D>
+where
+    D: for<'a> From<&'a T>,
+{
+    fn from(behaviour: &DeleteBehaviour<T>) -> Self {
+        match behaviour {
+            DeleteBehaviour::Restrict => DeleteBehaviourDocument::Restrict,
+            DeleteBehaviour::Cascade => DeleteBehaviourDocument::Cascade,
+            DeleteBehaviour::Reassign(replacement) => DeleteBehaviourDocument::Reassign(D::from(replacement)),
+        }
+    }
+}
+
+impl<T, D> From<&DeleteBehaviourDocument<D>> for DeleteBehaviour<T>
+where
+    T: for<'a> From<&'a D>,
+{
+    fn from(document: &DeleteBehaviourDocument<D>) -> Self {
+        match document {
+            DeleteBehaviourDocument::Restrict => DeleteBehaviour::Restrict,
+            DeleteBehaviourDocument::Cascade => DeleteBehaviour::Cascade,
+            DeleteBehaviourDocument::Reassign(replacement) => DeleteBehaviour::Reassign(T::from(replacement)),
+        }
+    }
+}
@@ -0,0 +1,325 @@
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+
+use expense_tracking::domain::repositories::{
+    BrandRepositoryCreateError, BrandRepositoryDeleteError, BrandRepositoryRestoreError, BrandRepositoryRetrieveAllError,
+    BrandRepositoryUpdateError, CategoryRepositoryError, ProductRepositoryError, StoreRepositoryError, TransactionRepositoryError,
+};
+
+/// Repository errors as they travel between server and client. The kind of entity is implied by the route, so
+/// every repository shares the same set of errors.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "error", rename_all = "snake_case")]
+pub enum ApiError {
+    NotFound,
+    NotInTrash,
+    AlreadyExists,
+    VersionConflict { current_version: u64 },
+    StillReferenced,
+    ReplacementNotFound,
+    BrandNotFound,
+    CategoryNotFound,
+    StoreNotFound,
+    ProductNotFound,
+    Unauthorized,
+    Storage { reason: String },
+}
+
+impl ApiError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::Unauthorized => StatusCode::UNAUTHORIZED,
+            ApiError::NotFound | ApiError::NotInTrash => StatusCode::NOT_FOUND,
+            ApiError::AlreadyExists | ApiError::VersionConflict { .. } | ApiError::StillReferenced => StatusCode::CONFLICT,
+            ApiError::ReplacementNotFound
+            | ApiError::BrandNotFound
+            | ApiError::CategoryNotFound
+            | ApiError::StoreNotFound
+            | ApiError::ProductNotFound => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::Storage { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status(), Json(self)).into_response()
+    }
+}
+
+impl From<BrandRepositoryCreateError> for ApiError {
+    fn from(error: BrandRepositoryCreateError) -> Self {
+        match error {
+            BrandRepositoryCreateError::UnableToSaveBrand(reason) => ApiError::Storage { reason },
+            BrandRepositoryCreateError::BrandAlreadyExists => ApiError::AlreadyExists,
+        }
+    }
+}
+
+impl From<BrandRepositoryRetrieveAllError> for ApiError {
+    fn from(error: BrandRepositoryRetrieveAllError) -> Self {
+        match error {
+            BrandRepositoryRetrieveAllError::UnableToRetrieveBrands(reason) => ApiError::Storage { reason },
+        }
+    }
+}
+
+impl From<BrandRepositoryUpdateError> for ApiError {
+    fn from(error: BrandRepositoryUpdateError) -> Self {
+        match error {
+            BrandRepositoryUpdateError::UnableToUpdateBrand(reason) => ApiError::Storage { reason },
+            BrandRepositoryUpdateError::BrandNotFound => ApiError::NotFound,
+            BrandRepositoryUpdateError::VersionConflict { current_version } => ApiError::VersionConflict { current_version },
+        }
+    }
+}
+
+impl From<BrandRepositoryDeleteError> for ApiError {
+    fn from(error: BrandRepositoryDeleteError) -> Self {
+        match error {
+            BrandRepositoryDeleteError::UnableToDeleteBrand(reason) => ApiError::Storage { reason },
+            BrandRepositoryDeleteError::BrandNotFound => ApiError::NotFound,
+            BrandRepositoryDeleteError::BrandStillReferenced => ApiError::StillReferenced,
+            BrandRepositoryDeleteError::ReplacementBrandNotFound => ApiError::ReplacementNotFound,
+        }
+    }
+}
+
+impl From<BrandRepositoryRestoreError> for ApiError {
+    fn from(error: BrandRepositoryRestoreError) -> Self {
+        match error {
+            BrandRepositoryRestoreError::UnableToRestoreBrand(reason) => ApiError::Storage { reason },
+            BrandRepositoryRestoreError::BrandNotInTrash => ApiError::NotInTrash,
+            BrandRepositoryRestoreError::BrandAlreadyExists => ApiError::AlreadyExists,
+        }
+    }
+}
+
+impl From<CategoryRepositoryError> for ApiError {
+    fn from(error: CategoryRepositoryError) -> Self {
+        match error {
+            CategoryRepositoryError::UnableToAccessStorage(reason) => ApiError::Storage { reason },
+            CategoryRepositoryError::CategoryNotFound => ApiError::NotFound,
+            CategoryRepositoryError::CategoryNotInTrash => ApiError::NotInTrash,
+            CategoryRepositoryError::CategoryAlreadyExists => ApiError::AlreadyExists,
+            CategoryRepositoryError::VersionConflict { current_version } => ApiError::VersionConflict { current_version },
+            CategoryRepositoryError::CategoryStillReferenced => ApiError::StillReferenced,
+            CategoryRepositoryError::ReplacementCategoryNotFound => ApiError::ReplacementNotFound,
+        }
+    }
+}
+
+impl From<StoreRepositoryError> for ApiError {
+    fn from(error: StoreRepositoryError) -> Self {
+        match error {
+            StoreRepositoryError::UnableToAccessStorage(reason) => ApiError::Storage { reason },
+            StoreRepositoryError::StoreNotFound => ApiError::NotFound,
+            StoreRepositoryError::StoreNotInTrash => ApiError::NotInTrash,
+            StoreRepositoryError::StoreAlreadyExists => ApiError::AlreadyExists,
+            StoreRepositoryError::VersionConflict { current_version } => ApiError::VersionConflict { current_version },
+            StoreRepositoryError::StoreStillReferenced => ApiError::StillReferenced,
+            StoreRepositoryError::ReplacementStoreNotFound => ApiError::ReplacementNotFound,
+        }
+    }
+}
+
+impl From<ProductRepositoryError> for ApiError {
+    fn from(error: ProductRepositoryError) -> Self {
+        match error {
+            ProductRepositoryError::UnableToAccessStorage(reason) => ApiError::Storage { reason },
+            ProductRepositoryError::ProductNotFound => ApiError::NotFound,
+            ProductRepositoryError::ProductNotInTrash => ApiError::NotInTrash,
+            ProductRepositoryError::ProductAlreadyExists => ApiError::AlreadyExists,
+            ProductRepositoryError::VersionConflict { current_version } => ApiError::VersionConflict { current_version },
+            ProductRepositoryError::ProductStillReferenced => ApiError::StillReferenced,
+            ProductRepositoryError::ReplacementProductNotFound => ApiError::ReplacementNotFound,
+            ProductRepositoryError::BrandNotFound => ApiError::BrandNotFound,
+            ProductRepositoryError::CategoryNotFound => ApiError::CategoryNotFound,
+        }
+    }
+}
+
+impl From<TransactionRepositoryError> for ApiError {
+    fn from(error: TransactionRepositoryError) -> Self {
+        match error {
+            TransactionRepositoryError::UnableToAccessStorage(reason) => ApiError::Storage { reason },
+            TransactionRepositoryError::TransactionNotFound => ApiError::NotFound,
+            TransactionRepositoryError::TransactionNotInTrash => ApiError::NotInTrash,
+            TransactionRepositoryError::TransactionAlreadyExists => ApiError::AlreadyExists,
+            TransactionRepositoryError::VersionConflict { current_version } => ApiError::VersionConflict { current_version },
+            TransactionRepositoryError::StoreNotFound => ApiError::StoreNotFound,
+            TransactionRepositoryError::ProductNotFound => ApiError::ProductNotFound,
+        }
+    }
+}
+
+// The client side: errors a route cannot answer with only come from a misbehaving server, and are reported as a
+// storage failure like any unreachable server.
+
+impl From<ApiError> for BrandRepositoryCreateError {
+    fn from(error: ApiError) -> Self {
+        match error {
+            ApiError::AlreadyExists => BrandRepositoryCreateError::BrandAlreadyExists,
+            ApiError::Storage { reason } => BrandRepositoryCreateError::UnableToSaveBrand(reason),
+            error => BrandRepositoryCreateError::UnableToSaveBrand(format!("{:?}", error)),
+        }
+    }
+}
+
+impl From<ApiError> for BrandRepositoryRetrieveAllError {
+    fn from(error: ApiError) -> Self {
+        match error {
+            ApiError::Storage { reason } => BrandRepositoryRetrieveAllError::UnableToRetrieveBrands(reason),
+            error => BrandRepositoryRetrieveAllError::UnableToRetrieveBrands(format!("{:?}", error)),
+        }
+    }
+}
+
+impl From<ApiError> for BrandRepositoryUpdateError {
+    fn from(error: ApiError) -> Self {
+        match error {
+            ApiError::NotFound => BrandRepositoryUpdateError::BrandNotFound,
+            ApiError::VersionConflict { current_version } => BrandRepositoryUpdateError::VersionConflict { current_version },
+            ApiError::Storage { reason } => BrandRepositoryUpdateError::UnableToUpdateBrand(reason),
+            error => BrandRepositoryUpdateError::UnableToUpdateBrand(format!("{:?}", error)),
+        }
+    }
+}
+
+impl From<ApiError> for BrandRepositoryDeleteError {
+    fn from(error: ApiError) -> Self {
+        match error {
+            ApiError::NotFound => BrandRepositoryDeleteError::BrandNotFound,
+            ApiError::StillReferenced => BrandRepositoryDeleteError::BrandStillReferenced,
+            ApiError::ReplacementNotFound => BrandRepositoryDeleteError::ReplacementBrandNotFound,
+            ApiError::Storage { reason } => BrandRepositoryDeleteError::UnableToDeleteBrand(reason),
+            error => BrandRepositoryDeleteError::UnableToDeleteBrand(format!("{:?}", error)),
+        }
+    }
+}
+
+impl From<ApiError> for BrandRepositoryRestoreError {
+    fn from(error: ApiError) -> Self {
+        match error {
+            ApiError::NotInTrash => BrandRepositoryRestoreError::BrandNotInTrash,
+            ApiError::AlreadyExists => BrandRepositoryRestoreError::BrandAlreadyExists,
+            ApiError::Storage { reason } => BrandRepositoryRestoreError::UnableToRestoreBrand(reason),
+            error => BrandRepositoryRestoreError::UnableToRestoreBrand(format!("{:?}", error)),
+        }
+    }
+}
+
+impl From<ApiError> for CategoryRepositoryError {
+    fn from(error: ApiError) -> Self {
+        match error {
+            ApiError::NotFound => CategoryRepositoryError::CategoryNotFound,
+            ApiError::NotInTrash => CategoryRepositoryError::CategoryNotInTrash,
+            ApiError::AlreadyExists => CategoryRepositoryError::CategoryAlreadyExists,
+            ApiError::VersionConflict { current_version } => CategoryRepositoryError::VersionConflict { current_version },
+            ApiError::StillReferenced => CategoryRepositoryError::CategoryStillReferenced,
+            ApiError::ReplacementNotFound => CategoryRepositoryError::ReplacementCategoryNotFound,
+            ApiError::Storage { reason } => CategoryRepositoryError::UnableToAccessStorage(reason),
+            error => CategoryRepositoryError::UnableToAccessStorage(format!("{:?}", error)),
+        }
+    }
+}
+
+impl From<ApiError> for StoreRepositoryError {
+    fn from(error: ApiError) -> Self {
+        match error {
+            ApiError::NotFound => StoreRepositoryError::StoreNotFound,
+            ApiError::NotInTrash => StoreRepositoryError::StoreNotInTrash,
+            ApiError::AlreadyExists => StoreRepositoryError::StoreAlreadyExists,
+            ApiError::VersionConflict { current_version } => StoreRepositoryError::VersionConflict { current_version },
+            ApiError::StillReferenced => StoreRepositoryError::StoreStillReferenced,
+            ApiError::ReplacementNotFound => StoreRepositoryError::ReplacementStoreNotFound,
+            ApiError::Storage { reason } => StoreRepositoryError::UnableToAccessStorage(reason),
+            error => StoreRepositoryError::UnableToAccessStorage(format!("{:?}", error)),
+        }
+    }
+}
+
+impl From<ApiError> for ProductRepositoryError {
+    fn from(error: ApiError) -> Self {
+        match error {
+            ApiError::NotFound => ProductRepositoryError::ProductNotFound,
+            ApiError::NotInTrash => ProductRepositoryError::ProductNotInTrash,
+            ApiError::AlreadyExists => ProductRepositoryError::ProductAlreadyExists,
+            ApiError::VersionConflict { current_version } => ProductRepositoryError::VersionConflict { current_version },
+            ApiError::StillReferenced => ProductRepositoryError::ProductStillReferenced,
+            ApiError::ReplacementNotFound => ProductRepositoryError::ReplacementProductNotFound,
+            ApiError::BrandNotFound => ProductRepositoryError::BrandNotFound,
+            ApiError::CategoryNotFound => ProductRepositoryError::CategoryNotFound,
+            ApiError::Storage { reason } => ProductRepositoryError::UnableToAccessStorage(reason),
+            error => ProductRepositoryError::UnableToAccessStorage(format!("{:?}", error)),
+        }
+    }
+}
+
+impl From<ApiError> for TransactionRepositoryError {
+    fn from(error: ApiError) -> Self {
+        match error {
+            ApiError::NotFound => TransactionRepositoryError::TransactionNotFound,
+            ApiError::NotInTrash => TransactionRepositoryError::TransactionNotInTrash,
+            ApiError::AlreadyExists => TransactionRepositoryError::TransactionAlreadyExists,
+            ApiError::VersionConflict { current_version } => TransactionRepositoryError::VersionConflict { current_version },
+            ApiError::StoreNotFound => TransactionRepositoryError::StoreNotFound,
+            ApiError::ProductNotFound => TransactionRepositoryError::ProductNotFound,
+            ApiError::Storage { reason } => TransactionRepositoryError::UnableToAccessStorage(reason),
+            error => TransactionRepositoryError::UnableToAccessStorage(format!("{:?}", error)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+
+    use super::ApiError;
+    use expense_tracking::domain::repositories::CategoryRepositoryError;
+
+    macro_rules! wire_format {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (error, json, status): (ApiError, &str, StatusCode) = $value;
+
+                let result: String = serde_json::to_string(&error).unwrap();
+
+                assert_eq!(result, json, "Expected {:?}, but got {:?}", json, result);
+                assert_eq!(serde_json::from_str::<ApiError>(json).unwrap(), error);
+                assert_eq!(error.status(), status);
+            }
+        )*
+        }
+    }
+
+    wire_format! {
+        not_found_should_answer_404: (ApiError::NotFound, r#"{"error":"not_found"}"#, StatusCode::NOT_FOUND),
+        missing_access_tokens_should_answer_401: (ApiError::Unauthorized, r#"{"error":"unauthorized"}"#, StatusCode::UNAUTHORIZED),
+        version_conflict_should_carry_the_current_version: (
+            ApiError::VersionConflict { current_version: 3 },
+            r#"{"error":"version_conflict","current_version":3}"#,
+            StatusCode::CONFLICT
+        ),
+        missing_references_should_answer_422: (ApiError::BrandNotFound, r#"{"error":"brand_not_found"}"#, StatusCode::UNPROCESSABLE_ENTITY),
+        storage_failures_should_carry_their_reason: (
+            ApiError::Storage { reason: "disk full".to_owned() },
+            r#"{"error":"storage","reason":"disk full"}"#,
+            StatusCode::INTERNAL_SERVER_ERROR
+        ),
+    }
+
+    #[test]
+    fn errors_a_repository_cannot_raise_should_become_storage_failures() {
+        let result: CategoryRepositoryError = CategoryRepositoryError::from(ApiError::ProductNotFound);
+        let expected: CategoryRepositoryError = CategoryRepositoryError::UnableToAccessStorage("ProductNotFound".to_owned());
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+}
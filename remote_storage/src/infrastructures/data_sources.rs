@@ -0,0 +1,3 @@
+mod remote_client;
+
+pub use remote_client::RemoteClient;
@@ -0,0 +1,98 @@
+use futures::{
+    StreamExt,
+    stream::{self, BoxStream},
+};
+use reqwest::{Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+
+use crate::infrastructures::api::{ApiError, PageQuery};
+
+const PAGE_SIZE: usize = 100;
+
+/// Talks to a repository server. Cloning is cheap and clones share their connections, so every remote repository
+/// of one server can hold its own.
+#[derive(Debug, Clone)]
+pub struct RemoteClient {
+    http: reqwest::Client,
+    base_url: String,
+    access_token: Option<String>,
+}
+
+impl RemoteClient {
+    /// A client of the server at `base_url`, such as `http://127.0.0.1:7878`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_owned(),
+            access_token: None,
+        }
+    }
+
+    /// Sends `access_token` with every request, as the server requires.
+    pub fn with_access_token(self, access_token: impl Into<String>) -> Self {
+        Self {
+            access_token: Some(access_token.into()),
+            ..self
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let request: RequestBuilder = self.http.request(method, format!("{}{}", self.base_url, path));
+
+        match &self.access_token {
+            Some(access_token) => request.bearer_auth(access_token),
+            None => request,
+        }
+    }
+
+    /// Sends `request` and decodes the answer. A server which cannot be reached or answers with something else
+    /// than an API error is reported as a storage failure.
+    pub async fn call<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T, ApiError> {
+        let response = request.send().await.map_err(to_storage_error)?;
+
+        if response.status().is_success() {
+            return response.json::<T>().await.map_err(to_storage_error);
+        }
+
+        let status = response.status();
+        Err(response.json::<ApiError>().await.unwrap_or_else(|_| ApiError::Storage {
+            reason: format!("server answered {}", status),
+        }))
+    }
+
+    /// Lists the documents at `path` a page at a time, `key` telling where the next page starts. A page is only asked
+    /// for once the previous one has been consumed.
+    pub fn paged<'a, D>(&'a self, path: &'a str, key: fn(&D) -> String) -> BoxStream<'a, Result<D, ApiError>>
+    where
+        D: DeserializeOwned + Send + 'a,
+    {
+        stream::unfold(Some(None::<String>), move |cursor: Option<Option<String>>| async move {
+            let query: PageQuery = PageQuery {
+                after: cursor?,
+                limit: Some(PAGE_SIZE),
+            };
+
+            match self.call::<Vec<D>>(self.request(Method::GET, path).query(&query)).await {
+                Ok(documents) => {
+                    let next: Option<Option<String>> = match documents.last() {
+                        Some(last) if documents.len() == PAGE_SIZE => Some(Some(key(last))),
+                        _ => None,
+                    };
+
+                    Some((documents.into_iter().map(Ok).collect::<Vec<Result<D, ApiError>>>(), next))
+                }
+                Err(e) => Some((vec![Err(e)], None)),
+            }
+        })
+        .flat_map(stream::iter)
+        .boxed()
+    }
+}
+
+fn to_storage_error(error: reqwest::Error) -> ApiError {
+    ApiError::Storage { reason: error.to_string() }
+}
@@ -0,0 +1,4 @@
+mod repository_router;
+
+pub use repository_router::ServedRepositories;
+pub use repository_router::repository_router;
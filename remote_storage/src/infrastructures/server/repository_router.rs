@@ -0,0 +1,396 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, Request, State},
+    http::header::AUTHORIZATION,
+    middleware::{self, Next},
+    response::Response,
+    routing::{delete, get, post},
+};
+use futures::{StreamExt, stream::BoxStream};
+use tokio::sync::RwLock;
+use uuid_b64::UuidB64;
+
+use expense_tracking::domain::{
+    entities::{Brand, Category, Product, Store, Transaction},
+    repositories::{
+        BrandRepository, CategoryRepository, DeleteBehaviour, ProductRepository, StoreRepository, TransactionRepository, Trashed,
+    },
+};
+
+use crate::infrastructures::api::{
+    ApiError, BrandDeletionDocument, BrandDocument, CategoryDocument, DeleteBehaviourDocument, PageQuery, ProductDocument, PurgeQuery,
+    StoreDocument, TransactionDocument, TrashedDocument,
+};
+
+/// The repositories a server shares with its clients.
+pub struct ServedRepositories {
+    pub brands: Box<dyn BrandRepository>,
+    pub categories: Box<dyn CategoryRepository + Send + Sync>,
+    pub stores: Box<dyn StoreRepository + Send + Sync>,
+    pub products: Box<dyn ProductRepository + Send + Sync>,
+    pub transactions: Box<dyn TransactionRepository + Send + Sync>,
+}
+
+/// Repositories taking `&mut self` for writes are shared between requests behind a lock, so that reads still run
+/// concurrently.
+struct ServerState {
+    brands: Box<dyn BrandRepository>,
+    categories: RwLock<Box<dyn CategoryRepository + Send + Sync>>,
+    stores: RwLock<Box<dyn StoreRepository + Send + Sync>>,
+    products: RwLock<Box<dyn ProductRepository + Send + Sync>>,
+    transactions: RwLock<Box<dyn TransactionRepository + Send + Sync>>,
+}
+
+type SharedState = State<Arc<ServerState>>;
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+/// Exposes the five repository traits as a JSON API. Every kind of entity gets the same routes:
+///
+/// - `GET /{kind}` lists the entities, a page of them with `?after=&limit=`, `PUT /{kind}` creates or updates one;
+/// - `DELETE /{kind}/{id}` moves one to the trash, with the delete behaviour as body;
+/// - `GET /{kind}/trash` lists the trash, `DELETE /{kind}/trash?older_than=` purges it;
+/// - `POST /{kind}/trash/{id}/restore` brings one back.
+///
+/// Brands are identified by name, so they are sent as body instead: `POST /brands` creates, `PUT /brands` updates,
+/// `DELETE /brands` deletes and `POST /brands/trash/restore` restores.
+///
+/// Every request must carry `access_token` as `Authorization: Bearer` header, anything else is answered with a 401.
+pub fn repository_router(repositories: ServedRepositories, access_token: impl Into<String>) -> Router {
+    let state: ServerState = ServerState {
+        brands: repositories.brands,
+        categories: RwLock::new(repositories.categories),
+        stores: RwLock::new(repositories.stores),
+        products: RwLock::new(repositories.products),
+        transactions: RwLock::new(repositories.transactions),
+    };
+
+    Router::new()
+        .route(
+            "/brands",
+            get(retrieve_brands).post(create_brand).put(update_brand).delete(delete_brand),
+        )
+        .route("/brands/trash", get(list_trashed_brands).delete(purge_brands))
+        .route("/brands/trash/restore", post(restore_brand))
+        .route("/categories", get(retrieve_categories).put(save_category))
+        .route("/categories/{id}", delete(delete_category))
+        .route("/categories/trash", get(list_trashed_categories).delete(purge_categories))
+        .route("/categories/trash/{id}/restore", post(restore_category))
+        .route("/stores", get(retrieve_stores).put(save_store))
+        .route("/stores/{id}", delete(delete_store))
+        .route("/stores/trash", get(list_trashed_stores).delete(purge_stores))
+        .route("/stores/trash/{id}/restore", post(restore_store))
+        .route("/products", get(retrieve_products).put(save_product))
+        .route("/products/{id}", delete(delete_product))
+        .route("/products/trash", get(list_trashed_products).delete(purge_products))
+        .route("/products/trash/{id}/restore", post(restore_product))
+        .route("/transactions", get(retrieve_transactions).put(save_transaction))
+        .route("/transactions/{id}", delete(delete_transaction))
+        .route("/transactions/trash", get(list_trashed_transactions).delete(purge_transactions))
+        .route("/transactions/trash/{id}/restore", post(restore_transaction))
+        .layer(middleware::from_fn_with_state(
+            Arc::<str>::from(access_token.into()),
+            require_access_token,
+        ))
+        .with_state(Arc::new(state))
+}
+
+async fn require_access_token(State(access_token): State<Arc<str>>, request: Request, next: Next) -> Result<Response, ApiError> {
+    let bearer: Option<&str> = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|header| header.to_str().ok())
+        .and_then(|header| header.strip_prefix("Bearer "));
+
+    match bearer {
+        Some(token) if constant_time_eq(token.as_bytes(), access_token.as_bytes()) => Ok(next.run(request).await),
+        _ => Err(ApiError::Unauthorized),
+    }
+}
+
+/// Compares every byte whatever the first difference, so that the time a refusal takes tells nothing about the token.
+fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    left.len() == right.len() && left.iter().zip(right).fold(0, |difference, (l, r)| difference | (l ^ r)) == 0
+}
+
+async fn retrieve_brands(State(state): SharedState, Query(query): Query<PageQuery>) -> ApiResult<Vec<BrandDocument>> {
+    let brands: Vec<Brand> = match query.limit {
+        Some(limit) => page(state.brands.stream_all(), |brand| brand.name.clone(), query.after.as_deref(), limit).await?,
+        None => state.brands.retrieve_all().await?,
+    };
+
+    Ok(Json(brands.iter().map(BrandDocument::from).collect()))
+}
+
+async fn create_brand(State(state): SharedState, Json(document): Json<BrandDocument>) -> ApiResult<BrandDocument> {
+    let created: Brand = state.brands.create(&Brand::from(&document)).await?;
+
+    Ok(Json(BrandDocument::from(&created)))
+}
+
+async fn update_brand(State(state): SharedState, Json(document): Json<BrandDocument>) -> ApiResult<BrandDocument> {
+    let updated: Brand = state.brands.update(&Brand::from(&document)).await?;
+
+    Ok(Json(BrandDocument::from(&updated)))
+}
+
+async fn delete_brand(State(state): SharedState, Json(deletion): Json<BrandDeletionDocument>) -> ApiResult<BrandDocument> {
+    let deleted: Brand = state
+        .brands
+        .delete(&Brand::from(&deletion.brand), DeleteBehaviour::from(&deletion.behaviour))
+        .await?;
+
+    Ok(Json(BrandDocument::from(&deleted)))
+}
+
+async fn list_trashed_brands(State(state): SharedState) -> ApiResult<Vec<TrashedDocument<BrandDocument>>> {
+    let trashed: Vec<Trashed<Brand>> = state.brands.list_trashed().await?;
+
+    Ok(Json(trashed.iter().map(TrashedDocument::from).collect()))
+}
+
+async fn restore_brand(State(state): SharedState, Json(document): Json<BrandDocument>) -> ApiResult<BrandDocument> {
+    let restored: Brand = state.brands.restore(&Brand::from(&document)).await?;
+
+    Ok(Json(BrandDocument::from(&restored)))
+}
+
+async fn purge_brands(State(state): SharedState, Query(query): Query<PurgeQuery>) -> ApiResult<Vec<BrandDocument>> {
+    let purged: Vec<Brand> = state.brands.purge_older_than(query.older_than).await?;
+
+    Ok(Json(purged.iter().map(BrandDocument::from).collect()))
+}
+
+async fn retrieve_categories(State(state): SharedState, Query(query): Query<PageQuery>) -> ApiResult<Vec<CategoryDocument>> {
+    let repository = state.categories.read().await;
+    let categories: Vec<Category> = match query.limit {
+        Some(limit) => {
+            page(
+                repository.stream_all(),
+                |category| category.id.to_string(),
+                query.after.as_deref(),
+                limit,
+            )
+            .await?
+        }
+        None => repository.retrieve_all().await?,
+    };
+
+    Ok(Json(categories.iter().map(CategoryDocument::from).collect()))
+}
+
+async fn save_category(State(state): SharedState, Json(document): Json<CategoryDocument>) -> ApiResult<Option<CategoryDocument>> {
+    let previous: Option<Category> = state.categories.write().await.create_or_update(&Category::from(&document)).await?;
+
+    Ok(Json(previous.as_ref().map(CategoryDocument::from)))
+}
+
+async fn delete_category(
+    State(state): SharedState,
+    Path(id): Path<UuidB64>,
+    Json(behaviour): Json<DeleteBehaviourDocument<CategoryDocument>>,
+) -> ApiResult<CategoryDocument> {
+    let deleted: Category = state
+        .categories
+        .write()
+        .await
+        .delete(&id, DeleteBehaviour::from(&behaviour))
+        .await?;
+
+    Ok(Json(CategoryDocument::from(&deleted)))
+}
+
+async fn list_trashed_categories(State(state): SharedState) -> ApiResult<Vec<TrashedDocument<CategoryDocument>>> {
+    let trashed: Vec<Trashed<Category>> = state.categories.read().await.list_trashed().await?;
+
+    Ok(Json(trashed.iter().map(TrashedDocument::from).collect()))
+}
+
+async fn restore_category(State(state): SharedState, Path(id): Path<UuidB64>) -> ApiResult<CategoryDocument> {
+    let restored: Category = state.categories.write().await.restore(&id).await?;
+
+    Ok(Json(CategoryDocument::from(&restored)))
+}
+
+async fn purge_categories(State(state): SharedState, Query(query): Query<PurgeQuery>) -> ApiResult<Vec<CategoryDocument>> {
+    let purged: Vec<Category> = state.categories.write().await.purge_older_than(query.older_than).await?;
+
+    Ok(Json(purged.iter().map(CategoryDocument::from).collect()))
+}
+
+async fn retrieve_stores(State(state): SharedState, Query(query): Query<PageQuery>) -> ApiResult<Vec<StoreDocument>> {
+    let repository = state.stores.read().await;
+    let stores: Vec<Store> = match query.limit {
+        Some(limit) => page(repository.stream_all(), |store| store.id.to_string(), query.after.as_deref(), limit).await?,
+        None => repository.retrieve_all().await?,
+    };
+
+    Ok(Json(stores.iter().map(StoreDocument::from).collect()))
+}
+
+async fn save_store(State(state): SharedState, Json(document): Json<StoreDocument>) -> ApiResult<Option<StoreDocument>> {
+    let previous: Option<Store> = state.stores.write().await.create_or_update(&Store::from(&document)).await?;
+
+    Ok(Json(previous.as_ref().map(StoreDocument::from)))
+}
+
+async fn delete_store(
+    State(state): SharedState,
+    Path(id): Path<UuidB64>,
+    Json(behaviour): Json<DeleteBehaviourDocument<StoreDocument>>,
+) -> ApiResult<StoreDocument> {
+    let deleted: Store = state.stores.write().await.delete(&id, DeleteBehaviour::from(&behaviour)).await?;
+
+    Ok(Json(StoreDocument::from(&deleted)))
+}
+
+async fn list_trashed_stores(State(state): SharedState) -> ApiResult<Vec<TrashedDocument<StoreDocument>>> {
+    let trashed: Vec<Trashed<Store>> = state.stores.read().await.list_trashed().await?;
+
+    Ok(Json(trashed.iter().map(TrashedDocument::from).collect()))
+}
+
+async fn restore_store(State(state): SharedState, Path(id): Path<UuidB64>) -> ApiResult<StoreDocument> {
+    let restored: Store = state.stores.write().await.restore(&id).await?;
+
+    Ok(Json(StoreDocument::from(&restored)))
+}
+
+async fn purge_stores(State(state): SharedState, Query(query): Query<PurgeQuery>) -> ApiResult<Vec<StoreDocument>> {
+    let purged: Vec<Store> = state.stores.write().await.purge_older_than(query.older_than).await?;
+
+    Ok(Json(purged.iter().map(StoreDocument::from).collect()))
+}
+
+async fn retrieve_products(State(state): SharedState, Query(query): Query<PageQuery>) -> ApiResult<Vec<ProductDocument>> {
+    let repository = state.products.read().await;
+    let products: Vec<Product> = match query.limit {
+        Some(limit) => {
+            page(
+                repository.stream_all(),
+                |product| product.id.to_string(),
+                query.after.as_deref(),
+                limit,
+            )
+            .await?
+        }
+        None => repository.retrieve_all().await?,
+    };
+
+    Ok(Json(products.iter().map(ProductDocument::from).collect()))
+}
+
+async fn save_product(State(state): SharedState, Json(document): Json<ProductDocument>) -> ApiResult<Option<ProductDocument>> {
+    let previous: Option<Product> = state.products.write().await.create_or_update(&Product::from(&document)).await?;
+
+    Ok(Json(previous.as_ref().map(ProductDocument::from)))
+}
+
+async fn delete_product(
+    State(state): SharedState,
+    Path(id): Path<UuidB64>,
+    Json(behaviour): Json<DeleteBehaviourDocument<ProductDocument>>,
+) -> ApiResult<ProductDocument> {
+    let deleted: Product = state.products.write().await.delete(&id, DeleteBehaviour::from(&behaviour)).await?;
+
+    Ok(Json(ProductDocument::from(&deleted)))
+}
+
+async fn list_trashed_products(State(state): SharedState) -> ApiResult<Vec<TrashedDocument<ProductDocument>>> {
+    let trashed: Vec<Trashed<Product>> = state.products.read().await.list_trashed().await?;
+
+    Ok(Json(trashed.iter().map(TrashedDocument::from).collect()))
+}
+
+async fn restore_product(State(state): SharedState, Path(id): Path<UuidB64>) -> ApiResult<ProductDocument> {
+    let restored: Product = state.products.write().await.restore(&id).await?;
+
+    Ok(Json(ProductDocument::from(&restored)))
+}
+
+async fn purge_products(State(state): SharedState, Query(query): Query<PurgeQuery>) -> ApiResult<Vec<ProductDocument>> {
+    let purged: Vec<Product> = state.products.write().await.purge_older_than(query.older_than).await?;
+
+    Ok(Json(purged.iter().map(ProductDocument::from).collect()))
+}
+
+async fn retrieve_transactions(State(state): SharedState, Query(query): Query<PageQuery>) -> ApiResult<Vec<TransactionDocument>> {
+    let repository = state.transactions.read().await;
+    let transactions: Vec<Transaction> = match query.limit {
+        Some(limit) => {
+            page(
+                repository.stream_all(),
+                |transaction| transaction.id.to_string(),
+                query.after.as_deref(),
+                limit,
+            )
+            .await?
+        }
+        None => repository.retrieve_all().await?,
+    };
+
+    Ok(Json(transactions.iter().map(TransactionDocument::from).collect()))
+}
+
+async fn save_transaction(State(state): SharedState, Json(document): Json<TransactionDocument>) -> ApiResult<Option<TransactionDocument>> {
+    let previous: Option<Transaction> = state
+        .transactions
+        .write()
+        .await
+        .create_or_update(&Transaction::from(&document))
+        .await?;
+
+    Ok(Json(previous.as_ref().map(TransactionDocument::from)))
+}
+
+async fn delete_transaction(State(state): SharedState, Path(id): Path<UuidB64>) -> ApiResult<TransactionDocument> {
+    let deleted: Transaction = state.transactions.write().await.delete(&id).await?;
+
+    Ok(Json(TransactionDocument::from(&deleted)))
+}
+
+async fn list_trashed_transactions(State(state): SharedState) -> ApiResult<Vec<TrashedDocument<TransactionDocument>>> {
+    let trashed: Vec<Trashed<Transaction>> = state.transactions.read().await.list_trashed().await?;
+
+    Ok(Json(trashed.iter().map(TrashedDocument::from).collect()))
+}
+
+async fn restore_transaction(State(state): SharedState, Path(id): Path<UuidB64>) -> ApiResult<TransactionDocument> {
+    let restored: Transaction = state.transactions.write().await.restore(&id).await?;
+
+    Ok(Json(TransactionDocument::from(&restored)))
+}
+
+async fn purge_transactions(State(state): SharedState, Query(query): Query<PurgeQuery>) -> ApiResult<Vec<TransactionDocument>> {
+    let purged: Vec<Transaction> = state.transactions.write().await.purge_older_than(query.older_than).await?;
+
+    Ok(Json(purged.iter().map(TransactionDocument::from).collect()))
+}
+
+/// The `limit` entities keyed right after `after`, picked while the repository streams them so that serving a page never
+/// holds more than one page, whatever order the repository streams in.
+async fn page<T, E>(
+    mut entities: BoxStream<'_, Result<T, E>>,
+    key: fn(&T) -> String,
+    after: Option<&str>,
+    limit: usize,
+) -> Result<Vec<T>, E> {
+    let mut page: BTreeMap<String, T> = BTreeMap::new();
+
+    while let Some(entity) = entities.next().await {
+        let entity: T = entity?;
+        let key: String = key(&entity);
+
+        if after.is_some_and(|after| key.as_str() <= after) {
+            continue;
+        }
+        page.insert(key, entity);
+        if page.len() > limit {
+            page.pop_last();
+        }
+    }
+
+    Ok(page.into_values().collect())
+}
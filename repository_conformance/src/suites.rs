@@ -0,0 +1,105 @@
+pub mod brands;
+pub mod categories;
+mod fixtures;
+pub mod products;
+mod repository_backend;
+pub mod stores;
+pub mod transactions;
+
+pub use repository_backend::RepositoryBackend;
+
+/// Expands into one `#[tokio::test]` per conformance check of the five repository traits, each running against a
+/// fresh backend built by `$backend`.
+///
+/// The tests are grouped in one module per repository trait, which can see everything in scope where the macro is
+/// invoked:
+///
+/// ```ignore
+/// #[cfg(test)]
+/// mod conformance_tests {
+///     use repository_conformance::repository_conformance_suite;
+///
+///     repository_conformance_suite!(InMemoryBackend::new());
+/// }
+/// ```
+#[macro_export]
+macro_rules! repository_conformance_suite {
+    ($backend:expr) => {
+        $crate::repository_conformance_tests! {
+            $backend;
+            brands: [
+                create_should_make_the_brand_retrievable,
+                create_should_refuse_an_existing_name,
+                create_should_refuse_a_name_differing_only_by_case_accents_or_spacing,
+                update_should_bump_the_version_and_refuse_stale_versions,
+                missing_brands_should_be_reported_as_not_found,
+                update_delete_and_restore_should_find_the_brand_under_any_spelling,
+                retrieve_all_and_stream_all_should_list_brands_ordered_by_name,
+                stream_all_should_list_brands_spanning_several_pages,
+                delete_restore_and_purge_should_move_the_brand_through_the_trash,
+                trashed_brands_should_be_visible_to_every_repository_of_the_backend,
+                concurrent_creates_of_one_name_should_keep_a_single_brand,
+            ],
+            categories: [
+                create_or_update_should_return_the_previous_category,
+                create_or_update_should_refuse_stale_versions,
+                create_or_update_should_refuse_a_name_differing_only_by_case_accents_or_spacing,
+                missing_categories_should_be_reported_as_not_found,
+                retrieve_all_and_stream_all_should_list_categories_ordered_by_id,
+                delete_restore_and_purge_should_move_the_category_through_the_trash,
+                trashed_categories_should_be_visible_to_every_repository_of_the_backend,
+                concurrent_creates_from_separate_repositories_should_all_be_kept,
+            ],
+            stores: [
+                create_or_update_should_return_the_previous_store,
+                create_or_update_should_refuse_stale_versions,
+                create_or_update_should_refuse_a_name_differing_only_by_case_accents_or_spacing,
+                missing_stores_should_be_reported_as_not_found,
+                retrieve_all_and_stream_all_should_list_stores_ordered_by_id,
+                delete_restore_and_purge_should_move_the_store_through_the_trash,
+                trashed_stores_should_be_visible_to_every_repository_of_the_backend,
+                concurrent_creates_from_separate_repositories_should_all_be_kept,
+            ],
+            products: [
+                create_or_update_should_require_an_existing_brand_and_category,
+                create_or_update_should_return_the_previous_product,
+                create_or_update_should_refuse_stale_versions,
+                create_or_update_should_refuse_a_name_taken_within_the_brand,
+                missing_products_should_be_reported_as_not_found,
+                retrieve_all_and_stream_all_should_list_products_ordered_by_id,
+                delete_with_restrict_should_refuse_a_referenced_brand_or_category,
+            ],
+            transactions: [
+                create_or_update_should_require_an_existing_store_and_products,
+                create_or_update_should_keep_the_items_and_return_the_previous_transaction,
+                create_or_update_should_refuse_stale_versions,
+                missing_transactions_should_be_reported_as_not_found,
+                retrieve_all_and_stream_all_should_list_transactions_ordered_by_id,
+                delete_restore_and_purge_should_move_the_transaction_through_the_trash,
+                trashed_transactions_should_be_visible_to_every_repository_of_the_backend,
+                delete_with_restrict_should_refuse_a_referenced_store_or_product,
+                delete_with_cascade_should_trash_the_transactions_until_their_root_is_restored,
+            ],
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! repository_conformance_tests {
+    ($backend:expr; $($suite:ident: [$($test:ident),* $(,)?]),* $(,)?) => {
+        $(
+            mod $suite {
+                #[allow(unused_imports)]
+                use super::*;
+
+                $(
+                    #[tokio::test]
+                    async fn $test() {
+                        $crate::suites::$suite::$test(&$backend).await;
+                    }
+                )*
+            }
+        )*
+    };
+}
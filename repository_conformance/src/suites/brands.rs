@@ -0,0 +1,207 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+
+use expense_tracking::domain::{
+    entities::Brand,
+    repositories::{
+        BrandRepository, BrandRepositoryCreateError, BrandRepositoryDeleteError, BrandRepositoryRestoreError,
+        BrandRepositoryRetrieveAllError, BrandRepositoryUpdateError, DeleteBehaviour, Trashed,
+    },
+};
+
+use crate::suites::{
+    RepositoryBackend,
+    fixtures::{CONCURRENT_WRITERS, collect_stream, given_brand},
+};
+
+pub async fn create_should_make_the_brand_retrievable(backend: &impl RepositoryBackend) {
+    let brand: Brand = Brand::new("Nestlé".to_owned());
+    let repository = backend.brands();
+
+    assert_eq!(repository.create(&brand).await, Ok(brand.clone()));
+
+    let result: Result<Vec<Brand>, BrandRepositoryRetrieveAllError> = backend.brands().retrieve_all().await;
+    let expected: Result<Vec<Brand>, BrandRepositoryRetrieveAllError> = Ok(vec![brand]);
+
+    assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+}
+
+pub async fn create_should_refuse_an_existing_name(backend: &impl RepositoryBackend) {
+    let brand: Brand = given_brand(backend, "Nestlé").await;
+
+    let result: Result<Brand, BrandRepositoryCreateError> = backend.brands().create(&brand).await;
+    let expected: Result<Brand, BrandRepositoryCreateError> = Err(BrandRepositoryCreateError::BrandAlreadyExists);
+
+    assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    assert_eq!(backend.brands().retrieve_all().await.map(|brands| brands.len()), Ok(1));
+}
+
+pub async fn create_should_refuse_a_name_differing_only_by_case_accents_or_spacing(backend: &impl RepositoryBackend) {
+    given_brand(backend, "Nestlé").await;
+    let repository = backend.brands();
+
+    for name in ["nestle", "NESTLÉ", " Nestlé ", "Nestle\u{301}"] {
+        let result: Result<Brand, BrandRepositoryCreateError> = repository.create(&Brand::new(name.to_owned())).await;
+        let expected: Result<Brand, BrandRepositoryCreateError> = Err(BrandRepositoryCreateError::BrandAlreadyExists);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+    assert_eq!(repository.retrieve_all().await, Ok(vec![Brand::new("Nestlé".to_owned())]));
+}
+
+pub async fn update_should_bump_the_version_and_refuse_stale_versions(backend: &impl RepositoryBackend) {
+    let brand: Brand = given_brand(backend, "Nestlé").await;
+    let repository = backend.brands();
+
+    let updated: Result<Brand, BrandRepositoryUpdateError> = repository.update(&brand).await;
+    assert_eq!(updated.as_ref().map(|brand| brand.version), Ok(1));
+
+    let result: Result<Brand, BrandRepositoryUpdateError> = repository.update(&brand).await;
+    let expected: Result<Brand, BrandRepositoryUpdateError> = Err(BrandRepositoryUpdateError::VersionConflict { current_version: 1 });
+
+    assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    assert_eq!(repository.retrieve_all().await, Ok(vec![updated.unwrap()]));
+}
+
+pub async fn missing_brands_should_be_reported_as_not_found(backend: &impl RepositoryBackend) {
+    given_brand(backend, "Nestlé").await;
+    let missing: Brand = Brand::new("Danone".to_owned());
+    let repository = backend.brands();
+
+    assert_eq!(repository.update(&missing).await, Err(BrandRepositoryUpdateError::BrandNotFound));
+    assert_eq!(
+        repository.delete(&missing, DeleteBehaviour::Restrict).await,
+        Err(BrandRepositoryDeleteError::BrandNotFound)
+    );
+    assert_eq!(
+        repository.restore(&missing).await,
+        Err(BrandRepositoryRestoreError::BrandNotInTrash)
+    );
+}
+
+pub async fn update_delete_and_restore_should_find_the_brand_under_any_spelling(backend: &impl RepositoryBackend) {
+    given_brand(backend, "Nestlé").await;
+    let repository = backend.brands();
+    let stored: Brand = Brand {
+        version: 1,
+        ..Brand::new("Nestlé".to_owned())
+    };
+
+    let result: Result<Brand, BrandRepositoryUpdateError> = repository.update(&Brand::new("nestle".to_owned())).await;
+    let expected: Result<Brand, BrandRepositoryUpdateError> = Ok(stored.clone());
+
+    assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    assert_eq!(
+        repository
+            .delete(&Brand::new("NESTLE ".to_owned()), DeleteBehaviour::Restrict)
+            .await,
+        Ok(stored.clone())
+    );
+    assert_eq!(repository.restore(&Brand::new("nestlé".to_owned())).await, Ok(stored.clone()));
+    assert_eq!(repository.retrieve_all().await, Ok(vec![stored]));
+}
+
+pub async fn retrieve_all_and_stream_all_should_list_brands_ordered_by_name(backend: &impl RepositoryBackend) {
+    for name in ["Migros Bio", "Alnatura", "Zweifel", "Coop"] {
+        given_brand(backend, name).await;
+    }
+    let repository = backend.brands();
+
+    let result: Result<Vec<String>, BrandRepositoryRetrieveAllError> = repository
+        .retrieve_all()
+        .await
+        .map(|brands| brands.into_iter().map(|brand| brand.name).collect());
+    let expected: Result<Vec<String>, BrandRepositoryRetrieveAllError> = Ok(vec![
+        "Alnatura".to_owned(),
+        "Coop".to_owned(),
+        "Migros Bio".to_owned(),
+        "Zweifel".to_owned(),
+    ]);
+
+    assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    assert_eq!(collect_stream(repository.stream_all()).await, repository.retrieve_all().await);
+}
+
+pub async fn stream_all_should_list_brands_spanning_several_pages(backend: &impl RepositoryBackend) {
+    for i in 0..250 {
+        given_brand(backend, &format!("Brand {:03}", i)).await;
+    }
+    let repository = backend.brands();
+
+    let result: Result<Vec<String>, BrandRepositoryRetrieveAllError> = collect_stream(repository.stream_all())
+        .await
+        .map(|brands| brands.into_iter().map(|brand| brand.name).collect());
+    let expected: Result<Vec<String>, BrandRepositoryRetrieveAllError> = Ok((0..250).map(|i| format!("Brand {:03}", i)).collect());
+
+    assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+}
+
+pub async fn delete_restore_and_purge_should_move_the_brand_through_the_trash(backend: &impl RepositoryBackend) {
+    let brand: Brand = given_brand(backend, "Nestlé").await;
+    let repository = backend.brands();
+
+    assert_eq!(repository.delete(&brand, DeleteBehaviour::Restrict).await, Ok(brand.clone()));
+    assert_eq!(repository.retrieve_all().await, Ok(vec![]));
+    assert_eq!(
+        repository
+            .list_trashed()
+            .await
+            .map(|trashed| trashed.into_iter().map(|t: Trashed<Brand>| t.entity).collect::<Vec<Brand>>()),
+        Ok(vec![brand.clone()])
+    );
+
+    assert_eq!(repository.restore(&brand).await, Ok(brand.clone()));
+    assert_eq!(repository.restore(&brand).await, Err(BrandRepositoryRestoreError::BrandNotInTrash));
+    assert_eq!(repository.retrieve_all().await, Ok(vec![brand.clone()]));
+
+    assert!(repository.delete(&brand, DeleteBehaviour::Restrict).await.is_ok());
+    assert_eq!(repository.purge_older_than(Utc::now() - Duration::days(30)).await, Ok(vec![]));
+    assert_eq!(
+        repository.purge_older_than(Utc::now() + Duration::seconds(1)).await,
+        Ok(vec![brand])
+    );
+    assert_eq!(repository.list_trashed().await, Ok(vec![]));
+}
+
+pub async fn trashed_brands_should_be_visible_to_every_repository_of_the_backend(backend: &impl RepositoryBackend) {
+    let brand: Brand = given_brand(backend, "Nestlé").await;
+
+    assert_eq!(backend.brands().delete(&brand, DeleteBehaviour::Restrict).await, Ok(brand.clone()));
+    assert_eq!(
+        backend
+            .brands()
+            .list_trashed()
+            .await
+            .map(|trashed| trashed.into_iter().map(|t: Trashed<Brand>| t.entity).collect::<Vec<Brand>>()),
+        Ok(vec![brand.clone()])
+    );
+    assert_eq!(backend.brands().restore(&brand).await, Ok(brand.clone()));
+    assert_eq!(backend.brands().retrieve_all().await, Ok(vec![brand]));
+}
+
+pub async fn concurrent_creates_of_one_name_should_keep_a_single_brand(backend: &impl RepositoryBackend) {
+    let repository: Arc<_> = Arc::new(backend.brands());
+
+    let tasks = (0..CONCURRENT_WRITERS).map(|_| {
+        let repository = Arc::clone(&repository);
+        tokio::spawn(async move { repository.create(&Brand::new("Nestlé".to_owned())).await })
+    });
+    let results: Vec<Result<Brand, BrandRepositoryCreateError>> = futures::future::join_all(tasks)
+        .await
+        .into_iter()
+        .map(|task| task.unwrap())
+        .collect();
+
+    let result: usize = results.iter().filter(|result| result.is_ok()).count();
+
+    assert_eq!(result, 1, "Expected {:?}, but got {:?}", 1, result);
+    assert!(
+        results
+            .iter()
+            .all(|result| matches!(result, Ok(_) | Err(BrandRepositoryCreateError::BrandAlreadyExists))),
+        "Unexpected results {:?}",
+        results
+    );
+    assert_eq!(backend.brands().retrieve_all().await, Ok(vec![Brand::new("Nestlé".to_owned())]));
+}
@@ -0,0 +1,169 @@
+use chrono::{Duration, Utc};
+
+use expense_tracking::domain::{
+    entities::Category,
+    repositories::{CategoryRepository, CategoryRepositoryError, DeleteBehaviour, Trashed},
+};
+
+use crate::suites::{
+    RepositoryBackend,
+    fixtures::{CONCURRENT_WRITERS, collect_stream, given_category, sorted_by_key},
+};
+
+pub async fn create_or_update_should_return_the_previous_category(backend: &impl RepositoryBackend) {
+    let category: Category = Category::new(None, "Dairy".to_owned());
+    let renamed: Category = Category::new(Some(category.id), "Dairy Products".to_owned());
+    let mut repository = backend.categories();
+
+    assert_eq!(repository.create_or_update(&category).await, Ok(None));
+
+    let result: Result<Option<Category>, CategoryRepositoryError> = repository.create_or_update(&renamed).await;
+    let expected: Result<Option<Category>, CategoryRepositoryError> = Ok(Some(category));
+
+    assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    assert_eq!(
+        backend.categories().retrieve_all().await,
+        Ok(vec![Category { version: 1, ..renamed }])
+    );
+}
+
+pub async fn create_or_update_should_refuse_stale_versions(backend: &impl RepositoryBackend) {
+    let category: Category = given_category(backend, "Dairy").await;
+    let mut repository = backend.categories();
+
+    assert!(repository.create_or_update(&category).await.is_ok());
+
+    let result: Result<Option<Category>, CategoryRepositoryError> = repository.create_or_update(&category).await;
+    let expected: Result<Option<Category>, CategoryRepositoryError> = Err(CategoryRepositoryError::VersionConflict { current_version: 1 });
+
+    assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+}
+
+pub async fn create_or_update_should_refuse_a_name_differing_only_by_case_accents_or_spacing(backend: &impl RepositoryBackend) {
+    let existing: Category = given_category(backend, "Crèmerie").await;
+    let other: Category = given_category(backend, "Bakery").await;
+    let mut repository = backend.categories();
+
+    for name in ["cremerie", "CRÈMERIE", " Crèmerie ", "Cre\u{300}merie"] {
+        for category in [
+            Category::new(None, name.to_owned()),
+            Category {
+                name: name.to_owned(),
+                ..other.clone()
+            },
+        ] {
+            let result: Result<Option<Category>, CategoryRepositoryError> = repository.create_or_update(&category).await;
+            let expected: Result<Option<Category>, CategoryRepositoryError> = Err(CategoryRepositoryError::CategoryAlreadyExists);
+
+            assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        }
+    }
+    assert_eq!(
+        repository.retrieve_all().await,
+        Ok(sorted_by_key(vec![existing, other], |category| category.id.to_string()))
+    );
+}
+
+pub async fn missing_categories_should_be_reported_as_not_found(backend: &impl RepositoryBackend) {
+    given_category(backend, "Dairy").await;
+    let missing: Category = Category::new(None, "Bakery".to_owned());
+    let mut repository = backend.categories();
+
+    assert_eq!(
+        repository.delete(&missing.id, DeleteBehaviour::Restrict).await,
+        Err(CategoryRepositoryError::CategoryNotFound)
+    );
+    assert_eq!(
+        repository.restore(&missing.id).await,
+        Err(CategoryRepositoryError::CategoryNotInTrash)
+    );
+}
+
+pub async fn retrieve_all_and_stream_all_should_list_categories_ordered_by_id(backend: &impl RepositoryBackend) {
+    let mut categories: Vec<Category> = Vec::new();
+    for name in ["Dairy", "Bakery", "Beverages", "Frozen Food", "Household"] {
+        categories.push(given_category(backend, name).await);
+    }
+    let repository = backend.categories();
+
+    let result: Result<Vec<Category>, CategoryRepositoryError> = repository.retrieve_all().await;
+    let expected: Result<Vec<Category>, CategoryRepositoryError> = Ok(sorted_by_key(categories, |category| category.id.to_string()));
+
+    assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    assert_eq!(collect_stream(repository.stream_all()).await, result);
+}
+
+pub async fn delete_restore_and_purge_should_move_the_category_through_the_trash(backend: &impl RepositoryBackend) {
+    let category: Category = given_category(backend, "Dairy").await;
+    let mut repository = backend.categories();
+
+    assert_eq!(
+        repository.delete(&category.id, DeleteBehaviour::Restrict).await,
+        Ok(category.clone())
+    );
+    assert_eq!(repository.retrieve_all().await, Ok(vec![]));
+    assert_eq!(
+        repository
+            .list_trashed()
+            .await
+            .map(|trashed| trashed.into_iter().map(|t: Trashed<Category>| t.entity).collect::<Vec<Category>>()),
+        Ok(vec![category.clone()])
+    );
+
+    assert_eq!(repository.restore(&category.id).await, Ok(category.clone()));
+    assert_eq!(
+        repository.restore(&category.id).await,
+        Err(CategoryRepositoryError::CategoryNotInTrash)
+    );
+    assert_eq!(repository.retrieve_all().await, Ok(vec![category.clone()]));
+
+    assert!(repository.delete(&category.id, DeleteBehaviour::Restrict).await.is_ok());
+    assert_eq!(repository.purge_older_than(Utc::now() - Duration::days(30)).await, Ok(vec![]));
+    assert_eq!(
+        repository.purge_older_than(Utc::now() + Duration::seconds(1)).await,
+        Ok(vec![category])
+    );
+    assert_eq!(repository.list_trashed().await, Ok(vec![]));
+}
+
+pub async fn trashed_categories_should_be_visible_to_every_repository_of_the_backend(backend: &impl RepositoryBackend) {
+    let category: Category = given_category(backend, "Dairy").await;
+
+    assert_eq!(
+        backend.categories().delete(&category.id, DeleteBehaviour::Restrict).await,
+        Ok(category.clone())
+    );
+    assert_eq!(
+        backend
+            .categories()
+            .list_trashed()
+            .await
+            .map(|trashed| trashed.into_iter().map(|t: Trashed<Category>| t.entity).collect::<Vec<Category>>()),
+        Ok(vec![category.clone()])
+    );
+    assert_eq!(backend.categories().restore(&category.id).await, Ok(category.clone()));
+    assert_eq!(backend.categories().retrieve_all().await, Ok(vec![category]));
+}
+
+pub async fn concurrent_creates_from_separate_repositories_should_all_be_kept(backend: &impl RepositoryBackend) {
+    let categories: Vec<Category> = (0..CONCURRENT_WRITERS)
+        .map(|index| Category::new(None, format!("Category {}", index)))
+        .collect();
+
+    let tasks = categories.iter().cloned().map(|category| {
+        let mut repository = backend.categories();
+        tokio::spawn(async move { repository.create_or_update(&category).await })
+    });
+    let results: Vec<Result<Option<Category>, CategoryRepositoryError>> = futures::future::join_all(tasks)
+        .await
+        .into_iter()
+        .map(|task| task.unwrap())
+        .collect();
+
+    assert!(results.iter().all(|result| *result == Ok(None)), "Unexpected results {:?}", results);
+
+    let result: Result<Vec<Category>, CategoryRepositoryError> = backend.categories().retrieve_all().await;
+    let expected: Result<Vec<Category>, CategoryRepositoryError> = Ok(sorted_by_key(categories, |category| category.id.to_string()));
+
+    assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+}
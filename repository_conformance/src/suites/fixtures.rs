@@ -0,0 +1,67 @@
+use chrono::{DateTime, TimeZone, Utc};
+use futures::StreamExt;
+
+use expense_tracking::domain::{
+    entities::{Brand, Category, Item, Product, Store, Transaction, Unit},
+    repositories::{BrandRepository, CategoryRepository, ProductRepository, StoreRepository},
+};
+
+use crate::suites::RepositoryBackend;
+
+/// Number of tasks writing at the same time in the concurrency checks.
+pub(crate) const CONCURRENT_WRITERS: usize = 16;
+
+pub(crate) async fn given_brand(backend: &impl RepositoryBackend, name: &str) -> Brand {
+    backend.brands().create(&Brand::new(name.to_owned())).await.unwrap()
+}
+
+pub(crate) async fn given_category(backend: &impl RepositoryBackend, name: &str) -> Category {
+    let category: Category = Category::new(None, name.to_owned());
+    backend.categories().create_or_update(&category).await.unwrap();
+    category
+}
+
+pub(crate) async fn given_store(backend: &impl RepositoryBackend, name: &str) -> Store {
+    let store: Store = Store::new(None, name.to_owned());
+    backend.stores().create_or_update(&store).await.unwrap();
+    store
+}
+
+pub(crate) async fn given_product(backend: &impl RepositoryBackend, name: &str) -> Product {
+    let product: Product = Product::new(
+        None,
+        name.to_owned(),
+        given_brand(backend, &format!("{} Brand", name)).await,
+        given_category(backend, &format!("{} Category", name)).await,
+    );
+    backend.products().create_or_update(&product).await.unwrap();
+    product
+}
+
+/// A transaction at a new store buying two new products, not saved yet.
+pub(crate) async fn given_new_transaction(backend: &impl RepositoryBackend) -> Transaction {
+    Transaction::new(
+        None,
+        vec![
+            Item::new(None, given_product(backend, "Milk").await, Unit::Liters(1.5), 1.95),
+            Item::new(None, given_product(backend, "Bread").await, Unit::None, 3.2),
+        ],
+        given_store(backend, "Migros").await,
+        given_datetime(),
+    )
+}
+
+/// Whole seconds, which every backend stores without loss.
+pub(crate) fn given_datetime() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 3, 14, 9, 26, 53).unwrap()
+}
+
+/// Collects a stream of results, stopping at the first error.
+pub(crate) async fn collect_stream<T, E>(stream: futures::stream::BoxStream<'_, Result<T, E>>) -> Result<Vec<T>, E> {
+    stream.collect::<Vec<Result<T, E>>>().await.into_iter().collect()
+}
+
+pub(crate) fn sorted_by_key<T, K: Ord>(mut entities: Vec<T>, key: impl Fn(&T) -> K) -> Vec<T> {
+    entities.sort_by_key(|entity| key(entity));
+    entities
+}
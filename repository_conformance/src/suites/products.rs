@@ -0,0 +1,130 @@
+use expense_tracking::domain::{
+    entities::{Brand, Category, Product},
+    repositories::{
+        BrandRepository, BrandRepositoryDeleteError, CategoryRepository, CategoryRepositoryError, DeleteBehaviour, ProductRepository,
+        ProductRepositoryError,
+    },
+};
+
+use crate::suites::{
+    RepositoryBackend,
+    fixtures::{collect_stream, given_brand, given_category, given_product, sorted_by_key},
+};
+
+pub async fn create_or_update_should_require_an_existing_brand_and_category(backend: &impl RepositoryBackend) {
+    let brand: Brand = given_brand(backend, "Nestlé").await;
+    let category: Category = given_category(backend, "Dairy").await;
+    let mut repository = backend.products();
+
+    let without_brand: Product = Product::new(None, "Milk".to_owned(), Brand::new("Danone".to_owned()), category);
+    let without_category: Product = Product::new(None, "Milk".to_owned(), brand, Category::new(None, "Bakery".to_owned()));
+
+    assert_eq!(
+        repository.create_or_update(&without_brand).await,
+        Err(ProductRepositoryError::BrandNotFound)
+    );
+    assert_eq!(
+        repository.create_or_update(&without_category).await,
+        Err(ProductRepositoryError::CategoryNotFound)
+    );
+    assert_eq!(repository.retrieve_all().await, Ok(vec![]));
+}
+
+pub async fn create_or_update_should_return_the_previous_product(backend: &impl RepositoryBackend) {
+    let product: Product = Product::new(
+        None,
+        "Milk".to_owned(),
+        given_brand(backend, "Nestlé").await,
+        given_category(backend, "Dairy").await,
+    );
+    let renamed: Product = Product {
+        name: "Whole Milk".to_owned(),
+        ..product.clone()
+    };
+    let mut repository = backend.products();
+
+    assert_eq!(repository.create_or_update(&product).await, Ok(None));
+
+    let result: Result<Option<Product>, ProductRepositoryError> = repository.create_or_update(&renamed).await;
+    let expected: Result<Option<Product>, ProductRepositoryError> = Ok(Some(product));
+
+    assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    assert_eq!(backend.products().retrieve_all().await, Ok(vec![Product { version: 1, ..renamed }]));
+}
+
+pub async fn create_or_update_should_refuse_stale_versions(backend: &impl RepositoryBackend) {
+    let product: Product = given_product(backend, "Milk").await;
+    let mut repository = backend.products();
+
+    assert!(repository.create_or_update(&product).await.is_ok());
+
+    let result: Result<Option<Product>, ProductRepositoryError> = repository.create_or_update(&product).await;
+    let expected: Result<Option<Product>, ProductRepositoryError> = Err(ProductRepositoryError::VersionConflict { current_version: 1 });
+
+    assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+}
+
+pub async fn create_or_update_should_refuse_a_name_taken_within_the_brand(backend: &impl RepositoryBackend) {
+    let brand: Brand = given_brand(backend, "Nestlé").await;
+    let category: Category = given_category(backend, "Dairy").await;
+    let product: Product = Product::new(None, "Crème".to_owned(), brand.clone(), category.clone());
+    let other_brand: Product = Product::new(None, "Crème".to_owned(), given_brand(backend, "Danone").await, category.clone());
+    let mut repository = backend.products();
+
+    assert_eq!(repository.create_or_update(&product).await, Ok(None));
+
+    let result: Result<Option<Product>, ProductRepositoryError> = repository
+        .create_or_update(&Product::new(None, " creme ".to_owned(), brand, category))
+        .await;
+    let expected: Result<Option<Product>, ProductRepositoryError> = Err(ProductRepositoryError::ProductAlreadyExists);
+
+    assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    assert_eq!(repository.create_or_update(&other_brand).await, Ok(None));
+    assert_eq!(
+        repository.retrieve_all().await,
+        Ok(sorted_by_key(vec![product, other_brand], |product| product.id.to_string()))
+    );
+}
+
+pub async fn missing_products_should_be_reported_as_not_found(backend: &impl RepositoryBackend) {
+    let product: Product = given_product(backend, "Milk").await;
+    let missing: Product = Product::new(None, "Bread".to_owned(), product.brand, product.category);
+    let mut repository = backend.products();
+
+    assert_eq!(
+        repository.delete(&missing.id, DeleteBehaviour::Restrict).await,
+        Err(ProductRepositoryError::ProductNotFound)
+    );
+    assert_eq!(
+        repository.restore(&missing.id).await,
+        Err(ProductRepositoryError::ProductNotInTrash)
+    );
+}
+
+pub async fn retrieve_all_and_stream_all_should_list_products_ordered_by_id(backend: &impl RepositoryBackend) {
+    let mut products: Vec<Product> = Vec::new();
+    for name in ["Milk", "Bread", "Butter", "Coffee", "Tea"] {
+        products.push(given_product(backend, name).await);
+    }
+    let repository = backend.products();
+
+    let result: Result<Vec<Product>, ProductRepositoryError> = repository.retrieve_all().await;
+    let expected: Result<Vec<Product>, ProductRepositoryError> = Ok(sorted_by_key(products, |product| product.id.to_string()));
+
+    assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    assert_eq!(collect_stream(repository.stream_all()).await, result);
+}
+
+pub async fn delete_with_restrict_should_refuse_a_referenced_brand_or_category(backend: &impl RepositoryBackend) {
+    let product: Product = given_product(backend, "Milk").await;
+
+    assert_eq!(
+        backend.brands().delete(&product.brand, DeleteBehaviour::Restrict).await,
+        Err(BrandRepositoryDeleteError::BrandStillReferenced)
+    );
+    assert_eq!(
+        backend.categories().delete(&product.category.id, DeleteBehaviour::Restrict).await,
+        Err(CategoryRepositoryError::CategoryStillReferenced)
+    );
+    assert_eq!(backend.products().retrieve_all().await, Ok(vec![product]));
+}
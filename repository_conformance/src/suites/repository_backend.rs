@@ -0,0 +1,25 @@
+use expense_tracking::domain::repositories::{
+    BrandRepository, CategoryRepository, ProductRepository, StoreRepository, TransactionRepository,
+};
+
+/// Hands out the repositories of one storage backend to the conformance suites.
+///
+/// Every call returns a new repository over the same storage, so what one of them writes is visible to the others.
+/// The suites rely on it to seed the brands, categories, stores and products that other entities refer to.
+pub trait RepositoryBackend: Send + Sync + 'static {
+    type Brands: BrandRepository + 'static;
+    type Categories: CategoryRepository + Send + Sync + 'static;
+    type Stores: StoreRepository + Send + Sync + 'static;
+    type Products: ProductRepository + Send + Sync + 'static;
+    type Transactions: TransactionRepository + Send + Sync + 'static;
+
+    fn brands(&self) -> Self::Brands;
+
+    fn categories(&self) -> Self::Categories;
+
+    fn stores(&self) -> Self::Stores;
+
+    fn products(&self) -> Self::Products;
+
+    fn transactions(&self) -> Self::Transactions;
+}
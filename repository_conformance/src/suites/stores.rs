@@ -0,0 +1,157 @@
+use chrono::{Duration, Utc};
+
+use expense_tracking::domain::{
+    entities::Store,
+    repositories::{DeleteBehaviour, StoreRepository, StoreRepositoryError, Trashed},
+};
+
+use crate::suites::{
+    RepositoryBackend,
+    fixtures::{CONCURRENT_WRITERS, collect_stream, given_store, sorted_by_key},
+};
+
+pub async fn create_or_update_should_return_the_previous_store(backend: &impl RepositoryBackend) {
+    let store: Store = Store::new(None, "Migros".to_owned());
+    let renamed: Store = Store::new(Some(store.id), "Migros Bahnhof".to_owned());
+    let mut repository = backend.stores();
+
+    assert_eq!(repository.create_or_update(&store).await, Ok(None));
+
+    let result: Result<Option<Store>, StoreRepositoryError> = repository.create_or_update(&renamed).await;
+    let expected: Result<Option<Store>, StoreRepositoryError> = Ok(Some(store));
+
+    assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    assert_eq!(backend.stores().retrieve_all().await, Ok(vec![Store { version: 1, ..renamed }]));
+}
+
+pub async fn create_or_update_should_refuse_stale_versions(backend: &impl RepositoryBackend) {
+    let store: Store = given_store(backend, "Migros").await;
+    let mut repository = backend.stores();
+
+    assert!(repository.create_or_update(&store).await.is_ok());
+
+    let result: Result<Option<Store>, StoreRepositoryError> = repository.create_or_update(&store).await;
+    let expected: Result<Option<Store>, StoreRepositoryError> = Err(StoreRepositoryError::VersionConflict { current_version: 1 });
+
+    assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+}
+
+pub async fn create_or_update_should_refuse_a_name_differing_only_by_case_accents_or_spacing(backend: &impl RepositoryBackend) {
+    let existing: Store = given_store(backend, "Épicerie").await;
+    let other: Store = given_store(backend, "Migros").await;
+    let mut repository = backend.stores();
+
+    for name in ["epicerie", "ÉPICERIE", " Épicerie ", "E\u{301}picerie"] {
+        for store in [
+            Store::new(None, name.to_owned()),
+            Store {
+                name: name.to_owned(),
+                ..other.clone()
+            },
+        ] {
+            let result: Result<Option<Store>, StoreRepositoryError> = repository.create_or_update(&store).await;
+            let expected: Result<Option<Store>, StoreRepositoryError> = Err(StoreRepositoryError::StoreAlreadyExists);
+
+            assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        }
+    }
+    assert_eq!(
+        repository.retrieve_all().await,
+        Ok(sorted_by_key(vec![existing, other], |store| store.id.to_string()))
+    );
+}
+
+pub async fn missing_stores_should_be_reported_as_not_found(backend: &impl RepositoryBackend) {
+    given_store(backend, "Migros").await;
+    let missing: Store = Store::new(None, "Coop".to_owned());
+    let mut repository = backend.stores();
+
+    assert_eq!(
+        repository.delete(&missing.id, DeleteBehaviour::Restrict).await,
+        Err(StoreRepositoryError::StoreNotFound)
+    );
+    assert_eq!(repository.restore(&missing.id).await, Err(StoreRepositoryError::StoreNotInTrash));
+}
+
+pub async fn retrieve_all_and_stream_all_should_list_stores_ordered_by_id(backend: &impl RepositoryBackend) {
+    let mut stores: Vec<Store> = Vec::new();
+    for name in ["Migros", "Coop", "Denner", "Aldi", "Lidl"] {
+        stores.push(given_store(backend, name).await);
+    }
+    let repository = backend.stores();
+
+    let result: Result<Vec<Store>, StoreRepositoryError> = repository.retrieve_all().await;
+    let expected: Result<Vec<Store>, StoreRepositoryError> = Ok(sorted_by_key(stores, |store| store.id.to_string()));
+
+    assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    assert_eq!(collect_stream(repository.stream_all()).await, result);
+}
+
+pub async fn delete_restore_and_purge_should_move_the_store_through_the_trash(backend: &impl RepositoryBackend) {
+    let store: Store = given_store(backend, "Migros").await;
+    let mut repository = backend.stores();
+
+    assert_eq!(repository.delete(&store.id, DeleteBehaviour::Restrict).await, Ok(store.clone()));
+    assert_eq!(repository.retrieve_all().await, Ok(vec![]));
+    assert_eq!(
+        repository
+            .list_trashed()
+            .await
+            .map(|trashed| trashed.into_iter().map(|t: Trashed<Store>| t.entity).collect::<Vec<Store>>()),
+        Ok(vec![store.clone()])
+    );
+
+    assert_eq!(repository.restore(&store.id).await, Ok(store.clone()));
+    assert_eq!(repository.restore(&store.id).await, Err(StoreRepositoryError::StoreNotInTrash));
+    assert_eq!(repository.retrieve_all().await, Ok(vec![store.clone()]));
+
+    assert!(repository.delete(&store.id, DeleteBehaviour::Restrict).await.is_ok());
+    assert_eq!(repository.purge_older_than(Utc::now() - Duration::days(30)).await, Ok(vec![]));
+    assert_eq!(
+        repository.purge_older_than(Utc::now() + Duration::seconds(1)).await,
+        Ok(vec![store])
+    );
+    assert_eq!(repository.list_trashed().await, Ok(vec![]));
+}
+
+pub async fn trashed_stores_should_be_visible_to_every_repository_of_the_backend(backend: &impl RepositoryBackend) {
+    let store: Store = given_store(backend, "Migros").await;
+
+    assert_eq!(
+        backend.stores().delete(&store.id, DeleteBehaviour::Restrict).await,
+        Ok(store.clone())
+    );
+    assert_eq!(
+        backend
+            .stores()
+            .list_trashed()
+            .await
+            .map(|trashed| trashed.into_iter().map(|t: Trashed<Store>| t.entity).collect::<Vec<Store>>()),
+        Ok(vec![store.clone()])
+    );
+    assert_eq!(backend.stores().restore(&store.id).await, Ok(store.clone()));
+    assert_eq!(backend.stores().retrieve_all().await, Ok(vec![store]));
+}
+
+pub async fn concurrent_creates_from_separate_repositories_should_all_be_kept(backend: &impl RepositoryBackend) {
+    let stores: Vec<Store> = (0..CONCURRENT_WRITERS)
+        .map(|index| Store::new(None, format!("Store {}", index)))
+        .collect();
+
+    let tasks = stores.iter().cloned().map(|store| {
+        let mut repository = backend.stores();
+        tokio::spawn(async move { repository.create_or_update(&store).await })
+    });
+    let results: Vec<Result<Option<Store>, StoreRepositoryError>> = futures::future::join_all(tasks)
+        .await
+        .into_iter()
+        .map(|task| task.unwrap())
+        .collect();
+
+    assert!(results.iter().all(|result| *result == Ok(None)), "Unexpected results {:?}", results);
+
+    let result: Result<Vec<Store>, StoreRepositoryError> = backend.stores().retrieve_all().await;
+    let expected: Result<Vec<Store>, StoreRepositoryError> = Ok(sorted_by_key(stores, |store| store.id.to_string()));
+
+    assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+}
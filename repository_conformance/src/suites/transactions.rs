@@ -0,0 +1,226 @@
+use chrono::{Duration, Utc};
+
+use expense_tracking::domain::{
+    entities::{Item, Product, Store, Transaction, Unit},
+    repositories::{
+        BrandRepository, DeleteBehaviour, ProductRepository, ProductRepositoryError, StoreRepository, StoreRepositoryError,
+        TransactionRepository, TransactionRepositoryError, Trashed,
+    },
+};
+
+use crate::suites::{
+    RepositoryBackend,
+    fixtures::{collect_stream, given_datetime, given_new_transaction, given_product, given_store, sorted_by_key},
+};
+
+async fn given_transaction(backend: &impl RepositoryBackend) -> Transaction {
+    let transaction: Transaction = given_new_transaction(backend).await;
+    backend.transactions().create_or_update(&transaction).await.unwrap();
+    transaction
+}
+
+pub async fn create_or_update_should_require_an_existing_store_and_products(backend: &impl RepositoryBackend) {
+    let product: Product = given_product(backend, "Milk").await;
+    let store: Store = given_store(backend, "Migros").await;
+    let mut repository = backend.transactions();
+
+    let at_missing_store: Transaction = Transaction::new(
+        None,
+        vec![Item::new(None, product.clone(), Unit::None, 1.95)],
+        Store::new(None, "Coop".to_owned()),
+        given_datetime(),
+    );
+    let with_missing_product: Transaction = Transaction::new(
+        None,
+        vec![Item::new(
+            None,
+            Product::new(None, "Bread".to_owned(), product.brand, product.category),
+            Unit::None,
+            3.2,
+        )],
+        store,
+        given_datetime(),
+    );
+
+    assert_eq!(
+        repository.create_or_update(&at_missing_store).await,
+        Err(TransactionRepositoryError::StoreNotFound)
+    );
+    assert_eq!(
+        repository.create_or_update(&with_missing_product).await,
+        Err(TransactionRepositoryError::ProductNotFound)
+    );
+    assert_eq!(repository.retrieve_all().await, Ok(vec![]));
+}
+
+pub async fn create_or_update_should_keep_the_items_and_return_the_previous_transaction(backend: &impl RepositoryBackend) {
+    let transaction: Transaction = given_new_transaction(backend).await;
+    let without_bread: Transaction = Transaction {
+        items: transaction.items[..1].to_vec(),
+        ..transaction.clone()
+    };
+    let mut repository = backend.transactions();
+
+    assert_eq!(repository.create_or_update(&transaction).await, Ok(None));
+    assert_eq!(backend.transactions().retrieve_all().await, Ok(vec![transaction.clone()]));
+
+    let result: Result<Option<Transaction>, TransactionRepositoryError> = repository.create_or_update(&without_bread).await;
+    let expected: Result<Option<Transaction>, TransactionRepositoryError> = Ok(Some(transaction));
+
+    assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    assert_eq!(
+        backend.transactions().retrieve_all().await,
+        Ok(vec![Transaction {
+            version: 1,
+            ..without_bread
+        }])
+    );
+}
+
+pub async fn create_or_update_should_refuse_stale_versions(backend: &impl RepositoryBackend) {
+    let transaction: Transaction = given_transaction(backend).await;
+    let mut repository = backend.transactions();
+
+    assert!(repository.create_or_update(&transaction).await.is_ok());
+
+    let result: Result<Option<Transaction>, TransactionRepositoryError> = repository.create_or_update(&transaction).await;
+    let expected: Result<Option<Transaction>, TransactionRepositoryError> =
+        Err(TransactionRepositoryError::VersionConflict { current_version: 1 });
+
+    assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+}
+
+pub async fn missing_transactions_should_be_reported_as_not_found(backend: &impl RepositoryBackend) {
+    let transaction: Transaction = given_transaction(backend).await;
+    let missing: Transaction = Transaction::new(None, vec![], transaction.store, given_datetime());
+    let mut repository = backend.transactions();
+
+    assert_eq!(
+        repository.delete(&missing.id).await,
+        Err(TransactionRepositoryError::TransactionNotFound)
+    );
+    assert_eq!(
+        repository.restore(&missing.id).await,
+        Err(TransactionRepositoryError::TransactionNotInTrash)
+    );
+}
+
+pub async fn retrieve_all_and_stream_all_should_list_transactions_ordered_by_id(backend: &impl RepositoryBackend) {
+    let template: Transaction = given_transaction(backend).await;
+    let mut transactions: Vec<Transaction> = vec![template.clone()];
+    let mut repository = backend.transactions();
+
+    for days in 1..5 {
+        let transaction: Transaction = Transaction::new(
+            None,
+            template
+                .items
+                .iter()
+                .map(|item| Item::new(None, item.product().clone(), item.unit().clone(), item.unitary_price()))
+                .collect(),
+            template.store.clone(),
+            given_datetime() - Duration::days(days),
+        );
+        repository.create_or_update(&transaction).await.unwrap();
+        transactions.push(transaction);
+    }
+
+    let result: Result<Vec<Transaction>, TransactionRepositoryError> = repository.retrieve_all().await;
+    let expected: Result<Vec<Transaction>, TransactionRepositoryError> =
+        Ok(sorted_by_key(transactions, |transaction| transaction.id.to_string()));
+
+    assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    assert_eq!(collect_stream(repository.stream_all()).await, result);
+}
+
+pub async fn delete_restore_and_purge_should_move_the_transaction_through_the_trash(backend: &impl RepositoryBackend) {
+    let transaction: Transaction = given_transaction(backend).await;
+    let mut repository = backend.transactions();
+
+    assert_eq!(repository.delete(&transaction.id).await, Ok(transaction.clone()));
+    assert_eq!(repository.retrieve_all().await, Ok(vec![]));
+    assert_eq!(
+        repository.list_trashed().await.map(|trashed| trashed
+            .into_iter()
+            .map(|t: Trashed<Transaction>| t.entity)
+            .collect::<Vec<Transaction>>()),
+        Ok(vec![transaction.clone()])
+    );
+
+    assert_eq!(repository.restore(&transaction.id).await, Ok(transaction.clone()));
+    assert_eq!(
+        repository.restore(&transaction.id).await,
+        Err(TransactionRepositoryError::TransactionNotInTrash)
+    );
+    assert_eq!(repository.retrieve_all().await, Ok(vec![transaction.clone()]));
+
+    assert!(repository.delete(&transaction.id).await.is_ok());
+    assert_eq!(repository.purge_older_than(Utc::now() - Duration::days(30)).await, Ok(vec![]));
+    assert_eq!(
+        repository.purge_older_than(Utc::now() + Duration::seconds(1)).await,
+        Ok(vec![transaction])
+    );
+    assert_eq!(repository.list_trashed().await, Ok(vec![]));
+}
+
+pub async fn trashed_transactions_should_be_visible_to_every_repository_of_the_backend(backend: &impl RepositoryBackend) {
+    let transaction: Transaction = given_transaction(backend).await;
+
+    assert_eq!(backend.transactions().delete(&transaction.id).await, Ok(transaction.clone()));
+    assert_eq!(
+        backend.transactions().list_trashed().await.map(|trashed| trashed
+            .into_iter()
+            .map(|t: Trashed<Transaction>| t.entity)
+            .collect::<Vec<Transaction>>()),
+        Ok(vec![transaction.clone()])
+    );
+    assert_eq!(backend.transactions().restore(&transaction.id).await, Ok(transaction.clone()));
+    assert_eq!(backend.transactions().retrieve_all().await, Ok(vec![transaction]));
+}
+
+pub async fn delete_with_restrict_should_refuse_a_referenced_store_or_product(backend: &impl RepositoryBackend) {
+    let transaction: Transaction = given_transaction(backend).await;
+
+    assert_eq!(
+        backend.stores().delete(&transaction.store.id, DeleteBehaviour::Restrict).await,
+        Err(StoreRepositoryError::StoreStillReferenced)
+    );
+    assert_eq!(
+        backend
+            .products()
+            .delete(&transaction.items[0].product().id, DeleteBehaviour::Restrict)
+            .await,
+        Err(ProductRepositoryError::ProductStillReferenced)
+    );
+    assert_eq!(backend.transactions().retrieve_all().await, Ok(vec![transaction]));
+}
+
+pub async fn delete_with_cascade_should_trash_the_transactions_until_their_root_is_restored(backend: &impl RepositoryBackend) {
+    let transaction: Transaction = given_transaction(backend).await;
+    let product: Product = transaction.items[0].product().clone();
+
+    assert!(backend.brands().delete(&product.brand, DeleteBehaviour::Cascade).await.is_ok());
+    assert_eq!(backend.transactions().retrieve_all().await, Ok(vec![]));
+    assert_eq!(
+        backend.products().retrieve_all().await,
+        Ok(vec![transaction.items[1].product().clone()])
+    );
+    assert!(backend.brands().restore(&product.brand).await.is_ok());
+    assert_eq!(backend.transactions().retrieve_all().await, Ok(vec![transaction.clone()]));
+
+    assert!(backend.products().delete(&product.id, DeleteBehaviour::Cascade).await.is_ok());
+    assert_eq!(backend.transactions().retrieve_all().await, Ok(vec![]));
+    assert!(backend.products().restore(&product.id).await.is_ok());
+    assert_eq!(backend.transactions().retrieve_all().await, Ok(vec![transaction.clone()]));
+
+    assert!(
+        backend
+            .stores()
+            .delete(&transaction.store.id, DeleteBehaviour::Cascade)
+            .await
+            .is_ok()
+    );
+    assert_eq!(backend.transactions().retrieve_all().await, Ok(vec![]));
+    assert!(backend.stores().restore(&transaction.store.id).await.is_ok());
+    assert_eq!(backend.transactions().retrieve_all().await, Ok(vec![transaction]));
+}
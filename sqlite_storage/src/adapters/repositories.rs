@@ -0,0 +1,13 @@
+mod brand_repository_sqlite_impl;
+mod category_repository_sqlite_impl;
+#[cfg(test)]
+mod conformance_tests;
+mod product_repository_sqlite_impl;
+mod store_repository_sqlite_impl;
+mod transaction_repository_sqlite_impl;
+
+pub use brand_repository_sqlite_impl::BrandRepositorySqliteImpl;
+pub use category_repository_sqlite_impl::CategoryRepositorySqliteImpl;
+pub use product_repository_sqlite_impl::ProductRepositorySqliteImpl;
+pub use store_repository_sqlite_impl::StoreRepositorySqliteImpl;
+pub use transaction_repository_sqlite_impl::TransactionRepositorySqliteImpl;
@@ -0,0 +1,371 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use rusqlite::{Connection, Row, params};
+
+use expense_tracking::domain::{
+    entities::{Brand, NamePolicy},
+    repositories::{
+        BrandRepository, BrandRepositoryCreateError, BrandRepositoryDeleteError, BrandRepositoryRestoreError,
+        BrandRepositoryRetrieveAllError, BrandRepositoryUpdateError, DeleteBehaviour, Trashed,
+    },
+};
+
+use crate::infrastructures::data_sources::{
+    SqliteDatabase, datetime_from_row, format_datetime, holding_products, is_active, paged_stream, purge_transactions_holding,
+    restore_transactions, trash_transactions,
+};
+
+#[derive(Debug, Clone)]
+pub struct BrandRepositorySqliteImpl {
+    database: SqliteDatabase,
+    name_policy: NamePolicy,
+}
+
+impl BrandRepositorySqliteImpl {
+    pub fn new(database: SqliteDatabase) -> Self {
+        Self {
+            database,
+            name_policy: NamePolicy::default(),
+        }
+    }
+
+    pub fn with_name_policy(self, name_policy: NamePolicy) -> Self {
+        Self { name_policy, ..self }
+    }
+}
+
+fn brand_from_row(row: &Row) -> rusqlite::Result<Brand> {
+    Ok(Brand {
+        version: row.get(1)?,
+        ..Brand::new(row.get(0)?)
+    })
+}
+
+/// Finds the brand whose stored name designates the same brand as `name` under `name_policy`, through its name key.
+fn find_brand(connection: &Connection, name_policy: &NamePolicy, name: &str) -> rusqlite::Result<Option<(Brand, Option<DateTime<Utc>>)>> {
+    let mut statement = connection.prepare_cached("SELECT name, version, deleted_at FROM brands WHERE name_key = ?1 ORDER BY name")?;
+    let mut brands = statement.query_map(params![name_policy.normalize(name)], |row| {
+        let deleted_at: Option<String> = row.get(2)?;
+        let deleted_at: Option<DateTime<Utc>> = match deleted_at {
+            Some(_) => Some(datetime_from_row(row, 2)?),
+            None => None,
+        };
+
+        Ok((brand_from_row(row)?, deleted_at))
+    })?;
+
+    brands.next().transpose()
+}
+
+#[async_trait]
+impl BrandRepository for BrandRepositorySqliteImpl {
+    async fn create(&self, brand: &Brand) -> Result<Brand, BrandRepositoryCreateError> {
+        let name_policy: NamePolicy = self.name_policy;
+        let brand: Brand = brand.clone();
+
+        self.database
+            .run(move |connection| {
+                if find_brand(connection, &name_policy, &brand.name)?.is_some() {
+                    return Ok(Err(BrandRepositoryCreateError::BrandAlreadyExists));
+                }
+
+                connection.execute(
+                    "INSERT INTO brands (name, name_key, version) VALUES (?1, ?2, ?3)",
+                    params![brand.name, name_policy.normalize(&brand.name), brand.version],
+                )?;
+                Ok(Ok(brand))
+            })
+            .await
+            .map_err(|e| BrandRepositoryCreateError::UnableToSaveBrand(format!("{:?}", e)))?
+    }
+
+    async fn retrieve_all(&self) -> Result<Vec<Brand>, BrandRepositoryRetrieveAllError> {
+        self.database
+            .run(move |connection| {
+                let mut statement = connection.prepare("SELECT name, version FROM brands WHERE deleted_at IS NULL ORDER BY name")?;
+                statement.query_map([], brand_from_row)?.collect()
+            })
+            .await
+            .map_err(|e| BrandRepositoryRetrieveAllError::UnableToRetrieveBrands(format!("{:?}", e)))
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Brand, BrandRepositoryRetrieveAllError>> {
+        paged_stream(
+            self.database.clone(),
+            |connection, after, limit| {
+                let mut statement = connection.prepare_cached(
+                    "SELECT name, version FROM brands WHERE deleted_at IS NULL AND (?1 IS NULL OR name > ?1) ORDER BY name LIMIT ?2",
+                )?;
+                statement
+                    .query_map(params![after, limit], |row| Ok((row.get(0)?, brand_from_row(row)?)))?
+                    .collect()
+            },
+            |e| BrandRepositoryRetrieveAllError::UnableToRetrieveBrands(format!("{:?}", e)),
+        )
+    }
+
+    async fn update(&self, brand: &Brand) -> Result<Brand, BrandRepositoryUpdateError> {
+        let name_policy: NamePolicy = self.name_policy;
+        let brand: Brand = brand.clone();
+
+        self.database
+            .run(move |connection| {
+                let existing: Brand = match find_brand(connection, &name_policy, &brand.name)? {
+                    Some((existing, None)) => existing,
+                    _ => return Ok(Err(BrandRepositoryUpdateError::BrandNotFound)),
+                };
+
+                if existing.version != brand.version {
+                    return Ok(Err(BrandRepositoryUpdateError::VersionConflict {
+                        current_version: existing.version,
+                    }));
+                }
+
+                let updated: Brand = Brand {
+                    version: existing.version + 1,
+                    ..existing
+                };
+
+                connection.execute(
+                    "UPDATE brands SET version = ?2 WHERE name = ?1",
+                    params![updated.name, updated.version],
+                )?;
+                Ok(Ok(updated))
+            })
+            .await
+            .map_err(|e| BrandRepositoryUpdateError::UnableToUpdateBrand(format!("{:?}", e)))?
+    }
+
+    async fn delete(&self, brand: &Brand, behaviour: DeleteBehaviour<Brand>) -> Result<Brand, BrandRepositoryDeleteError> {
+        let name_policy: NamePolicy = self.name_policy;
+        let brand: Brand = brand.clone();
+
+        self.database
+            .run(move |connection| {
+                let transaction = connection.transaction()?;
+
+                let removed: Brand = match find_brand(&transaction, &name_policy, &brand.name)? {
+                    Some((existing, None)) => existing,
+                    _ => return Ok(Err(BrandRepositoryDeleteError::BrandNotFound)),
+                };
+                let deleted_at: String = format_datetime(&Utc::now());
+
+                match &behaviour {
+                    DeleteBehaviour::Restrict => {
+                        if is_active(&transaction, "products", "brand_name", &removed.name)? {
+                            return Ok(Err(BrandRepositoryDeleteError::BrandStillReferenced));
+                        }
+                    }
+                    DeleteBehaviour::Cascade => {
+                        trash_transactions(&transaction, &holding_products("brand_name"), &removed.name, &deleted_at)?;
+                        transaction.execute(
+                            "UPDATE products SET deleted_at = ?2 WHERE brand_name = ?1 AND deleted_at IS NULL",
+                            params![removed.name, deleted_at],
+                        )?;
+                    }
+                    DeleteBehaviour::Reassign(replacement) => {
+                        let replacement: Brand = match find_brand(&transaction, &name_policy, &replacement.name)? {
+                            Some((replacement, None)) if replacement.name != removed.name => replacement,
+                            _ => return Ok(Err(BrandRepositoryDeleteError::ReplacementBrandNotFound)),
+                        };
+
+                        transaction.execute(
+                            "UPDATE products SET brand_name = ?2 WHERE brand_name = ?1",
+                            params![removed.name, replacement.name],
+                        )?;
+                    }
+                }
+
+                transaction.execute(
+                    "UPDATE brands SET deleted_at = ?2 WHERE name = ?1",
+                    params![removed.name, deleted_at],
+                )?;
+                transaction.commit()?;
+                Ok(Ok(removed))
+            })
+            .await
+            .map_err(|e| BrandRepositoryDeleteError::UnableToDeleteBrand(format!("{:?}", e)))?
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Brand>>, BrandRepositoryRetrieveAllError> {
+        self.database
+            .run(move |connection| {
+                let mut statement =
+                    connection.prepare("SELECT name, version, deleted_at FROM brands WHERE deleted_at IS NOT NULL ORDER BY name")?;
+                statement
+                    .query_map([], |row| Ok(Trashed::new(brand_from_row(row)?, datetime_from_row(row, 2)?)))?
+                    .collect()
+            })
+            .await
+            .map_err(|e| BrandRepositoryRetrieveAllError::UnableToRetrieveBrands(format!("{:?}", e)))
+    }
+
+    async fn restore(&self, brand: &Brand) -> Result<Brand, BrandRepositoryRestoreError> {
+        let name_policy: NamePolicy = self.name_policy;
+        let brand: Brand = brand.clone();
+
+        self.database
+            .run(move |connection| {
+                let transaction = connection.transaction()?;
+
+                let (trashed, deleted_at): (Brand, DateTime<Utc>) = match find_brand(&transaction, &name_policy, &brand.name)? {
+                    Some((trashed, Some(deleted_at))) => (trashed, deleted_at),
+                    _ => return Ok(Err(BrandRepositoryRestoreError::BrandNotInTrash)),
+                };
+                let deleted_at: String = format_datetime(&deleted_at);
+
+                transaction.execute("UPDATE brands SET deleted_at = NULL WHERE name = ?1", params![trashed.name])?;
+                transaction.execute(
+                    "UPDATE products SET deleted_at = NULL WHERE brand_name = ?1 AND deleted_at = ?2 \
+                     AND category_id IN (SELECT id FROM categories WHERE deleted_at IS NULL)",
+                    params![trashed.name, deleted_at],
+                )?;
+                restore_transactions(&transaction, &holding_products("brand_name"), &trashed.name, &deleted_at)?;
+                transaction.commit()?;
+                Ok(Ok(trashed))
+            })
+            .await
+            .map_err(|e| BrandRepositoryRestoreError::UnableToRestoreBrand(format!("{:?}", e)))?
+    }
+
+    async fn purge_older_than(&self, cutoff: DateTime<Utc>) -> Result<Vec<Brand>, BrandRepositoryDeleteError> {
+        self.database
+            .run(move |connection| {
+                let transaction = connection.transaction()?;
+                let cutoff: String = format_datetime(&cutoff);
+
+                let purged: Vec<Brand> = transaction
+                    .prepare("SELECT name, version FROM brands WHERE deleted_at < ?1 ORDER BY name")?
+                    .query_map(params![cutoff], brand_from_row)?
+                    .collect::<rusqlite::Result<Vec<Brand>>>()?;
+
+                purge_transactions_holding(
+                    &transaction,
+                    "products.brand_name IN (SELECT name FROM brands WHERE deleted_at < ?1)",
+                    &cutoff,
+                )?;
+                transaction.execute("DELETE FROM brands WHERE deleted_at < ?1", params![cutoff])?;
+                transaction.commit()?;
+                Ok(purged)
+            })
+            .await
+            .map_err(|e| BrandRepositoryDeleteError::UnableToDeleteBrand(format!("{:?}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use futures::StreamExt;
+
+    use super::BrandRepositorySqliteImpl;
+    use crate::infrastructures::data_sources::SqliteDatabase;
+    use expense_tracking::domain::{
+        entities::Brand,
+        repositories::{
+            BrandRepository, BrandRepositoryCreateError, BrandRepositoryDeleteError, BrandRepositoryRestoreError,
+            BrandRepositoryRetrieveAllError, BrandRepositoryUpdateError, DeleteBehaviour,
+        },
+    };
+
+    async fn given_repository_with(brands: Vec<Brand>) -> BrandRepositorySqliteImpl {
+        let repository: BrandRepositorySqliteImpl = BrandRepositorySqliteImpl::new(SqliteDatabase::open_in_memory().unwrap());
+
+        for brand in brands {
+            repository.create(&brand).await.unwrap();
+        }
+
+        repository
+    }
+
+    #[tokio::test]
+    async fn create_should_persist_brand() {
+        let brand: Brand = Brand::new("Mora Radunz".to_owned());
+        let repository: BrandRepositorySqliteImpl = given_repository_with(vec![]).await;
+
+        let result: Result<Brand, BrandRepositoryCreateError> = repository.create(&brand).await;
+        let expected: Result<Brand, BrandRepositoryCreateError> = Ok(brand.clone());
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(repository.retrieve_all().await, Ok(vec![brand]));
+    }
+
+    #[tokio::test]
+    async fn create_existing_brand_should_fail() {
+        let brand: Brand = Brand::new("Mora Radunz".to_owned());
+        let repository: BrandRepositorySqliteImpl = given_repository_with(vec![brand.clone()]).await;
+
+        let result: Result<Brand, BrandRepositoryCreateError> = repository.create(&brand).await;
+        let expected: Result<Brand, BrandRepositoryCreateError> = Err(BrandRepositoryCreateError::BrandAlreadyExists);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn stream_all_should_yield_all_brands_in_name_order() {
+        let brands: Vec<Brand> = (0..250).map(|i| Brand::new(format!("Brand {:03}", i))).collect();
+        let repository: BrandRepositorySqliteImpl = given_repository_with(brands.clone()).await;
+
+        let result: Result<Vec<Brand>, BrandRepositoryRetrieveAllError> =
+            repository.stream_all().collect::<Vec<_>>().await.into_iter().collect();
+        let expected: Result<Vec<Brand>, BrandRepositoryRetrieveAllError> = Ok(brands);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn update_should_bump_version_and_reject_stale_versions() {
+        let brand: Brand = Brand::new("Mora Radunz".to_owned());
+        let repository: BrandRepositorySqliteImpl = given_repository_with(vec![brand.clone()]).await;
+
+        let result: Result<Brand, BrandRepositoryUpdateError> = repository.update(&brand).await;
+        let expected: Result<Brand, BrandRepositoryUpdateError> = Ok(Brand {
+            version: 1,
+            ..brand.clone()
+        });
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+
+        let result: Result<Brand, BrandRepositoryUpdateError> = repository.update(&brand).await;
+        let expected: Result<Brand, BrandRepositoryUpdateError> = Err(BrandRepositoryUpdateError::VersionConflict { current_version: 1 });
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn delete_restore_and_purge_should_move_brand_through_trash() {
+        let brand: Brand = Brand::new("Mora Radunz".to_owned());
+        let repository: BrandRepositorySqliteImpl = given_repository_with(vec![brand.clone()]).await;
+
+        assert_eq!(repository.delete(&brand, DeleteBehaviour::Restrict).await, Ok(brand.clone()));
+        assert_eq!(repository.retrieve_all().await, Ok(vec![]));
+        assert_eq!(
+            repository
+                .list_trashed()
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|t| t.entity)
+                .collect::<Vec<Brand>>(),
+            vec![brand.clone()]
+        );
+
+        assert_eq!(repository.restore(&brand).await, Ok(brand.clone()));
+        assert_eq!(repository.restore(&brand).await, Err(BrandRepositoryRestoreError::BrandNotInTrash));
+
+        assert_eq!(repository.delete(&brand, DeleteBehaviour::Restrict).await, Ok(brand.clone()));
+        assert_eq!(repository.purge_older_than(Utc::now() - Duration::days(1)).await, Ok(vec![]));
+        assert_eq!(repository.purge_older_than(Utc::now() + Duration::days(1)).await, Ok(vec![brand]));
+        assert_eq!(repository.list_trashed().await, Ok(vec![]));
+    }
+
+    #[tokio::test]
+    async fn delete_unknown_brand_should_fail() {
+        let repository: BrandRepositorySqliteImpl = given_repository_with(vec![]).await;
+
+        let result: Result<Brand, BrandRepositoryDeleteError> =
+            repository.delete(&Brand::new("Unknown".to_owned()), DeleteBehaviour::Cascade).await;
+        let expected: Result<Brand, BrandRepositoryDeleteError> = Err(BrandRepositoryDeleteError::BrandNotFound);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+}
@@ -0,0 +1,328 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use rusqlite::{Connection, OptionalExtension, Row, params};
+use uuid_b64::UuidB64;
+
+use expense_tracking::domain::{
+    entities::{Category, NamePolicy},
+    repositories::{CategoryRepository, CategoryRepositoryError, DeleteBehaviour, Trashed},
+};
+
+use crate::infrastructures::data_sources::{
+    SqliteDatabase, SqliteDatabaseError, datetime_from_row, deleted_at, format_datetime, holding_products, is_active, is_name_taken,
+    paged_stream, purge_transactions_holding, restore_transactions, trash_transactions, uuid_from_row,
+};
+
+#[derive(Debug, Clone)]
+pub struct CategoryRepositorySqliteImpl {
+    database: SqliteDatabase,
+    name_policy: NamePolicy,
+}
+
+impl CategoryRepositorySqliteImpl {
+    pub fn new(database: SqliteDatabase) -> Self {
+        Self {
+            database,
+            name_policy: NamePolicy::default(),
+        }
+    }
+
+    pub fn with_name_policy(self, name_policy: NamePolicy) -> Self {
+        Self { name_policy, ..self }
+    }
+}
+
+fn storage_error(error: SqliteDatabaseError) -> CategoryRepositoryError {
+    CategoryRepositoryError::UnableToAccessStorage(format!("{:?}", error))
+}
+
+fn category_from_row(row: &Row) -> rusqlite::Result<Category> {
+    Ok(Category {
+        version: row.get(2)?,
+        ..Category::new(Some(uuid_from_row(row, 0)?), row.get(1)?)
+    })
+}
+
+fn find_category(connection: &Connection, id: &str) -> rusqlite::Result<Option<(Category, bool)>> {
+    connection
+        .query_row(
+            "SELECT id, name, version, deleted_at IS NOT NULL FROM categories WHERE id = ?1",
+            params![id],
+            |row| Ok((category_from_row(row)?, row.get(3)?)),
+        )
+        .optional()
+}
+
+#[async_trait]
+impl CategoryRepository for CategoryRepositorySqliteImpl {
+    async fn create_or_update(&mut self, category: &Category) -> Result<Option<Category>, CategoryRepositoryError> {
+        let name_key: String = self.name_policy.normalize(&category.name);
+        let category: Category = category.clone();
+
+        self.database
+            .run(move |connection| {
+                let id: String = category.id.to_string();
+
+                if is_name_taken(connection, "categories", &id, &name_key)? {
+                    return Ok(Err(CategoryRepositoryError::CategoryAlreadyExists));
+                }
+
+                match find_category(connection, &id)? {
+                    Some((_, true)) => Ok(Err(CategoryRepositoryError::CategoryAlreadyExists)),
+                    Some((existing, false)) if existing.version != category.version => Ok(Err(CategoryRepositoryError::VersionConflict {
+                        current_version: existing.version,
+                    })),
+                    Some((existing, false)) => {
+                        connection.execute(
+                            "UPDATE categories SET name = ?2, name_key = ?3, version = ?4 WHERE id = ?1",
+                            params![id, category.name, name_key, existing.version + 1],
+                        )?;
+                        Ok(Ok(Some(existing)))
+                    }
+                    None => {
+                        connection.execute(
+                            "INSERT INTO categories (id, name, name_key, version) VALUES (?1, ?2, ?3, ?4)",
+                            params![id, category.name, name_key, category.version],
+                        )?;
+                        Ok(Ok(None))
+                    }
+                }
+            })
+            .await
+            .map_err(storage_error)?
+    }
+
+    async fn retrieve_all(&self) -> Result<Vec<Category>, CategoryRepositoryError> {
+        self.database
+            .run(move |connection| {
+                let mut statement = connection.prepare("SELECT id, name, version FROM categories WHERE deleted_at IS NULL ORDER BY id")?;
+                statement.query_map([], category_from_row)?.collect()
+            })
+            .await
+            .map_err(storage_error)
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Category, CategoryRepositoryError>> {
+        paged_stream(
+            self.database.clone(),
+            |connection, after, limit| {
+                let mut statement = connection.prepare_cached(
+                    "SELECT id, name, version FROM categories WHERE deleted_at IS NULL AND (?1 IS NULL OR id > ?1) ORDER BY id LIMIT ?2",
+                )?;
+                statement
+                    .query_map(params![after, limit], |row| Ok((row.get(0)?, category_from_row(row)?)))?
+                    .collect()
+            },
+            storage_error,
+        )
+    }
+
+    async fn delete(&mut self, id: &UuidB64, behaviour: DeleteBehaviour<Category>) -> Result<Category, CategoryRepositoryError> {
+        let id: String = id.to_string();
+
+        self.database
+            .run(move |connection| {
+                let transaction = connection.transaction()?;
+
+                let removed: Category = match find_category(&transaction, &id)? {
+                    Some((existing, false)) => existing,
+                    _ => return Ok(Err(CategoryRepositoryError::CategoryNotFound)),
+                };
+                let deleted_at: String = format_datetime(&Utc::now());
+
+                match &behaviour {
+                    DeleteBehaviour::Restrict => {
+                        if is_active(&transaction, "products", "category_id", &id)? {
+                            return Ok(Err(CategoryRepositoryError::CategoryStillReferenced));
+                        }
+                    }
+                    DeleteBehaviour::Cascade => {
+                        trash_transactions(&transaction, &holding_products("category_id"), &id, &deleted_at)?;
+                        transaction.execute(
+                            "UPDATE products SET deleted_at = ?2 WHERE category_id = ?1 AND deleted_at IS NULL",
+                            params![id, deleted_at],
+                        )?;
+                    }
+                    DeleteBehaviour::Reassign(replacement) => {
+                        let replacement_id: String = replacement.id.to_string();
+
+                        if replacement_id == id || !is_active(&transaction, "categories", "id", &replacement_id)? {
+                            return Ok(Err(CategoryRepositoryError::ReplacementCategoryNotFound));
+                        }
+
+                        transaction.execute(
+                            "UPDATE products SET category_id = ?2 WHERE category_id = ?1",
+                            params![id, replacement_id],
+                        )?;
+                    }
+                }
+
+                transaction.execute("UPDATE categories SET deleted_at = ?2 WHERE id = ?1", params![id, deleted_at])?;
+                transaction.commit()?;
+                Ok(Ok(removed))
+            })
+            .await
+            .map_err(storage_error)?
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Category>>, CategoryRepositoryError> {
+        self.database
+            .run(move |connection| {
+                let mut statement =
+                    connection.prepare("SELECT id, name, version, deleted_at FROM categories WHERE deleted_at IS NOT NULL ORDER BY id")?;
+                statement
+                    .query_map([], |row| Ok(Trashed::new(category_from_row(row)?, datetime_from_row(row, 3)?)))?
+                    .collect()
+            })
+            .await
+            .map_err(storage_error)
+    }
+
+    async fn restore(&mut self, id: &UuidB64) -> Result<Category, CategoryRepositoryError> {
+        let id: String = id.to_string();
+
+        self.database
+            .run(move |connection| {
+                let transaction = connection.transaction()?;
+
+                let trashed: Category = match find_category(&transaction, &id)? {
+                    Some((trashed, true)) => trashed,
+                    _ => return Ok(Err(CategoryRepositoryError::CategoryNotInTrash)),
+                };
+                let deleted_at: String = deleted_at(&transaction, "categories", "id", &id)?;
+
+                transaction.execute("UPDATE categories SET deleted_at = NULL WHERE id = ?1", params![id])?;
+                transaction.execute(
+                    "UPDATE products SET deleted_at = NULL WHERE category_id = ?1 AND deleted_at = ?2 \
+                     AND brand_name IN (SELECT name FROM brands WHERE deleted_at IS NULL)",
+                    params![id, deleted_at],
+                )?;
+                restore_transactions(&transaction, &holding_products("category_id"), &id, &deleted_at)?;
+                transaction.commit()?;
+                Ok(Ok(trashed))
+            })
+            .await
+            .map_err(storage_error)?
+    }
+
+    async fn purge_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<Vec<Category>, CategoryRepositoryError> {
+        self.database
+            .run(move |connection| {
+                let transaction = connection.transaction()?;
+                let cutoff: String = format_datetime(&cutoff);
+
+                let purged: Vec<Category> = transaction
+                    .prepare("SELECT id, name, version FROM categories WHERE deleted_at < ?1 ORDER BY id")?
+                    .query_map(params![cutoff], category_from_row)?
+                    .collect::<rusqlite::Result<Vec<Category>>>()?;
+
+                purge_transactions_holding(
+                    &transaction,
+                    "products.category_id IN (SELECT id FROM categories WHERE deleted_at < ?1)",
+                    &cutoff,
+                )?;
+                transaction.execute("DELETE FROM categories WHERE deleted_at < ?1", params![cutoff])?;
+                transaction.commit()?;
+                Ok(purged)
+            })
+            .await
+            .map_err(storage_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::CategoryRepositorySqliteImpl;
+    use crate::infrastructures::data_sources::SqliteDatabase;
+    use expense_tracking::domain::{
+        entities::Category,
+        repositories::{CategoryRepository, CategoryRepositoryError, DeleteBehaviour},
+    };
+
+    fn given_empty_repository() -> CategoryRepositorySqliteImpl {
+        CategoryRepositorySqliteImpl::new(SqliteDatabase::open_in_memory().unwrap())
+    }
+
+    #[tokio::test]
+    async fn create_or_update_should_insert_then_update_with_new_version() {
+        let category: Category = Category::new(None, "Dairy".to_owned());
+        let mut repository: CategoryRepositorySqliteImpl = given_empty_repository();
+
+        assert_eq!(repository.create_or_update(&category).await, Ok(None));
+
+        let renamed: Category = Category {
+            name: "Dairy Products".to_owned(),
+            ..category.clone()
+        };
+        let result: Result<Option<Category>, CategoryRepositoryError> = repository.create_or_update(&renamed).await;
+        let expected: Result<Option<Category>, CategoryRepositoryError> = Ok(Some(category.clone()));
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+
+        assert_eq!(
+            repository.retrieve_all().await,
+            Ok(vec![Category {
+                version: 1,
+                ..renamed.clone()
+            }])
+        );
+        assert_eq!(
+            repository.create_or_update(&renamed).await,
+            Err(CategoryRepositoryError::VersionConflict { current_version: 1 })
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_with_reassign_should_require_active_replacement() {
+        let category: Category = Category::new(None, "Dairy".to_owned());
+        let replacement: Category = Category::new(None, "Food".to_owned());
+        let mut repository: CategoryRepositorySqliteImpl = given_empty_repository();
+        repository.create_or_update(&category).await.unwrap();
+
+        let result: Result<Category, CategoryRepositoryError> = repository
+            .delete(&category.id, DeleteBehaviour::Reassign(replacement.clone()))
+            .await;
+        let expected: Result<Category, CategoryRepositoryError> = Err(CategoryRepositoryError::ReplacementCategoryNotFound);
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+
+        repository.create_or_update(&replacement).await.unwrap();
+
+        let result: Result<Category, CategoryRepositoryError> =
+            repository.delete(&category.id, DeleteBehaviour::Reassign(replacement)).await;
+        let expected: Result<Category, CategoryRepositoryError> = Ok(category);
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn delete_restore_and_purge_should_move_category_through_trash() {
+        let category: Category = Category::new(None, "Dairy".to_owned());
+        let mut repository: CategoryRepositorySqliteImpl = given_empty_repository();
+        repository.create_or_update(&category).await.unwrap();
+
+        assert_eq!(
+            repository.delete(&category.id, DeleteBehaviour::Restrict).await,
+            Ok(category.clone())
+        );
+        assert_eq!(
+            repository.create_or_update(&category).await,
+            Err(CategoryRepositoryError::CategoryAlreadyExists)
+        );
+        assert_eq!(repository.restore(&category.id).await, Ok(category.clone()));
+        assert_eq!(
+            repository.restore(&category.id).await,
+            Err(CategoryRepositoryError::CategoryNotInTrash)
+        );
+
+        assert_eq!(
+            repository.delete(&category.id, DeleteBehaviour::Restrict).await,
+            Ok(category.clone())
+        );
+        assert_eq!(
+            repository.purge_older_than(Utc::now() + Duration::days(1)).await,
+            Ok(vec![category])
+        );
+        assert_eq!(repository.list_trashed().await, Ok(vec![]));
+    }
+}
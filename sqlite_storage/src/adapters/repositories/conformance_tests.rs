@@ -0,0 +1,51 @@
+use repository_conformance::{repository_conformance_suite, suites::RepositoryBackend};
+
+use crate::{
+    adapters::repositories::{
+        BrandRepositorySqliteImpl, CategoryRepositorySqliteImpl, ProductRepositorySqliteImpl, StoreRepositorySqliteImpl,
+        TransactionRepositorySqliteImpl,
+    },
+    infrastructures::data_sources::SqliteDatabase,
+};
+
+struct SqliteBackend {
+    database: SqliteDatabase,
+}
+
+impl SqliteBackend {
+    fn new() -> Self {
+        Self {
+            database: SqliteDatabase::open_in_memory().unwrap(),
+        }
+    }
+}
+
+impl RepositoryBackend for SqliteBackend {
+    type Brands = BrandRepositorySqliteImpl;
+    type Categories = CategoryRepositorySqliteImpl;
+    type Stores = StoreRepositorySqliteImpl;
+    type Products = ProductRepositorySqliteImpl;
+    type Transactions = TransactionRepositorySqliteImpl;
+
+    fn brands(&self) -> Self::Brands {
+        BrandRepositorySqliteImpl::new(self.database.clone())
+    }
+
+    fn categories(&self) -> Self::Categories {
+        CategoryRepositorySqliteImpl::new(self.database.clone())
+    }
+
+    fn stores(&self) -> Self::Stores {
+        StoreRepositorySqliteImpl::new(self.database.clone())
+    }
+
+    fn products(&self) -> Self::Products {
+        ProductRepositorySqliteImpl::new(self.database.clone())
+    }
+
+    fn transactions(&self) -> Self::Transactions {
+        TransactionRepositorySqliteImpl::new(self.database.clone())
+    }
+}
+
+repository_conformance_suite!(SqliteBackend::new());
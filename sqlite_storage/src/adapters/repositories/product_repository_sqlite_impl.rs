@@ -0,0 +1,374 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use rusqlite::{Connection, OptionalExtension, params};
+use uuid_b64::UuidB64;
+
+use expense_tracking::domain::{
+    entities::{NamePolicy, Product},
+    repositories::{DeleteBehaviour, ProductRepository, ProductRepositoryError, Trashed},
+};
+
+use crate::infrastructures::data_sources::{
+    PRODUCT_COLUMNS, PRODUCT_JOINS, SqliteDatabase, SqliteDatabaseError, datetime_from_row, deleted_at, format_datetime, holding_products,
+    is_active, paged_stream, product_from_row, purge_transactions_holding, restore_transactions, trash_transactions,
+};
+
+#[derive(Debug, Clone)]
+pub struct ProductRepositorySqliteImpl {
+    database: SqliteDatabase,
+    name_policy: NamePolicy,
+}
+
+impl ProductRepositorySqliteImpl {
+    pub fn new(database: SqliteDatabase) -> Self {
+        Self {
+            database,
+            name_policy: NamePolicy::default(),
+        }
+    }
+
+    pub fn with_name_policy(self, name_policy: NamePolicy) -> Self {
+        Self { name_policy, ..self }
+    }
+}
+
+fn storage_error(error: SqliteDatabaseError) -> ProductRepositoryError {
+    ProductRepositoryError::UnableToAccessStorage(format!("{:?}", error))
+}
+
+fn find_product(connection: &Connection, id: &str) -> rusqlite::Result<Option<(Product, bool)>> {
+    connection
+        .query_row(
+            &format!(
+                "SELECT {}, products.deleted_at IS NOT NULL FROM products {} WHERE products.id = ?1",
+                PRODUCT_COLUMNS, PRODUCT_JOINS
+            ),
+            params![id],
+            |row| Ok((product_from_row(row, 0)?, row.get(8)?)),
+        )
+        .optional()
+}
+
+fn check_references(connection: &Connection, product: &Product) -> rusqlite::Result<Result<(), ProductRepositoryError>> {
+    if !is_active(connection, "brands", "name", &product.brand.name)? {
+        return Ok(Err(ProductRepositoryError::BrandNotFound));
+    }
+
+    if !is_active(connection, "categories", "id", &product.category.id.to_string())? {
+        return Ok(Err(ProductRepositoryError::CategoryNotFound));
+    }
+
+    Ok(Ok(()))
+}
+
+/// Whether a product of `brand_name` other than `id`, even a trashed one, already holds `name_key`.
+fn is_product_name_taken(connection: &Connection, id: &str, brand_name: &str, name_key: &str) -> rusqlite::Result<bool> {
+    connection.query_row(
+        "SELECT EXISTS (SELECT 1 FROM products WHERE brand_name = ?2 AND name_key = ?3 AND id != ?1)",
+        params![id, brand_name, name_key],
+        |row| row.get(0),
+    )
+}
+
+#[async_trait]
+impl ProductRepository for ProductRepositorySqliteImpl {
+    async fn create_or_update(&mut self, product: &Product) -> Result<Option<Product>, ProductRepositoryError> {
+        let name_key: String = self.name_policy.normalize(&product.name);
+        let product: Product = product.clone();
+
+        self.database
+            .run(move |connection| {
+                let id: String = product.id.to_string();
+
+                if let Err(e) = check_references(connection, &product)? {
+                    return Ok(Err(e));
+                }
+
+                if is_product_name_taken(connection, &id, &product.brand.name, &name_key)? {
+                    return Ok(Err(ProductRepositoryError::ProductAlreadyExists));
+                }
+
+                match find_product(connection, &id)? {
+                    Some((_, true)) => Ok(Err(ProductRepositoryError::ProductAlreadyExists)),
+                    Some((existing, false)) if existing.version != product.version => Ok(Err(ProductRepositoryError::VersionConflict {
+                        current_version: existing.version,
+                    })),
+                    Some((existing, false)) => {
+                        connection.execute(
+                            "UPDATE products SET name = ?2, name_key = ?3, brand_name = ?4, category_id = ?5, version = ?6 WHERE id = ?1",
+                            params![
+                                id,
+                                product.name,
+                                name_key,
+                                product.brand.name,
+                                product.category.id.to_string(),
+                                existing.version + 1
+                            ],
+                        )?;
+                        Ok(Ok(Some(existing)))
+                    }
+                    None => {
+                        connection.execute(
+                            "INSERT INTO products (id, name, name_key, brand_name, category_id, version) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                            params![
+                                id,
+                                product.name,
+                                name_key,
+                                product.brand.name,
+                                product.category.id.to_string(),
+                                product.version
+                            ],
+                        )?;
+                        Ok(Ok(None))
+                    }
+                }
+            })
+            .await
+            .map_err(storage_error)?
+    }
+
+    async fn retrieve_all(&self) -> Result<Vec<Product>, ProductRepositoryError> {
+        self.database
+            .run(move |connection| {
+                let mut statement = connection.prepare(&format!(
+                    "SELECT {} FROM products {} WHERE products.deleted_at IS NULL ORDER BY products.id",
+                    PRODUCT_COLUMNS, PRODUCT_JOINS
+                ))?;
+                statement.query_map([], |row| product_from_row(row, 0))?.collect()
+            })
+            .await
+            .map_err(storage_error)
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Product, ProductRepositoryError>> {
+        paged_stream(
+            self.database.clone(),
+            |connection, after, limit| {
+                let mut statement = connection.prepare_cached(&format!(
+                    "SELECT {} FROM products {} WHERE products.deleted_at IS NULL AND (?1 IS NULL OR products.id > ?1) \
+                     ORDER BY products.id LIMIT ?2",
+                    PRODUCT_COLUMNS, PRODUCT_JOINS
+                ))?;
+                statement
+                    .query_map(params![after, limit], |row| Ok((row.get(0)?, product_from_row(row, 0)?)))?
+                    .collect()
+            },
+            storage_error,
+        )
+    }
+
+    async fn delete(&mut self, id: &UuidB64, behaviour: DeleteBehaviour<Product>) -> Result<Product, ProductRepositoryError> {
+        let id: String = id.to_string();
+
+        self.database
+            .run(move |connection| {
+                let transaction = connection.transaction()?;
+
+                let removed: Product = match find_product(&transaction, &id)? {
+                    Some((existing, false)) => existing,
+                    _ => return Ok(Err(ProductRepositoryError::ProductNotFound)),
+                };
+                let deleted_at: String = format_datetime(&Utc::now());
+
+                match &behaviour {
+                    DeleteBehaviour::Restrict => {
+                        let referenced: bool = transaction.query_row(
+                            "SELECT EXISTS (SELECT 1 FROM items JOIN transactions ON transactions.id = items.transaction_id \
+                             WHERE items.product_id = ?1 AND transactions.deleted_at IS NULL)",
+                            params![id],
+                            |row| row.get(0),
+                        )?;
+
+                        if referenced {
+                            return Ok(Err(ProductRepositoryError::ProductStillReferenced));
+                        }
+                    }
+                    DeleteBehaviour::Cascade => {
+                        trash_transactions(&transaction, &holding_products("id"), &id, &deleted_at)?;
+                    }
+                    DeleteBehaviour::Reassign(replacement) => {
+                        let replacement_id: String = replacement.id.to_string();
+
+                        if replacement_id == id || !is_active(&transaction, "products", "id", &replacement_id)? {
+                            return Ok(Err(ProductRepositoryError::ReplacementProductNotFound));
+                        }
+
+                        transaction.execute(
+                            "UPDATE items SET product_id = ?2 WHERE product_id = ?1",
+                            params![id, replacement_id],
+                        )?;
+                    }
+                }
+
+                transaction.execute("UPDATE products SET deleted_at = ?2 WHERE id = ?1", params![id, deleted_at])?;
+                transaction.commit()?;
+                Ok(Ok(removed))
+            })
+            .await
+            .map_err(storage_error)?
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Product>>, ProductRepositoryError> {
+        self.database
+            .run(move |connection| {
+                let mut statement = connection.prepare(&format!(
+                    "SELECT {}, products.deleted_at FROM products {} WHERE products.deleted_at IS NOT NULL ORDER BY products.id",
+                    PRODUCT_COLUMNS, PRODUCT_JOINS
+                ))?;
+                statement
+                    .query_map([], |row| Ok(Trashed::new(product_from_row(row, 0)?, datetime_from_row(row, 8)?)))?
+                    .collect()
+            })
+            .await
+            .map_err(storage_error)
+    }
+
+    async fn restore(&mut self, id: &UuidB64) -> Result<Product, ProductRepositoryError> {
+        let id: String = id.to_string();
+
+        self.database
+            .run(move |connection| {
+                let transaction = connection.transaction()?;
+
+                let trashed: Product = match find_product(&transaction, &id)? {
+                    Some((trashed, true)) => trashed,
+                    _ => return Ok(Err(ProductRepositoryError::ProductNotInTrash)),
+                };
+
+                if let Err(e) = check_references(&transaction, &trashed)? {
+                    return Ok(Err(e));
+                }
+
+                let deleted_at: String = deleted_at(&transaction, "products", "id", &id)?;
+
+                transaction.execute("UPDATE products SET deleted_at = NULL WHERE id = ?1", params![id])?;
+                restore_transactions(&transaction, &holding_products("id"), &id, &deleted_at)?;
+                transaction.commit()?;
+                Ok(Ok(trashed))
+            })
+            .await
+            .map_err(storage_error)?
+    }
+
+    async fn purge_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<Vec<Product>, ProductRepositoryError> {
+        self.database
+            .run(move |connection| {
+                let transaction = connection.transaction()?;
+                let cutoff: String = format_datetime(&cutoff);
+
+                let purged: Vec<Product> = transaction
+                    .prepare(&format!(
+                        "SELECT {} FROM products {} WHERE products.deleted_at < ?1 ORDER BY products.id",
+                        PRODUCT_COLUMNS, PRODUCT_JOINS
+                    ))?
+                    .query_map(params![cutoff], |row| product_from_row(row, 0))?
+                    .collect::<rusqlite::Result<Vec<Product>>>()?;
+
+                purge_transactions_holding(&transaction, "products.deleted_at < ?1", &cutoff)?;
+                transaction.execute("DELETE FROM products WHERE deleted_at < ?1", params![cutoff])?;
+                transaction.commit()?;
+                Ok(purged)
+            })
+            .await
+            .map_err(storage_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProductRepositorySqliteImpl;
+    use crate::{
+        adapters::repositories::{BrandRepositorySqliteImpl, CategoryRepositorySqliteImpl},
+        infrastructures::data_sources::SqliteDatabase,
+    };
+    use expense_tracking::domain::{
+        entities::{Brand, Category, Product},
+        repositories::{BrandRepository, CategoryRepository, DeleteBehaviour, ProductRepository, ProductRepositoryError},
+    };
+
+    async fn given_repository_with_references(brand: &Brand, category: &Category) -> (ProductRepositorySqliteImpl, SqliteDatabase) {
+        let database: SqliteDatabase = SqliteDatabase::open_in_memory().unwrap();
+
+        BrandRepositorySqliteImpl::new(database.clone()).create(brand).await.unwrap();
+        CategoryRepositorySqliteImpl::new(database.clone())
+            .create_or_update(category)
+            .await
+            .unwrap();
+
+        (ProductRepositorySqliteImpl::new(database.clone()), database)
+    }
+
+    #[tokio::test]
+    async fn create_or_update_should_persist_product_with_references() {
+        let brand: Brand = Brand::new("Nestlé".to_owned());
+        let category: Category = Category::new(None, "Dairy".to_owned());
+        let product: Product = Product::new(None, "Chocolate Milk 1L".to_owned(), brand.clone(), category.clone());
+        let (mut repository, _) = given_repository_with_references(&brand, &category).await;
+
+        assert_eq!(repository.create_or_update(&product).await, Ok(None));
+
+        let result: Result<Vec<Product>, ProductRepositoryError> = repository.retrieve_all().await;
+        let expected: Result<Vec<Product>, ProductRepositoryError> = Ok(vec![product]);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    macro_rules! create_or_update_with_missing_reference {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[tokio::test]
+            async fn $name() {
+                let (product, error): (Product, ProductRepositoryError) = $value;
+                let expected: Result<Option<Product>, ProductRepositoryError> = Err(error);
+                let (mut repository, _) =
+                    given_repository_with_references(&Brand::new("Nestlé".to_owned()), &Category::new(None, "Dairy".to_owned())).await;
+
+                let result: Result<Option<Product>, ProductRepositoryError> = repository.create_or_update(&product).await;
+
+                assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+            }
+        )*
+        }
+    }
+
+    create_or_update_with_missing_reference! {
+        create_or_update_with_unknown_brand_should_fail: (
+            Product::new(None, "Milk".to_owned(), Brand::new("Unknown".to_owned()), Category::new(None, "Dairy".to_owned())),
+            ProductRepositoryError::BrandNotFound
+        ),
+        create_or_update_with_unknown_category_should_fail: (
+            Product::new(None, "Milk".to_owned(), Brand::new("Nestlé".to_owned()), Category::new(None, "Unknown".to_owned())),
+            ProductRepositoryError::CategoryNotFound
+        ),
+    }
+
+    #[tokio::test]
+    async fn deleting_brand_with_cascade_should_trash_its_products_until_the_brand_is_restored() {
+        let brand: Brand = Brand::new("Nestlé".to_owned());
+        let category: Category = Category::new(None, "Dairy".to_owned());
+        let product: Product = Product::new(None, "Chocolate Milk 1L".to_owned(), brand.clone(), category.clone());
+        let (mut repository, database) = given_repository_with_references(&brand, &category).await;
+        repository.create_or_update(&product).await.unwrap();
+
+        let brands: BrandRepositorySqliteImpl = BrandRepositorySqliteImpl::new(database);
+        brands.delete(&brand, DeleteBehaviour::Cascade).await.unwrap();
+
+        assert_eq!(repository.retrieve_all().await, Ok(vec![]));
+        assert_eq!(
+            repository
+                .list_trashed()
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|t| t.entity)
+                .collect::<Vec<Product>>(),
+            vec![product.clone()]
+        );
+        assert_eq!(repository.restore(&product.id).await, Err(ProductRepositoryError::BrandNotFound));
+
+        brands.restore(&brand).await.unwrap();
+
+        assert_eq!(repository.retrieve_all().await, Ok(vec![product]));
+    }
+}
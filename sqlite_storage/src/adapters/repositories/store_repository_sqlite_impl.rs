@@ -0,0 +1,300 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use rusqlite::{Connection, OptionalExtension, Row, params};
+use uuid_b64::UuidB64;
+
+use expense_tracking::domain::{
+    entities::{NamePolicy, Store},
+    repositories::{DeleteBehaviour, StoreRepository, StoreRepositoryError, Trashed},
+};
+
+use crate::infrastructures::data_sources::{
+    SqliteDatabase, SqliteDatabaseError, datetime_from_row, deleted_at, format_datetime, is_active, is_name_taken, paged_stream,
+    restore_transactions, trash_transactions, uuid_from_row,
+};
+
+#[derive(Debug, Clone)]
+pub struct StoreRepositorySqliteImpl {
+    database: SqliteDatabase,
+    name_policy: NamePolicy,
+}
+
+impl StoreRepositorySqliteImpl {
+    pub fn new(database: SqliteDatabase) -> Self {
+        Self {
+            database,
+            name_policy: NamePolicy::default(),
+        }
+    }
+
+    pub fn with_name_policy(self, name_policy: NamePolicy) -> Self {
+        Self { name_policy, ..self }
+    }
+}
+
+fn storage_error(error: SqliteDatabaseError) -> StoreRepositoryError {
+    StoreRepositoryError::UnableToAccessStorage(format!("{:?}", error))
+}
+
+fn store_from_row(row: &Row) -> rusqlite::Result<Store> {
+    Ok(Store {
+        version: row.get(2)?,
+        ..Store::new(Some(uuid_from_row(row, 0)?), row.get(1)?)
+    })
+}
+
+fn find_store(connection: &Connection, id: &str) -> rusqlite::Result<Option<(Store, bool)>> {
+    connection
+        .query_row(
+            "SELECT id, name, version, deleted_at IS NOT NULL FROM stores WHERE id = ?1",
+            params![id],
+            |row| Ok((store_from_row(row)?, row.get(3)?)),
+        )
+        .optional()
+}
+
+#[async_trait]
+impl StoreRepository for StoreRepositorySqliteImpl {
+    async fn create_or_update(&mut self, store: &Store) -> Result<Option<Store>, StoreRepositoryError> {
+        let name_key: String = self.name_policy.normalize(&store.name);
+        let store: Store = store.clone();
+
+        self.database
+            .run(move |connection| {
+                let id: String = store.id.to_string();
+
+                if is_name_taken(connection, "stores", &id, &name_key)? {
+                    return Ok(Err(StoreRepositoryError::StoreAlreadyExists));
+                }
+
+                match find_store(connection, &id)? {
+                    Some((_, true)) => Ok(Err(StoreRepositoryError::StoreAlreadyExists)),
+                    Some((existing, false)) if existing.version != store.version => Ok(Err(StoreRepositoryError::VersionConflict {
+                        current_version: existing.version,
+                    })),
+                    Some((existing, false)) => {
+                        connection.execute(
+                            "UPDATE stores SET name = ?2, name_key = ?3, version = ?4 WHERE id = ?1",
+                            params![id, store.name, name_key, existing.version + 1],
+                        )?;
+                        Ok(Ok(Some(existing)))
+                    }
+                    None => {
+                        connection.execute(
+                            "INSERT INTO stores (id, name, name_key, version) VALUES (?1, ?2, ?3, ?4)",
+                            params![id, store.name, name_key, store.version],
+                        )?;
+                        Ok(Ok(None))
+                    }
+                }
+            })
+            .await
+            .map_err(storage_error)?
+    }
+
+    async fn retrieve_all(&self) -> Result<Vec<Store>, StoreRepositoryError> {
+        self.database
+            .run(move |connection| {
+                let mut statement = connection.prepare("SELECT id, name, version FROM stores WHERE deleted_at IS NULL ORDER BY id")?;
+                statement.query_map([], store_from_row)?.collect()
+            })
+            .await
+            .map_err(storage_error)
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Store, StoreRepositoryError>> {
+        paged_stream(
+            self.database.clone(),
+            |connection, after, limit| {
+                let mut statement = connection.prepare_cached(
+                    "SELECT id, name, version FROM stores WHERE deleted_at IS NULL AND (?1 IS NULL OR id > ?1) ORDER BY id LIMIT ?2",
+                )?;
+                statement
+                    .query_map(params![after, limit], |row| Ok((row.get(0)?, store_from_row(row)?)))?
+                    .collect()
+            },
+            storage_error,
+        )
+    }
+
+    async fn delete(&mut self, id: &UuidB64, behaviour: DeleteBehaviour<Store>) -> Result<Store, StoreRepositoryError> {
+        let id: String = id.to_string();
+
+        self.database
+            .run(move |connection| {
+                let transaction = connection.transaction()?;
+
+                let removed: Store = match find_store(&transaction, &id)? {
+                    Some((existing, false)) => existing,
+                    _ => return Ok(Err(StoreRepositoryError::StoreNotFound)),
+                };
+                let deleted_at: String = format_datetime(&Utc::now());
+
+                match &behaviour {
+                    DeleteBehaviour::Restrict => {
+                        if is_active(&transaction, "transactions", "store_id", &id)? {
+                            return Ok(Err(StoreRepositoryError::StoreStillReferenced));
+                        }
+                    }
+                    DeleteBehaviour::Cascade => {
+                        trash_transactions(&transaction, "store_id = ?1", &id, &deleted_at)?;
+                    }
+                    DeleteBehaviour::Reassign(replacement) => {
+                        let replacement_id: String = replacement.id.to_string();
+
+                        if replacement_id == id || !is_active(&transaction, "stores", "id", &replacement_id)? {
+                            return Ok(Err(StoreRepositoryError::ReplacementStoreNotFound));
+                        }
+
+                        transaction.execute(
+                            "UPDATE transactions SET store_id = ?2 WHERE store_id = ?1",
+                            params![id, replacement_id],
+                        )?;
+                    }
+                }
+
+                transaction.execute("UPDATE stores SET deleted_at = ?2 WHERE id = ?1", params![id, deleted_at])?;
+                transaction.commit()?;
+                Ok(Ok(removed))
+            })
+            .await
+            .map_err(storage_error)?
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Store>>, StoreRepositoryError> {
+        self.database
+            .run(move |connection| {
+                let mut statement =
+                    connection.prepare("SELECT id, name, version, deleted_at FROM stores WHERE deleted_at IS NOT NULL ORDER BY id")?;
+                statement
+                    .query_map([], |row| Ok(Trashed::new(store_from_row(row)?, datetime_from_row(row, 3)?)))?
+                    .collect()
+            })
+            .await
+            .map_err(storage_error)
+    }
+
+    async fn restore(&mut self, id: &UuidB64) -> Result<Store, StoreRepositoryError> {
+        let id: String = id.to_string();
+
+        self.database
+            .run(move |connection| {
+                let transaction = connection.transaction()?;
+
+                let trashed: Store = match find_store(&transaction, &id)? {
+                    Some((trashed, true)) => trashed,
+                    _ => return Ok(Err(StoreRepositoryError::StoreNotInTrash)),
+                };
+                let deleted_at: String = deleted_at(&transaction, "stores", "id", &id)?;
+
+                transaction.execute("UPDATE stores SET deleted_at = NULL WHERE id = ?1", params![id])?;
+                restore_transactions(&transaction, "store_id = ?1", &id, &deleted_at)?;
+                transaction.commit()?;
+                Ok(Ok(trashed))
+            })
+            .await
+            .map_err(storage_error)?
+    }
+
+    async fn purge_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<Vec<Store>, StoreRepositoryError> {
+        self.database
+            .run(move |connection| {
+                let transaction = connection.transaction()?;
+                let cutoff: String = format_datetime(&cutoff);
+
+                let purged: Vec<Store> = transaction
+                    .prepare("SELECT id, name, version FROM stores WHERE deleted_at < ?1 ORDER BY id")?
+                    .query_map(params![cutoff], store_from_row)?
+                    .collect::<rusqlite::Result<Vec<Store>>>()?;
+
+                transaction.execute("DELETE FROM stores WHERE deleted_at < ?1", params![cutoff])?;
+                transaction.commit()?;
+                Ok(purged)
+            })
+            .await
+            .map_err(storage_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::StoreRepositorySqliteImpl;
+    use crate::infrastructures::data_sources::SqliteDatabase;
+    use expense_tracking::domain::{
+        entities::Store,
+        repositories::{DeleteBehaviour, StoreRepository, StoreRepositoryError},
+    };
+
+    fn given_empty_repository() -> StoreRepositorySqliteImpl {
+        StoreRepositorySqliteImpl::new(SqliteDatabase::open_in_memory().unwrap())
+    }
+
+    #[tokio::test]
+    async fn create_or_update_should_insert_then_update_with_new_version() {
+        let store: Store = Store::new(None, "Migros".to_owned());
+        let mut repository: StoreRepositorySqliteImpl = given_empty_repository();
+
+        assert_eq!(repository.create_or_update(&store).await, Ok(None));
+
+        let renamed: Store = Store {
+            name: "Migros City".to_owned(),
+            ..store.clone()
+        };
+        let result: Result<Option<Store>, StoreRepositoryError> = repository.create_or_update(&renamed).await;
+        let expected: Result<Option<Store>, StoreRepositoryError> = Ok(Some(store.clone()));
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+
+        assert_eq!(
+            repository.retrieve_all().await,
+            Ok(vec![Store {
+                version: 1,
+                ..renamed.clone()
+            }])
+        );
+        assert_eq!(
+            repository.create_or_update(&renamed).await,
+            Err(StoreRepositoryError::VersionConflict { current_version: 1 })
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_with_reassign_should_require_active_replacement() {
+        let store: Store = Store::new(None, "Migros".to_owned());
+        let replacement: Store = Store::new(None, "Coop".to_owned());
+        let mut repository: StoreRepositorySqliteImpl = given_empty_repository();
+        repository.create_or_update(&store).await.unwrap();
+
+        let result: Result<Store, StoreRepositoryError> =
+            repository.delete(&store.id, DeleteBehaviour::Reassign(replacement.clone())).await;
+        let expected: Result<Store, StoreRepositoryError> = Err(StoreRepositoryError::ReplacementStoreNotFound);
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+
+        repository.create_or_update(&replacement).await.unwrap();
+
+        let result: Result<Store, StoreRepositoryError> = repository.delete(&store.id, DeleteBehaviour::Reassign(replacement)).await;
+        let expected: Result<Store, StoreRepositoryError> = Ok(store);
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn delete_restore_and_purge_should_move_store_through_trash() {
+        let store: Store = Store::new(None, "Migros".to_owned());
+        let mut repository: StoreRepositorySqliteImpl = given_empty_repository();
+        repository.create_or_update(&store).await.unwrap();
+
+        assert_eq!(repository.delete(&store.id, DeleteBehaviour::Restrict).await, Ok(store.clone()));
+        assert_eq!(
+            repository.create_or_update(&store).await,
+            Err(StoreRepositoryError::StoreAlreadyExists)
+        );
+        assert_eq!(repository.restore(&store.id).await, Ok(store.clone()));
+        assert_eq!(repository.restore(&store.id).await, Err(StoreRepositoryError::StoreNotInTrash));
+
+        assert_eq!(repository.delete(&store.id, DeleteBehaviour::Restrict).await, Ok(store.clone()));
+        assert_eq!(repository.purge_older_than(Utc::now() + Duration::days(1)).await, Ok(vec![store]));
+        assert_eq!(repository.list_trashed().await, Ok(vec![]));
+    }
+}
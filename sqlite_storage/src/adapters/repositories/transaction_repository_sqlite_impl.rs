@@ -0,0 +1,464 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use rusqlite::{Connection, OptionalExtension, Row, params};
+use uuid_b64::UuidB64;
+
+use expense_tracking::domain::{
+    entities::{Store, Transaction},
+    repositories::{TransactionRepository, TransactionRepositoryError, Trashed},
+};
+
+use crate::infrastructures::data_sources::{
+    SqliteDatabase, SqliteDatabaseError, datetime_from_row, format_datetime, is_active, load_items, paged_stream, unit_to_columns,
+    uuid_from_row,
+};
+
+const TRANSACTION_COLUMNS: &str = "transactions.id, transactions.datetime, transactions.version, stores.id, stores.name, stores.version";
+
+const TRANSACTION_JOINS: &str = "JOIN stores ON stores.id = transactions.store_id";
+
+#[derive(Debug, Clone)]
+pub struct TransactionRepositorySqliteImpl {
+    database: SqliteDatabase,
+}
+
+impl TransactionRepositorySqliteImpl {
+    pub fn new(database: SqliteDatabase) -> Self {
+        Self { database }
+    }
+}
+
+fn storage_error(error: SqliteDatabaseError) -> TransactionRepositoryError {
+    TransactionRepositoryError::UnableToAccessStorage(format!("{:?}", error))
+}
+
+fn transaction_from_row(row: &Row) -> rusqlite::Result<Transaction> {
+    let store: Store = Store {
+        version: row.get(5)?,
+        ..Store::new(Some(uuid_from_row(row, 3)?), row.get(4)?)
+    };
+
+    Ok(Transaction {
+        version: row.get(2)?,
+        ..Transaction::new(Some(uuid_from_row(row, 0)?), vec![], store, datetime_from_row(row, 1)?)
+    })
+}
+
+fn with_items(connection: &Connection, transactions: Vec<Transaction>) -> rusqlite::Result<Vec<Transaction>> {
+    transactions
+        .into_iter()
+        .map(|transaction| {
+            Ok(Transaction {
+                items: load_items(connection, &transaction.id.to_string())?,
+                ..transaction
+            })
+        })
+        .collect()
+}
+
+fn find_transaction(connection: &Connection, id: &str) -> rusqlite::Result<Option<(Transaction, bool)>> {
+    let found: Option<(Transaction, bool)> = connection
+        .query_row(
+            &format!(
+                "SELECT {}, transactions.deleted_at IS NOT NULL FROM transactions {} WHERE transactions.id = ?1",
+                TRANSACTION_COLUMNS, TRANSACTION_JOINS
+            ),
+            params![id],
+            |row| Ok((transaction_from_row(row)?, row.get(6)?)),
+        )
+        .optional()?;
+
+    match found {
+        Some((transaction, trashed)) => Ok(Some((with_items(connection, vec![transaction])?.remove(0), trashed))),
+        None => Ok(None),
+    }
+}
+
+fn check_references(connection: &Connection, transaction: &Transaction) -> rusqlite::Result<Result<(), TransactionRepositoryError>> {
+    if !is_active(connection, "stores", "id", &transaction.store.id.to_string())? {
+        return Ok(Err(TransactionRepositoryError::StoreNotFound));
+    }
+
+    for item in &transaction.items {
+        if !is_active(connection, "products", "id", &item.product().id.to_string())? {
+            return Ok(Err(TransactionRepositoryError::ProductNotFound));
+        }
+    }
+
+    Ok(Ok(()))
+}
+
+fn replace_items(connection: &Connection, transaction: &Transaction) -> rusqlite::Result<()> {
+    let id: String = transaction.id.to_string();
+
+    connection.execute("DELETE FROM items WHERE transaction_id = ?1", params![id])?;
+
+    let mut statement = connection.prepare_cached(
+        "INSERT INTO items (id, transaction_id, position, product_id, unit, amount, unitary_price) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+    )?;
+
+    for (position, item) in transaction.items.iter().enumerate() {
+        let (unit, amount): (&str, Option<f64>) = unit_to_columns(item.unit());
+
+        statement.execute(params![
+            item.id().to_string(),
+            id,
+            position,
+            item.product().id.to_string(),
+            unit,
+            amount,
+            item.unitary_price()
+        ])?;
+    }
+
+    Ok(())
+}
+
+fn select_transactions(connection: &Connection, condition: &str, parameters: impl rusqlite::Params) -> rusqlite::Result<Vec<Transaction>> {
+    let transactions: Vec<Transaction> = connection
+        .prepare_cached(&format!(
+            "SELECT {} FROM transactions {} WHERE {} ORDER BY transactions.id",
+            TRANSACTION_COLUMNS, TRANSACTION_JOINS, condition
+        ))?
+        .query_map(parameters, transaction_from_row)?
+        .collect::<rusqlite::Result<Vec<Transaction>>>()?;
+
+    with_items(connection, transactions)
+}
+
+#[async_trait]
+impl TransactionRepository for TransactionRepositorySqliteImpl {
+    async fn create_or_update(&mut self, transaction: &Transaction) -> Result<Option<Transaction>, TransactionRepositoryError> {
+        let transaction: Transaction = transaction.clone();
+
+        self.database
+            .run(move |connection| {
+                let sqlite_transaction = connection.transaction()?;
+                let id: String = transaction.id.to_string();
+
+                if let Err(e) = check_references(&sqlite_transaction, &transaction)? {
+                    return Ok(Err(e));
+                }
+
+                let previous: Option<Transaction> = match find_transaction(&sqlite_transaction, &id)? {
+                    Some((_, true)) => return Ok(Err(TransactionRepositoryError::TransactionAlreadyExists)),
+                    Some((existing, false)) if existing.version != transaction.version => {
+                        return Ok(Err(TransactionRepositoryError::VersionConflict {
+                            current_version: existing.version,
+                        }));
+                    }
+                    Some((existing, false)) => {
+                        sqlite_transaction.execute(
+                            "UPDATE transactions SET store_id = ?2, datetime = ?3, version = ?4 WHERE id = ?1",
+                            params![
+                                id,
+                                transaction.store.id.to_string(),
+                                format_datetime(&transaction.datetime),
+                                existing.version + 1
+                            ],
+                        )?;
+                        Some(existing)
+                    }
+                    None => {
+                        sqlite_transaction.execute(
+                            "INSERT INTO transactions (id, store_id, datetime, version) VALUES (?1, ?2, ?3, ?4)",
+                            params![
+                                id,
+                                transaction.store.id.to_string(),
+                                format_datetime(&transaction.datetime),
+                                transaction.version
+                            ],
+                        )?;
+                        None
+                    }
+                };
+
+                replace_items(&sqlite_transaction, &transaction)?;
+                sqlite_transaction.commit()?;
+                Ok(Ok(previous))
+            })
+            .await
+            .map_err(storage_error)?
+    }
+
+    async fn retrieve_all(&self) -> Result<Vec<Transaction>, TransactionRepositoryError> {
+        self.database
+            .run(move |connection| select_transactions(connection, "transactions.deleted_at IS NULL", []))
+            .await
+            .map_err(storage_error)
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Transaction, TransactionRepositoryError>> {
+        paged_stream(
+            self.database.clone(),
+            |connection, after, limit| {
+                let transactions: Vec<Transaction> = connection
+                    .prepare_cached(&format!(
+                        "SELECT {} FROM transactions {} WHERE transactions.deleted_at IS NULL AND (?1 IS NULL OR transactions.id > ?1) \
+                         ORDER BY transactions.id LIMIT ?2",
+                        TRANSACTION_COLUMNS, TRANSACTION_JOINS
+                    ))?
+                    .query_map(params![after, limit], transaction_from_row)?
+                    .collect::<rusqlite::Result<Vec<Transaction>>>()?;
+
+                Ok(with_items(connection, transactions)?
+                    .into_iter()
+                    .map(|t| (t.id.to_string(), t))
+                    .collect())
+            },
+            storage_error,
+        )
+    }
+
+    async fn delete(&mut self, id: &UuidB64) -> Result<Transaction, TransactionRepositoryError> {
+        let id: String = id.to_string();
+
+        self.database
+            .run(move |connection| match find_transaction(connection, &id)? {
+                Some((removed, false)) => {
+                    connection.execute(
+                        "UPDATE transactions SET deleted_at = ?2 WHERE id = ?1",
+                        params![id.to_string(), format_datetime(&Utc::now())],
+                    )?;
+                    Ok(Ok(removed))
+                }
+                _ => Ok(Err(TransactionRepositoryError::TransactionNotFound)),
+            })
+            .await
+            .map_err(storage_error)?
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<Trashed<Transaction>>, TransactionRepositoryError> {
+        self.database
+            .run(move |connection| {
+                let trashed: Vec<Trashed<Transaction>> = connection
+                    .prepare(&format!(
+                        "SELECT {}, transactions.deleted_at FROM transactions {} WHERE transactions.deleted_at IS NOT NULL \
+                         ORDER BY transactions.id",
+                        TRANSACTION_COLUMNS, TRANSACTION_JOINS
+                    ))?
+                    .query_map([], |row| Ok(Trashed::new(transaction_from_row(row)?, datetime_from_row(row, 6)?)))?
+                    .collect::<rusqlite::Result<Vec<Trashed<Transaction>>>>()?;
+
+                trashed
+                    .into_iter()
+                    .map(|t| {
+                        Ok(Trashed::new(
+                            Transaction {
+                                items: load_items(connection, &t.entity.id.to_string())?,
+                                ..t.entity
+                            },
+                            t.trashed_at,
+                        ))
+                    })
+                    .collect()
+            })
+            .await
+            .map_err(storage_error)
+    }
+
+    async fn restore(&mut self, id: &UuidB64) -> Result<Transaction, TransactionRepositoryError> {
+        let id: String = id.to_string();
+
+        self.database
+            .run(move |connection| match find_transaction(connection, &id)? {
+                Some((trashed, true)) => {
+                    if let Err(e) = check_references(connection, &trashed)? {
+                        return Ok(Err(e));
+                    }
+
+                    connection.execute("UPDATE transactions SET deleted_at = NULL WHERE id = ?1", params![id.to_string()])?;
+                    Ok(Ok(trashed))
+                }
+                _ => Ok(Err(TransactionRepositoryError::TransactionNotInTrash)),
+            })
+            .await
+            .map_err(storage_error)?
+    }
+
+    async fn purge_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<Vec<Transaction>, TransactionRepositoryError> {
+        self.database
+            .run(move |connection| {
+                let sqlite_transaction = connection.transaction()?;
+                let cutoff: String = format_datetime(&cutoff);
+
+                let purged: Vec<Transaction> = select_transactions(&sqlite_transaction, "transactions.deleted_at < ?1", params![cutoff])?;
+
+                sqlite_transaction.execute("DELETE FROM transactions WHERE deleted_at < ?1", params![cutoff])?;
+                sqlite_transaction.commit()?;
+                Ok(purged)
+            })
+            .await
+            .map_err(storage_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, Utc};
+    use futures::StreamExt;
+
+    use super::TransactionRepositorySqliteImpl;
+    use crate::{
+        adapters::repositories::{
+            BrandRepositorySqliteImpl, CategoryRepositorySqliteImpl, ProductRepositorySqliteImpl, StoreRepositorySqliteImpl,
+        },
+        infrastructures::data_sources::SqliteDatabase,
+    };
+    use expense_tracking::domain::{
+        entities::{Brand, Category, Item, Product, Store, Transaction, Unit},
+        repositories::{
+            BrandRepository, CategoryRepository, DeleteBehaviour, ProductRepository, StoreRepository, StoreRepositoryError,
+            TransactionRepository, TransactionRepositoryError,
+        },
+    };
+
+    struct Fixture {
+        database: SqliteDatabase,
+        store: Store,
+        product: Product,
+    }
+
+    async fn given_fixture() -> Fixture {
+        let database: SqliteDatabase = SqliteDatabase::open_in_memory().unwrap();
+        let brand: Brand = Brand::new("Nestlé".to_owned());
+        let category: Category = Category::new(None, "Dairy".to_owned());
+        let product: Product = Product::new(None, "Chocolate Milk 1L".to_owned(), brand.clone(), category.clone());
+        let store: Store = Store::new(None, "Migros".to_owned());
+
+        BrandRepositorySqliteImpl::new(database.clone()).create(&brand).await.unwrap();
+        CategoryRepositorySqliteImpl::new(database.clone())
+            .create_or_update(&category)
+            .await
+            .unwrap();
+        ProductRepositorySqliteImpl::new(database.clone())
+            .create_or_update(&product)
+            .await
+            .unwrap();
+        StoreRepositorySqliteImpl::new(database.clone())
+            .create_or_update(&store)
+            .await
+            .unwrap();
+
+        Fixture { database, store, product }
+    }
+
+    fn given_transaction(fixture: &Fixture) -> Transaction {
+        Transaction::new(
+            None,
+            vec![
+                Item::new(None, fixture.product.clone(), Unit::Quantity(2.), 1.95),
+                Item::new(None, fixture.product.clone(), Unit::Liters(1.5), 2.4),
+                Item::new(None, fixture.product.clone(), Unit::None, 0.5),
+            ],
+            fixture.store.clone(),
+            DateTime::parse_from_rfc3339("2025-03-14T09:26:53.589793Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        )
+    }
+
+    #[tokio::test]
+    async fn create_or_update_should_persist_transaction_with_items_in_order() {
+        let fixture: Fixture = given_fixture().await;
+        let transaction: Transaction = given_transaction(&fixture);
+        let mut repository: TransactionRepositorySqliteImpl = TransactionRepositorySqliteImpl::new(fixture.database.clone());
+
+        assert_eq!(repository.create_or_update(&transaction).await, Ok(None));
+
+        let result: Result<Vec<Transaction>, TransactionRepositoryError> = repository.retrieve_all().await;
+        let expected: Result<Vec<Transaction>, TransactionRepositoryError> = Ok(vec![transaction]);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn create_or_update_existing_transaction_should_replace_items() {
+        let fixture: Fixture = given_fixture().await;
+        let transaction: Transaction = given_transaction(&fixture);
+        let mut repository: TransactionRepositorySqliteImpl = TransactionRepositorySqliteImpl::new(fixture.database.clone());
+        repository.create_or_update(&transaction).await.unwrap();
+
+        let changed: Transaction = Transaction {
+            items: vec![transaction.items[0].clone()],
+            ..transaction.clone()
+        };
+
+        let result: Result<Option<Transaction>, TransactionRepositoryError> = repository.create_or_update(&changed).await;
+        let expected: Result<Option<Transaction>, TransactionRepositoryError> = Ok(Some(transaction));
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+
+        assert_eq!(repository.retrieve_all().await, Ok(vec![Transaction { version: 1, ..changed }]));
+    }
+
+    #[tokio::test]
+    async fn create_or_update_with_unknown_store_should_fail() {
+        let fixture: Fixture = given_fixture().await;
+        let transaction: Transaction = Transaction {
+            store: Store::new(None, "Unknown".to_owned()),
+            ..given_transaction(&fixture)
+        };
+        let mut repository: TransactionRepositorySqliteImpl = TransactionRepositorySqliteImpl::new(fixture.database.clone());
+
+        let result: Result<Option<Transaction>, TransactionRepositoryError> = repository.create_or_update(&transaction).await;
+        let expected: Result<Option<Transaction>, TransactionRepositoryError> = Err(TransactionRepositoryError::StoreNotFound);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn stream_all_should_yield_every_transaction() {
+        let fixture: Fixture = given_fixture().await;
+        let mut repository: TransactionRepositorySqliteImpl = TransactionRepositorySqliteImpl::new(fixture.database.clone());
+        let mut transactions: Vec<Transaction> = (0..120).map(|_| given_transaction(&fixture)).collect();
+
+        for transaction in &transactions {
+            repository.create_or_update(transaction).await.unwrap();
+        }
+        transactions.sort_by_key(|t| t.id.to_string());
+
+        let result: Result<Vec<Transaction>, TransactionRepositoryError> =
+            repository.stream_all().collect::<Vec<_>>().await.into_iter().collect();
+        let expected: Result<Vec<Transaction>, TransactionRepositoryError> = Ok(transactions);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn store_with_active_transactions_should_not_be_deleted_with_restrict() {
+        let fixture: Fixture = given_fixture().await;
+        let mut repository: TransactionRepositorySqliteImpl = TransactionRepositorySqliteImpl::new(fixture.database.clone());
+        repository.create_or_update(&given_transaction(&fixture)).await.unwrap();
+
+        let result: Result<Store, StoreRepositoryError> = StoreRepositorySqliteImpl::new(fixture.database.clone())
+            .delete(&fixture.store.id, DeleteBehaviour::Restrict)
+            .await;
+        let expected: Result<Store, StoreRepositoryError> = Err(StoreRepositoryError::StoreStillReferenced);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn delete_restore_and_purge_should_move_transaction_through_trash() {
+        let fixture: Fixture = given_fixture().await;
+        let transaction: Transaction = given_transaction(&fixture);
+        let mut repository: TransactionRepositorySqliteImpl = TransactionRepositorySqliteImpl::new(fixture.database.clone());
+        repository.create_or_update(&transaction).await.unwrap();
+
+        assert_eq!(repository.delete(&transaction.id).await, Ok(transaction.clone()));
+        assert_eq!(repository.retrieve_all().await, Ok(vec![]));
+        assert_eq!(repository.restore(&transaction.id).await, Ok(transaction.clone()));
+        assert_eq!(
+            repository.restore(&transaction.id).await,
+            Err(TransactionRepositoryError::TransactionNotInTrash)
+        );
+
+        assert_eq!(repository.delete(&transaction.id).await, Ok(transaction.clone()));
+        assert_eq!(
+            repository.purge_older_than(Utc::now() + Duration::days(1)).await,
+            Ok(vec![transaction])
+        );
+        assert_eq!(repository.list_trashed().await, Ok(vec![]));
+    }
+}
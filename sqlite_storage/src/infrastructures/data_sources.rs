@@ -0,0 +1,6 @@
+mod sqlite_database;
+mod sqlite_rows;
+
+pub use sqlite_database::SqliteDatabase;
+pub use sqlite_database::SqliteDatabaseError;
+pub(crate) use sqlite_rows::*;
@@ -0,0 +1,69 @@
+CREATE TABLE brands (
+    name TEXT PRIMARY KEY NOT NULL,
+    name_key TEXT NOT NULL,
+    version INTEGER NOT NULL DEFAULT 0,
+    deleted_at TEXT
+);
+
+CREATE UNIQUE INDEX brands_name_key_index ON brands (name_key);
+
+CREATE TABLE categories (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    name_key TEXT NOT NULL,
+    version INTEGER NOT NULL DEFAULT 0,
+    deleted_at TEXT
+);
+
+CREATE INDEX categories_name_index ON categories (name);
+CREATE UNIQUE INDEX categories_name_key_index ON categories (name_key);
+
+CREATE TABLE stores (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    name_key TEXT NOT NULL,
+    version INTEGER NOT NULL DEFAULT 0,
+    deleted_at TEXT
+);
+
+CREATE INDEX stores_name_index ON stores (name);
+CREATE UNIQUE INDEX stores_name_key_index ON stores (name_key);
+
+CREATE TABLE products (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    name_key TEXT NOT NULL,
+    brand_name TEXT NOT NULL REFERENCES brands (name) ON UPDATE CASCADE ON DELETE CASCADE,
+    category_id TEXT NOT NULL REFERENCES categories (id) ON DELETE CASCADE,
+    version INTEGER NOT NULL DEFAULT 0,
+    deleted_at TEXT
+);
+
+CREATE INDEX products_name_index ON products (name);
+CREATE UNIQUE INDEX products_name_key_index ON products (brand_name, name_key);
+CREATE INDEX products_brand_name_index ON products (brand_name);
+CREATE INDEX products_category_id_index ON products (category_id);
+
+CREATE TABLE transactions (
+    id TEXT PRIMARY KEY NOT NULL,
+    store_id TEXT NOT NULL REFERENCES stores (id) ON DELETE CASCADE,
+    datetime TEXT NOT NULL,
+    version INTEGER NOT NULL DEFAULT 0,
+    deleted_at TEXT
+);
+
+CREATE INDEX transactions_datetime_index ON transactions (datetime);
+CREATE INDEX transactions_store_id_index ON transactions (store_id);
+
+CREATE TABLE items (
+    id TEXT PRIMARY KEY NOT NULL,
+    transaction_id TEXT NOT NULL REFERENCES transactions (id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    product_id TEXT NOT NULL REFERENCES products (id) ON DELETE CASCADE,
+    unit TEXT NOT NULL,
+    amount REAL,
+    unitary_price REAL NOT NULL
+);
+
+CREATE INDEX items_transaction_id_index ON items (transaction_id, position);
+CREATE INDEX items_product_id_index ON items (product_id);
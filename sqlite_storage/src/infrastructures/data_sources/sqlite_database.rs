@@ -0,0 +1,456 @@
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    ptr::NonNull,
+    sync::{Arc, Mutex},
+};
+
+use rusqlite::{
+    Connection, DatabaseName, OpenFlags,
+    serialize::{Data, OwnedData},
+};
+use storage_encryption::infrastructures::encryption::{Cipher, CipherError};
+use storage_migrations::infrastructures::migrations::MigrationPlan;
+
+/// Schema migrations in the order they are applied, `user_version` counts how many of them a database went through.
+const MIGRATIONS: [(&str, &str); 1] = [("0001_initial_schema", include_str!("migrations/0001_initial_schema.sql"))];
+
+/// A SQLite database, either a plain file in WAL mode or a file sealed with a [`Cipher`].
+///
+/// An encrypted database is decrypted into memory when opened and sealed back to its file, atomically, after every
+/// statement that changed something, so the plaintext never touches the disk.
+#[derive(Debug, Clone)]
+pub struct SqliteDatabase {
+    connection: Arc<Mutex<Connection>>,
+    sealed_file: Option<Arc<SealedFile>>,
+}
+
+#[derive(Debug)]
+struct SealedFile {
+    path: PathBuf,
+    cipher: Cipher,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqliteDatabaseError {
+    UnableToOpen(String),
+    UnableToMigrate(String),
+    QueryFailed(String),
+    UnableToWrite(String),
+    PassphraseRequired,
+    WrongPassphrase,
+}
+
+impl SqliteDatabase {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, SqliteDatabaseError> {
+        if Self::is_encrypted(&path)? {
+            return Err(SqliteDatabaseError::PassphraseRequired);
+        }
+
+        let connection: Connection = Connection::open(path).map_err(|e| SqliteDatabaseError::UnableToOpen(e.to_string()))?;
+
+        connection
+            .pragma_update(None, "journal_mode", "WAL")
+            .map_err(|e| SqliteDatabaseError::UnableToOpen(e.to_string()))?;
+
+        Self::initialize(connection, None)
+    }
+
+    /// Opens a database sealed with `cipher`, or creates it when the file does not exist yet. A plaintext database
+    /// has to go through [`SqliteDatabase::rekey`] first.
+    pub fn open_encrypted(path: impl AsRef<Path>, cipher: Cipher) -> Result<Self, SqliteDatabaseError> {
+        let mut connection: Connection = Connection::open_in_memory().map_err(|e| SqliteDatabaseError::UnableToOpen(e.to_string()))?;
+
+        match fs::read(&path) {
+            Ok(contents) if Cipher::is_sealed(&contents) => {
+                let image: Vec<u8> = unseal(&contents, std::slice::from_ref(&cipher))?;
+                deserialize(&mut connection, &image).map_err(|e| SqliteDatabaseError::UnableToOpen(e.to_string()))?;
+            }
+            Ok(contents) if contents.is_empty() => {}
+            Ok(_) => {
+                return Err(SqliteDatabaseError::UnableToOpen(
+                    "database is not encrypted, rekey it before opening it with a passphrase".to_owned(),
+                ));
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(SqliteDatabaseError::UnableToOpen(e.to_string())),
+        }
+
+        let sealed_file: SealedFile = SealedFile {
+            path: path.as_ref().to_path_buf(),
+            cipher,
+        };
+
+        Self::initialize(connection, Some(sealed_file))
+    }
+
+    /// Whether the file exists and is sealed, in which case a passphrase is needed to open it.
+    pub fn is_encrypted(path: impl AsRef<Path>) -> Result<bool, SqliteDatabaseError> {
+        match fs::read(path) {
+            Ok(contents) => Ok(Cipher::is_sealed(&contents)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(SqliteDatabaseError::UnableToOpen(e.to_string())),
+        }
+    }
+
+    /// Rewrites the database file sealed with `new`, or as a plain SQLite file when `new` is `None`.
+    ///
+    /// A file already sealed with `new` is accepted too, so an interrupted rekey can simply be run again.
+    pub fn rekey(path: impl AsRef<Path>, current: Option<Cipher>, new: Option<Cipher>) -> Result<(), SqliteDatabaseError> {
+        let path: &Path = path.as_ref();
+        let contents: Vec<u8> = fs::read(path).map_err(|e| SqliteDatabaseError::UnableToOpen(e.to_string()))?;
+
+        let mut image: Vec<u8> = if Cipher::is_sealed(&contents) {
+            unseal(&contents, &current.into_iter().chain(new.clone()).collect::<Vec<Cipher>>())?
+        } else {
+            // Closing the only connection checkpoints the write-ahead log and removes it.
+            let connection: Connection = Connection::open(path).map_err(|e| SqliteDatabaseError::UnableToOpen(e.to_string()))?;
+            let image: Vec<u8> = connection
+                .serialize(DatabaseName::Main)
+                .map_err(|e| SqliteDatabaseError::QueryFailed(e.to_string()))?
+                .to_vec();
+            connection
+                .close()
+                .map_err(|(_, e)| SqliteDatabaseError::UnableToOpen(e.to_string()))?;
+            image
+        };
+        use_rollback_journal(&mut image);
+
+        if let Some(cipher) = new {
+            image = cipher
+                .seal(&image)
+                .map_err(|e| SqliteDatabaseError::UnableToWrite(format!("{:?}", e)))?;
+        }
+
+        write_atomically(path, &image).map_err(|e| SqliteDatabaseError::UnableToWrite(e.to_string()))
+    }
+
+    /// The migrations opening the database would apply, read without changing the file. A missing file would get
+    /// every one of them.
+    pub fn migration_plan(path: impl AsRef<Path>, cipher: Option<Cipher>) -> Result<MigrationPlan, SqliteDatabaseError> {
+        let current_version: usize = match fs::read(&path) {
+            Ok(contents) if Cipher::is_sealed(&contents) => {
+                let image: Vec<u8> = unseal(&contents, &Vec::from_iter(cipher))?;
+                let mut connection: Connection =
+                    Connection::open_in_memory().map_err(|e| SqliteDatabaseError::UnableToOpen(e.to_string()))?;
+                deserialize(&mut connection, &image).map_err(|e| SqliteDatabaseError::UnableToOpen(e.to_string()))?;
+                user_version(&connection)
+            }
+            Ok(contents) if contents.is_empty() => Ok(0),
+            Ok(_) => Connection::open_with_flags(&path, OpenFlags::SQLITE_OPEN_READ_ONLY).and_then(|connection| user_version(&connection)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(0),
+            Err(e) => return Err(SqliteDatabaseError::UnableToOpen(e.to_string())),
+        }
+        .map_err(|e| SqliteDatabaseError::UnableToOpen(e.to_string()))?;
+
+        plan(current_version)
+    }
+
+    pub fn open_in_memory() -> Result<Self, SqliteDatabaseError> {
+        let connection: Connection = Connection::open_in_memory().map_err(|e| SqliteDatabaseError::UnableToOpen(e.to_string()))?;
+
+        Self::initialize(connection, None)
+    }
+
+    pub fn schema_version(&self) -> Result<usize, SqliteDatabaseError> {
+        self.with_connection(|connection| user_version(connection))
+    }
+
+    pub(crate) fn with_connection<T>(&self, f: impl FnOnce(&mut Connection) -> rusqlite::Result<T>) -> Result<T, SqliteDatabaseError> {
+        let mut connection = self
+            .connection
+            .lock()
+            .map_err(|e| SqliteDatabaseError::QueryFailed(e.to_string()))?;
+
+        let changes_before: u64 = connection.total_changes();
+        let result: T = f(&mut connection).map_err(|e| SqliteDatabaseError::QueryFailed(e.to_string()))?;
+
+        if let Some(sealed_file) = &self.sealed_file
+            && connection.total_changes() != changes_before
+        {
+            sealed_file.save(&connection)?;
+        }
+
+        Ok(result)
+    }
+
+    /// Runs `f` on a blocking thread, so that waiting for the connection or for SQLite does not stall the runtime.
+    pub(crate) async fn run<T>(
+        &self,
+        f: impl FnOnce(&mut Connection) -> rusqlite::Result<T> + Send + 'static,
+    ) -> Result<T, SqliteDatabaseError>
+    where
+        T: Send + 'static,
+    {
+        let database: SqliteDatabase = self.clone();
+
+        tokio::task::spawn_blocking(move || database.with_connection(f))
+            .await
+            .map_err(|e| SqliteDatabaseError::QueryFailed(e.to_string()))?
+    }
+
+    fn initialize(mut connection: Connection, sealed_file: Option<SealedFile>) -> Result<Self, SqliteDatabaseError> {
+        connection
+            .pragma_update(None, "foreign_keys", true)
+            .map_err(|e| SqliteDatabaseError::UnableToOpen(e.to_string()))?;
+
+        migrate(&mut connection)?;
+
+        if let Some(sealed_file) = &sealed_file {
+            sealed_file.save(&connection)?;
+        }
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+            sealed_file: sealed_file.map(Arc::new),
+        })
+    }
+}
+
+impl SealedFile {
+    fn save(&self, connection: &Connection) -> Result<(), SqliteDatabaseError> {
+        let image: Data = connection
+            .serialize(DatabaseName::Main)
+            .map_err(|e| SqliteDatabaseError::UnableToWrite(e.to_string()))?;
+        let sealed: Vec<u8> = self
+            .cipher
+            .seal(&image)
+            .map_err(|e| SqliteDatabaseError::UnableToWrite(format!("{:?}", e)))?;
+
+        write_atomically(&self.path, &sealed).map_err(|e| SqliteDatabaseError::UnableToWrite(e.to_string()))
+    }
+}
+
+fn unseal(contents: &[u8], candidates: &[Cipher]) -> Result<Vec<u8>, SqliteDatabaseError> {
+    let mut error: SqliteDatabaseError = SqliteDatabaseError::PassphraseRequired;
+
+    for cipher in candidates {
+        match cipher.open(contents) {
+            Ok(mut image) => {
+                use_rollback_journal(&mut image);
+                return Ok(image);
+            }
+            Err(CipherError::WrongPassphrase) => error = SqliteDatabaseError::WrongPassphrase,
+            Err(e) => return Err(SqliteDatabaseError::UnableToOpen(format!("{:?}", e))),
+        }
+    }
+
+    Err(error)
+}
+
+/// Marks a database image as using a rollback journal, in-memory databases refuse images flagged for WAL.
+fn use_rollback_journal(image: &mut [u8]) {
+    if let Some(versions) = image.get_mut(18..20) {
+        versions.copy_from_slice(&[1, 1]);
+    }
+}
+
+fn deserialize(connection: &mut Connection, image: &[u8]) -> rusqlite::Result<()> {
+    if image.is_empty() {
+        return Ok(());
+    }
+
+    // SAFETY: SQLite takes ownership of the buffer, which therefore has to come from `sqlite3_malloc64`, and it is
+    // fully initialized by the copy before being handed over.
+    let data: OwnedData = unsafe {
+        let buffer: NonNull<u8> = NonNull::new(rusqlite::ffi::sqlite3_malloc64(image.len() as u64).cast::<u8>()).ok_or(
+            rusqlite::Error::SqliteFailure(rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_NOMEM), None),
+        )?;
+        std::ptr::copy_nonoverlapping(image.as_ptr(), buffer.as_ptr(), image.len());
+        OwnedData::from_raw_nonnull(buffer, image.len())
+    };
+
+    connection.deserialize(DatabaseName::Main, data, false)
+}
+
+fn write_atomically(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let file_name: String = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "database path has no file name"))?
+        .to_string_lossy()
+        .into_owned();
+    let temporary_path: PathBuf = path.with_file_name(format!(".{}.tmp", file_name));
+
+    let mut temporary: File = File::create(&temporary_path)?;
+    temporary.write_all(contents)?;
+    temporary.sync_all()?;
+    drop(temporary);
+
+    fs::rename(&temporary_path, path)
+}
+
+fn user_version(connection: &Connection) -> rusqlite::Result<usize> {
+    connection.pragma_query_value(None, "user_version", |row| row.get(0))
+}
+
+fn plan(current_version: usize) -> Result<MigrationPlan, SqliteDatabaseError> {
+    if current_version > MIGRATIONS.len() {
+        return Err(SqliteDatabaseError::UnableToMigrate(format!(
+            "schema version {} is newer than the supported {}",
+            current_version,
+            MIGRATIONS.len()
+        )));
+    }
+
+    Ok(MigrationPlan {
+        from_version: current_version as u32,
+        to_version: MIGRATIONS.len() as u32,
+        steps: MIGRATIONS[current_version..].iter().map(|(name, _)| (*name).to_owned()).collect(),
+    })
+}
+
+fn migrate(connection: &mut Connection) -> Result<(), SqliteDatabaseError> {
+    let current_version: usize = user_version(connection).map_err(|e| SqliteDatabaseError::UnableToMigrate(e.to_string()))?;
+    plan(current_version)?;
+
+    MIGRATIONS
+        .iter()
+        .enumerate()
+        .skip(current_version)
+        .try_for_each(|(index, (_, migration))| {
+            let transaction = connection.transaction()?;
+
+            transaction.execute_batch(migration)?;
+            transaction.pragma_update(None, "user_version", index + 1)?;
+            transaction.commit()
+        })
+        .map_err(|e| SqliteDatabaseError::UnableToMigrate(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use storage_encryption::infrastructures::encryption::{Cipher, KeyDerivationCost};
+
+    use super::{MIGRATIONS, SqliteDatabase, SqliteDatabaseError};
+
+    const CHEAP: KeyDerivationCost = KeyDerivationCost {
+        memory_kib: 8,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    #[test]
+    fn open_in_memory_should_apply_all_migrations() {
+        let database: SqliteDatabase = SqliteDatabase::open_in_memory().unwrap();
+
+        let result: Result<usize, SqliteDatabaseError> = database.schema_version();
+        let expected: Result<usize, SqliteDatabaseError> = Ok(MIGRATIONS.len());
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[test]
+    fn open_should_not_reapply_migrations_to_existing_file() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let path: std::path::PathBuf = directory.path().join("frosty_pine.sqlite");
+
+        SqliteDatabase::open(&path)
+            .unwrap()
+            .with_connection(|connection| connection.execute("INSERT INTO brands (name, name_key) VALUES ('Some Brand', 'some brand')", []))
+            .unwrap();
+
+        let database: SqliteDatabase = SqliteDatabase::open(&path).unwrap();
+        let result: Result<usize, SqliteDatabaseError> =
+            database.with_connection(|connection| connection.query_row("SELECT COUNT(*) FROM brands", [], |row| row.get(0)));
+        let expected: Result<usize, SqliteDatabaseError> = Ok(1);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(database.schema_version(), Ok(MIGRATIONS.len()));
+    }
+
+    #[test]
+    fn migration_plan_should_list_pending_migrations_without_applying_them() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let path: std::path::PathBuf = directory.path().join("frosty_pine.sqlite");
+
+        let result: Result<Vec<String>, SqliteDatabaseError> = SqliteDatabase::migration_plan(&path, None).map(|plan| plan.steps);
+        let expected: Result<Vec<String>, SqliteDatabaseError> = Ok(MIGRATIONS.iter().map(|(name, _)| (*name).to_owned()).collect());
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert!(!path.exists());
+
+        drop(SqliteDatabase::open(&path).unwrap());
+        assert_eq!(SqliteDatabase::migration_plan(&path, None).map(|plan| plan.is_empty()), Ok(true));
+
+        rusqlite::Connection::open(&path)
+            .unwrap()
+            .pragma_update(None, "user_version", MIGRATIONS.len() + 1)
+            .unwrap();
+        assert!(matches!(SqliteDatabase::open(&path), Err(SqliteDatabaseError::UnableToMigrate(_))));
+    }
+
+    #[test]
+    fn brands_should_not_share_a_name_key() {
+        let database: SqliteDatabase = SqliteDatabase::open_in_memory().unwrap();
+
+        let result: Result<usize, SqliteDatabaseError> = database.with_connection(|connection| {
+            connection.execute(
+                "INSERT INTO brands (name, name_key) VALUES ('Nestlé', 'nestle'), ('nestle', 'nestle')",
+                [],
+            )
+        });
+
+        assert!(result.is_err(), "Expected Err, got {:?}", result);
+    }
+
+    #[test]
+    fn foreign_keys_should_be_enforced() {
+        let database: SqliteDatabase = SqliteDatabase::open_in_memory().unwrap();
+
+        let result: Result<usize, SqliteDatabaseError> = database.with_connection(|connection| {
+            connection.execute(
+                "INSERT INTO products (id, name, name_key, brand_name, category_id) VALUES ('p', 'Product', 'product', 'Missing', 'Missing')",
+                [],
+            )
+        });
+
+        assert!(result.is_err(), "Expected Err, got {:?}", result);
+    }
+
+    #[test]
+    fn open_encrypted_should_keep_changes_sealed_across_reopen() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let path: std::path::PathBuf = directory.path().join("frosty_pine.sqlite");
+
+        SqliteDatabase::open_encrypted(&path, Cipher::new("correct horse").with_cost(CHEAP))
+            .unwrap()
+            .with_connection(|connection| connection.execute("INSERT INTO brands (name, name_key) VALUES ('Some Brand', 'some brand')", []))
+            .unwrap();
+
+        assert!(!std::fs::read(&path).unwrap().windows(10).any(|window| window == b"Some Brand"));
+        assert_eq!(SqliteDatabase::open(&path).err(), Some(SqliteDatabaseError::PassphraseRequired));
+        assert_eq!(
+            SqliteDatabase::open_encrypted(&path, Cipher::new("battery staple")).err(),
+            Some(SqliteDatabaseError::WrongPassphrase)
+        );
+
+        let database: SqliteDatabase = SqliteDatabase::open_encrypted(&path, Cipher::new("correct horse")).unwrap();
+        let result: Result<usize, SqliteDatabaseError> =
+            database.with_connection(|connection| connection.query_row("SELECT COUNT(*) FROM brands", [], |row| row.get(0)));
+        let expected: Result<usize, SqliteDatabaseError> = Ok(1);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[test]
+    fn rekey_should_encrypt_and_decrypt_an_existing_database() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let path: std::path::PathBuf = directory.path().join("frosty_pine.sqlite");
+        SqliteDatabase::open(&path)
+            .unwrap()
+            .with_connection(|connection| connection.execute("INSERT INTO brands (name, name_key) VALUES ('Some Brand', 'some brand')", []))
+            .unwrap();
+
+        SqliteDatabase::rekey(&path, None, Some(Cipher::new("correct horse").with_cost(CHEAP))).unwrap();
+        assert_eq!(SqliteDatabase::is_encrypted(&path), Ok(true));
+
+        SqliteDatabase::rekey(&path, Some(Cipher::new("correct horse")), None).unwrap();
+        let database: SqliteDatabase = SqliteDatabase::open(&path).unwrap();
+        let result: Result<usize, SqliteDatabaseError> =
+            database.with_connection(|connection| connection.query_row("SELECT COUNT(*) FROM brands", [], |row| row.get(0)));
+        let expected: Result<usize, SqliteDatabaseError> = Ok(1);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+}
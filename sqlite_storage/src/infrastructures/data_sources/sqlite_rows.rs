@@ -0,0 +1,210 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures::{
+    StreamExt,
+    stream::{self, BoxStream},
+};
+use rusqlite::{Connection, Row, params, types::Type};
+use uuid_b64::UuidB64;
+
+use expense_tracking::domain::entities::{Brand, Category, Item, Product, Unit};
+
+use crate::infrastructures::data_sources::{SqliteDatabase, SqliteDatabaseError};
+
+const PAGE_SIZE: usize = 100;
+
+pub(crate) const PRODUCT_COLUMNS: &str = "products.id, products.name, products.version, brands.name, brands.version, categories.id, \
+     categories.name, categories.version";
+
+pub(crate) const PRODUCT_JOINS: &str =
+    "JOIN brands ON brands.name = products.brand_name JOIN categories ON categories.id = products.category_id";
+
+pub(crate) fn format_datetime(datetime: &DateTime<Utc>) -> String {
+    datetime.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+pub(crate) fn datetime_from_row(row: &Row, index: usize) -> rusqlite::Result<DateTime<Utc>> {
+    let value: String = row.get(index)?;
+
+    DateTime::parse_from_rfc3339(&value)
+        .map(|d| d.with_timezone(&Utc))
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(index, Type::Text, Box::new(e)))
+}
+
+pub(crate) fn uuid_from_row(row: &Row, index: usize) -> rusqlite::Result<UuidB64> {
+    let value: String = row.get(index)?;
+
+    value
+        .parse::<UuidB64>()
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(index, Type::Text, format!("{:?}", e).into()))
+}
+
+pub(crate) fn product_from_row(row: &Row, offset: usize) -> rusqlite::Result<Product> {
+    let brand: Brand = Brand {
+        version: row.get(offset + 4)?,
+        ..Brand::new(row.get(offset + 3)?)
+    };
+    let category: Category = Category {
+        version: row.get(offset + 7)?,
+        ..Category::new(Some(uuid_from_row(row, offset + 5)?), row.get(offset + 6)?)
+    };
+
+    Ok(Product {
+        version: row.get(offset + 2)?,
+        ..Product::new(Some(uuid_from_row(row, offset)?), row.get(offset + 1)?, brand, category)
+    })
+}
+
+pub(crate) fn unit_to_columns(unit: &Unit) -> (&'static str, Option<f64>) {
+    match unit {
+        Unit::None => ("none", None),
+        Unit::Quantity(amount) => ("quantity", Some(*amount)),
+        Unit::Kilograms(weight) => ("kilograms", Some(*weight)),
+        Unit::Liters(volume) => ("liters", Some(*volume)),
+    }
+}
+
+pub(crate) fn unit_from_row(row: &Row, index: usize) -> rusqlite::Result<Unit> {
+    let kind: String = row.get(index)?;
+    let amount: Option<f64> = row.get(index + 1)?;
+
+    match (kind.as_str(), amount) {
+        ("none", _) => Ok(Unit::None),
+        ("quantity", Some(amount)) => Ok(Unit::Quantity(amount)),
+        ("kilograms", Some(weight)) => Ok(Unit::Kilograms(weight)),
+        ("liters", Some(volume)) => Ok(Unit::Liters(volume)),
+        _ => Err(rusqlite::Error::FromSqlConversionFailure(
+            index,
+            Type::Text,
+            format!("Invalid unit {} with amount {:?}", kind, amount).into(),
+        )),
+    }
+}
+
+pub(crate) fn load_items(connection: &Connection, transaction_id: &str) -> rusqlite::Result<Vec<Item>> {
+    let mut statement = connection.prepare_cached(&format!(
+        "SELECT items.id, items.unit, items.amount, items.unitary_price, {} FROM items JOIN products ON products.id = items.product_id {} \
+         WHERE items.transaction_id = ?1 ORDER BY items.position",
+        PRODUCT_COLUMNS, PRODUCT_JOINS
+    ))?;
+
+    statement
+        .query_map(params![transaction_id], |row| {
+            Ok(Item::new(
+                Some(uuid_from_row(row, 0)?),
+                product_from_row(row, 4)?,
+                unit_from_row(row, 1)?,
+                row.get(3)?,
+            ))
+        })?
+        .collect()
+}
+
+pub(crate) fn is_active(connection: &Connection, table: &str, key_column: &str, key: &str) -> rusqlite::Result<bool> {
+    connection.query_row(
+        &format!(
+            "SELECT EXISTS (SELECT 1 FROM {} WHERE {} = ?1 AND deleted_at IS NULL)",
+            table, key_column
+        ),
+        params![key],
+        |row| row.get(0),
+    )
+}
+
+/// Whether a row of `table` other than `id`, even a trashed one, already holds `name_key`.
+pub(crate) fn is_name_taken(connection: &Connection, table: &str, id: &str, name_key: &str) -> rusqlite::Result<bool> {
+    connection.query_row(
+        &format!("SELECT EXISTS (SELECT 1 FROM {} WHERE name_key = ?2 AND id != ?1)", table),
+        params![id, name_key],
+        |row| row.get(0),
+    )
+}
+
+pub(crate) fn deleted_at(connection: &Connection, table: &str, key_column: &str, key: &str) -> rusqlite::Result<String> {
+    connection.query_row(
+        &format!("SELECT deleted_at FROM {} WHERE {} = ?1", table, key_column),
+        params![key],
+        |row| row.get(0),
+    )
+}
+
+/// Filter selecting the transactions holding an item of a product whose `column` is `?1`.
+pub(crate) fn holding_products(column: &str) -> String {
+    format!(
+        "id IN (SELECT items.transaction_id FROM items JOIN products ON products.id = items.product_id WHERE products.{} = ?1)",
+        column
+    )
+}
+
+/// Trashes the active transactions matching `filter` with the `deleted_at` of the entity whose deletion cascades to them.
+pub(crate) fn trash_transactions(connection: &Connection, filter: &str, key: &str, deleted_at: &str) -> rusqlite::Result<usize> {
+    connection.execute(
+        &format!("UPDATE transactions SET deleted_at = ?2 WHERE deleted_at IS NULL AND {}", filter),
+        params![key, deleted_at],
+    )
+}
+
+/// Restores the transactions matching `filter` trashed at `deleted_at`, leaving those whose store or products are still in the trash.
+pub(crate) fn restore_transactions(connection: &Connection, filter: &str, key: &str, deleted_at: &str) -> rusqlite::Result<usize> {
+    connection.execute(
+        &format!(
+            "UPDATE transactions SET deleted_at = NULL WHERE deleted_at = ?2 AND {} \
+             AND EXISTS (SELECT 1 FROM stores WHERE stores.id = transactions.store_id AND stores.deleted_at IS NULL) \
+             AND NOT EXISTS (SELECT 1 FROM items JOIN products ON products.id = items.product_id \
+             WHERE items.transaction_id = transactions.id AND products.deleted_at IS NOT NULL)",
+            filter
+        ),
+        params![key, deleted_at],
+    )
+}
+
+/// Deletes the transactions holding an item of a product matching `products`, before the purge of those products drops
+/// their items.
+pub(crate) fn purge_transactions_holding(connection: &Connection, products: &str, cutoff: &str) -> rusqlite::Result<usize> {
+    connection.execute(
+        &format!(
+            "DELETE FROM transactions WHERE id IN (SELECT items.transaction_id FROM items JOIN products ON products.id = items.product_id \
+             WHERE {})",
+            products
+        ),
+        params![cutoff],
+    )
+}
+
+/// Streams the rows `page` returns a page at a time, each page fetched through [`SqliteDatabase::run`] and starting
+/// after the key of the last row of the previous one.
+pub(crate) fn paged_stream<T, E>(
+    database: SqliteDatabase,
+    page: impl Fn(&mut Connection, Option<&str>, usize) -> rusqlite::Result<Vec<(String, T)>> + Send + Sync + 'static,
+    to_error: fn(SqliteDatabaseError) -> E,
+) -> BoxStream<'static, Result<T, E>>
+where
+    T: Send + 'static,
+    E: Send + 'static,
+{
+    let page = Arc::new(page);
+
+    stream::unfold(Some(None::<String>), move |cursor: Option<Option<String>>| {
+        let database: SqliteDatabase = database.clone();
+        let page = Arc::clone(&page);
+
+        async move {
+            let cursor: Option<String> = cursor?;
+
+            match database.run(move |connection| page(connection, cursor.as_deref(), PAGE_SIZE)).await {
+                Ok(rows) => {
+                    let next: Option<Option<String>> = match rows.last() {
+                        Some((key, _)) if rows.len() == PAGE_SIZE => Some(Some(key.clone())),
+                        _ => None,
+                    };
+
+                    Some((rows.into_iter().map(|(_, value)| Ok(value)).collect::<Vec<Result<T, E>>>(), next))
+                }
+                Err(e) => Some((vec![Err(to_error(e))], None)),
+            }
+        }
+    })
+    .flat_map(stream::iter)
+    .boxed()
+}
@@ -0,0 +1,8 @@
+mod cipher;
+mod passphrase;
+
+pub use cipher::Cipher;
+pub use cipher::CipherError;
+pub use cipher::KeyDerivationCost;
+pub use passphrase::new_passphrase_from_environment;
+pub use passphrase::passphrase_from_environment;
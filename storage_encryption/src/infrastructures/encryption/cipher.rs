@@ -0,0 +1,313 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::{
+    Key, XChaCha20Poly1305, XNonce,
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload, rand_core::RngCore},
+};
+use zeroize::Zeroizing;
+
+const MAGIC: &[u8; 8] = b"FROSTENC";
+const FORMAT_VERSION: u8 = 1;
+const SALT_LENGTH: usize = 16;
+const KEY_LENGTH: usize = 32;
+const CHECK_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 24;
+const HEADER_LENGTH: usize = MAGIC.len() + 1 + 3 * 4 + SALT_LENGTH + CHECK_LENGTH + NONCE_LENGTH;
+
+/// Argon2id parameters used to turn a passphrase into a key, stored in every sealed file so that they can be raised
+/// later without breaking existing data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyDerivationCost {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for KeyDerivationCost {
+    fn default() -> Self {
+        Self {
+            memory_kib: Params::DEFAULT_M_COST,
+            iterations: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+/// Seals and opens whole files with XChaCha20-Poly1305 under a key derived from a passphrase with Argon2id.
+///
+/// A sealed file starts with a header holding the salt, the key derivation cost and a check value derived along
+/// with the key, which tells a wrong passphrase apart from a damaged file. The derived key is cached, so sealing the
+/// same data source over and over only pays for Argon2 once.
+#[derive(Clone)]
+pub struct Cipher {
+    passphrase: Arc<Zeroizing<String>>,
+    cost: KeyDerivationCost,
+    derived: Arc<Mutex<Option<DerivedKey>>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CipherError {
+    NotEncrypted,
+    UnsupportedVersion(u8),
+    WrongPassphrase,
+    Corrupted(String),
+    UnableToDeriveKey(String),
+    UnableToEncrypt(String),
+}
+
+#[derive(Clone)]
+struct DerivedKey {
+    cost: KeyDerivationCost,
+    salt: [u8; SALT_LENGTH],
+    key: Zeroizing<[u8; KEY_LENGTH]>,
+    check: [u8; CHECK_LENGTH],
+}
+
+struct Header {
+    cost: KeyDerivationCost,
+    salt: [u8; SALT_LENGTH],
+    check: [u8; CHECK_LENGTH],
+    nonce: [u8; NONCE_LENGTH],
+}
+
+impl Cipher {
+    pub fn new(passphrase: impl Into<String>) -> Self {
+        Self {
+            passphrase: Arc::new(Zeroizing::new(passphrase.into())),
+            cost: KeyDerivationCost::default(),
+            derived: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Cost used for the keys of newly sealed files, files sealed with another cost can still be opened.
+    pub fn with_cost(self, cost: KeyDerivationCost) -> Self {
+        Self { cost, ..self }
+    }
+
+    /// Whether `data` looks like something produced by [`Cipher::seal`].
+    pub fn is_sealed(data: &[u8]) -> bool {
+        data.starts_with(MAGIC)
+    }
+
+    pub fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>, CipherError> {
+        let derived: DerivedKey = match self.cached(|derived| derived.cost == self.cost) {
+            Some(derived) => derived,
+            None => {
+                let mut salt: [u8; SALT_LENGTH] = [0; SALT_LENGTH];
+                OsRng.fill_bytes(&mut salt);
+
+                let derived: DerivedKey = derive(&self.passphrase, self.cost, salt)?;
+                self.cache(derived.clone());
+                derived
+            }
+        };
+
+        let nonce: XNonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let header: Vec<u8> = Header {
+            cost: derived.cost,
+            salt: derived.salt,
+            check: derived.check,
+            nonce: nonce.into(),
+        }
+        .to_bytes();
+
+        let ciphertext: Vec<u8> = XChaCha20Poly1305::new(Key::from_slice(derived.key.as_slice()))
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext,
+                    aad: &header,
+                },
+            )
+            .map_err(|e| CipherError::UnableToEncrypt(e.to_string()))?;
+
+        Ok([header, ciphertext].concat())
+    }
+
+    pub fn open(&self, sealed: &[u8]) -> Result<Vec<u8>, CipherError> {
+        let header: Header = Header::from_bytes(sealed)?;
+
+        let derived: DerivedKey = match self.cached(|derived| derived.cost == header.cost && derived.salt == header.salt) {
+            Some(derived) => derived,
+            None => derive(&self.passphrase, header.cost, header.salt)?,
+        };
+
+        if derived.check != header.check {
+            return Err(CipherError::WrongPassphrase);
+        }
+
+        let plaintext: Vec<u8> = XChaCha20Poly1305::new(Key::from_slice(derived.key.as_slice()))
+            .decrypt(
+                XNonce::from_slice(&header.nonce),
+                Payload {
+                    msg: &sealed[HEADER_LENGTH..],
+                    aad: &sealed[..HEADER_LENGTH],
+                },
+            )
+            .map_err(|_| CipherError::Corrupted("authentication failed".to_owned()))?;
+
+        self.cache(derived);
+        Ok(plaintext)
+    }
+
+    fn cached(&self, matches: impl Fn(&DerivedKey) -> bool) -> Option<DerivedKey> {
+        self.derived
+            .lock()
+            .ok()
+            .and_then(|derived| derived.as_ref().filter(|derived| matches(derived)).cloned())
+    }
+
+    fn cache(&self, derived: DerivedKey) {
+        if let Ok(mut cached) = self.derived.lock() {
+            *cached = Some(derived);
+        }
+    }
+}
+
+impl fmt::Debug for Cipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cipher").field("cost", &self.cost).finish_non_exhaustive()
+    }
+}
+
+impl Header {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(HEADER_LENGTH);
+
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(FORMAT_VERSION);
+        bytes.extend_from_slice(&self.cost.memory_kib.to_le_bytes());
+        bytes.extend_from_slice(&self.cost.iterations.to_le_bytes());
+        bytes.extend_from_slice(&self.cost.parallelism.to_le_bytes());
+        bytes.extend_from_slice(&self.salt);
+        bytes.extend_from_slice(&self.check);
+        bytes.extend_from_slice(&self.nonce);
+
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, CipherError> {
+        if !Cipher::is_sealed(bytes) {
+            return Err(CipherError::NotEncrypted);
+        }
+
+        let mut rest: &[u8] = &bytes[MAGIC.len()..];
+        let version: u8 = *take(&mut rest, 1)?.first().unwrap_or(&0);
+
+        if version != FORMAT_VERSION {
+            return Err(CipherError::UnsupportedVersion(version));
+        }
+
+        Ok(Self {
+            cost: KeyDerivationCost {
+                memory_kib: take_u32(&mut rest)?,
+                iterations: take_u32(&mut rest)?,
+                parallelism: take_u32(&mut rest)?,
+            },
+            salt: take(&mut rest, SALT_LENGTH)?.try_into().unwrap_or_default(),
+            check: take(&mut rest, CHECK_LENGTH)?.try_into().unwrap_or_default(),
+            nonce: take(&mut rest, NONCE_LENGTH)?.try_into().unwrap_or_default(),
+        })
+    }
+}
+
+fn take<'a>(bytes: &mut &'a [u8], length: usize) -> Result<&'a [u8], CipherError> {
+    if bytes.len() < length {
+        return Err(CipherError::Corrupted("truncated header".to_owned()));
+    }
+
+    let (taken, rest) = bytes.split_at(length);
+    *bytes = rest;
+
+    Ok(taken)
+}
+
+fn take_u32(bytes: &mut &[u8]) -> Result<u32, CipherError> {
+    Ok(u32::from_le_bytes(take(bytes, 4)?.try_into().unwrap_or_default()))
+}
+
+fn derive(passphrase: &str, cost: KeyDerivationCost, salt: [u8; SALT_LENGTH]) -> Result<DerivedKey, CipherError> {
+    let params: Params = Params::new(cost.memory_kib, cost.iterations, cost.parallelism, Some(KEY_LENGTH + CHECK_LENGTH))
+        .map_err(|e| CipherError::UnableToDeriveKey(e.to_string()))?;
+    let mut output: Zeroizing<[u8; KEY_LENGTH + CHECK_LENGTH]> = Zeroizing::new([0; KEY_LENGTH + CHECK_LENGTH]);
+
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), &salt, output.as_mut_slice())
+        .map_err(|e| CipherError::UnableToDeriveKey(e.to_string()))?;
+
+    let mut key: Zeroizing<[u8; KEY_LENGTH]> = Zeroizing::new([0; KEY_LENGTH]);
+    key.copy_from_slice(&output[..KEY_LENGTH]);
+
+    let mut check: [u8; CHECK_LENGTH] = [0; CHECK_LENGTH];
+    check.copy_from_slice(&output[KEY_LENGTH..]);
+
+    Ok(DerivedKey { cost, salt, key, check })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cipher, CipherError, HEADER_LENGTH, KeyDerivationCost};
+
+    const CHEAP: KeyDerivationCost = KeyDerivationCost {
+        memory_kib: 8,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    #[test]
+    fn open_should_return_what_was_sealed() {
+        let cipher: Cipher = Cipher::new("correct horse").with_cost(CHEAP);
+        let sealed: Vec<u8> = cipher.seal(b"{\"brands\":[]}").unwrap();
+
+        let result: Result<Vec<u8>, CipherError> = Cipher::new("correct horse").open(&sealed);
+        let expected: Result<Vec<u8>, CipherError> = Ok(b"{\"brands\":[]}".to_vec());
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert!(Cipher::is_sealed(&sealed));
+        assert!(!sealed.windows(8).any(|window| window == b"\"brands\""));
+    }
+
+    #[test]
+    fn seal_should_use_a_fresh_nonce_every_time() {
+        let cipher: Cipher = Cipher::new("correct horse").with_cost(CHEAP);
+
+        assert_ne!(cipher.seal(b"same").unwrap(), cipher.seal(b"same").unwrap());
+    }
+
+    #[test]
+    fn open_with_wrong_passphrase_should_fail_with_wrong_passphrase() {
+        let sealed: Vec<u8> = Cipher::new("correct horse").with_cost(CHEAP).seal(b"secret").unwrap();
+
+        let result: Result<Vec<u8>, CipherError> = Cipher::new("battery staple").open(&sealed);
+        let expected: Result<Vec<u8>, CipherError> = Err(CipherError::WrongPassphrase);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[test]
+    fn open_tampered_data_should_fail_as_corrupted() {
+        let cipher: Cipher = Cipher::new("correct horse").with_cost(CHEAP);
+        let mut sealed: Vec<u8> = cipher.seal(b"secret").unwrap();
+        sealed[HEADER_LENGTH] ^= 1;
+
+        let result: Result<Vec<u8>, CipherError> = cipher.open(&sealed);
+
+        assert!(
+            matches!(result, Err(CipherError::Corrupted(_))),
+            "Expected Corrupted, but got {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn open_plaintext_should_fail_as_not_encrypted() {
+        let result: Result<Vec<u8>, CipherError> = Cipher::new("correct horse").open(b"[]\n");
+        let expected: Result<Vec<u8>, CipherError> = Err(CipherError::NotEncrypted);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+}
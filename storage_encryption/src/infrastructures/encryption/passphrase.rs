@@ -0,0 +1,17 @@
+use std::env;
+
+use crate::infrastructures::encryption::Cipher;
+
+const PASSPHRASE_VARIABLE: &str = "FROSTY_PINE_PASSPHRASE";
+const NEW_PASSPHRASE_VARIABLE: &str = "FROSTY_PINE_NEW_PASSPHRASE";
+
+/// The cipher for the passphrase of an existing file, when `FROSTY_PINE_PASSPHRASE` provides it, so that scripts and
+/// every front-end can open the same file without a prompt.
+pub fn passphrase_from_environment() -> Option<Cipher> {
+    env::var(PASSPHRASE_VARIABLE).ok().map(Cipher::new)
+}
+
+/// The cipher for the passphrase a file is about to be sealed with, when `FROSTY_PINE_NEW_PASSPHRASE` provides it.
+pub fn new_passphrase_from_environment() -> Option<Cipher> {
+    env::var(NEW_PASSPHRASE_VARIABLE).ok().map(Cipher::new)
+}
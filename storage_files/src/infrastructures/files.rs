@@ -0,0 +1,15 @@
+mod atomic_write;
+mod directory_lock;
+mod format_file;
+mod paged_stream;
+
+pub use atomic_write::sync_directory;
+pub use atomic_write::write_atomically;
+pub use directory_lock::DirectoryLock;
+pub use directory_lock::DirectoryLockError;
+pub use format_file::FORMAT_FILE;
+pub use format_file::format_migrations;
+pub use format_file::read_format_version;
+pub use format_file::write_format_version;
+pub use paged_stream::PAGE_SIZE;
+pub use paged_stream::paged_stream;
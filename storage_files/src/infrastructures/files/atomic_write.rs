@@ -0,0 +1,53 @@
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// Replaces `file_name` in `root` with `contents` through a temporary file renamed over it, so that a crash leaves
+/// either the old or the new contents behind, never a mix of both.
+pub fn write_atomically(root: &Path, file_name: &str, contents: &[u8]) -> io::Result<()> {
+    let temporary_path: PathBuf = root.join(format!(".{}.tmp", file_name));
+
+    let mut temporary: File = File::create(&temporary_path)?;
+    temporary.write_all(contents)?;
+    temporary.sync_all()?;
+    drop(temporary);
+
+    fs::rename(&temporary_path, root.join(file_name))?;
+
+    sync_directory(root)
+}
+
+/// Makes the files created, renamed or removed in `root` durable.
+#[cfg(unix)]
+pub fn sync_directory(root: &Path) -> io::Result<()> {
+    File::open(root)?.sync_all()
+}
+
+/// Makes the files created, renamed or removed in `root` durable.
+#[cfg(not(unix))]
+pub fn sync_directory(_root: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::write_atomically;
+
+    #[test]
+    fn write_atomically_should_replace_the_file_without_leaving_the_temporary_one() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        fs::write(directory.path().join("brands.json"), "[]").unwrap();
+
+        write_atomically(directory.path(), "brands.json", b"[1]").unwrap();
+
+        let result: String = fs::read_to_string(directory.path().join("brands.json")).unwrap();
+        let expected: &str = "[1]";
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert!(!directory.path().join(".brands.json.tmp").exists());
+    }
+}
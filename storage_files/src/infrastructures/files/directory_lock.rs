@@ -0,0 +1,59 @@
+use std::{
+    fs::{File, OpenOptions, TryLockError},
+    path::Path,
+};
+
+const LOCK_FILE: &str = ".lock";
+
+/// An exclusive lock on the `.lock` file of a directory, which keeps other processes from writing to it for as long
+/// as the lock is held.
+#[derive(Debug)]
+pub struct DirectoryLock {
+    _file: File,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DirectoryLockError {
+    AlreadyLocked,
+    UnableToLock(String),
+}
+
+impl DirectoryLock {
+    pub fn acquire(root: &Path) -> Result<Self, DirectoryLockError> {
+        let file: File = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(false)
+            .open(root.join(LOCK_FILE))
+            .map_err(|e| DirectoryLockError::UnableToLock(e.to_string()))?;
+
+        file.try_lock().map_err(|e| match e {
+            TryLockError::WouldBlock => DirectoryLockError::AlreadyLocked,
+            TryLockError::Error(e) => DirectoryLockError::UnableToLock(e.to_string()),
+        })?;
+
+        Ok(Self { _file: file })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DirectoryLock, DirectoryLockError};
+
+    #[test]
+    fn acquire_should_refuse_a_directory_already_locked_until_the_lock_is_dropped() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        let lock: DirectoryLock = DirectoryLock::acquire(directory.path()).unwrap();
+
+        let result: Result<DirectoryLock, DirectoryLockError> = DirectoryLock::acquire(directory.path());
+
+        assert!(
+            matches!(result, Err(DirectoryLockError::AlreadyLocked)),
+            "Expected AlreadyLocked, but got {:?}",
+            result
+        );
+
+        drop(lock);
+        assert!(DirectoryLock::acquire(directory.path()).is_ok());
+    }
+}
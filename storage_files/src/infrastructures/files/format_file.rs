@@ -0,0 +1,72 @@
+use std::{fs, io, path::Path};
+
+use serde_json::{Value, json};
+use storage_migrations::infrastructures::migrations::{Migration, MigrationRegistry};
+
+use crate::infrastructures::files::write_atomically;
+
+/// The file recording which version of its format a directory was written with.
+pub const FORMAT_FILE: &str = "format.json";
+
+/// The migrations of a format recorded in `format.json`, starting with the step that records it for directories
+/// written before the file existed.
+pub fn format_migrations(current_version: u32) -> MigrationRegistry {
+    MigrationRegistry::new(current_version).with(Migration::new(0, "Record the format version in format.json", |_| Ok(())))
+}
+
+/// Reads the version from `format.json`. Without it, a directory already holding data, as told by `holds_data`,
+/// predates versioning, while an empty one has no version yet.
+pub fn read_format_version(root: &Path, holds_data: impl FnOnce() -> bool) -> io::Result<Option<u32>> {
+    match fs::read(root.join(FORMAT_FILE)) {
+        Ok(contents) => serde_json::from_slice::<Value>(&contents)
+            .ok()
+            .and_then(|format| format["format_version"].as_u64())
+            .and_then(|version| u32::try_from(version).ok())
+            .map(Some)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing format_version")),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(holds_data().then_some(0)),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn write_format_version(root: &Path, version: u32) -> io::Result<()> {
+    let mut contents: Vec<u8> = serde_json::to_vec_pretty(&json!({ "format_version": version }))?;
+    contents.push(b'\n');
+
+    write_atomically(root, FORMAT_FILE, &contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{FORMAT_FILE, read_format_version, write_format_version};
+
+    #[test]
+    fn read_format_version_should_tell_unversioned_data_from_an_empty_directory() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+
+        let result: Vec<Option<u32>> = vec![
+            read_format_version(directory.path(), || false).unwrap(),
+            read_format_version(directory.path(), || true).unwrap(),
+        ];
+        let expected: Vec<Option<u32>> = vec![None, Some(0)];
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[test]
+    fn read_format_version_should_return_the_written_version() {
+        let directory: tempfile::TempDir = tempfile::tempdir().unwrap();
+        write_format_version(directory.path(), 3).unwrap();
+
+        let result: Option<u32> = read_format_version(directory.path(), || false).unwrap();
+        let expected: Option<u32> = Some(3);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(
+            fs::read_to_string(directory.path().join(FORMAT_FILE)).unwrap(),
+            "{\n  \"format_version\": 3\n}\n"
+        );
+    }
+}
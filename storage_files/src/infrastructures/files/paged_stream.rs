@@ -0,0 +1,67 @@
+use futures::{
+    StreamExt, future,
+    stream::{self, BoxStream},
+};
+
+/// How many documents [`paged_stream`] asks for at a time.
+pub const PAGE_SIZE: usize = 100;
+
+/// Streams documents a page at a time. `page` returns up to `PAGE_SIZE` of them, keyed and in key order, starting after
+/// the given key; a document it cannot resolve comes as `None` and is skipped. The next page starts after the last key
+/// of the previous one, so documents written while streaming are picked up as long as they sort after it.
+pub fn paged_stream<'a, T, E>(
+    page: impl Fn(Option<&str>, usize) -> Result<Vec<(String, Option<T>)>, E> + Send + Sync + 'a,
+) -> BoxStream<'a, Result<T, E>>
+where
+    T: Send + 'a,
+    E: Send + 'a,
+{
+    stream::unfold(Some(None::<String>), move |cursor: Option<Option<String>>| {
+        let step = cursor.map(|cursor| match page(cursor.as_deref(), PAGE_SIZE) {
+            Ok(rows) => {
+                let next: Option<Option<String>> = match rows.last() {
+                    Some((key, _)) if rows.len() == PAGE_SIZE => Some(Some(key.clone())),
+                    _ => None,
+                };
+
+                (
+                    rows.into_iter()
+                        .filter_map(|(_, value)| value.map(Ok))
+                        .collect::<Vec<Result<T, E>>>(),
+                    next,
+                )
+            }
+            Err(e) => (vec![Err(e)], None),
+        });
+
+        future::ready(step)
+    })
+    .flat_map(stream::iter)
+    .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use super::paged_stream;
+
+    #[tokio::test]
+    async fn paged_stream_should_go_through_every_page_and_skip_unresolved_documents() {
+        let keys: Vec<String> = (0..250).map(|i| format!("{:03}", i)).collect();
+
+        let result: Vec<Result<String, ()>> = paged_stream(|after: Option<&str>, limit: usize| {
+            Ok(keys
+                .iter()
+                .filter(|key| after.is_none_or(|after| key.as_str() > after))
+                .take(limit)
+                .map(|key| (key.clone(), (key != "100").then(|| key.clone())))
+                .collect())
+        })
+        .collect()
+        .await;
+        let expected: Vec<Result<String, ()>> = keys.iter().filter(|key| *key != "100").cloned().map(Ok).collect();
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+}
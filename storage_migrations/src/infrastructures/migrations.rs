@@ -0,0 +1,6 @@
+mod migration_registry;
+
+pub use migration_registry::Migration;
+pub use migration_registry::MigrationError;
+pub use migration_registry::MigrationPlan;
+pub use migration_registry::MigrationRegistry;
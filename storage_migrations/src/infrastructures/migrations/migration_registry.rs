@@ -0,0 +1,193 @@
+use serde_json::Value;
+
+/// A single upgrade of a persisted document from `from_version` to the next version.
+///
+/// Stores spread over several files rewrite them one by one, so a step has to leave already upgraded data as it is
+/// in case a migration interrupted halfway gets run again.
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub from_version: u32,
+    pub description: &'static str,
+    step: fn(&mut Value) -> Result<(), String>,
+}
+
+/// Upgrades documents of one persisted format step by step, from whichever version they were written with up to
+/// the current one.
+#[derive(Debug, Clone)]
+pub struct MigrationRegistry {
+    current_version: u32,
+    migrations: Vec<Migration>,
+}
+
+/// What upgrading a document from `from_version` involves, one description per step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MigrationPlan {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub steps: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MigrationError {
+    NewerThanSupported { version: u32, supported: u32 },
+    MissingStep(u32),
+    StepFailed { from_version: u32, reason: String },
+}
+
+impl Migration {
+    pub const fn new(from_version: u32, description: &'static str, step: fn(&mut Value) -> Result<(), String>) -> Self {
+        Self {
+            from_version,
+            description,
+            step,
+        }
+    }
+}
+
+impl MigrationRegistry {
+    pub fn new(current_version: u32) -> Self {
+        Self {
+            current_version,
+            migrations: Vec::new(),
+        }
+    }
+
+    pub fn with(mut self, migration: Migration) -> Self {
+        self.migrations.push(migration);
+        self.migrations.sort_by_key(|migration| migration.from_version);
+        self
+    }
+
+    pub fn current_version(&self) -> u32 {
+        self.current_version
+    }
+
+    pub fn plan(&self, from_version: u32) -> Result<MigrationPlan, MigrationError> {
+        let steps: Vec<&Migration> = self.steps(from_version)?;
+
+        Ok(MigrationPlan {
+            from_version,
+            to_version: self.current_version,
+            steps: steps.iter().map(|migration| migration.description.to_owned()).collect(),
+        })
+    }
+
+    /// Applies every step from `from_version` onwards to `document`. Nothing is applied when a step is missing.
+    pub fn upgrade(&self, document: &mut Value, from_version: u32) -> Result<MigrationPlan, MigrationError> {
+        for migration in self.steps(from_version)? {
+            (migration.step)(document).map_err(|reason| MigrationError::StepFailed {
+                from_version: migration.from_version,
+                reason,
+            })?;
+        }
+
+        self.plan(from_version)
+    }
+
+    fn steps(&self, from_version: u32) -> Result<Vec<&Migration>, MigrationError> {
+        if from_version > self.current_version {
+            return Err(MigrationError::NewerThanSupported {
+                version: from_version,
+                supported: self.current_version,
+            });
+        }
+
+        (from_version..self.current_version)
+            .map(|version| {
+                self.migrations
+                    .iter()
+                    .find(|migration| migration.from_version == version)
+                    .ok_or(MigrationError::MissingStep(version))
+            })
+            .collect()
+    }
+}
+
+impl MigrationPlan {
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::{Migration, MigrationError, MigrationPlan, MigrationRegistry};
+
+    fn given_registry() -> MigrationRegistry {
+        MigrationRegistry::new(3)
+            .with(Migration::new(2, "Rename brand to brand_name", |document| {
+                let brand: Value = document.as_object_mut().and_then(|o| o.remove("brand")).ok_or("missing brand")?;
+                document["brand_name"] = brand;
+                Ok(())
+            }))
+            .with(Migration::new(0, "Add tags", |document| {
+                document["tags"] = json!([]);
+                Ok(())
+            }))
+            .with(Migration::new(1, "Add discount", |document| {
+                document["discount"] = json!(0.0);
+                Ok(())
+            }))
+    }
+
+    #[test]
+    fn upgrade_should_apply_every_step_in_order() {
+        let mut document: Value = json!({ "brand": "Nestlé" });
+
+        let result: Result<MigrationPlan, MigrationError> = given_registry().upgrade(&mut document, 0);
+        let expected: Result<MigrationPlan, MigrationError> = Ok(MigrationPlan {
+            from_version: 0,
+            to_version: 3,
+            steps: vec![
+                "Add tags".to_owned(),
+                "Add discount".to_owned(),
+                "Rename brand to brand_name".to_owned(),
+            ],
+        });
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(document, json!({ "brand_name": "Nestlé", "tags": [], "discount": 0.0 }));
+    }
+
+    #[test]
+    fn upgrade_from_current_version_should_do_nothing() {
+        let mut document: Value = json!({ "brand_name": "Nestlé" });
+
+        let result: Result<bool, MigrationError> = given_registry().upgrade(&mut document, 3).map(|plan| plan.is_empty());
+
+        assert_eq!(
+            result,
+            Ok(true),
+            "Expected {:?}, but got {:?}",
+            Ok::<bool, MigrationError>(true),
+            result
+        );
+        assert_eq!(document, json!({ "brand_name": "Nestlé" }));
+    }
+
+    #[test]
+    fn upgrade_should_refuse_newer_versions_and_gaps() {
+        let registry: MigrationRegistry = MigrationRegistry::new(2).with(Migration::new(1, "Add discount", |_| Ok(())));
+
+        assert_eq!(
+            registry.plan(3),
+            Err(MigrationError::NewerThanSupported { version: 3, supported: 2 })
+        );
+        assert_eq!(registry.upgrade(&mut json!({}), 0), Err(MigrationError::MissingStep(0)));
+    }
+
+    #[test]
+    fn upgrade_should_report_the_failing_step() {
+        let mut document: Value = json!({});
+
+        let result: Result<MigrationPlan, MigrationError> = given_registry().upgrade(&mut document, 2);
+        let expected: Result<MigrationPlan, MigrationError> = Err(MigrationError::StepFailed {
+            from_version: 2,
+            reason: "missing brand".to_owned(),
+        });
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+}
@@ -0,0 +1,27 @@
+mod change_set;
+mod sync_engine;
+mod sync_journal;
+mod sync_report;
+mod sync_transport;
+mod vector_clock;
+
+pub use change_set::Change;
+pub use change_set::ChangeSet;
+pub use change_set::EntityKey;
+pub use change_set::EntityKind;
+pub use change_set::EntityState;
+pub use change_set::ItemState;
+pub use change_set::UnitState;
+pub use sync_engine::SyncEngine;
+pub use sync_engine::SyncError;
+pub use sync_engine::SyncRepositories;
+pub use sync_journal::SyncJournal;
+pub use sync_report::ConflictResolution;
+pub use sync_report::SyncConflict;
+pub use sync_report::SyncRejection;
+pub use sync_report::SyncReport;
+pub use sync_transport::exchange;
+pub use sync_transport::read_change_set;
+pub use sync_transport::write_change_set;
+pub use vector_clock::ClockOrdering;
+pub use vector_clock::VectorClock;
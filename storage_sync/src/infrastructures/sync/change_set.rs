@@ -0,0 +1,197 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid_b64::UuidB64;
+
+use expense_tracking::domain::entities::{Brand, Category, Item, Product, Store, Transaction, Unit};
+
+use crate::infrastructures::sync::VectorClock;
+
+/// Kinds of synchronized entities, ordered so that an entity only refers to kinds coming before its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityKind {
+    Brand,
+    Category,
+    Store,
+    Product,
+    Transaction,
+}
+
+/// Identifies an entity across replicas: brands by name, every other kind by id.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct EntityKey {
+    pub kind: EntityKind,
+    pub key: String,
+}
+
+/// The synchronized content of an entity. Versions are left out since each replica numbers its own writes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum EntityState {
+    Brand {
+        name: String,
+    },
+    Category {
+        id: UuidB64,
+        name: String,
+    },
+    Store {
+        id: UuidB64,
+        name: String,
+    },
+    Product {
+        id: UuidB64,
+        name: String,
+        brand: String,
+        category: UuidB64,
+    },
+    Transaction {
+        id: UuidB64,
+        store: UuidB64,
+        datetime: DateTime<Utc>,
+        items: Vec<ItemState>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ItemState {
+    pub id: UuidB64,
+    pub product: UuidB64,
+    pub unit: UnitState,
+    pub unitary_price: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "amount", rename_all = "snake_case")]
+pub enum UnitState {
+    None,
+    Quantity(f64),
+    Kilograms(f64),
+    Liters(f64),
+}
+
+/// The latest known change of an entity; a change without state records its deletion.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Change {
+    pub key: EntityKey,
+    pub clock: VectorClock,
+    pub modified_at: DateTime<Utc>,
+    pub modified_by: String,
+    pub state: Option<EntityState>,
+}
+
+/// Everything a replica knows about, as sent to its peers. Applying the same change set twice changes nothing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangeSet {
+    pub replica: String,
+    pub changes: Vec<Change>,
+}
+
+impl EntityKey {
+    pub fn new(kind: EntityKind, key: impl Into<String>) -> Self {
+        Self { kind, key: key.into() }
+    }
+}
+
+impl fmt::Display for EntityKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} {}", self.kind, self.key)
+    }
+}
+
+impl EntityState {
+    pub fn key(&self) -> EntityKey {
+        match self {
+            EntityState::Brand { name } => EntityKey::new(EntityKind::Brand, name.clone()),
+            EntityState::Category { id, .. } => EntityKey::new(EntityKind::Category, id.to_string()),
+            EntityState::Store { id, .. } => EntityKey::new(EntityKind::Store, id.to_string()),
+            EntityState::Product { id, .. } => EntityKey::new(EntityKind::Product, id.to_string()),
+            EntityState::Transaction { id, .. } => EntityKey::new(EntityKind::Transaction, id.to_string()),
+        }
+    }
+}
+
+impl From<&Brand> for EntityState {
+    fn from(brand: &Brand) -> Self {
+        EntityState::Brand { name: brand.name.clone() }
+    }
+}
+
+impl From<&Category> for EntityState {
+    fn from(category: &Category) -> Self {
+        EntityState::Category {
+            id: category.id,
+            name: category.name.clone(),
+        }
+    }
+}
+
+impl From<&Store> for EntityState {
+    fn from(store: &Store) -> Self {
+        EntityState::Store {
+            id: store.id,
+            name: store.name.clone(),
+        }
+    }
+}
+
+impl From<&Product> for EntityState {
+    fn from(product: &Product) -> Self {
+        EntityState::Product {
+            id: product.id,
+            name: product.name.clone(),
+            brand: product.brand.name.clone(),
+            category: product.category.id,
+        }
+    }
+}
+
+impl From<&Transaction> for EntityState {
+    fn from(transaction: &Transaction) -> Self {
+        let mut items: Vec<ItemState> = transaction.items.iter().map(ItemState::from).collect();
+        // Backends may list items in any order, which must not read as a change.
+        items.sort_by_key(|item| item.id.to_string());
+
+        EntityState::Transaction {
+            id: transaction.id,
+            store: transaction.store.id,
+            datetime: transaction.datetime,
+            items,
+        }
+    }
+}
+
+impl From<&Item> for ItemState {
+    fn from(item: &Item) -> Self {
+        Self {
+            id: item.id(),
+            product: item.product().id,
+            unit: UnitState::from(item.unit()),
+            unitary_price: item.unitary_price(),
+        }
+    }
+}
+
+impl From<&Unit> for UnitState {
+    fn from(unit: &Unit) -> Self {
+        match unit {
+            Unit::None => UnitState::None,
+            Unit::Quantity(amount) => UnitState::Quantity(*amount),
+            Unit::Kilograms(amount) => UnitState::Kilograms(*amount),
+            Unit::Liters(amount) => UnitState::Liters(*amount),
+        }
+    }
+}
+
+impl From<&UnitState> for Unit {
+    fn from(unit: &UnitState) -> Self {
+        match unit {
+            UnitState::None => Unit::None,
+            UnitState::Quantity(amount) => Unit::Quantity(*amount),
+            UnitState::Kilograms(amount) => Unit::Kilograms(*amount),
+            UnitState::Liters(amount) => Unit::Liters(*amount),
+        }
+    }
+}
@@ -0,0 +1,610 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use uuid_b64::UuidB64;
+
+use expense_tracking::domain::{
+    entities::{Brand, Category, Item, Product, Store, Transaction, Unit},
+    repositories::{BrandRepository, CategoryRepository, DeleteBehaviour, ProductRepository, StoreRepository, TransactionRepository},
+};
+
+use crate::infrastructures::sync::{
+    Change, ChangeSet, ClockOrdering, ConflictResolution, EntityKey, EntityKind, EntityState, SyncConflict, SyncJournal, SyncRejection,
+    SyncReport,
+};
+
+/// The repositories of the storage instance being synchronized.
+pub struct SyncRepositories {
+    pub brands: Box<dyn BrandRepository>,
+    pub categories: Box<dyn CategoryRepository + Send + Sync>,
+    pub stores: Box<dyn StoreRepository + Send + Sync>,
+    pub products: Box<dyn ProductRepository + Send + Sync>,
+    pub transactions: Box<dyn TransactionRepository + Send + Sync>,
+}
+
+/// Exchanges change sets between replicas of the same data so that edits made offline on each of them converge.
+///
+/// Storages do not track changes themselves: the engine compares what the storage holds with its journal, and
+/// every difference becomes a change of the local replica stamped with the time it was noticed.
+pub struct SyncEngine {
+    repositories: SyncRepositories,
+    journal: SyncJournal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncError {
+    UnableToReadStorage(String),
+    UnableToAccessJournal(String),
+    UnableToExchange(String),
+}
+
+/// The entities the storage currently holds, with the version each one is stored at.
+#[derive(Debug, Default)]
+struct LocalEntities {
+    entities: BTreeMap<EntityKey, (EntityState, u64)>,
+}
+
+impl SyncEngine {
+    pub fn new(repositories: SyncRepositories, journal: SyncJournal) -> Self {
+        Self { repositories, journal }
+    }
+
+    pub fn journal(&self) -> &SyncJournal {
+        &self.journal
+    }
+
+    /// Records what was created, edited or deleted in the storage since the last synchronization.
+    pub async fn record_local_changes(&mut self) -> Result<(), SyncError> {
+        let local: LocalEntities = self.read_local().await?;
+        self.record(&local, Utc::now());
+
+        self.journal.save()
+    }
+
+    pub async fn export(&mut self) -> Result<ChangeSet, SyncError> {
+        self.record_local_changes().await?;
+
+        Ok(ChangeSet {
+            replica: self.journal.replica().to_owned(),
+            changes: self.journal.changes().cloned().collect(),
+        })
+    }
+
+    /// Merges the changes of a peer into the storage. Entities are created parent first and deleted child first,
+    /// and a change the storage refuses is reported without stopping the others.
+    pub async fn import(&mut self, change_set: &ChangeSet) -> Result<SyncReport, SyncError> {
+        let mut local: LocalEntities = self.read_local().await?;
+        self.record(&local, Utc::now());
+
+        let mut report: SyncReport = SyncReport::default();
+        let mut accepted: Vec<Change> = change_set
+            .changes
+            .iter()
+            .filter_map(|remote| self.merge(remote, &mut report))
+            .collect();
+        accepted.sort_by(|a, b| match (&a.state, &b.state) {
+            (Some(_), Some(_)) => a.key.cmp(&b.key),
+            (None, None) => b.key.cmp(&a.key),
+            (left, _) => left.is_none().cmp(&b.state.is_none()),
+        });
+
+        for change in accepted {
+            match self.apply(&change, &mut local).await {
+                Ok(()) => {
+                    report.applied.push(change.key.clone());
+                    self.journal.record(change);
+                }
+                Err(reason) => report.rejected.push(SyncRejection { key: change.key, reason }),
+            }
+        }
+
+        self.journal.save()?;
+        Ok(report)
+    }
+
+    async fn read_local(&self) -> Result<LocalEntities, SyncError> {
+        let mut local: LocalEntities = LocalEntities::default();
+
+        for brand in self.repositories.brands.retrieve_all().await.map_err(to_read_error)? {
+            local.insert(EntityState::from(&brand), brand.version);
+        }
+        for category in self.repositories.categories.retrieve_all().await.map_err(to_read_error)? {
+            local.insert(EntityState::from(&category), category.version);
+        }
+        for store in self.repositories.stores.retrieve_all().await.map_err(to_read_error)? {
+            local.insert(EntityState::from(&store), store.version);
+        }
+        for product in self.repositories.products.retrieve_all().await.map_err(to_read_error)? {
+            local.insert(EntityState::from(&product), product.version);
+        }
+        for transaction in self.repositories.transactions.retrieve_all().await.map_err(to_read_error)? {
+            local.insert(EntityState::from(&transaction), transaction.version);
+        }
+
+        Ok(local)
+    }
+
+    fn record(&mut self, local: &LocalEntities, now: DateTime<Utc>) {
+        let replica: String = self.journal.replica().to_owned();
+
+        let edited = local
+            .entities
+            .iter()
+            .filter(|(key, (state, _))| self.journal.get(key).and_then(|change| change.state.as_ref()) != Some(state))
+            .map(|(key, (state, _))| (key.clone(), Some(state.clone())));
+        let deleted = self
+            .journal
+            .changes()
+            .filter(|change| change.state.is_some() && !local.entities.contains_key(&change.key))
+            .map(|change| (change.key.clone(), None));
+        let changed: Vec<(EntityKey, Option<EntityState>)> = edited.chain(deleted).collect();
+
+        for (key, state) in changed {
+            let mut clock = self.journal.get(&key).map(|change| change.clock.clone()).unwrap_or_default();
+            clock.increment(&replica);
+
+            self.journal.record(Change {
+                key,
+                clock,
+                modified_at: now,
+                modified_by: replica.clone(),
+                state,
+            });
+        }
+    }
+
+    /// The change to apply locally for `remote`, if any.
+    fn merge(&mut self, remote: &Change, report: &mut SyncReport) -> Option<Change> {
+        let Some(local) = self.journal.get(&remote.key).cloned() else {
+            return Some(remote.clone());
+        };
+
+        match remote.clock.compare(&local.clock) {
+            ClockOrdering::Before | ClockOrdering::Equal => None,
+            ClockOrdering::After => Some(remote.clone()),
+            ClockOrdering::Concurrent => {
+                let clock = remote.clock.merged(&local.clock);
+
+                if remote.state == local.state {
+                    self.journal.record(Change { clock, ..local });
+                    return None;
+                }
+
+                let remote_wins: bool = (remote.modified_at, &remote.modified_by) > (local.modified_at, &local.modified_by);
+                report.conflicts.push(SyncConflict {
+                    key: remote.key.clone(),
+                    local: local.state.clone(),
+                    remote: remote.state.clone(),
+                    resolution: match remote_wins {
+                        true => ConflictResolution::TookRemote,
+                        false => ConflictResolution::KeptLocal,
+                    },
+                });
+
+                match remote_wins {
+                    true => Some(Change { clock, ..remote.clone() }),
+                    false => {
+                        self.journal.record(Change { clock, ..local });
+                        None
+                    }
+                }
+            }
+        }
+    }
+
+    async fn apply(&mut self, change: &Change, local: &mut LocalEntities) -> Result<(), String> {
+        match &change.state {
+            Some(state) if local.entities.get(&change.key).map(|(current, _)| current) == Some(state) => Ok(()),
+            Some(state) => {
+                self.upsert(state, local).await?;
+                local.insert(state.clone(), local.version(&change.key));
+                Ok(())
+            }
+            None => {
+                self.remove(&change.key, local).await?;
+                local.entities.remove(&change.key);
+                Ok(())
+            }
+        }
+    }
+
+    async fn upsert(&mut self, state: &EntityState, local: &LocalEntities) -> Result<(), String> {
+        let version: u64 = local.version(&state.key());
+
+        match state {
+            EntityState::Brand { .. } if local.entities.contains_key(&state.key()) => Ok(()),
+            EntityState::Brand { name } => self
+                .repositories
+                .brands
+                .create(&local.brand(name))
+                .await
+                .map(|_| ())
+                .map_err(to_reason),
+            EntityState::Category { id, name } => self
+                .repositories
+                .categories
+                .create_or_update(&Category {
+                    version,
+                    ..Category::new(Some(*id), name.clone())
+                })
+                .await
+                .map(|_| ())
+                .map_err(to_reason),
+            EntityState::Store { id, name } => self
+                .repositories
+                .stores
+                .create_or_update(&Store {
+                    version,
+                    ..Store::new(Some(*id), name.clone())
+                })
+                .await
+                .map(|_| ())
+                .map_err(to_reason),
+            EntityState::Product { id, name, brand, category } => self
+                .repositories
+                .products
+                .create_or_update(&Product {
+                    version,
+                    ..Product::new(Some(*id), name.clone(), local.brand(brand), local.category(category))
+                })
+                .await
+                .map(|_| ())
+                .map_err(to_reason),
+            EntityState::Transaction {
+                id,
+                store,
+                datetime,
+                items,
+            } => {
+                let items: Vec<Item> = items
+                    .iter()
+                    .map(|item| {
+                        Item::new(
+                            Some(item.id),
+                            local.product(&item.product),
+                            Unit::from(&item.unit),
+                            item.unitary_price,
+                        )
+                    })
+                    .collect();
+
+                self.repositories
+                    .transactions
+                    .create_or_update(&Transaction {
+                        version,
+                        ..Transaction::new(Some(*id), items, local.store(store), *datetime)
+                    })
+                    .await
+                    .map(|_| ())
+                    .map_err(to_reason)
+            }
+        }
+    }
+
+    async fn remove(&mut self, key: &EntityKey, local: &LocalEntities) -> Result<(), String> {
+        let Some((state, _)) = local.entities.get(key) else {
+            return Ok(());
+        };
+
+        match state {
+            EntityState::Brand { name } => self
+                .repositories
+                .brands
+                .delete(&local.brand(name), DeleteBehaviour::Restrict)
+                .await
+                .map(|_| ())
+                .map_err(to_reason),
+            EntityState::Category { id, .. } => self
+                .repositories
+                .categories
+                .delete(id, DeleteBehaviour::Restrict)
+                .await
+                .map(|_| ())
+                .map_err(to_reason),
+            EntityState::Store { id, .. } => self
+                .repositories
+                .stores
+                .delete(id, DeleteBehaviour::Restrict)
+                .await
+                .map(|_| ())
+                .map_err(to_reason),
+            EntityState::Product { id, .. } => self
+                .repositories
+                .products
+                .delete(id, DeleteBehaviour::Restrict)
+                .await
+                .map(|_| ())
+                .map_err(to_reason),
+            EntityState::Transaction { id, .. } => self.repositories.transactions.delete(id).await.map(|_| ()).map_err(to_reason),
+        }
+    }
+}
+
+impl LocalEntities {
+    fn insert(&mut self, state: EntityState, version: u64) {
+        self.entities.insert(state.key(), (state, version));
+    }
+
+    fn version(&self, key: &EntityKey) -> u64 {
+        self.entities.get(key).map(|(_, version)| *version).unwrap_or_default()
+    }
+
+    // Entities referred to by another one. A missing reference is left for the repository to report.
+
+    fn brand(&self, name: &str) -> Brand {
+        Brand {
+            version: self.version(&EntityKey::new(EntityKind::Brand, name)),
+            ..Brand::new(name.to_owned())
+        }
+    }
+
+    fn category(&self, id: &UuidB64) -> Category {
+        match self.entities.get(&EntityKey::new(EntityKind::Category, id.to_string())) {
+            Some((EntityState::Category { name, .. }, version)) => Category {
+                version: *version,
+                ..Category::new(Some(*id), name.clone())
+            },
+            _ => Category::new(Some(*id), String::new()),
+        }
+    }
+
+    fn store(&self, id: &UuidB64) -> Store {
+        match self.entities.get(&EntityKey::new(EntityKind::Store, id.to_string())) {
+            Some((EntityState::Store { name, .. }, version)) => Store {
+                version: *version,
+                ..Store::new(Some(*id), name.clone())
+            },
+            _ => Store::new(Some(*id), String::new()),
+        }
+    }
+
+    fn product(&self, id: &UuidB64) -> Product {
+        match self.entities.get(&EntityKey::new(EntityKind::Product, id.to_string())) {
+            Some((EntityState::Product { name, brand, category, .. }, version)) => Product {
+                version: *version,
+                ..Product::new(Some(*id), name.clone(), self.brand(brand), self.category(category))
+            },
+            _ => Product::new(Some(*id), String::new(), Brand::default(), Category::default()),
+        }
+    }
+}
+
+fn to_read_error(error: impl std::fmt::Debug) -> SyncError {
+    SyncError::UnableToReadStorage(format!("{:?}", error))
+}
+
+fn to_reason(error: impl std::fmt::Debug) -> String {
+    format!("{:?}", error)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::sync::RwLock;
+
+    use expense_tracking::domain::{
+        entities::{Brand, Category, Item, Product, Store, Transaction, Unit},
+        repositories::{BrandRepository, CategoryRepository, DeleteBehaviour, ProductRepository, StoreRepository, TransactionRepository},
+    };
+    use in_memory_storage::{
+        adapters::repositories::{
+            BrandRepositoryInMemoryImpl, CategoryRepositoryInMemoryImpl, ProductRepositoryInMemoryImpl, StoreRepositoryInMemoryImpl,
+            TransactionRepositoryInMemoryImpl,
+        },
+        infrastructures::data_sources::InMemoryCache,
+    };
+
+    use super::{SyncEngine, SyncRepositories};
+    use crate::infrastructures::sync::{
+        ChangeSet, ConflictResolution, EntityKey, EntityKind, EntityState, SyncConflict, SyncJournal, SyncReport,
+    };
+
+    struct Replica {
+        cache: Arc<RwLock<InMemoryCache>>,
+        engine: SyncEngine,
+    }
+
+    impl Replica {
+        fn new(name: &str) -> Self {
+            let cache: Arc<RwLock<InMemoryCache>> = Arc::new(RwLock::new(InMemoryCache::new()));
+            let repositories: SyncRepositories = SyncRepositories {
+                brands: Box::new(BrandRepositoryInMemoryImpl::new(Arc::clone(&cache))),
+                categories: Box::new(CategoryRepositoryInMemoryImpl::new(Arc::clone(&cache))),
+                stores: Box::new(StoreRepositoryInMemoryImpl::new(Arc::clone(&cache))),
+                products: Box::new(ProductRepositoryInMemoryImpl::new(Arc::clone(&cache))),
+                transactions: Box::new(TransactionRepositoryInMemoryImpl::new(Arc::clone(&cache))),
+            };
+
+            Self {
+                engine: SyncEngine::new(repositories, SyncJournal::new(name)),
+                cache,
+            }
+        }
+
+        fn brands(&self) -> BrandRepositoryInMemoryImpl {
+            BrandRepositoryInMemoryImpl::new(Arc::clone(&self.cache))
+        }
+
+        fn categories(&self) -> CategoryRepositoryInMemoryImpl {
+            CategoryRepositoryInMemoryImpl::new(Arc::clone(&self.cache))
+        }
+
+        fn stores(&self) -> StoreRepositoryInMemoryImpl {
+            StoreRepositoryInMemoryImpl::new(Arc::clone(&self.cache))
+        }
+
+        fn products(&self) -> ProductRepositoryInMemoryImpl {
+            ProductRepositoryInMemoryImpl::new(Arc::clone(&self.cache))
+        }
+
+        fn transactions(&self) -> TransactionRepositoryInMemoryImpl {
+            TransactionRepositoryInMemoryImpl::new(Arc::clone(&self.cache))
+        }
+
+        /// Everything the replica holds, versions left out since each replica numbers its own writes.
+        async fn contents(&self) -> Vec<EntityState> {
+            let mut contents: Vec<EntityState> = Vec::new();
+            contents.extend(self.brands().retrieve_all().await.unwrap().iter().map(EntityState::from));
+            contents.extend(self.categories().retrieve_all().await.unwrap().iter().map(EntityState::from));
+            contents.extend(self.stores().retrieve_all().await.unwrap().iter().map(EntityState::from));
+            contents.extend(self.products().retrieve_all().await.unwrap().iter().map(EntityState::from));
+            contents.extend(self.transactions().retrieve_all().await.unwrap().iter().map(EntityState::from));
+            contents
+        }
+    }
+
+    /// One round trip, as done through files: `left` hands its changes to `right`, which answers with its own.
+    async fn synchronize(left: &mut Replica, right: &mut Replica) -> (SyncReport, SyncReport) {
+        let outgoing: ChangeSet = left.engine.export().await.unwrap();
+        let right_report: SyncReport = right.engine.import(&outgoing).await.unwrap();
+        let answer: ChangeSet = right.engine.export().await.unwrap();
+        let left_report: SyncReport = left.engine.import(&answer).await.unwrap();
+
+        (left_report, right_report)
+    }
+
+    async fn given_synchronized_replicas() -> (Replica, Replica, Product) {
+        let mut laptop: Replica = Replica::new("laptop");
+        let mut phone: Replica = Replica::new("phone");
+        let product: Product = Product::new(
+            None,
+            "Milk".to_owned(),
+            Brand::new("Nestlé".to_owned()),
+            Category::new(None, "Dairy".to_owned()),
+        );
+
+        laptop.brands().create(&product.brand).await.unwrap();
+        laptop.categories().create_or_update(&product.category).await.unwrap();
+        laptop.products().create_or_update(&product).await.unwrap();
+        synchronize(&mut laptop, &mut phone).await;
+
+        (laptop, phone, product)
+    }
+
+    #[tokio::test]
+    async fn offline_edits_on_both_sides_should_converge() {
+        let (mut laptop, mut phone, product) = given_synchronized_replicas().await;
+        let store: Store = Store::new(None, "Migros".to_owned());
+        let transaction: Transaction = Transaction::new(
+            None,
+            vec![Item::new(None, product.clone(), Unit::Liters(1.0), 1.95)],
+            store.clone(),
+            chrono::Utc::now(),
+        );
+
+        laptop.brands().create(&Brand::new("Danone".to_owned())).await.unwrap();
+        phone.stores().create_or_update(&store).await.unwrap();
+        phone.transactions().create_or_update(&transaction).await.unwrap();
+
+        let (laptop_report, phone_report) = synchronize(&mut laptop, &mut phone).await;
+
+        let result: Vec<EntityState> = laptop.contents().await;
+        let expected: Vec<EntityState> = phone.contents().await;
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(result.len(), 6);
+        assert_eq!(
+            laptop_report.applied,
+            vec![EntityState::from(&store).key(), EntityState::from(&transaction).key()]
+        );
+        assert_eq!(phone_report.applied, vec![EntityKey::new(EntityKind::Brand, "Danone")]);
+        assert!(laptop_report.conflicts.is_empty() && phone_report.conflicts.is_empty());
+    }
+
+    #[tokio::test]
+    async fn synchronizing_again_should_change_nothing() {
+        let (mut laptop, mut phone, _) = given_synchronized_replicas().await;
+
+        let result: (SyncReport, SyncReport) = synchronize(&mut laptop, &mut phone).await;
+        let expected: (SyncReport, SyncReport) = (SyncReport::default(), SyncReport::default());
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+    }
+
+    #[tokio::test]
+    async fn concurrent_edits_should_converge_on_the_latest_one_and_be_reported() {
+        let (mut laptop, mut phone, product) = given_synchronized_replicas().await;
+        let on_laptop: Category = Category {
+            name: "Dairy products".to_owned(),
+            ..product.category.clone()
+        };
+        let on_phone: Category = Category {
+            name: "Milk products".to_owned(),
+            ..product.category.clone()
+        };
+
+        laptop.categories().create_or_update(&on_laptop).await.unwrap();
+        laptop.engine.record_local_changes().await.unwrap();
+        phone.categories().create_or_update(&on_phone).await.unwrap();
+        phone.engine.record_local_changes().await.unwrap();
+
+        let (laptop_report, phone_report) = synchronize(&mut laptop, &mut phone).await;
+
+        let result: Vec<SyncConflict> = phone_report.conflicts;
+        let expected: Vec<SyncConflict> = vec![SyncConflict {
+            key: EntityState::from(&on_phone).key(),
+            local: Some(EntityState::from(&on_phone)),
+            remote: Some(EntityState::from(&on_laptop)),
+            resolution: ConflictResolution::KeptLocal,
+        }];
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(laptop_report.conflicts, vec![]);
+        assert_eq!(laptop.contents().await, phone.contents().await);
+        assert!(laptop.contents().await.contains(&EntityState::from(&on_phone)));
+    }
+
+    #[tokio::test]
+    async fn deletions_should_propagate_children_first() {
+        let (mut laptop, mut phone, product) = given_synchronized_replicas().await;
+
+        laptop.products().delete(&product.id, DeleteBehaviour::Restrict).await.unwrap();
+        laptop
+            .categories()
+            .delete(&product.category.id, DeleteBehaviour::Restrict)
+            .await
+            .unwrap();
+
+        let (_, phone_report) = synchronize(&mut laptop, &mut phone).await;
+
+        let result: Vec<EntityState> = phone.contents().await;
+        let expected: Vec<EntityState> = vec![EntityState::from(&product.brand)];
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(
+            phone_report.applied,
+            vec![EntityState::from(&product).key(), EntityState::from(&product.category).key()]
+        );
+    }
+
+    #[tokio::test]
+    async fn deleting_a_category_still_used_on_the_other_side_should_be_rejected() {
+        let (mut laptop, mut phone, product) = given_synchronized_replicas().await;
+        let butter: Product = Product::new(None, "Butter".to_owned(), product.brand.clone(), product.category.clone());
+
+        laptop.products().delete(&product.id, DeleteBehaviour::Restrict).await.unwrap();
+        laptop
+            .categories()
+            .delete(&product.category.id, DeleteBehaviour::Restrict)
+            .await
+            .unwrap();
+        phone.products().create_or_update(&butter).await.unwrap();
+
+        let (laptop_report, phone_report) = synchronize(&mut laptop, &mut phone).await;
+
+        let result: Vec<EntityKey> = phone_report.rejected.iter().map(|rejection| rejection.key.clone()).collect();
+        let expected: Vec<EntityKey> = vec![EntityState::from(&product.category).key()];
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(
+            laptop_report
+                .rejected
+                .iter()
+                .map(|rejection| rejection.key.clone())
+                .collect::<Vec<EntityKey>>(),
+            vec![EntityState::from(&butter).key()]
+        );
+        assert!(phone.contents().await.contains(&EntityState::from(&butter)));
+    }
+}
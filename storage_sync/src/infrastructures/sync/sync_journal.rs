@@ -0,0 +1,165 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::infrastructures::sync::{Change, EntityKey, SyncError};
+
+/// What a replica last knew about each entity, kept between synchronizations to tell local edits apart and to
+/// remember deletions.
+#[derive(Debug, Clone)]
+pub struct SyncJournal {
+    replica: String,
+    changes: BTreeMap<EntityKey, Change>,
+    path: Option<PathBuf>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JournalDocument {
+    replica: String,
+    changes: Vec<Change>,
+}
+
+impl SyncJournal {
+    /// A journal living in memory only, for replicas which never restart between synchronizations.
+    pub fn new(replica: impl Into<String>) -> Self {
+        Self {
+            replica: replica.into(),
+            changes: BTreeMap::new(),
+            path: None,
+        }
+    }
+
+    /// Loads the journal kept at `path`, starting an empty one for `replica` when there is none yet.
+    pub fn open(path: impl Into<PathBuf>, replica: impl Into<String>) -> Result<Self, SyncError> {
+        let path: PathBuf = path.into();
+        let replica: String = replica.into();
+
+        let contents: String = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                return Ok(Self {
+                    path: Some(path),
+                    ..Self::new(replica)
+                });
+            }
+            Err(e) => return Err(SyncError::UnableToAccessJournal(e.to_string())),
+        };
+        let document: JournalDocument = serde_json::from_str(&contents).map_err(|e| SyncError::UnableToAccessJournal(e.to_string()))?;
+
+        if document.replica != replica {
+            return Err(SyncError::UnableToAccessJournal(format!(
+                "journal belongs to replica {}",
+                document.replica
+            )));
+        }
+
+        Ok(Self {
+            replica,
+            changes: document.changes.into_iter().map(|change| (change.key.clone(), change)).collect(),
+            path: Some(path),
+        })
+    }
+
+    pub fn replica(&self) -> &str {
+        &self.replica
+    }
+
+    pub fn get(&self, key: &EntityKey) -> Option<&Change> {
+        self.changes.get(key)
+    }
+
+    /// Every known change, ordered by kind then key.
+    pub fn changes(&self) -> impl Iterator<Item = &Change> {
+        self.changes.values()
+    }
+
+    pub fn record(&mut self, change: Change) {
+        self.changes.insert(change.key.clone(), change);
+    }
+
+    pub fn save(&self) -> Result<(), SyncError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let document: JournalDocument = JournalDocument {
+            replica: self.replica.clone(),
+            changes: self.changes.values().cloned().collect(),
+        };
+        let contents: Vec<u8> = serde_json::to_vec(&document).map_err(|e| SyncError::UnableToAccessJournal(e.to_string()))?;
+
+        write_atomically(path, &contents).map_err(|e| SyncError::UnableToAccessJournal(e.to_string()))
+    }
+}
+
+pub(crate) fn write_atomically(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let file_name: String = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?
+        .to_string_lossy()
+        .into_owned();
+    let temporary_path: PathBuf = path.with_file_name(format!(".{}.tmp", file_name));
+
+    let mut temporary: File = File::create(&temporary_path)?;
+    temporary.write_all(contents)?;
+    temporary.sync_all()?;
+    drop(temporary);
+
+    fs::rename(&temporary_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::SyncJournal;
+    use crate::infrastructures::sync::{Change, EntityKey, EntityKind, EntityState, SyncError, VectorClock};
+
+    fn given_change(name: &str) -> Change {
+        let mut clock: VectorClock = VectorClock::new();
+        clock.increment("laptop");
+
+        Change {
+            key: EntityKey::new(EntityKind::Brand, name),
+            clock,
+            modified_at: Utc::now(),
+            modified_by: "laptop".to_owned(),
+            state: Some(EntityState::Brand { name: name.to_owned() }),
+        }
+    }
+
+    #[test]
+    fn save_then_open_should_restore_every_change() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("journal.json");
+        let mut journal: SyncJournal = SyncJournal::open(&path, "laptop").unwrap();
+        journal.record(given_change("Nestlé"));
+        journal.record(given_change("Migros"));
+        journal.save().unwrap();
+
+        let result: Vec<Change> = SyncJournal::open(&path, "laptop").unwrap().changes().cloned().collect();
+        let expected: Vec<Change> = journal.changes().cloned().collect();
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(result.len(), 2);
+    }
+
+    #[test]
+    fn open_should_refuse_the_journal_of_another_replica() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("journal.json");
+        SyncJournal::open(&path, "laptop").unwrap().save().unwrap();
+
+        let result: Result<SyncJournal, SyncError> = SyncJournal::open(&path, "phone");
+
+        assert!(
+            matches!(result, Err(SyncError::UnableToAccessJournal(_))),
+            "Expected UnableToAccessJournal, but got {:?}",
+            result
+        );
+    }
+}
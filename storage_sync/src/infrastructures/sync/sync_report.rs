@@ -0,0 +1,33 @@
+use crate::infrastructures::sync::{EntityKey, EntityState};
+
+/// What importing a peer's change set did to the local replica.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SyncReport {
+    pub applied: Vec<EntityKey>,
+    pub conflicts: Vec<SyncConflict>,
+    pub rejected: Vec<SyncRejection>,
+}
+
+/// An entity both replicas changed without knowing about each other. The most recent change wins, the replica
+/// name deciding between changes made at the same instant, so both replicas settle on the same side.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncConflict {
+    pub key: EntityKey,
+    pub local: Option<EntityState>,
+    pub remote: Option<EntityState>,
+    pub resolution: ConflictResolution,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictResolution {
+    KeptLocal,
+    TookRemote,
+}
+
+/// A remote change the local storage refused, such as deleting a category a local product still refers to. The
+/// local state is kept and the change is offered again on the next synchronization.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncRejection {
+    pub key: EntityKey,
+    pub reason: String,
+}
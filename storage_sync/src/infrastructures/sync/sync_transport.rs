@@ -0,0 +1,124 @@
+use std::{fs, path::Path};
+
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+
+use crate::infrastructures::sync::{ChangeSet, SyncEngine, SyncError, SyncReport, sync_journal::write_atomically};
+
+/// Writes a change set for a peer to pick up, e.g. through a shared folder or a USB stick.
+pub fn write_change_set(path: &Path, change_set: &ChangeSet) -> Result<(), SyncError> {
+    let contents: Vec<u8> = serde_json::to_vec(change_set).map_err(|e| SyncError::UnableToExchange(e.to_string()))?;
+
+    write_atomically(path, &contents).map_err(|e| SyncError::UnableToExchange(e.to_string()))
+}
+
+pub fn read_change_set(path: &Path) -> Result<ChangeSet, SyncError> {
+    let contents: String = fs::read_to_string(path).map_err(|e| SyncError::UnableToExchange(e.to_string()))?;
+
+    serde_json::from_str(&contents).map_err(|e| SyncError::UnableToExchange(e.to_string()))
+}
+
+/// Synchronizes with the peer at the other end of `stream`, a local socket for instance. Both ends call it: each
+/// sends its change set as one JSON line while reading the other's, then imports what it received.
+pub async fn exchange<S: AsyncRead + AsyncWrite>(engine: &mut SyncEngine, stream: S) -> Result<SyncReport, SyncError> {
+    let mut outgoing: String = serde_json::to_string(&engine.export().await?).map_err(|e| SyncError::UnableToExchange(e.to_string()))?;
+    outgoing.push('\n');
+
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut reader = BufReader::new(reader);
+    let mut incoming: String = String::new();
+
+    let (sent, received) = tokio::join!(
+        async {
+            writer.write_all(outgoing.as_bytes()).await?;
+            writer.flush().await
+        },
+        reader.read_line(&mut incoming)
+    );
+    sent.map_err(|e| SyncError::UnableToExchange(e.to_string()))?;
+    if received.map_err(|e| SyncError::UnableToExchange(e.to_string()))? == 0 {
+        return Err(SyncError::UnableToExchange("peer closed the connection".to_owned()));
+    }
+
+    let change_set: ChangeSet = serde_json::from_str(&incoming).map_err(|e| SyncError::UnableToExchange(e.to_string()))?;
+    engine.import(&change_set).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::sync::RwLock;
+
+    use expense_tracking::domain::{
+        entities::{Brand, Store},
+        repositories::{BrandRepository, StoreRepository},
+    };
+    use in_memory_storage::{
+        adapters::repositories::{
+            BrandRepositoryInMemoryImpl, CategoryRepositoryInMemoryImpl, ProductRepositoryInMemoryImpl, StoreRepositoryInMemoryImpl,
+            TransactionRepositoryInMemoryImpl,
+        },
+        infrastructures::data_sources::InMemoryCache,
+    };
+
+    use super::{exchange, read_change_set, write_change_set};
+    use crate::infrastructures::sync::{ChangeSet, SyncEngine, SyncJournal, SyncReport, SyncRepositories};
+
+    fn given_engine(name: &str, cache: &Arc<RwLock<InMemoryCache>>) -> SyncEngine {
+        let repositories: SyncRepositories = SyncRepositories {
+            brands: Box::new(BrandRepositoryInMemoryImpl::new(Arc::clone(cache))),
+            categories: Box::new(CategoryRepositoryInMemoryImpl::new(Arc::clone(cache))),
+            stores: Box::new(StoreRepositoryInMemoryImpl::new(Arc::clone(cache))),
+            products: Box::new(ProductRepositoryInMemoryImpl::new(Arc::clone(cache))),
+            transactions: Box::new(TransactionRepositoryInMemoryImpl::new(Arc::clone(cache))),
+        };
+
+        SyncEngine::new(repositories, SyncJournal::new(name))
+    }
+
+    #[tokio::test]
+    async fn write_then_read_should_restore_the_change_set() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("laptop.sync.json");
+        let cache: Arc<RwLock<InMemoryCache>> = Arc::new(RwLock::new(InMemoryCache::new()));
+        StoreRepositoryInMemoryImpl::new(Arc::clone(&cache))
+            .create_or_update(&Store::new(None, "Migros".to_owned()))
+            .await
+            .unwrap();
+        let change_set: ChangeSet = given_engine("laptop", &cache).export().await.unwrap();
+
+        write_change_set(&path, &change_set).unwrap();
+
+        let result: ChangeSet = read_change_set(&path).unwrap();
+
+        assert_eq!(result, change_set, "Expected {:?}, but got {:?}", change_set, result);
+    }
+
+    #[tokio::test]
+    async fn exchange_should_converge_both_ends_of_a_stream() {
+        let laptop_cache: Arc<RwLock<InMemoryCache>> = Arc::new(RwLock::new(InMemoryCache::new()));
+        let phone_cache: Arc<RwLock<InMemoryCache>> = Arc::new(RwLock::new(InMemoryCache::new()));
+        BrandRepositoryInMemoryImpl::new(Arc::clone(&laptop_cache))
+            .create(&Brand::new("Nestlé".to_owned()))
+            .await
+            .unwrap();
+        BrandRepositoryInMemoryImpl::new(Arc::clone(&phone_cache))
+            .create(&Brand::new("Danone".to_owned()))
+            .await
+            .unwrap();
+        let mut laptop: SyncEngine = given_engine("laptop", &laptop_cache);
+        let mut phone: SyncEngine = given_engine("phone", &phone_cache);
+        // A buffer smaller than a change set, so neither end can finish sending before the other reads.
+        let (laptop_end, phone_end) = tokio::io::duplex(64);
+
+        let (laptop_report, phone_report) = tokio::join!(exchange(&mut laptop, laptop_end), exchange(&mut phone, phone_end));
+
+        let result: Vec<Brand> = BrandRepositoryInMemoryImpl::new(laptop_cache).retrieve_all().await.unwrap();
+        let expected: Vec<Brand> = vec![Brand::new("Danone".to_owned()), Brand::new("Nestlé".to_owned())];
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(BrandRepositoryInMemoryImpl::new(phone_cache).retrieve_all().await, Ok(expected));
+        assert!(laptop_report.is_ok_and(|report: SyncReport| report.applied.len() == 1));
+        assert!(phone_report.is_ok_and(|report: SyncReport| report.applied.len() == 1));
+    }
+}
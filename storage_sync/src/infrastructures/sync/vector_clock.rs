@@ -0,0 +1,109 @@
+use std::{cmp::Ordering, collections::BTreeMap};
+
+use serde::{Deserialize, Serialize};
+
+/// Counts, per replica, the changes an entity went through, so that two replicas can tell whether one of them has
+/// seen everything the other did or whether both changed the entity without knowing about each other.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct VectorClock {
+    counters: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockOrdering {
+    Before,
+    Equal,
+    After,
+    Concurrent,
+}
+
+impl VectorClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, replica: &str) -> u64 {
+        self.counters.get(replica).copied().unwrap_or_default()
+    }
+
+    pub fn increment(&mut self, replica: &str) {
+        *self.counters.entry(replica.to_owned()).or_default() += 1;
+    }
+
+    /// The smallest clock having seen everything both `self` and `other` have seen.
+    pub fn merged(&self, other: &VectorClock) -> VectorClock {
+        let mut counters: BTreeMap<String, u64> = self.counters.clone();
+
+        for (replica, counter) in &other.counters {
+            let current: &mut u64 = counters.entry(replica.clone()).or_default();
+            *current = (*current).max(*counter);
+        }
+
+        VectorClock { counters }
+    }
+
+    /// How `self` relates to `other`: `Before` when `other` has seen every change `self` has and more.
+    pub fn compare(&self, other: &VectorClock) -> ClockOrdering {
+        let replicas = self.counters.keys().chain(other.counters.keys());
+
+        replicas.fold(ClockOrdering::Equal, |ordering, replica| {
+            match (ordering, self.get(replica).cmp(&other.get(replica))) {
+                (ordering, Ordering::Equal) => ordering,
+                (ClockOrdering::Equal | ClockOrdering::Before, Ordering::Less) => ClockOrdering::Before,
+                (ClockOrdering::Equal | ClockOrdering::After, Ordering::Greater) => ClockOrdering::After,
+                _ => ClockOrdering::Concurrent,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ClockOrdering, VectorClock};
+
+    fn given_clock(counters: &[(&str, u64)]) -> VectorClock {
+        let mut clock: VectorClock = VectorClock::new();
+
+        for (replica, counter) in counters {
+            (0..*counter).for_each(|_| clock.increment(replica));
+        }
+        clock
+    }
+
+    macro_rules! compare {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (left, right, expected): (&[(&str, u64)], &[(&str, u64)], ClockOrdering) = $value;
+
+                let result: ClockOrdering = given_clock(left).compare(&given_clock(right));
+
+                assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+            }
+        )*
+        }
+    }
+
+    compare! {
+        compare_should_find_empty_clocks_equal: (&[], &[], ClockOrdering::Equal),
+        compare_should_ignore_missing_replicas_at_zero: (&[("laptop", 2), ("phone", 0)], &[("laptop", 2)], ClockOrdering::Equal),
+        compare_should_find_a_clock_behind: (&[("laptop", 1)], &[("laptop", 2), ("phone", 1)], ClockOrdering::Before),
+        compare_should_find_a_clock_ahead: (&[("laptop", 3), ("phone", 1)], &[("phone", 1)], ClockOrdering::After),
+        compare_should_find_concurrent_clocks: (&[("laptop", 2), ("phone", 1)], &[("laptop", 1), ("phone", 2)], ClockOrdering::Concurrent),
+    }
+
+    #[test]
+    fn merged_should_keep_the_highest_counter_of_each_replica() {
+        let left: VectorClock = given_clock(&[("laptop", 2), ("phone", 1)]);
+        let right: VectorClock = given_clock(&[("laptop", 1), ("phone", 3), ("tablet", 1)]);
+
+        let result: VectorClock = left.merged(&right);
+        let expected: VectorClock = given_clock(&[("laptop", 2), ("phone", 3), ("tablet", 1)]);
+
+        assert_eq!(result, expected, "Expected {:?}, but got {:?}", expected, result);
+        assert_eq!(left.compare(&result), ClockOrdering::Before);
+        assert_eq!(right.compare(&result), ClockOrdering::Before);
+    }
+}
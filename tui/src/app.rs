@@ -1,6 +1,7 @@
 use std::error;
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use expense_tracking::domain::search::{SearchHit, SearchService};
 
 /// Application result type.
 pub type AppResult<T> = std::result::Result<T, Box<dyn error::Error>>;
@@ -12,6 +13,14 @@ pub struct App {
     pub running: bool,
     /// counter
     pub counter: u8,
+    /// Is the search input focused?
+    pub searching: bool,
+    /// Current search query
+    pub query: String,
+    /// Results for the current search query
+    pub results: Vec<SearchHit>,
+    /// Service used to look up entities by name
+    search_service: SearchService,
 }
 
 impl Default for App {
@@ -19,6 +28,10 @@ impl Default for App {
         Self {
             running: true,
             counter: 0,
+            searching: false,
+            query: String::new(),
+            results: Vec::new(),
+            search_service: SearchService::default(),
         }
     }
 }
@@ -29,6 +42,14 @@ impl App {
         Self::default()
     }
 
+    /// Constructs a new instance of [`App`] searching through the given service.
+    pub fn with_search_service(search_service: SearchService) -> Self {
+        Self {
+            search_service,
+            ..Self::default()
+        }
+    }
+
     /// Handles the tick event of the terminal.
     pub fn tick(&self) {}
 
@@ -48,8 +69,37 @@ impl App {
             self.counter = res;
         }
     }
+
+    fn refresh_results(&mut self) {
+        self.results = self.search_service.search(&self.query, 20);
+    }
+
+    /// Handles the key events while the search input is focused.
+    fn handle_search_key_events(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            // Leave the search input on `ESC` or `Enter`
+            KeyCode::Esc | KeyCode::Enter => {
+                self.searching = false;
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.refresh_results();
+            }
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                self.refresh_results();
+            }
+            _ => {}
+        }
+    }
+
     /// Handles the key events and updates the state of [`App`].
     pub fn handle_key_events(&mut self, key_event: KeyEvent) -> AppResult<()> {
+        if self.searching {
+            self.handle_search_key_events(key_event);
+            return Ok(());
+        }
+
         match key_event.code {
             // Exit application on `ESC` or `q`
             KeyCode::Esc | KeyCode::Char('q') => {
@@ -68,6 +118,10 @@ impl App {
             KeyCode::Left => {
                 self.decrement_counter();
             }
+            // Focus the search input on `/`
+            KeyCode::Char('/') => {
+                self.searching = true;
+            }
             // Other handlers you could add here.
             _ => {}
         }
@@ -1,4 +1,4 @@
-use std::io;
+use std::{env, io, path::PathBuf};
 
 use ratatui::{Terminal, backend::CrosstermBackend};
 
@@ -10,13 +10,17 @@ use crate::{
 
 mod app;
 mod event;
+mod storage;
 mod tui;
 mod ui;
 
 #[tokio::main]
 async fn main() -> AppResult<()> {
-    // Create an application.
-    let mut app: App = App::new();
+    // Create an application, searching through the snapshot given as first argument if any.
+    let mut app: App = match env::args_os().nth(1).map(PathBuf::from) {
+        Some(path) => App::with_search_service(storage::search_service_for(storage::load_snapshot(&path)?).await),
+        None => App::new(),
+    };
 
     // Initialize the terminal user interface.
     let backend: CrosstermBackend<io::Stdout> = CrosstermBackend::new(io::stdout());
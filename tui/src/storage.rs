@@ -0,0 +1,57 @@
+use std::{path::Path, sync::Arc};
+
+use expense_tracking::domain::search::SearchService;
+use in_memory_storage::{
+    adapters::repositories::{
+        BrandRepositoryInMemoryImpl, CategoryRepositoryInMemoryImpl, ProductRepositoryInMemoryImpl, StoreRepositoryInMemoryImpl,
+    },
+    infrastructures::data_sources::{InMemoryCache, InMemorySnapshot, SnapshotError},
+};
+use storage_encryption::infrastructures::encryption::{Cipher, passphrase_from_environment};
+use tokio::sync::RwLock;
+
+use crate::app::AppResult;
+
+const PASSPHRASE_ATTEMPTS: usize = 3;
+
+/// Loads the snapshot at `path`, asking for its passphrase when it is encrypted, before the terminal switches to
+/// the alternate screen.
+pub fn load_snapshot(path: &Path) -> AppResult<InMemoryCache> {
+    let snapshot: InMemorySnapshot = InMemorySnapshot::new(path);
+
+    if !snapshot.is_encrypted().map_err(|e| format!("Unable to load snapshot: {:?}", e))? {
+        return Ok(snapshot.load().map_err(|e| format!("Unable to load snapshot: {:?}", e))?);
+    }
+
+    if let Some(cipher) = passphrase_from_environment() {
+        return Ok(snapshot
+            .with_cipher(cipher)
+            .load()
+            .map_err(|e| format!("Unable to load snapshot: {:?}", e))?);
+    }
+
+    for _ in 0..PASSPHRASE_ATTEMPTS {
+        let passphrase: String = rpassword::prompt_password("Passphrase: ")?;
+
+        match snapshot.clone().with_cipher(Cipher::new(passphrase)).load() {
+            Ok(cache) => return Ok(cache),
+            Err(SnapshotError::WrongPassphrase) => eprintln!("Wrong passphrase, try again"),
+            Err(e) => return Err(format!("Unable to load snapshot: {:?}", e).into()),
+        }
+    }
+
+    Err("Unable to load snapshot: wrong passphrase".into())
+}
+
+/// Builds a search service over every named entity of the cache.
+pub async fn search_service_for(cache: InMemoryCache) -> SearchService {
+    let cache: Arc<RwLock<InMemoryCache>> = Arc::new(RwLock::new(cache));
+
+    SearchService::build_from(
+        &BrandRepositoryInMemoryImpl::new(Arc::clone(&cache)),
+        &CategoryRepositoryInMemoryImpl::new(Arc::clone(&cache)),
+        &StoreRepositoryInMemoryImpl::new(Arc::clone(&cache)),
+        &ProductRepositoryInMemoryImpl::new(cache),
+    )
+    .await
+}
@@ -1,7 +1,7 @@
 use ratatui::{
-    layout::Alignment,
+    layout::{Alignment, Constraint, Layout, Rect},
     style::{Color, Style},
-    widgets::{Block, BorderType, Paragraph},
+    widgets::{Block, BorderType, List, ListItem, Paragraph},
     Frame,
 };
 
@@ -13,11 +13,15 @@ pub fn render(app: &mut App, frame: &mut Frame) {
     // See the following resources:
     // - https://docs.rs/ratatui/latest/ratatui/widgets/index.html
     // - https://github.com/ratatui/ratatui/tree/master/examples
+    let [main_area, search_area, results_area]: [Rect; 3] =
+        Layout::vertical([Constraint::Length(6), Constraint::Length(3), Constraint::Min(0)]).areas(frame.area());
+
     frame.render_widget(
         Paragraph::new(format!(
             "This is a tui template.\n\
                 Press `Esc`, `Ctrl-C` or `q` to stop running.\n\
                 Press left and right to increment and decrement the counter respectively.\n\
+                Press `/` to search and `Esc` to leave the search.\n\
                 Counter: {}",
             app.counter
         ))
@@ -29,6 +33,29 @@ pub fn render(app: &mut App, frame: &mut Frame) {
         )
         .style(Style::default().fg(Color::Cyan).bg(Color::Black))
         .centered(),
-        frame.area(),
-    )
+        main_area,
+    );
+
+    let search_border: Color = if app.searching { Color::Yellow } else { Color::Cyan };
+    frame.render_widget(
+        Paragraph::new(app.query.as_str()).block(
+            Block::bordered()
+                .title("Search")
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(search_border)),
+        ),
+        search_area,
+    );
+
+    let results: Vec<ListItem> = app
+        .results
+        .iter()
+        .map(|hit| ListItem::new(format!("{:<10} {}", format!("{:?}", hit.kind), hit.title)))
+        .collect();
+    frame.render_widget(
+        List::new(results)
+            .block(Block::bordered().title("Results").border_type(BorderType::Rounded))
+            .style(Style::default().fg(Color::Cyan).bg(Color::Black)),
+        results_area,
+    );
 }